@@ -0,0 +1,32 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use hearts_app::bot::BotDifficulty;
+use hearts_app::controller::GameController;
+use hearts_core::game::match_state::MatchState;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rules::RuleSet;
+
+fn pass_controller(seed: u64, pass_count: usize) -> GameController {
+    let rules = RuleSet::default().with_pass_count(pass_count).unwrap();
+    let mut controller = GameController::new_from_match_state(MatchState::with_rules(
+        PlayerPosition::North,
+        seed,
+        rules,
+    ));
+    controller.set_bot_difficulty(BotDifficulty::NormalHeuristic);
+    controller
+}
+
+fn pass_decision_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pass_decision");
+    // South hands that timed slowest over seeds 0..3000, and a typical one.
+    for (seed, pass_count) in [(41u64, 3usize), (1040, 3), (2767, 4)] {
+        let controller = pass_controller(seed, pass_count);
+        group.bench_function(format!("normal_pass{}_{}", pass_count, seed), |b| {
+            b.iter(|| black_box(controller.simple_pass_for(PlayerPosition::South)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pass_decision_bench);
+criterion_main!(benches);
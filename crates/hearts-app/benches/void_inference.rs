@@ -0,0 +1,78 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use hearts_app::bot::{BotDifficulty, UnseenTracker};
+use hearts_app::controller::GameController;
+use hearts_core::model::card::Card;
+use hearts_core::model::card_set::CardSet;
+use hearts_core::model::hand::Hand;
+use hearts_core::model::per_suit::PerSuit;
+use hearts_core::model::player::PlayerPosition;
+
+/// North's tracker and hand six tricks into seed 7.
+fn mid_hand() -> (UnseenTracker, Hand) {
+    let seat = PlayerPosition::North;
+    let mut controller = GameController::new_with_seed(Some(7), seat);
+    controller.set_bot_difficulty(BotDifficulty::NormalHeuristic);
+    if controller.in_passing_phase() {
+        let _ = controller.submit_auto_passes_for_others(seat);
+        if let Some(cards) = controller.simple_pass_for(seat) {
+            let _ = controller.submit_pass(seat, cards);
+        }
+        let _ = controller.resolve_passes();
+    }
+    while controller.round().tricks_completed() < 6 {
+        let next = controller.expected_to_play();
+        controller.autoplay_one(next.next()).expect("play goes on");
+    }
+    let mut tracker = UnseenTracker::new();
+    tracker.rebuild_for_round(controller.round());
+    (tracker, controller.round().hand(seat).clone())
+}
+
+/// The same inference over card lists, as the tracker did before it kept a
+/// `CardSet`.
+fn infer_voids_by_scan(
+    tracker: &UnseenTracker,
+    unseen: &[Card],
+    observer: PlayerPosition,
+    hand: &Hand,
+) -> [PerSuit<bool>; 4] {
+    PlayerPosition::LOOP.map(|seat| {
+        PerSuit::from_fn(|suit| {
+            tracker.is_void(seat, suit)
+                || (seat != observer
+                    && !unseen
+                        .iter()
+                        .any(|card| card.suit == suit && !hand.contains(*card)))
+        })
+    })
+}
+
+fn void_inference_bench(c: &mut Criterion) {
+    let (tracker, hand) = mid_hand();
+    let unseen: Vec<Card> = CardSet::DECK
+        .iter()
+        .filter(|&card| tracker.is_unseen(card))
+        .collect();
+    assert_eq!(
+        tracker.infer_voids(PlayerPosition::North, &hand),
+        infer_voids_by_scan(&tracker, &unseen, PlayerPosition::North, &hand)
+    );
+    let mut group = c.benchmark_group("infer_voids");
+    group.bench_function("card_set", |b| {
+        b.iter(|| black_box(tracker.infer_voids(PlayerPosition::North, black_box(&hand))))
+    });
+    group.bench_function("card_scan", |b| {
+        b.iter(|| {
+            black_box(infer_voids_by_scan(
+                &tracker,
+                black_box(&unseen),
+                PlayerPosition::North,
+                black_box(&hand),
+            ))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, void_inference_bench);
+criterion_main!(benches);
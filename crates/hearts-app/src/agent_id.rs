@@ -0,0 +1,216 @@
+//! Agent names as bench outputs key them.
+//!
+//! Per-agent stats, ratings, paired comparisons and summaries are all joined
+//! on the agent's name, so two spellings of one agent split its results and
+//! two agents sharing a name pool theirs. An [`AgentId`] is a name that has
+//! been checked: not empty, no surrounding whitespace, and only ASCII
+//! letters, digits, `-`, `_` and `.`, since names end up in file names;
+//! for the same reason a name cannot be all dots, like `.` or `..`.
+//! Names in one run or one ratings file must also differ ignoring case.
+
+use crate::bot::BotDifficulty;
+use crate::outcome::agent_label;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Longest name, in characters.
+pub const MAX_AGENT_ID_CHARS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AgentId(String);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentIdError {
+    Empty,
+    TooLong {
+        id: String,
+    },
+    Whitespace {
+        id: String,
+    },
+    Character {
+        id: String,
+        ch: char,
+    },
+    /// Nothing but dots, which a path reads as a directory.
+    Dots {
+        id: String,
+    },
+    /// Two agents share a name, ignoring case.
+    Duplicate {
+        first: String,
+        second: String,
+    },
+}
+
+impl fmt::Display for AgentIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgentIdError::Empty => write!(f, "agent names cannot be empty"),
+            AgentIdError::TooLong { id } => write!(
+                f,
+                "agent name {id:?} is longer than {MAX_AGENT_ID_CHARS} characters"
+            ),
+            AgentIdError::Whitespace { id } => {
+                write!(f, "agent name {id:?} has leading or trailing whitespace")
+            }
+            AgentIdError::Character { id, ch } => write!(
+                f,
+                "agent name {id:?} contains {ch:?}; use letters, digits, '-', '_' or '.'"
+            ),
+            AgentIdError::Dots { id } => {
+                write!(f, "agent name {id:?} is only dots; add a letter or digit")
+            }
+            AgentIdError::Duplicate { first, second } => {
+                write!(
+                    f,
+                    "agent names {first:?} and {second:?} differ only in case"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AgentIdError {}
+
+impl AgentId {
+    pub fn new(raw: &str) -> Result<Self, AgentIdError> {
+        if raw.is_empty() {
+            return Err(AgentIdError::Empty);
+        }
+        if raw.trim() != raw {
+            return Err(AgentIdError::Whitespace {
+                id: raw.to_string(),
+            });
+        }
+        if raw.chars().count() > MAX_AGENT_ID_CHARS {
+            return Err(AgentIdError::TooLong {
+                id: raw.to_string(),
+            });
+        }
+        if let Some(ch) = raw
+            .chars()
+            .find(|ch| !(ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.')))
+        {
+            return Err(AgentIdError::Character {
+                id: raw.to_string(),
+                ch,
+            });
+        }
+        if raw.chars().all(|ch| ch == '.') {
+            return Err(AgentIdError::Dots {
+                id: raw.to_string(),
+            });
+        }
+        Ok(Self(raw.to_string()))
+    }
+
+    /// The built-in agent for `difficulty`, named by its label.
+    pub fn of(difficulty: BotDifficulty) -> Self {
+        Self(agent_label(difficulty).to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether `self` and `other` key the same agent, which ignores case.
+    pub fn same_agent(&self, other: &AgentId) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl fmt::Display for AgentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<&str> for AgentId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl TryFrom<String> for AgentId {
+    type Error = AgentIdError;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        AgentId::new(&raw)
+    }
+}
+
+impl From<AgentId> for String {
+    fn from(id: AgentId) -> Self {
+        id.0
+    }
+}
+
+/// Checks that `ids` name distinct agents, ignoring case. Repeats of the
+/// exact same name are fine: one agent can hold several seats.
+pub fn check_distinct<'a>(ids: impl IntoIterator<Item = &'a AgentId>) -> Result<(), AgentIdError> {
+    let mut seen: Vec<&AgentId> = Vec::new();
+    for id in ids {
+        if let Some(first) = seen.iter().find(|seen| seen.same_agent(id)) {
+            if first.0 != id.0 {
+                return Err(AgentIdError::Duplicate {
+                    first: first.0.clone(),
+                    second: id.0.clone(),
+                });
+            }
+            continue;
+        }
+        seen.push(id);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AgentId, AgentIdError, check_distinct};
+    use crate::bot::BotDifficulty;
+
+    #[test]
+    fn names_are_checked_for_what_breaks_a_join_or_a_file_name() {
+        assert_eq!(AgentId::new("baseline-2").unwrap().as_str(), "baseline-2");
+        assert_eq!(AgentId::new("hard_v1.3").unwrap().to_string(), "hard_v1.3");
+        assert_eq!(AgentId::new(""), Err(AgentIdError::Empty));
+        assert!(matches!(
+            AgentId::new(" hard"),
+            Err(AgentIdError::Whitespace { .. })
+        ));
+        assert!(matches!(
+            AgentId::new("hard/v2"),
+            Err(AgentIdError::Character { ch: '/', .. })
+        ));
+        for dots in [".", "..", "..."] {
+            assert!(
+                matches!(AgentId::new(dots), Err(AgentIdError::Dots { .. })),
+                "{dots}"
+            );
+        }
+        assert_eq!(AgentId::new(".hard").unwrap().as_str(), ".hard");
+        assert!(matches!(
+            AgentId::new(&"x".repeat(65)),
+            Err(AgentIdError::TooLong { .. })
+        ));
+        // Deserializing checks the name the same way.
+        assert!(serde_json::from_str::<AgentId>("\"hard \"").is_err());
+        let id: AgentId = serde_json::from_str("\"rollout\"").unwrap();
+        assert_eq!(id, AgentId::of(BotDifficulty::Rollout));
+    }
+
+    #[test]
+    fn names_differing_only_in_case_are_refused() {
+        let ids = ["hard", "normal", "hard", "Hard"].map(|raw| AgentId::new(raw).unwrap());
+        assert!(check_distinct(&ids[..3]).is_ok());
+        assert_eq!(
+            check_distinct(&ids),
+            Err(AgentIdError::Duplicate {
+                first: "hard".to_string(),
+                second: "Hard".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,225 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! Card-counting assists for the human seat, worked out from what that seat
+//! has seen: its own hand and every card played so far.
+//!
+//! At [`AssistLevel::FullCounting`], each suit the human holds gets a master
+//! claim: whether their highest card in the suit is the highest still out
+//! (nothing unseen can beat it), or how many unseen cards sit above it.
+
+use crate::controller::SuitTally;
+use hearts_core::model::card::Card;
+use hearts_core::model::rank::Rank;
+use hearts_core::model::suit::Suit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AssistLevel {
+    #[default]
+    Off,
+    FullCounting,
+}
+
+impl AssistLevel {
+    pub const fn as_u32(self) -> u32 {
+        match self {
+            AssistLevel::Off => 0,
+            AssistLevel::FullCounting => 1,
+        }
+    }
+
+    pub const fn from_u32(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(AssistLevel::Off),
+            1 => Some(AssistLevel::FullCounting),
+            _ => None,
+        }
+    }
+
+    pub const fn toggled(self) -> Self {
+        match self {
+            AssistLevel::Off => AssistLevel::FullCounting,
+            AssistLevel::FullCounting => AssistLevel::Off,
+        }
+    }
+
+    pub const fn shows_mastery(self) -> bool {
+        matches!(self, AssistLevel::FullCounting)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mastery {
+    /// Nothing unseen beats the seat's highest card in the suit.
+    Master,
+    /// At least one unseen card is higher.
+    Beatable,
+    /// The seat holds no card in the suit.
+    Void,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MasterySummary {
+    pub suit: Suit,
+    pub state: Mastery,
+    /// The seat's highest card in the suit.
+    pub top: Option<Card>,
+    /// Unseen cards above `top`, low to high.
+    pub higher_unseen: Vec<Card>,
+}
+
+impl MasterySummary {
+    pub fn from_tally(tally: &SuitTally) -> Self {
+        let top = tally.held.iter().copied().max_by_key(|card| card.rank);
+        let Some(top) = top else {
+            return Self {
+                suit: tally.suit,
+                state: Mastery::Void,
+                top: None,
+                higher_unseen: Vec::new(),
+            };
+        };
+        let mut higher_unseen: Vec<Card> = tally
+            .outstanding
+            .iter()
+            .copied()
+            .filter(|card| card.rank > top.rank)
+            .collect();
+        higher_unseen.sort_by_key(|card| card.rank);
+        let state = if higher_unseen.is_empty() {
+            Mastery::Master
+        } else {
+            Mastery::Beatable
+        };
+        Self {
+            suit: tally.suit,
+            state,
+            top: Some(top),
+            higher_unseen,
+        }
+    }
+
+    /// One line for the assist panel, e.g. "You hold the highest spade still
+    /// out (K)" or "2 spades above your 9 are still out".
+    pub fn claim(&self) -> String {
+        let suit = suit_noun(self.suit);
+        match (self.state, self.top) {
+            (Mastery::Master, Some(top)) => {
+                format!(
+                    "You hold the highest {suit} still out ({})",
+                    rank_label(top.rank)
+                )
+            }
+            (Mastery::Beatable, Some(top)) => {
+                let count = self.higher_unseen.len();
+                let plural = if count == 1 {
+                    format!("1 {suit} above your {} is", rank_label(top.rank))
+                } else {
+                    format!("{count} {suit}s above your {} are", rank_label(top.rank))
+                };
+                format!("{plural} still out")
+            }
+            _ => format!("You are void in {suit}s"),
+        }
+    }
+}
+
+fn suit_noun(suit: Suit) -> &'static str {
+    match suit {
+        Suit::Clubs => "club",
+        Suit::Diamonds => "diamond",
+        Suit::Spades => "spade",
+        Suit::Hearts => "heart",
+    }
+}
+
+fn rank_label(rank: Rank) -> String {
+    match rank {
+        Rank::Jack => "J".to_string(),
+        Rank::Queen => "Q".to_string(),
+        Rank::King => "K".to_string(),
+        Rank::Ace => "A".to_string(),
+        other => other.value().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::GameController;
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::player::PlayerPosition::{East, North, South, West};
+    use hearts_core::testkit::{RoundBuilder, card, cards, to_play};
+
+    fn controller_after(plays: &str) -> GameController {
+        let mut round = RoundBuilder::new()
+            .seat_hand(North, "2C 9S 3H")
+            .seat_hand(East, "3C KS 4H")
+            .seat_hand(South, "4C QS AH")
+            .seat_hand(West, "5C AS 5D")
+            .build();
+        for played in cards(plays) {
+            let seat = to_play(&round);
+            round.play_card(seat, played).unwrap();
+        }
+        let mut controller = GameController::new_with_seed(Some(1), North);
+        controller.set_round_and_scores_for_test(round, [0; 4]);
+        controller
+    }
+
+    fn mastery(controller: &GameController, seat: PlayerPosition, suit: Suit) -> MasterySummary {
+        controller.suit_mastery(seat)[suit.index()].clone()
+    }
+
+    #[test]
+    fn mastery_follows_the_cards_still_out() {
+        let controller = controller_after("");
+        let spades = mastery(&controller, South, Suit::Spades);
+        assert_eq!(spades.state, Mastery::Beatable);
+        assert_eq!(spades.top, Some(card("QS")));
+        assert_eq!(spades.higher_unseen, [card("KS"), card("AS")]);
+        assert_eq!(spades.claim(), "2 spades above your Q are still out");
+        let hearts = mastery(&controller, South, Suit::Hearts);
+        assert_eq!(hearts.state, Mastery::Master);
+        assert_eq!(hearts.claim(), "You hold the highest heart still out (A)");
+        let diamonds = mastery(&controller, South, Suit::Diamonds);
+        assert_eq!((diamonds.state, diamonds.top), (Mastery::Void, None));
+        assert_eq!(diamonds.claim(), "You are void in diamonds");
+    }
+
+    #[test]
+    fn cards_played_mid_round_promote_the_queen() {
+        // After the clubs, West leads A♠ and East drops K♠ under it: South's
+        // Q♠ is now the highest spade out, even mid-trick.
+        let controller = controller_after("2C 3C 4C 5C AS 9S KS");
+        let spades = mastery(&controller, South, Suit::Spades);
+        assert_eq!(spades.state, Mastery::Master);
+        assert_eq!(spades.claim(), "You hold the highest spade still out (Q)");
+        // Cards the seat has not seen count as out, dealt or not.
+        let north = mastery(&controller, North, Suit::Hearts);
+        assert_eq!(north.higher_unseen.len(), 11);
+        assert_eq!(north.claim(), "11 hearts above your 3 are still out");
+    }
+
+    #[test]
+    fn a_single_higher_card_reads_in_the_singular() {
+        let tally = SuitTally {
+            suit: Suit::Hearts,
+            played: cards("AH"),
+            held: cards("2H QH"),
+            outstanding: cards("3H 4H 5H 6H 7H 8H 9H 10H JH KH"),
+        };
+        let hearts = MasterySummary::from_tally(&tally);
+        assert_eq!(hearts.higher_unseen, [card("KH")]);
+        assert_eq!(hearts.claim(), "1 heart above your Q is still out");
+    }
+
+    #[test]
+    fn only_full_counting_shows_mastery() {
+        assert!(!AssistLevel::Off.shows_mastery());
+        assert!(AssistLevel::FullCounting.shows_mastery());
+        for level in [AssistLevel::Off, AssistLevel::FullCounting] {
+            assert_eq!(AssistLevel::from_u32(level.as_u32()), Some(level));
+            assert_eq!(level.toggled().toggled(), level);
+        }
+        assert_eq!(AssistLevel::from_u32(9), None);
+    }
+}
@@ -0,0 +1,226 @@
+//! Symmetry augmentation for the play dataset.
+//!
+//! Clubs and diamonds carry no points, so a position and its copy with the
+//! two suits swapped play out alike: the swapped sample is as valid as the
+//! one the bots produced. Augmentation emits both, with the candidate labels
+//! (the action space) moved through the same 52-card permutation. The one
+//! rule that tells the suits apart is the forced 2♣ opening lead, so that
+//! decision is not augmented; following it is, since the first-trick point
+//! rule treats both suits the same.
+//!
+//! Play samples carry no per-opponent fields, so relabelling the opponents'
+//! seats would reproduce the canonical row; only the suit swap is emitted.
+//! Variants keep the canonical deal fingerprint, so splits never separate a
+//! sample from its copies.
+
+use crate::dataset::PlaySampleRecord;
+use hearts_core::model::card::Card;
+use hearts_core::model::rank::Rank;
+use hearts_core::model::suit::Suit;
+
+/// Row tag for samples produced by swapping clubs and diamonds.
+pub const SWAP_MINORS: &str = "swap_minors";
+
+/// Index of `card` in suit-major order (clubs 2..A, diamonds, spades, hearts).
+pub fn card_index(card: Card) -> usize {
+    card.suit.index() * 13 + card.rank.index()
+}
+
+fn card_at(index: usize) -> Card {
+    let suit = Suit::from_index(index / 13).expect("index below 52");
+    Card::new(Rank::ORDERED[index % 13], suit)
+}
+
+/// A bijection on the 52 card indices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardPermutation([usize; 52]);
+
+impl CardPermutation {
+    pub fn swap_minors() -> Self {
+        Self(std::array::from_fn(|index| {
+            let card = card_at(index);
+            let suit = match card.suit {
+                Suit::Clubs => Suit::Diamonds,
+                Suit::Diamonds => Suit::Clubs,
+                other => other,
+            };
+            card_index(Card::new(card.rank, suit))
+        }))
+    }
+
+    pub fn apply(&self, card: Card) -> Card {
+        card_at(self.0[card_index(card)])
+    }
+
+    /// Card label as written in the dataset (`10C`, `QS`) after permutation.
+    fn apply_label(&self, label: &str) -> Option<String> {
+        let card = (0..52)
+            .map(card_at)
+            .find(|card| card.to_string() == label)?;
+        Some(self.apply(card).to_string())
+    }
+
+    fn apply_suit(&self, suit: Suit) -> Suit {
+        self.apply(Card::new(Rank::Two, suit)).suit
+    }
+}
+
+/// `sample` with every card and suit moved through `perm`; `None` when a
+/// label does not parse.
+pub fn permute_sample(
+    sample: &PlaySampleRecord,
+    perm: &CardPermutation,
+    tag: &str,
+) -> Option<PlaySampleRecord> {
+    let mut variant = sample.clone();
+    for candidate in variant.candidates.iter_mut() {
+        candidate.card = perm.apply_label(&candidate.card)?;
+    }
+    if let Some(lead) = variant.lead_suit.as_mut() {
+        let suit = Suit::ALL.into_iter().find(|s| s.to_string() == *lead)?;
+        *lead = perm.apply_suit(suit).to_string();
+    }
+    variant.symmetry = Some(tag.to_string());
+    Some(variant)
+}
+
+/// The canonical sample followed by its symmetric variants.
+pub fn augment(sample: PlaySampleRecord) -> Vec<PlaySampleRecord> {
+    let variant = if sample.trick_index == 0 && sample.lead_suit.is_none() {
+        None
+    } else {
+        permute_sample(&sample, &CardPermutation::swap_minors(), SWAP_MINORS)
+    };
+    std::iter::once(sample).chain(variant).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardPermutation, SWAP_MINORS, augment, card_at, card_index, permute_sample};
+    use crate::dataset::{PlayCandidateRecord, PlaySampleRecord};
+    use hearts_core::model::card::Card;
+    use hearts_core::model::rank::Rank;
+    use hearts_core::model::suit::Suit;
+
+    fn candidate(card: &str, total: i32) -> PlayCandidateRecord {
+        PlayCandidateRecord {
+            card: card.to_string(),
+            base: total,
+            continuation: None,
+            total,
+            penalty_value: 0,
+            belief_prob: 0.25,
+            void_in_lead: false,
+            is_best: false,
+            cont_breakdown: None,
+            adviser_bias: 0,
+        }
+    }
+
+    fn sample(trick_index: usize) -> PlaySampleRecord {
+        PlaySampleRecord {
+            seed: 7,
+            seat: "South".to_string(),
+            difficulty: "NormalHeuristic".to_string(),
+            trick_index,
+            legal_count: 4,
+            lead_suit: Some("C".to_string()),
+            hearts_broken: false,
+            penalties_on_trick: 0,
+            belief_entropy_self: 1.5,
+            candidates: vec![
+                candidate("10C", 40),
+                candidate("2D", -15),
+                candidate("QS", -900),
+                candidate("AH", 5),
+            ],
+            passing: "left".to_string(),
+            deal: Some("00ff".to_string()),
+            moon: Some(false),
+            split: None,
+            symmetry: None,
+        }
+    }
+
+    /// Observation vector: each legal card's heuristic total at its index.
+    fn observe(sample: &PlaySampleRecord) -> [f32; 52] {
+        let mut obs = [0.0; 52];
+        for cand in &sample.candidates {
+            let card = (0..52).map(card_at).find(|c| c.to_string() == cand.card);
+            obs[card_index(card.unwrap())] = cand.total as f32;
+        }
+        obs
+    }
+
+    /// Fixed network stub: per-card scores with weights tied across the two
+    /// minor suits, so it cannot tell clubs from diamonds.
+    fn network(obs: &[f32; 52]) -> [f32; 52] {
+        std::array::from_fn(|i| {
+            let weight = [0.5, 0.5, 1.5, -2.0][i / 13] + (i % 13) as f32 * 0.01;
+            obs[i] * weight
+        })
+    }
+
+    fn inverse(perm: &CardPermutation) -> CardPermutation {
+        let mut inverse = [0; 52];
+        for (from, &to) in perm.0.iter().enumerate() {
+            inverse[to] = from;
+        }
+        CardPermutation(inverse)
+    }
+
+    fn permute_policy(perm: &CardPermutation, policy: &[f32; 52]) -> [f32; 52] {
+        let mut out = [0.0; 52];
+        for (i, &p) in policy.iter().enumerate() {
+            out[card_index(perm.apply(card_at(i)))] = p;
+        }
+        out
+    }
+
+    #[test]
+    fn permutation_is_an_exact_bijection() {
+        let perm = CardPermutation::swap_minors();
+        let mut seen = [false; 52];
+        for i in 0..52 {
+            let image = card_index(perm.apply(card_at(i)));
+            assert!(!seen[image]);
+            seen[image] = true;
+            assert_eq!(inverse(&perm).apply(perm.apply(card_at(i))), card_at(i));
+        }
+        assert_eq!(
+            perm.apply(Card::new(Rank::Queen, Suit::Spades)).to_string(),
+            "QS"
+        );
+        assert_eq!(
+            perm.apply(Card::new(Rank::Two, Suit::Clubs)).to_string(),
+            "2D"
+        );
+    }
+
+    #[test]
+    fn inverse_permuted_policy_matches_canonical_policy() {
+        let perm = CardPermutation::swap_minors();
+        let canonical = sample(3);
+        let variant = permute_sample(&canonical, &perm, SWAP_MINORS).unwrap();
+        assert_eq!(variant.lead_suit.as_deref(), Some("D"));
+        let cards: Vec<&str> = variant.candidates.iter().map(|c| c.card.as_str()).collect();
+        assert_eq!(cards, ["10D", "2C", "QS", "AH"]);
+
+        let policy = network(&observe(&variant));
+        let restored = permute_policy(&inverse(&perm), &policy);
+        assert_eq!(restored, network(&observe(&canonical)));
+    }
+
+    #[test]
+    fn opening_lead_is_not_augmented() {
+        let mut opening = sample(0);
+        opening.lead_suit = None;
+        assert_eq!(augment(opening).len(), 1);
+        assert_eq!(augment(sample(0)).len(), 2);
+        let rows = augment(sample(2));
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].symmetry, None);
+        assert_eq!(rows[1].symmetry.as_deref(), Some(SWAP_MINORS));
+        assert_eq!(rows[1].deal, rows[0].deal);
+    }
+}
@@ -0,0 +1,354 @@
+//! Dry-run plan for `--match-mixed --plan`.
+//!
+//! The plan resolves a run's flags into what the run would do: the seeds,
+//! the seatings each deal plays, the hands, and the rows and bytes each
+//! output would receive. A few calibration hands are played and timed to
+//! turn the hand count into a wall-clock estimate. Nothing is written, so a
+//! permutation mode left on `full` or a seed range off by a digit shows up
+//! before the sweep starts rather than after.
+
+use crate::bot::BotDifficulty;
+use crate::outcome::agent_label;
+use crate::permutations::{BALANCED_BLOCK, PermutationMode, seat_permutations};
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rules::RuleSet;
+use std::path::PathBuf;
+
+/// Hands played to time a plan.
+pub const CALIBRATION_HANDS: u64 = 3;
+
+/// Mean cost of the calibration hands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    pub hands: u64,
+    pub secs: f64,
+    /// Mean bytes of one CSV row, newline included.
+    pub csv_row_bytes: f64,
+    /// Mean bytes of one uncompressed `--hand-outcomes` line.
+    pub outcome_row_bytes: f64,
+}
+
+impl Calibration {
+    pub fn secs_per_hand(&self) -> f64 {
+        if self.hands == 0 {
+            0.0
+        } else {
+            self.secs / self.hands as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunPlan {
+    pub seat: PlayerPosition,
+    pub seed_start: u64,
+    pub count: u64,
+    pub diffs: [BotDifficulty; 4],
+    pub permutations: Option<PermutationMode>,
+    pub baseline: bool,
+    pub memoize: bool,
+    pub rules: RuleSet,
+    pub out_path: Option<PathBuf>,
+    pub hand_outcomes: Option<PathBuf>,
+    pub summary_out: Option<PathBuf>,
+    pub telemetry_out: Option<PathBuf>,
+    pub calibration: Option<Calibration>,
+    /// Workers playing deals at once; only permutation runs use more than
+    /// one.
+    pub threads: usize,
+}
+
+impl RunPlan {
+    /// Fewest and most seatings one deal plays.
+    pub fn seatings_per_deal(&self) -> (u64, u64) {
+        match self.permutations {
+            None => (1, 1),
+            Some(mode) => {
+                let available = seat_permutations(self.diffs).len() as u64;
+                match mode {
+                    PermutationMode::Full => (available, available),
+                    PermutationMode::Adaptive { .. } => {
+                        (available.min(BALANCED_BLOCK as u64), available)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fewest and most hands the run plays, baseline hands included.
+    pub fn hands(&self) -> (u64, u64) {
+        let (lo, hi) = self.seatings_per_deal();
+        let baseline = if self.baseline { self.count } else { 0 };
+        (self.count * lo + baseline, self.count * hi + baseline)
+    }
+
+    /// Fewest and most data rows in the CSV and the hand-outcome log: one
+    /// per seating played.
+    pub fn rows(&self) -> (u64, u64) {
+        let (lo, hi) = self.seatings_per_deal();
+        (self.count * lo, self.count * hi)
+    }
+
+    /// Workers the run would play on.
+    pub fn workers(&self) -> usize {
+        if self.permutations.is_some() {
+            self.threads.max(1)
+        } else {
+            1
+        }
+    }
+
+    /// Lines a person should read before starting the run.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.count == 0 {
+            warnings.push("count is 0: the run plays nothing".to_string());
+        }
+        if self.out_path.is_none() {
+            warnings.push("no --out: CSV rows go to stdout and no run_meta is written".to_string());
+        }
+        if matches!(self.permutations, Some(PermutationMode::Full)) {
+            let (per_deal, _) = self.seatings_per_deal();
+            if per_deal > BALANCED_BLOCK as u64 {
+                warnings.push(format!(
+                    "--permutations full plays {per_deal} seatings per deal; adaptive stops after {BALANCED_BLOCK} on flat deals"
+                ));
+            }
+        }
+        warnings
+    }
+
+    pub fn render(&self) -> Vec<String> {
+        let mut lines = vec!["Plan for --match-mixed (dry run; nothing is written)".to_string()];
+        let last = self.seed_start + self.count.saturating_sub(1);
+        lines.push(format!(
+            "  seeds:         {}..={} ({} deals)",
+            self.seed_start, last, self.count
+        ));
+        lines.push(format!("  seat:          {:?}", self.seat));
+        let seating: Vec<String> = PlayerPosition::LOOP
+            .iter()
+            .map(|seat| format!("{seat:?}={}", agent_label(self.diffs[seat.index()])))
+            .collect();
+        lines.push(format!("  agents:        {}", seating.join(" ")));
+        let (lo, hi) = self.seatings_per_deal();
+        lines.push(match self.permutations {
+            None => "  permutations:  off (1 seating per deal)".to_string(),
+            Some(mode) => format!(
+                "  permutations:  {}, {} seatings per deal",
+                mode.label(),
+                range(lo, hi)
+            ),
+        });
+        lines.push(format!(
+            "  baseline:      {}",
+            if self.baseline {
+                "on (one all-normal reference hand per deal)"
+            } else {
+                "off"
+            }
+        ));
+        lines.push(format!("  memoize:       {}", on_off(self.memoize)));
+        lines.push(format!("  threads:       {}", self.workers()));
+        lines.push(format!(
+            "  rules:         pass {}, target {}, jack of diamonds {}",
+            self.rules.pass_count,
+            self.rules.target_score,
+            on_off(self.rules.jack_of_diamonds)
+        ));
+        let (hands_lo, hands_hi) = self.hands();
+        lines.push(format!("  hands:         {}", range(hands_lo, hands_hi)));
+
+        let (rows_lo, rows_hi) = self.rows();
+        let bytes = |per_row: Option<f64>| {
+            per_row.map_or_else(String::new, |per_row| {
+                format!(
+                    ", ~{}",
+                    range_bytes(rows_lo as f64 * per_row, rows_hi as f64 * per_row)
+                )
+            })
+        };
+        let csv_target = self
+            .out_path
+            .as_ref()
+            .map_or_else(|| "stdout".to_string(), |p| p.display().to_string());
+        lines.push(format!(
+            "  csv:           {csv_target}, {} rows{}",
+            range(rows_lo, rows_hi),
+            bytes(self.calibration.map(|c| c.csv_row_bytes))
+        ));
+        if let Some(path) = &self.hand_outcomes {
+            lines.push(format!(
+                "  hand outcomes: {}, {} rows{} before compression",
+                path.display(),
+                range(rows_lo, rows_hi),
+                bytes(self.calibration.map(|c| c.outcome_row_bytes))
+            ));
+        }
+        if let Some(path) = &self.summary_out {
+            lines.push(format!("  summary:       {}", path.display()));
+        }
+        if let Some(path) = &self.telemetry_out {
+            lines.push(format!("  telemetry:     {}", path.display()));
+        }
+        match self.calibration {
+            Some(cal) => {
+                lines.push(format!(
+                    "  calibration:   {} hands in {:.2}s ({:.3}s/hand)",
+                    cal.hands,
+                    cal.secs,
+                    cal.secs_per_hand()
+                ));
+                let per_hand = cal.secs_per_hand() / self.workers() as f64;
+                lines.push(format!(
+                    "  wall clock:    ~{}",
+                    range_duration(hands_lo as f64 * per_hand, hands_hi as f64 * per_hand)
+                ));
+            }
+            None => lines.push("  calibration:   skipped (no hands to play)".to_string()),
+        }
+        for warning in self.warnings() {
+            lines.push(format!("  warning: {warning}"));
+        }
+        lines
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value { "on" } else { "off" }
+}
+
+fn range(lo: u64, hi: u64) -> String {
+    if lo == hi {
+        lo.to_string()
+    } else {
+        format!("{lo}-{hi}")
+    }
+}
+
+fn range_bytes(lo: f64, hi: f64) -> String {
+    if format_bytes(lo) == format_bytes(hi) {
+        format_bytes(hi)
+    } else {
+        format!("{}-{}", format_bytes(lo), format_bytes(hi))
+    }
+}
+
+fn range_duration(lo: f64, hi: f64) -> String {
+    if format_duration(lo) == format_duration(hi) {
+        format_duration(hi)
+    } else {
+        format!("{}-{}", format_duration(lo), format_duration(hi))
+    }
+}
+
+fn format_bytes(bytes: f64) -> String {
+    const KIB: f64 = 1024.0;
+    if bytes < KIB {
+        format!("{bytes:.0} B")
+    } else if bytes < KIB * KIB {
+        format!("{:.1} KiB", bytes / KIB)
+    } else if bytes < KIB * KIB * KIB {
+        format!("{:.1} MiB", bytes / (KIB * KIB))
+    } else {
+        format!("{:.1} GiB", bytes / (KIB * KIB * KIB))
+    }
+}
+
+fn format_duration(secs: f64) -> String {
+    let total = secs.round() as u64;
+    let (h, m, s) = (total / 3600, (total / 60) % 60, total % 60);
+    if h > 0 {
+        format!("{h}h {m:02}m")
+    } else if m > 0 {
+        format!("{m}m {s:02}s")
+    } else {
+        format!("{s}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(permutations: Option<PermutationMode>, diffs: [BotDifficulty; 4]) -> RunPlan {
+        RunPlan {
+            seat: PlayerPosition::South,
+            seed_start: 100,
+            count: 50,
+            diffs,
+            permutations,
+            baseline: false,
+            memoize: false,
+            rules: RuleSet::STANDARD,
+            out_path: Some(PathBuf::from("runs/a.csv")),
+            hand_outcomes: None,
+            summary_out: None,
+            telemetry_out: None,
+            calibration: Some(Calibration {
+                hands: 3,
+                secs: 0.3,
+                csv_row_bytes: 20.0,
+                outcome_row_bytes: 1000.0,
+            }),
+            threads: 1,
+        }
+    }
+
+    const MIXED: [BotDifficulty; 4] = [
+        BotDifficulty::EasyLegacy,
+        BotDifficulty::NormalHeuristic,
+        BotDifficulty::FutureHard,
+        BotDifficulty::SearchLookahead,
+    ];
+
+    #[test]
+    fn full_permutations_multiply_hands_and_warn() {
+        let plan = plan(Some(PermutationMode::Full), MIXED);
+        assert_eq!(plan.seatings_per_deal(), (24, 24));
+        assert_eq!(plan.hands(), (1200, 1200));
+        assert!(plan.warnings().iter().any(|w| w.contains("24 seatings")));
+        let text = plan.render().join("\n");
+        assert!(
+            text.contains("seeds:         100..=149 (50 deals)"),
+            "{text}"
+        );
+        assert!(text.contains("hands:         1200"), "{text}");
+        assert!(text.contains("wall clock:    ~2m 00s"), "{text}");
+    }
+
+    #[test]
+    fn threads_only_divide_permutation_runs() {
+        let mut plan = plan(Some(PermutationMode::Full), MIXED);
+        plan.threads = 4;
+        let text = plan.render().join("\n");
+        assert!(text.contains("threads:       4"), "{text}");
+        assert!(text.contains("wall clock:    ~30s"), "{text}");
+
+        plan.permutations = None;
+        assert_eq!(plan.workers(), 1);
+    }
+
+    #[test]
+    fn adaptive_permutations_report_a_range() {
+        let plan = plan(Some(PermutationMode::Adaptive { spread: 1.0 }), MIXED);
+        assert_eq!(plan.seatings_per_deal(), (4, 24));
+        assert_eq!(plan.rows(), (200, 1200));
+        assert!(plan.warnings().is_empty());
+    }
+
+    #[test]
+    fn baseline_adds_one_hand_per_deal_and_missing_out_warns() {
+        let mut plan = plan(None, [BotDifficulty::NormalHeuristic; 4]);
+        plan.baseline = true;
+        plan.out_path = None;
+        assert_eq!(plan.hands(), (100, 100));
+        assert_eq!(plan.rows(), (50, 50));
+        assert!(plan.warnings().iter().any(|w| w.contains("no --out")));
+        assert!(
+            plan.render()
+                .join("\n")
+                .contains("csv:           stdout, 50 rows")
+        );
+    }
+}
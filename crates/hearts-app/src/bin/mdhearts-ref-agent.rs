@@ -0,0 +1,142 @@
+//! A minimal outside engine for `--agent`, speaking the stdio protocol of
+//! `docs/EXTERNAL_AGENTS.md`: one JSON message per line on stdin, one reply
+//! per line on stdout.
+//!
+//! It passes its highest cards and plays its lowest legal card. That is a
+//! weak game but a complete one, so it serves as a template for engines in
+//! other languages and as the opponent in the protocol's integration test.
+//!
+//! It speaks the newest protocol version offered: one process lasts the run,
+//! acknowledging a `reset` per hand and exiting on `shutdown`. It answers a
+//! `pass_request` with a `pass_response`, and a version 1 or 2 `pass` with a
+//! `pass`; likewise for plays.
+//!
+//! - `--name <name>` changes the name it gives in the hello.
+//! - `--crash-after <n>` exits without answering its `n`th pass or play, as
+//!   a crashing engine would. It does so once: the crash leaves a file named
+//!   `crashed` in the working directory, and a process that finds it plays on.
+//! - `MDH_REF_AGENT_LOG=<path>` appends the type of each message received
+//!   to `<path>`, one per line.
+
+use hearts_core::game::serialization::parse_card;
+use hearts_core::model::card::Card;
+use serde_json::{Value, json};
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+const PROTOCOL_VERSIONS: [u64; 3] = [1, 2, 3];
+
+fn cards(value: &Value) -> Vec<Card> {
+    value
+        .as_array()
+        .map(|codes| {
+            codes
+                .iter()
+                .filter_map(|code| code.as_str().and_then(parse_card))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn reply(request: &Value, name: &str) -> Value {
+    let id = request["id"].clone();
+    let kind = request["type"].as_str();
+    // The answer to `pass_request` is `pass_response`, to `pass` a `pass`.
+    let answer = |decision: &str| {
+        if kind.is_some_and(|k| k.ends_with("_request")) {
+            format!("{decision}_response")
+        } else {
+            decision.to_string()
+        }
+    };
+    match kind {
+        Some("hello") => {
+            let offered = request["versions"].as_array().into_iter().flatten();
+            let Some(version) = offered
+                .filter_map(Value::as_u64)
+                .filter(|v| PROTOCOL_VERSIONS.contains(v))
+                .max()
+            else {
+                return json!({
+                    "type": "error",
+                    "id": id,
+                    "message": format!("only protocol versions {PROTOCOL_VERSIONS:?} are spoken here"),
+                });
+            };
+            json!({ "type": "hello", "id": id, "version": version, "name": name })
+        }
+        Some("reset") => json!({ "type": "reset", "id": id }),
+        Some("pass" | "pass_request") => {
+            let mut hand = cards(&request["hand"]);
+            hand.sort_by_key(|card| std::cmp::Reverse(card.rank));
+            let count = request["count"].as_u64().unwrap_or(3) as usize;
+            let pass: Vec<String> = hand.iter().take(count).map(Card::to_string).collect();
+            json!({ "type": answer("pass"), "id": id, "cards": pass })
+        }
+        Some("play" | "play_request") => {
+            match cards(&request["legal"]).into_iter().min_by_key(|c| c.rank) {
+                Some(card) => json!({ "type": answer("play"), "id": id, "card": card.to_string() }),
+                None => json!({ "type": "error", "id": id, "message": "no legal cards" }),
+            }
+        }
+        other => json!({
+            "type": "error",
+            "id": id,
+            "message": format!("unknown request {other:?}"),
+        }),
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let mut name = "mdhearts-ref-agent".to_string();
+    let mut crash_after = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => name = args.next().unwrap_or(name),
+            "--crash-after" => crash_after = args.next().and_then(|n| n.parse::<usize>().ok()),
+            _ => {}
+        }
+    }
+    let marker = Path::new("crashed");
+    if marker.exists() {
+        crash_after = None;
+    }
+    let mut log = match std::env::var_os("MDH_REF_AGENT_LOG") {
+        Some(path) => Some(OpenOptions::new().create(true).append(true).open(path)?),
+        None => None,
+    };
+    let mut decisions = 0;
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let answer = match serde_json::from_str::<Value>(&line) {
+            Ok(request) => {
+                let kind = request["type"].as_str().unwrap_or_default();
+                if let Some(log) = log.as_mut() {
+                    writeln!(log, "{kind}")?;
+                }
+                match kind {
+                    "shutdown" => break,
+                    "pass" | "play" | "pass_request" | "play_request" => {
+                        decisions += 1;
+                        if crash_after == Some(decisions) {
+                            std::fs::write(marker, "")?;
+                            std::process::exit(1);
+                        }
+                    }
+                    _ => {}
+                }
+                reply(&request, &name)
+            }
+            Err(err) => json!({ "type": "error", "message": format!("invalid JSON: {err}") }),
+        };
+        writeln!(stdout, "{answer}")?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
@@ -1,5 +1,8 @@
-use super::BotContext;
+use super::{BotContext, BotStyle, determine_style, snapshot_scores};
 use hearts_core::model::card::Card;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rank::Rank;
+use hearts_core::model::suit::Suit;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
@@ -29,6 +32,9 @@ static PLAY_ADVISER: OnceLock<PhaseAdviser> = OnceLock::new();
 fn load_play_adviser() -> PhaseAdviser {
     let path = std::env::var("MDH_ADVISER_PLAY_PATH")
         .unwrap_or_else(|_| "assets/adviser/play.json".to_string());
+    let Ok(path) = crate::paths::expand_env_path(&path) else {
+        return PhaseAdviser::default();
+    };
     match fs::read_to_string(&path) {
         Ok(raw) => {
             if let Ok(parsed) = serde_json::from_str::<AdviserFile>(&raw)
@@ -62,3 +68,246 @@ pub fn play_bias(card: Card, ctx: &BotContext<'_>) -> i32 {
     let _ = ctx; // placeholder for future feature usage
     play_adviser().bias_for(card)
 }
+
+/// The strategic theme of a hand, as summarised for the human at round start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BriefingKind {
+    /// The bot style for this hand would go for the moon.
+    MoonAttempt,
+    /// Q♠, or A♠/K♠ without it, with too few spades to hide behind.
+    QueenDanger,
+    /// Another seat is close to ending the match.
+    HuntLeader(PlayerPosition),
+    /// A top heart that can stop a moon if it stays guarded.
+    MoonDefense(Card),
+    /// A short side suit that can be voided early.
+    VoidPotential(Suit),
+    SafeLow,
+}
+
+/// Classifies the seat's hand (after passing) into one briefing theme.
+/// Earlier checks win: a moon attempt outranks spade danger, which outranks
+/// the scoreboard objective, then moon defense and voids.
+pub fn briefing_kind(ctx: &BotContext<'_>) -> BriefingKind {
+    let style = determine_style(ctx);
+    if style == BotStyle::AggressiveMoon {
+        return BriefingKind::MoonAttempt;
+    }
+
+    let profile = ctx.hand_profile;
+    if profile.queen_exposed() {
+        return BriefingKind::QueenDanger;
+    }
+
+    if style == BotStyle::HuntLeader {
+        return BriefingKind::HuntLeader(snapshot_scores(ctx.scores).max_player);
+    }
+
+    let top_heart = ctx
+        .hand()
+        .iter()
+        .filter(|card| card.suit == Suit::Hearts)
+        .max_by_key(|card| card.rank);
+    if let Some(&card) = top_heart
+        && card.rank >= Rank::King
+        && profile.suit_len(Suit::Hearts) >= 2
+    {
+        return BriefingKind::MoonDefense(card);
+    }
+
+    let (suit, count) = profile.shortest_side_suit();
+    if count <= 2 {
+        return BriefingKind::VoidPotential(suit);
+    }
+
+    BriefingKind::SafeLow
+}
+
+fn suit_name(suit: Suit) -> &'static str {
+    match suit {
+        Suit::Clubs => "clubs",
+        Suit::Diamonds => "diamonds",
+        Suit::Spades => "spades",
+        Suit::Hearts => "hearts",
+    }
+}
+
+/// One-sentence round-start briefing for the seat in `ctx`.
+pub fn briefing(ctx: &BotContext<'_>) -> String {
+    match briefing_kind(ctx) {
+        BriefingKind::MoonAttempt => {
+            "This hand can shoot the moon - keep control of the hearts and run them late."
+                .to_string()
+        }
+        BriefingKind::QueenDanger => {
+            "Your spades are thin around the Q\u{2660} - avoid spade leads and shed it when you can."
+                .to_string()
+        }
+        BriefingKind::HuntLeader(leader) => {
+            format!("{leader} is close to ending the match - steer the points their way.")
+        }
+        BriefingKind::MoonDefense(card) => format!(
+            "Your hand is a strong moon-defense hand - keep your {} guarded.",
+            card
+        ),
+        BriefingKind::VoidPotential(suit) => {
+            format!("Good void potential in {} after your pass.", suit_name(suit))
+        }
+        BriefingKind::SafeLow => {
+            "A low, safe hand - duck tricks and let the others fight over the points.".to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BriefingKind, briefing, briefing_kind};
+    use crate::bot::{BotContext, BotDifficulty, UnseenTracker};
+    use Rank::{Ace, Eight, Five, Four, Jack, King, Nine, Queen, Seven, Six, Ten, Three, Two};
+    use hearts_core::model::card::Card;
+    use hearts_core::model::hand::Hand;
+    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::rank::Rank;
+    use hearts_core::model::round::{RoundPhase, RoundState};
+    use hearts_core::model::score::ScoreBoard;
+    use hearts_core::model::suit::Suit;
+    use hearts_core::model::suit::Suit::{Clubs, Diamonds, Hearts, Spades};
+
+    fn cards(spec: &[(Rank, Suit)]) -> Vec<Card> {
+        spec.iter()
+            .map(|&(rank, suit)| Card::new(rank, suit))
+            .collect()
+    }
+
+    fn kind_for(hand: &[Card], scores: [u32; 4]) -> (BriefingKind, String) {
+        let seat = PlayerPosition::South;
+        let mut hands = [Hand::new(), Hand::new(), Hand::new(), Hand::new()];
+        hands[seat.index()] = Hand::with_cards(hand.to_vec());
+        let round =
+            RoundState::from_hands(hands, seat, PassingDirection::Hold, RoundPhase::Playing);
+        let mut board = ScoreBoard::new();
+        for seat in PlayerPosition::LOOP {
+            board.set_score(seat, scores[seat.index()]);
+        }
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = BotContext::new(
+            seat,
+            &round,
+            board,
+            PassingDirection::Hold,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        (briefing_kind(&ctx), briefing(&ctx))
+    }
+
+    #[test]
+    fn moon_hand_is_briefed_as_attempt() {
+        let hand = cards(&[
+            (Ace, Hearts),
+            (King, Hearts),
+            (Queen, Hearts),
+            (Jack, Hearts),
+            (Ten, Hearts),
+            (Nine, Hearts),
+            (Eight, Hearts),
+            (Ace, Spades),
+            (King, Spades),
+            (Two, Clubs),
+            (Three, Clubs),
+            (Four, Diamonds),
+            (Five, Diamonds),
+        ]);
+        assert_eq!(kind_for(&hand, [0; 4]).0, BriefingKind::MoonAttempt);
+    }
+
+    #[test]
+    fn short_queen_of_spades_is_danger() {
+        let hand = cards(&[
+            (Queen, Spades),
+            (Three, Spades),
+            (Two, Clubs),
+            (Five, Clubs),
+            (Seven, Clubs),
+            (Nine, Clubs),
+            (Four, Diamonds),
+            (Six, Diamonds),
+            (Eight, Diamonds),
+            (Ten, Diamonds),
+            (Two, Hearts),
+            (Three, Hearts),
+            (Four, Hearts),
+        ]);
+        assert_eq!(kind_for(&hand, [0; 4]).0, BriefingKind::QueenDanger);
+    }
+
+    #[test]
+    fn guarded_top_heart_is_moon_defense() {
+        let hand = cards(&[
+            (Ace, Hearts),
+            (Four, Hearts),
+            (Two, Hearts),
+            (Two, Spades),
+            (Four, Spades),
+            (Six, Spades),
+            (Eight, Spades),
+            (Three, Clubs),
+            (Five, Clubs),
+            (Seven, Clubs),
+            (Three, Diamonds),
+            (Five, Diamonds),
+            (Seven, Diamonds),
+        ]);
+        let (kind, text) = kind_for(&hand, [0; 4]);
+        assert_eq!(kind, BriefingKind::MoonDefense(Card::new(Ace, Hearts)));
+        assert!(text.contains(&Card::new(Ace, Hearts).to_string()));
+    }
+
+    #[test]
+    fn short_side_suit_is_void_potential() {
+        let hand = cards(&[
+            (Nine, Diamonds),
+            (Two, Spades),
+            (Four, Spades),
+            (Six, Spades),
+            (Eight, Spades),
+            (Two, Clubs),
+            (Three, Clubs),
+            (Five, Clubs),
+            (Seven, Clubs),
+            (Two, Hearts),
+            (Three, Hearts),
+            (Five, Hearts),
+            (Seven, Hearts),
+        ]);
+        let (kind, text) = kind_for(&hand, [0; 4]);
+        assert_eq!(kind, BriefingKind::VoidPotential(Diamonds));
+        assert!(text.contains("diamonds"));
+    }
+
+    #[test]
+    fn balanced_low_hand_is_safe_unless_a_leader_is_near_the_end() {
+        let hand = cards(&[
+            (Two, Spades),
+            (Four, Spades),
+            (Six, Spades),
+            (Two, Clubs),
+            (Three, Clubs),
+            (Five, Clubs),
+            (Seven, Clubs),
+            (Two, Diamonds),
+            (Four, Diamonds),
+            (Six, Diamonds),
+            (Two, Hearts),
+            (Three, Hearts),
+            (Five, Hearts),
+        ]);
+        assert_eq!(kind_for(&hand, [0; 4]).0, BriefingKind::SafeLow);
+        assert_eq!(
+            kind_for(&hand, [10, 92, 20, 30]).0,
+            BriefingKind::HuntLeader(PlayerPosition::East)
+        );
+    }
+}
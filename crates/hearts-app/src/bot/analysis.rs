@@ -0,0 +1,253 @@
+//! The heuristic's full evaluation of a position, for tooling.
+//!
+//! [`evaluate_plays`] scores every legal play of `ctx.seat` and
+//! [`evaluate_passes`] every pass, each with the named terms behind its
+//! total. Both read nothing but their arguments and leave the planners'
+//! per-thread statistics untouched, so the same position always evaluates the
+//! same way and can be evaluated from anywhere. The explainers, the dataset
+//! labels and the `--debug-deal` REPL are built on the same scorer.
+
+use super::BotContext;
+use super::pass::{PassBreakdown, PassPlanner, PassWeights, ScorePart};
+use super::play::{forced_play, score_plays, without_stats};
+use hearts_core::model::card::Card;
+use serde::{Serialize, Serializer};
+
+/// One legal play as the heuristic scores it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlayEvaluation {
+    #[serde(serialize_with = "card_code")]
+    pub card: Card,
+    pub total: i32,
+    /// `base` first, then each adjustment; zero terms are left out.
+    pub parts: Vec<ScorePart>,
+    /// The card takes the trick, as the scorer plays it out.
+    pub wins_trick: bool,
+    /// Penalty points in that trick.
+    pub penalties: u8,
+    /// A moon shot has to be stopped and the planner plays this card
+    /// whatever it scores.
+    pub forced: bool,
+}
+
+impl PlayEvaluation {
+    /// The terms as `name=delta` pairs, e.g. `base=-1200 void_creation=300`.
+    pub fn parts_text(&self) -> String {
+        let parts: Vec<String> = self
+            .parts
+            .iter()
+            .map(|part| format!("{}={}", part.name, part.delta))
+            .collect();
+        parts.join(" ")
+    }
+}
+
+fn card_code<S: Serializer>(card: &Card, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(card)
+}
+
+/// Every legal play for `ctx.seat`, best first with ties in canonical card
+/// order; empty when the seat has nothing to play.
+pub fn evaluate_plays(ctx: &BotContext<'_>) -> Vec<PlayEvaluation> {
+    let legal = ctx.round.legal_plays(ctx.seat);
+    without_stats(|| {
+        let forced = forced_play(&legal, ctx);
+        score_plays(&legal, ctx, None)
+            .into_iter()
+            .map(|scored| PlayEvaluation {
+                card: scored.card,
+                total: scored.total,
+                parts: scored.parts,
+                wins_trick: scored.winner == ctx.seat,
+                penalties: scored.penalties,
+                forced: forced == Some(scored.card),
+            })
+            .collect()
+    })
+}
+
+/// Every pass of the round's pass count from `ctx.seat`'s hand under
+/// `weights`, best first; empty on a hold hand.
+pub fn evaluate_passes(ctx: &BotContext<'_>, weights: PassWeights) -> Vec<PassBreakdown> {
+    if !ctx.passing_direction.requires_selection() {
+        return Vec::new();
+    }
+    PassPlanner::breakdowns(ctx.hand(), ctx, weights, usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::{BotDifficulty, PlayPlanner, UnseenTracker};
+    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::player::PlayerPosition::{East, North, South, West};
+    use hearts_core::model::round::RoundState;
+    use hearts_core::model::score::ScoreBoard;
+    use hearts_core::testkit::{RoundBuilder, card, cards, to_play};
+
+    fn round_after(plays: &str) -> RoundState {
+        let mut round = RoundBuilder::new()
+            .seat_hand(North, "2C 9S QS 3H")
+            .seat_hand(East, "3C KS 4H 8D")
+            .seat_hand(South, "4C 5S AH 9D")
+            .seat_hand(West, "5C AS 5D KD")
+            .build();
+        for played in cards(plays) {
+            let seat = to_play(&round);
+            round.play_card(seat, played).unwrap();
+        }
+        round
+    }
+
+    fn evaluate(round: &RoundState) -> Vec<PlayEvaluation> {
+        let seat = to_play(round);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(round);
+        let ctx = BotContext::new(
+            seat,
+            round,
+            ScoreBoard::new(),
+            PassingDirection::Hold,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        evaluate_plays(&ctx)
+    }
+
+    fn summary(evaluations: &[PlayEvaluation]) -> Vec<(String, i32, String)> {
+        evaluations
+            .iter()
+            .map(|e| (e.card.to_string(), e.total, e.parts_text()))
+            .collect()
+    }
+
+    fn owned(rows: &[(&str, i32, &str)]) -> Vec<(String, i32, String)> {
+        rows.iter()
+            .map(|(card, total, parts)| (card.to_string(), *total, parts.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn a_lead_scores_every_card_in_hand() {
+        // West took the clubs and leads from A♠ K♦ 5♦.
+        let evaluations = evaluate(&round_after("2C 3C 4C 5C"));
+        assert_eq!(
+            summary(&evaluations),
+            owned(&[
+                (
+                    "5D",
+                    610,
+                    "base=600 lead_rank_bias=-50 cards_played_bias=40 unseen_card_bonus=20"
+                ),
+                (
+                    "AS",
+                    -4832,
+                    "base=-5502 void_creation=750 lead_rank_bias=-140 cards_played_bias=40 unseen_card_bonus=20"
+                ),
+                (
+                    "KD",
+                    -5104,
+                    "base=-5034 lead_rank_bias=-130 cards_played_bias=40 unseen_card_bonus=20"
+                ),
+            ])
+        );
+        assert!(evaluations[1].wins_trick && !evaluations[0].wins_trick);
+        assert!(evaluations.iter().all(|e| !e.forced));
+    }
+
+    #[test]
+    fn following_under_the_ace_sheds_the_queen() {
+        let evaluations = evaluate(&round_after("2C 3C 4C 5C AS"));
+        assert_eq!(
+            summary(&evaluations),
+            owned(&[
+                (
+                    "QS",
+                    6882,
+                    "base=7100 follow_high_rank_penalty=-288 cards_played_bias=50 unseen_card_bonus=20"
+                ),
+                (
+                    "9S",
+                    454,
+                    "base=600 follow_high_rank_penalty=-216 cards_played_bias=50 unseen_card_bonus=20"
+                ),
+            ])
+        );
+        assert_eq!(
+            (evaluations[0].wins_trick, evaluations[0].penalties),
+            (false, 13)
+        );
+    }
+
+    #[test]
+    fn a_void_seat_dumps_the_queen_first() {
+        let evaluations = evaluate(&round_after("2C 3C 4C 5C KD"));
+        assert_eq!(
+            summary(&evaluations),
+            owned(&[
+                (
+                    "QS",
+                    16070,
+                    "base=8200 off_suit_dump_bonus=7800 cards_played_bias=50 unseen_card_bonus=20"
+                ),
+                (
+                    "3H",
+                    2720,
+                    "base=1300 void_creation=750 off_suit_dump_bonus=600 cards_played_bias=50 unseen_card_bonus=20"
+                ),
+                (
+                    "9S",
+                    -30,
+                    "base=-100 cards_played_bias=50 unseen_card_bonus=20"
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn evaluations_agree_with_the_planner() {
+        for plays in ["2C", "2C 3C 4C 5C", "2C 3C 4C 5C AS", "2C 3C 4C 5C KD"] {
+            let round = round_after(plays);
+            let seat = to_play(&round);
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(&round);
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                ScoreBoard::new(),
+                PassingDirection::Hold,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            let evaluations = evaluate_plays(&ctx);
+            for evaluation in &evaluations {
+                let sum: i32 = evaluation.parts.iter().map(|part| part.delta).sum();
+                assert_eq!(sum, evaluation.total, "{plays}: {}", evaluation.card);
+            }
+            let legal = round.legal_plays(seat);
+            let totals: Vec<(Card, i32)> = evaluations.iter().map(|e| (e.card, e.total)).collect();
+            assert_eq!(totals, PlayPlanner::explain_candidates(&legal, &ctx));
+            assert_eq!(
+                evaluations.first().map(|e| e.card),
+                PlayPlanner::choose(&legal, &ctx)
+            );
+        }
+    }
+
+    #[test]
+    fn hold_hands_have_no_passes_to_evaluate() {
+        let round = round_after("");
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = BotContext::new(
+            North,
+            &round,
+            ScoreBoard::new(),
+            PassingDirection::Hold,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert!(evaluate_passes(&ctx, PassWeights::default()).is_empty());
+        assert_eq!(card("2C"), evaluate_plays(&ctx)[0].card);
+    }
+}
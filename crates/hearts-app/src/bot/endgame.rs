@@ -1,381 +1,418 @@
-// Phase 3: Endgame Perfect Play
+// Exact endgame play.
 //
-// This module implements a dynamic programming solver for endgame positions
-// (when each player has ≤ 6 cards remaining). With perfect information about
-// remaining cards, we can solve these positions optimally.
+// Once the seat is down to `BotParams::endgame_cards` cards the rest of the
+// game tree is small enough to walk in full. The solver deals the cards the
+// seat cannot see into worlds consistent with the tracker, plays every
+// sequence out in each world by minimax, the seat keeping its round points
+// lowest and the other three raising them, and picks the card with the
+// lowest expected round points over the worlds. Identical worlds are solved once and
+// weighted by how often they were drawn, so a position the tracker has pinned
+// down is solved once, on the true hands.
 //
-// Algorithm: Minimax with memoization
-// - Hash position based on: cards in each hand + current trick + hearts broken
-// - Recursively evaluate all possible play sequences
-// - Cache results to avoid recomputation
-// - Guaranteed optimal play in the endgame
-
-use super::{BotContext, UnseenTracker};
+// Worlds are drawn from a seed derived from the position, so a decision is
+// reproducible. A world is solved with hands as card bitmasks and a memo of
+// trick boundaries, keyed by the cards left, the leader, whether hearts are
+// broken and the points taken, and cut with alpha-beta bounds on what the
+// seat can still take.
+
+use super::rollout::position_key;
+use super::{BotContext, DecisionLimit, PlayPlannerHard};
 use hearts_core::model::card::Card;
 use hearts_core::model::player::PlayerPosition;
-use hearts_core::model::round::RoundState;
+use hearts_core::model::rank::Rank;
+use hearts_core::model::rules::{JACK_OF_DIAMONDS, JACK_OF_DIAMONDS_BONUS, RuleSet};
+use hearts_core::model::score::hand_points;
+use hearts_core::model::suit::Suit;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use std::collections::HashMap;
 use std::time::Instant;
 
 // ============================================================================
-// Position Hashing
+// Card sets
 // ============================================================================
 
-#[derive(Hash, Eq, PartialEq, Clone, Debug)]
-struct EndgamePosition {
-    // Sorted hands for each player
-    hands: [Vec<Card>; 4],
-    // Current trick cards
-    trick_cards: Vec<(PlayerPosition, Card)>,
-    // Game state
-    hearts_broken: bool,
-    // Who leads next (if trick is empty)
+const SUIT_BITS: u64 = (1 << 13) - 1;
+
+fn card_bit(card: Card) -> u64 {
+    1 << (card.suit as usize * 13 + card.rank.index())
+}
+
+fn card_at(idx: u32) -> Card {
+    let suit = Suit::from_index(idx as usize / 13).expect("card index below 52");
+    let rank = Rank::from_index(idx as usize % 13).expect("card index below 52");
+    Card::new(rank, suit)
+}
+
+fn suit_bits(suit: Suit) -> u64 {
+    SUIT_BITS << (suit as usize * 13)
+}
+
+fn cards_bits<'c>(cards: impl IntoIterator<Item = &'c Card>) -> u64 {
+    cards
+        .into_iter()
+        .fold(0, |bits, &card| bits | card_bit(card))
+}
+
+/// Whether trading `a` for `b` could only change who wins a trick, never
+/// what it is worth.
+fn interchangeable(a: Card, b: Card) -> bool {
+    a.penalty_value() == b.penalty_value() && a != JACK_OF_DIAMONDS && b != JACK_OF_DIAMONDS
+}
+
+// ============================================================================
+// Positions
+// ============================================================================
+
+const NO_JACK: u8 = 4;
+
+const QUEEN_OF_SPADES: Card = Card::new(Rank::Queen, Suit::Spades);
+
+/// A position inside one world.
+#[derive(Clone, Copy)]
+struct Node {
+    hands: [u64; 4],
+    trick: [(PlayerPosition, Card); 4],
+    trick_len: usize,
+    /// The cards in `trick`.
+    trick_bits: u64,
     leader: PlayerPosition,
-    // Accumulated penalty points for each player
-    accumulated_penalties: [u8; 4],
+    hearts_broken: bool,
+    /// Penalty points each seat has taken this round.
+    taken: [u8; 4],
+    /// Seat index that took J♦, or `NO_JACK`.
+    jack: u8,
 }
 
-impl EndgamePosition {
-    fn from_round(
-        round: &RoundState,
-        our_seat: PlayerPosition,
-        tracker: &UnseenTracker,
-        use_sampling: bool,
-    ) -> Self {
-        let mut hands: [Vec<Card>; 4] = Default::default();
-
-        // Our hand is known
-        let our_cards: Vec<Card> = round.hand(our_seat).iter().copied().collect();
-        hands[our_seat.index()] = our_cards;
-
-        if use_sampling && tracker.unseen_count() > 0 {
-            // Use belief-state sampling for imperfect information
-            use rand::SeedableRng;
-            let mut rng = rand::rngs::SmallRng::from_entropy();
-            let sampled = tracker.sample_world(&mut rng, our_seat, round);
-
-            // Merge sampled hands with known cards from round
-            for pos in [
-                PlayerPosition::North,
-                PlayerPosition::East,
-                PlayerPosition::South,
-                PlayerPosition::West,
-            ] {
-                if pos == our_seat {
-                    continue; // Already set
-                }
+impl Node {
+    fn seat_to_play(&self) -> PlayerPosition {
+        match self.trick_len {
+            0 => self.leader,
+            len => self.trick[len - 1].0.next(),
+        }
+    }
 
-                // Get visible cards for this opponent (cards they've already played in current trick)
-                let mut visible_cards: Vec<Card> = round.hand(pos).iter().copied().collect();
-                // Add sampled cards
-                visible_cards.extend(sampled.hands[pos.index()].iter().copied());
-                hands[pos.index()] = visible_cards;
+    /// The cards `seat` may play, under the rules
+    /// [`RoundState::legal_plays`](hearts_core::model::round::RoundState::legal_plays)
+    /// applies after the first trick.
+    fn legal(&self, seat: PlayerPosition) -> u64 {
+        let hand = self.hands[seat.index()];
+        if self.trick_len == 0 {
+            let safe = hand & !suit_bits(Suit::Hearts);
+            if self.hearts_broken || safe == 0 {
+                hand
+            } else {
+                safe
             }
         } else {
-            // Use perfect information (for testing or when all cards are known)
-            for pos in [
-                PlayerPosition::North,
-                PlayerPosition::East,
-                PlayerPosition::South,
-                PlayerPosition::West,
-            ] {
-                if pos == our_seat {
-                    continue; // Already set
-                }
+            let follow = hand & suit_bits(self.trick[0].1.suit);
+            if follow == 0 { hand } else { follow }
+        }
+    }
 
-                let opponent_cards: Vec<Card> = round.hand(pos).iter().copied().collect();
-                hands[pos.index()] = opponent_cards;
+    fn play(&self, seat: PlayerPosition, card: Card) -> Node {
+        let mut next = *self;
+        next.hands[seat.index()] &= !card_bit(card);
+        next.trick[next.trick_len] = (seat, card);
+        next.trick_len += 1;
+        next.trick_bits |= card_bit(card);
+        if card.is_penalty() {
+            next.hearts_broken = true;
+        }
+        if next.trick_len == 4 {
+            let lead = next.trick[0].1.suit;
+            let (winner, _) = next
+                .trick
+                .iter()
+                .copied()
+                .filter(|(_, card)| card.suit == lead)
+                .max_by_key(|(_, card)| card.rank)
+                .expect("the lead card follows itself");
+            let points: u8 = next
+                .trick
+                .iter()
+                .map(|(_, card)| card.penalty_value())
+                .sum();
+            next.taken[winner.index()] += points;
+            if next.trick.iter().any(|&(_, card)| card == JACK_OF_DIAMONDS) {
+                next.jack = winner.index() as u8;
             }
+            next.leader = winner;
+            next.trick_len = 0;
+            next.trick_bits = 0;
         }
+        next
+    }
 
-        // Current trick
-        let trick_cards: Vec<(PlayerPosition, Card)> = round
-            .current_trick()
-            .plays()
-            .iter()
-            .map(|p| (p.position, p.card))
-            .collect();
-
-        // Leader is always the trick leader
-        let leader = round.current_trick().leader();
+    fn boundary_key(&self, remaining: u64) -> BoundaryKey {
+        (
+            remaining,
+            self.leader,
+            self.hearts_broken,
+            self.taken,
+            self.jack,
+        )
+    }
 
-        EndgamePosition {
-            hands,
-            trick_cards,
-            hearts_broken: round.hearts_broken(),
-            leader,
-            accumulated_penalties: [0, 0, 0, 0], // Start with zero penalties
-        }
+    fn remaining(&self) -> u64 {
+        self.hands.iter().fold(0, |bits, hand| bits | hand)
     }
 }
 
 // ============================================================================
-// Endgame Solver
+// Solver
 // ============================================================================
 
+/// A trick boundary within one world: the cards left, the leader, whether
+/// hearts are broken, the points taken and who holds J♦.
+type BoundaryKey = (u64, PlayerPosition, bool, [u8; 4], u8);
+
+/// The solver's pick and the round points it expects the seat to finish
+/// with, moon rule and any J♦ bonus applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EndgameSolution {
+    pub card: Card,
+    pub expected_points: f32,
+}
+
 pub struct EndgameSolver {
-    // Memo key includes perspective (our_seat) because evaluation depends on viewpoint
-    memo: HashMap<(EndgamePosition, PlayerPosition), EndgameResult>,
+    rules: RuleSet,
+    /// The seat solved for.
+    seat: PlayerPosition,
+    /// Cards that still change anyone's points.
+    scoring: u64,
+    /// Bounds on the seat's points from each trick boundary searched.
+    memo: HashMap<BoundaryKey, (i32, i32)>,
     nodes_evaluated: usize,
-    // Optional deadline for timeout protection
     deadline: Option<Instant>,
 }
 
-#[derive(Clone, Debug)]
-struct EndgameResult {
-    best_move: Card,
-    expected_penalties: [u8; 4], // Expected final penalties for each player
-}
-
 impl EndgameSolver {
     pub fn new() -> Self {
         Self {
+            rules: RuleSet::STANDARD,
+            seat: PlayerPosition::North,
+            scoring: 0,
             memo: HashMap::new(),
             nodes_evaluated: 0,
             deadline: None,
         }
     }
 
-    /// Set a timeout deadline for this solver
-    #[allow(dead_code)]
+    /// Gives up, returning `None` from [`Self::solve`], once `deadline`
+    /// passes.
     pub fn set_deadline(&mut self, deadline: Instant) {
         self.deadline = Some(deadline);
     }
 
-    /// Check if deadline has been exceeded
-    fn time_expired(&self) -> bool {
-        if let Some(deadline) = self.deadline {
-            Instant::now() >= deadline
-        } else {
-            false
-        }
-    }
-
-    /// Solve the endgame and return the best move
-    pub fn solve(&mut self, ctx: &BotContext<'_>, legal: &[Card]) -> Option<Card> {
-        if legal.is_empty() {
+    /// The best of `legal` for `ctx.seat`, the earliest on a tie, or `None`
+    /// when the hand is longer than `ctx.params.endgame_cards` or the
+    /// deadline passes.
+    pub fn solve(&mut self, ctx: &BotContext<'_>, legal: &[Card]) -> Option<EndgameSolution> {
+        if legal.is_empty() || ctx.hand().len() > ctx.params.endgame_cards as usize {
             return None;
         }
-
-        // Only solve if we're in the endgame (few cards remaining)
-        let max_cards = endgame_max_cards(ctx);
-        if ctx.hand().len() > max_cards {
-            return None; // Not in endgame yet
+        self.rules = RuleSet::STANDARD.with_jack_of_diamonds(ctx.jack_of_diamonds);
+        self.seat = ctx.seat;
+        self.scoring = suit_bits(Suit::Hearts) | card_bit(QUEEN_OF_SPADES);
+        if ctx.jack_of_diamonds {
+            self.scoring |= card_bit(JACK_OF_DIAMONDS);
+        }
+        let round = ctx.round;
+        let trick = round.current_trick();
+        let mut start = Node {
+            hands: [0; 4],
+            trick: [(ctx.seat, legal[0]); 4],
+            trick_len: trick.plays().len(),
+            trick_bits: cards_bits(trick.plays().iter().map(|play| &play.card)),
+            leader: trick.leader(),
+            hearts_broken: round.hearts_broken(),
+            taken: round.penalty_totals(),
+            jack: round
+                .captured_by(JACK_OF_DIAMONDS)
+                .map_or(NO_JACK, |seat| seat.index() as u8),
+        };
+        for (slot, play) in start.trick.iter_mut().zip(trick.plays()) {
+            *slot = (play.position, play.card);
+        }
+        if start.seat_to_play() != ctx.seat {
+            return None;
         }
 
-        // Use belief-state sampling for imperfect information endgame solving
-        let use_sampling = endgame_use_sampling();
-        let pos = EndgamePosition::from_round(ctx.round, ctx.seat, ctx.tracker, use_sampling);
-
-        let result = self.minimax(&pos, ctx.seat, true)?;
-
-        // Make sure the best move is actually legal
-        if legal.contains(&result.best_move) {
-            Some(result.best_move)
-        } else {
-            None
+        let worlds = deal_worlds(ctx);
+        let mut totals = vec![0.0f32; legal.len()];
+        let mut weight_sum = 0.0f32;
+        for (hands, weight) in worlds {
+            self.memo.clear();
+            let node = Node { hands, ..start };
+            for (total, &card) in totals.iter_mut().zip(legal) {
+                let points = self.value(&node.play(ctx.seat, card), i32::MIN, i32::MAX)?;
+                *total += weight as f32 * points as f32;
+            }
+            weight_sum += weight as f32;
         }
+        let (idx, total) = totals
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+        Some(EndgameSolution {
+            card: legal[idx],
+            expected_points: total / weight_sum,
+        })
     }
 
-    fn minimax(
-        &mut self,
-        pos: &EndgamePosition,
-        our_seat: PlayerPosition,
-        is_our_turn: bool,
-    ) -> Option<EndgameResult> {
+    /// The seat's round points from `node` on when it plays to keep them
+    /// lowest and the other seats play to raise them, or a bound past
+    /// `alpha`/`beta` once the answer falls outside them.
+    fn value(&mut self, node: &Node, mut alpha: i32, mut beta: i32) -> Option<i32> {
         self.nodes_evaluated += 1;
-
-        // Check for timeout
-        if self.time_expired() {
+        if self.nodes_evaluated.is_multiple_of(1024)
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
             return None;
         }
-
-        // Check memo (keyed by position + perspective)
-        if let Some(result) = self.memo.get(&(pos.clone(), our_seat)) {
-            return Some(result.clone());
+        let remaining = node.remaining();
+        if remaining & self.scoring == 0 && node.trick_bits & self.scoring == 0 {
+            let jack = PlayerPosition::from_index(node.jack as usize);
+            // The bots play without the sun, so `self.rules` never scores one.
+            return Some(hand_points(node.taken, jack, None, &self.rules)[self.seat.index()]);
         }
-
-        // Base case: all hands empty
-        if pos.hands.iter().all(|h| h.is_empty()) {
-            let mut final_penalties = pos.accumulated_penalties;
-
-            // Check for moon shooting: if any player took all 26 points
-            for player_idx in 0..4 {
-                if final_penalties[player_idx] == 26 {
-                    // Moon shot! Shooter gets 0, all others get 26
-                    final_penalties = [26, 26, 26, 26];
-                    final_penalties[player_idx] = 0;
-                    break;
-                }
-            }
-
-            let result = EndgameResult {
-                best_move: Card::new(
-                    hearts_core::model::rank::Rank::Two,
-                    hearts_core::model::suit::Suit::Clubs,
-                ), // Dummy
-                expected_penalties: final_penalties,
-            };
-            return Some(result);
+        let (lower, upper) = self.reach(node, remaining);
+        if lower >= beta || upper <= alpha || lower == upper {
+            return Some(if lower >= beta { lower } else { upper });
         }
-
-        // Determine who plays next
-        let to_play = if pos.trick_cards.is_empty() {
-            pos.leader
-        } else {
-            let last = pos.trick_cards.last().unwrap().0;
-            last.next()
-        };
-
-        let legal = self.get_legal_moves(pos, to_play);
-        if legal.is_empty() {
-            return None;
+        let key = node.boundary_key(remaining);
+        if node.trick_len == 0
+            && let Some(&(lower, upper)) = self.memo.get(&key)
+        {
+            if lower == upper || lower >= beta {
+                return Some(lower);
+            }
+            if upper <= alpha {
+                return Some(upper);
+            }
+            alpha = alpha.max(lower);
+            beta = beta.min(upper);
         }
-
-        let mut best_result: Option<EndgameResult> = None;
-
-        for card in legal {
-            // Apply the move
-            let next_pos = self.apply_move(pos, to_play, card);
-
-            // Determine who plays next in the new position
-            let next_to_play = if next_pos.trick_cards.is_empty() {
-                next_pos.leader // Trick completed, new leader plays
-            } else {
-                next_pos.trick_cards.last().unwrap().0.next() // Trick in progress
-            };
-
-            // Recursively evaluate
-            let result = self.minimax(&next_pos, our_seat, next_to_play == our_seat)?;
-
-            // Choose best move based on whose turn it is
-            if is_our_turn {
-                // Minimize our penalties
-                if best_result.is_none()
-                    || result.expected_penalties[our_seat.index()]
-                        < best_result.as_ref().unwrap().expected_penalties[our_seat.index()]
-                {
-                    best_result = Some(EndgameResult {
-                        best_move: card,
-                        expected_penalties: result.expected_penalties,
-                    });
+        let (alpha_in, beta_in) = (alpha, beta);
+
+        let seat = node.seat_to_play();
+        let ours = seat == self.seat;
+        let hand = node.hands[seat.index()];
+        let live = remaining | node.trick_bits;
+        let mut moves = node.legal(seat);
+        let mut best = if ours { i32::MAX } else { i32::MIN };
+        while moves != 0 {
+            let idx = moves.trailing_zeros();
+            moves &= moves - 1;
+            let card = card_at(idx);
+            // A card next in rank to a lower one from the same hand, with
+            // nothing left between them, wins and loses the same tricks.
+            let below = live & suit_bits(card.suit) & ((1 << idx) - 1);
+            if below != 0 {
+                let lower = 63 - below.leading_zeros();
+                if hand & (1 << lower) != 0 && interchangeable(card, card_at(lower)) {
+                    continue;
                 }
+            }
+            let points = self.value(&node.play(seat, card), alpha, beta)?;
+            if ours {
+                best = best.min(points);
+                beta = beta.min(best);
             } else {
-                // Opponent minimizes their penalties (we assume optimal play)
-                if best_result.is_none()
-                    || result.expected_penalties[to_play.index()]
-                        < best_result.as_ref().unwrap().expected_penalties[to_play.index()]
-                {
-                    best_result = Some(EndgameResult {
-                        best_move: card,
-                        expected_penalties: result.expected_penalties,
-                    });
-                }
+                best = best.max(points);
+                alpha = alpha.max(best);
+            }
+            if alpha >= beta {
+                break;
             }
         }
-
-        if let Some(ref result) = best_result {
-            self.memo.insert((pos.clone(), our_seat), result.clone());
+        if node.trick_len == 0 {
+            let bounds = if best <= alpha_in {
+                (i32::MIN, best)
+            } else if best >= beta_in {
+                (best, i32::MAX)
+            } else {
+                (best, best)
+            };
+            let entry = self.memo.entry(key).or_insert((i32::MIN, i32::MAX));
+            *entry = (entry.0.max(bounds.0), entry.1.min(bounds.1));
         }
-
-        best_result
+        Some(best)
     }
 
-    fn get_legal_moves(&self, pos: &EndgamePosition, seat: PlayerPosition) -> Vec<Card> {
-        let hand = &pos.hands[seat.index()];
-
-        if pos.trick_cards.is_empty() {
-            // Leading - can play anything except hearts if not broken
-            if pos.hearts_broken {
-                hand.clone()
+    /// The least and most the seat can finish with from `node`. A moon is
+    /// only in reach of a seat while no other seat has taken points.
+    fn reach(&self, node: &Node, remaining: u64) -> (i32, i32) {
+        let outstanding = (remaining | node.trick_bits) & self.scoring;
+        let points = ((outstanding & suit_bits(Suit::Hearts)).count_ones()
+            + if outstanding & card_bit(QUEEN_OF_SPADES) != 0 {
+                13
             } else {
-                let non_hearts: Vec<Card> = hand
-                    .iter()
-                    .copied()
-                    .filter(|c| c.suit != hearts_core::model::suit::Suit::Hearts)
-                    .collect();
-                if non_hearts.is_empty() {
-                    hand.clone() // Only hearts left
-                } else {
-                    non_hearts
-                }
-            }
+                0
+            }) as i32;
+        let own = i32::from(node.taken[self.seat.index()]);
+        let bonus = i32::from(JACK_OF_DIAMONDS_BONUS);
+        let jack = if outstanding & card_bit(JACK_OF_DIAMONDS) != 0 {
+            (-bonus, 0)
+        } else if self.rules.jack_of_diamonds && node.jack == self.seat.index() as u8 {
+            (-bonus, -bonus)
         } else {
-            // Following - must follow suit if possible
-            let lead_suit = pos.trick_cards[0].1.suit;
-            let following: Vec<Card> = hand
-                .iter()
-                .copied()
-                .filter(|c| c.suit == lead_suit)
-                .collect();
-            if following.is_empty() {
-                hand.clone() // Can play anything
-            } else {
-                following
+            (0, 0)
+        };
+        let (mut lower, mut upper) = (own + jack.0, own + points + jack.1);
+        let mut scorers = PlayerPosition::LOOP
+            .into_iter()
+            .filter(|seat| node.taken[seat.index()] > 0);
+        let (first, second) = (scorers.next(), scorers.next());
+        if second.is_none() {
+            if first.is_none_or(|seat| seat == self.seat) {
+                lower = lower.min(jack.0);
+            }
+            if first.is_none_or(|seat| seat != self.seat) {
+                upper = upper.max(26);
             }
         }
+        (lower, upper)
     }
 
-    fn apply_move(
-        &self,
-        pos: &EndgamePosition,
-        seat: PlayerPosition,
-        card: Card,
-    ) -> EndgamePosition {
-        let mut new_pos = pos.clone();
-
-        // Remove card from hand
-        new_pos.hands[seat.index()].retain(|&c| c != card);
-
-        // Add to trick
-        new_pos.trick_cards.push((seat, card));
-
-        // Check if trick is complete
-        if new_pos.trick_cards.len() == 4 {
-            // Determine winner and penalties
-            let lead_suit = new_pos.trick_cards[0].1.suit;
-            let mut winner = new_pos.trick_cards[0].0;
-            let mut best_rank = new_pos.trick_cards[0].1.rank;
-
-            for &(player, played_card) in &new_pos.trick_cards[1..] {
-                if played_card.suit == lead_suit && played_card.rank > best_rank {
-                    winner = player;
-                    best_rank = played_card.rank;
-                }
-            }
-
-            // Calculate and accumulate penalties
-            let penalties: u8 = new_pos
-                .trick_cards
-                .iter()
-                .map(|(_, c)| c.penalty_value())
-                .sum();
-
-            // Add penalties to winner's accumulated total
-            new_pos.accumulated_penalties[winner.index()] += penalties;
+    #[cfg(test)]
+    fn stats(&self) -> (usize, usize) {
+        (self.memo.len(), self.nodes_evaluated)
+    }
+}
 
-            // Clear trick and set new leader
-            new_pos.trick_cards.clear();
-            new_pos.leader = winner;
+impl Default for EndgameSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-            // Update hearts broken
-            if !new_pos.hearts_broken {
-                for &(_, c) in &pos.trick_cards {
-                    if c.suit == hearts_core::model::suit::Suit::Hearts {
-                        new_pos.hearts_broken = true;
-                        break;
-                    }
-                }
+/// Up to `ctx.params.endgame_worlds` deals of the unseen cards, with how
+/// many times each distinct deal was drawn.
+fn deal_worlds(ctx: &BotContext<'_>) -> Vec<([u64; 4], u32)> {
+    let mut rng = StdRng::seed_from_u64(position_key(ctx));
+    let mut worlds: Vec<([u64; 4], u32)> = Vec::new();
+    for _ in 0..ctx.params.endgame_worlds.max(1) {
+        let sampled = ctx
+            .tracker
+            .sample_consistent_world(&mut rng, ctx.seat, ctx.round);
+        let hands = PlayerPosition::LOOP.map(|seat| {
+            if seat == ctx.seat {
+                cards_bits(ctx.hand().iter())
+            } else {
+                cards_bits(&sampled.hands[seat.index()])
             }
+        });
+        match worlds.iter_mut().find(|(seen, _)| *seen == hands) {
+            Some((_, count)) => *count += 1,
+            None => worlds.push((hands, 1)),
         }
-
-        new_pos
-    }
-
-    #[allow(dead_code)]
-    pub fn stats(&self) -> (usize, usize) {
-        (self.memo.len(), self.nodes_evaluated)
     }
+    worlds
 }
 
 // ============================================================================
@@ -390,50 +427,21 @@ fn endgame_enabled() -> bool {
         .unwrap_or(true) // ENABLED BY DEFAULT
 }
 
-/// Check if endgame solver should use belief-state sampling
-/// DEFAULT: ENABLED for imperfect information (can be disabled with MDH_ENDGAME_USE_SAMPLING=0)
-fn endgame_use_sampling() -> bool {
-    std::env::var("MDH_ENDGAME_USE_SAMPLING")
-        .map(|v| v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("on"))
-        .unwrap_or(true) // ENABLED BY DEFAULT for proper imperfect information handling
-}
-
-/// Maximum number of cards for endgame perfect play
-/// SearchLookahead difficulty: 13 cards (PERFECT ENDGAME - entire hand!)
-/// Default: 7 cards for strong endgame play
-fn endgame_max_cards(ctx: &BotContext<'_>) -> usize {
-    // PERFECT endgame for SearchLookahead difficulty - entire hand!
-    if matches!(ctx.difficulty, super::BotDifficulty::SearchLookahead) {
-        return std::env::var("MDH_ENDGAME_MAX_CARDS")
-            .ok()
-            .and_then(|s| s.parse::<usize>().ok())
-            .unwrap_or(13) // PERFECT: 13 cards = entire hand for Search difficulty
-            .clamp(2, 13);
-    }
-
-    std::env::var("MDH_ENDGAME_MAX_CARDS")
-        .ok()
-        .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(7) // Normal: 7 cards
-        .clamp(2, 10)
-}
-
 // ============================================================================
 // Integration
 // ============================================================================
 
-use super::PlayPlannerHard;
-
 impl PlayPlannerHard {
     pub fn choose_with_endgame_solver(
         legal: &[Card],
         ctx: &BotContext<'_>,
-        limit: Option<&super::DecisionLimit<'_>>,
+        limit: Option<&DecisionLimit<'_>>,
     ) -> Option<Card> {
         if !endgame_enabled() {
             return None;
         }
 
+        let mut solver = EndgameSolver::new();
         // Check if we have time budget remaining
         if let Some(l) = limit {
             if l.expired() {
@@ -445,9 +453,129 @@ impl PlayPlannerHard {
             {
                 return None;
             }
+            if let Some(deadline) = l.deadline {
+                solver.set_deadline(deadline);
+            }
         }
 
-        let mut solver = EndgameSolver::new();
-        solver.solve(ctx, legal)
+        solver.solve(ctx, legal).map(|solution| solution.card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EndgameSolution, EndgameSolver};
+    use crate::bot::{BotContext, BotDifficulty, PlayPlanner, UnseenTracker};
+    use crate::endgame_export::{EndgameExport, EndgameRehydrate};
+    use hearts_core::model::card::Card;
+    use hearts_core::model::deck::Deck;
+    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::round::RoundState;
+    use hearts_core::model::score::ScoreBoard;
+    use std::time::{Duration, Instant};
+
+    fn solve(json: &str) -> EndgameSolution {
+        let export: EndgameExport = serde_json::from_str(json).expect("valid endgame export");
+        let EndgameRehydrate {
+            round,
+            scores,
+            passing_direction,
+            tracker,
+            next_to_play,
+        } = export.rehydrate().expect("rehydrate endgame");
+        let ctx = BotContext::new(
+            next_to_play,
+            &round,
+            scores,
+            passing_direction,
+            &tracker,
+            BotDifficulty::SearchLookahead,
+        );
+        let legal = round.legal_plays(next_to_play);
+        EndgameSolver::new()
+            .solve(&ctx, &legal)
+            .expect("three cards are within reach")
+    }
+
+    #[test]
+    fn exported_endgames_solve_to_their_known_cards() {
+        for (json, card, points) in [
+            (
+                include_str!("../../tests/fixtures/endgame_1373_north.json"),
+                "KD",
+                8.0,
+            ),
+            (
+                include_str!("../../tests/fixtures/endgame_2120_west.json"),
+                "JS",
+                8.75,
+            ),
+            (
+                include_str!("../../tests/fixtures/endgame_2263_west.json"),
+                "JD",
+                1.5,
+            ),
+            (
+                include_str!("../../tests/fixtures/endgame_2315_west.json"),
+                "10C",
+                1.875,
+            ),
+        ] {
+            let solution = solve(json);
+            assert_eq!(solution.card, card.parse::<Card>().unwrap(), "{solution:?}");
+            assert_eq!(solution.expected_points, points, "{solution:?}");
+        }
+    }
+
+    #[test]
+    fn five_card_decisions_solve_quickly() {
+        let mut round = RoundState::deal(
+            &Deck::shuffled_with_seed(2261),
+            PlayerPosition::North,
+            PassingDirection::Hold,
+        );
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let mut slowest = Duration::ZERO;
+        let mut most_nodes = 0;
+        loop {
+            let trick = round.current_trick();
+            let seat = trick
+                .plays()
+                .last()
+                .map_or(trick.leader(), |play| play.position.next());
+            if round.hand(seat).len() < 5 {
+                break;
+            }
+            let legal = round.legal_plays(seat);
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                ScoreBoard::new(),
+                PassingDirection::Hold,
+                &tracker,
+                BotDifficulty::SearchLookahead,
+            );
+            let card = if round.hand(seat).len() == 5 {
+                let mut solver = EndgameSolver::new();
+                let started = Instant::now();
+                let solution = solver.solve(&ctx, &legal).expect("five cards");
+                slowest = slowest.max(started.elapsed());
+                most_nodes = most_nodes.max(solver.stats().1);
+                assert!(legal.contains(&solution.card));
+                solution.card
+            } else {
+                PlayPlanner::choose(&legal, &ctx).expect("a legal card")
+            };
+            round.play_card(seat, card).unwrap();
+            tracker.observe_latest_play(&round);
+        }
+        // Five cards a seat, eight worlds, every legal card: well under the
+        // 5ms a decision may take once optimised.
+        assert!(most_nodes < 40_000, "{most_nodes} nodes");
+        if !cfg!(debug_assertions) {
+            assert!(slowest < Duration::from_millis(5), "{slowest:?}");
+        }
     }
 }
@@ -0,0 +1,216 @@
+//! Opt-in memo of play decisions for batch runs. When the same agent meets an
+//! identical position again (the same deal replayed with another seat mix, up
+//! to the first diverging play), the cached card is returned instead of
+//! searching again.
+//!
+//! A cached answer is only correct for a deterministic agent: Normal always
+//! is, while Hard and Search are only with `MDH_HARD_DETERMINISTIC` set.
+//! Callers check `memo_allowed` before using the memo and never store a
+//! decision that ran out of think time.
+
+use super::{BotContext, BotDifficulty, card_sort_key};
+use hearts_core::model::card::Card;
+use hearts_core::model::player::PlayerPosition;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemoKey {
+    agent: BotDifficulty,
+    position: u64,
+    /// The deciding seat's hand, kept in full so a hash collision alone can
+    /// never return a card the seat does not hold.
+    hand: Vec<Card>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DecisionMemo {
+    entries: HashMap<MemoKey, Card>,
+    stats: MemoStats,
+}
+
+impl DecisionMemo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached card for `key`, counting the lookup as a hit or miss.
+    pub fn lookup(&mut self, key: &MemoKey) -> Option<Card> {
+        let found = self.entries.get(key).copied();
+        if found.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+        found
+    }
+
+    pub fn insert(&mut self, key: MemoKey, card: Card) {
+        self.entries.insert(key, card);
+    }
+
+    /// Takes in the entries and counts of a memo kept by another worker.
+    pub fn absorb(&mut self, other: DecisionMemo) {
+        self.entries.extend(other.entries);
+        self.stats.hits += other.stats.hits;
+        self.stats.misses += other.stats.misses;
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+}
+
+/// Whether decisions by `difficulty` are reproducible, so a cached answer
+/// matches what the planner would compute now.
+pub fn memo_allowed(difficulty: BotDifficulty) -> bool {
+    match difficulty {
+        BotDifficulty::EasyLegacy | BotDifficulty::NormalHeuristic => true,
+        BotDifficulty::Rollout => super::BotParams::from_env().rollout_budget_ms == 0,
+        BotDifficulty::FutureHard | BotDifficulty::SearchLookahead => {
+            std::env::var("MDH_HARD_DETERMINISTIC")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("on"))
+                .unwrap_or(false)
+        }
+    }
+}
+
+/// Key for the decision `ctx.seat` faces. The position hash covers everything
+/// the planners read: all hands, the tricks so far, scores, passing direction
+/// and the tracker's moon states.
+pub fn memo_key(ctx: &BotContext<'_>) -> MemoKey {
+    let mut hasher = DefaultHasher::new();
+    ctx.seat.hash(&mut hasher);
+    ctx.passing_direction.as_str().hash(&mut hasher);
+    ctx.scores.standings().hash(&mut hasher);
+    ctx.target_score.hash(&mut hasher);
+    for seat in PlayerPosition::LOOP {
+        ctx.round.hand(seat).cards().hash(&mut hasher);
+        ctx.tracker.moon_state(seat).hash(&mut hasher);
+    }
+    for trick in ctx
+        .round
+        .trick_history()
+        .iter()
+        .chain(std::iter::once(ctx.round.current_trick()))
+    {
+        trick.leader().hash(&mut hasher);
+        for play in trick.plays() {
+            play.position.hash(&mut hasher);
+            play.card.hash(&mut hasher);
+        }
+    }
+    let mut hand = ctx.hand().cards().to_vec();
+    hand.sort_by_key(|card| card_sort_key(*card));
+    MemoKey {
+        agent: ctx.difficulty,
+        position: hasher.finish(),
+        hand,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecisionMemo, memo_allowed, memo_key};
+    use crate::bot::{BotContext, BotDifficulty, UnseenTracker};
+    use hearts_core::model::card::Card;
+    use hearts_core::model::hand::Hand;
+    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::rank::Rank;
+    use hearts_core::model::round::{RoundPhase, RoundState};
+    use hearts_core::model::score::ScoreBoard;
+    use hearts_core::model::suit::Suit;
+
+    fn round() -> RoundState {
+        let hands = [
+            Hand::with_cards(vec![Card::new(Rank::Two, Suit::Clubs)]),
+            Hand::with_cards(vec![Card::new(Rank::Three, Suit::Clubs)]),
+            Hand::with_cards(vec![Card::new(Rank::Four, Suit::Clubs)]),
+            Hand::with_cards(vec![Card::new(Rank::Five, Suit::Clubs)]),
+        ];
+        RoundState::from_hands(
+            hands,
+            PlayerPosition::North,
+            PassingDirection::Hold,
+            RoundPhase::Playing,
+        )
+    }
+
+    #[test]
+    fn identical_positions_share_a_key_per_agent() {
+        let round = round();
+        let tracker = UnseenTracker::new();
+        let ctx = |difficulty| {
+            BotContext::new(
+                PlayerPosition::North,
+                &round,
+                ScoreBoard::new(),
+                PassingDirection::Hold,
+                &tracker,
+                difficulty,
+            )
+        };
+        let normal = memo_key(&ctx(BotDifficulty::NormalHeuristic));
+        assert_eq!(normal, memo_key(&ctx(BotDifficulty::NormalHeuristic)));
+        assert_ne!(normal, memo_key(&ctx(BotDifficulty::FutureHard)));
+
+        let mut scores = ScoreBoard::new();
+        scores.add_penalty(PlayerPosition::East, 5);
+        let shifted = BotContext::new(
+            PlayerPosition::North,
+            &round,
+            scores,
+            PassingDirection::Hold,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert_ne!(normal, memo_key(&shifted));
+    }
+
+    #[test]
+    fn lookups_count_hits_and_misses() {
+        let round = round();
+        let tracker = UnseenTracker::new();
+        let ctx = BotContext::new(
+            PlayerPosition::North,
+            &round,
+            ScoreBoard::new(),
+            PassingDirection::Hold,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let key = memo_key(&ctx);
+        let mut memo = DecisionMemo::new();
+        assert_eq!(memo.lookup(&key), None);
+        memo.insert(key.clone(), Card::new(Rank::Two, Suit::Clubs));
+        assert_eq!(memo.lookup(&key), Some(Card::new(Rank::Two, Suit::Clubs)));
+        assert_eq!((memo.stats().hits, memo.stats().misses), (1, 1));
+        assert_eq!(memo.stats().hit_rate(), Some(0.5));
+        assert_eq!(memo.entry_count(), 1);
+    }
+
+    #[test]
+    fn heuristic_agents_are_always_memoizable() {
+        assert!(memo_allowed(BotDifficulty::EasyLegacy));
+        assert!(memo_allowed(BotDifficulty::NormalHeuristic));
+    }
+}
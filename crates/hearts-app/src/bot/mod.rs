@@ -1,38 +1,49 @@
 mod adviser;
+pub mod analysis;
 mod endgame;
+mod memo;
 mod pass;
 pub(crate) mod play;
+pub mod policy;
+mod rollout;
 pub mod scoring;
 pub mod search;
 pub mod search_deep;
 mod tracker;
+mod trick_view;
 
-pub use adviser::play_bias;
-pub use pass::PassPlanner;
-pub use play::{PlayPlanner, debug_weights_string};
+pub use adviser::{briefing, play_bias};
+pub use memo::{DecisionMemo, memo_allowed, memo_key};
+pub use pass::{PassExplanation, PassPlanner, PassWeights, block_shooter_urgency};
+pub use play::{PlayPlanner, WeightSet, debug_weights_string, with_weight_set};
+pub use rollout::RolloutPolicy;
 pub use search::{PlayPlannerHard, debug_hard_weights_string};
 #[allow(unused_imports)]
-pub use tracker::{MoonState, SampledWorld, UnseenTracker};
+pub use tracker::{MatchMemory, MoonState, SampledWorld, UnseenTracker, match_memory_enabled};
+pub use trick_view::TrickView;
 
 use hearts_core::model::card::Card;
 use hearts_core::model::hand::Hand;
+use hearts_core::model::hand_profile::HandProfile;
 use hearts_core::model::passing::PassingDirection;
 use hearts_core::model::player::PlayerPosition;
-use hearts_core::model::rank::Rank;
 use hearts_core::model::round::RoundState;
+use hearts_core::model::rules::DEFAULT_TARGET_SCORE;
 use hearts_core::model::score::ScoreBoard;
 use hearts_core::model::suit::Suit;
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum BotDifficulty {
     EasyLegacy,
     #[default]
     NormalHeuristic,
     FutureHard,
     SearchLookahead,
+    /// Monte Carlo rollouts over sampled worlds; see [`RolloutPolicy`].
+    Rollout,
 }
 
 impl BotDifficulty {
@@ -48,6 +59,7 @@ impl BotDifficulty {
                 "future" => BotDifficulty::FutureHard,
                 "search" => BotDifficulty::SearchLookahead,
                 "lookahead" => BotDifficulty::SearchLookahead,
+                "rollout" => BotDifficulty::Rollout,
                 _ => BotDifficulty::default(),
             },
             Err(_) => BotDifficulty::default(),
@@ -62,6 +74,108 @@ pub enum BotStyle {
     HuntLeader,
 }
 
+/// Score thresholds of the style choice, each a percentage of the match
+/// target so a short game reaches them as early, relatively, as the
+/// standard one. The defaults are the thresholds at target 100. The last
+/// three tune what the match memory changes, and do nothing while it is off.
+/// The rollout fields size [`RolloutPolicy`] and are read from
+/// `MDH_ROLLOUT_WORLDS` and `MDH_ROLLOUT_BUDGET_MS` when set, and the
+/// endgame fields size the exact endgame solver, from `MDH_ENDGAME_MAX_CARDS`
+/// and `MDH_ENDGAME_WORLDS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BotParams {
+    /// FutureHard hunts a leader who has reached this share (80).
+    pub hunt_leader_hard_pct: u32,
+    /// Every difficulty hunts a leader who has reached this share (90).
+    pub hunt_leader_pct: u32,
+    /// No moon attempt once the seat's own total reaches this share (70).
+    pub moon_abort_pct: u32,
+    /// A seat leading alone by this share makes no moon attempt (25).
+    pub safe_lead_pct: u32,
+    /// A total at this share is one bad round from ending the match: the
+    /// planners shed penalties at it, feed a leader at it and search harder
+    /// (90).
+    pub near_target_pct: u32,
+    /// The seat avoids taking points harder once its own total reaches this
+    /// share (85).
+    pub self_danger_pct: u32,
+    /// From this share of its own, the seat passes its penalty cards away
+    /// and Search looks wider (75).
+    pub self_caution_pct: u32,
+    /// A leader at this share makes the game late: moon threats count for
+    /// more in play, passing and search (80).
+    pub late_game_pct: u32,
+    /// Under moon pressure, the penalty for capturing grows with each point
+    /// the leader has beyond this share (70).
+    pub leader_pressure_pct: u32,
+    /// Shooter pressure added to a seat's passes after it suffers a moon (20).
+    pub moon_shock_boost: i32,
+    /// Rounds the boost lasts (2).
+    pub moon_shock_rounds: u32,
+    /// Worlds the rollout policy samples per decision (8).
+    pub rollout_worlds: u32,
+    /// Milliseconds a rollout decision may take, 0 for no limit (0). A
+    /// limit makes the choice depend on machine speed.
+    pub rollout_budget_ms: u32,
+    /// The hard planners solve the rest of the round exactly once the seat
+    /// holds this many cards or fewer (5, at most 8).
+    pub endgame_cards: u32,
+    /// Worlds the endgame solver deals per decision (8).
+    pub endgame_worlds: u32,
+}
+
+impl BotParams {
+    pub const DEFAULT: BotParams = BotParams {
+        hunt_leader_hard_pct: 80,
+        hunt_leader_pct: 90,
+        moon_abort_pct: 70,
+        safe_lead_pct: 25,
+        near_target_pct: 90,
+        self_danger_pct: 85,
+        self_caution_pct: 75,
+        late_game_pct: 80,
+        leader_pressure_pct: 70,
+        moon_shock_boost: 20,
+        moon_shock_rounds: 2,
+        rollout_worlds: 8,
+        rollout_budget_ms: 0,
+        endgame_cards: 5,
+        endgame_worlds: 8,
+    };
+
+    /// [`Self::DEFAULT`] with the rollout and endgame fields overridden from
+    /// the environment.
+    pub fn from_env() -> Self {
+        static CACHED: OnceLock<BotParams> = OnceLock::new();
+        *CACHED.get_or_init(|| {
+            let read = |name: &str, default: u32| {
+                std::env::var(name)
+                    .ok()
+                    .and_then(|raw| raw.trim().parse::<u32>().ok())
+                    .unwrap_or(default)
+            };
+            BotParams {
+                rollout_worlds: read("MDH_ROLLOUT_WORLDS", Self::DEFAULT.rollout_worlds).max(1),
+                rollout_budget_ms: read("MDH_ROLLOUT_BUDGET_MS", Self::DEFAULT.rollout_budget_ms),
+                endgame_cards: read("MDH_ENDGAME_MAX_CARDS", Self::DEFAULT.endgame_cards).min(8),
+                endgame_worlds: read("MDH_ENDGAME_WORLDS", Self::DEFAULT.endgame_worlds).max(1),
+                ..Self::DEFAULT
+            }
+        })
+    }
+
+    /// `pct` percent of `target`, rounded down.
+    pub const fn threshold(pct: u32, target: u32) -> u32 {
+        target * pct / 100
+    }
+}
+
+impl Default for BotParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ScoreSnapshot {
     pub min_score: u32,
@@ -80,6 +194,13 @@ pub struct BotContext<'a> {
     pub tracker: &'a UnseenTracker,
     pub difficulty: BotDifficulty,
     pub controller_bias_delta: Option<i32>,
+    /// Profile of the seat's hand, computed once when the context is built.
+    pub hand_profile: HandProfile,
+    /// The score that ends the match.
+    pub target_score: u32,
+    /// The Omnibus rule is on: taking J♦ is worth 10 points.
+    pub jack_of_diamonds: bool,
+    pub params: BotParams,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -134,9 +255,23 @@ impl<'a> BotContext<'a> {
             tracker,
             difficulty,
             controller_bias_delta: None,
+            hand_profile: HandProfile::from_hand(round.hand(seat)),
+            target_score: DEFAULT_TARGET_SCORE,
+            jack_of_diamonds: false,
+            params: BotParams::from_env(),
         }
     }
 
+    pub fn with_target_score(mut self, target: u32) -> Self {
+        self.target_score = target;
+        self
+    }
+
+    pub fn with_jack_of_diamonds(mut self, enabled: bool) -> Self {
+        self.jack_of_diamonds = enabled;
+        self
+    }
+
     pub fn with_controller_bias_delta(mut self, delta: Option<i32>) -> Self {
         self.controller_bias_delta = delta;
         self
@@ -149,30 +284,54 @@ impl<'a> BotContext<'a> {
     pub fn cards_played(&self) -> usize {
         52usize.saturating_sub(self.tracker.unseen_count())
     }
+
+    pub fn trick_view(&self) -> TrickView<'a> {
+        TrickView::new(self.round.current_trick())
+    }
+
+    /// `pct` percent of this match's target score.
+    pub fn threshold(&self, pct: u32) -> u32 {
+        BotParams::threshold(pct, self.target_score)
+    }
 }
 
 pub(crate) fn determine_style(ctx: &BotContext<'_>) -> BotStyle {
     let snapshot = snapshot_scores(ctx.scores);
     let my_score = ctx.scores.score(ctx.seat);
-    let hand = ctx.hand();
 
     // Persisted moon attempt
     if ctx.tracker.moon_state(ctx.seat) == MoonState::Committed {
         return BotStyle::AggressiveMoon;
     }
 
-    if should_try_shoot_moon(hand, my_score, snapshot.min_score, ctx.cards_played()) {
+    // A comfortable leader has more to lose than to gain from a failed shot.
+    let safe_lead = ctx
+        .tracker
+        .match_memory()
+        .lead_margin(ctx.seat)
+        .is_some_and(|margin| margin >= ctx.threshold(ctx.params.safe_lead_pct));
+    if !safe_lead
+        && should_try_shoot_moon(
+            &ctx.hand_profile,
+            my_score,
+            snapshot.min_score,
+            ctx.cards_played(),
+            ctx.threshold(ctx.params.moon_abort_pct),
+        )
+    {
         return BotStyle::AggressiveMoon;
     }
 
     if matches!(ctx.difficulty, BotDifficulty::FutureHard)
-        && snapshot.max_score >= 80
+        && snapshot.max_score >= ctx.threshold(ctx.params.hunt_leader_hard_pct)
         && snapshot.max_player != ctx.seat
     {
         return BotStyle::HuntLeader;
     }
 
-    if snapshot.max_score >= 90 && snapshot.max_player != ctx.seat {
+    if snapshot.max_score >= ctx.threshold(ctx.params.hunt_leader_pct)
+        && snapshot.max_player != ctx.seat
+    {
         return BotStyle::HuntLeader;
     }
 
@@ -180,36 +339,23 @@ pub(crate) fn determine_style(ctx: &BotContext<'_>) -> BotStyle {
 }
 
 fn should_try_shoot_moon(
-    hand: &Hand,
+    profile: &HandProfile,
     my_score: u32,
     leader_score: u32,
     cards_played: usize,
+    abort_score: u32,
 ) -> bool {
     if cards_played > 12 {
         return false;
     }
-    if my_score >= 70 {
+    if my_score >= abort_score {
         return false;
     }
     if my_score > leader_score + 15 {
         return false;
     }
 
-    let hearts = count_cards_in_suit(hand, Suit::Hearts);
-    if hearts < 7 {
-        return false;
-    }
-
-    let control_hearts = hand
-        .iter()
-        .filter(|card| card.suit == Suit::Hearts && card.rank >= Rank::Ten)
-        .count();
-    let high_spades = hand
-        .iter()
-        .filter(|card| card.suit == Suit::Spades && card.rank >= Rank::Queen)
-        .count();
-    let has_ace_spades = hand.contains(Card::new(Rank::Ace, Suit::Spades));
-    control_hearts >= 4 && high_spades >= 2 && has_ace_spades
+    profile.moon_shape()
 }
 
 pub(crate) fn snapshot_scores(scores: ScoreBoard) -> ScoreSnapshot {
@@ -253,7 +399,7 @@ pub(crate) fn snapshot_scores(scores: ScoreBoard) -> ScoreSnapshot {
 }
 
 pub(crate) fn detect_moon_pressure(ctx: &BotContext<'_>, snapshot: &ScoreSnapshot) -> bool {
-    let leader_near_moon = snapshot.max_score >= 80;
+    let leader_near_moon = snapshot.max_score >= ctx.threshold(ctx.params.late_game_pct);
     let trick_penalty = ctx.round.current_trick().penalty_total() >= 13;
     let others_committed = PlayerPosition::LOOP.iter().copied().any(|seat| {
         seat != ctx.seat
@@ -265,6 +411,31 @@ pub(crate) fn detect_moon_pressure(ctx: &BotContext<'_>, snapshot: &ScoreSnapsho
     leader_near_moon || trick_penalty || others_committed
 }
 
+/// The single seat that has captured every point so far this round, if any.
+pub(crate) fn only_point_taker(round: &RoundState) -> Option<PlayerPosition> {
+    let totals = round.penalty_totals();
+    let mut takers = PlayerPosition::LOOP
+        .iter()
+        .copied()
+        .filter(|seat| totals[seat.index()] > 0);
+    let taker = takers.next()?;
+    if takers.next().is_some() {
+        return None;
+    }
+    Some(taker)
+}
+
+/// Hearts that have not yet been captured in a completed trick.
+pub(crate) fn hearts_remaining(round: &RoundState) -> usize {
+    let captured = round
+        .trick_history()
+        .iter()
+        .flat_map(|trick| trick.plays().iter())
+        .filter(|play| play.card.suit == Suit::Hearts)
+        .count();
+    13usize.saturating_sub(captured)
+}
+
 pub(crate) fn count_cards_in_suit(hand: &Hand, suit: Suit) -> usize {
     hand.iter().filter(|card| card.suit == suit).count()
 }
@@ -276,6 +447,7 @@ pub(crate) fn card_sort_key(card: Card) -> (u8, u8) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hearts_core::model::rank::Rank;
     use hearts_core::model::round::RoundPhase;
 
     fn build_round(seat: PlayerPosition, hand_cards: &[Card]) -> RoundState {
@@ -479,4 +651,159 @@ mod tests {
         };
         assert_eq!(style_90, BotStyle::HuntLeader);
     }
+
+    #[test]
+    fn style_thresholds_scale_with_a_short_game() {
+        let style_at = |seat: PlayerPosition, hand: &str, totals, difficulty| {
+            let round = build_round(seat, &hearts_core::testkit::cards(hand));
+            let tracker = make_tracker(&round);
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                build_scores(totals),
+                PassingDirection::Hold,
+                &tracker,
+                difficulty,
+            )
+            .with_target_score(50);
+            determine_style(&ctx)
+        };
+        let normal = BotDifficulty::NormalHeuristic;
+        // Hunting the leader starts at 45 of 50, 90% as at 100.
+        let north = PlayerPosition::North;
+        assert_eq!(
+            style_at(north, "AC", [10, 20, 30, 44], normal),
+            BotStyle::Cautious
+        );
+        assert_eq!(
+            style_at(north, "AC", [10, 20, 30, 45], normal),
+            BotStyle::HuntLeader
+        );
+        // FutureHard starts at 40, 80% of the target.
+        let hard = BotDifficulty::FutureHard;
+        assert_eq!(
+            style_at(north, "AC", [10, 20, 30, 39], hard),
+            BotStyle::Cautious
+        );
+        assert_eq!(
+            style_at(north, "AC", [10, 20, 30, 40], hard),
+            BotStyle::HuntLeader
+        );
+        // No moon attempt from 35, 70% of the target.
+        let moon = "AH KH QH JH 10H 9H 8H AS KS 2C 3C 4D 5D";
+        let south = PlayerPosition::South;
+        assert_eq!(
+            style_at(south, moon, [25, 28, 34, 30], normal),
+            BotStyle::AggressiveMoon
+        );
+        assert_eq!(
+            style_at(south, moon, [25, 28, 35, 30], normal),
+            BotStyle::Cautious
+        );
+        // The standard game keeps its absolute thresholds.
+        let round = build_round(north, &hearts_core::testkit::cards("AC"));
+        let tracker = make_tracker(&round);
+        let ctx = BotContext::new(
+            north,
+            &round,
+            build_scores([10, 20, 30, 45]),
+            PassingDirection::Hold,
+            &tracker,
+            normal,
+        );
+        assert_eq!(ctx.threshold(ctx.params.hunt_leader_pct), 90);
+        assert_eq!(determine_style(&ctx), BotStyle::Cautious);
+    }
+
+    #[test]
+    fn a_safe_leader_makes_no_moon_attempt() {
+        let seat = PlayerPosition::South;
+        let moon = "AH KH QH JH 10H 9H 8H AS KS 2C 3C 4D 5D";
+        let round = build_round(seat, &hearts_core::testkit::cards(moon));
+        let mut tracker = make_tracker(&round);
+        let totals = [40, 50, 14, 60];
+        let style = |tracker: &UnseenTracker| {
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                build_scores(totals),
+                PassingDirection::Hold,
+                tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            determine_style(&ctx)
+        };
+        // Without a memory of the match the hand is shot as usual.
+        assert_eq!(style(&tracker), BotStyle::AggressiveMoon);
+        // 26 ahead of the next seat is past a quarter of the target.
+        tracker.note_round_scored([13, 0, 0, 13], totals);
+        assert_eq!(style(&tracker), BotStyle::Cautious);
+        tracker.note_round_scored([0, 0, 0, 0], [40, 50, 16, 60]);
+        assert_eq!(style(&tracker), BotStyle::AggressiveMoon);
+    }
+
+    #[test]
+    fn moon_inputs_move_only_when_the_trick_completes() {
+        use hearts_core::testkit::{RoundBuilder, card, cards, to_play};
+        let mut round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "2C 9S QS 3H")
+            .seat_hand(PlayerPosition::East, "3C KS 4H 8D")
+            .seat_hand(PlayerPosition::South, "4C 5S AH 9D")
+            .seat_hand(PlayerPosition::West, "5C AS 5D KD")
+            .build();
+        let mut tracker = make_tracker(&round);
+        let mut observe = |round: &mut RoundState, played: Card| {
+            let seat = to_play(round);
+            round.play_card(seat, played).unwrap();
+            tracker.note_card_played(seat, played);
+            let likelihood = tracker.belief_state(PlayerPosition::West).moon_likelihood();
+            (only_point_taker(round), hearts_remaining(round), likelihood)
+        };
+        for played in cards("2C 3C 4C 5C AS") {
+            observe(&mut round, played);
+        }
+        let before = observe(&mut round, card("QS"));
+        // Q♠ on the table belongs to nobody until the last card falls.
+        assert_eq!(before.0, None);
+        assert_eq!(observe(&mut round, card("KS")), before);
+        let after = observe(&mut round, card("5S"));
+        assert_eq!(after.0, Some(PlayerPosition::West));
+        assert_eq!((after.1, after.2), (before.1, before.2));
+    }
+
+    #[test]
+    fn hand_profile_matches_direct_counts_on_dealt_hands() {
+        for seed in 0..200u64 {
+            let deck = hearts_core::model::deck::Deck::shuffled_with_seed(seed);
+            let round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Left);
+            for seat in PlayerPosition::LOOP {
+                let hand = round.hand(seat);
+                let profile = HandProfile::from_hand(hand);
+                let hearts = count_cards_in_suit(hand, Suit::Hearts);
+                let control_hearts = hand
+                    .iter()
+                    .filter(|card| card.suit == Suit::Hearts && card.rank >= Rank::Ten)
+                    .count();
+                let high_spades = hand
+                    .iter()
+                    .filter(|card| card.suit == Suit::Spades && card.rank >= Rank::Queen)
+                    .count();
+                let has_ace_spades = hand.contains(Card::new(Rank::Ace, Suit::Spades));
+                for suit in Suit::ALL {
+                    assert_eq!(profile.suit_len(suit), count_cards_in_suit(hand, suit));
+                }
+                assert_eq!(profile.high_hearts(), control_hearts);
+                assert_eq!(profile.high_spades(), high_spades);
+                assert_eq!(
+                    profile.moon_shape(),
+                    hearts >= 7 && control_hearts >= 4 && high_spades >= 2 && has_ace_spades,
+                    "seed {seed} {seat:?}"
+                );
+                assert_eq!(
+                    profile.queen_exposed(),
+                    high_spades > 0 && count_cards_in_suit(hand, Suit::Spades) < 4
+                );
+            }
+        }
+    }
 }
@@ -1,100 +1,477 @@
-use super::{
-    BotContext, BotStyle, card_sort_key, count_cards_in_suit, determine_style, snapshot_scores,
-};
+use super::{BotContext, BotStyle, MoonState, card_sort_key, determine_style, snapshot_scores};
 use crate::debug::debug_enabled;
 use hearts_core::model::card::Card;
 use hearts_core::model::hand::Hand;
+use hearts_core::model::hand_profile::HandProfile;
+use hearts_core::model::passing::{PassCards, PassingDirection};
 use hearts_core::model::rank::Rank;
 use hearts_core::model::suit::Suit;
+use serde::{Serialize, Serializer};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::sync::OnceLock;
 
 pub struct PassPlanner;
 
 impl PassPlanner {
-    pub fn choose(hand: &Hand, ctx: &BotContext<'_>) -> Option<[Card; 3]> {
-        if hand.len() < 3 {
-            return None;
-        }
-
-        let style = determine_style(ctx);
-        let snapshot = snapshot_scores(ctx.scores);
-        let passing_target = ctx.passing_direction.target(ctx.seat);
-        // In Hearts: low score = winning/leading, high score = losing/trailing
-        // "leader" = person with lowest score (winning the game)
-        // "trailing" = person with highest score (losing the game, closer to 100)
-        let passing_to_trailing = passing_target == snapshot.max_player;
-        let passing_to_leader = passing_target == snapshot.min_player;
-        let my_score = ctx.scores.score(ctx.seat);
-
-        let cards: Vec<Card> = hand.iter().copied().collect();
-        let suit_counts = suit_tally(hand);
-
-        let mut best: Option<(i32, [Card; 3], [Card; 3])> = None;
-
-        for i in 0..cards.len() - 2 {
-            for j in i + 1..cards.len() - 1 {
-                for k in j + 1..cards.len() {
-                    let triple = [cards[i], cards[j], cards[k]];
-                    let score = score_pass_set(
-                        &triple,
-                        hand,
-                        ctx,
-                        style,
-                        passing_to_trailing,
-                        passing_to_leader,
-                        my_score,
-                        snapshot,
-                        &suit_counts,
-                    );
-                    let mut ordered = triple;
-                    ordered.sort_by_key(|card| card_sort_key(*card));
-
-                    match &mut best {
-                        None => best = Some((score, triple, ordered)),
-                        Some((best_score, best_triple, best_ordered)) => {
-                            if score > *best_score
-                                || (score == *best_score
-                                    && compare_sorted_triples(&ordered, best_ordered)
-                                        == std::cmp::Ordering::Less)
-                            {
-                                *best_score = score;
-                                *best_triple = triple;
-                                *best_ordered = ordered;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    /// Best pass of the round's pass count (three, or four under the house rule).
+    pub fn choose(hand: &Hand, ctx: &BotContext<'_>) -> Option<PassCards> {
+        let mut session = ScoringSession::new(hand, ctx, pass_weights());
+        let ranked = Self::ranked(hand, &mut session);
+        let best = ranked.first().copied();
 
         if debug_enabled()
-            && let Some((score, picks, _)) = &best
+            && let Some((score, picks)) = &best
         {
+            let cards: Vec<String> = picks.iter().map(|card| card.to_string()).collect();
             eprintln!(
-                "mdhearts: pass best score={} cards=[{}, {}, {}]",
-                score, picks[0], picks[1], picks[2]
+                "mdhearts: pass best score={} cards=[{}] ({} cards scored for {} passes)",
+                score,
+                cards.join(", "),
+                session.card_scorings,
+                ranked.len()
             );
+            for card in picks.iter().copied() {
+                let scored = session.card(card);
+                let detail: Vec<String> = scored
+                    .parts
+                    .iter()
+                    .map(|part| format!("{}={}", part.name, part.delta))
+                    .collect();
+                eprintln!(
+                    "mdhearts: pass {} total={} parts: {}",
+                    card,
+                    scored.total,
+                    detail.join(" ")
+                );
+            }
         }
 
-        best.map(|(_, picks, _)| picks)
+        best.map(|(_, picks)| picks)
+    }
+
+    /// The pass [`choose`](Self::choose) makes under `weights`, with its full
+    /// score breakdown and the `alternatives` next-best passes. The scorer
+    /// reads nothing but its arguments, so rebuilding the hand, context and
+    /// weights of a recorded decision reproduces it exactly.
+    pub fn explain(
+        hand: &Hand,
+        ctx: &BotContext<'_>,
+        weights: PassWeights,
+        alternatives: usize,
+    ) -> Option<PassExplanation> {
+        let mut session = ScoringSession::new(hand, ctx, weights);
+        let ranked = Self::ranked(hand, &mut session);
+        let mut breakdowns = ranked
+            .iter()
+            .take(alternatives.saturating_add(1))
+            .map(|(_, cards)| session.breakdown(*cards))
+            .collect::<Vec<_>>()
+            .into_iter();
+        let chosen = breakdowns.next()?;
+        Some(explanation(
+            &session.input,
+            ranked.len(),
+            chosen,
+            breakdowns.collect(),
+        ))
+    }
+
+    /// [`explain`](Self::explain) over passes already scored with
+    /// [`breakdowns`](Self::breakdowns), all of them and best first.
+    pub(crate) fn explain_ranked(
+        hand: &Hand,
+        ctx: &BotContext<'_>,
+        weights: PassWeights,
+        ranked: Vec<PassBreakdown>,
+        alternatives: usize,
+    ) -> Option<PassExplanation> {
+        let input = PassScoreInput::new(hand, ctx, weights);
+        let candidates = ranked.len();
+        let mut breakdowns = ranked.into_iter().take(alternatives.saturating_add(1));
+        let chosen = breakdowns.next()?;
+        Some(explanation(
+            &input,
+            candidates,
+            chosen,
+            breakdowns.collect(),
+        ))
+    }
+
+    /// Breakdowns of the `limit` best passes, best first.
+    pub(crate) fn breakdowns(
+        hand: &Hand,
+        ctx: &BotContext<'_>,
+        weights: PassWeights,
+        limit: usize,
+    ) -> Vec<PassBreakdown> {
+        let mut session = ScoringSession::new(hand, ctx, weights);
+        Self::ranked(hand, &mut session)
+            .iter()
+            .take(limit)
+            .map(|(_, cards)| session.breakdown(*cards))
+            .collect()
+    }
+
+    /// Every pass of `ctx.round.pass_count()` cards with its score, best first
+    /// in `pass_candidate_order`.
+    #[cfg(test)]
+    fn ranked_candidates(
+        hand: &Hand,
+        ctx: &BotContext<'_>,
+        weights: PassWeights,
+    ) -> Vec<(i32, PassCards)> {
+        Self::ranked(hand, &mut ScoringSession::new(hand, ctx, weights))
+    }
+
+    fn ranked(hand: &Hand, session: &mut ScoringSession<'_>) -> Vec<(i32, PassCards)> {
+        let pass_count = session.input.pass_count;
+        if hand.len() < pass_count {
+            return Vec::new();
+        }
+        let cards: Vec<Card> = hand.iter().copied().collect();
+
+        let mut candidates: Vec<(i32, PassCards)> = pass_combinations(&cards, pass_count)
+            .into_iter()
+            .map(|set| (session.score_set(&set), set))
+            .collect();
+        candidates.sort_by(pass_candidate_order);
+        candidates
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn score_card(
-    card: Card,
-    hand: &Hand,
-    ctx: &BotContext<'_>,
+/// One named adjustment of a pass score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScorePart {
+    pub name: &'static str,
+    pub delta: i32,
+}
+
+impl ScorePart {
+    /// Adjustments that protect against a specific bad pass rather than rate
+    /// the card, reported as guards by the explainer.
+    pub fn is_guard(&self) -> bool {
+        GUARD_PARTS.contains(&self.name)
+    }
+}
+
+const GUARD_PARTS: &[&str] = &[
+    "avoid_qs_to_leader",
+    "to_leader_penalty_avoid",
+    "two_of_clubs_keep",
+    "moon_keep_hearts",
+    "moon_keep_qs",
+    "moon_keep_high_spades",
+    "premium_support_guard",
+    "ten_plus_safety_guard",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CardScore {
+    #[serde(serialize_with = "card_code")]
+    pub card: Card,
+    pub total: i32,
+    pub parts: Vec<ScorePart>,
+}
+
+/// A whole pass: each card's score plus the adjustments that depend on the
+/// cards together.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PassBreakdown {
+    #[serde(serialize_with = "card_codes")]
+    pub cards: PassCards,
+    pub total: i32,
+    pub per_card: Vec<CardScore>,
+    pub set_parts: Vec<ScorePart>,
+}
+
+impl PassBreakdown {
+    /// Guard adjustments on any card, then every set-level adjustment.
+    pub fn guards(&self) -> Vec<String> {
+        let card_guards = self.per_card.iter().flat_map(|scored| {
+            scored
+                .parts
+                .iter()
+                .filter(|part| part.is_guard())
+                .map(move |part| format!("{} on {} ({:+})", part.name, scored.card, part.delta))
+        });
+        let set_guards = self
+            .set_parts
+            .iter()
+            .map(|part| format!("{} ({:+})", part.name, part.delta));
+        card_guards.chain(set_guards).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PassExplanation {
+    pub seat: hearts_core::model::player::PlayerPosition,
+    pub direction: &'static str,
+    pub style: String,
+    pub passing_to_leader: bool,
+    pub passing_to_trailing: bool,
+    pub weights: PassWeights,
+    /// Passes scored.
+    pub candidates: usize,
+    pub chosen: PassBreakdown,
+    pub alternatives: Vec<PassBreakdown>,
+}
+
+fn explanation(
+    input: &PassScoreInput<'_>,
+    candidates: usize,
+    chosen: PassBreakdown,
+    alternatives: Vec<PassBreakdown>,
+) -> PassExplanation {
+    PassExplanation {
+        seat: input.ctx.seat,
+        direction: input.ctx.passing_direction.as_str(),
+        style: format!("{:?}", input.style),
+        passing_to_leader: input.passing_to_leader,
+        passing_to_trailing: input.passing_to_trailing,
+        weights: input.weights,
+        candidates,
+        chosen,
+        alternatives,
+    }
+}
+
+fn card_code<S: Serializer>(card: &Card, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(card)
+}
+
+fn card_codes<S: Serializer>(cards: &PassCards, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(cards.iter().map(|card| card.to_string()))
+}
+
+/// Every `count`-card subset of `cards`, each sorted by `card_sort_key`.
+fn pass_combinations(cards: &[Card], count: usize) -> Vec<PassCards> {
+    let mut out = Vec::new();
+    if count == 0 || count > cards.len() {
+        return out;
+    }
+    let mut idx: Vec<usize> = (0..count).collect();
+    loop {
+        let mut set: Vec<Card> = idx.iter().map(|&i| cards[i]).collect();
+        set.sort_by_key(|card| card_sort_key(*card));
+        out.extend(PassCards::from_slice(&set));
+        // Advance the rightmost index that still has room.
+        let Some(pos) = (0..count)
+            .rev()
+            .find(|&pos| idx[pos] < cards.len() - count + pos)
+        else {
+            return out;
+        };
+        idx[pos] += 1;
+        for next in pos + 1..count {
+            idx[next] = idx[next - 1] + 1;
+        }
+    }
+}
+
+/// Total order for pass candidates: score descending, then the canonical
+/// (suit, rank)-sorted set ascending. Keeps the pick independent of the
+/// order candidates were generated in.
+fn pass_candidate_order(a: &(i32, PassCards), b: &(i32, PassCards)) -> Ordering {
+    b.0.cmp(&a.0).then_with(|| compare_sorted_sets(&a.1, &b.1))
+}
+
+/// Per-decision inputs shared by every card and triple the pass scorer rates.
+#[derive(Clone, Copy)]
+struct PassScoreInput<'a> {
+    ctx: &'a BotContext<'a>,
+    profile: HandProfile,
     style: BotStyle,
     passing_to_trailing: bool,
     passing_to_leader: bool,
     my_score: u32,
     snapshot: super::ScoreSnapshot,
-) -> i32 {
+    pass_count: usize,
+    weights: PassWeights,
+    /// 0-100 estimate that the pass target is setting up a moon shot.
+    shooter_pressure: i32,
+}
+
+/// One pass decision's scorer: its inputs and the card scores every
+/// candidate shares. A card's score reads only the card and the inputs, so
+/// each card is scored once however many passes hold it: 13 scorings for
+/// the 286 three-card passes of a full hand instead of 858.
+struct ScoringSession<'a> {
+    input: PassScoreInput<'a>,
+    cards: HashMap<Card, CardScore>,
+    /// Calls into `score_card_parts` so far.
+    card_scorings: usize,
+}
+
+impl<'a> ScoringSession<'a> {
+    fn new(hand: &Hand, ctx: &'a BotContext<'a>, weights: PassWeights) -> Self {
+        Self {
+            input: PassScoreInput::new(hand, ctx, weights),
+            cards: HashMap::new(),
+            card_scorings: 0,
+        }
+    }
+
+    fn card(&mut self, card: Card) -> &CardScore {
+        let input = &self.input;
+        let scorings = &mut self.card_scorings;
+        self.cards.entry(card).or_insert_with(|| {
+            *scorings += 1;
+            score_card_parts(card, input)
+        })
+    }
+
+    fn score_set(&mut self, cards: &[Card]) -> i32 {
+        let card_total: i32 = cards.iter().map(|&card| self.card(card).total).sum();
+        card_total
+            + set_adjustments(cards, &self.input)
+                .iter()
+                .map(|part| part.delta)
+                .sum::<i32>()
+    }
+
+    fn breakdown(&mut self, cards: PassCards) -> PassBreakdown {
+        let per_card: Vec<CardScore> = cards.iter().map(|&card| self.card(card).clone()).collect();
+        let set_parts = set_adjustments(&cards, &self.input);
+        let total = per_card.iter().map(|scored| scored.total).sum::<i32>()
+            + set_parts.iter().map(|part| part.delta).sum::<i32>();
+        PassBreakdown {
+            cards,
+            total,
+            per_card,
+            set_parts,
+        }
+    }
+}
+
+/// How hard the moon-defense guards push for a pass direction. The seat to
+/// the left plays after us in every trick, so a shooter there is the hardest
+/// to stop; across less so, and the seat to the right always plays before
+/// us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DirectionProfile {
+    /// Shooter pressure at which the guards switch on.
+    shooter_threshold: i32,
+    /// Percentage of the full guard penalty applied.
+    guard_scale: i32,
+}
+
+impl DirectionProfile {
+    fn for_direction(direction: PassingDirection) -> Option<Self> {
+        let (shooter_threshold, guard_scale) = match direction {
+            PassingDirection::Left => (40, 100),
+            PassingDirection::Across => (50, 80),
+            PassingDirection::Right => (70, 60),
+            PassingDirection::Hold => return None,
+        };
+        Some(Self {
+            shooter_threshold,
+            guard_scale,
+        })
+    }
+
+    fn scaled(&self, full: i32) -> i32 {
+        full * self.guard_scale / 100
+    }
+}
+
+/// Moon threat from the seat the pass goes to: its tracked moon state, its
+/// belief moon likelihood, and a late game where a shot by anyone but the
+/// trailing seat could end the match on that seat.
+fn shooter_pressure(ctx: &BotContext<'_>, snapshot: &super::ScoreSnapshot) -> i32 {
+    if !ctx.passing_direction.requires_selection() {
+        return 0;
+    }
+    let target = ctx.passing_direction.target(ctx.seat);
+    let state = match ctx.tracker.moon_state(target) {
+        MoonState::Committed => 100,
+        MoonState::Considering => 60,
+        MoonState::Inactive => 0,
+    };
+    let belief = (ctx.tracker.belief_state(target).moon_likelihood() * 100.0).round() as i32;
+    let late_game = if snapshot.max_score >= ctx.threshold(ctx.params.late_game_pct)
+        && snapshot.max_player != target
+    {
+        50
+    } else {
+        0
+    };
+    // A seat that was just shot against guards its next passes harder.
+    let shock = if ctx
+        .tracker
+        .match_memory()
+        .suffered_moon_within(ctx.seat, ctx.params.moon_shock_rounds)
+    {
+        ctx.params.moon_shock_boost
+    } else {
+        0
+    };
+    state.max(belief).max(late_game) + shock
+}
+
+/// Shooter pressure on `ctx.seat`'s pass when it is high enough to switch
+/// the moon-defense guards on, or `None` when the pass is an ordinary one.
+pub fn block_shooter_urgency(ctx: &BotContext<'_>) -> Option<u8> {
+    let direction = DirectionProfile::for_direction(ctx.passing_direction)?;
+    if matches!(determine_style(ctx), BotStyle::AggressiveMoon) {
+        return None;
+    }
+    let pressure = shooter_pressure(ctx, &snapshot_scores(ctx.scores));
+    (pressure >= direction.shooter_threshold).then(|| pressure.clamp(0, 100) as u8)
+}
+
+impl<'a> PassScoreInput<'a> {
+    fn new(hand: &Hand, ctx: &'a BotContext<'a>, weights: PassWeights) -> Self {
+        let snapshot = snapshot_scores(ctx.scores);
+        let passing_target = ctx.passing_direction.target(ctx.seat);
+        // In Hearts: low score = winning/leading, high score = losing/trailing
+        // "leader" = person with lowest score (winning the game)
+        // "trailing" = person with highest score (losing the game, closer to 100)
+        Self {
+            ctx,
+            profile: HandProfile::from_hand(hand),
+            style: determine_style(ctx),
+            passing_to_trailing: passing_target == snapshot.max_player,
+            passing_to_leader: passing_target == snapshot.min_player,
+            my_score: ctx.scores.score(ctx.seat),
+            snapshot,
+            pass_count: ctx.round.pass_count(),
+            weights,
+            shooter_pressure: shooter_pressure(ctx, &snapshot),
+        }
+    }
+
+    /// The direction's guard profile when the moon-defense guards are on for
+    /// this pass.
+    fn moon_guards(&self) -> Option<DirectionProfile> {
+        DirectionProfile::for_direction(self.ctx.passing_direction).filter(|direction| {
+            self.shooter_pressure >= direction.shooter_threshold
+                && !matches!(self.style, BotStyle::AggressiveMoon)
+        })
+    }
+}
+
+#[cfg(test)]
+fn score_card(card: Card, input: &PassScoreInput<'_>) -> i32 {
+    score_card_parts(card, input).total
+}
+
+fn score_card_parts(card: Card, input: &PassScoreInput<'_>) -> CardScore {
+    let PassScoreInput {
+        ctx,
+        profile,
+        style,
+        passing_to_trailing,
+        passing_to_leader,
+        my_score,
+        snapshot,
+        pass_count,
+        weights,
+        ..
+    } = *input;
     let mut score: i32 = 0;
     let mut parts: Vec<(&'static str, i32)> = Vec::new();
-    let suit_len = count_cards_in_suit(hand, card.suit);
+    let suit_len = profile.suit_len(card.suit);
     let rank_value = card.rank.value() as i32;
     let card_penalty = card.penalty_value() as i32;
 
@@ -103,6 +480,12 @@ fn score_card(
         parts.push(("qs_priority", 18_000));
     }
 
+    // Under the Omnibus rule J♦ is a bonus to keep, not a neutral discard.
+    if ctx.jack_of_diamonds && card.is_jack_of_diamonds() {
+        score -= 8_000;
+        parts.push(("jack_of_diamonds_keep", -8000));
+    }
+
     if card.suit == Suit::Spades && !matches!(style, BotStyle::AggressiveMoon) {
         match card.rank {
             Rank::Ace => {
@@ -135,7 +518,8 @@ fn score_card(
         parts.push(("high_rank_offsuit", d));
     }
 
-    if suit_len <= 2 {
+    // Suits the pass can empty outright.
+    if suit_len < pass_count {
         let d = 4_000 - (suit_len as i32 * 800);
         score += d;
         parts.push(("short_suit_void", d));
@@ -152,7 +536,7 @@ fn score_card(
     }
 
     if passing_to_leader {
-        let d = -(card_penalty * pass_weights().to_leader_penalty);
+        let d = -(card_penalty * weights.to_leader_penalty);
         score += d;
         parts.push(("to_leader_penalty_avoid", d));
         if card.is_queen_of_spades() {
@@ -162,7 +546,7 @@ fn score_card(
         }
     }
 
-    if my_score >= 75 {
+    if my_score >= ctx.threshold(ctx.params.self_caution_pct) {
         let d = card_penalty * 1_600;
         score += d;
         parts.push(("high_self_score_shed", d));
@@ -173,6 +557,29 @@ fn score_card(
         parts.push(("unseen_bonus", 90));
     }
 
+    // Moon defense: keep the cards that stop the target from running the
+    // table when it looks like a shooter.
+    if let Some(direction) = input.moon_guards() {
+        // A premium heart with no other high heart behind it is our only
+        // stopper, and in the target's hand it is a winner.
+        let high_heart_support = ctx
+            .hand()
+            .iter()
+            .filter(|c| c.suit == Suit::Hearts && c.rank >= Rank::Ten && **c != card)
+            .count();
+        if card.suit == Suit::Hearts && card.rank >= Rank::King && high_heart_support <= 1 {
+            let d = -direction.scaled(12_000);
+            score += d;
+            parts.push(("premium_support_guard", d));
+        }
+        // Ten-plus minor-suit cards hand the shooter a way back into the lead.
+        if matches!(card.suit, Suit::Clubs | Suit::Diamonds) && card.rank >= Rank::Ten {
+            let d = -direction.scaled((rank_value - 9) * 1_200);
+            score += d;
+            parts.push(("ten_plus_safety_guard", d));
+        }
+    }
+
     let two_of_clubs = Card::new(Rank::Two, Suit::Clubs);
     if card == two_of_clubs {
         score -= 4_000;
@@ -226,114 +633,164 @@ fn score_card(
         score += d;
         parts.push(("leader_high_rank_bias", d));
     }
-    if debug_enabled() {
-        let mut detail = String::new();
-        use std::fmt::Write as _;
-        for (name, delta) in &parts {
-            let _ = write!(&mut detail, " {}={}", name, delta);
-        }
-        eprintln!("mdhearts: pass {} total={} parts:{}", card, score, detail);
+    CardScore {
+        card,
+        total: score,
+        parts: parts
+            .into_iter()
+            .filter(|(_, delta)| *delta != 0)
+            .map(|(name, delta)| ScorePart { name, delta })
+            .collect(),
     }
-    score
 }
 
-fn suit_tally(hand: &Hand) -> [usize; 4] {
-    let mut counts = [0usize; 4];
-    for card in hand.iter() {
-        counts[suit_index(card.suit)] += 1;
-    }
-    counts
+fn compare_sorted_sets(a: &[Card], b: &[Card]) -> Ordering {
+    a.iter()
+        .map(|card| card_sort_key(*card))
+        .cmp(b.iter().map(|card| card_sort_key(*card)))
 }
 
-fn suit_index(suit: Suit) -> usize {
-    match suit {
-        Suit::Clubs => 0,
-        Suit::Diamonds => 1,
-        Suit::Hearts => 2,
-        Suit::Spades => 3,
-    }
-}
-
-fn compare_sorted_triples(a: &[Card; 3], b: &[Card; 3]) -> Ordering {
-    for idx in 0..3 {
-        let key_a = card_sort_key(a[idx]);
-        let key_b = card_sort_key(b[idx]);
-        match key_a.cmp(&key_b) {
-            Ordering::Equal => continue,
-            other => return other,
-        }
-    }
-    Ordering::Equal
+/// A pass's score without a [`ScoringSession`], every card scored afresh.
+#[cfg(test)]
+fn score_pass_set(cards: &[Card], input: &PassScoreInput<'_>) -> i32 {
+    let card_total: i32 = cards
+        .iter()
+        .map(|card| score_card_parts(*card, input).total)
+        .sum();
+    card_total
+        + set_adjustments(cards, input)
+            .iter()
+            .map(|part| part.delta)
+            .sum::<i32>()
 }
 
-#[allow(clippy::too_many_arguments)]
-fn score_pass_set(
-    triple: &[Card; 3],
-    hand: &Hand,
-    ctx: &BotContext<'_>,
-    style: BotStyle,
-    passing_to_trailing: bool,
-    passing_to_leader: bool,
-    my_score: u32,
-    snapshot: super::ScoreSnapshot,
-    suit_counts: &[usize; 4],
-) -> i32 {
-    let mut total = 0;
-    for card in triple.iter().copied() {
-        total += score_card(
-            card,
-            hand,
-            ctx,
-            style,
-            passing_to_trailing,
-            passing_to_leader,
-            my_score,
-            snapshot,
-        );
-    }
-
-    let mut removed = [0usize; 4];
-    for card in triple.iter() {
-        removed[suit_index(card.suit)] += 1;
-    }
-
-    for (idx, removed_count) in removed.iter().enumerate() {
-        if *removed_count > 0 && suit_counts[idx] == *removed_count {
-            total += 1_800;
+/// Adjustments that depend on the passed cards together.
+fn set_adjustments(cards: &[Card], input: &PassScoreInput<'_>) -> Vec<ScorePart> {
+    let mut parts = Vec::new();
+    for suit in Suit::ALL {
+        let removed = cards.iter().filter(|card| card.suit == suit).count();
+        if removed > 0 && input.profile.suit_len(suit) == removed {
+            parts.push(ScorePart {
+                name: "voids_suit",
+                delta: 1_800,
+            });
         }
     }
 
-    let high_club_count = triple
+    let high_club_count = cards
         .iter()
         .filter(|card| card.suit == Suit::Clubs && card.rank >= Rank::Queen)
         .count();
     if high_club_count >= 2 {
-        total += 6_000 + (high_club_count as i32 * 800);
+        parts.push(ScorePart {
+            name: "high_clubs_pair",
+            delta: 6_000 + (high_club_count as i32 * 800),
+        });
     }
 
-    if triple
+    if cards
         .iter()
         .any(|card| card.suit == Suit::Hearts && card.penalty_value() > 0)
-        && passing_to_trailing
+        && input.passing_to_trailing
     {
-        total += 1_200;
+        parts.push(ScorePart {
+            name: "hearts_to_trailing",
+            delta: 1_200,
+        });
     }
 
-    total
+    parts.extend(short_hearts_plan(cards, input));
+    parts
 }
 
-struct PassWeights {
-    to_leader_penalty: i32,
+/// Hands with at most this many hearts get a plan for them.
+const SHORT_HEARTS: usize = 2;
+
+/// With two hearts or fewer, the heart guards have little to protect, so the
+/// pass picks a plan for them. Passing right or across, nobody behind us
+/// sees the void coming: shed every heart and discard penalties from the
+/// first heart lead. Passing left, the receiver plays after us all hand, so
+/// keep one low heart instead, to lead once hearts are broken and hand the
+/// lead away. Moon hands keep their hearts, and a void is not worth handing
+/// a suspected shooter the hearts.
+fn short_hearts_plan(cards: &[Card], input: &PassScoreInput<'_>) -> Option<ScorePart> {
+    let held = input.profile.suit_len(Suit::Hearts);
+    if held == 0 || held > SHORT_HEARTS || matches!(input.style, BotStyle::AggressiveMoon) {
+        return None;
+    }
+    let passed = cards
+        .iter()
+        .filter(|card| card.suit == Suit::Hearts)
+        .count();
+    match input.ctx.passing_direction {
+        PassingDirection::Right | PassingDirection::Across
+            if passed == held && input.moon_guards().is_none() =>
+        {
+            Some(ScorePart {
+                name: "hearts_void_plan",
+                delta: input.weights.hearts_void_plan,
+            })
+        }
+        PassingDirection::Left if passed + 1 == held => {
+            let kept = input
+                .ctx
+                .hand()
+                .iter()
+                .find(|card| card.suit == Suit::Hearts && !cards.contains(card))?;
+            (kept.rank <= Rank::Four).then_some(ScorePart {
+                name: "heartbreak_keep",
+                delta: input.weights.heartbreak_keep,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Tunable pass weights. Read from the environment once per process by
+/// [`PassPlanner::choose`] and handed to the scorer, which never reads it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PassWeights {
+    /// `MDH_W_PASS_TO_LEADER_PENALTY`: per penalty point passed to the leader.
+    pub to_leader_penalty: i32,
+    /// `MDH_W_PASS_HEARTS_VOID_PLAN`: for passing every heart of a short
+    /// holding right or across.
+    pub hearts_void_plan: i32,
+    /// `MDH_W_PASS_HEARTBREAK_KEEP`: for keeping a single low heart of a short
+    /// holding when passing left.
+    pub heartbreak_keep: i32,
 }
 
-fn pass_weights() -> &'static PassWeights {
+impl Default for PassWeights {
+    fn default() -> Self {
+        Self {
+            to_leader_penalty: 1400,
+            hearts_void_plan: 2500,
+            heartbreak_keep: 8000,
+        }
+    }
+}
+
+impl PassWeights {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let read = |name: &str, default: i32| {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.parse::<i32>().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            to_leader_penalty: read("MDH_W_PASS_TO_LEADER_PENALTY", defaults.to_leader_penalty),
+            hearts_void_plan: read("MDH_W_PASS_HEARTS_VOID_PLAN", defaults.hearts_void_plan),
+            heartbreak_keep: read("MDH_W_PASS_HEARTBREAK_KEEP", defaults.heartbreak_keep),
+        }
+    }
+}
+
+/// The process-wide weights [`PassPlanner::choose`] uses.
+pub fn pass_weights() -> PassWeights {
     static CACHED: OnceLock<PassWeights> = OnceLock::new();
-    CACHED.get_or_init(|| PassWeights {
-        to_leader_penalty: std::env::var("MDH_W_PASS_TO_LEADER_PENALTY")
-            .ok()
-            .and_then(|s| s.parse::<i32>().ok())
-            .unwrap_or(1400),
-    })
+    *CACHED.get_or_init(PassWeights::from_env)
 }
 
 #[cfg(test)]
@@ -371,6 +828,130 @@ mod tests {
         scores
     }
 
+    #[test]
+    fn tied_pass_candidates_resolve_the_same_for_any_hand_order() {
+        let seat = PlayerPosition::North;
+        let passing = PassingDirection::Left;
+        // Passing either ace with both hearts scores the same.
+        let hand = vec![
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Clubs),
+            Card::new(Rank::Nine, Suit::Clubs),
+            Card::new(Rank::Ace, Suit::Clubs),
+            Card::new(Rank::Three, Suit::Diamonds),
+            Card::new(Rank::Seven, Suit::Diamonds),
+            Card::new(Rank::Ace, Suit::Diamonds),
+            Card::new(Rank::Four, Suit::Spades),
+            Card::new(Rank::Five, Suit::Spades),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Ten, Suit::Spades),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Eight, Suit::Hearts),
+        ];
+        let scores = build_scores([10, 10, 10, 10]);
+        let mut tracker = UnseenTracker::new();
+
+        let round = build_round(seat, &hand, passing);
+        tracker.reset_for_round(&round);
+        let ctx = BotContext::new(
+            seat,
+            &round,
+            scores,
+            passing,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let ranked = PassPlanner::ranked_candidates(round.hand(seat), &ctx, PassWeights::default());
+        assert_eq!(ranked[0].0, ranked[1].0, "hand should produce a top tie");
+        assert_eq!(
+            compare_sorted_sets(&ranked[0].1, &ranked[1].1),
+            Ordering::Less
+        );
+        let expected = PassPlanner::choose(round.hand(seat), &ctx).unwrap();
+        assert_eq!(expected, ranked[0].1);
+
+        let mut shuffled = hand.clone();
+        for step in 0..8 {
+            shuffled.rotate_left(5);
+            if step % 2 == 1 {
+                shuffled.reverse();
+            }
+            let round = build_round(seat, &shuffled, passing);
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                scores,
+                passing,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            assert_eq!(PassPlanner::choose(round.hand(seat), &ctx), Some(expected));
+        }
+    }
+
+    #[test]
+    fn pass_candidate_order_breaks_ties_by_canonical_triple() {
+        let low = [
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Three, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Clubs),
+        ];
+        let high = [
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Three, Suit::Clubs),
+            Card::new(Rank::Five, Suit::Clubs),
+        ];
+        let (low, high) = (PassCards::from(low), PassCards::from(high));
+        let mut candidates = vec![(5, high), (7, high), (5, low)];
+        candidates.sort_by(pass_candidate_order);
+        assert_eq!(candidates, vec![(7, high), (5, low), (5, high)]);
+    }
+
+    #[test]
+    fn four_card_pass_enumerates_every_set_and_voids_a_three_card_suit() {
+        let seat = PlayerPosition::North;
+        let passing = PassingDirection::Right;
+        let hand = vec![
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Five, Suit::Clubs),
+            Card::new(Rank::Eight, Suit::Clubs),
+            Card::new(Rank::Jack, Suit::Clubs),
+            Card::new(Rank::Three, Suit::Diamonds),
+            Card::new(Rank::Six, Suit::Diamonds),
+            Card::new(Rank::Nine, Suit::Diamonds),
+            Card::new(Rank::Two, Suit::Spades),
+            Card::new(Rank::Four, Suit::Spades),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Nine, Suit::Spades),
+            Card::new(Rank::Ten, Suit::Spades),
+            Card::new(Rank::Ace, Suit::Hearts),
+        ];
+        assert_eq!(pass_combinations(&hand, 4).len(), 715);
+        assert_eq!(pass_combinations(&hand, 3).len(), 286);
+
+        let mut hands = [Hand::new(), Hand::new(), Hand::new(), Hand::new()];
+        hands[seat.index()] = Hand::with_cards(hand.clone());
+        let phase = RoundPhase::Passing(PassingState::with_count(passing, 4));
+        let round = RoundState::from_hands(hands, seat, passing, phase);
+        let scores = build_scores([10, 10, 10, 10]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = BotContext::new(
+            seat,
+            &round,
+            scores,
+            passing,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let picks = PassPlanner::choose(round.hand(seat), &ctx).unwrap();
+        assert_eq!(picks.len(), 4);
+        assert!(picks.contains(&Card::new(Rank::Ace, Suit::Hearts)));
+        // Four cards are enough to shed diamonds entirely along with the ace.
+        let diamonds = picks.iter().filter(|c| c.suit == Suit::Diamonds).count();
+        assert_eq!(diamonds, 3, "picked {picks:?}");
+    }
+
     #[test]
     fn cautious_pass_drops_queen_of_spades() {
         let seat = PlayerPosition::North;
@@ -541,6 +1122,242 @@ mod tests {
         let club_passes = picks.iter().filter(|card| card.suit == Suit::Clubs).count();
         assert!(club_passes >= 1, "picks {:?}", picks);
     }
+    /// South's pass with the target's moon state set to `target_moon`.
+    fn pass_against(
+        passing: PassingDirection,
+        hand: &[Card],
+        target_moon: MoonState,
+    ) -> (PassCards, Option<PassExplanation>) {
+        let seat = PlayerPosition::South;
+        let round = build_round(seat, hand, passing);
+        let scores = build_scores([10, 20, 30, 15]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        tracker.set_moon_state(passing.target(seat), target_moon);
+        let ctx = BotContext::new(
+            seat,
+            &round,
+            scores,
+            passing,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let picks = PassPlanner::choose(round.hand(seat), &ctx).unwrap();
+        let explanation = PassPlanner::explain(round.hand(seat), &ctx, PassWeights::default(), 0);
+        (picks, explanation)
+    }
+
+    fn ace_with_low_support() -> Vec<Card> {
+        vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Three, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Spades),
+            Card::new(Rank::Five, Suit::Spades),
+            Card::new(Rank::Six, Suit::Spades),
+            Card::new(Rank::Three, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Clubs),
+            Card::new(Rank::Five, Suit::Clubs),
+            Card::new(Rank::Six, Suit::Clubs),
+            Card::new(Rank::Two, Suit::Diamonds),
+            Card::new(Rank::Three, Suit::Diamonds),
+            Card::new(Rank::Four, Suit::Diamonds),
+        ]
+    }
+
+    fn king_offsuit() -> Vec<Card> {
+        vec![
+            Card::new(Rank::King, Suit::Diamonds),
+            Card::new(Rank::Four, Suit::Diamonds),
+            Card::new(Rank::Five, Suit::Diamonds),
+            Card::new(Rank::Six, Suit::Diamonds),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Six, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Spades),
+            Card::new(Rank::Five, Suit::Spades),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Three, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Clubs),
+            Card::new(Rank::Five, Suit::Clubs),
+            Card::new(Rank::Nine, Suit::Clubs),
+        ]
+    }
+
+    #[test]
+    fn premium_heart_with_low_support_stays_home_against_a_shooter() {
+        let ace = Card::new(Rank::Ace, Suit::Hearts);
+        for passing in [PassingDirection::Left, PassingDirection::Across] {
+            let (quiet, _) = pass_against(passing, &ace_with_low_support(), MoonState::Inactive);
+            assert!(quiet.contains(&ace), "{passing:?}: {quiet:?}");
+            let (guarded, explanation) =
+                pass_against(passing, &ace_with_low_support(), MoonState::Considering);
+            assert!(!guarded.contains(&ace), "{passing:?}: {guarded:?}");
+            assert_eq!(explanation.unwrap().chosen.cards, guarded);
+        }
+    }
+
+    #[test]
+    fn guards_scale_with_pass_direction() {
+        let ace = Card::new(Rank::Ace, Suit::Hearts);
+        let seat = PlayerPosition::South;
+        let delta = |passing: PassingDirection| {
+            let round = build_round(seat, &ace_with_low_support(), passing);
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(&round);
+            tracker.set_moon_state(passing.target(seat), MoonState::Committed);
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                build_scores([10, 20, 30, 15]),
+                passing,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            let input = PassScoreInput::new(round.hand(seat), &ctx, PassWeights::default());
+            score_card_parts(ace, &input)
+                .parts
+                .iter()
+                .find(|part| part.name == "premium_support_guard")
+                .map(|part| part.delta)
+        };
+        assert_eq!(delta(PassingDirection::Left), Some(-12_000));
+        assert_eq!(delta(PassingDirection::Across), Some(-9_600));
+        assert_eq!(delta(PassingDirection::Right), Some(-7_200));
+    }
+
+    #[test]
+    fn offsuit_king_is_not_fed_to_a_shooter() {
+        let king = Card::new(Rank::King, Suit::Diamonds);
+        for passing in [PassingDirection::Left, PassingDirection::Across] {
+            let (quiet, _) = pass_against(passing, &king_offsuit(), MoonState::Inactive);
+            assert!(quiet.contains(&king), "{passing:?}: {quiet:?}");
+            let (guarded, explanation) =
+                pass_against(passing, &king_offsuit(), MoonState::Considering);
+            assert!(!guarded.contains(&king), "{passing:?}: {guarded:?}");
+            assert_eq!(explanation.unwrap().chosen.cards, guarded);
+        }
+        // A considering shooter to the right is below that direction's threshold.
+        let (right, _) = pass_against(
+            PassingDirection::Right,
+            &king_offsuit(),
+            MoonState::Considering,
+        );
+        assert!(right.contains(&king), "{right:?}");
+    }
+
+    #[test]
+    fn block_shooter_urgency_follows_the_guard_threshold() {
+        let seat = PlayerPosition::South;
+        let urgency = |passing: PassingDirection, target_moon: MoonState| {
+            let round = build_round(seat, &king_offsuit(), passing);
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(&round);
+            tracker.set_moon_state(passing.target(seat), target_moon);
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                build_scores([10, 20, 30, 15]),
+                passing,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            block_shooter_urgency(&ctx)
+        };
+        assert_eq!(urgency(PassingDirection::Left, MoonState::Inactive), None);
+        assert_eq!(
+            urgency(PassingDirection::Left, MoonState::Considering),
+            Some(60)
+        );
+        assert_eq!(
+            urgency(PassingDirection::Right, MoonState::Considering),
+            None
+        );
+        assert_eq!(
+            urgency(PassingDirection::Right, MoonState::Committed),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn a_short_target_makes_the_game_late_sooner() {
+        // East on 40 is 80% of a 50-point match, so a moon by anyone but West
+        // could end it; at 100 the same totals are early.
+        let seat = PlayerPosition::South;
+        let passing = PassingDirection::Left;
+        let round = build_round(seat, &king_offsuit(), passing);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let urgency = |target: u32| {
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                build_scores([10, 40, 30, 15]),
+                passing,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            )
+            .with_target_score(target);
+            block_shooter_urgency(&ctx)
+        };
+        assert_eq!(urgency(100), None);
+        assert_eq!(urgency(50), Some(50));
+    }
+
+    #[test]
+    fn a_recent_moon_raises_the_urgency_for_two_rounds() {
+        let seat = PlayerPosition::South;
+        let passing = PassingDirection::Right;
+        let round = build_round(seat, &king_offsuit(), passing);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        tracker.set_moon_state(passing.target(seat), MoonState::Considering);
+        let urgency = |tracker: &UnseenTracker| {
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                build_scores([10, 20, 30, 15]),
+                passing,
+                tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            block_shooter_urgency(&ctx)
+        };
+        assert_eq!(urgency(&tracker), None);
+        // West shot the moon last round: 60 + 20 clears the right's 70.
+        tracker.note_round_scored([0, 0, 0, 26], [26, 26, 26, 0]);
+        assert_eq!(urgency(&tracker), Some(80));
+        tracker.note_round_scored([13, 0, 13, 0], [39, 26, 39, 0]);
+        assert_eq!(urgency(&tracker), Some(80));
+        tracker.note_round_scored([13, 0, 13, 0], [52, 26, 52, 0]);
+        assert_eq!(urgency(&tracker), None);
+    }
+
+    #[test]
+    fn the_jack_of_diamonds_stays_home_under_omnibus() {
+        let seat = PlayerPosition::South;
+        let passing = PassingDirection::Left;
+        let hand = hearts_core::testkit::cards("JD 2C 3C 4C 5C 6C 7C 8C 2S 3S 4S 5S 6S");
+        let round = build_round(seat, &hand, passing);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let pass = |omnibus: bool| {
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                build_scores([0, 0, 0, 0]),
+                passing,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            )
+            .with_jack_of_diamonds(omnibus);
+            PassPlanner::choose(round.hand(seat), &ctx).unwrap()
+        };
+        let jack = hearts_core::testkit::card("JD");
+        // A lone jack is an easy void to make in the standard game.
+        assert!(pass(false).contains(&jack));
+        assert!(!pass(true).contains(&jack));
+    }
+
     #[test]
     fn pass_tracker_respects_seen_queen() {
         let seat = PlayerPosition::North;
@@ -582,16 +1399,20 @@ mod tests {
         let hand_ref = round.hand(seat);
         let queen = Card::new(Rank::Queen, Suit::Spades);
 
-        let score_unseen = super::score_card(
-            queen,
-            hand_ref,
-            &ctx_unseen,
+        let input = |ctx| PassScoreInput {
+            ctx,
+            profile: HandProfile::from_hand(hand_ref),
             style,
             passing_to_trailing,
             passing_to_leader,
             my_score,
             snapshot,
-        );
+            pass_count: 3,
+            weights: PassWeights::default(),
+            shooter_pressure: 0,
+        };
+
+        let score_unseen = super::score_card(queen, &input(&ctx_unseen));
 
         let mut tracker_seen = tracker_unseen.clone();
         tracker_seen.note_card_revealed(queen);
@@ -603,18 +1424,143 @@ mod tests {
             &tracker_seen,
             BotDifficulty::NormalHeuristic,
         );
-        let snapshot_seen = snapshot_scores(ctx_seen.scores);
-        let score_seen = super::score_card(
-            queen,
-            hand_ref,
-            &ctx_seen,
-            style,
-            passing_to_trailing,
-            passing_to_leader,
-            my_score,
-            snapshot_seen,
-        );
+        let score_seen = super::score_card(queen, &input(&ctx_seen));
 
         assert!(score_seen < score_unseen);
     }
+
+    #[test]
+    fn short_hearts_pick_a_plan_by_direction() {
+        let seat = PlayerPosition::North;
+        // 3♥ and J♥ with no other hearts, and no short suit to void instead.
+        let hand = vec![
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Six, Suit::Clubs),
+            Card::new(Rank::Nine, Suit::Clubs),
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Diamonds),
+            Card::new(Rank::Eight, Suit::Diamonds),
+            Card::new(Rank::Ace, Suit::Diamonds),
+            Card::new(Rank::Three, Suit::Spades),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Ten, Suit::Spades),
+            Card::new(Rank::Jack, Suit::Spades),
+            Card::new(Rank::Three, Suit::Hearts),
+            Card::new(Rank::Jack, Suit::Hearts),
+        ];
+        let scores = build_scores([10, 10, 10, 10]);
+        let choose_with = |passing: PassingDirection, weights: PassWeights| {
+            let round = build_round(seat, &hand, passing);
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(&round);
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                scores,
+                passing,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            let explained = PassPlanner::explain(round.hand(seat), &ctx, weights, 0).unwrap();
+            let plan: Vec<&str> = explained
+                .chosen
+                .set_parts
+                .iter()
+                .map(|part| part.name)
+                .filter(|name| *name == "hearts_void_plan" || *name == "heartbreak_keep")
+                .collect();
+            (explained.chosen.cards, plan)
+        };
+        let choose = |passing| choose_with(passing, PassWeights::default());
+        let void = PassCards::from([
+            Card::new(Rank::Ace, Suit::Diamonds),
+            Card::new(Rank::Three, Suit::Hearts),
+            Card::new(Rank::Jack, Suit::Hearts),
+        ]);
+        let keep = PassCards::from([
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::Ace, Suit::Diamonds),
+            Card::new(Rank::Jack, Suit::Hearts),
+        ]);
+        assert_eq!(
+            choose(PassingDirection::Right),
+            (void, vec!["hearts_void_plan"])
+        );
+        assert_eq!(
+            choose(PassingDirection::Across),
+            (void, vec!["hearts_void_plan"])
+        );
+        assert_eq!(
+            choose(PassingDirection::Left),
+            (keep, vec!["heartbreak_keep"])
+        );
+        // Without the keep plan, left passes both hearts like the others.
+        let no_keep = PassWeights {
+            heartbreak_keep: 0,
+            ..PassWeights::default()
+        };
+        assert_eq!(choose_with(PassingDirection::Left, no_keep).0, void);
+
+        // Hold hands pass nothing, so no plan applies to any set.
+        let round = build_round(seat, &hand, PassingDirection::Hold);
+        let tracker = UnseenTracker::new();
+        let ctx = BotContext::new(
+            seat,
+            &round,
+            scores,
+            PassingDirection::Hold,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let input = PassScoreInput::new(round.hand(seat), &ctx, PassWeights::default());
+        assert!(short_hearts_plan(&void, &input).is_none());
+        assert!(short_hearts_plan(&keep, &input).is_none());
+    }
+
+    fn uncached_ranking(hand: &Hand, ctx: &BotContext<'_>) -> Vec<(i32, PassCards)> {
+        let input = PassScoreInput::new(hand, ctx, PassWeights::default());
+        let cards: Vec<Card> = hand.iter().copied().collect();
+        let mut ranked: Vec<(i32, PassCards)> = pass_combinations(&cards, ctx.round.pass_count())
+            .into_iter()
+            .map(|set| (score_pass_set(&set, &input), set))
+            .collect();
+        ranked.sort_by(pass_candidate_order);
+        ranked
+    }
+
+    #[test]
+    fn the_scoring_session_ranks_like_scoring_every_card_afresh() {
+        use hearts_core::model::deck::Deck;
+        use hearts_core::model::passing::MAX_PASS_COUNT;
+
+        let scores = build_scores([40, 12, 71, 25]);
+        for seed in 0..24u64 {
+            let deck = Deck::shuffled_with_seed(seed);
+            let direction = PassingDirection::CYCLE[seed as usize % 3];
+            let pass_count = if seed % 4 == 0 { MAX_PASS_COUNT } else { 3 };
+            let round = RoundState::deal_with_pass_count(
+                &deck,
+                PlayerPosition::North,
+                direction,
+                pass_count,
+            );
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(&round);
+            for seat in PlayerPosition::LOOP {
+                let ctx = BotContext::new(
+                    seat,
+                    &round,
+                    scores,
+                    direction,
+                    &tracker,
+                    BotDifficulty::NormalHeuristic,
+                );
+                let hand = round.hand(seat);
+                let mut session = ScoringSession::new(hand, &ctx, PassWeights::default());
+                let ranked = PassPlanner::ranked(hand, &mut session);
+                assert_eq!(ranked, uncached_ranking(hand, &ctx), "seed {seed} {seat:?}");
+                assert_eq!(session.card_scorings, hand.len());
+            }
+        }
+    }
 }
@@ -1,5 +1,6 @@
+use super::pass::ScorePart;
 use super::{
-    BotContext, BotStyle, DecisionLimit, MoonState, card_sort_key, count_cards_in_suit,
+    BotContext, BotStyle, DecisionLimit, MoonState, TrickView, card_sort_key, count_cards_in_suit,
     detect_moon_pressure, determine_style, snapshot_scores,
 };
 use crate::debug::debug_enabled;
@@ -7,6 +8,7 @@ use hearts_core::model::card::Card;
 use hearts_core::model::player::PlayerPosition;
 use hearts_core::model::rank::Rank;
 use hearts_core::model::round::{PlayOutcome, RoundState};
+use hearts_core::model::rules::JACK_OF_DIAMONDS_BONUS;
 use hearts_core::model::suit::Suit;
 use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
@@ -126,7 +128,27 @@ pub(crate) fn reset_hard_nudge_hits() {
     HARD_NUDGE_HITS.with(|cell| cell.set(0));
 }
 
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without counting mix-hint and nudge hits, so an analysis pass
+/// leaves the statistics of the last real decision as they were.
+pub(crate) fn without_stats<R>(f: impl FnOnce() -> R) -> R {
+    let was = QUIET.with(|quiet| quiet.replace(true));
+    let out = f();
+    QUIET.with(|quiet| quiet.set(was));
+    out
+}
+
+fn stats_paused() -> bool {
+    QUIET.with(Cell::get)
+}
+
 pub(crate) fn record_hard_nudge_hit() {
+    if stats_paused() {
+        return;
+    }
     HARD_NUDGE_HITS.with(|cell| cell.set(cell.get().saturating_add(1)));
 }
 
@@ -149,6 +171,9 @@ pub(crate) fn reset_mix_hint_bias_stats() {
 }
 
 fn record_mix_hint_bias(counter: MixHintBiasCounter) {
+    if stats_paused() {
+        return;
+    }
     MIX_HINT_BIAS.with(|cell| {
         let mut stats = cell.borrow_mut();
         match counter {
@@ -188,7 +213,7 @@ pub(crate) fn reset_hard_nudge_trace() {
 }
 
 fn record_hard_nudge_guard(reason: &'static str) {
-    if !nudge_trace_enabled() {
+    if !nudge_trace_enabled() || stats_paused() {
         return;
     }
     HARD_NUDGE_TRACE.with(|cell| {
@@ -245,6 +270,14 @@ impl PlayPlanner {
         let limit_ms = limit.and_then(|lim| lim.remaining_millis());
         let snapshot = snapshot_scores(ctx.scores);
         let lead_suit = ctx.round.current_trick().lead_suit();
+
+        if let Some(card) = moon_emergency_card(legal, ctx, style, snapshot.max_player) {
+            if debug_enabled() {
+                eprintln!("mdhearts: moon emergency, taking points with {}", card);
+            }
+            return Some(card);
+        }
+
         let mut best: Option<(Card, i32)> = None;
 
         for &card in legal {
@@ -253,126 +286,11 @@ impl PlayPlanner {
             {
                 break;
             }
-            let (winner, penalties) = simulate_trick(card, ctx, style, snapshot.max_player);
-            let will_capture = winner == ctx.seat;
-            let mut score = base_score(
-                ctx,
-                card,
-                winner,
-                will_capture,
-                penalties,
-                lead_suit,
-                style,
-                &snapshot,
-                limit_ms,
-            );
-            let mut dbg = DebugParts::maybe_new(card, style, lead_suit, score);
-
-            // Void creation bonus.
-            let suit_remaining = count_cards_in_suit(ctx.hand(), card.suit);
-            if suit_remaining <= 1 {
-                let bonus = weights().void_creation_bonus;
-                score += bonus;
-                dbg.add("void_creation", bonus);
-            }
-
-            // Prefer dumping high cards when following suit.
-            if let Some(lead) = lead_suit {
-                if card.suit == lead {
-                    let d = -((card.rank.value() as i32) * weights().follow_high_rank_multiplier);
-                    score += d;
-                    dbg.add("follow_high_rank_penalty", d);
-                } else {
-                    let d = card.penalty_value() as i32 * weights().off_suit_dump_bonus;
-                    score += d;
-                    dbg.add("off_suit_dump_bonus", d);
-                }
-            } else {
-                // We are leading.
-                let d = -((card.rank.value() as i32) * weights().lead_rank_bias_multiplier);
-                score += d;
-                dbg.add("lead_rank_bias", d);
-                if card.suit == Suit::Hearts
-                    && !ctx.round.hearts_broken()
-                    && style != BotStyle::HuntLeader
-                {
-                    let penalty = -weights().lead_unbroken_hearts_penalty;
-                    score += penalty;
-                    dbg.add("lead_unbroken_hearts_penalty", penalty);
-                }
-                // Early-round caution: even if hearts are broken, avoid leading hearts too early in Cautious style
-                if style == BotStyle::Cautious
-                    && card.suit == Suit::Hearts
-                    && ctx.round.hearts_broken()
-                    && ctx.cards_played() < 16
-                {
-                    let p = -weights().early_hearts_lead_caution;
-                    score += p;
-                    dbg.add("early_round_lead_hearts_caution", p);
-                }
-                if style == BotStyle::HuntLeader && card.penalty_value() > 0 {
-                    let d = weights().hunt_leader_lead_base
-                        + (card.penalty_value() as i32 * weights().hunt_leader_lead_perpen);
-                    score += d;
-                    dbg.add("hunt_leader_lead_dump", d);
-                }
-                if style == BotStyle::AggressiveMoon && card.suit == Suit::Hearts {
-                    let bonus = weights().moon_lead_hearts_bonus;
-                    score += bonus;
-                    dbg.add("moon_lead_hearts_bonus", bonus);
-                }
-            }
-
-            // Late-round urgency to shed penalties if we are at risk.
-            if snapshot.max_player == ctx.seat && snapshot.max_score >= 90 {
-                if will_capture {
-                    let d = -(penalties as i32 * 1200);
-                    score += d;
-                    dbg.add("near100_self_capture_penalty", d);
-                } else {
-                    let d = penalties as i32 * 300;
-                    score += d;
-                    dbg.add("near100_shed_bonus", d);
-                }
-            }
-
-            if matches!(ctx.difficulty, super::BotDifficulty::FutureHard) && penalties > 0 {
-                let round_totals = ctx.round.penalty_totals();
-                let mut projected = [0i32; 4];
-                for seat in PlayerPosition::LOOP.iter().copied() {
-                    projected[seat.index()] = round_totals[seat.index()] as i32;
-                }
-                projected[winner.index()] =
-                    projected[winner.index()].saturating_add(penalties as i32);
-                if will_capture {
-                    let projected_self = projected[ctx.seat.index()];
-                    let mut best_other = i32::MIN;
-                    for seat in PlayerPosition::LOOP.iter().copied() {
-                        if seat == ctx.seat {
-                            continue;
-                        }
-                        best_other = best_other.max(projected[seat.index()]);
-                    }
-                    if projected_self > best_other {
-                        let projected_gap = projected_self - best_other;
-                        let d = -((penalties as i32) * 600 + projected_gap * 120);
-                        score += d;
-                        dbg.add("round_leader_self_capture_penalty", d);
-                    }
-                }
-            }
-
-            // Tracker-based pacing: fewer unseen cards => accelerate shedding points.
-            let cards_played = ctx.cards_played() as i32;
-            let d = cards_played * weights().cards_played_bias;
-            score += d;
-            dbg.add("cards_played_bias", d);
-            if ctx.tracker.is_unseen(card) {
-                score += 20;
-                dbg.add("unseen_card_bonus", 20);
+            let scored = score_play(card, ctx, style, &snapshot, lead_suit, limit_ms);
+            if debug_enabled() {
+                debug_scored(&scored, style, lead_suit);
             }
-
-            dbg.finish(score);
+            let score = scored.total;
 
             match best {
                 None => best = Some((card, score)),
@@ -399,109 +317,227 @@ impl PlayPlanner {
         ctx: &BotContext<'_>,
         limit_ms: Option<u32>,
     ) -> Vec<(Card, i32)> {
-        if legal.is_empty() {
-            return Vec::new();
-        }
         reset_mix_hint_bias_stats();
-        let style = determine_style(ctx);
-        let snapshot = snapshot_scores(ctx.scores);
-        let lead_suit = ctx.round.current_trick().lead_suit();
-        let mut out: Vec<(Card, i32)> = Vec::new();
-        for &card in legal {
-            let (winner, penalties) = simulate_trick(card, ctx, style, snapshot.max_player);
-            let will_capture = winner == ctx.seat;
-            let mut score = base_score(
-                ctx,
-                card,
-                winner,
-                will_capture,
-                penalties,
-                lead_suit,
-                style,
-                &snapshot,
-                limit_ms,
-            );
-            let suit_remaining = count_cards_in_suit(ctx.hand(), card.suit);
-            if suit_remaining <= 1 {
-                score += weights().void_creation_bonus;
-            }
-            if let Some(lead) = lead_suit {
-                if card.suit == lead {
-                    score -= (card.rank.value() as i32) * weights().follow_high_rank_multiplier;
-                } else {
-                    score += card.penalty_value() as i32 * weights().off_suit_dump_bonus;
-                }
-            } else {
-                score -= (card.rank.value() as i32) * weights().lead_rank_bias_multiplier;
-                if card.suit == Suit::Hearts
-                    && !ctx.round.hearts_broken()
-                    && style != BotStyle::HuntLeader
-                {
-                    score -= weights().lead_unbroken_hearts_penalty;
-                }
-                if style == BotStyle::HuntLeader && card.penalty_value() > 0 {
-                    score += weights().hunt_leader_lead_base
-                        + (card.penalty_value() as i32 * weights().hunt_leader_lead_perpen);
-                }
-                if style == BotStyle::AggressiveMoon && card.suit == Suit::Hearts {
-                    score += weights().moon_lead_hearts_bonus;
-                }
-            }
-            if snapshot.max_player == ctx.seat && snapshot.max_score >= 90 {
-                if will_capture {
-                    score -= penalties as i32 * 1_200;
-                } else {
-                    score += penalties as i32 * 300;
-                }
-            }
-            let cards_played = ctx.cards_played() as i32;
-            score += cards_played * weights().cards_played_bias;
-            if ctx.tracker.is_unseen(card) {
-                score += 20;
-            }
-            out.push((card, score));
-        }
-        out.sort_by(|a, b| {
-            b.1.cmp(&a.1)
-                .then_with(|| card_sort_key(a.0).cmp(&card_sort_key(b.0)))
-        });
-        out
+        score_plays(legal, ctx, limit_ms)
+            .into_iter()
+            .map(|scored| (scored.card, scored.total))
+            .collect()
     }
 }
 
-struct DebugParts {
-    on: bool,
-    msg: String,
+/// A candidate play as the heuristic scores it: the total, the named terms
+/// that make it up, and how the trick would go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScoredPlay {
+    pub card: Card,
+    pub total: i32,
+    /// `base` first, then each adjustment; zero terms are left out.
+    pub parts: Vec<ScorePart>,
+    pub winner: PlayerPosition,
+    /// Penalty points the trick would carry.
+    pub penalties: u8,
 }
 
-impl DebugParts {
-    fn maybe_new(card: Card, style: BotStyle, lead: Option<Suit>, base: i32) -> Self {
-        if debug_enabled() {
-            let s = format!(
-                "mdhearts: cand {} {:?} lead={:?} base={} parts:",
-                card, style, lead, base
-            );
-            Self { on: true, msg: s }
+/// Scores every card of `legal`, best first with ties in canonical card
+/// order.
+pub(crate) fn score_plays(
+    legal: &[Card],
+    ctx: &BotContext<'_>,
+    limit_ms: Option<u32>,
+) -> Vec<ScoredPlay> {
+    let style = determine_style(ctx);
+    let snapshot = snapshot_scores(ctx.scores);
+    let lead_suit = ctx.round.current_trick().lead_suit();
+    let mut out: Vec<ScoredPlay> = legal
+        .iter()
+        .map(|&card| score_play(card, ctx, style, &snapshot, lead_suit, limit_ms))
+        .collect();
+    out.sort_by(|a, b| {
+        b.total
+            .cmp(&a.total)
+            .then_with(|| card_sort_key(a.card).cmp(&card_sort_key(b.card)))
+    });
+    out
+}
+
+/// The card [`PlayPlanner::choose`] plays without scoring when a moon shot
+/// has to be stopped, if any.
+pub(crate) fn forced_play(legal: &[Card], ctx: &BotContext<'_>) -> Option<Card> {
+    let style = determine_style(ctx);
+    let snapshot = snapshot_scores(ctx.scores);
+    moon_emergency_card(legal, ctx, style, snapshot.max_player)
+}
+
+fn score_play(
+    card: Card,
+    ctx: &BotContext<'_>,
+    style: BotStyle,
+    snapshot: &super::ScoreSnapshot,
+    lead_suit: Option<Suit>,
+    limit_ms: Option<u32>,
+) -> ScoredPlay {
+    let (winner, penalties) = simulate_trick(card, ctx, style, snapshot.max_player);
+    let will_capture = winner == ctx.seat;
+    let base = base_score(
+        ctx,
+        card,
+        winner,
+        will_capture,
+        penalties,
+        lead_suit,
+        style,
+        snapshot,
+        limit_ms,
+    );
+    let mut parts: Vec<(&'static str, i32)> = vec![("base", base)];
+
+    // Under the Omnibus rule J♦ is worth winning and costly to give away.
+    if ctx.jack_of_diamonds {
+        let value = i32::from(JACK_OF_DIAMONDS_BONUS) * weights().base_capture_perpen;
+        let jack_on_table = ctx
+            .round
+            .current_trick()
+            .plays()
+            .iter()
+            .any(|play| play.card.is_jack_of_diamonds());
+        if will_capture && (jack_on_table || card.is_jack_of_diamonds()) {
+            parts.push(("jack_of_diamonds", value));
+        } else if card.is_jack_of_diamonds() {
+            parts.push(("jack_of_diamonds", -value));
+        }
+    }
+
+    // Void creation bonus.
+    let suit_remaining = count_cards_in_suit(ctx.hand(), card.suit);
+    if suit_remaining <= 1 {
+        parts.push(("void_creation", weights().void_creation_bonus));
+    }
+
+    // Prefer dumping high cards when following suit.
+    if let Some(lead) = lead_suit {
+        if card.suit == lead {
+            parts.push((
+                "follow_high_rank_penalty",
+                -((card.rank.value() as i32) * weights().follow_high_rank_multiplier),
+            ));
         } else {
-            Self {
-                on: false,
-                msg: String::new(),
-            }
+            parts.push((
+                "off_suit_dump_bonus",
+                card.penalty_value() as i32 * weights().off_suit_dump_bonus,
+            ));
+        }
+    } else {
+        // We are leading.
+        parts.push((
+            "lead_rank_bias",
+            -((card.rank.value() as i32) * weights().lead_rank_bias_multiplier),
+        ));
+        if card.suit == Suit::Hearts && !ctx.round.hearts_broken() && style != BotStyle::HuntLeader
+        {
+            parts.push((
+                "lead_unbroken_hearts_penalty",
+                -weights().lead_unbroken_hearts_penalty,
+            ));
+        }
+        // Early-round caution: even if hearts are broken, avoid leading hearts too early in Cautious style
+        if style == BotStyle::Cautious
+            && card.suit == Suit::Hearts
+            && ctx.round.hearts_broken()
+            && ctx.cards_played() < 16
+        {
+            parts.push((
+                "early_round_lead_hearts_caution",
+                -weights().early_hearts_lead_caution,
+            ));
+        }
+        if style == BotStyle::HuntLeader && card.penalty_value() > 0 {
+            parts.push((
+                "hunt_leader_lead_dump",
+                weights().hunt_leader_lead_base
+                    + (card.penalty_value() as i32 * weights().hunt_leader_lead_perpen),
+            ));
+        }
+        if style == BotStyle::AggressiveMoon && card.suit == Suit::Hearts {
+            parts.push(("moon_lead_hearts_bonus", weights().moon_lead_hearts_bonus));
         }
     }
-    fn add(&mut self, name: &str, delta: i32) {
-        if self.on {
-            use std::fmt::Write as _;
-            let _ = write!(&mut self.msg, " {}={}", name, delta);
+
+    // Late-round urgency to shed penalties if we are at risk.
+    if snapshot.max_player == ctx.seat
+        && snapshot.max_score >= ctx.threshold(ctx.params.near_target_pct)
+    {
+        if will_capture {
+            parts.push(("near100_self_capture_penalty", -(penalties as i32 * 1200)));
+        } else {
+            parts.push(("near100_shed_bonus", penalties as i32 * 300));
         }
     }
-    fn finish(self, total: i32) {
-        if self.on {
-            eprintln!("{} total={}", self.msg, total);
+
+    if matches!(ctx.difficulty, super::BotDifficulty::FutureHard) && penalties > 0 && will_capture {
+        let round_totals = ctx.round.penalty_totals();
+        let mut projected = [0i32; 4];
+        for seat in PlayerPosition::LOOP.iter().copied() {
+            projected[seat.index()] = round_totals[seat.index()] as i32;
+        }
+        projected[winner.index()] = projected[winner.index()].saturating_add(penalties as i32);
+        let projected_self = projected[ctx.seat.index()];
+        let mut best_other = i32::MIN;
+        for seat in PlayerPosition::LOOP.iter().copied() {
+            if seat == ctx.seat {
+                continue;
+            }
+            best_other = best_other.max(projected[seat.index()]);
         }
+        if projected_self > best_other {
+            let projected_gap = projected_self - best_other;
+            parts.push((
+                "round_leader_self_capture_penalty",
+                -((penalties as i32) * 600 + projected_gap * 120),
+            ));
+        }
+    }
+
+    // Tracker-based pacing: fewer unseen cards => accelerate shedding points.
+    let cards_played = ctx.cards_played() as i32;
+    parts.push((
+        "cards_played_bias",
+        cards_played * weights().cards_played_bias,
+    ));
+    if ctx.tracker.is_unseen(card) {
+        parts.push(("unseen_card_bonus", 20));
+    }
+
+    ScoredPlay {
+        card,
+        total: parts.iter().map(|(_, delta)| delta).sum(),
+        parts: parts
+            .into_iter()
+            .filter(|(_, delta)| *delta != 0)
+            .map(|(name, delta)| ScorePart { name, delta })
+            .collect(),
+        winner,
+        penalties,
     }
 }
 
+fn debug_scored(scored: &ScoredPlay, style: BotStyle, lead: Option<Suit>) {
+    let base = scored
+        .parts
+        .iter()
+        .find(|part| part.name == "base")
+        .map_or(0, |part| part.delta);
+    let parts: String = scored
+        .parts
+        .iter()
+        .filter(|part| part.name != "base")
+        .map(|part| format!(" {}={}", part.name, part.delta))
+        .collect();
+    eprintln!(
+        "mdhearts: cand {} {:?} lead={:?} base={} parts:{} total={}",
+        scored.card, style, lead, base, parts, scored.total
+    );
+}
+
 #[cfg(test)]
 pub(crate) fn score_candidate_for_tests(card: Card, ctx: &BotContext<'_>, style: BotStyle) -> i32 {
     let snapshot = snapshot_scores(ctx.scores);
@@ -546,7 +582,9 @@ pub(crate) fn score_candidate_for_tests(card: Card, ctx: &BotContext<'_>, style:
         }
     }
 
-    if snapshot.max_player == ctx.seat && snapshot.max_score >= 90 {
+    if snapshot.max_player == ctx.seat
+        && snapshot.max_score >= ctx.threshold(ctx.params.near_target_pct)
+    {
         if will_capture {
             score -= penalties as i32 * 1_200;
         } else {
@@ -626,90 +664,387 @@ fn trick_contains_qs(trick: &hearts_core::model::trick::Trick) -> bool {
     trick.plays().iter().any(|p| p.card == target)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn base_score(
+/// Moon emergency: one opponent has taken every point and at most four hearts
+/// are left (seven if that opponent's pass to us was a moon tell), so eat a
+/// point-bearing trick now with the cheapest winning card.
+fn moon_emergency_card(
+    legal: &[Card],
     ctx: &BotContext<'_>,
-    card: Card,
-    winner: PlayerPosition,
-    will_capture: bool,
-    penalties: u8,
-    lead_suit: Option<Suit>,
     style: BotStyle,
-    snapshot: &super::ScoreSnapshot,
-    limit_ms: Option<u32>,
-) -> i32 {
-    let penalties_i32 = penalties as i32;
-    let mut score: i32 = 0;
-    // Current trick penalties already on table before our play
-    let penalties_on_table_now: i32 = ctx
-        .round
-        .current_trick()
-        .plays()
+    leader_target: PlayerPosition,
+) -> Option<Card> {
+    let taker = super::only_point_taker(ctx.round)?;
+    let passed_moon_tell = ctx
+        .tracker
+        .received_pass(ctx.seat)
+        .is_some_and(|profile| profile.giver == taker && profile.is_moon_tell());
+    let hearts_limit = if passed_moon_tell { 7 } else { 4 };
+    if taker == ctx.seat || super::hearts_remaining(ctx.round) > hearts_limit {
+        return None;
+    }
+    legal
         .iter()
-        .map(|p| p.card.penalty_value() as i32)
-        .sum();
-    let limit_ms = limit_ms.unwrap_or(0);
-    let mix_hint = mix_hint_for_play();
+        .copied()
+        .filter_map(|card| {
+            let (winner, penalties) = simulate_trick(card, ctx, style, leader_target);
+            (winner == ctx.seat && penalties > 0).then_some((card, penalties))
+        })
+        .min_by_key(|&(card, penalties)| (penalties, card.rank.value(), card_sort_key(card)))
+        .map(|(card, _)| card)
+}
 
-    if will_capture {
-        score -= weights().base_capture_penalty;
-        score -= penalties_i32 * weights().base_capture_perpen;
-    } else {
-        score += weights().base_shed_bonus;
-        score += penalties_i32 * weights().base_shed_perpen;
+/// Cost of leading or discarding the last low card (below 8) of a suit while
+/// a ten or higher in it stays behind and opponents still hold enough of it to
+/// lead it again. Without an exit card the hand ends up winning every late
+/// trick in that suit. Following suit is exempt: ducking is what the low card
+/// is for.
+fn exit_preservation_penalty(ctx: &BotContext<'_>, card: Card, lead_suit: Option<Suit>) -> i32 {
+    if card.rank >= Rank::Eight || lead_suit == Some(card.suit) {
+        return 0;
     }
-
-    if penalties == 0 && will_capture {
-        // Winning a clean trick is still mildly negative to keep low profile.
-        score -= (card.rank.value() as i32) * weights().clean_trick_rank_multiplier;
+    let hand = ctx.hand();
+    // Too few tricks left for a stranded suit to matter.
+    if hand.len() <= 4 {
+        return 0;
     }
+    let in_suit = count_cards_in_suit(hand, card.suit);
+    let lows = hand
+        .iter()
+        .filter(|c| c.suit == card.suit && c.rank < Rank::Eight)
+        .count();
+    let stranded_high = hand
+        .iter()
+        .any(|c| c.suit == card.suit && c.rank >= Rank::Ten);
+    if in_suit < 2 || lows > 1 || !stranded_high {
+        return 0;
+    }
+    let outstanding = ctx
+        .tracker
+        .unseen_in_suit(card.suit)
+        .saturating_sub(in_suit);
+    if outstanding < 3 {
+        return 0;
+    }
+    weights().exit_preservation_weight
+}
 
-    if let Some(lead) = lead_suit
-        && card.suit != lead
-        && !ctx.round.current_trick().plays().is_empty()
-    {
-        score += weights().off_suit_play_bonus;
+/// Cost of an early lead in a suit the seat that passed to us is probably
+/// short in: once it runs out it discards points on whoever wins the trick.
+/// Hearts count instead when that seat held on to its high hearts, since a
+/// heart lead then feeds a possible run. Scaled by rank, since a low lead is
+/// less likely to win.
+fn received_pass_lead_penalty(ctx: &BotContext<'_>, card: Card, lead_suit: Option<Suit>) -> i32 {
+    if lead_suit.is_some() || ctx.round.tricks_completed() > 3 {
+        return 0;
+    }
+    let Some(profile) = ctx.tracker.received_pass(ctx.seat) else {
+        return 0;
+    };
+    let applies = if card.suit == Suit::Hearts {
+        profile.giver_kept_premium_hearts()
+    } else {
+        profile.giver_likely_short(card.suit) && !ctx.tracker.is_void(profile.giver, card.suit)
+    };
+    if !applies {
+        return 0;
     }
+    weights().pass_short_lead_weight * card.rank.value() as i32 / 14
+}
 
-    match style {
-        BotStyle::AggressiveMoon => {
-            if will_capture {
-                score +=
-                    weights().moon_capture_bonus + penalties_i32 * weights().moon_capture_perpen;
-            } else {
-                score -= penalties_i32 * weights().moon_shed_perpen;
-            }
-        }
-        BotStyle::HuntLeader => {
-            if !will_capture && penalties > 0 && winner == snapshot.max_player {
-                score += penalties_i32 * weights().hunt_feed_perpen;
-            }
-            if will_capture {
-                score -= 1_000;
-            }
-        }
-        BotStyle::Cautious => {}
+/// Cost of leading into an opponent's concentration suit, scaled by its
+/// danger value (see [`super::UnseenTracker::danger_suits`]). A lead that wins the
+/// trick keeps the lead and costs nothing.
+fn danger_suit_lead_penalty(
+    ctx: &BotContext<'_>,
+    card: Card,
+    lead_suit: Option<Suit>,
+    will_capture: bool,
+) -> i32 {
+    if lead_suit.is_some() || will_capture {
+        return 0;
     }
+    let danger = ctx.tracker.danger_suits(ctx.seat, ctx.round)[card.suit];
+    weights().danger_suit_weight * i32::from(danger) / 100
+}
 
-    let leader_gap = snapshot
-        .max_score
-        .saturating_sub(ctx.scores.score(ctx.seat));
-    let moon_state = ctx.tracker.moon_state(ctx.seat);
-    let under_moon_pressure = matches!(moon_state, MoonState::Considering | MoonState::Committed);
-    let gap_units = (leader_gap.min(30) / 10) as i32; // 0..=3
-    let leader_feed_per = weights().leader_feed_base + gap_units * weights().leader_feed_gap_per10;
-    // Planner-level leader targeting: small positive even before near-100 scenarios.
-    if snapshot.max_player != ctx.seat
-        && !will_capture
-        && penalties > 0
-        && ctx.scores.score(winner) == snapshot.max_score
-        && snapshot.leader_gap > 0
+/// Early-lead adjustment from how the seat we passed to has played our
+/// cards (`MDH_FEATURE_PASS_INFERENCE`). A seat that led or followed with
+/// them kept the suit and will follow a lead in it; one that discarded them
+/// is emptying the suit and will soon discard on it. Positive favours the
+/// lead. Scaled by rank like the received-pass penalty.
+fn passed_card_lead_adjustment(ctx: &BotContext<'_>, card: Card, lead_suit: Option<Suit>) -> i32 {
+    if lead_suit.is_some()
+        || ctx.round.tricks_completed() > 3
+        || !ctx.passing_direction.requires_selection()
+        || !super::tracker::pass_inference_enabled()
     {
-        score += penalties_i32 * leader_feed_per;
+        return 0;
     }
-
-    if under_moon_pressure && will_capture && penalties > 0 {
-        score -= penalties_i32 * 90;
+    let receiver = ctx.passing_direction.target(ctx.seat);
+    if ctx.tracker.is_void(receiver, card.suit) {
+        return 0;
+    }
+    let lean = ctx.tracker.passed_suit_lean(receiver, card.suit);
+    let scaled = weights().pass_inference_lead_weight as f32 * (lean.min(2.0) - 1.0);
+    scaled as i32 * card.rank.value() as i32 / 14
+}
+
+/// A♠ and K♠ are liabilities while Q♠ is out in another hand and we hold
+/// fewer than four spades, since either can then be forced to win the trick
+/// the queen is dropped on: shed them on discards and avoid leading them.
+/// Once the queen has been captured they are safe winners, kept on discards
+/// and led freely when going for the moon.
+fn high_spade_adjustment(
+    ctx: &BotContext<'_>,
+    card: Card,
+    lead_suit: Option<Suit>,
+    style: BotStyle,
+) -> i32 {
+    if card.suit != Suit::Spades || card.rank < Rank::King {
+        return 0;
+    }
+    let queen_played = ctx.round.queen_played();
+    let liability = !queen_played
+        && !ctx.hand().contains(queen_of_spades())
+        && count_cards_in_suit(ctx.hand(), Suit::Spades) < 4;
+    if !liability && !queen_played {
+        return 0;
+    }
+    let weight = weights().high_spade_liability_weight;
+    match lead_suit {
+        Some(Suit::Spades) => 0,
+        Some(_) if liability => weight,
+        Some(_) => -weight,
+        None if liability => -weight,
+        None if style == BotStyle::AggressiveMoon => weight,
+        None => 0,
+    }
+}
+
+/// How many spade leads short of safe our Q♠ is: the leads opponents can
+/// still make in the worst case, their outstanding spades spread over the
+/// seats not known void, beyond the other spades we can follow with. Zero
+/// once the queen is gone or not ours. Q♠ with two others against ten out
+/// is two short; Q♠ with three or more others is safe.
+fn queen_escape_deficit(ctx: &BotContext<'_>) -> u32 {
+    let hand = ctx.hand();
+    if ctx.round.queen_played() || !hand.contains(queen_of_spades()) {
+        return 0;
+    }
+    let spades = count_cards_in_suit(hand, Suit::Spades);
+    let outstanding = ctx
+        .tracker
+        .unseen_in_suit(Suit::Spades)
+        .saturating_sub(spades);
+    let voids = ctx.tracker.infer_voids(ctx.seat, hand);
+    let active = PlayerPosition::LOOP
+        .iter()
+        .filter(|&&seat| seat != ctx.seat && !voids[seat.index()][Suit::Spades])
+        .count();
+    if active == 0 {
+        return 0;
+    }
+    let leads = outstanding.div_ceil(active);
+    leads.saturating_sub(spades - 1) as u32
+}
+
+/// Discard priority while our Q♠ is short of guards: shed the queen the
+/// first time an off-suit trick allows it, and until then keep the other
+/// spades, which are what we follow spade leads with. Grows with the
+/// deficit, so Q♠xx feels it and Q♠xxxxx does not.
+fn queen_escape_urgency(ctx: &BotContext<'_>, card: Card, lead_suit: Option<Suit>) -> i32 {
+    if card.suit != Suit::Spades || lead_suit.is_none_or(|lead| lead == Suit::Spades) {
+        return 0;
+    }
+    let urgency = weights().queen_escape_weight * queen_escape_deficit(ctx) as i32;
+    if card.is_queen_of_spades() {
+        urgency
+    } else {
+        -urgency
+    }
+}
+
+/// Following the opening clubs trick. It cannot carry points, so winning
+/// it is free and the usual pull toward the lowest club only wastes the
+/// chance to unload a high one. When the card takes the trick and the hand
+/// then has a short side suit to lead at trick two, the capture cost is
+/// lifted and higher winners score more; a card that loses the trick has
+/// its follow-rank cost lifted so the highest losing club is dumped. A
+/// winning card with no lead plan keeps its capture cost.
+fn first_trick_club_adjustment(
+    ctx: &BotContext<'_>,
+    card: Card,
+    lead_suit: Option<Suit>,
+    will_capture: bool,
+    penalties: u8,
+) -> i32 {
+    if !ctx.round.is_first_trick()
+        || lead_suit != Some(Suit::Clubs)
+        || card.suit != Suit::Clubs
+        || penalties > 0
+    {
+        return 0;
+    }
+    let w = weights();
+    let rank = card.rank.value() as i32;
+    let unload = w.first_trick_unload_weight * rank / 14;
+    if !will_capture {
+        return rank * w.follow_high_rank_multiplier + unload;
+    }
+    if !first_trick_lead_plan(ctx, card) {
+        return 0;
+    }
+    w.base_capture_penalty
+        + w.base_shed_bonus
+        + rank * (w.clean_trick_rank_multiplier + w.follow_high_rank_multiplier)
+        + unload
+}
+
+/// Whether taking trick one with `card` leaves a lead worth having at trick
+/// two: a side suit of one or two cards that can be led toward a void.
+/// Spades count only without A♠, K♠ or Q♠ among them, since leading those
+/// invites the queen.
+fn first_trick_lead_plan(ctx: &BotContext<'_>, card: Card) -> bool {
+    let hand = ctx.hand();
+    [Suit::Clubs, Suit::Diamonds, Suit::Spades]
+        .into_iter()
+        .any(|suit| {
+            let held: Vec<Card> = hand
+                .iter()
+                .copied()
+                .filter(|c| c.suit == suit && *c != card)
+                .collect();
+            let short = matches!(held.len(), 1 | 2);
+            short && (suit != Suit::Spades || held.iter().all(|c| c.rank < Rank::Queen))
+        })
+}
+
+/// Q♠ drop plan while the queen is well guarded (4+ other spades in hand).
+///
+/// Returns `Some(true)` when the spade trick is currently won by the point
+/// leader with a card above the queen, `Some(false)` when someone else holds
+/// it that way (keep the guards and wait), and `None` when the plan does not apply.
+fn guarded_queen_drop_target(
+    ctx: &BotContext<'_>,
+    snapshot: &super::ScoreSnapshot,
+) -> Option<bool> {
+    let trick = ctx.trick_view();
+    if trick.led_suit() != Some(Suit::Spades) || snapshot.max_player == ctx.seat {
+        return None;
+    }
+    let hand = ctx.hand();
+    if !hand.contains(queen_of_spades()) {
+        return None;
+    }
+    let guards = hand
+        .iter()
+        .filter(|c| c.suit == Suit::Spades && c.rank != Rank::Queen)
+        .count();
+    if guards < 4 {
+        return None;
+    }
+    let winner = trick.current_winner()?;
+    if trick.winning_card()?.rank <= Rank::Queen {
+        return None;
+    }
+    Some(ctx.scores.score(winner) == snapshot.max_score)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn base_score(
+    ctx: &BotContext<'_>,
+    card: Card,
+    winner: PlayerPosition,
+    will_capture: bool,
+    penalties: u8,
+    lead_suit: Option<Suit>,
+    style: BotStyle,
+    snapshot: &super::ScoreSnapshot,
+    limit_ms: Option<u32>,
+) -> i32 {
+    let penalties_i32 = penalties as i32;
+    let mut score: i32 = 0;
+    // Current trick penalties already on table before our play
+    let penalties_on_table_now = ctx.trick_view().points_on_table() as i32;
+    let limit_ms = limit_ms.unwrap_or(0);
+    let mix_hint = mix_hint_for_play();
+
+    if will_capture {
+        score -= weights().base_capture_penalty;
+        score -= penalties_i32 * weights().base_capture_perpen;
+    } else {
+        score += weights().base_shed_bonus;
+        score += penalties_i32 * weights().base_shed_perpen;
+    }
+
+    if penalties == 0 && will_capture {
+        // Winning a clean trick is still mildly negative to keep low profile.
+        score -= (card.rank.value() as i32) * weights().clean_trick_rank_multiplier;
+    }
+
+    if let Some(lead) = lead_suit
+        && card.suit != lead
+        && ctx.trick_view().position_in_trick() > 0
+    {
+        score += weights().off_suit_play_bonus;
+    }
+
+    match style {
+        BotStyle::AggressiveMoon => {
+            if will_capture {
+                score +=
+                    weights().moon_capture_bonus + penalties_i32 * weights().moon_capture_perpen;
+            } else {
+                score -= penalties_i32 * weights().moon_shed_perpen;
+            }
+        }
+        BotStyle::HuntLeader => {
+            if !will_capture && penalties > 0 && winner == snapshot.max_player {
+                score += penalties_i32 * weights().hunt_feed_perpen;
+            }
+            if will_capture {
+                score -= 1_000;
+            }
+        }
+        BotStyle::Cautious => {}
+    }
+
+    let leader_gap = snapshot
+        .max_score
+        .saturating_sub(ctx.scores.score(ctx.seat));
+    let moon_state = ctx.tracker.moon_state(ctx.seat);
+    let under_moon_pressure = matches!(moon_state, MoonState::Considering | MoonState::Committed);
+    let gap_units = (leader_gap.min(30) / 10) as i32; // 0..=3
+    let leader_feed_per = weights().leader_feed_base + gap_units * weights().leader_feed_gap_per10;
+    // Planner-level leader targeting: small positive even before near-100 scenarios.
+    if snapshot.max_player != ctx.seat
+        && !will_capture
+        && penalties > 0
+        && ctx.scores.score(winner) == snapshot.max_score
+        && snapshot.leader_gap > 0
+    {
+        score += penalties_i32 * leader_feed_per;
+    }
+
+    if under_moon_pressure && will_capture && penalties > 0 {
+        score -= penalties_i32 * 90;
+    }
+
+    score -= exit_preservation_penalty(ctx, card, lead_suit);
+    score -= received_pass_lead_penalty(ctx, card, lead_suit);
+    score += passed_card_lead_adjustment(ctx, card, lead_suit);
+    score -= danger_suit_lead_penalty(ctx, card, lead_suit, will_capture);
+    score += high_spade_adjustment(ctx, card, lead_suit, style);
+    score += queen_escape_urgency(ctx, card, lead_suit);
+    score += first_trick_club_adjustment(ctx, card, lead_suit, will_capture, penalties);
+
+    // Controlled Q♠ timing: release it onto the point leader, otherwise keep it guarded.
+    if card.is_queen_of_spades() && !will_capture {
+        match guarded_queen_drop_target(ctx, snapshot) {
+            Some(true) => score += weights().qs_drop_leader_bonus,
+            Some(false) => score -= weights().qs_guard_preserve_penalty,
+            None => {}
+        }
     }
 
     if matches!(
@@ -1097,10 +1432,12 @@ fn base_score(
 
     // Endgame nuance
     let my_score = ctx.scores.score(ctx.seat);
+    let near_target = ctx.threshold(ctx.params.near_target_pct);
+    let self_danger = my_score >= ctx.threshold(ctx.params.self_danger_pct);
     let cards_left = ctx.hand().len() as i32;
     // If someone else is near 100 and we can feed them, mildly prefer it in all styles.
     if snapshot.max_player != ctx.seat
-        && snapshot.max_score >= 90
+        && snapshot.max_score >= near_target
         && !will_capture
         && penalties > 0
         && winner == snapshot.max_player
@@ -1108,7 +1445,7 @@ fn base_score(
         score += penalties_i32 * (400 + (20 * (10 - cards_left.max(1))))
     }
     // If we are near 100, avoid captures even more.
-    if my_score >= 85 {
+    if self_danger {
         if will_capture {
             score -= weights().near100_self_capture_base + penalties_i32 * 900;
         } else {
@@ -1118,7 +1455,9 @@ fn base_score(
     let moon_pressure = detect_moon_pressure(ctx, snapshot);
     if moon_pressure {
         let trick_pen = ctx.round.current_trick().penalty_total() as i32;
-        let leader_pressure = (snapshot.max_score as i32).saturating_sub(70).max(0);
+        let leader_pressure = (snapshot.max_score as i32)
+            .saturating_sub(ctx.threshold(ctx.params.leader_pressure_pct) as i32)
+            .max(0);
         let moon_base = 1500 + leader_pressure * 20 + trick_pen * 15;
         if will_capture {
             // Strongly discourage taking penalty tricks while others threaten to shoot.
@@ -1133,13 +1472,13 @@ fn base_score(
     }
     // Phase B (Hard-only default): tiny extra penalty when near 100 and we would capture points now.
     if matches!(ctx.difficulty, super::BotDifficulty::FutureHard)
-        && my_score >= 85
+        && self_danger
         && will_capture
         && penalties_on_table_now > 0
         && !ctx.round.is_first_trick()
     {
         score -= penalties_i32 * 30;
-        if snapshot.max_score >= 90 {
+        if snapshot.max_score >= near_target {
             score -= penalties_i32 * 20;
         }
     }
@@ -1212,50 +1551,144 @@ struct Weights {
     moon_capture_bonus: i32,
     moon_capture_perpen: i32,
     moon_shed_perpen: i32,
+    qs_drop_leader_bonus: i32,
+    qs_guard_preserve_penalty: i32,
+    exit_preservation_weight: i32,
+    pass_short_lead_weight: i32,
+    pass_inference_lead_weight: i32,
+    danger_suit_weight: i32,
+    high_spade_liability_weight: i32,
+    queen_escape_weight: i32,
+    first_trick_unload_weight: i32,
 }
 
 fn parse_env_i32(key: &str) -> Option<i32> {
     std::env::var(key).ok().and_then(|s| s.parse::<i32>().ok())
 }
 
+thread_local! {
+    static WEIGHT_OVERRIDE: Cell<Option<&'static Weights>> = const { Cell::new(None) };
+}
+
 fn weights() -> &'static Weights {
+    if let Some(overridden) = WEIGHT_OVERRIDE.with(Cell::get) {
+        return overridden;
+    }
     static CACHED: OnceLock<Weights> = OnceLock::new();
-    CACHED.get_or_init(|| Weights {
-        // Existing weights
-        off_suit_dump_bonus: parse_env_i32("MDH_W_OFFSUIT_BONUS").unwrap_or(600),
-        cards_played_bias: parse_env_i32("MDH_W_CARDS_PLAYED").unwrap_or(10),
-        early_hearts_lead_caution: parse_env_i32("MDH_W_EARLY_HEARTS_LEAD").unwrap_or(600),
-        near100_self_capture_base: parse_env_i32("MDH_W_NEAR100_SELF_CAPTURE_BASE").unwrap_or(1300),
-        near100_shed_perpen: parse_env_i32("MDH_W_NEAR100_SHED_PERPEN").unwrap_or(250),
-        hunt_feed_perpen: parse_env_i32("MDH_W_HUNT_FEED_PERPEN").unwrap_or(800),
-        leader_feed_base: parse_env_i32("MDH_W_LEADER_FEED_BASE").unwrap_or(120),
-        nonleader_feed_perpen: parse_env_i32("MDH_W_NONLEADER_FEED_PERPEN").unwrap_or(2000),
-        leader_feed_gap_per10: parse_env_i32("MDH_W_LEADER_FEED_GAP_PER10").unwrap_or(40),
-        endgame_feed_cap_perpen: parse_env_i32("MDH_W_ENDGAME_FEED_CAP").unwrap_or(0),
-        // Newly exposed weights (previously hardcoded)
-        void_creation_bonus: parse_env_i32("MDH_W_VOID_CREATION_BONUS").unwrap_or(750),
-        follow_high_rank_multiplier: parse_env_i32("MDH_W_FOLLOW_HIGH_RANK_MULT").unwrap_or(24),
-        lead_rank_bias_multiplier: parse_env_i32("MDH_W_LEAD_RANK_BIAS_MULT").unwrap_or(10),
-        lead_unbroken_hearts_penalty: parse_env_i32("MDH_W_LEAD_UNBROKEN_HEARTS").unwrap_or(1100),
-        hunt_leader_lead_base: parse_env_i32("MDH_W_HUNT_LEADER_LEAD_BASE").unwrap_or(10000),
-        hunt_leader_lead_perpen: parse_env_i32("MDH_W_HUNT_LEADER_LEAD_PERPEN").unwrap_or(400),
-        moon_lead_hearts_bonus: parse_env_i32("MDH_W_MOON_LEAD_HEARTS_BONUS").unwrap_or(1300),
-        base_capture_penalty: parse_env_i32("MDH_W_BASE_CAPTURE_PENALTY").unwrap_or(4800),
-        base_capture_perpen: parse_env_i32("MDH_W_BASE_CAPTURE_PERPEN").unwrap_or(700),
-        base_shed_bonus: parse_env_i32("MDH_W_BASE_SHED_BONUS").unwrap_or(600),
-        base_shed_perpen: parse_env_i32("MDH_W_BASE_SHED_PERPEN").unwrap_or(500),
-        clean_trick_rank_multiplier: parse_env_i32("MDH_W_CLEAN_TRICK_RANK_MULT").unwrap_or(18),
-        off_suit_play_bonus: parse_env_i32("MDH_W_OFF_SUIT_PLAY_BONUS").unwrap_or(200),
-        moon_capture_bonus: parse_env_i32("MDH_W_MOON_CAPTURE_BONUS").unwrap_or(5500),
-        moon_capture_perpen: parse_env_i32("MDH_W_MOON_CAPTURE_PERPEN").unwrap_or(900),
-        moon_shed_perpen: parse_env_i32("MDH_W_MOON_SHED_PERPEN").unwrap_or(800),
-    })
+    CACHED.get_or_init(|| Weights::from_lookup(parse_env_i32))
+}
+
+impl Weights {
+    fn from_lookup(lookup: impl Fn(&str) -> Option<i32>) -> Self {
+        Weights {
+            // Existing weights
+            off_suit_dump_bonus: lookup("MDH_W_OFFSUIT_BONUS").unwrap_or(600),
+            cards_played_bias: lookup("MDH_W_CARDS_PLAYED").unwrap_or(10),
+            early_hearts_lead_caution: lookup("MDH_W_EARLY_HEARTS_LEAD").unwrap_or(600),
+            near100_self_capture_base: lookup("MDH_W_NEAR100_SELF_CAPTURE_BASE").unwrap_or(1300),
+            near100_shed_perpen: lookup("MDH_W_NEAR100_SHED_PERPEN").unwrap_or(250),
+            hunt_feed_perpen: lookup("MDH_W_HUNT_FEED_PERPEN").unwrap_or(800),
+            leader_feed_base: lookup("MDH_W_LEADER_FEED_BASE").unwrap_or(120),
+            nonleader_feed_perpen: lookup("MDH_W_NONLEADER_FEED_PERPEN").unwrap_or(2000),
+            leader_feed_gap_per10: lookup("MDH_W_LEADER_FEED_GAP_PER10").unwrap_or(40),
+            endgame_feed_cap_perpen: lookup("MDH_W_ENDGAME_FEED_CAP").unwrap_or(0),
+            // Newly exposed weights (previously hardcoded)
+            void_creation_bonus: lookup("MDH_W_VOID_CREATION_BONUS").unwrap_or(750),
+            follow_high_rank_multiplier: lookup("MDH_W_FOLLOW_HIGH_RANK_MULT").unwrap_or(24),
+            lead_rank_bias_multiplier: lookup("MDH_W_LEAD_RANK_BIAS_MULT").unwrap_or(10),
+            lead_unbroken_hearts_penalty: lookup("MDH_W_LEAD_UNBROKEN_HEARTS").unwrap_or(1100),
+            hunt_leader_lead_base: lookup("MDH_W_HUNT_LEADER_LEAD_BASE").unwrap_or(10000),
+            hunt_leader_lead_perpen: lookup("MDH_W_HUNT_LEADER_LEAD_PERPEN").unwrap_or(400),
+            moon_lead_hearts_bonus: lookup("MDH_W_MOON_LEAD_HEARTS_BONUS").unwrap_or(1300),
+            base_capture_penalty: lookup("MDH_W_BASE_CAPTURE_PENALTY").unwrap_or(4800),
+            base_capture_perpen: lookup("MDH_W_BASE_CAPTURE_PERPEN").unwrap_or(700),
+            base_shed_bonus: lookup("MDH_W_BASE_SHED_BONUS").unwrap_or(600),
+            base_shed_perpen: lookup("MDH_W_BASE_SHED_PERPEN").unwrap_or(500),
+            clean_trick_rank_multiplier: lookup("MDH_W_CLEAN_TRICK_RANK_MULT").unwrap_or(18),
+            off_suit_play_bonus: lookup("MDH_W_OFF_SUIT_PLAY_BONUS").unwrap_or(200),
+            moon_capture_bonus: lookup("MDH_W_MOON_CAPTURE_BONUS").unwrap_or(5500),
+            moon_capture_perpen: lookup("MDH_W_MOON_CAPTURE_PERPEN").unwrap_or(900),
+            moon_shed_perpen: lookup("MDH_W_MOON_SHED_PERPEN").unwrap_or(800),
+            qs_drop_leader_bonus: lookup("MDH_W_QS_DROP_LEADER_BONUS").unwrap_or(3000),
+            qs_guard_preserve_penalty: lookup("MDH_W_QS_GUARD_PRESERVE").unwrap_or(30000),
+            exit_preservation_weight: lookup("MDH_W_EXIT_PRESERVATION").unwrap_or(400),
+            pass_short_lead_weight: lookup("MDH_W_PASS_SHORT_LEAD").unwrap_or(350),
+            pass_inference_lead_weight: lookup("MDH_W_PASS_INFERENCE_LEAD").unwrap_or(350),
+            danger_suit_weight: lookup("MDH_W_DANGER_SUIT").unwrap_or(400),
+            high_spade_liability_weight: lookup("MDH_W_HIGH_SPADE_LIABILITY").unwrap_or(450),
+            queen_escape_weight: lookup("MDH_W_QUEEN_ESCAPE").unwrap_or(300),
+            first_trick_unload_weight: lookup("MDH_W_FIRST_TRICK_UNLOAD").unwrap_or(400),
+        }
+    }
+}
+
+/// A complete set of heuristic weights, e.g. loaded from a weight file for
+/// side-by-side comparison against the process-wide (env/default) weights.
+#[derive(Clone, Copy)]
+pub struct WeightSet(&'static Weights);
+
+impl std::fmt::Debug for WeightSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WeightSet(..)")
+    }
+}
+
+impl WeightSet {
+    /// Parses `MDH_W_*=value` lines (`#` comments and blank lines ignored).
+    /// Keys not listed keep their env/default value; unknown keys are an error.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut values: BTreeMap<String, i32> = BTreeMap::new();
+        for (idx, raw) in contents.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected KEY=VALUE", idx + 1))?;
+            let value = value
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| format!("line {}: invalid value '{}'", idx + 1, value.trim()))?;
+            values.insert(key.trim().to_string(), value);
+        }
+        let used: RefCell<Vec<String>> = RefCell::new(Vec::new());
+        let weights = Weights::from_lookup(|key| {
+            let value = values.get(key).copied();
+            if value.is_some() {
+                used.borrow_mut().push(key.to_string());
+            }
+            value.or_else(|| parse_env_i32(key))
+        });
+        let used = used.into_inner();
+        if let Some(unknown) = values.keys().find(|key| !used.contains(key)) {
+            return Err(format!("unknown weight '{unknown}'"));
+        }
+        Ok(Self(Box::leak(Box::new(weights))))
+    }
+
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        Self::parse(&contents).map_err(|err| format!("{}: {err}", path.display()))
+    }
+}
+
+/// Runs `f` with `set` in place of the process-wide weights on this thread.
+pub fn with_weight_set<R>(set: Option<WeightSet>, f: impl FnOnce() -> R) -> R {
+    let Some(WeightSet(weights)) = set else {
+        return f();
+    };
+    let previous = WEIGHT_OVERRIDE.with(|cell| cell.replace(Some(weights)));
+    let out = f();
+    WEIGHT_OVERRIDE.with(|cell| cell.set(previous));
+    out
 }
 
 pub fn debug_weights_string() -> String {
     let w = weights();
     format!(
-        "off_suit_dump_bonus={} cards_played_bias={} early_hearts_lead_caution={} near100_self_capture_base={} near100_shed_perpen={} hunt_feed_perpen={} leader_feed_base={} nonleader_feed_perpen={} leader_feed_gap_per10={} endgame_feed_cap_perpen={} void_creation_bonus={} follow_high_rank_mult={} lead_rank_bias_mult={} lead_unbroken_hearts={} hunt_leader_lead_base={} hunt_leader_lead_perpen={} moon_lead_hearts_bonus={} base_capture_penalty={} base_capture_perpen={} base_shed_bonus={} base_shed_perpen={} clean_trick_rank_mult={} off_suit_play_bonus={} moon_capture_bonus={} moon_capture_perpen={} moon_shed_perpen={}",
+        "off_suit_dump_bonus={} cards_played_bias={} early_hearts_lead_caution={} near100_self_capture_base={} near100_shed_perpen={} hunt_feed_perpen={} leader_feed_base={} nonleader_feed_perpen={} leader_feed_gap_per10={} endgame_feed_cap_perpen={} void_creation_bonus={} follow_high_rank_mult={} lead_rank_bias_mult={} lead_unbroken_hearts={} hunt_leader_lead_base={} hunt_leader_lead_perpen={} moon_lead_hearts_bonus={} base_capture_penalty={} base_capture_perpen={} base_shed_bonus={} base_shed_perpen={} clean_trick_rank_mult={} off_suit_play_bonus={} moon_capture_bonus={} moon_capture_perpen={} moon_shed_perpen={} qs_drop_leader_bonus={} qs_guard_preserve={} exit_preservation={} pass_short_lead={} pass_inference_lead={} danger_suit={} high_spade_liability={} queen_escape={} first_trick_unload={}",
         w.off_suit_dump_bonus,
         w.cards_played_bias,
         w.early_hearts_lead_caution,
@@ -1281,7 +1714,16 @@ pub fn debug_weights_string() -> String {
         w.off_suit_play_bonus,
         w.moon_capture_bonus,
         w.moon_capture_perpen,
-        w.moon_shed_perpen
+        w.moon_shed_perpen,
+        w.qs_drop_leader_bonus,
+        w.qs_guard_preserve_penalty,
+        w.exit_preservation_weight,
+        w.pass_short_lead_weight,
+        w.pass_inference_lead_weight,
+        w.danger_suit_weight,
+        w.high_spade_liability_weight,
+        w.queen_escape_weight,
+        w.first_trick_unload_weight
     )
 }
 
@@ -1400,7 +1842,7 @@ fn choose_followup_card(
             .any(|c| c.suit == lead && c.rank.value() > current_lead_rank);
         if can_overcall
             && hard_stage2_enabled()
-            && let Some(current_winner) = provisional_winner(round)
+            && let Some(current_winner) = TrickView::new(round.current_trick()).current_winner()
             && current_winner != seat
         {
             let round_totals = round.penalty_totals();
@@ -1500,7 +1942,7 @@ fn choose_followup_card(
         // Can't follow: dump strategy.
         // Bias towards dumping hearts if broken; otherwise queen of spades, then max penalty.
         let hearts_void = !legal.iter().any(|c| c.suit == Suit::Hearts);
-        let provisional = provisional_winner(round);
+        let provisional = TrickView::new(round.current_trick()).current_winner();
         let giving_to_origin = provisional == Some(origin);
         // Avoid giving points to origin (the player we are simulating for) to prevent self-dump skew
         if giving_to_origin {
@@ -1911,7 +2353,7 @@ fn simulate_lead_outcome(
                         let summary: Vec<String> = trick
                             .plays()
                             .iter()
-                            .map(|p| format!("{:?}:{:?}", p.position, p.card))
+                            .map(|p| format!("{:?}:{}", p.position, p.card))
                             .collect();
                         eprintln!(
                             "mdhearts:test_followup simulate_lead_outcome trick winner={winner:?} plays={:?}",
@@ -2048,15 +2490,7 @@ fn opponents_can_overcall_on_lead(round: &RoundState, seat: PlayerPosition, card
 }
 
 fn legal_moves_for(round: &RoundState, seat: PlayerPosition) -> Vec<Card> {
-    let legal: Vec<Card> = round
-        .hand(seat)
-        .iter()
-        .copied()
-        .filter(|card| {
-            let mut probe = round.clone();
-            probe.play_card(seat, *card).is_ok()
-        })
-        .collect();
+    let legal = round.legal_plays(seat);
     if !legal.is_empty() {
         return legal;
     }
@@ -2079,17 +2513,6 @@ fn compare_penalty_dump(a: Card, b: Card) -> Ordering {
     weight(a).cmp(&weight(b))
 }
 
-fn provisional_winner(round: &RoundState) -> Option<PlayerPosition> {
-    let trick = round.current_trick();
-    let lead = trick.lead_suit()?;
-    trick
-        .plays()
-        .iter()
-        .filter(|p| p.card.suit == lead)
-        .max_by_key(|p| p.card.rank.value())
-        .map(|p| p.position)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2104,6 +2527,7 @@ mod tests {
     use hearts_core::model::score::ScoreBoard;
     use hearts_core::model::suit::Suit;
     use hearts_core::model::trick::Trick;
+    use hearts_core::testkit::{RoundBuilder, ScoreBuilder, card};
     use std::sync::{Mutex, MutexGuard};
 
     static ENV_GUARD: Mutex<()> = Mutex::new(());
@@ -2112,6 +2536,27 @@ mod tests {
         ENV_GUARD.lock().unwrap()
     }
 
+    #[test]
+    fn weight_set_overrides_listed_weights_on_this_thread_only() {
+        let baseline = debug_weights_string();
+        let set = WeightSet::parse("# candidate\n\nMDH_W_OFFSUIT_BONUS = 17\n").unwrap();
+        let inside = with_weight_set(Some(set), debug_weights_string);
+        assert!(inside.contains("off_suit_dump_bonus=17 "));
+        assert_eq!(debug_weights_string(), baseline);
+        assert_eq!(with_weight_set(None, debug_weights_string), baseline);
+    }
+
+    #[test]
+    fn weight_set_rejects_unknown_keys_and_bad_lines() {
+        assert!(
+            WeightSet::parse("MDH_W_NOT_A_WEIGHT=1")
+                .unwrap_err()
+                .contains("MDH_W_NOT_A_WEIGHT")
+        );
+        assert!(WeightSet::parse("MDH_W_OFFSUIT_BONUS").is_err());
+        assert!(WeightSet::parse("MDH_W_OFFSUIT_BONUS=lots").is_err());
+    }
+
     fn build_round(
         starting: PlayerPosition,
         hands_vec: [Vec<Card>; 4],
@@ -2178,13 +2623,7 @@ mod tests {
     }
 
     fn build_scores(values: [u32; 4]) -> ScoreBoard {
-        let mut scores = ScoreBoard::new();
-        for (idx, value) in values.iter().enumerate() {
-            if let Some(pos) = PlayerPosition::from_index(idx) {
-                scores.set_score(pos, *value);
-            }
-        }
-        scores
+        ScoreBuilder::new().totals(values).build()
     }
 
     fn make_ctx<'a>(
@@ -2207,24 +2646,14 @@ mod tests {
     #[test]
     fn cautious_dumps_points_when_safe() {
         let seat = PlayerPosition::South;
-        let round = build_round(
-            PlayerPosition::North,
-            [
-                vec![Card::new(Rank::King, Suit::Clubs)],
-                vec![Card::new(Rank::Ace, Suit::Clubs)],
-                vec![
-                    Card::new(Rank::Queen, Suit::Hearts),
-                    Card::new(Rank::Ten, Suit::Hearts),
-                    Card::new(Rank::Three, Suit::Spades),
-                ],
-                vec![Card::new(Rank::Two, Suit::Clubs)],
-            ],
-            &[
-                (PlayerPosition::North, Card::new(Rank::King, Suit::Clubs)),
-                (PlayerPosition::East, Card::new(Rank::Ace, Suit::Clubs)),
-            ],
-            false,
-        );
+        let round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "9D")
+            .seat_hand(PlayerPosition::East, "8D")
+            .seat_hand(PlayerPosition::South, "QH 10H 3S")
+            .seat_hand(PlayerPosition::West, "7C")
+            .trick(PlayerPosition::North, "3C 4C 5C 6C")
+            .current(PlayerPosition::North, "KC AC")
+            .build();
 
         let scores = build_scores([20, 18, 22, 19]);
         let mut tracker = UnseenTracker::new();
@@ -2245,6 +2674,43 @@ mod tests {
         assert!(choice.penalty_value() > 0);
     }
 
+    #[test]
+    fn a_short_target_brings_the_near_target_shed_forward() {
+        // 45 is 90% of a 50-point match, but far from 100.
+        let seat = PlayerPosition::South;
+        let round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "9D")
+            .seat_hand(PlayerPosition::East, "8D")
+            .seat_hand(PlayerPosition::South, "QH 10H 3S")
+            .seat_hand(PlayerPosition::West, "7C")
+            .trick(PlayerPosition::North, "3C 4C 5C 6C")
+            .current(PlayerPosition::North, "KC AC")
+            .build();
+        let scores = build_scores([10, 12, 45, 14]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let legal = legal_moves_for(&round, seat);
+        let shed_bonus = |target: u32| {
+            let ctx = make_ctx(
+                seat,
+                &round,
+                &scores,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            )
+            .with_target_score(target);
+            let scored = score_plays(&legal, &ctx, None);
+            let queen = scored.iter().find(|play| play.card == card("QH")).unwrap();
+            queen
+                .parts
+                .iter()
+                .find(|part| part.name == "near100_shed_bonus")
+                .map(|part| part.delta)
+        };
+        assert_eq!(shed_bonus(100), None);
+        assert_eq!(shed_bonus(50), Some(300));
+    }
+
     #[test]
     fn cautious_avoids_capturing_points_when_possible() {
         let seat = PlayerPosition::South;
@@ -3330,4 +3796,885 @@ mod tests {
         let choice = PlayPlanner::choose(&legal, &ctx).unwrap();
         assert_eq!(choice, Card::new(Rank::Queen, Suit::Hearts));
     }
+
+    fn guarded_queen_spade_round() -> RoundState {
+        build_round(
+            PlayerPosition::North,
+            [
+                vec![Card::new(Rank::Two, Suit::Clubs)],
+                vec![Card::new(Rank::Three, Suit::Clubs)],
+                vec![
+                    Card::new(Rank::Queen, Suit::Spades),
+                    Card::new(Rank::Two, Suit::Spades),
+                    Card::new(Rank::Four, Suit::Spades),
+                    Card::new(Rank::Five, Suit::Spades),
+                    Card::new(Rank::Six, Suit::Spades),
+                ],
+                vec![
+                    Card::new(Rank::Seven, Suit::Spades),
+                    Card::new(Rank::Four, Suit::Clubs),
+                ],
+            ],
+            &[
+                (PlayerPosition::North, Card::new(Rank::King, Suit::Spades)),
+                (PlayerPosition::East, Card::new(Rank::Three, Suit::Spades)),
+            ],
+            false,
+        )
+    }
+
+    #[test]
+    fn guarded_queen_dropped_on_point_leader() {
+        let seat = PlayerPosition::South;
+        let round = guarded_queen_spade_round();
+        let scores = build_scores([60, 20, 25, 30]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let legal = legal_moves_for(&round, seat);
+        let choice = PlayPlanner::choose(&legal, &ctx).unwrap();
+        assert_eq!(choice, queen_of_spades());
+    }
+
+    #[test]
+    fn guarded_queen_kept_when_low_scorer_winning() {
+        let seat = PlayerPosition::South;
+        let round = guarded_queen_spade_round();
+        let scores = build_scores([10, 20, 25, 60]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let legal = legal_moves_for(&round, seat);
+        let choice = PlayPlanner::choose(&legal, &ctx).unwrap();
+        assert_ne!(choice, queen_of_spades());
+        assert_eq!(choice.suit, Suit::Spades);
+        let explained = PlayPlanner::explain_candidates(&legal, &ctx);
+        assert_eq!(explained.first().map(|(c, _)| *c), Some(choice));
+    }
+
+    fn exit_card_lead_round() -> RoundState {
+        build_round(
+            PlayerPosition::South,
+            [
+                vec![
+                    Card::new(Rank::Queen, Suit::Diamonds),
+                    Card::new(Rank::Seven, Suit::Clubs),
+                    Card::new(Rank::Two, Suit::Spades),
+                    Card::new(Rank::Nine, Suit::Hearts),
+                    Card::new(Rank::Five, Suit::Diamonds),
+                    Card::new(Rank::Jack, Suit::Spades),
+                    Card::new(Rank::Eight, Suit::Clubs),
+                ],
+                vec![
+                    Card::new(Rank::Seven, Suit::Diamonds),
+                    Card::new(Rank::Jack, Suit::Clubs),
+                    Card::new(Rank::Three, Suit::Spades),
+                    Card::new(Rank::Ten, Suit::Hearts),
+                    Card::new(Rank::Four, Suit::Diamonds),
+                    Card::new(Rank::Queen, Suit::Spades),
+                    Card::new(Rank::Two, Suit::Hearts),
+                ],
+                vec![
+                    Card::new(Rank::Three, Suit::Diamonds),
+                    Card::new(Rank::King, Suit::Diamonds),
+                    Card::new(Rank::Ace, Suit::Diamonds),
+                    Card::new(Rank::Four, Suit::Clubs),
+                    Card::new(Rank::Five, Suit::Clubs),
+                    Card::new(Rank::Ten, Suit::Clubs),
+                    Card::new(Rank::King, Suit::Spades),
+                ],
+                vec![
+                    Card::new(Rank::Nine, Suit::Diamonds),
+                    Card::new(Rank::Queen, Suit::Clubs),
+                    Card::new(Rank::Four, Suit::Spades),
+                    Card::new(Rank::Jack, Suit::Hearts),
+                    Card::new(Rank::Six, Suit::Diamonds),
+                    Card::new(Rank::Five, Suit::Spades),
+                    Card::new(Rank::Three, Suit::Hearts),
+                ],
+            ],
+            &[],
+            false,
+        )
+    }
+
+    #[test]
+    fn exit_card_kept_when_leading_early() {
+        let _guard = env_lock();
+        let seat = PlayerPosition::South;
+        let round = exit_card_lead_round();
+        let scores = build_scores([10, 12, 8, 14]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let legal = legal_moves_for(&round, seat);
+        let three_diamonds = Card::new(Rank::Three, Suit::Diamonds);
+
+        // Without the term the lowest lead wins and burns the only low diamond.
+        let naive = WeightSet::parse("MDH_W_EXIT_PRESERVATION=0").unwrap();
+        let naive_choice =
+            with_weight_set(Some(naive), || PlayPlanner::choose(&legal, &ctx)).unwrap();
+        assert_eq!(naive_choice, three_diamonds);
+
+        let choice = PlayPlanner::choose(&legal, &ctx).unwrap();
+        assert_ne!(choice, three_diamonds);
+        assert_ne!(choice.suit, Suit::Diamonds);
+        assert!(choice.rank.value() < 8);
+    }
+
+    #[test]
+    fn burning_the_only_low_diamond_costs_the_exit_weight() {
+        let _guard = env_lock();
+        let seat = PlayerPosition::South;
+        let round = exit_card_lead_round();
+        let scores = build_scores([10, 12, 8, 14]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let weight = weights().exit_preservation_weight;
+        let card = |rank, suit| Card::new(rank, suit);
+
+        // 3♦ is the only diamond below 8, with K♦ and A♦ left behind it.
+        assert_eq!(
+            exit_preservation_penalty(&ctx, card(Rank::Three, Suit::Diamonds), None),
+            weight
+        );
+        // Discarding it on another suit's lead burns it just the same.
+        assert_eq!(
+            exit_preservation_penalty(&ctx, card(Rank::Three, Suit::Diamonds), Some(Suit::Spades)),
+            weight
+        );
+        // Following suit with it is what it is kept for.
+        assert_eq!(
+            exit_preservation_penalty(
+                &ctx,
+                card(Rank::Three, Suit::Diamonds),
+                Some(Suit::Diamonds)
+            ),
+            0
+        );
+        // The high diamonds are not exits, and clubs keep a second low card.
+        assert_eq!(
+            exit_preservation_penalty(&ctx, card(Rank::King, Suit::Diamonds), None),
+            0
+        );
+        assert_eq!(
+            exit_preservation_penalty(&ctx, card(Rank::Four, Suit::Clubs), None),
+            0
+        );
+    }
+
+    fn moon_runaway_round() -> RoundState {
+        let mut history = Vec::new();
+        for plays in [
+            [
+                (PlayerPosition::West, Card::new(Rank::Ace, Suit::Hearts)),
+                (PlayerPosition::North, Card::new(Rank::Queen, Suit::Hearts)),
+                (PlayerPosition::East, Card::new(Rank::Jack, Suit::Hearts)),
+                (PlayerPosition::South, Card::new(Rank::Ten, Suit::Hearts)),
+            ],
+            [
+                (PlayerPosition::West, Card::new(Rank::Eight, Suit::Hearts)),
+                (PlayerPosition::North, Card::new(Rank::Seven, Suit::Hearts)),
+                (PlayerPosition::East, Card::new(Rank::Six, Suit::Hearts)),
+                (PlayerPosition::South, Card::new(Rank::Five, Suit::Hearts)),
+            ],
+            [
+                (PlayerPosition::West, Card::new(Rank::Ace, Suit::Spades)),
+                (PlayerPosition::North, Card::new(Rank::Queen, Suit::Spades)),
+                (PlayerPosition::East, Card::new(Rank::Three, Suit::Hearts)),
+                (PlayerPosition::South, Card::new(Rank::Two, Suit::Spades)),
+            ],
+        ] {
+            let mut trick = Trick::new(PlayerPosition::West);
+            for (seat, card) in plays {
+                trick.play(seat, card).unwrap();
+            }
+            history.push(trick);
+        }
+        let mut current = Trick::new(PlayerPosition::North);
+        current
+            .play(PlayerPosition::North, Card::new(Rank::Four, Suit::Hearts))
+            .unwrap();
+        let hands = [
+            Hand::with_cards(vec![
+                Card::new(Rank::Ten, Suit::Clubs),
+                Card::new(Rank::Jack, Suit::Clubs),
+            ]),
+            Hand::with_cards(vec![
+                Card::new(Rank::Three, Suit::Clubs),
+                Card::new(Rank::Two, Suit::Hearts),
+                Card::new(Rank::King, Suit::Hearts),
+            ]),
+            Hand::with_cards(vec![
+                Card::new(Rank::Seven, Suit::Diamonds),
+                Card::new(Rank::Eight, Suit::Diamonds),
+                Card::new(Rank::Nine, Suit::Diamonds),
+            ]),
+            Hand::with_cards(vec![
+                Card::new(Rank::Five, Suit::Clubs),
+                Card::new(Rank::Six, Suit::Clubs),
+                Card::new(Rank::Nine, Suit::Hearts),
+            ]),
+        ];
+        RoundState::from_hands_with_state(
+            hands,
+            PlayerPosition::North,
+            PassingDirection::Hold,
+            RoundPhase::Playing,
+            current,
+            history,
+            true,
+        )
+    }
+
+    #[test]
+    fn moon_emergency_takes_cheapest_point_trick() {
+        let seat = PlayerPosition::East;
+        let round = moon_runaway_round();
+        assert_eq!(
+            crate::bot::only_point_taker(&round),
+            Some(PlayerPosition::West)
+        );
+        assert_eq!(crate::bot::hearts_remaining(&round), 4);
+        let scores = build_scores([10, 20, 30, 40]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let legal = legal_moves_for(&round, seat);
+        let king = Card::new(Rank::King, Suit::Hearts);
+        assert_eq!(PlayPlanner::choose(&legal, &ctx), Some(king));
+
+        // With three cards left the hard planner solves the round exactly:
+        // ducking with 2♥ lets West take this trick, but East can still shed
+        // K♥ on North's club, so West never takes every heart and East
+        // finishes on nothing rather than the three points K♥ would cost.
+        let hard_ctx = make_ctx(seat, &round, &scores, &tracker, BotDifficulty::FutureHard);
+        assert_eq!(
+            crate::bot::PlayPlannerHard::choose(&legal, &hard_ctx),
+            Some(Card::new(Rank::Two, Suit::Hearts))
+        );
+    }
+
+    /// West has taken every point and six hearts are still out, too many for
+    /// the plain moon emergency. East can stop the run with K♥ or duck with 2♥.
+    fn early_moon_suspect_round() -> RoundState {
+        let mut history = Vec::new();
+        for plays in [
+            [
+                (PlayerPosition::West, Card::new(Rank::Ace, Suit::Hearts)),
+                (PlayerPosition::North, Card::new(Rank::Queen, Suit::Hearts)),
+                (PlayerPosition::East, Card::new(Rank::Jack, Suit::Hearts)),
+                (PlayerPosition::South, Card::new(Rank::Ten, Suit::Hearts)),
+            ],
+            [
+                (PlayerPosition::West, Card::new(Rank::Ace, Suit::Spades)),
+                (PlayerPosition::North, Card::new(Rank::Queen, Suit::Spades)),
+                (PlayerPosition::East, Card::new(Rank::Three, Suit::Hearts)),
+                (PlayerPosition::South, Card::new(Rank::Two, Suit::Spades)),
+            ],
+            [
+                (PlayerPosition::West, Card::new(Rank::King, Suit::Spades)),
+                (PlayerPosition::North, Card::new(Rank::Four, Suit::Hearts)),
+                (PlayerPosition::East, Card::new(Rank::Five, Suit::Hearts)),
+                (PlayerPosition::South, Card::new(Rank::Three, Suit::Spades)),
+            ],
+        ] {
+            let mut trick = Trick::new(PlayerPosition::West);
+            for (seat, card) in plays {
+                trick.play(seat, card).unwrap();
+            }
+            history.push(trick);
+        }
+        let mut current = Trick::new(PlayerPosition::North);
+        current
+            .play(PlayerPosition::North, Card::new(Rank::Six, Suit::Hearts))
+            .unwrap();
+        let hands = [
+            Hand::with_cards(vec![
+                Card::new(Rank::Ten, Suit::Clubs),
+                Card::new(Rank::Jack, Suit::Clubs),
+            ]),
+            Hand::with_cards(vec![
+                Card::new(Rank::Three, Suit::Clubs),
+                Card::new(Rank::Two, Suit::Hearts),
+                Card::new(Rank::King, Suit::Hearts),
+            ]),
+            Hand::with_cards(vec![
+                Card::new(Rank::Seven, Suit::Diamonds),
+                Card::new(Rank::Eight, Suit::Diamonds),
+                Card::new(Rank::Nine, Suit::Diamonds),
+            ]),
+            Hand::with_cards(vec![
+                Card::new(Rank::Five, Suit::Clubs),
+                Card::new(Rank::Six, Suit::Clubs),
+                Card::new(Rank::Nine, Suit::Hearts),
+            ]),
+        ];
+        RoundState::from_hands_with_state(
+            hands,
+            PlayerPosition::North,
+            PassingDirection::Hold,
+            RoundPhase::Playing,
+            current,
+            history,
+            true,
+        )
+    }
+
+    #[test]
+    fn moon_tell_from_giver_triggers_early_moon_defense() {
+        let seat = PlayerPosition::East;
+        let round = early_moon_suspect_round();
+        assert_eq!(
+            crate::bot::only_point_taker(&round),
+            Some(PlayerPosition::West)
+        );
+        assert_eq!(crate::bot::hearts_remaining(&round), 6);
+        let scores = build_scores([10, 20, 30, 40]);
+        let legal = legal_moves_for(&round, seat);
+        let king = Card::new(Rank::King, Suit::Hearts);
+
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let style = determine_style(&ctx);
+        let leader = snapshot_scores(scores).max_player;
+        assert_eq!(moon_emergency_card(&legal, &ctx, style, leader), None);
+
+        // West kept its hearts and shed three low diamonds to East.
+        tracker.note_pass_received(
+            seat,
+            PlayerPosition::West,
+            [
+                Card::new(Rank::Two, Suit::Diamonds),
+                Card::new(Rank::Three, Suit::Diamonds),
+                Card::new(Rank::Four, Suit::Diamonds),
+            ],
+        );
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert_eq!(moon_emergency_card(&legal, &ctx, style, leader), Some(king));
+        assert_eq!(PlayPlanner::choose(&legal, &ctx), Some(king));
+
+        // The same pass from a seat that is not running the table changes nothing.
+        let mut other = UnseenTracker::new();
+        other.reset_for_round(&round);
+        other.note_pass_received(
+            seat,
+            PlayerPosition::North,
+            [
+                Card::new(Rank::Two, Suit::Diamonds),
+                Card::new(Rank::Three, Suit::Diamonds),
+                Card::new(Rank::Four, Suit::Diamonds),
+            ],
+        );
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &other,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert_eq!(moon_emergency_card(&legal, &ctx, style, leader), None);
+    }
+
+    #[test]
+    fn early_lead_avoids_suit_the_giver_shortened() {
+        let seat = PlayerPosition::South;
+        let hands = [
+            Hand::with_cards(vec![Card::new(Rank::Two, Suit::Hearts)]),
+            Hand::with_cards(vec![Card::new(Rank::Three, Suit::Hearts)]),
+            Hand::with_cards(vec![
+                Card::new(Rank::King, Suit::Clubs),
+                Card::new(Rank::Queen, Suit::Clubs),
+                Card::new(Rank::Nine, Suit::Diamonds),
+            ]),
+            Hand::with_cards(vec![Card::new(Rank::Four, Suit::Hearts)]),
+        ];
+        let round =
+            RoundState::from_hands(hands, seat, PassingDirection::Hold, RoundPhase::Playing);
+        let scores = build_scores([0, 0, 0, 0]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        tracker.note_pass_received(
+            seat,
+            PlayerPosition::East,
+            [
+                Card::new(Rank::King, Suit::Clubs),
+                Card::new(Rank::Queen, Suit::Clubs),
+                Card::new(Rank::Two, Suit::Spades),
+            ],
+        );
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let king_clubs = Card::new(Rank::King, Suit::Clubs);
+        let nine_diamonds = Card::new(Rank::Nine, Suit::Diamonds);
+        assert!(received_pass_lead_penalty(&ctx, king_clubs, None) > 0);
+        assert_eq!(received_pass_lead_penalty(&ctx, nine_diamonds, None), 0);
+        // East passed K♣ Q♣ and a spade, so it kept nothing of note in hearts.
+        let two_hearts = Card::new(Rank::Two, Suit::Hearts);
+        assert_eq!(received_pass_lead_penalty(&ctx, two_hearts, None), 0);
+        // Following suit is never penalised.
+        assert_eq!(
+            received_pass_lead_penalty(&ctx, king_clubs, Some(Suit::Clubs)),
+            0
+        );
+
+        tracker.note_void(PlayerPosition::East, Suit::Clubs);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert_eq!(received_pass_lead_penalty(&ctx, king_clubs, None), 0);
+    }
+
+    #[test]
+    fn early_lead_follows_what_the_left_hand_seat_did_with_our_pass() {
+        let seat = PlayerPosition::South;
+        let west = PlayerPosition::West;
+        let hands = [
+            Hand::with_cards(vec![Card::new(Rank::Two, Suit::Hearts)]),
+            Hand::with_cards(vec![Card::new(Rank::Three, Suit::Hearts)]),
+            Hand::with_cards(vec![
+                Card::new(Rank::Jack, Suit::Diamonds),
+                Card::new(Rank::Ten, Suit::Clubs),
+            ]),
+            Hand::with_cards(vec![Card::new(Rank::Four, Suit::Hearts)]),
+        ];
+        let round =
+            RoundState::from_hands(hands, seat, PassingDirection::Left, RoundPhase::Playing);
+        let scores = build_scores([0, 0, 0, 0]);
+        let nine = Card::new(Rank::Nine, Suit::Diamonds);
+        let jack = Card::new(Rank::Jack, Suit::Diamonds);
+        let ten_clubs = Card::new(Rank::Ten, Suit::Clubs);
+        // West played the 9♦ South passed it, on a diamond lead or a spade one.
+        let tracker_after = |lead: Suit| {
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(&round);
+            tracker.note_pass_received(
+                west,
+                seat,
+                [
+                    nine,
+                    Card::new(Rank::Three, Suit::Diamonds),
+                    Card::new(Rank::King, Suit::Clubs),
+                ],
+            );
+            tracker.note_card_played(west, nine);
+            tracker.note_passed_card_played(west, nine, 1, lead);
+            tracker
+        };
+        let adjust = |tracker: &UnseenTracker, card: Card, lead: Option<Suit>| {
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                scores,
+                PassingDirection::Left,
+                tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            passed_card_lead_adjustment(&ctx, card, lead)
+        };
+        let led_back = tracker_after(Suit::Diamonds);
+        let discarded = tracker_after(Suit::Spades);
+
+        unsafe {
+            std::env::set_var("MDH_FEATURE_PASS_INFERENCE", "1");
+        }
+        let led_back_jack = adjust(&led_back, jack, None);
+        let discarded_jack = adjust(&discarded, jack, None);
+        let discarded_clubs = adjust(&discarded, ten_clubs, None);
+        let discarded_follow = adjust(&discarded, jack, Some(Suit::Diamonds));
+        unsafe {
+            std::env::remove_var("MDH_FEATURE_PASS_INFERENCE");
+        }
+        // Leading back into West's kept diamonds is safer; into the suit it
+        // is emptying, riskier. Other suits and following are untouched.
+        assert!(led_back_jack > 0, "{led_back_jack}");
+        assert!(discarded_jack < 0, "{discarded_jack}");
+        assert_eq!(discarded_clubs, 0);
+        assert_eq!(discarded_follow, 0);
+        // Off by default.
+        assert_eq!(adjust(&discarded, jack, None), 0);
+    }
+
+    /// South, void in clubs, discards on East's clean A♣ with A♠ 3♠ 9♦ 4♦
+    /// in hand. The first trick either took Q♠ or was a clubs trick with the
+    /// queen still out in West's hand.
+    fn high_spade_discard_round(queen_fallen: bool) -> RoundState {
+        let (seed_trick, west) = if queen_fallen {
+            ("5S QS 6S 7S", "2C 8S")
+        } else {
+            ("3C 4C 5C 6C", "2C QS")
+        };
+        RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "10D")
+            .seat_hand(PlayerPosition::East, "JD")
+            .seat_hand(PlayerPosition::South, "AS 3S 9D 4D")
+            .seat_hand(PlayerPosition::West, west)
+            .trick(PlayerPosition::North, seed_trick)
+            .current(PlayerPosition::North, "KC AC")
+            .build()
+    }
+
+    #[test]
+    fn high_spades_become_safe_once_the_queen_falls() {
+        let seat = PlayerPosition::South;
+        let scores = build_scores([10, 10, 10, 10]);
+        let ace_spades = card("AS");
+
+        let before = high_spade_discard_round(false);
+        assert!(!before.queen_played());
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&before);
+        let ctx = make_ctx(
+            seat,
+            &before,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let weight = weights().high_spade_liability_weight;
+        let clubs = Some(Suit::Clubs);
+        assert_eq!(
+            high_spade_adjustment(&ctx, ace_spades, clubs, BotStyle::Cautious),
+            weight
+        );
+        assert_eq!(
+            high_spade_adjustment(&ctx, ace_spades, None, BotStyle::Cautious),
+            -weight
+        );
+        let legal = legal_moves_for(&before, seat);
+        assert_eq!(PlayPlanner::choose(&legal, &ctx), Some(ace_spades));
+
+        let after = high_spade_discard_round(true);
+        assert!(after.queen_played());
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&after);
+        let ctx = make_ctx(
+            seat,
+            &after,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert_eq!(
+            high_spade_adjustment(&ctx, ace_spades, clubs, BotStyle::Cautious),
+            -weight
+        );
+        assert_eq!(
+            high_spade_adjustment(&ctx, ace_spades, None, BotStyle::Cautious),
+            0
+        );
+        assert_eq!(
+            high_spade_adjustment(&ctx, ace_spades, None, BotStyle::AggressiveMoon),
+            weight
+        );
+        let legal = legal_moves_for(&after, seat);
+        let choice = PlayPlanner::choose(&legal, &ctx).unwrap();
+        assert_ne!(choice, ace_spades);
+        assert_eq!(choice.suit, Suit::Diamonds);
+    }
+
+    /// South, void in clubs, discards on the opening clubs trick with Q♠ and
+    /// `guards` low spades, A♥ K♥ J♥ and K♦ 4♦, hearts filling the rest.
+    /// Every other card is dealt out among the other seats.
+    fn queen_escape_round(guards: &[&str]) -> RoundState {
+        let mut south: Vec<String> = ["QS", "AH", "KH", "JH", "KD", "4D"]
+            .iter()
+            .chain(guards)
+            .map(|code| code.to_string())
+            .collect();
+        for filler in ["10H", "9H", "8H", "7H", "6H", "5H", "4H"] {
+            if south.len() < 13 {
+                south.push(filler.to_string());
+            }
+        }
+        let rest: Vec<String> = Suit::iter()
+            .flat_map(|suit| Rank::iter().map(move |rank| Card::new(rank, suit)))
+            .map(|card| card.to_string())
+            .filter(|code| !south.contains(code) && code != "2C" && code != "AC")
+            .collect();
+        RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, &rest[..12].join(" "))
+            .seat_hand(PlayerPosition::East, &rest[12..24].join(" "))
+            .seat_hand(PlayerPosition::South, &south.join(" "))
+            .seat_hand(PlayerPosition::West, &rest[24..].join(" "))
+            .current(PlayerPosition::North, "2C AC")
+            .build()
+    }
+
+    #[test]
+    fn short_queen_keeps_its_guards_on_discards() {
+        let seat = PlayerPosition::South;
+        let scores = build_scores([10, 40, 20, 30]);
+        let weight = weights().queen_escape_weight;
+        let clubs = Some(Suit::Clubs);
+
+        let short = queen_escape_round(&["3S", "7S"]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&short);
+        let ctx = make_ctx(
+            seat,
+            &short,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert_eq!(queen_escape_deficit(&ctx), 2);
+        assert_eq!(queen_escape_urgency(&ctx, card("3S"), clubs), -2 * weight);
+        assert_eq!(queen_escape_urgency(&ctx, card("QS"), clubs), 2 * weight);
+        assert_eq!(queen_escape_urgency(&ctx, card("3S"), None), 0);
+        assert_eq!(queen_escape_urgency(&ctx, card("KD"), clubs), 0);
+        let legal = legal_moves_for(&short, seat);
+        let scored = PlayPlanner::explain_candidates(&legal, &ctx);
+        let score_of = |code: &str| scored.iter().find(|(c, _)| *c == card(code)).unwrap().1;
+        assert!(score_of("KD") > score_of("3S"));
+        assert_eq!(PlayPlanner::choose(&legal, &ctx), Some(card("KD")));
+
+        let long = queen_escape_round(&["3S", "4S", "5S", "6S", "7S"]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&long);
+        let ctx = make_ctx(
+            seat,
+            &long,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert_eq!(queen_escape_deficit(&ctx), 0);
+        assert_eq!(queen_escape_urgency(&ctx, card("3S"), clubs), 0);
+        let legal = legal_moves_for(&long, seat);
+        let scored = PlayPlanner::explain_candidates(&legal, &ctx);
+        let score_of = |code: &str| scored.iter().find(|(c, _)| *c == card(code)).unwrap().1;
+        assert_eq!(score_of("KD"), score_of("3S"));
+    }
+
+    #[test]
+    fn short_queen_deficit_shrinks_as_opponents_run_out() {
+        let seat = PlayerPosition::South;
+        let scores = build_scores([10, 40, 20, 30]);
+        let round = queen_escape_round(&["3S", "7S"]);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        tracker.note_void(PlayerPosition::East, Suit::Spades);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        // Ten spades out between two seats: five leads against two guards.
+        assert_eq!(queen_escape_deficit(&ctx), 3);
+    }
+
+    /// Trick one after North's 2♣ and East's 3♣, South to play. West holds
+    /// only high clubs, so any South club below Q♣ loses the trick.
+    fn first_trick_round(south: &str) -> RoundState {
+        let west = "QC KC 2D 3D 4D 5D 6D 2S 3S 5S 2H 4H 6H";
+        let taken: Vec<&str> = south.split(' ').chain(west.split(' ')).collect();
+        let rest: Vec<String> = Suit::iter()
+            .flat_map(|suit| Rank::iter().map(move |rank| Card::new(rank, suit)))
+            .map(|card| card.to_string())
+            .filter(|code| !taken.contains(&code.as_str()) && code != "2C" && code != "3C")
+            .collect();
+        RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, &rest[..12].join(" "))
+            .seat_hand(PlayerPosition::East, &rest[12..].join(" "))
+            .seat_hand(PlayerPosition::South, south)
+            .seat_hand(PlayerPosition::West, west)
+            .current(PlayerPosition::North, "2C 3C")
+            .build()
+    }
+
+    #[test]
+    fn first_trick_takes_the_lead_for_a_singleton_or_dumps_a_high_club() {
+        let seat = PlayerPosition::South;
+        let scores = build_scores([0, 0, 0, 0]);
+        let choose = |round: &RoundState| {
+            assert!(round.is_first_trick());
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(round);
+            let ctx = make_ctx(
+                seat,
+                round,
+                &scores,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            assert_eq!(
+                first_trick_club_adjustment(&ctx, card("9D"), Some(Suit::Clubs), false, 0),
+                0
+            );
+            let legal = legal_moves_for(round, seat);
+            (
+                first_trick_lead_plan(&ctx, card("AC")),
+                PlayPlanner::choose(&legal, &ctx),
+            )
+        };
+
+        // A♣ wins the free trick and 9♦ is led next to void diamonds.
+        let singleton = first_trick_round("AC 5C 9D 4S 6S 8S 10S JS 3H 5H 7H 9H JH");
+        assert_eq!(choose(&singleton), (true, Some(card("AC"))));
+
+        // No short suit to lead: 9♣ is the highest club that loses.
+        let flat = first_trick_round("AC 9C 5C 4C 9D 8D 7D 4S 6S 8S 3H 5H 7H");
+        assert_eq!(choose(&flat), (false, Some(card("9C"))));
+
+        // A short spade suit headed by the king is no lead plan.
+        let king = first_trick_round("AC 9C 5C 4C 9D 8D 7D KS 6S 3H 5H 7H 9H");
+        assert_eq!(choose(&king), (false, Some(card("9C"))));
+    }
+
+    #[test]
+    fn omnibus_takes_the_trick_with_the_jack_of_diamonds() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "2C 9D 3S 4S")
+            .seat_hand(PlayerPosition::East, "3C JD 5S 6S")
+            .seat_hand(PlayerPosition::South, "4C QD 3D 8S")
+            .seat_hand(PlayerPosition::West, "5C 2D 9S 10S")
+            .build();
+        for played in hearts_core::testkit::cards("2C 3C 4C 5C 2D 9D JD") {
+            let seat = hearts_core::testkit::to_play(&round);
+            round.play_card(seat, played).unwrap();
+        }
+        let seat = PlayerPosition::South;
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let legal = round.legal_plays(seat);
+        let choose = |omnibus: bool| {
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                ScoreBoard::new(),
+                PassingDirection::Hold,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            )
+            .with_jack_of_diamonds(omnibus);
+            PlayPlanner::choose(&legal, &ctx)
+        };
+        // The standard game ducks a clean trick; Omnibus wins the jack.
+        assert_eq!(choose(false), Some(card("3D")));
+        assert_eq!(choose(true), Some(card("QD")));
+    }
+
+    #[test]
+    fn leads_into_a_two_suit_voids_concentration_cost_unless_they_win() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "2C 5C 3D 8S 10S 2H 3H")
+            .seat_hand(PlayerPosition::East, "3C 6C 4D 9S JS 4H 5H")
+            .seat_hand(PlayerPosition::South, "AC KC AD 7C 5D 6S AS")
+            .seat_hand(PlayerPosition::West, "2S 3S 4S QS KS 8H 9H")
+            .build();
+        // West shows out of clubs at trick one and of diamonds at trick two;
+        // South wins all three and leads to trick four.
+        for played in hearts_core::testkit::cards("2C 3C AC 2S AD 3S 3D 4D KC 4S 5C 6C") {
+            let seat = hearts_core::testkit::to_play(&round);
+            round.play_card(seat, played).unwrap();
+        }
+        let seat = PlayerPosition::South;
+        assert_eq!(hearts_core::testkit::to_play(&round), seat);
+        let mut tracker = UnseenTracker::new();
+        tracker.rebuild_for_round(&round);
+        assert!(tracker.is_void(PlayerPosition::West, Suit::Clubs));
+        assert!(tracker.is_void(PlayerPosition::West, Suit::Diamonds));
+
+        // Eight spades and thirteen hearts are out, split three ways.
+        let danger = tracker.danger_suits(seat, &round);
+        assert_eq!(danger.0, [0, 0, 38, 62]);
+        // North counts from its own hand: eight spades to eleven hearts.
+        assert_eq!(
+            tracker.danger_suits(PlayerPosition::North, &round).0,
+            [0, 0, 42, 58]
+        );
+
+        let scores = build_scores([0, 0, 0, 0]);
+        let ctx = make_ctx(
+            seat,
+            &round,
+            &scores,
+            &tracker,
+            BotDifficulty::NormalHeuristic,
+        );
+        let weight = weights().danger_suit_weight;
+        assert_eq!(
+            danger_suit_lead_penalty(&ctx, card("6S"), None, false),
+            weight * 38 / 100
+        );
+        // A♠ keeps the lead, clubs are no concentration suit, and following
+        // is not leading.
+        assert_eq!(danger_suit_lead_penalty(&ctx, card("AS"), None, true), 0);
+        assert_eq!(danger_suit_lead_penalty(&ctx, card("7C"), None, false), 0);
+        assert_eq!(
+            danger_suit_lead_penalty(&ctx, card("6S"), Some(Suit::Spades), false),
+            0
+        );
+
+        let legal = legal_moves_for(&round, seat);
+        let scored = |set: Option<WeightSet>| {
+            with_weight_set(set, || PlayPlanner::explain_candidates(&legal, &ctx))
+        };
+        let with = scored(None);
+        let without = scored(Some(WeightSet::parse("MDH_W_DANGER_SUIT=0").unwrap()));
+        for ((card, score), (_, unweighted)) in with.iter().zip(&without) {
+            let expected = if card.to_string() == "6S" {
+                weight * 38 / 100
+            } else {
+                0
+            };
+            assert_eq!(unweighted - score, expected, "{card}");
+        }
+    }
 }
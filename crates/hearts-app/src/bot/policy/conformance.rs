@@ -0,0 +1,294 @@
+//! Checks that a [`Policy`] gives legal, well-formed answers across a fixed
+//! battery of positions. The battery covers passes in every direction and
+//! under the four-card house rule, hold hands, and positions from seeded
+//! playouts: the forced 2♣ lead, a must-follow spot, a single legal card,
+//! and three cards left.
+//!
+//! The battery only asks whether each answer could be played. It does not
+//! grade how good the answers are. A policy that panics on a case gets a
+//! violation for it, and the run goes on.
+
+use super::Policy;
+use crate::controller::GameController;
+use hearts_core::game::match_state::MatchState;
+use hearts_core::model::card::Card;
+use hearts_core::model::passing::PassingDirection;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rules::RuleSet;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+const SEEDS: [u64; 3] = [11, 23, 37];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub case: String,
+    pub problem: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceReport {
+    pub policy: String,
+    pub cases: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl ConformanceReport {
+    pub fn is_conformant(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// One line for the totals, then one per violation.
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "{}: {} cases, {} violations",
+            self.policy,
+            self.cases,
+            self.violations.len()
+        );
+        for violation in &self.violations {
+            out.push_str(&format!("\n  {}: {}", violation.case, violation.problem));
+        }
+        out
+    }
+}
+
+struct Battery<'p> {
+    policy: &'p mut dyn Policy,
+    cases: usize,
+    violations: Vec<Violation>,
+}
+
+impl Battery<'_> {
+    fn fail(&mut self, case: &str, problem: String) {
+        self.violations.push(Violation {
+            case: case.to_string(),
+            problem,
+        });
+    }
+
+    fn check_pass(&mut self, case: &str, controller: &GameController, seat: PlayerPosition) {
+        self.cases += 1;
+        let round = controller.round();
+        let hand = round.hand(seat);
+        let ctx = controller.bot_context(seat);
+        let passes = round.passing_direction().requires_selection();
+        let Ok(answer) = catch_unwind(AssertUnwindSafe(|| self.policy.choose_pass(hand, &ctx)))
+        else {
+            self.fail(case, "panicked".to_string());
+            return;
+        };
+        let cards = match (answer, passes) {
+            (None, false) => return,
+            (Some(cards), false) => {
+                self.fail(case, format!("passed {cards:?} on a hold hand"));
+                return;
+            }
+            (None, true) => {
+                self.fail(case, "returned no pass".to_string());
+                return;
+            }
+            (Some(cards), true) => cards,
+        };
+        let expected = round.pass_count();
+        if cards.len() != expected {
+            self.fail(
+                case,
+                format!("passed {} cards, expected {expected}", cards.len()),
+            );
+        }
+        if let Some(card) = cards.iter().find(|card| !hand.contains(**card)) {
+            self.fail(case, format!("passed {card}, which is not in the hand"));
+        }
+        let mut distinct: Vec<Card> = cards.iter().copied().collect();
+        distinct.sort_by_key(|card| (card.suit, card.rank));
+        distinct.dedup();
+        if distinct.len() != cards.len() {
+            self.fail(case, format!("passed a card twice in {cards:?}"));
+        }
+    }
+
+    fn check_play(&mut self, case: &str, controller: &GameController) {
+        self.cases += 1;
+        let seat = controller.expected_to_play();
+        let legal = controller.legal_moves(seat);
+        let ctx = controller.bot_context(seat);
+        let Ok(answer) = catch_unwind(AssertUnwindSafe(|| self.policy.choose_play(&legal, &ctx)))
+        else {
+            self.fail(case, "panicked".to_string());
+            return;
+        };
+        match answer {
+            None => self.fail(case, "returned no card".to_string()),
+            Some(card) if !legal.contains(&card) => {
+                let legal: Vec<String> = legal.iter().map(|card| card.to_string()).collect();
+                self.fail(
+                    case,
+                    format!("played {card}, legal were {}", legal.join(" ")),
+                );
+            }
+            Some(_) => {}
+        }
+    }
+
+    fn passes(&mut self, seed: u64) {
+        let four = RuleSet::STANDARD
+            .with_pass_count(4)
+            .expect("four-card pass is supported");
+        for rules in [RuleSet::STANDARD, four] {
+            for direction in PassingDirection::CYCLE {
+                let controller = GameController::new_from_match_state(
+                    MatchState::with_seed_round_direction_rules(
+                        seed,
+                        1,
+                        direction,
+                        PlayerPosition::North,
+                        rules,
+                    ),
+                );
+                let case = format!(
+                    "pass/{}/{}-card/seed {seed}",
+                    direction.as_str().to_ascii_lowercase(),
+                    rules.pass_count
+                );
+                for seat in PlayerPosition::LOOP {
+                    self.check_pass(&format!("{case}/{seat}"), &controller, seat);
+                }
+            }
+        }
+    }
+
+    /// Walks a hold hand with the first legal card each turn and checks the
+    /// policy at the first position of each kind.
+    fn plays(&mut self, seed: u64) {
+        let mut controller =
+            GameController::new_from_match_state(MatchState::with_seed_round_direction(
+                seed,
+                1,
+                PassingDirection::Hold,
+                PlayerPosition::North,
+            ));
+        self.check_play(&format!("play/two_of_clubs_lead/seed {seed}"), &controller);
+        let (mut must_follow, mut one_legal, mut three_cards) = (false, false, false);
+        loop {
+            let seat = controller.expected_to_play();
+            let legal = controller.legal_moves(seat);
+            let Some(&first) = legal.first() else {
+                break;
+            };
+            let round = controller.round();
+            let following = !round.current_trick().plays().is_empty();
+            let hand_len = round.hand(seat).len();
+            if !round.is_first_trick() {
+                if !must_follow && following && legal.len() > 1 && legal.len() < hand_len {
+                    must_follow = true;
+                    self.check_play(&format!("play/must_follow/seed {seed}"), &controller);
+                }
+                if !one_legal && legal.len() == 1 && hand_len > 1 {
+                    one_legal = true;
+                    self.check_play(&format!("play/one_legal/seed {seed}"), &controller);
+                }
+                if !three_cards && hand_len == 3 {
+                    three_cards = true;
+                    self.check_play(&format!("play/three_cards/seed {seed}"), &controller);
+                }
+            }
+            if controller.play(seat, first).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+/// Runs `policy` through the battery.
+pub fn run(policy: &mut dyn Policy) -> ConformanceReport {
+    let name = policy.name();
+    let mut battery = Battery {
+        policy,
+        cases: 0,
+        violations: Vec::new(),
+    };
+    for seed in SEEDS {
+        battery.passes(seed);
+        battery.plays(seed);
+    }
+    ConformanceReport {
+        policy: name,
+        cases: battery.cases,
+        violations: battery.violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::bot::policy::{PlannerPolicy, Policy};
+    use crate::bot::{BotContext, BotDifficulty};
+    use hearts_core::model::card::Card;
+    use hearts_core::model::hand::Hand;
+    use hearts_core::model::passing::PassCards;
+
+    #[test]
+    fn built_in_planners_conform() {
+        for difficulty in [
+            BotDifficulty::EasyLegacy,
+            BotDifficulty::NormalHeuristic,
+            BotDifficulty::FutureHard,
+        ] {
+            let report = run(&mut PlannerPolicy::new(difficulty));
+            assert!(report.is_conformant(), "{}", report.summary());
+            assert!(report.cases >= 90, "{}", report.summary());
+        }
+    }
+
+    /// Passes its first two cards, even on hold hands, and leads with its
+    /// first card regardless of the rules.
+    struct Careless;
+
+    impl Policy for Careless {
+        fn name(&self) -> String {
+            "careless".to_string()
+        }
+
+        fn choose_pass(&mut self, hand: &Hand, _ctx: &BotContext<'_>) -> Option<PassCards> {
+            PassCards::from_slice(&hand.cards()[..2])
+        }
+
+        fn choose_play(&mut self, _legal: &[Card], ctx: &BotContext<'_>) -> Option<Card> {
+            ctx.round.hand(ctx.seat).cards().last().copied()
+        }
+    }
+
+    #[test]
+    fn violations_name_the_case_and_the_problem() {
+        let report = run(&mut Careless);
+        assert!(!report.is_conformant());
+        let problems = |prefix: &str| {
+            report
+                .violations
+                .iter()
+                .filter(|v| v.case.starts_with(prefix))
+                .map(|v| v.problem.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert!(
+            problems("pass/left/3-card")
+                .iter()
+                .all(|p| *p == "passed 2 cards, expected 3")
+        );
+        assert!(
+            problems("pass/hold")
+                .iter()
+                .all(|p| p.ends_with("on a hold hand"))
+        );
+        assert_eq!(problems("pass/hold").len(), 2 * 3 * 4);
+        assert_eq!(problems("play/two_of_clubs_lead").len(), 3);
+        assert!(
+            problems("play/one_legal")
+                .iter()
+                .all(|p| p.contains(", legal were "))
+        );
+        let summary = report.summary();
+        assert!(summary.starts_with("careless: "));
+        assert_eq!(summary.lines().count(), report.violations.len() + 1);
+    }
+}
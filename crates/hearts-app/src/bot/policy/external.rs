@@ -0,0 +1,983 @@
+//! A [`Policy`] played by an outside engine. The engine either runs as a
+//! child process speaking over stdin/stdout, or as a service reached over a
+//! WebSocket. Both transports carry the same messages: one JSON object per
+//! line or per text frame, each request tagged with an `id` that the reply
+//! echoes.
+//!
+//! The session opens with a `hello` that offers [`PROTOCOL_VERSIONS`]; the
+//! agent answers with the version it picked and its name. The session, and a
+//! stdio agent's process, then lasts the whole run: from version 2 each hand
+//! opens with a `reset` and the run ends with a `shutdown`. A `pass` request
+//! carries the seat's hand and the direction and count, and a `play` request
+//! adds the legal cards, the trick so far and the tricks already played;
+//! version 3 names them `pass_request` and `play_request`. Either answer may
+//! be typed `pass`/`play` or `pass_response`/`play_response`. The agent may
+//! answer any request with `{"type": "error", "message": ...}`.
+//! `docs/EXTERNAL_AGENTS.md` lists every field.
+//!
+//! A reply that misses its timeout is an error for that decision only; a late
+//! reply is dropped by its `id` when it turns up. A dropped connection is
+//! reopened with doubling backoff, the hello repeated and the request sent
+//! again; for a stdio agent that means respawning a process that crashed or
+//! closed its stdout. Once the agent has been reached, failing to reach it
+//! again is retried the same way, so a server that is briefly down is waited
+//! for. A card that is not legal, or a pass that is not the right number of
+//! distinct cards from the hand, is refused here rather than left to the
+//! game; the refused reply is kept for the warning that reports it, and
+//! [`ExternalFallback`] names who decides instead.
+
+use super::Policy;
+use super::websocket::WebSocketChannel;
+use crate::bot::{BotContext, BotDifficulty};
+use hearts_core::game::serialization::parse_card;
+use hearts_core::model::card::Card;
+use hearts_core::model::hand::Hand;
+use hearts_core::model::passing::PassCards;
+use hearts_core::model::player::PlayerPosition;
+use serde_json::{Value, json};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Protocol versions this side speaks, oldest first. Version 2 adds the
+/// `reset` and `shutdown` messages, and version 3 sends the decisions as
+/// `pass_request` and `play_request`.
+pub const PROTOCOL_VERSIONS: [u32; 3] = [1, 2, 3];
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+/// A spawned engine may load a model before it can answer the hello.
+const DEFAULT_STARTUP: Duration = Duration::from_secs(10);
+const DEFAULT_RECONNECTS: u32 = 3;
+/// A stdio agent that dies is respawned once per request, then given up on.
+const DEFAULT_RESPAWNS: u32 = 1;
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transport {
+    /// Spawn `command` with `args` in `cwd`, with `env` added to the
+    /// environment, and talk over its stdin and stdout.
+    Stdio {
+        command: String,
+        args: Vec<String>,
+        cwd: Option<PathBuf>,
+        env: Vec<(String, String)>,
+    },
+    /// Connect to a running agent at a `ws://` URL.
+    WebSocket { url: String },
+}
+
+/// Who decides a pass or play the agent fails, answers late or answers
+/// illegally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalFallback {
+    /// The seat's difficulty in the run's mix.
+    Mix,
+    /// A fixed built-in difficulty.
+    Difficulty(BotDifficulty),
+}
+
+impl ExternalFallback {
+    fn parse(raw: &str) -> Option<Self> {
+        let difficulty = match raw.to_ascii_lowercase().as_str() {
+            "mix" => return Some(ExternalFallback::Mix),
+            "easy" => BotDifficulty::EasyLegacy,
+            "normal" => BotDifficulty::NormalHeuristic,
+            "hard" => BotDifficulty::FutureHard,
+            "search" => BotDifficulty::SearchLookahead,
+            "rollout" => BotDifficulty::Rollout,
+            _ => return None,
+        };
+        Some(ExternalFallback::Difficulty(difficulty))
+    }
+
+    /// The difficulty standing in for a seat whose mix difficulty is `mix`.
+    pub fn difficulty(self, mix: BotDifficulty) -> BotDifficulty {
+        match self {
+            ExternalFallback::Mix => mix,
+            ExternalFallback::Difficulty(difficulty) => difficulty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalOptions {
+    pub transport: Transport,
+    /// How long each reply may take.
+    pub timeout: Duration,
+    /// How long the hello may take, which covers starting the engine.
+    pub startup: Duration,
+    /// Reconnects, or for stdio respawns, tried for one request before
+    /// giving up on it.
+    pub reconnects: u32,
+    /// Wait before the first reconnect; doubled for each one after.
+    pub backoff: Duration,
+    pub fallback: ExternalFallback,
+}
+
+impl ExternalOptions {
+    pub fn new(transport: Transport) -> Self {
+        let reconnects = match transport {
+            Transport::Stdio { .. } => DEFAULT_RESPAWNS,
+            Transport::WebSocket { .. } => DEFAULT_RECONNECTS,
+        };
+        Self {
+            transport,
+            timeout: DEFAULT_TIMEOUT,
+            startup: DEFAULT_STARTUP,
+            reconnects,
+            backoff: DEFAULT_BACKOFF,
+            fallback: ExternalFallback::Mix,
+        }
+    }
+
+    /// Reads `key=value` pairs separated by commas, e.g.
+    /// `transport=websocket,url=ws://127.0.0.1:9000,timeout_ms=500` or
+    /// `transport=stdio,command=python agent.py`. The stdio command is split
+    /// on whitespace. `transport` defaults to stdio. `fallback` is `mix` (the
+    /// default) or a difficulty name. A stdio agent also takes `cwd=<dir>`
+    /// and any number of `env=KEY=VALUE`.
+    pub fn parse(spec: &str) -> Result<Self, ExternalError> {
+        let mut transport = "stdio".to_string();
+        let (mut command, mut url, mut cwd) = (None, None, None);
+        let mut env = Vec::new();
+        let mut timeout = DEFAULT_TIMEOUT;
+        let mut startup = DEFAULT_STARTUP;
+        let mut reconnects = None;
+        let mut backoff = DEFAULT_BACKOFF;
+        let mut fallback = ExternalFallback::Mix;
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once('=').ok_or_else(|| {
+                ExternalError::Options(format!("expected key=value, got '{part}'"))
+            })?;
+            let value = value.trim();
+            let millis = || {
+                value
+                    .parse::<u64>()
+                    .map(Duration::from_millis)
+                    .map_err(|_| ExternalError::Options(format!("invalid {key} '{value}'")))
+            };
+            match key.trim() {
+                "transport" => transport = value.to_ascii_lowercase(),
+                "command" => command = Some(value.to_string()),
+                "url" => url = Some(value.to_string()),
+                "cwd" => cwd = Some(PathBuf::from(value)),
+                "env" => {
+                    let (name, setting) = value.split_once('=').ok_or_else(|| {
+                        ExternalError::Options(format!("expected env=KEY=VALUE, got '{value}'"))
+                    })?;
+                    env.push((name.to_string(), setting.to_string()));
+                }
+                "timeout_ms" => timeout = millis()?,
+                "startup_ms" => startup = millis()?,
+                "backoff_ms" => backoff = millis()?,
+                "fallback" => {
+                    fallback = ExternalFallback::parse(value).ok_or_else(|| {
+                        ExternalError::Options(format!("invalid fallback '{value}'"))
+                    })?
+                }
+                "reconnects" => {
+                    reconnects = Some(value.parse().map_err(|_| {
+                        ExternalError::Options(format!("invalid reconnects '{value}'"))
+                    })?)
+                }
+                other => return Err(ExternalError::Options(format!("unknown key '{other}'"))),
+            }
+        }
+        let transport = match transport.as_str() {
+            "stdio" => {
+                let command = command
+                    .ok_or_else(|| ExternalError::Options("stdio needs command=".to_string()))?;
+                let mut words = command.split_whitespace().map(str::to_string);
+                let program = words
+                    .next()
+                    .ok_or_else(|| ExternalError::Options("empty command".to_string()))?;
+                Transport::Stdio {
+                    command: program,
+                    args: words.collect(),
+                    cwd,
+                    env,
+                }
+            }
+            "websocket" | "ws" if cwd.is_some() || !env.is_empty() => {
+                return Err(ExternalError::Options(
+                    "cwd= and env= apply to stdio agents only".to_string(),
+                ));
+            }
+            "websocket" | "ws" => Transport::WebSocket {
+                url: url
+                    .ok_or_else(|| ExternalError::Options("websocket needs url=".to_string()))?,
+            },
+            other => {
+                return Err(ExternalError::Options(format!(
+                    "unknown transport '{other}' (expected stdio or websocket)"
+                )));
+            }
+        };
+        let defaults = Self::new(transport);
+        Ok(Self {
+            timeout,
+            startup,
+            reconnects: reconnects.unwrap_or(defaults.reconnects),
+            backoff,
+            fallback,
+            ..defaults
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalError {
+    Options(String),
+    /// The agent could not be started or reached.
+    Connect(String),
+    /// The connection dropped and every reconnect failed.
+    Disconnected(String),
+    Timeout,
+    /// The agent picked a version this side did not offer.
+    Version(u32),
+    /// A reply that does not follow the protocol.
+    Protocol(String),
+    /// The agent answered with an error message.
+    Agent(String),
+    /// A well-formed answer the rules do not allow.
+    Illegal(String),
+}
+
+impl fmt::Display for ExternalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalError::Options(m) => write!(f, "invalid external agent options: {m}"),
+            ExternalError::Connect(m) => write!(f, "cannot reach agent: {m}"),
+            ExternalError::Disconnected(m) => write!(f, "agent disconnected: {m}"),
+            ExternalError::Timeout => write!(f, "agent did not reply in time"),
+            ExternalError::Version(v) => write!(
+                f,
+                "agent chose protocol version {v}, offered {PROTOCOL_VERSIONS:?}"
+            ),
+            ExternalError::Protocol(m) => write!(f, "protocol error: {m}"),
+            ExternalError::Agent(m) => write!(f, "agent error: {m}"),
+            ExternalError::Illegal(m) => write!(f, "illegal answer: {m}"),
+        }
+    }
+}
+
+impl std::error::Error for ExternalError {}
+
+/// Moves whole messages to and from an agent. `Send` so a seat can ask its
+/// agent from a worker thread.
+pub(crate) trait Channel: Send {
+    fn send(&mut self, message: &str) -> io::Result<()>;
+
+    /// The next message, or `None` if nothing arrived before `deadline`. An
+    /// error means the connection is gone.
+    fn recv(&mut self, deadline: Instant) -> io::Result<Option<String>>;
+}
+
+fn open(transport: &Transport) -> io::Result<Box<dyn Channel>> {
+    match transport {
+        Transport::Stdio {
+            command,
+            args,
+            cwd,
+            env,
+        } => Ok(Box::new(StdioChannel::spawn(
+            command,
+            args,
+            cwd.as_deref(),
+            env,
+        )?)),
+        Transport::WebSocket { url } => Ok(Box::new(WebSocketChannel::connect(url)?)),
+    }
+}
+
+struct StdioChannel {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<io::Result<String>>,
+}
+
+impl StdioChannel {
+    fn spawn(
+        command: &str,
+        args: &[String],
+        cwd: Option<&Path>,
+        env: &[(String, String)],
+    ) -> io::Result<Self> {
+        let mut command = Command::new(command);
+        if let Some(dir) = cwd {
+            command.current_dir(dir);
+        }
+        let mut child = command
+            .args(args)
+            .envs(env.iter().map(|(name, value)| (name, value)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            child,
+            stdin,
+            lines,
+        })
+    }
+}
+
+impl Channel for StdioChannel {
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        writeln!(self.stdin, "{message}")?;
+        self.stdin.flush()
+    }
+
+    fn recv(&mut self, deadline: Instant) -> io::Result<Option<String>> {
+        let wait = deadline.saturating_duration_since(Instant::now());
+        match self.lines.recv_timeout(wait) {
+            Ok(line) => line.map(Some),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "agent closed its stdout",
+            )),
+        }
+    }
+}
+
+impl Drop for StdioChannel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The request/reply loop shared by both transports.
+pub(crate) struct Session {
+    options: ExternalOptions,
+    channel: Option<Box<dyn Channel>>,
+    next_id: u64,
+    agent_name: Option<String>,
+    version: Option<u32>,
+    reconnects: u32,
+    /// Processes spawned after the first, for a stdio agent.
+    respawns: u32,
+    /// Whether the agent has been started or dialled before.
+    opened: bool,
+    /// The text of the last reply read, as the agent sent it.
+    last_reply: Option<String>,
+}
+
+impl Session {
+    pub(crate) fn new(options: ExternalOptions) -> Self {
+        Self {
+            options,
+            channel: None,
+            next_id: 1,
+            agent_name: None,
+            version: None,
+            reconnects: 0,
+            respawns: 0,
+            opened: false,
+            last_reply: None,
+        }
+    }
+
+    /// Connects unless already connected, so the engine starts now rather
+    /// than inside its first decision.
+    pub(crate) fn start(&mut self) -> Result<(), ExternalError> {
+        if self.channel.is_some() {
+            return Ok(());
+        }
+        self.connect().map_err(|failure| match failure {
+            Failure::Lost(reason) => ExternalError::Disconnected(reason),
+            Failure::Fatal(error) => error,
+        })
+    }
+
+    /// Sends `request` with a fresh `id` and returns the matching reply,
+    /// reconnecting if the connection drops on the way.
+    pub(crate) fn request(&mut self, mut request: Value) -> Result<Value, ExternalError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.last_reply = None;
+        request["id"] = json!(id);
+        let kind = request["type"].as_str().unwrap_or_default().to_string();
+        let mut backoff = self.options.backoff;
+        let mut attempt = 0;
+        loop {
+            match self.exchange(id, &kind, &mut request) {
+                Err(Failure::Lost(reason)) => {
+                    self.channel = None;
+                    if attempt >= self.options.reconnects {
+                        return Err(ExternalError::Disconnected(reason));
+                    }
+                    attempt += 1;
+                    self.reconnects += 1;
+                    thread::sleep(backoff);
+                    backoff = backoff.saturating_mul(2);
+                }
+                Err(Failure::Fatal(error)) => return Err(error),
+                Ok(reply) => return Ok(reply),
+            }
+        }
+    }
+
+    fn exchange(&mut self, id: u64, kind: &str, request: &mut Value) -> Result<Value, Failure> {
+        if self.channel.is_none() {
+            self.connect()?;
+        }
+        // The name depends on the version, which a reconnect may change.
+        request["type"] = json!(match kind {
+            "pass" | "play" if self.version.is_some_and(|version| version >= 3) => {
+                format!("{kind}_request")
+            }
+            _ => kind.to_string(),
+        });
+        let channel = self.channel.as_mut().expect("connected above");
+        channel.send(&request.to_string()).map_err(Failure::lost)?;
+        let (reply, raw) = await_reply(channel.as_mut(), id, self.options.timeout)?;
+        self.last_reply = Some(raw);
+        expect_kind(&reply, kind).map_err(Failure::Fatal)?;
+        Ok(reply)
+    }
+
+    /// Opens the channel and says hello. Failing to open it is fatal the
+    /// first time, e.g. a wrong command or URL, and worth retrying after.
+    fn connect(&mut self) -> Result<(), Failure> {
+        let mut channel = open(&self.options.transport).map_err(|e| {
+            if self.opened {
+                Failure::lost(e)
+            } else {
+                Failure::Fatal(ExternalError::Connect(e.to_string()))
+            }
+        })?;
+        if self.opened && matches!(self.options.transport, Transport::Stdio { .. }) {
+            self.respawns += 1;
+        }
+        self.opened = true;
+        let hello = json!({
+            "type": "hello",
+            "id": 0,
+            "engine": "mdhearts",
+            "versions": PROTOCOL_VERSIONS,
+        });
+        channel.send(&hello.to_string()).map_err(Failure::lost)?;
+        let (reply, _) = await_reply(channel.as_mut(), 0, self.options.startup)?;
+        expect_kind(&reply, "hello").map_err(Failure::Fatal)?;
+        let version = reply["version"]
+            .as_u64()
+            .ok_or_else(|| Failure::Fatal(missing("version")))? as u32;
+        if !PROTOCOL_VERSIONS.contains(&version) {
+            return Err(Failure::Fatal(ExternalError::Version(version)));
+        }
+        self.version = Some(version);
+        self.agent_name = reply["name"].as_str().map(str::to_string);
+        self.channel = Some(channel);
+        Ok(())
+    }
+
+    pub(crate) fn agent_name(&self) -> Option<&str> {
+        self.agent_name.as_deref()
+    }
+
+    pub(crate) fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    pub(crate) fn reconnects(&self) -> u32 {
+        self.reconnects
+    }
+
+    pub(crate) fn respawns(&self) -> u32 {
+        self.respawns
+    }
+
+    /// Sends the `shutdown` an agent on version 2 expects and gives it the
+    /// reply timeout to close its end. Whatever is still open is then closed,
+    /// which kills a stdio agent that did not exit.
+    pub(crate) fn shutdown(&mut self) {
+        let Some(mut channel) = self.channel.take() else {
+            return;
+        };
+        if self.version.is_some_and(|version| version >= 2) {
+            let message = json!({ "type": "shutdown", "id": self.next_id }).to_string();
+            self.next_id += 1;
+            if channel.send(&message).is_ok() {
+                let deadline = Instant::now() + self.options.timeout;
+                while let Ok(Some(_)) = channel.recv(deadline) {}
+            }
+        }
+    }
+
+    pub(crate) fn last_reply(&self) -> Option<&str> {
+        self.last_reply.as_deref()
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+enum Failure {
+    /// The connection is gone; worth reconnecting.
+    Lost(String),
+    Fatal(ExternalError),
+}
+
+impl Failure {
+    fn lost(error: io::Error) -> Self {
+        Failure::Lost(error.to_string())
+    }
+}
+
+impl From<ExternalError> for Failure {
+    fn from(error: ExternalError) -> Self {
+        Failure::Fatal(error)
+    }
+}
+
+/// Reads until the reply tagged `id`, dropping late replies to earlier ones,
+/// and returns it parsed and as sent.
+fn await_reply(
+    channel: &mut dyn Channel,
+    id: u64,
+    timeout: Duration,
+) -> Result<(Value, String), Failure> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let Some(message) = channel.recv(deadline).map_err(Failure::lost)? else {
+            return Err(ExternalError::Timeout.into());
+        };
+        let reply: Value = serde_json::from_str(&message)
+            .map_err(|e| ExternalError::Protocol(format!("invalid JSON '{message}': {e}")))?;
+        match reply["id"].as_u64() {
+            Some(got) if got == id => return Ok((reply, message)),
+            Some(got) if got < id => continue,
+            _ => return Err(missing("matching id").into()),
+        }
+    }
+}
+
+fn expect_kind(reply: &Value, kind: &str) -> Result<(), ExternalError> {
+    match reply["type"].as_str() {
+        Some("error") => Err(ExternalError::Agent(
+            reply["message"]
+                .as_str()
+                .unwrap_or("no message")
+                .to_string(),
+        )),
+        Some(got) if got == kind || got.strip_suffix("_response") == Some(kind) => Ok(()),
+        Some(got) => Err(ExternalError::Protocol(format!(
+            "expected a '{kind}' reply, got '{got}'"
+        ))),
+        None => Err(missing("type")),
+    }
+}
+
+fn missing(field: &str) -> ExternalError {
+    ExternalError::Protocol(format!("reply has no {field}"))
+}
+
+fn card_codes(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+fn scores(ctx: &BotContext<'_>) -> [u32; 4] {
+    *ctx.scores.standings()
+}
+
+pub(crate) fn encode_pass(hand: &Hand, ctx: &BotContext<'_>) -> Value {
+    json!({
+        "type": "pass",
+        "seat": ctx.seat,
+        "direction": ctx.passing_direction.as_str(),
+        "count": ctx.round.pass_count(),
+        "hand": card_codes(hand.cards()),
+        "scores": scores(ctx),
+    })
+}
+
+pub(crate) fn encode_play(legal: &[Card], ctx: &BotContext<'_>) -> Value {
+    let trick: Vec<Value> = ctx
+        .round
+        .current_trick()
+        .plays()
+        .iter()
+        .map(|play| json!({ "seat": play.position, "card": play.card.to_string() }))
+        .collect();
+    let history: Vec<Value> = ctx
+        .round
+        .trick_history()
+        .iter()
+        .map(|trick| {
+            let plays: Vec<Value> = trick
+                .plays()
+                .iter()
+                .map(|play| json!({ "seat": play.position, "card": play.card.to_string() }))
+                .collect();
+            json!({ "leader": trick.leader(), "plays": plays, "winner": trick.winner() })
+        })
+        .collect();
+    json!({
+        "type": "play",
+        "seat": ctx.seat,
+        "direction": ctx.passing_direction.as_str(),
+        "hand": card_codes(ctx.round.hand(ctx.seat).cards()),
+        "legal": card_codes(legal),
+        "trick": trick,
+        "history": history,
+        "hearts_broken": ctx.round.hearts_broken(),
+        "tricks_completed": ctx.round.tricks_completed(),
+        "scores": scores(ctx),
+    })
+}
+
+fn decode_card(value: &Value) -> Result<Card, ExternalError> {
+    let code = value
+        .as_str()
+        .ok_or_else(|| ExternalError::Protocol(format!("expected a card code, got {value}")))?;
+    parse_card(code).ok_or_else(|| ExternalError::Protocol(format!("unknown card '{code}'")))
+}
+
+pub(crate) fn decode_pass(reply: &Value) -> Result<PassCards, ExternalError> {
+    let cards = reply["cards"]
+        .as_array()
+        .ok_or_else(|| missing("cards"))?
+        .iter()
+        .map(decode_card)
+        .collect::<Result<Vec<_>, _>>()?;
+    PassCards::from_slice(&cards)
+        .ok_or_else(|| ExternalError::Protocol(format!("cannot pass {} cards", cards.len())))
+}
+
+pub(crate) fn decode_play(reply: &Value) -> Result<Card, ExternalError> {
+    decode_card(&reply["card"])
+}
+
+/// Refuses a pass that is not `count` distinct cards from `hand`.
+fn check_pass(cards: PassCards, hand: &Hand, count: usize) -> Result<PassCards, ExternalError> {
+    let chosen = cards.as_slice();
+    if chosen.len() != count {
+        return Err(ExternalError::Illegal(format!(
+            "passed {} cards, {count} are due",
+            chosen.len()
+        )));
+    }
+    for (i, card) in chosen.iter().enumerate() {
+        if chosen[..i].contains(card) {
+            return Err(ExternalError::Illegal(format!("passed {card} twice")));
+        }
+        if !hand.contains(*card) {
+            return Err(ExternalError::Illegal(format!(
+                "passed {card}, not in hand"
+            )));
+        }
+    }
+    Ok(cards)
+}
+
+fn check_play(card: Card, legal: &[Card]) -> Result<Card, ExternalError> {
+    if legal.contains(&card) {
+        Ok(card)
+    } else {
+        Err(ExternalError::Illegal(format!(
+            "{card} is not a legal play"
+        )))
+    }
+}
+
+/// An outside engine behind either transport. A decision that fails comes
+/// back as `None`; [`ExternalPolicy::last_error`] says why.
+pub struct ExternalPolicy {
+    session: Session,
+    last_error: Option<ExternalError>,
+}
+
+impl ExternalPolicy {
+    /// Nothing is started or dialled until the first decision.
+    pub fn new(options: ExternalOptions) -> Self {
+        Self {
+            session: Session::new(options),
+            last_error: None,
+        }
+    }
+
+    pub fn last_error(&self) -> Option<&ExternalError> {
+        self.last_error.as_ref()
+    }
+
+    /// The version agreed in the last hello, if one has happened.
+    pub fn protocol_version(&self) -> Option<u32> {
+        self.session.version()
+    }
+
+    pub fn reconnects(&self) -> u32 {
+        self.session.reconnects()
+    }
+
+    /// How many times a stdio agent's process had to be started again.
+    pub fn respawns(&self) -> u32 {
+        self.session.respawns()
+    }
+
+    /// Starts or dials the agent and says hello, so a slow start is not
+    /// charged to the first decision.
+    pub fn start(&mut self) -> Result<(), ExternalError> {
+        self.session.start()
+    }
+
+    /// Tells the agent a new hand begins, dealt from `seed`, with `seat` its
+    /// seat. Agents on version 1 are not told.
+    pub fn begin_hand(&mut self, seat: PlayerPosition, seed: u64) -> Result<(), ExternalError> {
+        self.start()?;
+        if self.session.version().is_some_and(|version| version >= 2) {
+            self.session
+                .request(json!({ "type": "reset", "seat": seat, "seed": seed }))?;
+        }
+        Ok(())
+    }
+
+    /// Ends the session: an agent on version 2 is sent a `shutdown` and
+    /// given the reply timeout to exit. Dropping the policy does the same.
+    pub fn shutdown(&mut self) {
+        self.session.shutdown();
+    }
+
+    pub fn fallback(&self) -> ExternalFallback {
+        self.session.options.fallback
+    }
+
+    /// The reply behind the last failed decision, as the agent sent it;
+    /// `None` when the decision failed before any reply arrived.
+    pub fn refused_reply(&self) -> Option<&str> {
+        self.last_error.as_ref().and(self.session.last_reply())
+    }
+
+    fn decide<T>(
+        &mut self,
+        request: Value,
+        decode: impl FnOnce(&Value) -> Result<T, ExternalError>,
+    ) -> Option<T> {
+        let answer = self
+            .session
+            .request(request)
+            .and_then(|reply| decode(&reply));
+        match answer {
+            Ok(value) => {
+                self.last_error = None;
+                Some(value)
+            }
+            Err(error) => {
+                self.last_error = Some(error);
+                None
+            }
+        }
+    }
+}
+
+impl Policy for ExternalPolicy {
+    fn name(&self) -> String {
+        self.session.agent_name().unwrap_or("external").to_string()
+    }
+
+    fn choose_pass(&mut self, hand: &Hand, ctx: &BotContext<'_>) -> Option<PassCards> {
+        if !ctx.passing_direction.requires_selection() {
+            return None;
+        }
+        let count = ctx.round.pass_count();
+        self.decide(encode_pass(hand, ctx), |reply| {
+            check_pass(decode_pass(reply)?, hand, count)
+        })
+    }
+
+    fn choose_play(&mut self, legal: &[Card], ctx: &BotContext<'_>) -> Option<Card> {
+        self.decide(encode_play(legal, ctx), |reply| {
+            check_play(decode_play(reply)?, legal)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_both_transports() {
+        let ws = ExternalOptions::parse(
+            "transport=websocket, url=ws://127.0.0.1:9000/agent, timeout_ms=250",
+        )
+        .unwrap();
+        assert_eq!(
+            ws.transport,
+            Transport::WebSocket {
+                url: "ws://127.0.0.1:9000/agent".to_string()
+            }
+        );
+        assert_eq!(ws.timeout, Duration::from_millis(250));
+        assert_eq!(ws.reconnects, DEFAULT_RECONNECTS);
+
+        let stdio = ExternalOptions::parse("command=python agent.py --fast,reconnects=0").unwrap();
+        assert_eq!(
+            stdio.transport,
+            Transport::Stdio {
+                command: "python".to_string(),
+                args: vec!["agent.py".to_string(), "--fast".to_string()],
+                cwd: None,
+                env: Vec::new(),
+            }
+        );
+        assert_eq!(stdio.reconnects, 0);
+        assert_eq!(stdio.startup, DEFAULT_STARTUP);
+        assert_eq!(stdio.fallback, ExternalFallback::Mix);
+
+        let model = ExternalOptions::parse(
+            "command=./agent,cwd=/opt/agent,env=MODEL=big.onnx,env=THREADS=2,startup_ms=60000",
+        )
+        .unwrap();
+        assert_eq!(
+            model.transport,
+            Transport::Stdio {
+                command: "./agent".to_string(),
+                args: Vec::new(),
+                cwd: Some(PathBuf::from("/opt/agent")),
+                env: vec![
+                    ("MODEL".to_string(), "big.onnx".to_string()),
+                    ("THREADS".to_string(), "2".to_string()),
+                ],
+            }
+        );
+        assert_eq!(model.startup, Duration::from_secs(60));
+        assert_eq!(model.reconnects, DEFAULT_RESPAWNS);
+
+        let hard = ExternalOptions::parse("command=agent,fallback=hard").unwrap();
+        assert_eq!(
+            hard.fallback,
+            ExternalFallback::Difficulty(BotDifficulty::FutureHard)
+        );
+        assert_eq!(
+            hard.fallback.difficulty(BotDifficulty::EasyLegacy),
+            BotDifficulty::FutureHard
+        );
+        assert_eq!(
+            stdio.fallback.difficulty(BotDifficulty::EasyLegacy),
+            BotDifficulty::EasyLegacy
+        );
+
+        for bad in [
+            "transport=websocket",
+            "transport=pigeon,url=x",
+            "timeout_ms=soon",
+            "url",
+            "command=agent,fallback=random",
+            "command=agent,env=MODEL",
+            "transport=websocket,url=ws://x,cwd=/tmp",
+        ] {
+            assert!(
+                matches!(ExternalOptions::parse(bad), Err(ExternalError::Options(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_rejects_malformed_replies() {
+        assert_eq!(
+            decode_play(&json!({"card": "QS"})).unwrap().to_string(),
+            "QS"
+        );
+        assert!(matches!(
+            decode_play(&json!({"card": "ZZ"})),
+            Err(ExternalError::Protocol(_))
+        ));
+        assert!(matches!(
+            decode_pass(&json!({"cards": ["2C", "3C", "4C", "5C", "6C"]})),
+            Err(ExternalError::Protocol(_))
+        ));
+        assert_eq!(
+            decode_pass(&json!({"cards": ["2C", "3C", "4C"]}))
+                .unwrap()
+                .len(),
+            3
+        );
+        assert!(matches!(
+            expect_kind(&json!({"type": "error", "message": "no model"}), "play"),
+            Err(ExternalError::Agent(m)) if m == "no model"
+        ));
+        assert!(expect_kind(&json!({"type": "play_response"}), "play").is_ok());
+        assert!(matches!(
+            expect_kind(&json!({"type": "pass_response"}), "play"),
+            Err(ExternalError::Protocol(_))
+        ));
+    }
+
+    #[test]
+    fn passes_and_plays_the_rules_refuse_are_illegal() {
+        let hand = Hand::with_cards(
+            ["2C", "3C", "QS", "AH"]
+                .map(|code| parse_card(code).unwrap())
+                .to_vec(),
+        );
+        let pass = |codes: &[&str]| {
+            let cards: Vec<Card> = codes.iter().map(|c| parse_card(c).unwrap()).collect();
+            check_pass(PassCards::from_slice(&cards).unwrap(), &hand, 3)
+        };
+        assert!(pass(&["2C", "QS", "AH"]).is_ok());
+        for (bad, why) in [
+            (&["2C", "QS"][..], "passed 2 cards, 3 are due"),
+            (&["2C", "2C", "AH"][..], "passed 2C twice"),
+            (&["2C", "QS", "KH"][..], "passed KH, not in hand"),
+        ] {
+            assert_eq!(pass(bad), Err(ExternalError::Illegal(why.to_string())));
+        }
+        let legal = [parse_card("2C").unwrap()];
+        assert!(check_play(legal[0], &legal).is_ok());
+        assert_eq!(
+            check_play(parse_card("QS").unwrap(), &legal),
+            Err(ExternalError::Illegal("QS is not a legal play".to_string()))
+        );
+    }
+
+    #[test]
+    fn play_requests_carry_the_tricks_already_played() {
+        let mut controller = crate::controller::GameController::new_with_seed(
+            Some(11),
+            hearts_core::model::player::PlayerPosition::North,
+        );
+        while controller.in_passing_phase() {
+            for seat in hearts_core::model::player::PlayerPosition::LOOP {
+                let cards = controller.simple_pass_for(seat).unwrap();
+                let _ = controller.submit_pass(seat, cards);
+            }
+            controller.resolve_passes().unwrap();
+        }
+        for _ in 0..5 {
+            let seat = controller.expected_to_play();
+            let card = controller.legal_moves(seat)[0];
+            controller.play(seat, card).unwrap();
+        }
+        let seat = controller.expected_to_play();
+        let legal = controller.legal_moves(seat);
+        let request = encode_play(&legal, &controller.bot_context(seat));
+        let history = request["history"].as_array().unwrap();
+        assert_eq!(history.len(), 1);
+        let first = &controller.round().trick_history()[0];
+        assert_eq!(history[0]["leader"], json!(first.leader()));
+        assert_eq!(history[0]["winner"], json!(first.winner()));
+        assert_eq!(history[0]["plays"].as_array().unwrap().len(), 4);
+        assert_eq!(request["trick"].as_array().unwrap().len(), 1);
+        assert!(request["direction"].is_string());
+    }
+}
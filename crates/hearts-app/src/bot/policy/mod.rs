@@ -0,0 +1,71 @@
+//! The decision interface shared by the built-in planners and by anything
+//! else that wants to play a seat, such as an adapter for an outside engine.
+//! [`conformance::run`] is the bar an implementation has to clear.
+
+pub mod conformance;
+pub mod external;
+mod websocket;
+
+#[cfg(test)]
+pub(crate) use websocket::test_agent;
+
+use super::{BotContext, BotDifficulty, PassPlanner, PlayPlanner, PlayPlannerHard, RolloutPolicy};
+use hearts_core::model::card::Card;
+use hearts_core::model::hand::Hand;
+use hearts_core::model::passing::PassCards;
+
+/// Chooses passes and plays for whichever seat `ctx` describes.
+pub trait Policy {
+    /// Label used in reports.
+    fn name(&self) -> String;
+
+    /// The `ctx.round.pass_count()` cards to pass from `hand`, the seat's own
+    /// hand, or `None` on a hold hand.
+    fn choose_pass(&mut self, hand: &Hand, ctx: &BotContext<'_>) -> Option<PassCards>;
+
+    /// One of `legal`, which the caller has already filtered by the rules.
+    fn choose_play(&mut self, legal: &[Card], ctx: &BotContext<'_>) -> Option<Card>;
+}
+
+/// A built-in planner at a fixed difficulty, choosing as the controller
+/// would without a think limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannerPolicy {
+    pub difficulty: BotDifficulty,
+}
+
+impl PlannerPolicy {
+    pub fn new(difficulty: BotDifficulty) -> Self {
+        Self { difficulty }
+    }
+}
+
+impl Policy for PlannerPolicy {
+    fn name(&self) -> String {
+        crate::outcome::agent_label(self.difficulty).to_string()
+    }
+
+    fn choose_pass(&mut self, hand: &Hand, ctx: &BotContext<'_>) -> Option<PassCards> {
+        if !ctx.passing_direction.requires_selection() {
+            return None;
+        }
+        match self.difficulty {
+            BotDifficulty::EasyLegacy => hand
+                .cards()
+                .get(..ctx.round.pass_count())
+                .and_then(PassCards::from_slice),
+            _ => PassPlanner::choose(hand, ctx),
+        }
+    }
+
+    fn choose_play(&mut self, legal: &[Card], ctx: &BotContext<'_>) -> Option<Card> {
+        match self.difficulty {
+            BotDifficulty::EasyLegacy => legal.first().copied(),
+            BotDifficulty::NormalHeuristic => PlayPlanner::choose(legal, ctx),
+            BotDifficulty::FutureHard | BotDifficulty::SearchLookahead => {
+                PlayPlannerHard::choose(legal, ctx)
+            }
+            BotDifficulty::Rollout => RolloutPolicy::new(0).choose(legal, ctx),
+        }
+    }
+}
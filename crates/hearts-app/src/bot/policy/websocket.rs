@@ -0,0 +1,748 @@
+//! The small part of RFC 6455 an agent connection needs: a `ws://` client
+//! handshake and text frames. Pings are answered, fragmented messages are
+//! joined, and a close frame or EOF ends the connection. A frame or joined
+//! message over [`MAX_MESSAGE_BYTES`] is refused as invalid data, so a peer
+//! cannot grow the read buffer without bound. There is no TLS.
+
+use super::external::Channel;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::Instant;
+
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Largest frame, and largest message joined from fragments, accepted. A
+/// play request is a few kilobytes.
+pub(crate) const MAX_MESSAGE_BYTES: usize = 1 << 20;
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+pub(crate) struct WebSocketChannel {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    fragments: Vec<u8>,
+}
+
+impl WebSocketChannel {
+    pub(crate) fn connect(url: &str) -> io::Result<Self> {
+        let (host, path) = split_url(url)?;
+        let mut stream = TcpStream::connect(host)?;
+        stream.set_nodelay(true)?;
+        let key = base64(&rand::random::<[u8; 16]>());
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: {host}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Key: {key}\r\nSec-WebSocket-Version: 13\r\n\r\n"
+        )?;
+        let (head, rest) = read_head(&mut stream)?;
+        let mut lines = head.lines();
+        let status = lines.next().unwrap_or_default();
+        if status.split_whitespace().nth(1) != Some("101") {
+            return Err(invalid(format!("upgrade refused: {status}")));
+        }
+        let accepted = lines
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("sec-websocket-accept"))
+            .map(|(_, value)| value.trim().to_string());
+        if accepted.as_deref() != Some(accept_key(&key).as_str()) {
+            return Err(invalid("bad Sec-WebSocket-Accept".to_string()));
+        }
+        Ok(Self {
+            stream,
+            buffer: rest,
+            fragments: Vec::new(),
+        })
+    }
+
+    fn write_frame(&mut self, opcode: u8, payload: &[u8]) -> io::Result<()> {
+        let frame = encode_frame(opcode, payload, Some(rand::random()));
+        self.stream.write_all(&frame)
+    }
+}
+
+impl Channel for WebSocketChannel {
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        self.write_frame(OP_TEXT, message.as_bytes())
+    }
+
+    fn recv(&mut self, deadline: Instant) -> io::Result<Option<String>> {
+        loop {
+            while let Some(frame) = take_frame(&mut self.buffer)? {
+                match frame.opcode {
+                    OP_TEXT | OP_CONTINUATION => {
+                        if self.fragments.len() + frame.payload.len() > MAX_MESSAGE_BYTES {
+                            self.fragments.clear();
+                            return Err(too_large());
+                        }
+                        self.fragments.extend_from_slice(&frame.payload);
+                        if frame.fin {
+                            let text = String::from_utf8(std::mem::take(&mut self.fragments))
+                                .map_err(|_| invalid("text frame is not UTF-8".to_string()))?;
+                            return Ok(Some(text));
+                        }
+                    }
+                    OP_PING => self.write_frame(OP_PONG, &frame.payload)?,
+                    OP_PONG => {}
+                    OP_CLOSE => {
+                        let _ = self.write_frame(OP_CLOSE, &[]);
+                        return Err(io::Error::new(
+                            io::ErrorKind::ConnectionAborted,
+                            "agent closed the connection",
+                        ));
+                    }
+                    other => return Err(invalid(format!("unsupported opcode {other:#x}"))),
+                }
+            }
+            let wait = deadline.saturating_duration_since(Instant::now());
+            if wait.is_zero() {
+                return Ok(None);
+            }
+            self.stream.set_read_timeout(Some(wait))?;
+            let mut chunk = [0u8; 4096];
+            match self.stream.read(&mut chunk) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "agent closed the connection",
+                    ));
+                }
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    return Ok(None);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn too_large() -> io::Error {
+    invalid(format!("message larger than {MAX_MESSAGE_BYTES} bytes"))
+}
+
+/// `ws://host:port/path` into the socket address and the request path.
+fn split_url(url: &str) -> io::Result<(&str, &str)> {
+    let rest = url.strip_prefix("ws://").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{url}' is not a ws:// URL"),
+        )
+    })?;
+    Ok(match rest.find('/') {
+        Some(slash) => (&rest[..slash], &rest[slash..]),
+        None => (rest, "/"),
+    })
+}
+
+/// The response head up to the blank line, and whatever followed it.
+fn read_head(stream: &mut TcpStream) -> io::Result<(String, Vec<u8>)> {
+    let mut bytes = Vec::new();
+    let mut chunk = [0u8; 1024];
+    loop {
+        if let Some(end) = bytes.windows(4).position(|w| w == b"\r\n\r\n") {
+            let rest = bytes.split_off(end + 4);
+            return Ok((String::from_utf8_lossy(&bytes).into_owned(), rest));
+        }
+        if bytes.len() > 16 * 1024 {
+            return Err(invalid("upgrade response too long".to_string()));
+        }
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed during upgrade",
+            ));
+        }
+        bytes.extend_from_slice(&chunk[..n]);
+    }
+}
+
+pub(crate) fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{ACCEPT_GUID}").as_bytes()))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Frame {
+    pub fin: bool,
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+/// A single unfragmented frame. Clients mask what they send; servers do not.
+pub(crate) fn encode_frame(opcode: u8, payload: &[u8], mask: Option<[u8; 4]>) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+    match payload.len() {
+        len @ 0..=125 => frame.push(mask_bit | len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(mask_bit | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(mask_bit | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    match mask {
+        Some(mask) => {
+            frame.extend_from_slice(&mask);
+            frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        }
+        None => frame.extend_from_slice(payload),
+    }
+    frame
+}
+
+/// Removes and returns the first whole frame in `buffer`, if there is one.
+/// A frame whose header announces more than [`MAX_MESSAGE_BYTES`] is an
+/// error before any of its payload is waited for.
+pub(crate) fn take_frame(buffer: &mut Vec<u8>) -> io::Result<Option<Frame>> {
+    if buffer.len() < 2 {
+        return Ok(None);
+    }
+    let fin = buffer[0] & 0x80 != 0;
+    let opcode = buffer[0] & 0x0F;
+    let masked = buffer[1] & 0x80 != 0;
+    let (len, mut at): (u64, usize) = match buffer[1] & 0x7F {
+        126 if buffer.len() >= 4 => (u16::from_be_bytes([buffer[2], buffer[3]]) as u64, 4),
+        127 if buffer.len() >= 10 => {
+            let bytes: [u8; 8] = buffer[2..10].try_into().expect("eight bytes");
+            (u64::from_be_bytes(bytes), 10)
+        }
+        126 | 127 => return Ok(None),
+        len => (len as u64, 2),
+    };
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|&len| len <= MAX_MESSAGE_BYTES)
+        .ok_or_else(too_large)?;
+    let mask_len = if masked { 4 } else { 0 };
+    let end = at
+        .checked_add(mask_len)
+        .and_then(|start| start.checked_add(len))
+        .ok_or_else(too_large)?;
+    if buffer.len() < end {
+        return Ok(None);
+    }
+    let mask = masked.then(|| {
+        let mask = [buffer[at], buffer[at + 1], buffer[at + 2], buffer[at + 3]];
+        at += 4;
+        mask
+    });
+    let mut payload = buffer[at..at + len].to_vec();
+    if let Some(mask) = mask {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+    buffer.drain(..at + len);
+    Ok(Some(Frame {
+        fin,
+        opcode,
+        payload,
+    }))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let next = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = next;
+        }
+        for (slot, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *slot = slot.wrapping_add(value);
+        }
+    }
+    let mut out = [0u8; 20];
+    for (i, word) in h.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+/// A local agent speaking the protocol over this module's frames, for tests
+/// here and for end-to-end runs through the CLI.
+#[cfg(test)]
+pub(crate) mod test_agent {
+    use super::*;
+    use serde_json::{Value, json};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    /// How the test agent treats each connection it accepts.
+    #[derive(Clone, Copy)]
+    pub(crate) enum Agent {
+        /// Passes its first cards and plays its first legal card.
+        Plain,
+        /// Answers the hello with an unoffered version.
+        WrongVersion,
+        /// Drops the connection on its first request, then behaves.
+        DropFirst,
+        /// Leaves its first request unanswered until after the next arrives.
+        LateFirst,
+        /// Plays as `Plain` but waits this long before answering a pass.
+        SlowPass(Duration),
+        /// Passes one card over and over and plays a card it may not play.
+        Illegal,
+    }
+
+    pub(crate) struct Server {
+        pub(crate) url: String,
+        /// Yields how many pass and play requests arrived.
+        pub(crate) handle: JoinHandle<usize>,
+    }
+
+    /// Pass and play requests being answered right now, across every server
+    /// that shares it, and the most there have been at once.
+    #[derive(Default)]
+    pub(crate) struct InFlight {
+        now: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl InFlight {
+        pub(crate) fn peak(&self) -> usize {
+            self.peak.load(Ordering::SeqCst)
+        }
+
+        fn enter(&self) {
+            let now = self.now.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+        }
+
+        fn leave(&self) {
+            self.now.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) fn serve(agent: Agent, connections: usize) -> Server {
+        serve_gauged(agent, connections, Arc::default())
+    }
+
+    /// As [`serve`], counting the requests it answers in `in_flight`.
+    pub(crate) fn serve_gauged(
+        agent: Agent,
+        connections: usize,
+        in_flight: Arc<InFlight>,
+    ) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}/agent", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = 0usize;
+            for _ in 0..connections {
+                let (stream, _) = listener.accept().unwrap();
+                session(stream, agent, &mut requests, &in_flight);
+            }
+            requests
+        });
+        Server { url, handle }
+    }
+
+    /// Drops the connection on its first request and stays down for
+    /// `outage` before it listens again on the same address.
+    pub(crate) fn serve_with_outage(outage: Duration) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let url = format!("ws://{addr}/agent");
+        let handle = thread::spawn(move || {
+            let mut requests = 0usize;
+            let (stream, _) = listener.accept().unwrap();
+            drop(listener);
+            session(
+                stream,
+                Agent::DropFirst,
+                &mut requests,
+                &InFlight::default(),
+            );
+            thread::sleep(outage);
+            let listener = TcpListener::bind(addr).unwrap();
+            let (stream, _) = listener.accept().unwrap();
+            session(stream, Agent::Plain, &mut requests, &InFlight::default());
+            requests
+        });
+        Server { url, handle }
+    }
+
+    fn session(mut stream: TcpStream, agent: Agent, requests: &mut usize, in_flight: &InFlight) {
+        let (head, mut buffer) = read_head(&mut stream).unwrap();
+        let key = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("sec-websocket-key"))
+            .map(|(_, value)| value.trim().to_string())
+            .unwrap();
+        write!(
+            stream,
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(&key)
+        )
+        .unwrap();
+        let mut held: Option<Value> = None;
+        loop {
+            let frame = loop {
+                if let Some(frame) = take_frame(&mut buffer).unwrap() {
+                    break frame;
+                }
+                let mut chunk = [0u8; 4096];
+                match stream.read(&mut chunk) {
+                    Ok(0) | Err(_) => return,
+                    Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                }
+            };
+            if frame.opcode == OP_CLOSE {
+                return;
+            }
+            let request: Value = serde_json::from_slice(&frame.payload).unwrap();
+            let reply = match request["type"].as_str().unwrap() {
+                "hello" => json!({
+                    "type": "hello",
+                    "id": 0,
+                    "version": if matches!(agent, Agent::WrongVersion) { 99 } else { 1 },
+                    "name": "ws-echo",
+                }),
+                _ => {
+                    *requests += 1;
+                    if *requests == 1 && matches!(agent, Agent::DropFirst) {
+                        return;
+                    }
+                    in_flight.enter();
+                    let reply = answer(&request, agent);
+                    if let (Agent::SlowPass(delay), Some("pass")) =
+                        (agent, request["type"].as_str())
+                    {
+                        thread::sleep(delay);
+                    }
+                    in_flight.leave();
+                    if *requests == 1 && matches!(agent, Agent::LateFirst) {
+                        held = Some(reply);
+                        continue;
+                    }
+                    reply
+                }
+            };
+            for reply in held.take().into_iter().chain([reply]) {
+                let frame = encode_frame(OP_TEXT, reply.to_string().as_bytes(), None);
+                stream.write_all(&frame).unwrap();
+            }
+        }
+    }
+
+    fn answer(request: &Value, agent: Agent) -> Value {
+        let id = request["id"].clone();
+        let hand = request["hand"].as_array().unwrap();
+        match (request["type"].as_str().unwrap(), agent) {
+            ("pass", Agent::Illegal) => {
+                let count = request["count"].as_u64().unwrap() as usize;
+                json!({ "type": "pass", "id": id, "cards": vec![&hand[0]; count] })
+            }
+            ("pass", _) => {
+                let count = request["count"].as_u64().unwrap() as usize;
+                let cards: Vec<Value> = hand[..count].to_vec();
+                json!({ "type": "pass", "id": id, "cards": cards })
+            }
+            (_, Agent::Illegal) => {
+                let legal = request["legal"].as_array().unwrap();
+                // A held card it may not follow with, or else one it lacks.
+                let card = hand
+                    .iter()
+                    .find(|card| !legal.contains(card))
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        let held: Vec<&str> = hand.iter().filter_map(Value::as_str).collect();
+                        let missing = hearts_core::model::suit::Suit::ALL
+                            .iter()
+                            .flat_map(|&suit| {
+                                hearts_core::model::rank::Rank::ORDERED
+                                    .map(|rank| hearts_core::model::card::Card::new(rank, suit))
+                            })
+                            .map(|card| card.to_string())
+                            .find(|code| !held.contains(&code.as_str()))
+                            .unwrap();
+                        json!(missing)
+                    });
+                json!({ "type": "play", "id": id, "card": card })
+            }
+            _ => json!({ "type": "play", "id": id, "card": request["legal"][0] }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_agent::{Agent, serve, serve_with_outage};
+    use super::*;
+    use crate::bot::policy::Policy;
+    use crate::bot::policy::conformance;
+    use crate::bot::policy::external::{ExternalError, ExternalOptions, ExternalPolicy, Transport};
+    use crate::controller::GameController;
+    use hearts_core::model::player::PlayerPosition;
+    use std::time::Duration;
+
+    #[test]
+    fn accept_key_matches_the_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn oversized_frames_are_refused_from_the_header() {
+        // A 64-bit length near u64::MAX, with no payload behind it.
+        let mut buffer = vec![0x81, 127];
+        buffer.extend_from_slice(&u64::MAX.to_be_bytes());
+        let err = take_frame(&mut buffer).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut buffer = vec![0x81, 0x80 | 127];
+        buffer.extend_from_slice(&((MAX_MESSAGE_BYTES + 1) as u64).to_be_bytes());
+        assert!(take_frame(&mut buffer).is_err());
+
+        // At the limit the frame is simply incomplete until it all arrives.
+        let mut buffer = vec![0x81, 127];
+        buffer.extend_from_slice(&(MAX_MESSAGE_BYTES as u64).to_be_bytes());
+        assert_eq!(take_frame(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn fragments_joining_past_the_limit_are_refused() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let (head, _) = read_head(&mut stream).unwrap();
+            let key = head
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.eq_ignore_ascii_case("sec-websocket-key"))
+                .map(|(_, value)| value.trim().to_string())
+                .unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                 Sec-WebSocket-Accept: {}\r\n\r\n",
+                accept_key(&key)
+            )
+            .unwrap();
+            // Two unfinished halves, each within the frame limit.
+            let half = vec![b'x'; MAX_MESSAGE_BYTES / 2 + 1];
+            let mut first = encode_frame(OP_TEXT, &half, None);
+            first[0] &= 0x7F;
+            let mut second = encode_frame(OP_CONTINUATION, &half, None);
+            second[0] &= 0x7F;
+            let _ = stream.write_all(&first);
+            let _ = stream.write_all(&second);
+            let _ = stream.read(&mut [0u8; 16]);
+        });
+        let mut channel = WebSocketChannel::connect(&format!("ws://{addr}/")).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let err = channel.recv(deadline).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        drop(channel);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn frames_round_trip_masked_and_split() {
+        let long = vec![b'x'; 300];
+        let mut buffer = encode_frame(OP_TEXT, b"hi", Some([1, 2, 3, 4]));
+        buffer.extend(encode_frame(OP_TEXT, &long, None));
+        let tail = buffer.split_off(buffer.len() - 10);
+        let first = take_frame(&mut buffer).unwrap().unwrap();
+        assert_eq!(
+            (first.fin, first.opcode, first.payload.as_slice()),
+            (true, OP_TEXT, &b"hi"[..])
+        );
+        assert_eq!(take_frame(&mut buffer).unwrap(), None);
+        buffer.extend(tail);
+        assert_eq!(take_frame(&mut buffer).unwrap().unwrap().payload, long);
+        assert!(buffer.is_empty());
+    }
+
+    fn options(url: &str) -> ExternalOptions {
+        let mut options = ExternalOptions::new(Transport::WebSocket {
+            url: url.to_string(),
+        });
+        options.timeout = Duration::from_millis(500);
+        options.backoff = Duration::from_millis(5);
+        options
+    }
+
+    #[test]
+    fn websocket_agent_passes_the_conformance_battery() {
+        let server = serve(Agent::Plain, 1);
+        let mut policy = ExternalPolicy::new(options(&server.url));
+        let report = conformance::run(&mut policy);
+        assert!(report.is_conformant(), "{}", report.summary());
+        assert_eq!(policy.name(), "ws-echo");
+        assert_eq!(policy.protocol_version(), Some(1));
+        assert_eq!(policy.reconnects(), 0);
+        drop(policy);
+        server.handle.join().unwrap();
+    }
+
+    #[test]
+    fn unoffered_version_is_refused() {
+        let server = serve(Agent::WrongVersion, 1);
+        let mut policy = ExternalPolicy::new(options(&server.url));
+        let controller = GameController::new_with_seed(Some(5), PlayerPosition::North);
+        let seat = controller.expected_to_play();
+        let legal = controller.legal_moves(seat);
+        assert_eq!(
+            policy.choose_play(&legal, &controller.bot_context(seat)),
+            None
+        );
+        assert_eq!(policy.last_error(), Some(&ExternalError::Version(99)));
+        server.handle.join().unwrap();
+    }
+
+    #[test]
+    fn dropped_connection_is_reopened_and_the_request_resent() {
+        let server = serve(Agent::DropFirst, 2);
+        let mut policy = ExternalPolicy::new(options(&server.url));
+        let report = conformance::run(&mut policy);
+        assert!(report.is_conformant(), "{}", report.summary());
+        assert_eq!(policy.reconnects(), 1);
+        drop(policy);
+        server.handle.join().unwrap();
+    }
+
+    #[test]
+    fn a_server_briefly_down_is_retried_with_backoff() {
+        let server = serve_with_outage(Duration::from_millis(60));
+        let mut opts = options(&server.url);
+        opts.backoff = Duration::from_millis(20);
+        opts.reconnects = 4;
+        let mut policy = ExternalPolicy::new(opts);
+        let controller = GameController::new_with_seed(Some(5), PlayerPosition::North);
+        let seat = controller.expected_to_play();
+        let legal = controller.legal_moves(seat);
+        let card = policy.choose_play(&legal, &controller.bot_context(seat));
+        assert_eq!(card, legal.first().copied(), "{:?}", policy.last_error());
+        // Refused dials count as reconnects too.
+        assert!(policy.reconnects() >= 2, "{}", policy.reconnects());
+        drop(policy);
+        server.handle.join().unwrap();
+    }
+
+    #[test]
+    fn illegal_answers_are_refused_and_kept_for_the_warning() {
+        let server = serve(Agent::Illegal, 1);
+        let mut policy = ExternalPolicy::new(options(&server.url));
+        let controller = GameController::new_with_seed(Some(5), PlayerPosition::North);
+        let seat = PlayerPosition::East;
+        let hand = controller.round().hand(seat).clone();
+        assert_eq!(
+            policy.choose_pass(&hand, &controller.bot_context(seat)),
+            None
+        );
+        assert!(
+            matches!(policy.last_error(), Some(ExternalError::Illegal(m)) if m.contains("twice")),
+            "{:?}",
+            policy.last_error()
+        );
+        let reply = policy.refused_reply().unwrap();
+        assert!(reply.contains("\"type\":\"pass\""), "{reply}");
+
+        let mut controller = controller;
+        while controller.in_passing_phase() {
+            for passer in PlayerPosition::LOOP {
+                let cards = controller.simple_pass_for(passer).unwrap();
+                let _ = controller.submit_pass(passer, cards);
+            }
+            controller.resolve_passes().unwrap();
+        }
+        let seat = controller.expected_to_play();
+        let legal = controller.legal_moves(seat);
+        assert_eq!(
+            policy.choose_play(&legal, &controller.bot_context(seat)),
+            None
+        );
+        assert!(matches!(
+            policy.last_error(),
+            Some(ExternalError::Illegal(m)) if m.ends_with("is not a legal play")
+        ));
+        assert!(policy.refused_reply().unwrap().contains("\"card\""));
+        drop(policy);
+        server.handle.join().unwrap();
+    }
+
+    #[test]
+    fn timed_out_request_fails_alone_and_its_late_reply_is_skipped() {
+        let server = serve(Agent::LateFirst, 1);
+        let mut opts = options(&server.url);
+        opts.timeout = Duration::from_millis(100);
+        let mut policy = ExternalPolicy::new(opts);
+        let report = conformance::run(&mut policy);
+        assert_eq!(report.violations.len(), 1, "{}", report.summary());
+        assert_eq!(report.violations[0].problem, "returned no pass");
+        assert_eq!(policy.last_error(), None);
+        drop(policy);
+        server.handle.join().unwrap();
+    }
+}
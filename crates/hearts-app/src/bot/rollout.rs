@@ -0,0 +1,288 @@
+//! Monte Carlo rollout play. For each legal card the policy deals the cards
+//! the seat cannot see into worlds the tracker finds consistent, plays every
+//! world out with the Normal heuristic at all four seats, and keeps the card
+//! whose worlds end best for the seat on average. Every card is scored on the
+//! same worlds, so the comparison is not at the mercy of one lucky deal.
+
+use super::{BotContext, BotDifficulty, DecisionLimit, PlayPlanner, SampledWorld, UnseenTracker};
+use hearts_core::model::card::Card;
+use hearts_core::model::hand::Hand;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::round::{RoundPhase, RoundState};
+use hearts_core::model::rules::{JACK_OF_DIAMONDS, RuleSet};
+use hearts_core::model::score::hand_points;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::time::{Duration, Instant};
+
+/// Chooses plays by rollouts, sized by `ctx.params`. A choice depends only
+/// on the seed, the position and the params, so two runs with the same seed
+/// play the same cards unless `rollout_budget_ms` cuts one short.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RolloutPolicy {
+    seed: u64,
+}
+
+impl RolloutPolicy {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    pub fn choose(&self, legal: &[Card], ctx: &BotContext<'_>) -> Option<Card> {
+        self.choose_with_limit(legal, ctx, None)
+    }
+
+    /// The best of `legal`, the earliest on a tie. Stops sampling when
+    /// `limit` expires, after at least one world.
+    pub fn choose_with_limit(
+        &self,
+        legal: &[Card],
+        ctx: &BotContext<'_>,
+        limit: Option<&DecisionLimit<'_>>,
+    ) -> Option<Card> {
+        self.evaluate(legal, ctx, limit)
+            .into_iter()
+            .rev()
+            .max_by_key(|&(_, score)| score)
+            .map(|(card, _)| card)
+    }
+
+    /// Each of `legal` with its mean over the worlds, in hundredths of a
+    /// point: the opponents' average round score less the seat's own, with
+    /// the moon rule and any J♦ bonus applied.
+    pub fn explain_candidates(&self, legal: &[Card], ctx: &BotContext<'_>) -> Vec<(Card, i32)> {
+        self.evaluate(legal, ctx, None)
+    }
+
+    fn evaluate(
+        &self,
+        legal: &[Card],
+        ctx: &BotContext<'_>,
+        limit: Option<&DecisionLimit<'_>>,
+    ) -> Vec<(Card, i32)> {
+        if legal.len() <= 1 {
+            return legal.iter().map(|&card| (card, 0)).collect();
+        }
+        let budget = (ctx.params.rollout_budget_ms > 0).then(|| {
+            Instant::now() + Duration::from_millis(u64::from(ctx.params.rollout_budget_ms))
+        });
+        let out_of_time = || {
+            limit.is_some_and(|limit| limit.expired())
+                || budget.is_some_and(|deadline| Instant::now() >= deadline)
+        };
+        let rules = RuleSet::STANDARD.with_jack_of_diamonds(ctx.jack_of_diamonds);
+        let mut rng = StdRng::seed_from_u64(self.seed ^ position_key(ctx));
+        let mut totals = vec![0i64; legal.len()];
+        let mut worlds = 0i64;
+        while worlds < i64::from(ctx.params.rollout_worlds.max(1)) {
+            if worlds > 0 && out_of_time() {
+                break;
+            }
+            let sampled = ctx
+                .tracker
+                .sample_consistent_world(&mut rng, ctx.seat, ctx.round);
+            let world = world_round(ctx, &sampled);
+            for (total, &card) in totals.iter_mut().zip(legal) {
+                let played = play_out(world.clone(), ctx, card);
+                *total += differential(&played, ctx.seat, &rules);
+            }
+            worlds += 1;
+        }
+        legal
+            .iter()
+            .zip(totals)
+            .map(|(&card, total)| (card, (total / worlds) as i32))
+            .collect()
+    }
+}
+
+/// 64-bit FNV-1a hash of the seat and the cards played so far, in order.
+/// Fixed, unlike `DefaultHasher`, so the worlds a seed draws stay the same
+/// from one build to the next.
+pub(super) fn position_key(ctx: &BotContext<'_>) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut mix = |byte: u8| {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    };
+    mix(ctx.seat.index() as u8);
+    for trick in ctx
+        .round
+        .trick_history()
+        .iter()
+        .chain(std::iter::once(ctx.round.current_trick()))
+    {
+        for play in trick.plays() {
+            for byte in [
+                play.position.index() as u8,
+                play.card.suit as u8,
+                play.card.rank.value(),
+            ] {
+                mix(byte);
+            }
+        }
+    }
+    hash
+}
+
+/// The round as it stands with the other seats holding `sampled`.
+fn world_round(ctx: &BotContext<'_>, sampled: &SampledWorld) -> RoundState {
+    let hands = PlayerPosition::LOOP.map(|seat| {
+        if seat == ctx.seat {
+            ctx.hand().clone()
+        } else {
+            Hand::with_cards(sampled.hands[seat.index()].clone())
+        }
+    });
+    RoundState::from_hands_with_state(
+        hands,
+        ctx.round.starting_player(),
+        ctx.passing_direction,
+        RoundPhase::Playing,
+        ctx.round.current_trick().clone(),
+        ctx.round.trick_history().to_vec(),
+        ctx.round.hearts_broken(),
+    )
+}
+
+/// Plays `card` for the seat, then the heuristic for everyone until the
+/// round is complete. Returns the finished round.
+fn play_out(mut round: RoundState, ctx: &BotContext<'_>, card: Card) -> RoundState {
+    let mut tracker = UnseenTracker::new();
+    tracker.rebuild_for_round(&round);
+    let mut next = Some((ctx.seat, card));
+    while let RoundPhase::Playing = round.phase() {
+        let (seat, choice) = match next.take() {
+            Some(play) => play,
+            None => {
+                let trick = round.current_trick();
+                let seat = trick
+                    .plays()
+                    .last()
+                    .map_or(trick.leader(), |play| play.position.next());
+                let legal = round.legal_plays(seat);
+                let Some(&fallback) = legal.first() else {
+                    break;
+                };
+                let seat_ctx = BotContext::new(
+                    seat,
+                    &round,
+                    ctx.scores,
+                    ctx.passing_direction,
+                    &tracker,
+                    BotDifficulty::NormalHeuristic,
+                )
+                .with_target_score(ctx.target_score)
+                .with_jack_of_diamonds(ctx.jack_of_diamonds);
+                (
+                    seat,
+                    PlayPlanner::choose(&legal, &seat_ctx).unwrap_or(fallback),
+                )
+            }
+        };
+        if round.play_card(seat, choice).is_err() {
+            break;
+        }
+        tracker.observe_latest_play(&round);
+    }
+    round
+}
+
+/// The opponents' mean round score less `seat`'s under `rules`, in
+/// hundredths.
+fn differential(round: &RoundState, seat: PlayerPosition, rules: &RuleSet) -> i64 {
+    let points = hand_points(
+        round.penalty_totals(),
+        round.captured_by(JACK_OF_DIAMONDS),
+        round.sun_shooter(),
+        rules,
+    )
+    .map(i64::from);
+    let own = points[seat.index()];
+    let others: i64 = points.iter().sum::<i64>() - own;
+    others * 100 / 3 - own * 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RolloutPolicy, differential, position_key};
+    use crate::bot::{BotContext, BotDifficulty, UnseenTracker};
+    use hearts_core::model::deck::Deck;
+    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::round::{RoundPhase, RoundState};
+    use hearts_core::model::rules::RuleSet;
+    use hearts_core::model::score::ScoreBoard;
+    use hearts_core::testkit::RoundBuilder;
+
+    #[test]
+    fn one_world_rollouts_repeat_under_a_seed_and_stay_legal() {
+        let play = |seed: u64| {
+            let policy = RolloutPolicy::new(seed);
+            let mut round = RoundState::deal(
+                &Deck::shuffled_with_seed(2260),
+                PlayerPosition::North,
+                PassingDirection::Hold,
+            );
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(&round);
+            let mut choices = Vec::new();
+            while let RoundPhase::Playing = round.phase() {
+                let trick = round.current_trick();
+                let seat = trick
+                    .plays()
+                    .last()
+                    .map_or(trick.leader(), |play| play.position.next());
+                let legal = round.legal_plays(seat);
+                let mut ctx = BotContext::new(
+                    seat,
+                    &round,
+                    ScoreBoard::new(),
+                    PassingDirection::Hold,
+                    &tracker,
+                    BotDifficulty::Rollout,
+                );
+                ctx.params.rollout_worlds = 1;
+                ctx.params.rollout_budget_ms = 0;
+                let card = policy.choose(&legal, &ctx).expect("a legal card");
+                assert!(legal.contains(&card), "{seat} chose {card} from {legal:?}");
+                round.play_card(seat, card).unwrap();
+                tracker.observe_latest_play(&round);
+                choices.push(card);
+            }
+            choices
+        };
+        let first = play(7);
+        assert_eq!(first.len(), 52);
+        assert_eq!(first, play(7));
+    }
+    #[test]
+    fn the_differential_scores_the_jack_under_omnibus() {
+        let round = RoundBuilder::new()
+            .trick(PlayerPosition::North, "2C JD 3C 4C")
+            .trick(PlayerPosition::West, "AH 2H 3H 4H")
+            .build();
+        let west = PlayerPosition::West;
+        assert_eq!(differential(&round, west, &RuleSet::STANDARD), -400);
+        let omnibus = RuleSet::STANDARD.with_jack_of_diamonds(true);
+        assert_eq!(differential(&round, west, &omnibus), 600);
+    }
+
+    #[test]
+    fn position_keys_are_fixed_across_builds() {
+        let round = RoundBuilder::new()
+            .trick(PlayerPosition::North, "2C JD 3C 4C")
+            .current(PlayerPosition::West, "AH")
+            .build();
+        let tracker = UnseenTracker::new();
+        let ctx = BotContext::new(
+            PlayerPosition::North,
+            &round,
+            ScoreBoard::new(),
+            PassingDirection::Hold,
+            &tracker,
+            BotDifficulty::Rollout,
+        );
+        assert_eq!(position_key(&ctx), 0xfe9c_3731_88c7_a006);
+    }
+}
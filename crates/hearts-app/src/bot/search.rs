@@ -1,10 +1,13 @@
 use super::{
-    BotContext, BotDifficulty, DecisionLimit, MoonState, PlayPlanner, detect_moon_pressure,
-    snapshot_scores,
+    BotContext, BotDifficulty, DecisionLimit, MoonState, PlayPlanner, TrickView,
+    detect_moon_pressure, snapshot_scores,
 };
 use crate::debug::debug_enabled;
 use hearts_core::model::card::Card;
+use hearts_core::model::card_set::CardSet;
+use hearts_core::model::per_suit::PerSuit;
 use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rank::Rank;
 use hearts_core::model::round::{PlayOutcome, RoundState};
 use hearts_core::model::suit::Suit;
 use once_cell::sync::Lazy;
@@ -13,11 +16,19 @@ use serde_json;
 use std::cell::Cell;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
 use std::time::Instant;
 
+/// What [`PlayPlannerHard::choose_anytime`] decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(windows), allow(dead_code))]
+pub struct AnytimeChoice {
+    pub card: Option<Card>,
+    /// The search ran out of time and the heuristic pick stands in for it.
+    pub fell_back: bool,
+}
+
 // Stage 3 scaffold: Hard planner with configurable branch limit and (future) depth/time caps.
 // For now, it orders by heuristic and considers the top N branches.
 pub struct PlayPlannerHard;
@@ -262,7 +273,7 @@ fn load_continuation_schedule() -> Option<ContinuationSchedule> {
         .ok()
         .filter(|s| !s.trim().is_empty())
         .unwrap_or_else(|| "tmp/continuation_fit_latest.json".to_string());
-    let path = Path::new(&path);
+    let path = crate::paths::expand_env_path(&path).ok()?;
     let contents = fs::read_to_string(path).ok()?;
     let raw: HashMap<String, HashMap<String, RawSeatSchedule>> =
         serde_json::from_str(&contents).ok()?;
@@ -422,7 +433,7 @@ impl PlayPlannerHard {
         if leader_gap <= 5 {
             cfg.branch_limit += 1;
         }
-        if my_score >= 75 {
+        if my_score >= ctx.threshold(ctx.params.self_caution_pct) {
             cfg.branch_limit += 1;
             cfg.next_branch_limit += 1;
             min_scan = min_scan.max(3);
@@ -699,6 +710,29 @@ impl PlayPlannerHard {
         Self::choose_with_limit(legal, ctx, None)
     }
 
+    /// Searches under `limit` with the heuristic's pick worked out first as
+    /// the anytime answer. If the deadline passes before the search settles,
+    /// or it finds nothing, the heuristic pick is played instead.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub fn choose_anytime(
+        legal: &[Card],
+        ctx: &BotContext<'_>,
+        limit: Option<&DecisionLimit<'_>>,
+    ) -> AnytimeChoice {
+        let heuristic = super::play::without_stats(|| PlayPlanner::choose(legal, ctx));
+        let searched = Self::choose_with_limit(legal, ctx, limit);
+        match searched {
+            Some(card) if !limit.is_some_and(|limit| limit.expired()) => AnytimeChoice {
+                card: Some(card),
+                fell_back: false,
+            },
+            _ => AnytimeChoice {
+                card: heuristic.or(searched),
+                fell_back: heuristic.is_some(),
+            },
+        }
+    }
+
     pub fn choose_with_limit(
         legal: &[Card],
         ctx: &BotContext<'_>,
@@ -736,7 +770,7 @@ impl PlayPlannerHard {
             PlayPlanner::explain_candidates_with_limit(legal, ctx, limit_ms_remaining);
         Self::apply_play_adviser_bias(&mut explained, ctx);
         let planner_nudge_hits = super::play::take_hard_nudge_hits();
-        explained.sort_by(|a, b| b.1.cmp(&a.1));
+        explained.sort_by_key(|x| std::cmp::Reverse(x.1));
         let best_base = explained.first().map(|x| x.1).unwrap_or(0);
         let boost_gap = parse_env_i32("MDH_HARD_CONT_BOOST_GAP").unwrap_or(0);
         let boost_factor = parse_env_i32("MDH_HARD_CONT_BOOST_FACTOR").unwrap_or(1);
@@ -784,7 +818,12 @@ impl PlayPlannerHard {
         if moon_pressure {
             phaseb_topk = phaseb_topk.saturating_add(1 + usize::from(high_scan >= 4));
             if ab_margin > 0 {
-                let scale = if snapshot.max_score >= 90 { 0.65 } else { 0.75 };
+                let near_target = ctx.threshold(ctx.params.near_target_pct);
+                let scale = if snapshot.max_score >= near_target {
+                    0.65
+                } else {
+                    0.75
+                };
                 ab_margin = ((ab_margin as f32) * scale).round() as i32;
                 ab_margin = ab_margin.max(30);
             }
@@ -823,13 +862,7 @@ impl PlayPlannerHard {
                         75
                     }
                 }
-                PlayerPosition::North => {
-                    if moon_pressure || trailing {
-                        100
-                    } else {
-                        0
-                    }
-                }
+                PlayerPosition::North if moon_pressure || trailing => 100,
                 _ => 0,
             };
             if seat_bonus > 0 {
@@ -1089,7 +1122,7 @@ impl PlayPlannerHard {
         let mut v = PlayPlanner::explain_candidates(legal, ctx);
         Self::apply_play_adviser_bias(&mut v, ctx);
         let planner_nudge_hits = super::play::take_hard_nudge_hits();
-        v.sort_by(|a, b| b.1.cmp(&a.1));
+        v.sort_by_key(|x| std::cmp::Reverse(x.1));
         let best_base = v.first().map(|x| x.1).unwrap_or(0);
         let boost_gap = parse_env_i32("MDH_HARD_CONT_BOOST_GAP").unwrap_or(0);
         let boost_factor = parse_env_i32("MDH_HARD_CONT_BOOST_FACTOR").unwrap_or(1);
@@ -1270,7 +1303,7 @@ impl PlayPlannerHard {
         let mut v = PlayPlanner::explain_candidates(legal, ctx);
         Self::apply_play_adviser_bias(&mut v, ctx);
         let planner_nudge_hits = super::play::take_hard_nudge_hits();
-        v.sort_by(|a, b| b.1.cmp(&a.1));
+        v.sort_by_key(|x| std::cmp::Reverse(x.1));
         let best_base = v.first().map(|x| x.1).unwrap_or(0);
         let boost_gap = parse_env_i32("MDH_HARD_CONT_BOOST_GAP").unwrap_or(0);
         let boost_factor = parse_env_i32("MDH_HARD_CONT_BOOST_FACTOR").unwrap_or(1);
@@ -1355,7 +1388,7 @@ impl PlayPlannerHard {
             .and_then(|s| s.parse::<usize>().ok());
         let mut v = PlayPlanner::explain_candidates(legal, ctx);
         Self::apply_play_adviser_bias(&mut v, ctx);
-        v.sort_by(|a, b| b.1.cmp(&a.1));
+        v.sort_by_key(|x| std::cmp::Reverse(x.1));
         let snapshot = snapshot_scores(ctx.scores);
         let start = Instant::now();
         let mut budget = Budget::new(cfg.time_cap_ms, deterministic, step_cap, None, None);
@@ -1692,7 +1725,7 @@ impl PlayPlannerHard {
                     let has_ace_spades = sim
                         .hand(seat)
                         .iter()
-                        .any(|c| c.suit == Suit::Spades && c.rank.value() == 14);
+                        .any(|c| c.suit == Suit::Spades && c.rank == Rank::Ace);
                     if has_ace_spades {
                         parts.qs_risk = -weights().qs_risk_per;
                         cont += parts.qs_risk;
@@ -1765,10 +1798,10 @@ fn choose_followup_search(
     // If we have a sampled world, filter legal moves to only include cards
     // that are in the opponent's sampled hand (belief-state sampling)
     if let Some(world) = sampled_world {
-        let sampled_hand = world.hand(seat);
+        let sampled_hand: CardSet = world.hand(seat).iter().collect();
         if !sampled_hand.is_empty() {
             // Only keep cards that are both legal and in the sampled hand
-            legal.retain(|card| sampled_hand.contains(card));
+            legal.retain(|&card| sampled_hand.contains(card));
 
             // If filtering removed all cards (shouldn't happen but defensive),
             // fall back to full legal set
@@ -1790,7 +1823,7 @@ fn choose_followup_search(
         let hearts_void = tracker
             .map(|t| t.is_void(seat, Suit::Hearts))
             .unwrap_or(false);
-        let provisional = provisional_winner(round);
+        let provisional = TrickView::new(round.current_trick()).current_winner();
         if provisional == Some(origin) {
             // Avoid giving points to origin (our seat) in simulation
             if let Some(card) = legal
@@ -2004,44 +2037,25 @@ fn xorshift64(mut x: u64) -> u64 {
 }
 
 fn legal_moves_for(round: &RoundState, seat: PlayerPosition) -> Vec<Card> {
-    round
-        .hand(seat)
-        .iter()
-        .copied()
-        .filter(|card| {
-            let mut probe = round.clone();
-            probe.play_card(seat, *card).is_ok()
-        })
-        .collect()
-}
-
-fn provisional_winner(round: &RoundState) -> Option<PlayerPosition> {
-    let trick = round.current_trick();
-    let lead = trick.lead_suit()?;
-    trick
-        .plays()
-        .iter()
-        .filter(|p| p.card.suit == lead)
-        .max_by_key(|p| p.card.rank.value())
-        .map(|p| p.position)
+    round.legal_plays(seat)
 }
 
 fn next_trick_start_bonus(round: &RoundState, leader: PlayerPosition) -> i32 {
     let hand = round.hand(leader);
     // +25 per singleton in non-hearts suits (max 3). Small +2 per heart when hearts are broken (cap 10).
-    let mut counts = [0u8; 4];
+    let mut counts = PerSuit::splat(0u8);
     for c in hand.iter() {
-        counts[c.suit as usize] = counts[c.suit as usize].saturating_add(1);
+        counts[c.suit] = counts[c.suit].saturating_add(1);
     }
     let mut bonus = 0;
-    for (i, &cnt) in counts.iter().enumerate() {
-        if i != Suit::Hearts as usize && cnt == 1 {
+    for (suit, &cnt) in counts.iter() {
+        if suit != Suit::Hearts && cnt == 1 {
             bonus += weights().next_trick_singleton_bonus;
         }
     }
     bonus = bonus.min(weights().next_trick_singleton_bonus * 3);
     if round.hearts_broken() {
-        let hearts = counts[Suit::Hearts as usize] as i32;
+        let hearts = i32::from(counts[Suit::Hearts]);
         bonus += (weights().next_trick_hearts_per * hearts).min(weights().next_trick_hearts_cap);
     }
     bonus
@@ -2170,13 +2184,14 @@ fn next_trick_probe(
         ctx.passing_direction,
         ctx.tracker,
         ctx.difficulty,
-    );
+    )
+    .with_target_score(ctx.target_score);
     let legal = legal_moves_for(sim_round, leader);
     if legal.is_empty() {
         return 0;
     }
     let mut ordered = PlayPlanner::explain_candidates(&legal, &tmp_ctx);
-    ordered.sort_by(|a, b| b.1.cmp(&a.1));
+    ordered.sort_by_key(|x| std::cmp::Reverse(x.1));
     let start = Instant::now();
     let mut bonus = 0;
     let mut next_limit = if limits.next_probe_m > 0 {
@@ -2848,7 +2863,7 @@ fn continuation_from_resolution(
             let has_ace_spades = sim
                 .hand(seat)
                 .iter()
-                .any(|c| c.suit == Suit::Spades && c.rank.value() == 14);
+                .any(|c| c.suit == Suit::Spades && c.rank == Rank::Ace);
             if has_ace_spades {
                 cont -= weights().qs_risk_per;
             }
@@ -2956,13 +2971,14 @@ fn evaluate_depth2_bonus(
         ctx.passing_direction,
         ctx.tracker,
         ctx.difficulty,
-    );
+    )
+    .with_target_score(ctx.target_score);
     let legal = legal_moves_for(&resolution.round, ctx.seat);
     if legal.is_empty() {
         return 0;
     }
     let mut ordered = PlayPlanner::explain_candidates(&legal, &depth_ctx);
-    ordered.sort_by(|a, b| b.1.cmp(&a.1));
+    ordered.sort_by_key(|x| std::cmp::Reverse(x.1));
     let best_base = ordered.first().map(|x| x.1).unwrap_or(0);
     let mut best_total = best_base;
     let mut considered = 0usize;
@@ -3120,10 +3136,10 @@ fn compute_leverage(ctx: &BotContext<'_>) -> (Tier, u8) {
     let my = ctx.scores.score(ctx.seat) as i32;
     let lead_score = snap.max_score as i32;
     let mut s: i32 = 0;
-    // Proximity to 100
-    s += if lead_score >= 90 {
+    // Proximity to the target
+    s += if lead_score >= ctx.threshold(ctx.params.near_target_pct) as i32 {
         40
-    } else if lead_score >= 80 {
+    } else if lead_score >= ctx.threshold(ctx.params.late_game_pct) as i32 {
         25
     } else {
         10
@@ -3145,7 +3161,7 @@ fn compute_leverage(ctx: &BotContext<'_>) -> (Tier, u8) {
     if pen > 0 {
         s += 10;
     }
-    if let Some(pw) = provisional_winner(ctx.round) {
+    if let Some(pw) = ctx.trick_view().current_winner() {
         if pw == snap.max_player {
             s += 10;
         }
@@ -9,151 +9,187 @@
 
 use super::{BotContext, PlayPlanner, PlayPlannerHard};
 use hearts_core::model::card::Card;
+use hearts_core::model::card_set::CardSet;
 use hearts_core::model::passing::PassingDirection;
 use hearts_core::model::player::PlayerPosition;
 use hearts_core::model::round::{PlayOutcome, RoundState};
 use hearts_core::model::score::ScoreBoard;
+use hearts_core::model::suit::Suit;
 use std::collections::HashMap;
+use std::sync::Mutex;
 use std::time::Instant;
 
 // ============================================================================
 // Transposition Table
 // ============================================================================
 
+/// A position as the search sees it: the cards each seat still holds, the
+/// trick in progress, whether hearts are broken and the points each seat has
+/// taken. Everything the search and its evaluation read follows from these,
+/// so two move orders reaching the same key reach the same position.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PositionKey {
+    hands: [CardSet; 4],
+    /// The card each seat has put on the current trick.
+    trick: [Option<Card>; 4],
+    leader: PlayerPosition,
+    hearts_broken: bool,
+    penalties: [u8; 4],
+}
+
+impl PositionKey {
+    fn of(round: &RoundState) -> Self {
+        let hands = PlayerPosition::LOOP.map(|seat| round.hand(seat).as_set());
+        let mut trick = [None; 4];
+        for play in round.current_trick().plays() {
+            trick[play.position.index()] = Some(play.card);
+        }
+        Self {
+            hands,
+            trick,
+            leader: round.current_trick().leader(),
+            hearts_broken: round.hearts_broken(),
+            penalties: round.penalty_totals(),
+        }
+    }
+}
+
+/// What a cached score answers. A lead of ours depends on both bounds of
+/// its window, so they are part of the key; an opponent node keeps its beta
+/// with the score instead (see [`OpponentEntry`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NodeKind {
+    Opponent,
+    Lead {
+        seat: PlayerPosition,
+        card: Card,
+        alpha: i32,
+        beta: i32,
+    },
+}
+
+/// An opponent node's score and its peak: the highest score compared
+/// against beta anywhere in the subtree searched for it.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)]
-pub enum NodeType {
-    Exact,      // Exact score
-    LowerBound, // Alpha cutoff (score is at least this value)
-    UpperBound, // Beta cutoff (score is at most this value)
+struct OpponentScore {
+    score: i32,
+    peak: i32,
 }
 
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct TableEntry {
-    hash: u64,
+/// A searched opponent node. With the peak below beta nothing was cut, so
+/// the score is the plain maximum and holds for any beta above the peak;
+/// after a cutoff it holds for that beta alone.
+#[derive(Debug, Clone, Copy)]
+struct OpponentEntry {
+    node: OpponentScore,
+    cut_at: Option<i32>,
+}
+
+impl OpponentEntry {
+    fn answers(&self, beta: i32) -> bool {
+        match self.cut_at {
+            None => self.node.peak < beta,
+            Some(cut) => cut == beta,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TableKey {
+    position: PositionKey,
     depth: u8,
-    score: i32,
-    best_move: Option<Card>,
-    node_type: NodeType,
+    node: NodeKind,
 }
 
-#[allow(dead_code)]
+/// Scores of positions already searched this decision. A key holds the
+/// position, the depth left and the window, so a hit returns exactly what
+/// searching again would: the cache saves nodes without changing a decision.
+/// Once `max_size` entries are stored, new positions are searched but not
+/// kept.
 pub struct TranspositionTable {
-    entries: HashMap<u64, TableEntry>,
+    entries: HashMap<TableKey, i32>,
+    opponents: HashMap<TableKey, OpponentEntry>,
     max_size: usize,
     hits: usize,
     misses: usize,
 }
 
-#[allow(dead_code)]
 impl TranspositionTable {
     pub fn new(max_size: usize) -> Self {
         Self {
-            entries: HashMap::with_capacity(max_size.min(1_000_000)),
+            entries: HashMap::new(),
+            opponents: HashMap::new(),
             max_size,
             hits: 0,
             misses: 0,
         }
     }
 
-    pub fn probe(&mut self, hash: u64, depth: u8, alpha: i32, beta: i32) -> Option<i32> {
-        if let Some(entry) = self.entries.get(&hash)
-            && entry.hash == hash
-            && entry.depth >= depth
-        {
+    fn probe(&mut self, key: &TableKey) -> Option<i32> {
+        let found = self.entries.get(key).copied();
+        if found.is_some() {
             self.hits += 1;
-            match entry.node_type {
-                NodeType::Exact => return Some(entry.score),
-                NodeType::LowerBound if entry.score >= beta => return Some(entry.score),
-                NodeType::UpperBound if entry.score <= alpha => return Some(entry.score),
-                _ => {}
-            }
+        } else {
+            self.misses += 1;
         }
-        self.misses += 1;
-        None
+        found
     }
 
-    pub fn store(
-        &mut self,
-        hash: u64,
-        depth: u8,
-        score: i32,
-        best_move: Option<Card>,
-        node_type: NodeType,
-    ) {
-        // Evict random entry if table is full
-        if self.entries.len() >= self.max_size
-            && let Some(&key) = self.entries.keys().next()
-        {
-            self.entries.remove(&key);
+    fn store(&mut self, key: TableKey, score: i32) {
+        if self.len() < self.max_size {
+            self.entries.insert(key, score);
+        }
+    }
+
+    fn probe_opponent(&mut self, key: &TableKey, beta: i32) -> Option<OpponentScore> {
+        let found = self
+            .opponents
+            .get(key)
+            .filter(|entry| entry.answers(beta))
+            .map(|entry| entry.node);
+        if found.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
         }
+        found
+    }
 
-        self.entries.insert(
-            hash,
-            TableEntry {
-                hash,
-                depth,
-                score,
-                best_move,
-                node_type,
-            },
-        );
+    /// Keeps `node`, searched against `beta`, unless the table is full or
+    /// already holds an uncut score for the position.
+    fn store_opponent(&mut self, key: TableKey, beta: i32, node: OpponentScore) {
+        let entry = OpponentEntry {
+            node,
+            cut_at: (node.peak >= beta).then_some(beta),
+        };
+        let room = self.len() < self.max_size;
+        match self.opponents.get_mut(&key) {
+            Some(held) if held.cut_at.is_some() => *held = entry,
+            Some(_) => {}
+            None if room => {
+                self.opponents.insert(key, entry);
+            }
+            None => {}
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len() + self.opponents.len()
     }
 
-    #[allow(dead_code)]
+    /// Entries, hits and misses.
     pub fn stats(&self) -> (usize, usize, usize) {
-        (self.entries.len(), self.hits, self.misses)
+        (self.len(), self.hits, self.misses)
     }
 
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.opponents.clear();
         self.hits = 0;
         self.misses = 0;
     }
 }
 
-// ============================================================================
-// Position Hashing
-// ============================================================================
-
-#[allow(dead_code)]
-fn hash_position(round: &RoundState, seat: PlayerPosition) -> u64 {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-
-    let mut hasher = DefaultHasher::new();
-
-    // Hash our hand
-    let hand = round.hand(seat);
-    for card in hand.cards() {
-        card.hash(&mut hasher);
-    }
-
-    // Hash current trick
-    for play in round.current_trick().plays() {
-        play.position.hash(&mut hasher);
-        play.card.hash(&mut hasher);
-    }
-
-    // Hash the player-to-move (critical for correct transposition table lookups)
-    let next_to_play = round
-        .current_trick()
-        .plays()
-        .last()
-        .map(|play| play.position.next())
-        .unwrap_or_else(|| round.current_trick().leader());
-    next_to_play.hash(&mut hasher);
-
-    // Hash trick count (game phase)
-    round.trick_history().len().hash(&mut hasher);
-
-    // Hash hearts broken state
-    round.hearts_broken().hash(&mut hasher);
-
-    hasher.finish()
-}
-
 // ============================================================================
 // Deep Search Implementation
 // ============================================================================
@@ -167,6 +203,32 @@ pub struct SearchResult {
     pub depth_reached: u8,
 }
 
+/// The work one deep search did: nodes searched and how the transposition
+/// table served it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    pub nodes: usize,
+    pub depth_reached: u8,
+    pub tt_entries: usize,
+    pub tt_hits: usize,
+    pub tt_misses: usize,
+}
+
+impl SearchStats {
+    pub fn hit_rate(&self) -> Option<f64> {
+        let probes = self.tt_hits + self.tt_misses;
+        (probes > 0).then(|| self.tt_hits as f64 / probes as f64)
+    }
+}
+
+static LAST_STATS: Mutex<Option<SearchStats>> = Mutex::new(None);
+
+/// Stats of the last deep search to finish, cleared by reading them so a
+/// decision the deep search did not make reports none.
+pub fn take_last_stats() -> Option<SearchStats> {
+    LAST_STATS.lock().ok().and_then(|mut slot| slot.take())
+}
+
 #[allow(dead_code)]
 pub struct DeepSearch {
     tt: TranspositionTable,
@@ -381,7 +443,7 @@ impl DeepSearch {
         }
 
         // Sort by score descending (killer move will be first if present)
-        evaluated.sort_by(|a, b| b.1.cmp(&a.1));
+        evaluated.sort_by_key(|x| std::cmp::Reverse(x.1));
         evaluated.into_iter().map(|(card, _)| card).collect()
     }
 
@@ -389,49 +451,48 @@ impl DeepSearch {
     fn order_moves(&self, legal: &[Card], ctx: &BotContext<'_>) -> Vec<Card> {
         let mut evaluated = PlayPlanner::explain_candidates(legal, ctx);
         // Sort by score descending (best moves first)
-        evaluated.sort_by(|a, b| b.1.cmp(&a.1));
+        evaluated.sort_by_key(|x| std::cmp::Reverse(x.1));
         evaluated.into_iter().map(|(card, _)| card).collect()
     }
 
-    /// Order moves for opponent simulation (simple heuristic)
+    /// Order moves for opponent simulation and our later leads, point-dodging
+    /// plays first so the cutoffs come early: on a follow, the cards that
+    /// duck under the trick (highest first), then discards (most points
+    /// first), then the cards that win it (lowest first). Leads go low and
+    /// in clubs, diamonds, spades, then hearts.
     fn order_moves_simple(
         &self,
         legal: &[Card],
-        _round: &RoundState,
+        round: &RoundState,
         _seat: PlayerPosition,
     ) -> Vec<Card> {
+        let trick = round.current_trick();
+        let winning = trick.lead_suit().and_then(|lead| {
+            trick
+                .plays()
+                .iter()
+                .map(|play| play.card)
+                .filter(|card| card.suit == lead)
+                .max_by_key(|card| card.rank)
+        });
         let mut moves: Vec<_> = legal.to_vec();
-
-        // Simple ordering: prefer low cards to avoid taking penalties
-        // This is a rough heuristic for opponent play
         moves.sort_by_key(|card| {
-            let rank_val = match card.rank {
-                hearts_core::model::rank::Rank::Two => 0,
-                hearts_core::model::rank::Rank::Three => 1,
-                hearts_core::model::rank::Rank::Four => 2,
-                hearts_core::model::rank::Rank::Five => 3,
-                hearts_core::model::rank::Rank::Six => 4,
-                hearts_core::model::rank::Rank::Seven => 5,
-                hearts_core::model::rank::Rank::Eight => 6,
-                hearts_core::model::rank::Rank::Nine => 7,
-                hearts_core::model::rank::Rank::Ten => 8,
-                hearts_core::model::rank::Rank::Jack => 9,
-                hearts_core::model::rank::Rank::Queen => 10,
-                hearts_core::model::rank::Rank::King => 11,
-                hearts_core::model::rank::Rank::Ace => 12,
-            };
-
-            // Prefer clubs, then diamonds, then spades, then hearts (to avoid penalties)
-            let suit_val = match card.suit {
-                hearts_core::model::suit::Suit::Clubs => 0,
-                hearts_core::model::suit::Suit::Diamonds => 1,
-                hearts_core::model::suit::Suit::Spades => 2,
-                hearts_core::model::suit::Suit::Hearts => 3,
-            };
-
-            (suit_val, rank_val)
+            let rank = card.rank.value() as i32;
+            match winning {
+                Some(top) if card.suit == top.suit && card.rank < top.rank => (0, 0, -rank),
+                Some(top) if card.suit == top.suit => (2, 0, rank),
+                Some(_) => (1, -(card.penalty_value() as i32), -rank),
+                None => {
+                    let suit = match card.suit {
+                        Suit::Clubs => 0,
+                        Suit::Diamonds => 1,
+                        Suit::Spades => 2,
+                        Suit::Hearts => 3,
+                    };
+                    (0, suit, rank)
+                }
+            }
         });
-
         moves
     }
 
@@ -489,14 +550,47 @@ impl DeepSearch {
         }
     }
 
-    #[allow(dead_code)]
     fn search_opponent(
         &mut self,
         round: &RoundState,
         depth: u8,
-        mut alpha: i32,
+        alpha: i32,
         beta: i32,
     ) -> Option<i32> {
+        self.opponent_node(round, depth, alpha, beta)
+            .map(|node| node.score)
+    }
+
+    /// [`Self::search_opponent`] with the peak of the subtree it searched.
+    /// Alpha only ever rises to meet beta here, so beta alone decides where
+    /// the search cuts off and what it returns.
+    fn opponent_node(
+        &mut self,
+        round: &RoundState,
+        depth: u8,
+        alpha: i32,
+        beta: i32,
+    ) -> Option<OpponentScore> {
+        let key = TableKey {
+            position: PositionKey::of(round),
+            depth,
+            node: NodeKind::Opponent,
+        };
+        if let Some(found) = self.tt.probe_opponent(&key, beta) {
+            return Some(found);
+        }
+        let node = self.search_opponent_uncached(round, depth, alpha, beta)?;
+        self.tt.store_opponent(key, beta, node);
+        Some(node)
+    }
+
+    fn search_opponent_uncached(
+        &mut self,
+        round: &RoundState,
+        depth: u8,
+        mut alpha: i32,
+        beta: i32,
+    ) -> Option<OpponentScore> {
         self.nodes_searched += 1;
 
         if self.time_expired() {
@@ -507,10 +601,14 @@ impl DeepSearch {
         let legal = legal_moves_for(round, next);
 
         if legal.is_empty() {
-            return Some(0);
+            return Some(OpponentScore {
+                score: 0,
+                peak: i32::MIN,
+            });
         }
 
         let mut best_score = i32::MIN;
+        let mut peak = i32::MIN;
 
         // Use move ordering for better pruning
         let ordered = self.order_moves_simple(&legal, round, next);
@@ -535,7 +633,9 @@ impl DeepSearch {
                         penalty_delta + self.evaluate_position(&probe, next)
                     } else {
                         // Continue to next trick with recursive search
-                        penalty_delta + self.search_opponent(&probe, depth - 1, alpha, beta)?
+                        let below = self.opponent_node(&probe, depth - 1, alpha, beta)?;
+                        peak = peak.max(below.peak);
+                        penalty_delta + below.score
                     }
                 }
                 _ => {
@@ -544,12 +644,15 @@ impl DeepSearch {
                         self.evaluate_position(&probe, next)
                     } else {
                         // Recursively search deeper
-                        self.search_opponent(&probe, depth - 1, alpha, beta)?
+                        let below = self.opponent_node(&probe, depth - 1, alpha, beta)?;
+                        peak = peak.max(below.peak);
+                        below.score
                     }
                 }
             };
 
             best_score = best_score.max(score);
+            peak = peak.max(score);
             alpha = alpha.max(score);
 
             if alpha >= beta {
@@ -557,7 +660,10 @@ impl DeepSearch {
             }
         }
 
-        Some(best_score)
+        Some(OpponentScore {
+            score: best_score,
+            peak,
+        })
     }
 
     fn search_next_trick(
@@ -619,6 +725,33 @@ impl DeepSearch {
         depth: u8,
         alpha: i32,
         beta: i32,
+    ) -> Option<i32> {
+        let key = TableKey {
+            position: PositionKey::of(round),
+            depth,
+            node: NodeKind::Lead {
+                seat,
+                card,
+                alpha,
+                beta,
+            },
+        };
+        if let Some(score) = self.tt.probe(&key) {
+            return Some(score);
+        }
+        let score = self.search_lead_uncached(round, seat, card, depth, alpha, beta)?;
+        self.tt.store(key, score);
+        Some(score)
+    }
+
+    fn search_lead_uncached(
+        &mut self,
+        round: &RoundState,
+        seat: PlayerPosition,
+        card: Card,
+        depth: u8,
+        alpha: i32,
+        beta: i32,
     ) -> Option<i32> {
         self.nodes_searched += 1;
 
@@ -633,12 +766,6 @@ impl DeepSearch {
             Err(_) => return Some(i32::MIN), // Illegal move
         };
 
-        // Check transposition table for cached result
-        let hash = hash_position(&new_round, seat);
-        if let Some(score) = self.tt.probe(hash, depth, alpha, beta) {
-            return Some(score);
-        }
-
         let result = match outcome {
             PlayOutcome::TrickCompleted { winner, penalties } => {
                 let penalty_delta = if winner == seat {
@@ -666,16 +793,6 @@ impl DeepSearch {
             }
         };
 
-        // Store result in transposition table
-        let node_type = if result <= alpha {
-            NodeType::UpperBound
-        } else if result >= beta {
-            NodeType::LowerBound
-        } else {
-            NodeType::Exact
-        };
-        self.tt.store(hash, depth, result, Some(card), node_type);
-
         Some(result)
     }
 
@@ -711,6 +828,19 @@ impl DeepSearch {
         evaluated.iter().map(|(_, score)| *score).max().unwrap_or(0)
     }
 
+    /// Work done by the last [`Self::choose_best_move`], reaching
+    /// `depth_reached`.
+    pub fn stats(&self, depth_reached: u8) -> SearchStats {
+        let (tt_entries, tt_hits, tt_misses) = self.tt.stats();
+        SearchStats {
+            nodes: self.nodes_searched,
+            depth_reached,
+            tt_entries,
+            tt_hits,
+            tt_misses,
+        }
+    }
+
     fn time_expired(&self) -> bool {
         self.start_time.elapsed() >= self.time_budget
     }
@@ -733,15 +863,7 @@ fn next_to_play(round: &RoundState) -> PlayerPosition {
 
 #[allow(dead_code)]
 fn legal_moves_for(round: &RoundState, seat: PlayerPosition) -> Vec<Card> {
-    round
-        .hand(seat)
-        .iter()
-        .copied()
-        .filter(|card| {
-            let mut probe = round.clone();
-            probe.play_card(seat, *card).is_ok()
-        })
-        .collect()
+    round.legal_plays(seat)
 }
 
 // ============================================================================
@@ -844,6 +966,9 @@ impl PlayPlannerHard {
 
         let mut search = DeepSearch::new(tt_size, time_ms);
         let result = search.choose_best_move(legal, ctx);
+        if let Ok(mut slot) = LAST_STATS.lock() {
+            *slot = Some(search.stats(result.depth_reached));
+        }
 
         Some(result.best_move)
     }
@@ -1,12 +1,17 @@
 use hearts_core::model::card::Card;
+use hearts_core::model::card_set::CardSet;
 use hearts_core::model::hand::Hand;
+use hearts_core::model::passing::PassCards;
+use hearts_core::model::per_suit::PerSuit;
 use hearts_core::model::player::PlayerPosition;
 use hearts_core::model::rank::Rank;
 use hearts_core::model::round::RoundState;
 use hearts_core::model::suit::Suit;
+use hearts_core::model::trick::{Play, Trick};
 use parking_lot::RwLock;
 use rand::Rng;
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 use std::array;
 use std::collections::{HashSet, VecDeque, hash_map::DefaultHasher};
 use std::hash::{Hash, Hasher};
@@ -14,7 +19,6 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-const SUIT_COUNT: usize = 4;
 const RANK_COUNT: usize = 13;
 
 #[allow(dead_code)]
@@ -22,7 +26,7 @@ const RANK_COUNT: usize = 13;
 pub struct BeliefState {
     #[allow(dead_code)]
     seat: PlayerPosition,
-    card_probs: [[f32; RANK_COUNT]; SUIT_COUNT],
+    card_probs: PerSuit<[f32; RANK_COUNT]>,
     total_mass: f32,
     #[allow(dead_code)]
     queen_spades_risk: f32,
@@ -36,7 +40,7 @@ impl BeliefState {
     pub fn new(seat: PlayerPosition) -> Self {
         Self {
             seat,
-            card_probs: [[0.0; RANK_COUNT]; SUIT_COUNT],
+            card_probs: PerSuit::splat([0.0; RANK_COUNT]),
             total_mass: 0.0,
             queen_spades_risk: 0.0,
             moon_likelihood: 0.0,
@@ -65,8 +69,7 @@ impl BeliefState {
     }
 
     pub fn card_probability(&self, card: Card) -> f32 {
-        let (suit_idx, rank_idx) = card_indices(card);
-        self.card_probs[suit_idx][rank_idx]
+        self.card_probs[card.suit][card.rank.index()]
     }
 
     pub fn reset_uniform(&mut self, cards: &[Card]) {
@@ -76,26 +79,25 @@ impl BeliefState {
         }
         let uniform = 1.0f32 / cards.len() as f32;
         for &card in cards {
-            let (suit_idx, rank_idx) = card_indices(card);
-            self.card_probs[suit_idx][rank_idx] = uniform;
+            self.card_probs[card.suit][card.rank.index()] = uniform;
         }
         self.total_mass = 1.0;
         self.recompute_entropy();
     }
 
     pub fn remove_card(&mut self, card: Card) {
-        let (suit_idx, rank_idx) = card_indices(card);
-        let removed = self.card_probs[suit_idx][rank_idx];
+        let slot = &mut self.card_probs[card.suit][card.rank.index()];
+        let removed = *slot;
         if removed == 0.0 {
             return;
         }
-        self.card_probs[suit_idx][rank_idx] = 0.0;
+        *slot = 0.0;
         if self.total_mass > 0.0 {
             let remaining = (self.total_mass - removed).max(0.0);
             self.total_mass = remaining;
             if remaining > 0.0 {
                 let scale = 1.0 / remaining;
-                for suit in &mut self.card_probs {
+                for suit in self.card_probs.values_mut() {
                     for prob in suit.iter_mut() {
                         if *prob > 0.0 {
                             *prob *= scale;
@@ -111,9 +113,8 @@ impl BeliefState {
     }
 
     pub fn remove_suit(&mut self, suit: Suit) {
-        let suit_idx = suit as usize;
         let mut removed_total = 0.0;
-        for prob in self.card_probs[suit_idx].iter_mut() {
+        for prob in self.card_probs[suit].iter_mut() {
             removed_total += *prob;
             *prob = 0.0;
         }
@@ -125,7 +126,7 @@ impl BeliefState {
             self.total_mass = remaining;
             if remaining > 0.0 {
                 let scale = 1.0 / remaining;
-                for suit_probs in &mut self.card_probs {
+                for suit_probs in self.card_probs.values_mut() {
                     for prob in suit_probs.iter_mut() {
                         if *prob > 0.0 {
                             *prob *= scale;
@@ -140,12 +141,36 @@ impl BeliefState {
         self.recompute_entropy();
     }
 
+    /// Multiplies the weight of every card of `suit` by `factor` and
+    /// renormalizes the rest, so the seat looks longer (`factor` above 1) or
+    /// shorter in the suit.
+    pub fn scale_suit(&mut self, suit: Suit, factor: f32) {
+        let before: f32 = self.card_probs[suit].iter().sum();
+        if before == 0.0 || self.total_mass == 0.0 {
+            return;
+        }
+        for prob in self.card_probs[suit].iter_mut() {
+            *prob *= factor;
+        }
+        let total = self.total_mass - before + before * factor;
+        if total > 0.0 {
+            let scale = 1.0 / total;
+            for suit_probs in self.card_probs.values_mut() {
+                for prob in suit_probs.iter_mut() {
+                    *prob *= scale;
+                }
+            }
+            self.total_mass = 1.0;
+        }
+        self.recompute_entropy();
+    }
+
     pub fn set_moon_likelihood(&mut self, value: f32) {
         self.moon_likelihood = value.clamp(0.0, 1.0);
     }
 
     pub fn clear(&mut self) {
-        for suit in &mut self.card_probs {
+        for suit in self.card_probs.values_mut() {
             suit.fill(0.0);
         }
         self.total_mass = 0.0;
@@ -330,15 +355,9 @@ impl BeliefCache {
     }
 }
 
-fn card_indices(card: Card) -> (usize, usize) {
-    let suit_idx = card.suit as usize;
-    let rank_idx = (card.rank.value() - 2) as usize;
-    (suit_idx, rank_idx)
-}
-
-fn entropy_of_matrix(matrix: &[[f32; RANK_COUNT]; SUIT_COUNT]) -> f32 {
+fn entropy_of_matrix(matrix: &PerSuit<[f32; RANK_COUNT]>) -> f32 {
     let mut entropy = 0.0f32;
-    for prob in matrix.iter().flat_map(|row| row.iter()) {
+    for prob in matrix.values().flat_map(|row| row.iter()) {
         if *prob > 0.0 {
             entropy -= *prob * prob.ln();
         }
@@ -402,21 +421,203 @@ fn belief_weighted_sampling_enabled() -> bool {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MoonState {
     Inactive,
     Considering,
     Committed,
 }
 
+/// Moon suspicion at which a received pass is read as a moon tell.
+const MOON_TELL_SUSPICION: f32 = 0.6;
+
+/// What the cards a seat received say about the seat that passed them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReceivedPassProfile {
+    pub giver: PlayerPosition,
+    pub cards: PassCards,
+    short: PerSuit<bool>,
+    kept_premium_hearts: bool,
+    moon_suspicion: f32,
+}
+
+impl ReceivedPassProfile {
+    pub fn infer(giver: PlayerPosition, cards: impl Into<PassCards>) -> Self {
+        let cards = cards.into();
+        let mut counts = PerSuit::splat(0usize);
+        for card in cards {
+            counts[card.suit] += 1;
+        }
+        // A pass normally sheds the most dangerous cards. A pass entirely below
+        // the jack with no heart in it means the giver held on to whatever high
+        // hearts it had.
+        let kept_premium_hearts =
+            counts[Suit::Hearts] == 0 && cards.iter().all(|card| card.rank < Rank::Jack);
+        let mut moon_suspicion = 0.0;
+        if kept_premium_hearts {
+            moon_suspicion += 0.45;
+            if cards.iter().all(|card| card.rank <= Rank::Eight) {
+                moon_suspicion += 0.25;
+            }
+            if counts.values().any(|&count| count == cards.len()) {
+                moon_suspicion += 0.2;
+            }
+        }
+        Self {
+            giver,
+            cards,
+            short: counts.map(|count| count >= 2),
+            kept_premium_hearts,
+            moon_suspicion,
+        }
+    }
+
+    /// Two or more of the passed cards were `suit`, so the giver is probably
+    /// short there and may soon discard on it.
+    pub fn giver_likely_short(&self, suit: Suit) -> bool {
+        self.short[suit]
+    }
+
+    pub fn giver_kept_premium_hearts(&self) -> bool {
+        self.kept_premium_hearts
+    }
+
+    /// 0-1 estimate that the giver is setting up a moon shot.
+    pub fn moon_suspicion(&self) -> f32 {
+        self.moon_suspicion
+    }
+
+    pub fn is_moon_tell(&self) -> bool {
+        self.moon_suspicion >= MOON_TELL_SUSPICION
+    }
+}
+
+/// Whether bots read the passed cards a seat plays in the first tricks
+/// (`MDH_FEATURE_PASS_INFERENCE`, off by default); see
+/// [`UnseenTracker::note_passed_card_played`].
+pub fn pass_inference_enabled() -> bool {
+    let v = std::env::var("MDH_FEATURE_PASS_INFERENCE").unwrap_or_default();
+    v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("on")
+}
+
+/// Tricks in which playing a passed card still says how the pass was used.
+const PASS_INFERENCE_TRICKS: usize = 4;
+/// Belief scale for a suit a seat played its passed card in: it kept length.
+const KEPT_SUIT_SCALE: f32 = 1.5;
+/// Belief scale for a suit a seat discarded its passed card from: it is
+/// working towards a void.
+const DUMPED_SUIT_SCALE: f32 = 0.5;
+
+/// Whether bots remember earlier rounds of the match
+/// (`MDH_FEATURE_MATCH_MEMORY`, off by default). Off, the controller records
+/// nothing and every round is played as if it were the first.
+pub fn match_memory_enabled() -> bool {
+    let v = std::env::var("MDH_FEATURE_MATCH_MEMORY").unwrap_or_default();
+    v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("on")
+}
+
+/// What the table has seen of the match so far. Unlike the rest of the
+/// tracker it survives `reset_for_round`: the controller feeds it each round
+/// as it is scored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchMemory {
+    rounds: u32,
+    /// Rounds in which the seat took the fewest points, ties included.
+    rounds_won: [u32; 4],
+    /// Rounds in which the seat took the most, ties included.
+    rounds_lost: [u32; 4],
+    moons_suffered: [u32; 4],
+    /// Rounds scored since the seat last suffered a moon, if it ever has.
+    since_moon: [Option<u32>; 4],
+    totals: [u32; 4],
+}
+
+impl MatchMemory {
+    /// Records a finished round from its raw penalties and the match totals
+    /// they produced. A moon counts as won by the shooter and lost, and
+    /// suffered, by everyone else.
+    pub fn note_round(&mut self, penalties: [u8; 4], totals: [u32; 4]) {
+        let shooter = penalties.iter().position(|&points| points == 26);
+        let points: [u8; 4] = match shooter {
+            Some(shooter) => array::from_fn(|idx| if idx == shooter { 0 } else { 26 }),
+            None => penalties,
+        };
+        let fewest = points.iter().copied().min().unwrap_or(0);
+        let most = points.iter().copied().max().unwrap_or(0);
+        for (idx, &taken) in points.iter().enumerate() {
+            if taken == fewest {
+                self.rounds_won[idx] += 1;
+            }
+            if taken == most {
+                self.rounds_lost[idx] += 1;
+            }
+            if let Some(since) = self.since_moon[idx].as_mut() {
+                *since += 1;
+            }
+            if shooter.is_some_and(|shooter| shooter != idx) {
+                self.moons_suffered[idx] += 1;
+                self.since_moon[idx] = Some(0);
+            }
+        }
+        self.rounds += 1;
+        self.totals = totals;
+    }
+
+    pub fn rounds_played(&self) -> u32 {
+        self.rounds
+    }
+
+    pub fn rounds_won(&self, seat: PlayerPosition) -> u32 {
+        self.rounds_won[seat.index()]
+    }
+
+    pub fn rounds_lost(&self, seat: PlayerPosition) -> u32 {
+        self.rounds_lost[seat.index()]
+    }
+
+    pub fn moons_suffered(&self, seat: PlayerPosition) -> u32 {
+        self.moons_suffered[seat.index()]
+    }
+
+    /// Whether `seat` suffered a moon in one of the last `rounds` rounds.
+    pub fn suffered_moon_within(&self, seat: PlayerPosition, rounds: u32) -> bool {
+        matches!(self.since_moon[seat.index()], Some(since) if since < rounds)
+    }
+
+    /// 1 for the lowest total; tied seats share a place.
+    pub fn placement(&self, seat: PlayerPosition) -> usize {
+        let mine = self.totals[seat.index()];
+        1 + self.totals.iter().filter(|&&total| total < mine).count()
+    }
+
+    /// How far `seat` is ahead of the next seat, when it leads alone.
+    pub fn lead_margin(&self, seat: PlayerPosition) -> Option<u32> {
+        let mine = self.totals[seat.index()];
+        let next = self
+            .totals
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| idx != seat.index())
+            .map(|(_, &total)| total)
+            .min()?;
+        (mine < next).then(|| next - mine)
+    }
+}
+
 #[derive(Debug)]
 pub struct UnseenTracker {
-    unseen: HashSet<Card>,
+    unseen: CardSet,
     // Known suit voids per seat (seat_idx x suit_idx)
-    voids: [[bool; 4]; 4],
+    voids: [PerSuit<bool>; 4],
     // Moon attempt state per seat for Stage 2 heuristics
     moon: [MoonState; 4],
+    // What each seat's received pass says about its giver, by receiving seat
+    received: [Option<ReceivedPassProfile>; 4],
+    // Suit length read from passed cards played early, per seat (1.0 = none)
+    passed_lean: [PerSuit<f32>; 4],
     beliefs: [BeliefState; 4],
+    // Earlier rounds of the match; kept across rounds
+    memory: MatchMemory,
     belief_cache: Arc<RwLock<BeliefCache>>,
     belief_cache_hits: AtomicUsize,
     belief_cache_misses: AtomicUsize,
@@ -425,10 +626,13 @@ pub struct UnseenTracker {
 impl Clone for UnseenTracker {
     fn clone(&self) -> Self {
         Self {
-            unseen: self.unseen.clone(),
+            unseen: self.unseen,
             voids: self.voids,
             moon: self.moon,
+            received: self.received,
+            passed_lean: self.passed_lean,
             beliefs: self.beliefs.clone(),
+            memory: self.memory,
             belief_cache: Arc::clone(&self.belief_cache),
             belief_cache_hits: AtomicUsize::new(self.belief_cache_hits.load(Ordering::Relaxed)),
             belief_cache_misses: AtomicUsize::new(self.belief_cache_misses.load(Ordering::Relaxed)),
@@ -449,10 +653,13 @@ impl UnseenTracker {
         });
         let cache_capacity = belief_cache_capacity_from_env();
         let mut tracker = Self {
-            unseen: full_deck_cards().collect(),
-            voids: [[false; 4]; 4],
+            unseen: CardSet::DECK,
+            voids: [PerSuit::splat(false); 4],
             moon: [MoonState::Inactive; 4],
+            received: [None; 4],
+            passed_lean: [PerSuit::splat(1.0); 4],
             beliefs,
+            memory: MatchMemory::default(),
             belief_cache: Arc::new(RwLock::new(BeliefCache::new(cache_capacity))),
             belief_cache_hits: AtomicUsize::new(0),
             belief_cache_misses: AtomicUsize::new(0),
@@ -472,7 +679,7 @@ impl UnseenTracker {
     }
 
     fn rebuild_beliefs_uniform(&mut self) {
-        let cards: Vec<Card> = self.unseen.iter().copied().collect();
+        let cards: Vec<Card> = self.unseen.iter().collect();
         for (idx, belief) in self.beliefs.iter_mut().enumerate() {
             let seat = PlayerPosition::from_index(idx).unwrap_or(PlayerPosition::North);
             *belief = BeliefState::new(seat);
@@ -480,14 +687,23 @@ impl UnseenTracker {
         }
     }
 
-    pub fn reset_for_round(&mut self, round: &RoundState) {
-        self.unseen = full_deck_cards().collect();
-        self.voids = [[false; 4]; 4];
+    fn clear_round(&mut self) {
+        self.unseen = CardSet::DECK;
+        self.voids = [PerSuit::splat(false); 4];
         self.moon = [MoonState::Inactive; 4];
+        self.received = [None; 4];
+        self.passed_lean = [PerSuit::splat(1.0); 4];
         self.belief_cache_hits.store(0, Ordering::Relaxed);
         self.belief_cache_misses.store(0, Ordering::Relaxed);
         self.rebuild_beliefs_uniform();
         self.belief_cache.write().clear();
+    }
+
+    /// Starts `round` over, marking the cards already played as seen. Voids
+    /// and moon reads are not taken from them; [`Self::rebuild_for_round`]
+    /// does that.
+    pub fn reset_for_round(&mut self, round: &RoundState) {
+        self.clear_round();
         for trick in round.trick_history() {
             for play in trick.plays() {
                 self.note_card_revealed(play.card);
@@ -496,43 +712,309 @@ impl UnseenTracker {
         for play in round.current_trick().plays() {
             self.note_card_revealed(play.card);
         }
+        self.debug_assert_no_carryover(round);
+    }
+
+    /// Starts `round` over and replays every card played so far through
+    /// [`Self::observe_play`], leaving the tracker a live one would hold at
+    /// this point. Pass reads are not part of the round and stay cleared.
+    pub fn rebuild_for_round(&mut self, round: &RoundState) {
+        self.clear_round();
+        let mut hearts_broken = false;
+        for trick in round
+            .trick_history()
+            .iter()
+            .chain(core::iter::once(round.current_trick()))
+        {
+            let mut so_far = Trick::new(trick.leader());
+            for play in trick.plays() {
+                so_far
+                    .play(play.position, play.card)
+                    .expect("replaying a trick in its own order");
+                hearts_broken |= play.card.is_penalty();
+                self.observe_play(&so_far, hearts_broken);
+            }
+        }
+        self.debug_assert_no_carryover(round);
+    }
+
+    // Every card not yet played this round is unseen again, including the
+    // caller's own hand; anything less is a reveal left over from an earlier
+    // round.
+    fn debug_assert_no_carryover(&self, round: &RoundState) {
+        if cfg!(debug_assertions) {
+            let played: CardSet = round
+                .trick_history()
+                .iter()
+                .chain(core::iter::once(round.current_trick()))
+                .flat_map(|trick| trick.plays().iter().map(|play| play.card))
+                .collect();
+            debug_assert_eq!(
+                self.unseen.len(),
+                52 - played.len(),
+                "tracker kept reveals from an earlier round"
+            );
+        }
+    }
+
+    /// Folds the last play of `trick` into the tracker: the reveal, a void
+    /// when it did not follow, and the moon reads once it completes the
+    /// trick. `trick` holds the plays up to and including this one and
+    /// `hearts_broken` is the round's flag after it. The cost does not grow
+    /// with the round, so a tracker carried from play to play stays current
+    /// without rescanning the history.
+    pub fn observe_play(&mut self, trick: &Trick, hearts_broken: bool) {
+        let Some(&Play { position, card }) = trick.plays().last() else {
+            return;
+        };
+        self.note_card_played(position, card);
+        if let Some(lead) = trick.lead_suit()
+            && card.suit != lead
+        {
+            self.note_void(position, lead);
+        }
+        if pass_inference_enabled()
+            && let Some(lead) = trick.lead_suit()
+        {
+            let trick_index = (52 - self.unseen.len()).saturating_sub(1) / 4;
+            self.note_passed_card_played(position, card, trick_index, lead);
+        }
+        if let Some(winner) = trick.winner() {
+            let plays: Vec<(PlayerPosition, Card)> = trick
+                .plays()
+                .iter()
+                .map(|play| (play.position, play.card))
+                .collect();
+            self.note_trick_completion(&plays, winner, trick.penalty_total(), hearts_broken);
+        }
+    }
+
+    /// [`Self::observe_play`] for the card `round` has just taken, which is
+    /// the last of the current trick or, when it completed one, of the last
+    /// finished trick.
+    pub fn observe_latest_play(&mut self, round: &RoundState) {
+        let trick = if round.current_trick().plays().is_empty() {
+            round.trick_history().last()
+        } else {
+            Some(round.current_trick())
+        };
+        if let Some(trick) = trick {
+            self.observe_play(trick, round.hearts_broken());
+        }
+    }
+
+    pub fn match_memory(&self) -> &MatchMemory {
+        &self.memory
+    }
+
+    pub fn set_match_memory(&mut self, memory: MatchMemory) {
+        self.memory = memory;
+    }
+
+    /// Remembers a scored round for the rest of the match; see
+    /// [`MatchMemory::note_round`].
+    pub fn note_round_scored(&mut self, penalties: [u8; 4], totals: [u32; 4]) {
+        self.memory.note_round(penalties, totals);
     }
 
     pub fn note_pass_selection(&mut self, _seat: PlayerPosition, _cards: &[Card]) {
         // Passing moves cards between hidden hands; it should not reveal them.
     }
 
+    /// Records the pass `receiver` got from `giver` once passes resolve. A moon
+    /// tell also raises the giver's moon likelihood.
+    pub fn note_pass_received(
+        &mut self,
+        receiver: PlayerPosition,
+        giver: PlayerPosition,
+        cards: impl Into<PassCards>,
+    ) {
+        let profile = ReceivedPassProfile::infer(giver, cards);
+        let belief = &mut self.beliefs[giver.index()];
+        let raised = belief.moon_likelihood().max(profile.moon_suspicion());
+        belief.set_moon_likelihood(raised);
+        self.received[receiver.index()] = Some(profile);
+    }
+
+    pub fn received_pass(&self, receiver: PlayerPosition) -> Option<&ReceivedPassProfile> {
+        self.received[receiver.index()].as_ref()
+    }
+
+    /// Reads `seat` playing `card`, a card it was passed, in trick
+    /// `trick_index` (0-based) on a `lead` of that suit or another. Within
+    /// the first few tricks a passed card played in suit means the seat kept
+    /// the suit and is long in it; one discarded means the seat is emptying
+    /// the suit. Either scales the seat's belief weights for the suit.
+    /// Cards the seat was not passed, and later tricks, change nothing.
+    pub fn note_passed_card_played(
+        &mut self,
+        seat: PlayerPosition,
+        card: Card,
+        trick_index: usize,
+        lead: Suit,
+    ) {
+        if trick_index >= PASS_INFERENCE_TRICKS {
+            return;
+        }
+        let passed = self.received[seat.index()]
+            .as_ref()
+            .is_some_and(|profile| profile.cards.as_slice().contains(&card));
+        if !passed {
+            return;
+        }
+        let factor = if card.suit == lead {
+            KEPT_SUIT_SCALE
+        } else {
+            DUMPED_SUIT_SCALE
+        };
+        self.passed_lean[seat.index()][card.suit] *= factor;
+        self.beliefs[seat.index()].scale_suit(card.suit, factor);
+    }
+
+    /// How the passed cards `seat` played early read its length in `suit`:
+    /// above 1 when it kept the suit, below 1 when it is shedding it, 1
+    /// without a read.
+    pub fn passed_suit_lean(&self, seat: PlayerPosition, suit: Suit) -> f32 {
+        self.passed_lean[seat.index()][suit]
+    }
+
+    /// Who holds `card` as far as `observer` knows from the pass alone: the
+    /// seat it passed the card to, or itself for a card it was passed.
+    /// `None` once the card is played or when the pass did not involve it.
+    pub fn known_holder(&self, observer: PlayerPosition, card: Card) -> Option<PlayerPosition> {
+        if !self.is_unseen(card) {
+            return None;
+        }
+        self.received
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, profile)| {
+                Some((PlayerPosition::from_index(idx)?, profile.as_ref()?))
+            })
+            .find(|(receiver, profile)| {
+                (profile.giver == observer || *receiver == observer)
+                    && profile.cards.as_slice().contains(&card)
+            })
+            .map(|(receiver, _)| receiver)
+    }
+
+    /// Probability, as `observer` sees it, that `seat` holds `card`. A card
+    /// the pass placed is certain; otherwise the mass is the seats' belief
+    /// weights over the opponents of `observer` not known void in the suit.
+    /// Cards in `observer`'s own hand beyond the pass are not known here.
+    pub fn holding_probability(
+        &self,
+        observer: PlayerPosition,
+        seat: PlayerPosition,
+        card: Card,
+    ) -> f32 {
+        if !self.is_unseen(card) {
+            return 0.0;
+        }
+        if let Some(holder) = self.known_holder(observer, card) {
+            return if holder == seat { 1.0 } else { 0.0 };
+        }
+        let weight = |other: PlayerPosition| {
+            if other == observer || self.is_void(other, card.suit) {
+                0.0
+            } else {
+                self.beliefs[other.index()].card_probability(card)
+            }
+        };
+        let total: f32 = PlayerPosition::LOOP.into_iter().map(weight).sum();
+        if total > 0.0 {
+            weight(seat) / total
+        } else {
+            0.0
+        }
+    }
+
     pub fn note_card_played(&mut self, _seat: PlayerPosition, card: Card) {
         self.note_card_revealed(card);
     }
 
     pub fn note_card_revealed(&mut self, card: Card) {
-        self.unseen.remove(&card);
+        self.unseen.remove(card);
         for belief in &mut self.beliefs {
             belief.remove_card(card);
         }
     }
 
     pub fn is_unseen(&self, card: Card) -> bool {
-        self.unseen.contains(&card)
+        self.unseen.contains(card)
     }
 
     pub fn unseen_count(&self) -> usize {
         self.unseen.len()
     }
 
-    #[inline]
-    fn suit_index(suit: Suit) -> usize {
-        suit as usize
+    /// Cards of `suit` not yet played, including any in the caller's own hand.
+    pub fn unseen_in_suit(&self, suit: Suit) -> usize {
+        self.unseen.count_in_suit(suit)
     }
 
     pub fn note_void(&mut self, seat: PlayerPosition, suit: Suit) {
-        self.voids[seat.index()][Self::suit_index(suit)] = true;
+        self.voids[seat.index()][suit] = true;
         self.beliefs[seat.index()].remove_suit(suit);
     }
 
     pub fn is_void(&self, seat: PlayerPosition, suit: Suit) -> bool {
-        self.voids[seat.index()][Self::suit_index(suit)]
+        self.voids[seat.index()][suit]
+    }
+
+    /// The voids `observer`, holding `hand`, can infer: those seen in play,
+    /// and every other seat in a suit whose unseen cards all sit in `hand`.
+    pub fn infer_voids(&self, observer: PlayerPosition, hand: &Hand) -> [PerSuit<bool>; 4] {
+        let elsewhere = self.unseen - hand.as_set();
+        array::from_fn(|idx| {
+            PerSuit::from_fn(|suit| {
+                self.voids[idx][suit]
+                    || (idx != observer.index() && elsewhere.suit(suit).is_empty())
+            })
+        })
+    }
+
+    /// How much of an opponent's hand each suit is thought to be, 0-100, for
+    /// the opponents `observer` can place void in two suits or more. Their
+    /// cards are then concentrated in what is left, and leading that suit
+    /// keeps handing them safe follows while everyone else runs out.
+    ///
+    /// The estimate splits each suit's cards outside the observer's hand
+    /// evenly between the opponents not void in it, and takes the suit's
+    /// part of the opponent's estimated holding. A suit counts at the highest
+    /// value any such opponent gives it, and is 0 once nobody else holds it.
+    pub fn danger_suits(&self, observer: PlayerPosition, round: &RoundState) -> PerSuit<u8> {
+        let hand = round.hand(observer);
+        let voids = self.infer_voids(observer, hand);
+        let elsewhere = self.unseen - hand.as_set();
+        let opponents = || PlayerPosition::LOOP.into_iter().filter(|&p| p != observer);
+        let estimate = PerSuit::from_fn(|suit| {
+            let holders = opponents().filter(|p| !voids[p.index()][suit]).count();
+            if holders == 0 {
+                0.0
+            } else {
+                elsewhere.count_in_suit(suit) as f32 / holders as f32
+            }
+        });
+        let mut danger = PerSuit::splat(0u8);
+        for seat in opponents() {
+            let void = &voids[seat.index()];
+            if void.values().filter(|&&v| v).count() < 2 || round.hand(seat).is_empty() {
+                continue;
+            }
+            let held: f32 = Suit::iter()
+                .filter(|&suit| !void[suit])
+                .map(|suit| estimate[suit])
+                .sum();
+            if held <= 0.0 {
+                continue;
+            }
+            for suit in Suit::iter().filter(|&suit| !void[suit]) {
+                let share = (100.0 * estimate[suit] / held).round() as u8;
+                danger[suit] = danger[suit].max(share);
+            }
+        }
+        danger
     }
 
     pub fn moon_state(&self, seat: PlayerPosition) -> MoonState {
@@ -651,7 +1133,7 @@ impl UnseenTracker {
         world.seed = seed;
 
         // Collect all unseen cards
-        let mut unseen_cards: Vec<Card> = self.unseen.iter().copied().collect();
+        let mut unseen_cards: Vec<Card> = self.unseen.iter().collect();
 
         // Determine how many cards each player should get
         // Each opponent should have approximately (unseen_count / 3) cards
@@ -690,9 +1172,19 @@ impl UnseenTracker {
         }
 
         // Distribute cards respecting void constraints
-        let mut dealt = HashSet::new();
+        let mut dealt = CardSet::new();
+        // Cards we passed sit with their receiver; cards passed to us are ours.
+        for card in &unseen_cards {
+            let Some(holder) = self.known_holder(our_seat, *card) else {
+                continue;
+            };
+            if holder != our_seat {
+                world.hands[holder.index()].push(*card);
+            }
+            dealt.insert(*card);
+        }
         for card in &unseen_cards {
-            if dealt.contains(card) {
+            if dealt.contains(*card) {
                 continue;
             }
 
@@ -766,6 +1258,80 @@ impl UnseenTracker {
         world
     }
 
+    /// A deal of every card `observer` cannot see, giving each other seat
+    /// exactly as many cards as it holds in `round`. Cards the pass placed go
+    /// to their holder. The rest are drawn by [`Self::holding_probability`],
+    /// the card with the fewest seats open to it first, and a void read is
+    /// only broken when no seat with room is left open for the suit.
+    pub fn sample_consistent_world(
+        &self,
+        rng: &mut impl Rng,
+        observer: PlayerPosition,
+        round: &RoundState,
+    ) -> SampledWorld {
+        let mut world = SampledWorld::new();
+        world.seed = rng.r#gen();
+        let own = round.hand(observer);
+        let mut room = PlayerPosition::LOOP.map(|seat| {
+            if seat == observer {
+                0
+            } else {
+                round.hand(seat).len()
+            }
+        });
+        let mut open: Vec<Card> = (self.unseen - own.as_set()).iter().collect();
+        open.retain(|&card| match self.known_holder(observer, card) {
+            Some(holder) if room[holder.index()] > 0 => {
+                world.hands[holder.index()].push(card);
+                room[holder.index()] -= 1;
+                false
+            }
+            _ => true,
+        });
+        while !open.is_empty() {
+            let seats_for = |card: Card| -> Vec<PlayerPosition> {
+                PlayerPosition::LOOP
+                    .into_iter()
+                    .filter(|&seat| room[seat.index()] > 0 && !self.is_void(seat, card.suit))
+                    .collect()
+            };
+            let (idx, mut seats) = open
+                .iter()
+                .map(|&card| seats_for(card))
+                .enumerate()
+                .min_by_key(|(_, seats)| seats.len())
+                .expect("open is not empty");
+            let card = open.remove(idx);
+            if seats.is_empty() {
+                seats = PlayerPosition::LOOP
+                    .into_iter()
+                    .filter(|&seat| room[seat.index()] > 0)
+                    .collect();
+            }
+            let Some(&first) = seats.first() else {
+                // More cards unseen than seats have room for: the tracker
+                // and the round disagree, so leave the card out.
+                continue;
+            };
+            let weights: Vec<f32> = seats
+                .iter()
+                .map(|&seat| self.holding_probability(observer, seat, card).max(0.001))
+                .collect();
+            let mut roll = rng.r#gen::<f32>() * weights.iter().sum::<f32>();
+            let mut chosen = first;
+            for (&seat, weight) in seats.iter().zip(weights) {
+                chosen = seat;
+                if roll < weight {
+                    break;
+                }
+                roll -= weight;
+            }
+            world.hands[chosen.index()].push(card);
+            room[chosen.index()] -= 1;
+        }
+        world
+    }
+
     /// Calculate the "certainty" of a card's ownership - higher means more certain
     /// which player has it (used for sorting in belief-weighted sampling)
     fn card_certainty(&self, card: Card, our_seat: PlayerPosition) -> f32 {
@@ -800,23 +1366,18 @@ impl UnseenTracker {
     }
 }
 
-fn full_deck_cards() -> impl Iterator<Item = Card> {
-    Suit::ALL.into_iter().flat_map(|suit| {
-        Rank::ORDERED
-            .into_iter()
-            .map(move |rank| Card::new(rank, suit))
-    })
-}
-
 #[cfg(test)]
 mod tests {
-    use super::UnseenTracker;
+    use super::{MoonState, ReceivedPassProfile, UnseenTracker};
+    use hearts_core::game::match_state::MatchState;
     use hearts_core::model::card::Card;
+    use hearts_core::model::card_set::CardSet;
     use hearts_core::model::deck::Deck;
-    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::hand::Hand;
+    use hearts_core::model::passing::{PassCards, PassingDirection};
     use hearts_core::model::player::PlayerPosition;
     use hearts_core::model::rank::Rank;
-    use hearts_core::model::round::RoundState;
+    use hearts_core::model::round::{RoundPhase, RoundState};
     use hearts_core::model::suit::Suit;
     use rand::SeedableRng;
     use rand::rngs::StdRng;
@@ -852,6 +1413,30 @@ mod tests {
         assert_eq!(tracker.unseen_count(), 52);
     }
 
+    #[test]
+    fn holding_every_unseen_card_of_a_suit_voids_the_other_seats() {
+        let mut tracker = UnseenTracker::new();
+        let hand = Hand::parse("QH KH AH 2C 3C").unwrap();
+        for card in CardSet::DECK.iter().filter(|c| c.suit == Suit::Hearts) {
+            if !hand.contains(card) {
+                tracker.note_card_revealed(card);
+            }
+        }
+        tracker.note_void(PlayerPosition::South, Suit::Clubs);
+        let voids = tracker.infer_voids(PlayerPosition::North, &hand);
+        for seat in [
+            PlayerPosition::East,
+            PlayerPosition::South,
+            PlayerPosition::West,
+        ] {
+            assert!(voids[seat.index()][Suit::Hearts], "{seat:?}");
+            assert!(!voids[seat.index()][Suit::Spades], "{seat:?}");
+        }
+        assert!(!voids[PlayerPosition::North.index()][Suit::Hearts]);
+        assert!(voids[PlayerPosition::South.index()][Suit::Clubs]);
+        assert!(!voids[PlayerPosition::East.index()][Suit::Clubs]);
+    }
+
     #[test]
     fn tracker_voids_and_moon_state() {
         let mut tracker = UnseenTracker::new();
@@ -994,8 +1579,8 @@ mod tests {
         for seat in PlayerPosition::LOOP.iter().copied() {
             let mut cards1: Vec<Card> = world1.hand(seat).to_vec();
             let mut cards2: Vec<Card> = world2.hand(seat).to_vec();
-            cards1.sort_by_key(|c| (c.suit as u8, c.rank.value()));
-            cards2.sort_by_key(|c| (c.suit as u8, c.rank.value()));
+            cards1.sort_by_key(|c| (c.suit, c.rank));
+            cards2.sort_by_key(|c| (c.suit, c.rank));
 
             assert_eq!(
                 cards1, cards2,
@@ -1044,4 +1629,447 @@ mod tests {
             qs_in_south_count
         );
     }
+
+    #[test]
+    fn low_one_suit_pass_reads_as_moon_tell() {
+        let giver = PlayerPosition::West;
+        let receiver = PlayerPosition::North;
+        let mut tracker = UnseenTracker::new();
+        tracker.note_pass_received(
+            receiver,
+            giver,
+            [
+                Card::new(Rank::Two, Suit::Diamonds),
+                Card::new(Rank::Four, Suit::Diamonds),
+                Card::new(Rank::Six, Suit::Diamonds),
+            ],
+        );
+        let profile = tracker.received_pass(receiver).expect("profile stored");
+        assert_eq!(profile.giver, giver);
+        assert!(profile.giver_likely_short(Suit::Diamonds));
+        assert!(!profile.giver_likely_short(Suit::Clubs));
+        assert!(profile.giver_kept_premium_hearts());
+        assert!(profile.is_moon_tell());
+        assert!(tracker.belief_state(giver).moon_likelihood() >= profile.moon_suspicion());
+        assert!(tracker.received_pass(PlayerPosition::East).is_none());
+
+        let round = RoundState::deal(
+            &Deck::shuffled_with_seed(4),
+            receiver,
+            PassingDirection::Left,
+        );
+        tracker.reset_for_round(&round);
+        assert!(tracker.received_pass(receiver).is_none());
+    }
+
+    #[test]
+    fn dumping_danger_cards_is_no_moon_tell() {
+        let dump = ReceivedPassProfile::infer(
+            PlayerPosition::East,
+            [
+                Card::new(Rank::Queen, Suit::Spades),
+                Card::new(Rank::Ace, Suit::Hearts),
+                Card::new(Rank::King, Suit::Hearts),
+            ],
+        );
+        assert!(!dump.giver_kept_premium_hearts());
+        assert_eq!(dump.moon_suspicion(), 0.0);
+        assert!(dump.giver_likely_short(Suit::Hearts));
+
+        // High side-suit cards are an ordinary shortening pass, not a tell.
+        let shortening = ReceivedPassProfile::infer(
+            PlayerPosition::East,
+            [
+                Card::new(Rank::Ace, Suit::Clubs),
+                Card::new(Rank::King, Suit::Clubs),
+                Card::new(Rank::Two, Suit::Diamonds),
+            ],
+        );
+        assert!(shortening.giver_likely_short(Suit::Clubs));
+        assert!(!shortening.is_moon_tell());
+    }
+
+    /// Plays `state`'s round to the end: the pass (each seat its first
+    /// cards), then the first legal card for each seat in turn, feeding the
+    /// tracker what live play would.
+    fn play_out_round(state: &mut MatchState, tracker: &mut UnseenTracker) {
+        let round = state.round_mut();
+        pass_first_cards(round, tracker);
+        while round.tricks_completed() < 13 {
+            play_first_legal(round, tracker);
+        }
+    }
+
+    /// Resolves `round`'s pass with each seat passing its first cards and
+    /// returns what each seat passed; nothing on a hold hand.
+    fn pass_first_cards(
+        round: &mut RoundState,
+        tracker: &mut UnseenTracker,
+    ) -> Vec<(PlayerPosition, PassCards)> {
+        let RoundPhase::Passing(passing) = round.phase() else {
+            return Vec::new();
+        };
+        let direction = passing.direction();
+        let count = round.pass_count();
+        let passes: Vec<(PlayerPosition, PassCards)> = PlayerPosition::LOOP
+            .iter()
+            .map(|&seat| {
+                let cards = &round.hand(seat).cards()[..count];
+                (seat, PassCards::from_slice(cards).unwrap())
+            })
+            .collect();
+        for &(seat, cards) in &passes {
+            tracker.note_pass_selection(seat, cards.as_slice());
+            round.submit_pass(seat, cards).unwrap();
+        }
+        round.resolve_passes().unwrap();
+        for &(seat, cards) in &passes {
+            tracker.note_pass_received(direction.target(seat), seat, cards);
+        }
+        passes
+    }
+
+    /// Plays the first legal card for the seat to play.
+    fn play_first_legal(round: &mut RoundState, tracker: &mut UnseenTracker) {
+        let trick = round.current_trick();
+        let seat = trick
+            .plays()
+            .last()
+            .map_or(trick.leader(), |play| play.position.next());
+        let card = round.legal_plays(seat)[0];
+        round.play_card(seat, card).unwrap();
+        tracker.observe_latest_play(round);
+    }
+
+    #[test]
+    fn passed_cards_stay_certain_with_their_receiver_until_played() {
+        use PlayerPosition::{East, North, South};
+        let mut state = MatchState::with_seed(North, 2259);
+        assert_eq!(state.passing_direction(), PassingDirection::Left);
+        let round = state.round_mut();
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(round);
+        let passes = pass_first_cards(round, &mut tracker);
+        let north_pass = passes[North.index()].1;
+
+        let mut rng = StdRng::seed_from_u64(2259);
+        let world = tracker.sample_world(&mut rng, North, round);
+        for card in north_pass {
+            assert!(world.hand(East).contains(&card), "{card}");
+        }
+
+        let mut checked = 0;
+        while round.tricks_completed() < 13 {
+            for card in north_pass {
+                if !tracker.is_unseen(card) {
+                    assert_eq!(tracker.known_holder(North, card), None);
+                    continue;
+                }
+                checked += 1;
+                assert_eq!(tracker.known_holder(North, card), Some(East));
+                assert_eq!(tracker.known_holder(East, card), Some(East));
+                assert_eq!(tracker.known_holder(South, card), None);
+                for seat in PlayerPosition::LOOP {
+                    let expected = if seat == East { 1.0 } else { 0.0 };
+                    assert_eq!(tracker.holding_probability(North, seat, card), expected);
+                }
+                assert!(
+                    !tracker.is_void(East, card.suit),
+                    "{card} held by a void seat"
+                );
+                // South knows nothing of the pass: the card is spread over
+                // its opponents.
+                let spread: f32 = PlayerPosition::LOOP
+                    .into_iter()
+                    .map(|seat| tracker.holding_probability(South, seat, card))
+                    .sum();
+                assert!((spread - 1.0).abs() < 1e-5, "{spread}");
+            }
+            play_first_legal(round, &mut tracker);
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn consistent_worlds_fill_each_hand_and_keep_every_read() {
+        use PlayerPosition::{East, North};
+        let mut state = MatchState::with_seed(North, 2260);
+        let round = state.round_mut();
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(round);
+        let passes = pass_first_cards(round, &mut tracker);
+        let north_pass = passes[North.index()].1;
+
+        let mut rng = StdRng::seed_from_u64(2260);
+        while round.tricks_completed() < 13 {
+            let world = tracker.sample_consistent_world(&mut rng, North, round);
+            assert!(world.hand(North).is_empty());
+            for seat in PlayerPosition::LOOP.into_iter().filter(|&s| s != North) {
+                let held = world.hand(seat);
+                assert_eq!(held.len(), round.hand(seat).len(), "{seat}");
+                for card in held {
+                    assert!(tracker.is_unseen(*card) && !round.hand(North).contains(*card));
+                    assert!(!tracker.is_void(seat, card.suit), "{seat} void in {card}");
+                }
+            }
+            for card in north_pass {
+                if tracker.is_unseen(card) {
+                    assert!(world.hand(East).contains(&card), "{card}");
+                }
+            }
+            play_first_legal(round, &mut tracker);
+        }
+    }
+
+    /// Asserts the two trackers read every card, void and moon likelihood
+    /// the same.
+    fn assert_same_reads(carried: &UnseenTracker, rebuilt: &UnseenTracker, after: usize) {
+        for card in CardSet::DECK {
+            assert_eq!(
+                carried.is_unseen(card),
+                rebuilt.is_unseen(card),
+                "{card} after {after} plays"
+            );
+        }
+        for seat in PlayerPosition::LOOP {
+            for suit in Suit::iter() {
+                assert_eq!(carried.is_void(seat, suit), rebuilt.is_void(seat, suit));
+            }
+            let (a, b) = (carried.belief_state(seat), rebuilt.belief_state(seat));
+            for card in CardSet::DECK {
+                assert_eq!(
+                    a.card_probability(card),
+                    b.card_probability(card),
+                    "{seat} {card} after {after} plays"
+                );
+            }
+            assert_eq!(a.moon_likelihood(), b.moon_likelihood(), "{seat}");
+            assert_eq!(a.entropy(), b.entropy(), "{seat}");
+        }
+    }
+
+    #[test]
+    fn carried_tracker_matches_a_rebuild_after_every_play() {
+        let mut round = RoundState::deal(
+            &Deck::shuffled_with_seed(2258),
+            PlayerPosition::North,
+            PassingDirection::Hold,
+        );
+        let mut carried = UnseenTracker::new();
+        carried.reset_for_round(&round);
+        let mut plays = 0;
+        while round.tricks_completed() < 13 {
+            let trick = round.current_trick();
+            let seat = trick
+                .plays()
+                .last()
+                .map_or(trick.leader(), |play| play.position.next());
+            // Spread the choices so seats run out of suits and break hearts.
+            let legal = round.legal_plays(seat);
+            let card = legal[plays * 7 % legal.len()];
+            round.play_card(seat, card).unwrap();
+            carried.observe_latest_play(&round);
+            plays += 1;
+
+            let mut rebuilt = UnseenTracker::new();
+            rebuilt.rebuild_for_round(&round);
+            assert_same_reads(&carried, &rebuilt, plays);
+        }
+        let voids = PlayerPosition::LOOP
+            .iter()
+            .flat_map(|&seat| Suit::iter().map(move |suit| (seat, suit)))
+            .filter(|&(seat, suit)| carried.is_void(seat, suit))
+            .count();
+        assert!(voids > 0, "the round should exercise void reads");
+    }
+
+    /// Asserts `tracker` knows nothing about `round` that a tracker created
+    /// for it would not.
+    fn assert_no_carryover(tracker: &UnseenTracker, round: &RoundState) {
+        let mut fresh = UnseenTracker::new();
+        fresh.reset_for_round(round);
+        assert_eq!(tracker.unseen_count(), fresh.unseen_count());
+        for seat in PlayerPosition::LOOP {
+            assert!(tracker.received_pass(seat).is_none(), "{seat:?} pass");
+            assert_eq!(tracker.moon_state(seat), MoonState::Inactive, "{seat:?}");
+            for suit in Suit::ALL {
+                assert!(!tracker.is_void(seat, suit), "{seat:?} void in {suit:?}");
+            }
+            let (belief, expected) = (tracker.belief_state(seat), fresh.belief_state(seat));
+            assert_eq!(
+                belief.moon_likelihood(),
+                expected.moon_likelihood(),
+                "{seat:?}"
+            );
+            for card in CardSet::DECK {
+                assert_eq!(
+                    belief.card_probability(card),
+                    expected.card_probability(card),
+                    "{seat:?} {card}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn next_round_starts_without_the_last_rounds_passes_or_reads() {
+        let mut state = MatchState::with_seed(PlayerPosition::North, 2256);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(state.round());
+        assert_eq!(state.passing_direction(), PassingDirection::Left);
+        play_out_round(&mut state, &mut tracker);
+        tracker.set_moon_state(PlayerPosition::East, MoonState::Committed);
+        assert!(
+            PlayerPosition::LOOP
+                .iter()
+                .any(|&seat| { Suit::ALL.iter().any(|&suit| tracker.is_void(seat, suit)) })
+        );
+        assert!(tracker.received_pass(PlayerPosition::East).is_some());
+
+        assert_eq!(state.finish_round_and_start_next(), None);
+        tracker.reset_for_round(state.round());
+        assert_no_carryover(&tracker, state.round());
+    }
+
+    #[test]
+    fn pass_back_from_the_other_side_is_read_against_this_round_only() {
+        use PlayerPosition::{East, North, West};
+        let mut state = MatchState::with_seed(North, 2256);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(state.round());
+        // Passing left, North passes to East and receives from West.
+        play_out_round(&mut state, &mut tracker);
+        assert_eq!(tracker.received_pass(North).map(|p| p.giver), Some(West));
+        assert_eq!(tracker.received_pass(East).map(|p| p.giver), Some(North));
+
+        state.finish_round_and_start_next();
+        tracker.reset_for_round(state.round());
+        assert_no_carryover(&tracker, state.round());
+
+        // Passing right, East passes back to North; the reads are this
+        // round's, not North's earlier pass to East.
+        assert_eq!(state.passing_direction(), PassingDirection::Right);
+        let east_pass = state.round().hand(East).cards()[..3].to_vec();
+        play_out_round(&mut state, &mut tracker);
+        let profile = tracker.received_pass(North).expect("North received a pass");
+        assert_eq!(profile.giver, East);
+        assert_eq!(
+            *profile,
+            ReceivedPassProfile::infer(East, PassCards::from_slice(&east_pass).unwrap())
+        );
+        assert!(tracker.received_pass(East).is_none_or(|p| p.giver != North));
+
+        state.finish_round_and_start_next();
+        tracker.reset_for_round(state.round());
+        assert_no_carryover(&tracker, state.round());
+    }
+
+    #[test]
+    fn match_memory_survives_the_next_deal() {
+        use PlayerPosition::{East, North, South, West};
+        let mut rng = StdRng::seed_from_u64(2245);
+        let deck = Deck::shuffled(&mut rng);
+        let round = RoundState::deal(&deck, North, PassingDirection::Left);
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        tracker.note_round_scored([3, 0, 13, 10], [3, 0, 13, 10]);
+        // East shoots the moon.
+        tracker.note_round_scored([0, 26, 0, 0], [29, 0, 39, 36]);
+        tracker.reset_for_round(&round);
+
+        let memory = tracker.match_memory();
+        assert_eq!(memory.rounds_played(), 2);
+        assert_eq!((memory.rounds_won(East), memory.rounds_lost(South)), (2, 2));
+        assert_eq!(memory.rounds_lost(North), 1);
+        assert_eq!(memory.moons_suffered(North), 1);
+        assert_eq!(memory.moons_suffered(East), 0);
+        assert_eq!((memory.placement(East), memory.placement(West)), (1, 3));
+        assert_eq!(memory.lead_margin(East), Some(29));
+        assert_eq!(memory.lead_margin(North), None);
+
+        // The shock lasts two rounds.
+        assert!(memory.suffered_moon_within(North, 2));
+        tracker.note_round_scored([0, 13, 0, 13], [29, 13, 39, 49]);
+        assert!(tracker.match_memory().suffered_moon_within(North, 2));
+        tracker.note_round_scored([13, 13, 0, 0], [42, 26, 39, 49]);
+        assert!(!tracker.match_memory().suffered_moon_within(North, 2));
+        assert!(!tracker.match_memory().suffered_moon_within(East, 2));
+    }
+
+    /// South passes K♣ 9♦ 3♦ to West, its left-hand opponent, and West
+    /// then plays the 9♦ in the second trick: led back on `lead`.
+    fn west_plays_passed_nine(lead: Suit) -> UnseenTracker {
+        let round = RoundState::deal(
+            &Deck::shuffled_with_seed(2262),
+            PlayerPosition::North,
+            PassingDirection::Left,
+        );
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        tracker.note_pass_received(
+            PlayerPosition::West,
+            PlayerPosition::South,
+            [
+                Card::new(Rank::King, Suit::Clubs),
+                Card::new(Rank::Nine, Suit::Diamonds),
+                Card::new(Rank::Three, Suit::Diamonds),
+            ],
+        );
+        let nine = Card::new(Rank::Nine, Suit::Diamonds);
+        tracker.note_card_played(PlayerPosition::West, nine);
+        tracker.note_passed_card_played(PlayerPosition::West, nine, 1, lead);
+        tracker
+    }
+
+    #[test]
+    fn a_passed_card_led_back_reads_as_length_and_one_discarded_as_a_void() {
+        let west = PlayerPosition::West;
+        let queen = Card::new(Rank::Queen, Suit::Diamonds);
+        let mut plain = UnseenTracker::new();
+        plain.reset_for_round(&RoundState::deal(
+            &Deck::shuffled_with_seed(2262),
+            PlayerPosition::North,
+            PassingDirection::Left,
+        ));
+        plain.note_card_played(west, Card::new(Rank::Nine, Suit::Diamonds));
+        let before = plain.belief_state(west).card_probability(queen);
+
+        let led = west_plays_passed_nine(Suit::Diamonds);
+        assert!(led.passed_suit_lean(west, Suit::Diamonds) > 1.0);
+        assert!(led.belief_state(west).card_probability(queen) > before);
+
+        let dumped = west_plays_passed_nine(Suit::Spades);
+        assert!(dumped.passed_suit_lean(west, Suit::Diamonds) < 1.0);
+        assert!(dumped.belief_state(west).card_probability(queen) < before);
+        for tracker in [&led, &dumped] {
+            assert_eq!(tracker.passed_suit_lean(west, Suit::Clubs), 1.0);
+            let mass: f32 = CardSet::DECK
+                .iter()
+                .map(|card| tracker.belief_state(west).card_probability(card))
+                .sum();
+            assert!((mass - 1.0).abs() < 1e-4, "mass {mass}");
+        }
+    }
+
+    #[test]
+    fn only_early_plays_of_a_passed_card_are_read() {
+        let west = PlayerPosition::West;
+        let mut tracker = west_plays_passed_nine(Suit::Diamonds);
+        let lean = tracker.passed_suit_lean(west, Suit::Diamonds);
+        // The king was passed too, but by the fifth trick it says nothing.
+        let king = Card::new(Rank::King, Suit::Clubs);
+        tracker.note_passed_card_played(west, king, 4, Suit::Clubs);
+        assert_eq!(tracker.passed_suit_lean(west, Suit::Clubs), 1.0);
+        // A card West was not passed is no read either.
+        let ten = Card::new(Rank::Ten, Suit::Diamonds);
+        tracker.note_passed_card_played(west, ten, 1, Suit::Clubs);
+        assert_eq!(tracker.passed_suit_lean(west, Suit::Diamonds), lean);
+        // Nor is a passed card played by another seat.
+        let three = Card::new(Rank::Three, Suit::Diamonds);
+        tracker.note_passed_card_played(PlayerPosition::North, three, 1, Suit::Clubs);
+        assert_eq!(
+            tracker.passed_suit_lean(PlayerPosition::North, Suit::Diamonds),
+            1.0
+        );
+    }
 }
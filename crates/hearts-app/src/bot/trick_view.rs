@@ -0,0 +1,135 @@
+use hearts_core::model::card::Card;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::suit::Suit;
+use hearts_core::model::trick::{Play, Trick};
+
+/// Read-only questions about the trick in progress: what was led, who is
+/// winning it so far, and what is already on the table.
+#[derive(Debug, Clone, Copy)]
+pub struct TrickView<'a> {
+    trick: &'a Trick,
+}
+
+impl<'a> TrickView<'a> {
+    pub fn new(trick: &'a Trick) -> Self {
+        Self { trick }
+    }
+
+    pub fn led_suit(&self) -> Option<Suit> {
+        self.trick.lead_suit()
+    }
+
+    fn winning_play(&self) -> Option<&'a Play> {
+        let lead = self.trick.lead_suit()?;
+        self.trick
+            .plays()
+            .iter()
+            .filter(|p| p.card.suit == lead)
+            .max_by_key(|p| p.card.rank.value())
+    }
+
+    /// Seat holding the trick so far (highest card of the led suit).
+    pub fn current_winner(&self) -> Option<PlayerPosition> {
+        self.winning_play().map(|p| p.position)
+    }
+
+    pub fn winning_card(&self) -> Option<Card> {
+        self.winning_play().map(|p| p.card)
+    }
+
+    pub fn points_on_table(&self) -> u8 {
+        self.trick.penalty_total()
+    }
+
+    /// Cards already played, i.e. the 0-based position of the seat to act next
+    /// (0 means we are leading).
+    pub fn position_in_trick(&self) -> usize {
+        self.trick.plays().len()
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn cards_by_seat(&self) -> [Option<Card>; 4] {
+        let mut cards = [None; 4];
+        for play in self.trick.plays() {
+            cards[play.position.index()] = Some(play.card);
+        }
+        cards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hearts_core::model::rank::Rank;
+
+    fn trick_with(leader: PlayerPosition, cards: &[Card]) -> Trick {
+        let mut trick = Trick::new(leader);
+        let mut seat = leader;
+        for &card in cards {
+            trick.play(seat, card).unwrap();
+            seat = seat.next();
+        }
+        trick
+    }
+
+    #[test]
+    fn empty_trick_from_leader_view() {
+        let trick = trick_with(PlayerPosition::South, &[]);
+        let view = TrickView::new(&trick);
+        assert_eq!(view.led_suit(), None);
+        assert_eq!(view.current_winner(), None);
+        assert_eq!(view.winning_card(), None);
+        assert_eq!(view.points_on_table(), 0);
+        assert_eq!(view.position_in_trick(), 0);
+        assert_eq!(view.cards_by_seat(), [None; 4]);
+    }
+
+    #[test]
+    fn off_suit_cards_never_win() {
+        let trick = trick_with(
+            PlayerPosition::North,
+            &[
+                Card::new(Rank::Five, Suit::Clubs),
+                Card::new(Rank::Ace, Suit::Hearts),
+                Card::new(Rank::Nine, Suit::Clubs),
+            ],
+        );
+        let view = TrickView::new(&trick);
+        assert_eq!(view.led_suit(), Some(Suit::Clubs));
+        assert_eq!(view.current_winner(), Some(PlayerPosition::South));
+        assert_eq!(
+            view.winning_card(),
+            Some(Card::new(Rank::Nine, Suit::Clubs))
+        );
+        assert_eq!(view.points_on_table(), 1);
+        assert_eq!(view.position_in_trick(), 3);
+        assert_eq!(
+            view.cards_by_seat(),
+            [
+                Some(Card::new(Rank::Five, Suit::Clubs)),
+                Some(Card::new(Rank::Ace, Suit::Hearts)),
+                Some(Card::new(Rank::Nine, Suit::Clubs)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn queen_of_spades_counts_on_table() {
+        let trick = trick_with(
+            PlayerPosition::West,
+            &[
+                Card::new(Rank::King, Suit::Spades),
+                Card::new(Rank::Queen, Suit::Spades),
+            ],
+        );
+        let view = TrickView::new(&trick);
+        assert_eq!(view.current_winner(), Some(PlayerPosition::West));
+        assert_eq!(
+            view.winning_card(),
+            Some(Card::new(Rank::King, Suit::Spades))
+        );
+        assert_eq!(view.points_on_table(), 13);
+        assert_eq!(view.position_in_trick(), 2);
+    }
+}
@@ -0,0 +1,275 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! How card faces and the outlines around them look, worked out without the
+//! window so it only has to paint.
+//!
+//! The classic look draws faces from the card atlas, with red hearts and
+//! diamonds against black spades and clubs. Three display options help
+//! players who cannot tell those apart:
+//!
+//! - Four-color suits: spades black, hearts red, diamonds blue, clubs green.
+//! - Suit letters: the corner index reads `10S` instead of `10♠`.
+//! - High contrast: a black card edge, thicker outlines, larger indices, and
+//!   stronger colors for the passing and trick-winner highlights.
+//!
+//! Turning any of them on paints the faces from [`FaceMetrics`] instead of
+//! the atlas, so they hold at every card size.
+
+use hearts_core::model::card::Card;
+use hearts_core::model::suit::Suit;
+
+/// An RGBA color, each channel 0.0-1.0.
+pub type Rgba = [f32; 4];
+
+const RED: Rgba = [0.78, 0.08, 0.1, 1.0];
+const BLACK: Rgba = [0.06, 0.06, 0.08, 1.0];
+const BLUE: Rgba = [0.05, 0.3, 0.78, 1.0];
+const GREEN: Rgba = [0.0, 0.5, 0.18, 1.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardStyleOption {
+    FourColor,
+    SuitLetters,
+    HighContrast,
+}
+
+impl CardStyleOption {
+    pub const ALL: [CardStyleOption; 3] = [
+        CardStyleOption::FourColor,
+        CardStyleOption::SuitLetters,
+        CardStyleOption::HighContrast,
+    ];
+
+    const fn bit(self) -> u32 {
+        match self {
+            CardStyleOption::FourColor => 1,
+            CardStyleOption::SuitLetters => 2,
+            CardStyleOption::HighContrast => 4,
+        }
+    }
+}
+
+/// The options in force; the default is the classic atlas look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CardStyle {
+    pub four_color: bool,
+    pub suit_letters: bool,
+    pub high_contrast: bool,
+}
+
+/// What an outline marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emphasis {
+    /// A card that can be played, or any card not in the South hand.
+    Plain,
+    /// A South card that cannot be played now.
+    Unplayable,
+    /// A card chosen to pass, or one just received.
+    Selected,
+    /// The card winning the trick so far.
+    Leading,
+}
+
+/// Sizes for a face painted at one card size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FaceMetrics {
+    /// Gap between the card edge and the corner index.
+    pub inset: f32,
+    /// Height of the corner index text.
+    pub index_height: f32,
+    /// Height of the suit drawn in the middle of the card.
+    pub pip_height: f32,
+    /// Width of the card edge.
+    pub edge: f32,
+}
+
+impl CardStyle {
+    pub const fn is_set(self, option: CardStyleOption) -> bool {
+        match option {
+            CardStyleOption::FourColor => self.four_color,
+            CardStyleOption::SuitLetters => self.suit_letters,
+            CardStyleOption::HighContrast => self.high_contrast,
+        }
+    }
+
+    pub const fn toggled(self, option: CardStyleOption) -> Self {
+        let mut next = self;
+        match option {
+            CardStyleOption::FourColor => next.four_color = !self.four_color,
+            CardStyleOption::SuitLetters => next.suit_letters = !self.suit_letters,
+            CardStyleOption::HighContrast => next.high_contrast = !self.high_contrast,
+        }
+        next
+    }
+
+    pub fn as_u32(self) -> u32 {
+        CardStyleOption::ALL
+            .iter()
+            .filter(|option| self.is_set(**option))
+            .map(|option| option.bit())
+            .sum()
+    }
+
+    /// `None` when `raw` has bits no option uses, e.g. from a newer build.
+    pub fn from_u32(raw: u32) -> Option<Self> {
+        let mut style = CardStyle::default();
+        let mut rest = raw;
+        for option in CardStyleOption::ALL {
+            if rest & option.bit() != 0 {
+                style = style.toggled(option);
+                rest &= !option.bit();
+            }
+        }
+        (rest == 0).then_some(style)
+    }
+
+    /// Whether faces come from the card atlas rather than being painted.
+    pub const fn uses_atlas(self) -> bool {
+        !(self.four_color || self.suit_letters || self.high_contrast)
+    }
+
+    pub const fn suit_color(self, suit: Suit) -> Rgba {
+        match (suit, self.four_color) {
+            (Suit::Hearts, _) => RED,
+            (Suit::Spades, _) => BLACK,
+            (Suit::Diamonds, true) => BLUE,
+            (Suit::Clubs, true) => GREEN,
+            (Suit::Diamonds, false) => RED,
+            (Suit::Clubs, false) => BLACK,
+        }
+    }
+
+    pub const fn suit_glyph(self, suit: Suit) -> &'static str {
+        match (suit, self.suit_letters) {
+            (Suit::Clubs, false) => "\u{2663}",
+            (Suit::Diamonds, false) => "\u{2666}",
+            (Suit::Spades, false) => "\u{2660}",
+            (Suit::Hearts, false) => "\u{2665}",
+            (Suit::Clubs, true) => "C",
+            (Suit::Diamonds, true) => "D",
+            (Suit::Spades, true) => "S",
+            (Suit::Hearts, true) => "H",
+        }
+    }
+
+    /// The corner index, rank then suit: `Q♠`, or `QS` with suit letters.
+    pub fn index_label(self, card: Card) -> String {
+        format!("{}{}", card.rank, self.suit_glyph(card.suit))
+    }
+
+    pub fn face_metrics(self, card_width: f32) -> FaceMetrics {
+        let index_scale = if self.high_contrast { 0.3 } else { 0.22 };
+        FaceMetrics {
+            inset: (card_width * 0.06).max(2.0),
+            index_height: card_width * index_scale,
+            pip_height: card_width * 0.5,
+            edge: if self.high_contrast { 2.5 } else { 1.0 },
+        }
+    }
+
+    /// The line around a painted face.
+    pub const fn edge_color(self) -> Rgba {
+        if self.high_contrast {
+            [0.0, 0.0, 0.0, 1.0]
+        } else {
+            [0.35, 0.35, 0.4, 1.0]
+        }
+    }
+
+    /// Outline width for `emphasis`. High contrast keeps the marked cards
+    /// at twice the plain width so they read without relying on color.
+    pub const fn stroke(self, emphasis: Emphasis) -> f32 {
+        match (emphasis, self.high_contrast) {
+            (_, false) => 2.0,
+            (Emphasis::Plain | Emphasis::Unplayable, true) => 2.5,
+            (Emphasis::Selected | Emphasis::Leading, true) => 5.0,
+        }
+    }
+
+    pub const fn emphasis_color(self, emphasis: Emphasis) -> Rgba {
+        match (emphasis, self.high_contrast) {
+            (Emphasis::Plain, false) => [0.2, 0.5, 0.3, 1.0],
+            (Emphasis::Unplayable, false) | (Emphasis::Leading, false) => [1.0, 1.0, 1.0, 0.95],
+            (Emphasis::Selected, false) => [1.0, 0.85, 0.2, 1.0],
+            (Emphasis::Plain, true) => [0.0, 0.0, 0.0, 1.0],
+            (Emphasis::Unplayable, true) => [0.5, 0.5, 0.5, 1.0],
+            (Emphasis::Selected, true) => [1.0, 0.9, 0.0, 1.0],
+            (Emphasis::Leading, true) => [0.0, 0.85, 1.0, 1.0],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardStyle, CardStyleOption, Emphasis};
+    use hearts_core::model::card::Card;
+    use hearts_core::model::rank::Rank;
+    use hearts_core::model::suit::Suit;
+
+    #[test]
+    fn every_combination_round_trips_and_stray_bits_are_refused() {
+        for raw in 0..8 {
+            let style = CardStyle::from_u32(raw).unwrap();
+            assert_eq!(style.as_u32(), raw);
+            assert_eq!(style.uses_atlas(), raw == 0);
+        }
+        assert_eq!(CardStyle::from_u32(8), None);
+        let style = CardStyle::default().toggled(CardStyleOption::HighContrast);
+        assert!(style.high_contrast && !style.four_color && !style.suit_letters);
+        assert_eq!(
+            style.toggled(CardStyleOption::HighContrast),
+            CardStyle::default()
+        );
+    }
+
+    #[test]
+    fn four_colors_give_every_suit_its_own_hue() {
+        let classic = CardStyle::default();
+        assert_eq!(
+            classic.suit_color(Suit::Hearts),
+            classic.suit_color(Suit::Diamonds)
+        );
+        assert_eq!(
+            classic.suit_color(Suit::Spades),
+            classic.suit_color(Suit::Clubs)
+        );
+        let four = classic.toggled(CardStyleOption::FourColor);
+        for (i, a) in Suit::ALL.iter().enumerate() {
+            for b in &Suit::ALL[i + 1..] {
+                assert_ne!(four.suit_color(*a), four.suit_color(*b), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn letters_replace_the_suit_symbols() {
+        let queen = Card::new(Rank::Queen, Suit::Spades);
+        let ten = Card::new(Rank::Ten, Suit::Hearts);
+        let classic = CardStyle::default();
+        assert_eq!(classic.index_label(queen), "Q\u{2660}");
+        let letters = classic.toggled(CardStyleOption::SuitLetters);
+        assert_eq!(letters.index_label(queen), "QS");
+        assert_eq!(letters.index_label(ten), "10H");
+    }
+
+    #[test]
+    fn high_contrast_enlarges_indices_and_thickens_highlights() {
+        let classic = CardStyle::default();
+        let high = classic.toggled(CardStyleOption::HighContrast);
+        let (plain, bold) = (classic.face_metrics(100.0), high.face_metrics(100.0));
+        assert!(bold.index_height > plain.index_height);
+        assert!(bold.edge > plain.edge);
+        for emphasis in [Emphasis::Selected, Emphasis::Leading] {
+            assert!(high.stroke(emphasis) >= 2.0 * high.stroke(Emphasis::Plain));
+            assert_ne!(
+                high.emphasis_color(emphasis),
+                high.emphasis_color(Emphasis::Plain)
+            );
+        }
+        // The classic outlines are the ones the table always drew.
+        assert_eq!(classic.stroke(Emphasis::Leading), 2.0);
+        assert_eq!(
+            classic.emphasis_color(Emphasis::Selected),
+            [1.0, 0.85, 0.2, 1.0]
+        );
+    }
+}
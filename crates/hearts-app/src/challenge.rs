@@ -0,0 +1,208 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+
+//! Developer "challenge mode": two weight files share the bot seats of one
+//! match, swapping partnerships every round, with per-file PPH tracked.
+//!
+//! Enabled only when both `MDH_DEV_CHALLENGE_A` and `MDH_DEV_CHALLENGE_B`
+//! point at weight files; regular players never see it.
+
+use crate::bot::WeightSet;
+use hearts_core::model::player::PlayerPosition;
+
+#[derive(Clone, Debug)]
+pub struct ChallengeProfile {
+    pub label: String,
+    pub weights: WeightSet,
+}
+
+/// Which of the two profiles plays `seat` in `round_number` (1-based).
+/// North/South play profile 0 on odd rounds and 1 on even rounds; East/West
+/// take the other, so every seat sees both profiles equally.
+pub fn profile_index_for(round_number: u32, seat: PlayerPosition) -> usize {
+    let north_south = matches!(seat, PlayerPosition::North | PlayerPosition::South);
+    let odd_round = round_number % 2 == 1;
+    if north_south == odd_round { 0 } else { 1 }
+}
+
+/// Per-profile seat-rounds played and penalty points taken.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChallengeStats {
+    pub hands: [u32; 2],
+    pub points: [u32; 2],
+}
+
+impl ChallengeStats {
+    /// Attributes each bot seat's round penalties to the profile that played it.
+    /// The human seat, if any, is not counted.
+    pub fn record_round(
+        &mut self,
+        round_number: u32,
+        penalties: [u8; 4],
+        human: Option<PlayerPosition>,
+    ) {
+        for seat in PlayerPosition::LOOP {
+            if Some(seat) == human {
+                continue;
+            }
+            let idx = profile_index_for(round_number, seat);
+            self.hands[idx] += 1;
+            self.points[idx] += penalties[seat.index()] as u32;
+        }
+    }
+
+    pub fn pph(&self, idx: usize) -> Option<f64> {
+        (self.hands[idx] > 0).then(|| self.points[idx] as f64 / self.hands[idx] as f64)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Challenge {
+    profiles: [ChallengeProfile; 2],
+    human: Option<PlayerPosition>,
+    stats: ChallengeStats,
+}
+
+impl Challenge {
+    pub fn new(profiles: [ChallengeProfile; 2], human: Option<PlayerPosition>) -> Self {
+        Self {
+            profiles,
+            human,
+            stats: ChallengeStats::default(),
+        }
+    }
+
+    /// Loads the developer challenge from `MDH_DEV_CHALLENGE_A`/`_B`, with
+    /// South as the human seat.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let (Ok(a), Ok(b)) = (
+            std::env::var("MDH_DEV_CHALLENGE_A"),
+            std::env::var("MDH_DEV_CHALLENGE_B"),
+        ) else {
+            return Ok(None);
+        };
+        let profiles = [Self::load_profile(&a)?, Self::load_profile(&b)?];
+        Ok(Some(Self::new(profiles, Some(PlayerPosition::South))))
+    }
+
+    fn load_profile(path: &str) -> Result<ChallengeProfile, String> {
+        let path = crate::paths::expand_env_path(path.trim()).map_err(|err| err.to_string())?;
+        let label = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Ok(ChallengeProfile {
+            label,
+            weights: WeightSet::load(&path)?,
+        })
+    }
+
+    pub fn profile_for(
+        &self,
+        round_number: u32,
+        seat: PlayerPosition,
+    ) -> Option<&ChallengeProfile> {
+        if Some(seat) == self.human {
+            return None;
+        }
+        Some(&self.profiles[profile_index_for(round_number, seat)])
+    }
+
+    pub fn record_round(&mut self, round_number: u32, penalties: [u8; 4]) {
+        self.stats.record_round(round_number, penalties, self.human);
+    }
+
+    #[cfg(test)]
+    pub fn stats(&self) -> ChallengeStats {
+        self.stats
+    }
+
+    /// Head-to-head line for the end-of-match summary (lower PPH is better).
+    pub fn summary(&self) -> String {
+        let side = |idx: usize| match self.stats.pph(idx) {
+            Some(pph) => format!(
+                "{} {:.2} PPH ({} hands)",
+                self.profiles[idx].label, pph, self.stats.hands[idx]
+            ),
+            None => format!("{} no hands", self.profiles[idx].label),
+        };
+        let verdict = match (self.stats.pph(0), self.stats.pph(1)) {
+            (Some(a), Some(b)) if a < b => {
+                format!("{} ahead by {:.2}", self.profiles[0].label, b - a)
+            }
+            (Some(a), Some(b)) if b < a => {
+                format!("{} ahead by {:.2}", self.profiles[1].label, a - b)
+            }
+            (Some(_), Some(_)) => "even".to_string(),
+            _ => "no result".to_string(),
+        };
+        format!("Challenge: {} vs {} - {verdict}", side(0), side(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Challenge, ChallengeProfile, ChallengeStats, profile_index_for};
+    use crate::bot::WeightSet;
+    use hearts_core::model::player::PlayerPosition;
+
+    #[test]
+    fn partnerships_swap_profiles_every_round() {
+        for round in 1..=6u32 {
+            let ns = profile_index_for(round, PlayerPosition::North);
+            assert_eq!(ns, profile_index_for(round, PlayerPosition::South));
+            let ew = profile_index_for(round, PlayerPosition::East);
+            assert_eq!(ew, profile_index_for(round, PlayerPosition::West));
+            assert_ne!(ns, ew);
+            assert_eq!(ns, if round % 2 == 1 { 0 } else { 1 });
+            assert_ne!(ns, profile_index_for(round + 1, PlayerPosition::North));
+        }
+    }
+
+    #[test]
+    fn stats_attribute_penalties_to_playing_profile() {
+        let mut stats = ChallengeStats::default();
+        // Round 1: N/S -> profile 0, E/W -> profile 1; South is human.
+        stats.record_round(1, [4, 13, 0, 9], Some(PlayerPosition::South));
+        assert_eq!(stats.hands, [1, 2]);
+        assert_eq!(stats.points, [4, 22]);
+        // Round 2 swaps: E/W -> profile 0.
+        stats.record_round(2, [0, 6, 20, 0], Some(PlayerPosition::South));
+        assert_eq!(stats.hands, [3, 3]);
+        assert_eq!(stats.points, [10, 22]);
+        assert_eq!(stats.pph(0), Some(10.0 / 3.0));
+        assert_eq!(ChallengeStats::default().pph(1), None);
+    }
+
+    #[test]
+    fn challenge_skips_human_seat_and_reports_head_to_head() {
+        let weights = WeightSet::parse("").unwrap();
+        let profile = |label: &str| ChallengeProfile {
+            label: label.to_string(),
+            weights,
+        };
+        let mut challenge = Challenge::new(
+            [profile("best"), profile("candidate")],
+            Some(PlayerPosition::South),
+        );
+        assert!(challenge.profile_for(1, PlayerPosition::South).is_none());
+        assert_eq!(
+            challenge
+                .profile_for(1, PlayerPosition::North)
+                .unwrap()
+                .label,
+            "best"
+        );
+        assert_eq!(
+            challenge
+                .profile_for(2, PlayerPosition::North)
+                .unwrap()
+                .label,
+            "candidate"
+        );
+        challenge.record_round(1, [2, 10, 0, 14]);
+        assert_eq!(
+            challenge.summary(),
+            "Challenge: best 2.00 PPH (1 hands) vs candidate 12.00 PPH (2 hands) - best ahead by 10.00"
+        );
+    }
+}
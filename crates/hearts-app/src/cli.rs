@@ -1,11 +1,15 @@
+use crate::bot::policy::Policy as _;
+use crate::bot::policy::external::{ExternalFallback, ExternalOptions, ExternalPolicy};
 use crate::debug::debug_enabled;
 use crate::endgame_export::EndgameExport;
 use hearts_core::game::match_state::MatchState;
 use hearts_core::game::serialization::MatchSnapshot;
+use hearts_core::model::deal::Deal;
 use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::round::RoundState;
+use hearts_core::model::rules::{RuleSet, SunScore};
 use std::fs;
 use std::path::PathBuf;
-#[cfg(windows)]
 use std::sync::OnceLock;
 #[cfg(windows)]
 use windows::Win32::Foundation::HWND;
@@ -14,6 +18,14 @@ use windows::Win32::UI::WindowsAndMessaging::{
     MB_ICONERROR, MB_ICONINFORMATION, MB_OK, MESSAGEBOX_STYLE, MessageBoxW,
 };
 
+static STARTUP_SEED: OnceLock<u64> = OnceLock::new();
+
+/// Seed requested with `--seed N` for the interactive match, if any.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn startup_seed() -> Option<u64> {
+    STARTUP_SEED.get().copied()
+}
+
 pub enum CliOutcome {
     Handled,
     NotHandled,
@@ -27,6 +39,9 @@ pub enum CliError {
     Json(serde_json::Error),
     InvalidSeat(String),
     InvalidValue { flag: &'static str, value: String },
+    GoldenMismatch(String),
+    SoakFailures(u32),
+    PathTemplate(crate::paths::PathError),
 }
 
 impl std::fmt::Display for CliError {
@@ -40,6 +55,9 @@ impl std::fmt::Display for CliError {
             CliError::InvalidValue { flag, value } => {
                 write!(f, "Invalid value for {flag}: {value}")
             }
+            CliError::GoldenMismatch(diff) => write!(f, "Decision golden mismatch: {diff}"),
+            CliError::SoakFailures(count) => write!(f, "Soak found {count} failures"),
+            CliError::PathTemplate(err) => write!(f, "Invalid path: {err}"),
         }
     }
 }
@@ -53,6 +71,34 @@ impl From<std::io::Error> for CliError {
 /// Result type for DP flip detection: (trick_index, normal_explanation, hard_explanation)
 type DpFlipResult = Option<(usize, Option<String>, Option<String>)>;
 
+impl From<crate::paths::PathError> for CliError {
+    fn from(value: crate::paths::PathError) -> Self {
+        CliError::PathTemplate(value)
+    }
+}
+
+impl From<crate::outputs::RetentionError> for CliError {
+    fn from(value: crate::outputs::RetentionError) -> Self {
+        match value {
+            crate::outputs::RetentionError::NoRunDirectory(template) => CliError::InvalidValue {
+                flag: "--keep-last",
+                value: template,
+            },
+            crate::outputs::RetentionError::Path(err) => CliError::PathTemplate(err),
+            crate::outputs::RetentionError::Io(err) => CliError::Io(err),
+        }
+    }
+}
+
+impl From<crate::persist::PersistError> for CliError {
+    fn from(value: crate::persist::PersistError) -> Self {
+        match value {
+            crate::persist::PersistError::Io(err) => CliError::Io(err),
+            other => CliError::Io(std::io::Error::other(other.to_string())),
+        }
+    }
+}
+
 impl From<serde_json::Error> for CliError {
     fn from(value: serde_json::Error) -> Self {
         CliError::Json(value)
@@ -94,7 +140,83 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             );
             Ok(CliOutcome::Handled)
         }
+        "--seed" => {
+            // Usage: --seed <n>  (starts the interactive match with this exact seed)
+            let raw = args.next().ok_or(CliError::MissingArgument("--seed <n>"))?;
+            let seed = raw.parse::<u64>().map_err(|_| CliError::InvalidValue {
+                flag: "--seed",
+                value: raw.clone(),
+            })?;
+            let _ = STARTUP_SEED.set(seed);
+            Ok(CliOutcome::NotHandled)
+        }
+        "--lint-telemetry" => {
+            // Usage: --lint-telemetry <file.ndjson> [--max <n>]
+            let path = args
+                .next()
+                .map(PathBuf::from)
+                .ok_or(CliError::MissingArgument("--lint-telemetry <file.ndjson>"))?;
+            let mut max_offending = 10usize;
+            while let Some(arg) = args.next() {
+                if arg == "--max" {
+                    let raw = args
+                        .next()
+                        .ok_or(CliError::MissingArgument("--lint-telemetry --max <n>"))?;
+                    max_offending = raw.parse().map_err(|_| CliError::InvalidValue {
+                        flag: "--max",
+                        value: raw.clone(),
+                    })?;
+                } else {
+                    return Err(CliError::UnknownCommand(arg));
+                }
+            }
+            let contents = crate::outputs::read_to_string(&path)?;
+            let report = crate::telemetry::lint_ndjson(&contents, max_offending);
+            println!("{}: {}", path.display(), report.summary_line());
+            for (line, reason) in &report.offending {
+                println!("  line {line}: {reason}");
+            }
+            if report.malformed_total() > 0 {
+                return Err(CliError::InvalidValue {
+                    flag: "--lint-telemetry",
+                    value: format!("{} malformed records", report.malformed_total()),
+                });
+            }
+            Ok(CliOutcome::Handled)
+        }
+        "--check-agent" => {
+            // Usage: --check-agent <difficulty | external agent options>
+            // Runs the policy conformance battery, e.g. against
+            // `transport=websocket,url=ws://127.0.0.1:9000`.
+            let spec = args.next().ok_or(CliError::MissingArgument(
+                "--check-agent <difficulty | agent options>",
+            ))?;
+            let report = match parse_difficulty_opt(&spec) {
+                Some(difficulty) => crate::bot::policy::conformance::run(
+                    &mut crate::bot::policy::PlannerPolicy::new(difficulty),
+                ),
+                None => {
+                    let mut policy =
+                        ExternalPolicy::new(parse_agent_options("--check-agent", &spec)?);
+                    let report = crate::bot::policy::conformance::run(&mut policy);
+                    if let Some(err) = policy.last_error() {
+                        println!("Last agent error: {err}");
+                    }
+                    policy.shutdown();
+                    report
+                }
+            };
+            println!("{}", report.summary());
+            if !report.is_conformant() {
+                return Err(CliError::InvalidValue {
+                    flag: "--check-agent",
+                    value: format!("{} violations", report.violations.len()),
+                });
+            }
+            Ok(CliOutcome::Handled)
+        }
         "--export-play-dataset" => {
+            // Usage: --export-play-dataset <seat> <seed_start> <count> <difficulty> <out> [--balance-passing] [--split <train,val,test>] [--manifest <path>] [--compress <zstd|gzip|none>] [--augment-symmetries] [Hard flags]
             let seat = args.next().map(|s| parse_seat(&s)).transpose()?.ok_or(
                 CliError::MissingArgument(
                     "--export-play-dataset <seat> <seed_start> <count> <difficulty> <out>",
@@ -121,18 +243,90 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                 .ok_or(CliError::MissingArgument(
                     "--export-play-dataset <seat> <seed_start> <count> <difficulty> <out>",
                 ))?;
-            parse_hard_cli_flags(&mut args)?;
+            let mut balance_passing = false;
+            let mut augment_symmetries = false;
+            let mut ratios: Option<crate::dataset::SplitRatios> = None;
+            let mut manifest_path: Option<PathBuf> = None;
+            let mut compress = crate::outputs::Compression::None;
+            let mut tail_tokens: Vec<String> = Vec::new();
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--balance-passing" => balance_passing = true,
+                    "--augment-symmetries" => augment_symmetries = true,
+                    "--split" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--split <train,val,test>"))?;
+                        ratios = Some(crate::dataset::SplitRatios::parse(&raw).ok_or(
+                            CliError::InvalidValue {
+                                flag: "--split",
+                                value: raw,
+                            },
+                        )?);
+                    }
+                    "--compress" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--compress <zstd|gzip|none>"))?;
+                        compress = crate::outputs::Compression::parse(&raw).ok_or(
+                            CliError::InvalidValue {
+                                flag: "--compress",
+                                value: raw,
+                            },
+                        )?;
+                    }
+                    "--manifest" => {
+                        manifest_path = Some(PathBuf::from(
+                            args.next()
+                                .ok_or(CliError::MissingArgument("--manifest <path>"))?,
+                        ));
+                    }
+                    other if is_shared_cli_flag(other) => {
+                        tail_tokens.push(other.to_string());
+                        if shared_flag_needs_value(other) {
+                            let v = args
+                                .next()
+                                .ok_or(CliError::MissingArgument("shared flag value"))?;
+                            tail_tokens.push(v);
+                        }
+                    }
+                    other => return Err(CliError::UnknownCommand(other.to_string())),
+                }
+            }
+            parse_hard_cli_flags(&mut tail_tokens.into_iter())?;
+            let explicit_ratios = ratios;
+            if manifest_path.is_some() && ratios.is_none() {
+                ratios = Some(crate::dataset::SplitRatios::default());
+            }
             if let Some(parent) = out.parent() {
                 fs::create_dir_all(parent)?;
             }
-            let mut file = std::fs::File::create(&out)?;
+            let mut file = match ratios {
+                Some(_) => None,
+                None => Some(compress.create(&out)?),
+            };
+            let mut samples = Vec::new();
             let mut written = 0usize;
             for offset in 0..count {
                 let seed = seed_start + offset;
-                let mut controller = crate::controller::GameController::new_with_seed(
-                    Some(seed),
-                    PlayerPosition::North,
-                );
+                let mut controller = if balance_passing {
+                    let direction =
+                        hearts_core::model::passing::PassingDirection::CYCLE[(offset % 4) as usize];
+                    crate::controller::GameController::new_from_match_state(
+                        MatchState::with_seed_round_direction(
+                            seed,
+                            1,
+                            direction,
+                            PlayerPosition::North,
+                        ),
+                    )
+                } else {
+                    crate::controller::GameController::new_with_seed(
+                        Some(seed),
+                        PlayerPosition::North,
+                    )
+                };
+                let deal = crate::dataset::deal_fingerprint(controller.round());
                 controller.set_bot_difficulty(difficulty);
                 if controller.in_passing_phase() {
                     if let Some(cards) = controller.simple_pass_for(seat) {
@@ -151,14 +345,108 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                         break;
                     }
                 }
-                if let Some(sample) = crate::dataset::collect_play_sample(&controller, seat, seed) {
-                    let line = serde_json::to_string(&sample)?;
+                let Some(mut sample) = crate::dataset::collect_play_sample(&controller, seat, seed)
+                else {
+                    continue;
+                };
+                // Play the round out so the sample can be stratified by moon.
+                let mut guard = 0u32;
+                while !controller.is_round_decided() && guard <= 600 {
+                    let to_play = controller.expected_to_play();
+                    if !matches!(
+                        controller.autoplay_one_with_status(to_play.next()),
+                        crate::controller::AutoplayOutcome::Played(_, _)
+                    ) {
+                        break;
+                    }
+                    guard += 1;
+                }
+                sample.deal = Some(deal);
+                sample.moon = Some(controller.penalties_this_round().contains(&26));
+                let rows = if augment_symmetries {
+                    crate::augment::augment(sample)
+                } else {
+                    vec![sample]
+                };
+                if let Some(file) = file.as_mut() {
+                    for row in rows {
+                        let line = serde_json::to_string(&row)?;
+                        use std::io::Write as _;
+                        writeln!(file, "{}", line)?;
+                        written += 1;
+                    }
+                } else {
+                    samples.extend(rows);
+                }
+            }
+            let Some(ratios) = ratios else {
+                let path = match file {
+                    Some(file) => file.finish()?,
+                    None => out,
+                };
+                println!("Wrote {} play samples to {}", written, path.display());
+                return Ok(CliOutcome::Handled);
+            };
+            let manifest_path =
+                manifest_path.unwrap_or_else(|| dataset_split_path(&out, "manifest", "json"));
+            let mut manifest = if manifest_path.exists() {
+                let existing: crate::dataset::PartitionManifest =
+                    serde_json::from_str(&fs::read_to_string(&manifest_path)?)?;
+                if explicit_ratios.is_some_and(|ratios| ratios != existing.ratios) {
+                    return Err(CliError::InvalidValue {
+                        flag: "--split",
+                        value: format!(
+                            "{} was built with {:.2},{:.2},{:.2}",
+                            manifest_path.display(),
+                            existing.ratios.train,
+                            existing.ratios.val,
+                            existing.ratios.test
+                        ),
+                    });
+                }
+                existing
+            } else {
+                crate::dataset::PartitionManifest::new(ratios)
+            };
+            let entries: Vec<crate::dataset::DealEntry> = samples
+                .iter()
+                .filter_map(|sample| {
+                    Some(crate::dataset::DealEntry {
+                        fingerprint: sample.deal.clone()?,
+                        passing: sample.passing.clone(),
+                        moon: sample.moon?,
+                    })
+                })
+                .collect();
+            manifest.extend(&entries);
+            let mut lines: [Vec<String>; 3] = Default::default();
+            for mut sample in samples {
+                let Some(split) = sample.deal.as_deref().and_then(|d| manifest.split_of(d)) else {
+                    continue;
+                };
+                sample.split = Some(split);
+                lines[split as usize].push(serde_json::to_string(&sample)?);
+            }
+            for split in crate::dataset::Split::ALL {
+                let mut writer =
+                    compress.create(&dataset_split_path(&out, split.as_str(), "jsonl"))?;
+                for line in &lines[split as usize] {
                     use std::io::Write as _;
-                    writeln!(file, "{}", line)?;
-                    written += 1;
+                    writeln!(writer, "{line}")?;
                 }
+                let path = writer.finish()?;
+                println!(
+                    "Wrote {} {} samples to {}",
+                    lines[split as usize].len(),
+                    split.as_str(),
+                    path.display()
+                );
+            }
+            if let Some(parent) = manifest_path.parent() {
+                fs::create_dir_all(parent)?;
             }
-            println!("Wrote {} play samples to {}", written, out.display());
+            fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+            println!("Wrote partition manifest to {}", manifest_path.display());
             Ok(CliOutcome::Handled)
         }
         "--export-endgame" => {
@@ -361,11 +649,23 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                 .map(|s| parse_seat(&s))
                 .transpose()?
                 .ok_or(CliError::MissingArgument("--explain-once <seed> <seat>"))?;
-            let difficulty = args.next().and_then(|s| parse_difficulty_opt(&s));
-            parse_hard_cli_flags(&mut args)?;
+            let mut rest: Vec<String> = args.collect();
+            let difficulty = match rest.first().and_then(|s| parse_difficulty_opt(s)) {
+                Some(difficulty) => {
+                    rest.remove(0);
+                    Some(difficulty)
+                }
+                None => None,
+            };
+            let deal = take_deal_flag(&mut rest)?;
+            parse_hard_cli_flags(&mut rest.into_iter())?;
 
+            let mut match_state = MatchState::with_seed(PlayerPosition::North, seed);
+            if let Some(deal) = &deal {
+                pin_deal(&mut match_state, deal);
+            }
             let mut controller =
-                crate::controller::GameController::new_with_seed(Some(seed), PlayerPosition::North);
+                crate::controller::GameController::new_from_match_state(match_state);
             if let Some(d) = difficulty {
                 controller.set_bot_difficulty(d);
             }
@@ -389,7 +689,10 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                 return Ok(CliOutcome::Handled);
             }
             let explained = controller.explain_candidates_for(seat);
-            println!("Explain {:?} (seed {}):", seat, seed);
+            match &deal {
+                Some(deal) => println!("Explain {:?} (seed {}, deal {}):", seat, seed, deal),
+                None => println!("Explain {:?} (seed {}):", seat, seed),
+            }
             println!(
                 "  {} candidates (difficulty={:?})",
                 explained.len(),
@@ -442,6 +745,14 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             for (card, score) in explained.iter() {
                 println!("  {} => {}", card, score);
             }
+            let danger = controller.danger_suits(seat);
+            if danger.values().any(|&value| value > 0) {
+                let values: Vec<String> = danger
+                    .iter()
+                    .map(|(suit, value)| format!("{suit}={value}"))
+                    .collect();
+                println!("  danger suits: {}", values.join(" "));
+            }
             // Optional verbose breakdown for Hard when debug logs are enabled
             if matches!(
                 controller.bot_difficulty(),
@@ -495,6 +806,26 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             }
             Ok(CliOutcome::Handled)
         }
+        "--debug-deal" => {
+            // Usage: --debug-deal <seed> [difficulty] [Hard flags]
+            let seed = args
+                .next()
+                .and_then(|s| s.parse::<u64>().ok())
+                .ok_or(CliError::MissingArgument("--debug-deal <seed>"))?;
+            let mut rest: Vec<String> = args.collect();
+            let difficulty = match rest.first().and_then(|s| parse_difficulty_opt(s)) {
+                Some(difficulty) => {
+                    rest.remove(0);
+                    difficulty
+                }
+                None => crate::bot::BotDifficulty::NormalHeuristic,
+            };
+            parse_hard_cli_flags(&mut rest.into_iter())?;
+            let mut session = crate::debug::DebugSession::new(seed, difficulty);
+            let stdin = std::io::stdin();
+            session.run(stdin.lock(), &mut std::io::stdout())?;
+            Ok(CliOutcome::Handled)
+        }
         "--explain-batch" => {
             let seat = args.next().map(|s| parse_seat(&s)).transpose()?.ok_or(
                 CliError::MissingArgument("--explain-batch <seat> <seed_start> <count>"),
@@ -638,6 +969,39 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             }
             Ok(CliOutcome::Handled)
         }
+        "--export-human-plays" => {
+            // Usage: --export-human-plays <out> [--from <path>]
+            let out = args
+                .next()
+                .map(PathBuf::from)
+                .ok_or(CliError::MissingArgument("--export-human-plays <out>"))?;
+            let mut from = crate::recording::recording_path();
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--from" => {
+                        from = args
+                            .next()
+                            .map(PathBuf::from)
+                            .ok_or(CliError::MissingArgument("--from <path>"))?;
+                    }
+                    other => return Err(CliError::UnknownCommand(other.to_string())),
+                }
+            }
+            // Parsing every row first keeps a damaged recording from being
+            // passed on as training data.
+            let records = crate::recording::load_recording(&from)?;
+            if let Some(parent) = out.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(&from, &out)?;
+            println!(
+                "Exported {} recorded plays from {} to {}",
+                records.len(),
+                from.display(),
+                out.display()
+            );
+            Ok(CliOutcome::Handled)
+        }
         "--export-snapshot" => {
             let path = args
                 .next()
@@ -785,12 +1149,140 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                         .collect::<Vec<_>>()
                         .join(", ")
                 );
-                println!("  Picks: {}, {}, {}", picks[0], picks[1], picks[2]);
+                println!(
+                    "  Picks: {}",
+                    picks
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
             } else {
                 println!("Not enough cards to pass for {:?}", seat);
             }
             Ok(CliOutcome::Handled)
         }
+        "--explain-pass" => {
+            // Usage: --explain-pass (--deal "<N hand / E / S / W>" | --seed <n>) --seat <seat> [--direction <left|right|across>] [--scores <n,e,s,w>] [--difficulty <d>] [--top <n>] [--json]
+            let mut hands: Option<[Vec<hearts_core::model::card::Card>; 4]> = None;
+            let mut seed_direction = None;
+            let mut seat: Option<PlayerPosition> = None;
+            let mut direction = None;
+            let mut scores = [0u32; 4];
+            let mut difficulty = crate::bot::BotDifficulty::NormalHeuristic;
+            let mut top = 5usize;
+            let mut json = false;
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--deal" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--deal <hands>"))?;
+                        hands = Some(crate::pass_explain::parse_deal(&raw).map_err(|reason| {
+                            CliError::InvalidValue {
+                                flag: "--deal",
+                                value: reason,
+                            }
+                        })?);
+                    }
+                    "--seed" => {
+                        let seed = args
+                            .next()
+                            .and_then(|s| s.parse::<u64>().ok())
+                            .ok_or(CliError::MissingArgument("--seed <n>"))?;
+                        let state = MatchState::with_seed(PlayerPosition::North, seed);
+                        let round = state.round();
+                        hands = Some(PlayerPosition::LOOP.map(|s| round.hand(s).cards().to_vec()));
+                        seed_direction = Some(round.passing_direction());
+                    }
+                    "--seat" => {
+                        seat = Some(parse_seat(
+                            &args
+                                .next()
+                                .ok_or(CliError::MissingArgument("--seat <seat>"))?,
+                        )?);
+                    }
+                    "--direction" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--direction <dir>"))?;
+                        direction = Some(
+                            raw.parse::<hearts_core::model::passing::PassingDirection>()
+                                .map_err(|_| CliError::InvalidValue {
+                                    flag: "--direction",
+                                    value: raw.clone(),
+                                })?,
+                        );
+                    }
+                    "--scores" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--scores <n,e,s,w>"))?;
+                        let parsed: Vec<u32> = raw
+                            .split(',')
+                            .filter_map(|v| v.trim().parse().ok())
+                            .collect();
+                        scores = parsed.try_into().map_err(|_| CliError::InvalidValue {
+                            flag: "--scores",
+                            value: raw,
+                        })?;
+                    }
+                    "--difficulty" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--difficulty <d>"))?;
+                        difficulty = parse_difficulty_opt(&raw).ok_or(CliError::InvalidValue {
+                            flag: "--difficulty",
+                            value: raw,
+                        })?;
+                    }
+                    "--top" => {
+                        top = args
+                            .next()
+                            .and_then(|s| s.parse::<usize>().ok())
+                            .ok_or(CliError::MissingArgument("--top <n>"))?;
+                    }
+                    "--json" => json = true,
+                    other => return Err(CliError::UnknownCommand(other.to_string())),
+                }
+            }
+            let hands = hands.ok_or(CliError::MissingArgument(
+                "--explain-pass requires --deal <hands> or --seed <n>",
+            ))?;
+            let seat = seat.ok_or(CliError::MissingArgument("--explain-pass --seat <seat>"))?;
+            let direction = direction
+                .or(seed_direction)
+                .ok_or(CliError::MissingArgument(
+                    "--explain-pass --direction <left|right|across>",
+                ))?;
+            let request = crate::pass_explain::PassRequest {
+                hands,
+                seat,
+                direction,
+                scores,
+                difficulty,
+                weights: crate::bot::PassWeights::from_env(),
+            };
+            let Some(explanation) = crate::pass_explain::explain(&request, top) else {
+                return Err(CliError::InvalidValue {
+                    flag: "--direction",
+                    value: format!("{} does not pass", direction.as_str()),
+                });
+            };
+            if json {
+                let mut value = serde_json::to_value(&explanation).map_err(CliError::Json)?;
+                value["deal"] = crate::pass_explain::deal_string(&request.hands).into();
+                value["scores"] = serde_json::json!(scores);
+                value["guards"] = serde_json::json!(explanation.chosen.guards());
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&value).map_err(CliError::Json)?
+                );
+            } else {
+                print!("{}", crate::pass_explain::render_text(&explanation));
+            }
+            Ok(CliOutcome::Handled)
+        }
         "--explain-pass-batch" => {
             let seat = args.next().map(|s| parse_seat(&s)).transpose()?.ok_or(
                 CliError::MissingArgument("--explain-pass-batch <seat> <seed_start> <count>"),
@@ -827,13 +1319,133 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                             .collect::<Vec<_>>()
                             .join(", ")
                     );
-                    println!("  Picks: {}, {}, {}", picks[0], picks[1], picks[2]);
+                    println!(
+                        "  Picks: {}",
+                        picks
+                            .iter()
+                            .map(|c| c.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
                 } else {
                     println!("[seed {}] Not enough cards to pass for {:?}", seed, seat);
                 }
             }
             Ok(CliOutcome::Handled)
         }
+        "--find-seed" => {
+            // Usage: --find-seed --predicate <name> [--difficulty <d> | --mix <xxxx>] [--max-seeds <n>] [--seed-start <n>] [--limit <n>] [--threads <n>] [--pass-count <3|4>] [Hard flags]
+            let mut predicate = None;
+            let mut diffs = [crate::bot::BotDifficulty::NormalHeuristic; 4];
+            let mut seed_start = 0u64;
+            let mut max_seeds = 1000u64;
+            let mut limit: Option<usize> = None;
+            let mut threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+            let mut rules = RuleSet::default();
+            let mut tail_tokens: Vec<String> = Vec::new();
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--predicate" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--predicate <name>"))?;
+                        predicate =
+                            Some(crate::seed_search::Predicate::parse(&raw).ok_or_else(|| {
+                                let names: Vec<&str> = crate::seed_search::Predicate::ALL
+                                    .iter()
+                                    .map(|p| p.name())
+                                    .collect();
+                                CliError::InvalidValue {
+                                    flag: "--predicate",
+                                    value: format!("{raw} (use {})", names.join(", ")),
+                                }
+                            })?);
+                    }
+                    "--difficulty" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--difficulty <d>"))?;
+                        diffs = [parse_difficulty_opt(&raw).ok_or(CliError::InvalidValue {
+                            flag: "--difficulty",
+                            value: raw,
+                        })?; 4];
+                    }
+                    "--mix" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--mix <xxxx>"))?;
+                        diffs =
+                            crate::determinism::parse_mix(&raw).ok_or(CliError::InvalidValue {
+                                flag: "--mix",
+                                value: raw,
+                            })?;
+                    }
+                    "--max-seeds" | "--seed-start" => {
+                        let value = args
+                            .next()
+                            .and_then(|s| s.parse::<u64>().ok())
+                            .ok_or(CliError::MissingArgument("--max-seeds/--seed-start <n>"))?;
+                        if flag == "--max-seeds" {
+                            max_seeds = value;
+                        } else {
+                            seed_start = value;
+                        }
+                    }
+                    "--limit" => {
+                        limit = Some(
+                            args.next()
+                                .and_then(|s| s.parse::<usize>().ok())
+                                .filter(|&n| n > 0)
+                                .ok_or(CliError::MissingArgument("--limit <n>"))?,
+                        );
+                    }
+                    "--threads" => {
+                        threads = args
+                            .next()
+                            .and_then(|s| s.parse::<usize>().ok())
+                            .filter(|&n| n > 0)
+                            .ok_or(CliError::MissingArgument("--threads <n>"))?;
+                    }
+                    "--pass-count" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--pass-count <3|4>"))?;
+                        rules = raw
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|count| rules.with_pass_count(count).ok())
+                            .ok_or(CliError::InvalidValue {
+                                flag: "--pass-count",
+                                value: raw,
+                            })?;
+                    }
+                    other if is_shared_cli_flag(other) => {
+                        tail_tokens.push(other.to_string());
+                        if shared_flag_needs_value(other) {
+                            let v = args
+                                .next()
+                                .ok_or(CliError::MissingArgument("shared flag value"))?;
+                            tail_tokens.push(v);
+                        }
+                    }
+                    other => return Err(CliError::UnknownCommand(other.to_string())),
+                }
+            }
+            parse_hard_cli_flags(&mut tail_tokens.into_iter())?;
+            let predicate =
+                predicate.ok_or(CliError::MissingArgument("--find-seed --predicate <name>"))?;
+            let report = crate::seed_search::search(&crate::seed_search::SearchConfig {
+                predicate,
+                diffs,
+                rules,
+                seed_start,
+                max_seeds,
+                limit,
+                threads,
+            });
+            print!("{}", crate::seed_search::render_text(&report));
+            Ok(CliOutcome::Handled)
+        }
         "--compare-once" => {
             let seed = args
                 .next()
@@ -1076,50 +1688,319 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             }
             Ok(CliOutcome::Handled)
         }
-        "--explain-json" => {
-            let seed = args.next().and_then(|s| s.parse::<u64>().ok()).ok_or(
-                CliError::MissingArgument("--explain-json <seed> <seat> <path> [difficulty]"),
-            )?;
-            let seat = args.next().map(|s| parse_seat(&s)).transpose()?.ok_or(
-                CliError::MissingArgument("--explain-json <seed> <seat> <path> [difficulty]"),
-            )?;
-            let path = args
+        "--compare-runs" => {
+            // Usage: --compare-runs <a.csv> <b.csv> [--allow-unpaired]
+            let usage = "--compare-runs <a.csv> <b.csv>";
+            let left = args
                 .next()
                 .map(PathBuf::from)
-                .ok_or(CliError::MissingArgument(
-                    "--explain-json <seed> <seat> <path> [difficulty]",
-                ))?;
-            let difficulty = args.next().and_then(|s| parse_difficulty_opt(&s));
-
-            let mut controller =
-                crate::controller::GameController::new_with_seed(Some(seed), PlayerPosition::North);
-            if let Some(d) = difficulty {
-                controller.set_bot_difficulty(d);
-            }
-            if controller.in_passing_phase() {
-                if let Some(cards) = controller.simple_pass_for(seat) {
-                    let _ = controller.submit_pass(seat, cards);
+                .ok_or(CliError::MissingArgument(usage))?;
+            let right = args
+                .next()
+                .map(PathBuf::from)
+                .ok_or(CliError::MissingArgument(usage))?;
+            let mut allow_unpaired = false;
+            for flag in args {
+                match flag.as_str() {
+                    "--allow-unpaired" => allow_unpaired = true,
+                    other => return Err(CliError::UnknownCommand(other.to_string())),
                 }
-                let _ = controller.submit_auto_passes_for_others(seat);
-                let _ = controller.resolve_passes();
             }
-            while !controller.in_passing_phase() && controller.expected_to_play() != seat {
-                if controller.autoplay_one(seat).is_none() {
-                    break;
+            let load_meta = |csv: &std::path::Path| -> Result<serde_json::Value, CliError> {
+                let raw = fs::read_to_string(crate::run_meta::sidecar_path(csv))?;
+                Ok(serde_json::from_str(&raw)?)
+            };
+            let (meta_a, meta_b) = (load_meta(&left)?, load_meta(&right)?);
+            let drift = crate::run_meta::diff(&meta_a, &meta_b);
+            if drift.is_empty() {
+                println!("Run metadata: identical");
+            } else {
+                println!(
+                    "WARNING: run configuration differs ({} field{})",
+                    drift.len(),
+                    if drift.len() == 1 { "" } else { "s" }
+                );
+                for field in &drift {
+                    println!("  ! {field}");
                 }
+                println!("Results below may not be comparable.");
             }
-            let explained = controller.explain_candidates_for(seat);
-            let diff = format!("{:?}", controller.bot_difficulty());
-            let mut stats_obj = serde_json::Value::Null;
-            if matches!(
-                controller.bot_difficulty(),
-                crate::bot::BotDifficulty::FutureHard
-            ) && let Some(stats) = crate::bot::search::last_stats()
-            {
-                let mut stats_map = serde_json::Map::new();
-                stats_map.insert("scanned".into(), stats.scanned.into());
-                stats_map.insert("elapsed_ms".into(), stats.elapsed_ms.into());
-                stats_map.insert("tier".into(), format!("{:?}", stats.tier).into());
+            let usage = |meta: &serde_json::Value| {
+                serde_json::from_value::<crate::resource::ResourceUsage>(
+                    meta.get("resources")?.clone(),
+                )
+                .ok()
+            };
+            if let (Some(a), Some(b)) = (usage(&meta_a), usage(&meta_b)) {
+                println!("{}", a.delta_line(&b));
+            }
+
+            let values = |csv: &std::path::Path,
+                          meta: &serde_json::Value|
+             -> Result<Vec<(u64, f64)>, CliError> {
+                let raw = fs::read_to_string(csv)?;
+                crate::run_meta::per_seed_values(&raw, meta["seat"].as_str()).ok_or_else(|| {
+                    CliError::InvalidValue {
+                        flag: "--compare-runs",
+                        value: format!("{} has no per-seed result column", csv.display()),
+                    }
+                })
+            };
+            let (rows_a, rows_b) = (values(&left, &meta_a)?, values(&right, &meta_b)?);
+            let seeds =
+                |rows: &[(u64, f64)]| rows.iter().map(|(seed, _)| *seed).collect::<Vec<_>>();
+            let seeds_differ = seeds(&rows_a) != seeds(&rows_b)
+                || drift.iter().any(|field| field.path.starts_with("seeds"));
+            let significance = if !seeds_differ {
+                crate::run_meta::paired(&rows_a, &rows_b)
+            } else if allow_unpaired {
+                crate::run_meta::unpaired(&rows_a, &rows_b)
+            } else {
+                println!(
+                    "Significance not computed: the runs played different deal seeds (--allow-unpaired compares their means anyway)."
+                );
+                return Ok(CliOutcome::Handled);
+            };
+            match significance {
+                Some(sig) => println!(
+                    "Mean per-seed delta (B - A): {:+.3} +/- {:.3} (n={}, {}, z={:.2}): {}",
+                    sig.mean_delta,
+                    sig.std_err,
+                    sig.n,
+                    if sig.paired { "paired" } else { "unpaired" },
+                    sig.z(),
+                    if sig.significant() {
+                        "significant at 95%"
+                    } else {
+                        "not significant at 95%"
+                    }
+                ),
+                None => println!("No results to compare."),
+            }
+            Ok(CliOutcome::Handled)
+        }
+        "--decision-golden" => {
+            // Usage: --decision-golden <path> [--bless] [--mix <xxxx>] [--seed-start <n>] [--count <n>]
+            let path = args
+                .next()
+                .map(PathBuf::from)
+                .ok_or(CliError::MissingArgument(
+                    "--decision-golden <path> [--bless]",
+                ))?;
+            let mut bless = false;
+            let mut mix = "nnnn".to_string();
+            let mut seed_start = 1u64;
+            let mut count = 50u64;
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--bless" => bless = true,
+                    "--mix" => {
+                        mix = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--mix <xxxx>"))?;
+                    }
+                    "--seed-start" | "--count" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--seed-start/--count <n>"))?;
+                        let value = raw.parse::<u64>().map_err(|_| CliError::InvalidValue {
+                            flag: "--seed-start/--count",
+                            value: raw.clone(),
+                        })?;
+                        if flag == "--count" {
+                            count = value;
+                        } else {
+                            seed_start = value;
+                        }
+                    }
+                    other => return Err(CliError::UnknownCommand(other.to_string())),
+                }
+            }
+            if bless {
+                let golden =
+                    crate::determinism::record_golden(&mix, seed_start, count).map_err(|_| {
+                        CliError::InvalidValue {
+                            flag: "--mix",
+                            value: mix.clone(),
+                        }
+                    })?;
+                if let Some(parent) = path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+                crate::persist::save(&path, &golden)?;
+                println!(
+                    "Blessed {} rounds ({} seeds from {}) into {}",
+                    golden.rounds.len(),
+                    mix,
+                    seed_start,
+                    path.display()
+                );
+            } else {
+                crate::determinism::check_golden(&path).map_err(CliError::GoldenMismatch)?;
+                println!("Decision golden {} matches", path.display());
+            }
+            Ok(CliOutcome::Handled)
+        }
+        "--shadow-check" => {
+            // Usage: --shadow-check <seed_start> <count> [primary shadow] [--out <path>] [Hard flags]
+            let seed_start = args.next().and_then(|s| s.parse::<u64>().ok()).ok_or(
+                CliError::MissingArgument("--shadow-check <seed_start> <count>"),
+            )?;
+            let count = args.next().and_then(|s| s.parse::<u64>().ok()).ok_or(
+                CliError::MissingArgument("--shadow-check <seed_start> <count>"),
+            )?;
+            let mut primary = crate::bot::BotDifficulty::NormalHeuristic;
+            let mut shadow = crate::bot::BotDifficulty::FutureHard;
+            let mut out_path: Option<PathBuf> = None;
+            let mut positional = 0usize;
+            let mut tail_tokens: Vec<String> = Vec::new();
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--out" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--out <path>"))?;
+                        out_path = Some(PathBuf::from(p));
+                    }
+                    other if !other.starts_with("--") && positional < 2 => {
+                        let diff =
+                            parse_difficulty_opt(other).ok_or_else(|| CliError::InvalidValue {
+                                flag: "difficulty",
+                                value: other.to_string(),
+                            })?;
+                        if positional == 0 {
+                            primary = diff;
+                        } else {
+                            shadow = diff;
+                        }
+                        positional += 1;
+                    }
+                    _ => tail_tokens.push(flag),
+                }
+            }
+            parse_hard_cli_flags(&mut tail_tokens.into_iter())?;
+
+            let mut summary = crate::shadow::ShadowSummary::default();
+            let mut rows = Vec::new();
+            for seed in seed_start..seed_start + count {
+                let (round_summary, disagreements) =
+                    crate::shadow::shadow_check_round(seed, primary, shadow);
+                summary.merge(&round_summary);
+                rows.extend(disagreements);
+            }
+            print!("{}", summary.markdown(primary, shadow));
+            let csv = crate::shadow::disagreements_csv(&rows);
+            if let Some(path) = out_path {
+                if let Some(parent) = path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+                std::fs::write(&path, csv)?;
+                println!("Wrote {} disagreements to {}", rows.len(), path.display());
+            } else {
+                print!("{}", csv);
+            }
+            Ok(CliOutcome::Handled)
+        }
+        "--soak" => {
+            // Usage: --soak [--minutes <n>] [--seed <n>] [--out <path>]
+            let mut minutes = 10.0f64;
+            let mut seed: u64 = rand::random();
+            let mut out_path: Option<PathBuf> = None;
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--minutes" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--minutes <n>"))?;
+                        minutes = raw
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|m| m.is_finite() && *m > 0.0)
+                            .ok_or(CliError::InvalidValue {
+                                flag: "--minutes",
+                                value: raw,
+                            })?;
+                    }
+                    "--seed" => {
+                        let raw = args.next().ok_or(CliError::MissingArgument("--seed <n>"))?;
+                        seed = raw.parse::<u64>().map_err(|_| CliError::InvalidValue {
+                            flag: "--seed",
+                            value: raw,
+                        })?;
+                    }
+                    "--out" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--out <path>"))?;
+                        out_path = Some(PathBuf::from(p));
+                    }
+                    other => return Err(CliError::UnknownCommand(other.to_string())),
+                }
+            }
+            let report = crate::soak::run(&crate::soak::SoakConfig {
+                budget: std::time::Duration::from_secs_f64(minutes * 60.0),
+                seed,
+            });
+            print!("{}", report.markdown());
+            let json = serde_json::to_string_pretty(&report)?;
+            if let Some(path) = out_path {
+                if let Some(parent) = path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+                std::fs::write(&path, json)?;
+                println!("Wrote soak report to {}", path.display());
+            } else {
+                println!("{json}");
+            }
+            if !report.is_healthy() {
+                return Err(CliError::SoakFailures(report.failures));
+            }
+            Ok(CliOutcome::Handled)
+        }
+        "--explain-json" => {
+            let seed = args.next().and_then(|s| s.parse::<u64>().ok()).ok_or(
+                CliError::MissingArgument("--explain-json <seed> <seat> <path> [difficulty]"),
+            )?;
+            let seat = args.next().map(|s| parse_seat(&s)).transpose()?.ok_or(
+                CliError::MissingArgument("--explain-json <seed> <seat> <path> [difficulty]"),
+            )?;
+            let path = args
+                .next()
+                .map(PathBuf::from)
+                .ok_or(CliError::MissingArgument(
+                    "--explain-json <seed> <seat> <path> [difficulty]",
+                ))?;
+            let difficulty = args.next().and_then(|s| parse_difficulty_opt(&s));
+
+            let mut controller =
+                crate::controller::GameController::new_with_seed(Some(seed), PlayerPosition::North);
+            if let Some(d) = difficulty {
+                controller.set_bot_difficulty(d);
+            }
+            if controller.in_passing_phase() {
+                if let Some(cards) = controller.simple_pass_for(seat) {
+                    let _ = controller.submit_pass(seat, cards);
+                }
+                let _ = controller.submit_auto_passes_for_others(seat);
+                let _ = controller.resolve_passes();
+            }
+            while !controller.in_passing_phase() && controller.expected_to_play() != seat {
+                if controller.autoplay_one(seat).is_none() {
+                    break;
+                }
+            }
+            let explained = controller.explain_candidates_for(seat);
+            let diff = format!("{:?}", controller.bot_difficulty());
+            let mut stats_obj = serde_json::Value::Null;
+            if matches!(
+                controller.bot_difficulty(),
+                crate::bot::BotDifficulty::FutureHard
+            ) && let Some(stats) = crate::bot::search::last_stats()
+            {
+                let mut stats_map = serde_json::Map::new();
+                stats_map.insert("scanned".into(), stats.scanned.into());
+                stats_map.insert("elapsed_ms".into(), stats.elapsed_ms.into());
+                stats_map.insert("tier".into(), format!("{:?}", stats.tier).into());
                 stats_map.insert("leverage".into(), stats.leverage_score.into());
                 stats_map.insert("utilization".into(), stats.utilization.into());
                 stats_map.insert(
@@ -1219,6 +2100,11 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                 "seat": format!("{:?}", seat),
                 "difficulty": diff,
                 "candidates": explained.iter().map(|(c,s)| serde_json::json!({"card": c.to_string(), "score": s})).collect::<Vec<_>>(),
+                "danger_suits": controller
+                    .danger_suits(seat)
+                    .iter()
+                    .map(|(suit, value)| (suit.to_string(), serde_json::json!(value)))
+                    .collect::<serde_json::Map<_, _>>(),
                 "hard_stats": stats_obj,
                 "weights": weights,
                 "candidates_verbose": verbose,
@@ -1278,7 +2164,7 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             samples.sort();
             let n = samples.len() as u128;
             let sum: u128 = samples.iter().copied().sum();
-            let avg = if n > 0 { (sum / n) as u64 } else { 0 };
+            let avg = sum.checked_div(n).unwrap_or(0) as u64;
             let p95 = if samples.is_empty() {
                 0u64
             } else {
@@ -1312,7 +2198,7 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             Ok(CliOutcome::Handled)
         }
         "--match-batch" => {
-            // Usage: --match-batch <seat> <seed_start> <count> [difficultyA difficultyB] [--out <path>] [Hard flags]
+            // Usage: --match-batch <seat> <seed_start> <count> [difficultyA difficultyB] [--out <path>] [--run-id <id>] [--progress] [--max-duration <secs>] [--compress <zstd|gzip|none>] [--keep-last <n>] [Hard flags]
             let seat = args.next().map(|s| parse_seat(&s)).transpose()?.ok_or(
                 CliError::MissingArgument("--match-batch <seat> <seed_start> <count>"),
             )?;
@@ -1336,7 +2222,10 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             // Optional flags: --out <path>, --stats, plus Hard flags
             let mut out_path: Option<std::path::PathBuf> = None;
             let mut telemetry_out: Option<std::path::PathBuf> = None;
+            let mut run_id: Option<String> = None;
             let mut include_stats: bool = false;
+            let mut progress_opts = BatchProgressOptions::default();
+            let mut output_opts = BatchOutputOptions::default();
             let mut tail_tokens: Vec<String> = Vec::new();
             while let Some(flag) = args.next() {
                 match flag.as_str() {
@@ -1346,6 +2235,12 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                             .ok_or(CliError::MissingArgument("--out <path>"))?;
                         out_path = Some(std::path::PathBuf::from(p));
                     }
+                    "--run-id" => {
+                        run_id = Some(
+                            args.next()
+                                .ok_or(CliError::MissingArgument("--run-id <id>"))?,
+                        );
+                    }
                     "--telemetry-out" => {
                         let p = args
                             .next()
@@ -1355,6 +2250,12 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                     "--stats" => {
                         include_stats = true;
                     }
+                    "--compress" | "--keep-last" => {
+                        output_opts.parse_flag(&flag, &mut args)?;
+                    }
+                    "--progress" | "--max-duration" => {
+                        progress_opts.parse_flag(&flag, &mut args)?;
+                    }
                     other if is_shared_cli_flag(other) => {
                         tail_tokens.push(other.to_string());
                         if shared_flag_needs_value(other) {
@@ -1368,6 +2269,11 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                 }
             }
             parse_hard_cli_flags(&mut tail_tokens.into_iter())?;
+            let out_template = out_path.as_ref().map(|p| p.to_string_lossy().into_owned());
+            output_opts.check_retention(out_template.as_deref())?;
+            let vars = crate::paths::PathVars::now(run_id);
+            let out_path = resolve_template_path(out_path, &vars)?;
+            let telemetry_out = resolve_template_path(telemetry_out, &vars)?;
 
             let mut rows = Vec::new();
             if include_stats {
@@ -1378,7 +2284,12 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             } else {
                 rows.push("seed,seat,diffA,diffB,a_pen,b_pen,delta".to_string());
             }
+            let mut progress = BatchProgress::new(count, progress_opts, WallClock::start());
+            let meter = crate::resource::Meter::start();
             for i in 0..count {
+                if progress.should_stop() {
+                    break;
+                }
                 let seed = seed_start + i;
                 let a_pen = simulate_one_round(seed, seat, diff_a)?;
                 let b_pen = simulate_one_round(seed, seat, diff_b)?;
@@ -1410,35 +2321,51 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                         seed, seat, diff_a, diff_b, a_pen, b_pen, delta
                     ));
                 }
+                if let Some(line) = progress.record_hand(rows.len() - 1) {
+                    eprintln!("{line}");
+                }
             }
+            let usage = meter.finish();
             if let Some(path) = out_path {
                 if let Some(parent) = path.parent() {
                     let _ = std::fs::create_dir_all(parent);
                 }
                 std::fs::write(&path, rows.join("\n")).map_err(CliError::Io)?;
                 println!("Wrote match CSV to {}", path.display());
+                write_run_meta(
+                    &path,
+                    "match-batch",
+                    serde_json::json!({
+                        "seat": format!("{seat:?}"),
+                        "difficulties": [
+                            crate::outcome::agent_label(diff_a),
+                            crate::outcome::agent_label(diff_b),
+                        ],
+                        "seeds": { "start": seed_start, "count": count },
+                        "hands": progress.hands_done(),
+                        "permutations": null,
+                        "resources": usage,
+                    }),
+                )?;
             } else {
                 for line in rows {
                     println!("{}", line);
                 }
             }
+            if let Some(note) = progress.truncation_note() {
+                println!("{note}");
+            }
+            println!("{}", usage.summary_line());
             if let Some(path) = telemetry_out {
-                if let Some(parent) = path.parent() {
-                    std::fs::create_dir_all(parent).map_err(CliError::Io)?;
-                }
-                let (path, summary) =
-                    crate::telemetry::hard::export(Some(path)).map_err(CliError::Io)?;
-                println!(
-                    "Telemetry written to {} (records: {})",
-                    path.display(),
-                    summary.record_count
-                );
+                write_hard_telemetry(path, output_opts.compress)?;
             }
+            output_opts.apply_retention(out_template.as_deref(), &vars)?;
             Ok(CliOutcome::Handled)
         }
         "--match-mixed" => {
-            // Usage: --match-mixed <seat> <seed_start> <count> <mix> [--out <path>] [Hard flags]
-            // <mix> is a 4-char string in order N,E,S,W using: e|n|h|s (easy|normal|hard|search)
+            // Usage: --match-mixed <seat> <seed_start> <count> <mix> [--out <path>] [--baseline] [--qs-stats] [--tempo-stats] [--moon-stats] [--summary-out <path>] [--hand-outcomes <path.jsonl>] [--memoize] [--pass-count <3|4>] [--target-score <n>] [--jack-of-diamonds] [--shoot-the-sun] [--permutations <full|adaptive>] [--adaptive-spread <pph>] [--threads <n>] [--bootstrap <n>] [--bootstrap-seed <n>] [--group-field] [--run-id <id>] [--progress] [--max-duration <secs>] [--compress <zstd|gzip|none>] [--keep-last <n>] [--plan] [--deals-file <path.jsonl>] [--agent <seat> <options>] [--ratings-out <path.json>] [--ratings-prior <path.json>] [Hard flags]
+            // <mix> is a 4-char string in order N,E,S,W using: e|n|h|s|r (easy|normal|hard|search|rollout),
+            // or `<candidate>+3<field>` (e.g. h+3n) with the candidate at <seat>
             let seat = args.next().map(|s| parse_seat(&s)).transpose()?.ok_or(
                 CliError::MissingArgument("--match-mixed <seat> <seed_start> <count> <mix>"),
             )?;
@@ -1449,9 +2376,12 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                 CliError::MissingArgument("--match-mixed <seat> <seed_start> <count> <mix>"),
             )?;
             let mix = args.next().ok_or(CliError::MissingArgument(
-                "--match-mixed requires <mix> (e|n|h|s for N,E,S,W)",
+                "--match-mixed requires <mix> (e|n|h|s|r for N,E,S,W)",
             ))?;
-            if mix.len() != 4 {
+            // Expand the `h+3n` field shorthand before the 4-agent check.
+            let field = crate::field::FieldMix::parse(&mix);
+            let mix = field.map_or(mix, |field| field.expand(seat));
+            if mix.chars().count() != 4 {
                 return Err(CliError::UnknownCommand(
                     "--match-mixed <mix> must be 4 chars (N,E,S,W)".to_string(),
                 ));
@@ -1463,30 +2393,159 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                     'n' | 'N' => Some(crate::bot::BotDifficulty::NormalHeuristic),
                     'h' | 'H' => Some(crate::bot::BotDifficulty::FutureHard),
                     's' | 'S' => Some(crate::bot::BotDifficulty::SearchLookahead),
+                    'r' | 'R' => Some(crate::bot::BotDifficulty::Rollout),
                     _ => None,
                 }
             };
             let mut diffs = [crate::bot::BotDifficulty::NormalHeuristic; 4];
             for (i, c) in chars.into_iter().enumerate() {
                 let d = map_char(c).ok_or(CliError::UnknownCommand(
-                    "--match-mixed invalid mix char (use e|n|h|s)".to_string(),
+                    "--match-mixed invalid mix char (use e|n|h|s|r)".to_string(),
                 ))?;
                 diffs[i] = d;
             }
 
-            // Optional flags: --out <path>, --telemetry-out <path>, --stats, plus Hard flags
+            // Optional flags: --out <path>, --telemetry-out <path>, --stats, --baseline, plus Hard flags
             let mut out_path: Option<std::path::PathBuf> = None;
             let mut telemetry_out: Option<std::path::PathBuf> = None;
+            let mut run_id: Option<String> = None;
             let mut include_stats: bool = false;
+            let mut include_baseline: bool = false;
+            let mut include_qs_stats: bool = false;
+            let mut include_tempo_stats: bool = false;
+            let mut include_moon_stats: bool = false;
+            let mut summary_out: Option<std::path::PathBuf> = None;
+            let mut hand_outcomes: Option<std::path::PathBuf> = None;
+            let mut ratings_out: Option<std::path::PathBuf> = None;
+            let mut ratings_prior: Option<std::path::PathBuf> = None;
+            let mut memo: Option<crate::bot::DecisionMemo> = None;
+            let mut permutations: Option<crate::permutations::PermutationMode> = None;
+            let mut adaptive_spread: Option<f64> = None;
+            let mut bootstrap: Option<u32> = None;
+            let mut bootstrap_seed: Option<u64> = None;
+            let mut group_field: bool = false;
+            let mut plan: bool = false;
+            let mut threads: Option<usize> = None;
+            let mut deals: Option<Vec<Deal>> = None;
+            let mut rules = RuleSet::STANDARD;
+            let mut external = ExternalSeats::default();
+            let mut progress_opts = BatchProgressOptions::default();
+            let mut output_opts = BatchOutputOptions::default();
             let mut tail_tokens: Vec<String> = Vec::new();
             while let Some(flag) = args.next() {
                 match flag.as_str() {
+                    "--memoize" => {
+                        memo = Some(crate::bot::DecisionMemo::new());
+                    }
+                    "--agent" => {
+                        let agent_seat = args
+                            .next()
+                            .map(|s| parse_seat(&s))
+                            .transpose()?
+                            .ok_or(CliError::MissingArgument("--agent <seat> <options>"))?;
+                        let spec = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--agent <seat> <options>"))?;
+                        external.attach(agent_seat, parse_agent_options("--agent", &spec)?);
+                    }
+                    "--group-field" => {
+                        group_field = true;
+                    }
+                    "--plan" => {
+                        plan = true;
+                    }
+                    "--threads" => {
+                        threads = Some(
+                            args.next()
+                                .and_then(|s| s.parse::<usize>().ok())
+                                .filter(|&n| n > 0)
+                                .ok_or(CliError::MissingArgument("--threads <n>"))?,
+                        );
+                    }
+                    "--deals-file" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--deals-file <path.jsonl>"))?;
+                        deals = Some(load_deals_file(std::path::Path::new(&p))?);
+                    }
+                    "--jack-of-diamonds" => {
+                        rules = rules.with_jack_of_diamonds(true);
+                    }
+                    "--shoot-the-sun" => {
+                        rules = rules.with_shoot_the_sun(Some(SunScore::default()));
+                    }
+                    "--pass-count" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--pass-count <3|4>"))?;
+                        rules = raw
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|count| rules.with_pass_count(count).ok())
+                            .ok_or(CliError::InvalidValue {
+                                flag: "--pass-count",
+                                value: raw,
+                            })?;
+                    }
+                    "--permutations" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--permutations <full|adaptive>"))?;
+                        permutations =
+                            Some(crate::permutations::PermutationMode::parse(&raw).ok_or(
+                                CliError::InvalidValue {
+                                    flag: "--permutations",
+                                    value: raw,
+                                },
+                            )?);
+                    }
+                    "--adaptive-spread" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--adaptive-spread <pph>"))?;
+                        adaptive_spread = Some(
+                            raw.parse::<f64>()
+                                .ok()
+                                .filter(|v| v.is_finite() && *v >= 0.0)
+                                .ok_or(CliError::InvalidValue {
+                                    flag: "--adaptive-spread",
+                                    value: raw,
+                                })?,
+                        );
+                    }
+                    "--bootstrap" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--bootstrap <n>"))?;
+                        bootstrap = Some(raw.parse::<u32>().ok().filter(|&n| n > 0).ok_or(
+                            CliError::InvalidValue {
+                                flag: "--bootstrap",
+                                value: raw,
+                            },
+                        )?);
+                    }
+                    "--bootstrap-seed" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--bootstrap-seed <n>"))?;
+                        bootstrap_seed =
+                            Some(raw.parse::<u64>().map_err(|_| CliError::InvalidValue {
+                                flag: "--bootstrap-seed",
+                                value: raw,
+                            })?);
+                    }
                     "--out" => {
                         let p = args
                             .next()
                             .ok_or(CliError::MissingArgument("--out <path>"))?;
                         out_path = Some(std::path::PathBuf::from(p));
                     }
+                    "--run-id" => {
+                        run_id = Some(
+                            args.next()
+                                .ok_or(CliError::MissingArgument("--run-id <id>"))?,
+                        );
+                    }
                     "--telemetry-out" => {
                         let p = args
                             .next()
@@ -1496,6 +2555,61 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                     "--stats" => {
                         include_stats = true;
                     }
+                    "--baseline" => {
+                        include_baseline = true;
+                    }
+                    "--qs-stats" => {
+                        include_qs_stats = true;
+                    }
+                    "--tempo-stats" => {
+                        include_tempo_stats = true;
+                    }
+                    "--moon-stats" => {
+                        include_moon_stats = true;
+                    }
+                    "--summary-out" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--summary-out <path>"))?;
+                        summary_out = Some(std::path::PathBuf::from(p));
+                    }
+                    "--hand-outcomes" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--hand-outcomes <path.jsonl>"))?;
+                        hand_outcomes = Some(std::path::PathBuf::from(p));
+                    }
+                    "--ratings-out" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--ratings-out <path.json>"))?;
+                        ratings_out = Some(std::path::PathBuf::from(p));
+                    }
+                    "--ratings-prior" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--ratings-prior <path.json>"))?;
+                        ratings_prior = Some(std::path::PathBuf::from(p));
+                    }
+                    "--compress" | "--keep-last" => {
+                        output_opts.parse_flag(&flag, &mut args)?;
+                    }
+                    "--progress" | "--max-duration" => {
+                        progress_opts.parse_flag(&flag, &mut args)?;
+                    }
+                    "--target-score" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--target-score <n>"))?;
+                        rules = raw
+                            .parse::<u32>()
+                            .ok()
+                            .and_then(|target| rules.with_target_score(target).ok())
+                            .ok_or(CliError::InvalidValue {
+                                flag: "--target-score",
+                                value: raw,
+                            })?;
+                    }
                     other if is_shared_cli_flag(other) => {
                         tail_tokens.push(other.to_string());
                         if shared_flag_needs_value(other) {
@@ -1509,23 +2623,223 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                 }
             }
             parse_hard_cli_flags(&mut tail_tokens.into_iter())?;
+            if let Some(deals) = &deals
+                && (deals.len() as u64) < count
+            {
+                return Err(CliError::InvalidValue {
+                    flag: "--deals-file",
+                    value: format!("holds {} deals but the run asks for {count}", deals.len()),
+                });
+            }
+            if external.any() && (permutations.is_some() || plan) {
+                return Err(CliError::UnknownCommand(
+                    "--agent cannot be combined with --permutations or --plan".to_string(),
+                ));
+            }
+            if let Some(mode) = permutations.as_mut() {
+                if include_stats
+                    || include_baseline
+                    || include_qs_stats
+                    || include_tempo_stats
+                    || include_moon_stats
+                {
+                    return Err(CliError::UnknownCommand(
+                        "--permutations cannot be combined with --stats, --baseline, --qs-stats, --tempo-stats or --moon-stats"
+                            .to_string(),
+                    ));
+                }
+                if let Some(value) = adaptive_spread {
+                    match mode {
+                        crate::permutations::PermutationMode::Adaptive { spread } => {
+                            *spread = value;
+                        }
+                        crate::permutations::PermutationMode::Full => {
+                            return Err(CliError::MissingArgument(
+                                "--adaptive-spread requires --permutations adaptive",
+                            ));
+                        }
+                    }
+                }
+            } else if adaptive_spread.is_some() {
+                return Err(CliError::MissingArgument(
+                    "--adaptive-spread requires --permutations adaptive",
+                ));
+            } else if threads.is_some() {
+                return Err(CliError::MissingArgument(
+                    "--threads requires --permutations",
+                ));
+            } else if bootstrap.is_some() || bootstrap_seed.is_some() {
+                return Err(CliError::MissingArgument(
+                    "--bootstrap and --bootstrap-seed require --permutations",
+                ));
+            }
+            let threads = threads
+                .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+            let out_template = out_path.as_ref().map(|p| p.to_string_lossy().into_owned());
+            output_opts.check_retention(out_template.as_deref())?;
+            let vars = crate::paths::PathVars::now(run_id);
+            let out_path = resolve_template_path(out_path, &vars)?;
+            let telemetry_out = resolve_template_path(telemetry_out, &vars)?;
+            let summary_out = resolve_template_path(summary_out, &vars)?;
+            let hand_outcomes = resolve_template_path(hand_outcomes, &vars)?;
+            let ratings_out = resolve_template_path(ratings_out, &vars)?;
+            if plan {
+                let calibration = calibrate_match_mixed(
+                    seed_start,
+                    count,
+                    seat,
+                    diffs,
+                    rules,
+                    &mix,
+                    permutations.is_some(),
+                )?;
+                let plan = crate::bench_plan::RunPlan {
+                    seat,
+                    seed_start,
+                    count,
+                    diffs,
+                    permutations,
+                    baseline: include_baseline,
+                    memoize: memo.is_some(),
+                    rules,
+                    out_path,
+                    hand_outcomes,
+                    summary_out,
+                    telemetry_out,
+                    calibration,
+                    threads,
+                };
+                for line in plan.render() {
+                    println!("{line}");
+                }
+                return Ok(CliOutcome::Handled);
+            }
+            let mut hand_outcomes = HandOutcomeLog::create(hand_outcomes, output_opts.compress)?;
+            let show_ratings = ratings_out.is_some() || ratings_prior.is_some();
+            let mut ratings = start_ratings("hand", ratings_prior.as_deref())?;
 
             if telemetry_out.is_some() {
                 crate::telemetry::hard::reset();
             }
+            if group_field && field.is_none() {
+                return Err(CliError::MissingArgument(
+                    "--group-field requires a field mix such as h+3n",
+                ));
+            }
+            let field_meta = field.map(|field| {
+                let field_seat = seat.next();
+                let names: Vec<crate::agent_id::AgentId> =
+                    crate::field::agent_names(diffs, seat, false)
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(idx, _)| *idx != seat.index())
+                        .map(|(_, name)| name)
+                        .collect();
+                serde_json::json!({
+                    "difficulty": crate::outcome::agent_label(diffs[field_seat.index()]),
+                    "count": field.count,
+                    "candidate_seat": format!("{seat:?}"),
+                    "agents": names,
+                    "grouped": group_field,
+                })
+            });
+
+            if let Some(mode) = permutations {
+                run_match_mixed_permutations(
+                    MixedPermutationRun {
+                        seat,
+                        seed_start,
+                        count,
+                        diffs,
+                        mode,
+                        progress_opts,
+                        out_path,
+                        summary_out,
+                        field: field_meta,
+                        rules,
+                        deals,
+                        threads,
+                        bootstrap: bootstrap
+                            .unwrap_or(crate::permutations::DEFAULT_BOOTSTRAP_RESAMPLES),
+                        bootstrap_seed: bootstrap_seed.unwrap_or(seed_start),
+                    },
+                    &mut memo,
+                    &mut hand_outcomes,
+                    &mut ratings,
+                )?;
+                hand_outcomes.finish()?;
+                if show_ratings {
+                    finish_ratings(&ratings, ratings_out)?;
+                }
+                if let Some(path) = telemetry_out {
+                    write_hard_telemetry(path, output_opts.compress)?;
+                }
+                output_opts.apply_retention(out_template.as_deref(), &vars)?;
+                return Ok(CliOutcome::Handled);
+            }
 
             let mut rows = Vec::new();
-            if include_stats {
-                rows.push(
-                    "seed,seat,mix,pen,scanned,elapsed_ms,dp_hits,nudge_hits,nudge_guard"
-                        .to_string(),
-                );
+            let mut header = if include_stats {
+                "seed,seat,mix,pen,scanned,elapsed_ms,dp_hits,nudge_hits,nudge_guard".to_string()
             } else {
-                rows.push("seed,seat,mix,pen".to_string());
+                "seed,seat,mix,pen".to_string()
+            };
+            if include_baseline {
+                header.push_str(",baseline,above_baseline");
             }
+            rows.push(header);
+            let agents = if field.is_some() {
+                crate::field::agent_names(diffs, seat, group_field)
+            } else {
+                diffs.map(crate::agent_id::AgentId::of)
+            };
+            let mut baselines = DealBaselineCache::new(rules);
+            let mut pen_total: i64 = 0;
+            let mut suns = [0u32; 4];
+            let mut baseline_total: i64 = 0;
+            let mut outcomes: Vec<crate::outcome::HandOutcome> = Vec::new();
+            let mut progress = BatchProgress::new(count, progress_opts, WallClock::start());
+            let meter = crate::resource::Meter::start();
+            external.start();
             for i in 0..count {
+                if progress.should_stop() {
+                    break;
+                }
                 let seed = seed_start + i;
-                let pen = simulate_one_round_mixed(seed, seat, diffs)?;
+                let deal = deals.as_ref().map(|deals| &deals[i as usize]);
+                let outcome = simulate_hand_outcome_with_agents(
+                    seed,
+                    deal,
+                    seat,
+                    diffs,
+                    rules,
+                    &mut memo,
+                    &mut external,
+                )?;
+                let pen = outcome.bench_points(seat, &rules);
+                hand_outcomes.write(&outcome, 0, diffs)?;
+                if let Some(shooter) = outcome.sun_shooter.filter(|_| rules.shoot_the_sun) {
+                    suns[shooter.index()] += 1;
+                }
+                ratings.record(
+                    &agents,
+                    PlayerPosition::LOOP.map(|p| i64::from(outcome.bench_points(p, &rules))),
+                );
+                if include_qs_stats
+                    || include_tempo_stats
+                    || include_moon_stats
+                    || summary_out.is_some()
+                {
+                    outcomes.push(outcome);
+                }
+                pen_total += i64::from(pen);
+                let baseline_cols = if include_baseline {
+                    let baseline = baselines.penalties_for(seed, deal, seat, &mut memo)?;
+                    baseline_total += i64::from(baseline);
+                    format!(", {}, {}", baseline, pen - baseline)
+                } else {
+                    String::new()
+                };
                 if include_stats {
                     let stats = crate::bot::search::last_stats();
                     let (nudges, nudge_trace) = collect_nudge_metrics(stats.as_ref());
@@ -1533,45 +2847,301 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                         .map(|s| (s.scanned, s.elapsed_ms, s.endgame_dp_hits))
                         .unwrap_or((0usize, 0u32, 0usize));
                     rows.push(format!(
-                        "{}, {:?}, {}, {}, {}, {}, {}, {}, {}",
-                        seed, seat, mix, pen, scanned, elapsed, dp, nudges, nudge_trace
+                        "{}, {:?}, {}, {}, {}, {}, {}, {}, {}{}",
+                        seed,
+                        seat,
+                        mix,
+                        pen,
+                        scanned,
+                        elapsed,
+                        dp,
+                        nudges,
+                        nudge_trace,
+                        baseline_cols
                     ));
                 } else {
-                    rows.push(format!("{}, {:?}, {}, {}", seed, seat, mix, pen));
-                }
+                    rows.push(format!(
+                        "{}, {:?}, {}, {}{}",
+                        seed, seat, mix, pen, baseline_cols
+                    ));
+                }
+                if let Some(line) = progress.record_hand(rows.len() - 1) {
+                    eprintln!("{line}");
+                }
             }
+            external.shutdown();
+            let usage = meter.finish();
+            hand_outcomes.finish()?;
             if let Some(path) = out_path {
                 if let Some(parent) = path.parent() {
                     let _ = std::fs::create_dir_all(parent);
                 }
                 std::fs::write(&path, rows.join("\n")).map_err(CliError::Io)?;
                 println!("Wrote mixed-match CSV to {}", path.display());
+                write_run_meta(
+                    &path,
+                    "match-mixed",
+                    serde_json::json!({
+                        "seat": format!("{seat:?}"),
+                        "mix": mix,
+                        "difficulties": diffs.map(crate::outcome::agent_label),
+                        "field": field_meta.clone(),
+                        "rules": rules,
+                        "seeds": { "start": seed_start, "count": count },
+                        "hands": progress.hands_done(),
+                        "permutations": null,
+                        "external_agents": external.meta(),
+                        "resources": usage,
+                    }),
+                )?;
             } else {
                 for line in rows {
                     println!("{}", line);
                 }
             }
-            if let Some(path) = telemetry_out {
+            for line in external.report() {
+                println!("{line}");
+            }
+            if rules.shoot_the_sun {
+                println!(
+                    "Suns N/E/S/W: {}/{}/{}/{}",
+                    suns[0], suns[1], suns[2], suns[3]
+                );
+            }
+            let played = progress.hands_done();
+            if include_baseline && played > 0 {
+                let pph = pen_total as f64 / played as f64;
+                let baseline_pph = baseline_total as f64 / played as f64;
+                println!(
+                    "PPH above baseline: {:+.2} (pph={:.2} baseline_pph={:.2} hands={})",
+                    pph - baseline_pph,
+                    pph,
+                    baseline_pph,
+                    played
+                );
+            }
+            if let Some(note) = progress.truncation_note() {
+                println!("{note}");
+            }
+            let memo_stats = memo.as_ref().map(|memo| memo.stats());
+            if let Some(memo) = &memo {
+                println!("{}", memo_summary_line(memo));
+            }
+            println!("{}", usage.summary_line());
+            let queen_stats = crate::outcome::queen_stats(&outcomes, &agents);
+            if include_qs_stats {
+                println!();
+                print!("{}", crate::outcome::queen_stats_markdown(&queen_stats));
+            }
+            let tempo_stats = crate::outcome::tempo_stats(&outcomes, &agents);
+            if include_tempo_stats {
+                println!();
+                print!("{}", crate::outcome::tempo_stats_markdown(&tempo_stats));
+            }
+            let moon_defense = crate::outcome::moon_defense_stats(&outcomes, &agents);
+            if include_moon_stats {
+                println!();
+                print!("{}", crate::outcome::moon_defense_markdown(&moon_defense));
+            }
+            if show_ratings {
+                finish_ratings(&ratings, ratings_out)?;
+            }
+            if let Some(path) = summary_out {
                 if let Some(parent) = path.parent() {
                     std::fs::create_dir_all(parent).map_err(CliError::Io)?;
                 }
-                let (path, summary) =
-                    crate::telemetry::hard::export(Some(path)).map_err(CliError::Io)?;
+                let summary = serde_json::json!({
+                    "seat": format!("{seat:?}"),
+                    "mix": mix,
+                    "seed_start": seed_start,
+                    "hands": played,
+                    "pph": if played > 0 { pen_total as f64 / played as f64 } else { 0.0 },
+                    "agents": agents,
+                    "field": field_meta,
+                    "rules": rules,
+                    "queen_of_spades": queen_stats,
+                    "tempo": tempo_stats,
+                    "moon_defense": moon_defense,
+                    "suns": rules.shoot_the_sun.then_some(suns),
+                    "ratings": ratings.table(),
+                    "external_agents": external.meta(),
+                    "resources": usage,
+                    "memo": memo_stats.map(|stats| serde_json::json!({
+                        "hits": stats.hits,
+                        "misses": stats.misses,
+                        "hit_rate": stats.hit_rate(),
+                    })),
+                });
+                let json = serde_json::to_string_pretty(&summary).map_err(CliError::Json)?;
+                std::fs::write(&path, json).map_err(CliError::Io)?;
+                println!("Wrote summary to {}", path.display());
+            }
+            if let Some(path) = telemetry_out {
+                write_hard_telemetry(path, output_opts.compress)?;
+            }
+            output_opts.apply_retention(out_template.as_deref(), &vars)?;
+            Ok(CliOutcome::Handled)
+        }
+        "--match-full" => {
+            // Usage: --match-full <seed_start> <count> <mix> [--target-score <n>] [--jack-of-diamonds] [--shoot-the-sun] [--hand-outcomes <path.jsonl>] [--summary-out <path>] [--ratings-out <path.json>] [--ratings-prior <path.json>]
+            // Plays whole matches, round after round, until a seat reaches the target.
+            let seed_start = args.next().and_then(|s| s.parse::<u64>().ok()).ok_or(
+                CliError::MissingArgument("--match-full <seed_start> <count> <mix>"),
+            )?;
+            let count = args.next().and_then(|s| s.parse::<u64>().ok()).ok_or(
+                CliError::MissingArgument("--match-full <seed_start> <count> <mix>"),
+            )?;
+            let raw_mix = args.next().ok_or(CliError::MissingArgument(
+                "--match-full <seed_start> <count> <mix>",
+            ))?;
+            let diffs = crate::determinism::parse_mix(&raw_mix).ok_or(CliError::InvalidValue {
+                flag: "--match-full",
+                value: raw_mix.clone(),
+            })?;
+            let mut rules = RuleSet::default();
+            let mut hand_outcomes: Option<std::path::PathBuf> = None;
+            let mut summary_out: Option<std::path::PathBuf> = None;
+            let mut ratings_out: Option<std::path::PathBuf> = None;
+            let mut ratings_prior: Option<std::path::PathBuf> = None;
+            while let Some(flag) = args.next() {
+                match flag.as_str() {
+                    "--jack-of-diamonds" => {
+                        rules = rules.with_jack_of_diamonds(true);
+                    }
+                    "--shoot-the-sun" => {
+                        rules = rules.with_shoot_the_sun(Some(SunScore::default()));
+                    }
+                    "--hand-outcomes" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--hand-outcomes <path.jsonl>"))?;
+                        hand_outcomes = Some(std::path::PathBuf::from(p));
+                    }
+                    "--ratings-out" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--ratings-out <path.json>"))?;
+                        ratings_out = Some(std::path::PathBuf::from(p));
+                    }
+                    "--ratings-prior" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--ratings-prior <path.json>"))?;
+                        ratings_prior = Some(std::path::PathBuf::from(p));
+                    }
+                    "--summary-out" => {
+                        let p = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--summary-out <path>"))?;
+                        summary_out = Some(std::path::PathBuf::from(p));
+                    }
+                    "--target-score" => {
+                        let raw = args
+                            .next()
+                            .ok_or(CliError::MissingArgument("--target-score <n>"))?;
+                        rules = raw
+                            .parse::<u32>()
+                            .ok()
+                            .and_then(|target| rules.with_target_score(target).ok())
+                            .ok_or(CliError::InvalidValue {
+                                flag: "--target-score",
+                                value: raw,
+                            })?;
+                    }
+                    other => return Err(CliError::UnknownCommand(other.to_string())),
+                }
+            }
+            let mut log = HandOutcomeLog::create(hand_outcomes, crate::outputs::Compression::None)?;
+            let mut ratings = start_ratings("match", ratings_prior.as_deref())?;
+            let agents = diffs.map(crate::agent_id::AgentId::of);
+            println!("seed,rounds,winner,north,east,south,west,moons");
+            let mut wins = [0u32; 4];
+            let mut rounds = 0u32;
+            let mut moons = 0u32;
+            let mut suns = 0u32;
+            let mut matches = Vec::new();
+            for seed in seed_start..seed_start + count {
+                let FullMatchResult { record, hands } = simulate_full_match(seed, diffs, rules);
                 println!(
-                    "Telemetry written to {} (records: {})",
-                    path.display(),
-                    summary.record_count
+                    "{},{},{:?},{},{},{},{},{}",
+                    seed,
+                    record.rounds,
+                    record.winner,
+                    record.totals[0],
+                    record.totals[1],
+                    record.totals[2],
+                    record.totals[3],
+                    record.moons
                 );
+                for hand in &hands {
+                    log.write_match_row(hand)?;
+                }
+                log.write_match_row(&crate::outcome::MatchRow::Match(record.clone()))?;
+                ratings.record(&agents, record.placements.map(i64::from));
+                if let Some(winner) = record.winner {
+                    wins[winner.index()] += 1;
+                }
+                rounds += record.rounds;
+                moons += record.moons;
+                suns += record.suns;
+                matches.push(record);
+            }
+            log.finish()?;
+            let suns_note = if rules.shoot_the_sun {
+                format!(" | suns {suns}")
+            } else {
+                String::new()
+            };
+            println!(
+                "Matches: {} to {} | wins N/E/S/W {}/{}/{}/{} | avg rounds {:.1} | moons {}{} | match memory {}",
+                count,
+                rules.target_score,
+                wins[0],
+                wins[1],
+                wins[2],
+                wins[3],
+                rounds as f64 / count.max(1) as f64,
+                moons,
+                suns_note,
+                if crate::bot::match_memory_enabled() {
+                    "on"
+                } else {
+                    "off"
+                }
+            );
+            let agent_stats = crate::outcome::match_stats(&matches, &agents);
+            println!();
+            print!("{}", crate::outcome::match_stats_markdown(&agent_stats));
+            finish_ratings(&ratings, ratings_out)?;
+            if let Some(path) = summary_out {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(CliError::Io)?;
+                }
+                let summary = serde_json::json!({
+                    "mix": raw_mix,
+                    "seed_start": seed_start,
+                    "matches": count,
+                    "hands": rounds,
+                    "moons": moons,
+                    "suns": suns,
+                    "rules": rules,
+                    "seat_wins": wins,
+                    "agents": agent_stats,
+                    "ratings": ratings.table(),
+                });
+                let json = serde_json::to_string_pretty(&summary).map_err(CliError::Json)?;
+                std::fs::write(&path, json).map_err(CliError::Io)?;
+                println!("Wrote summary to {}", path.display());
             }
             Ok(CliOutcome::Handled)
         }
         "--match-mixed-file" => {
-            // Usage: --match-mixed-file <seat> <mix> --seeds-file <path> [--out <path>] [Hard flags]
+            // Usage: --match-mixed-file <seat> <mix> --seeds-file <path> [--out <path>] [--run-id <id>] [--compress <zstd|gzip|none>] [--keep-last <n>] [Hard flags]
             let seat = args.next().map(|s| parse_seat(&s)).transpose()?.ok_or(
                 CliError::MissingArgument("--match-mixed-file <seat> <mix> --seeds-file <path>"),
             )?;
             let mix = args.next().ok_or(CliError::MissingArgument(
-                "--match-mixed-file requires <mix> (e|n|h|s for N,E,S,W)",
+                "--match-mixed-file requires <mix> (e|n|h|s|r for N,E,S,W)",
             ))?;
             if mix.len() != 4 {
                 return Err(CliError::UnknownCommand(
@@ -1583,7 +3153,9 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             let mut seeds_path: Option<std::path::PathBuf> = None;
             let mut out_path: Option<std::path::PathBuf> = None;
             let mut telemetry_out: Option<std::path::PathBuf> = None;
+            let mut run_id: Option<String> = None;
             let mut include_stats: bool = false;
+            let mut output_opts = BatchOutputOptions::default();
             let mut tail_tokens: Vec<String> = Vec::new();
             while let Some(flag) = args.next() {
                 match flag.as_str() {
@@ -1599,6 +3171,12 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                             .ok_or(CliError::MissingArgument("--out <path>"))?;
                         out_path = Some(std::path::PathBuf::from(p));
                     }
+                    "--run-id" => {
+                        run_id = Some(
+                            args.next()
+                                .ok_or(CliError::MissingArgument("--run-id <id>"))?,
+                        );
+                    }
                     "--telemetry-out" => {
                         let p = args
                             .next()
@@ -1608,6 +3186,9 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                     "--stats" => {
                         include_stats = true;
                     }
+                    "--compress" | "--keep-last" => {
+                        output_opts.parse_flag(&flag, &mut args)?;
+                    }
                     other if is_shared_cli_flag(other) => {
                         tail_tokens.push(other.to_string());
                         if shared_flag_needs_value(other) {
@@ -1620,8 +3201,14 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                     other => return Err(CliError::UnknownCommand(other.to_string())),
                 }
             }
-            let seeds_path = seeds_path.ok_or(CliError::MissingArgument("--seeds-file <path>"))?;
             parse_hard_cli_flags(&mut tail_tokens.into_iter())?;
+            let out_template = out_path.as_ref().map(|p| p.to_string_lossy().into_owned());
+            output_opts.check_retention(out_template.as_deref())?;
+            let vars = crate::paths::PathVars::now(run_id);
+            let seeds_path = resolve_template_path(seeds_path, &vars)?
+                .ok_or(CliError::MissingArgument("--seeds-file <path>"))?;
+            let out_path = resolve_template_path(out_path, &vars)?;
+            let telemetry_out = resolve_template_path(telemetry_out, &vars)?;
 
             if telemetry_out.is_some() {
                 crate::telemetry::hard::reset();
@@ -1635,13 +3222,14 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                     'n' | 'N' => Some(crate::bot::BotDifficulty::NormalHeuristic),
                     'h' | 'H' => Some(crate::bot::BotDifficulty::FutureHard),
                     's' | 'S' => Some(crate::bot::BotDifficulty::SearchLookahead),
+                    'r' | 'R' => Some(crate::bot::BotDifficulty::Rollout),
                     _ => None,
                 }
             };
             let mut diffs = [crate::bot::BotDifficulty::NormalHeuristic; 4];
             for (i, c) in chars.into_iter().enumerate() {
                 let d = map_char(c).ok_or(CliError::UnknownCommand(
-                    "--match-mixed-file invalid mix char (use e|n|h|s)".to_string(),
+                    "--match-mixed-file invalid mix char (use e|n|h|s|r)".to_string(),
                 ))?;
                 diffs[i] = d;
             }
@@ -1655,14 +3243,21 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
             } else {
                 rows.push("seed,seat,mix,pen".to_string());
             }
-            let content = std::fs::read_to_string(&seeds_path).map_err(CliError::Io)?;
+            let content = crate::outputs::read_to_string(&seeds_path).map_err(CliError::Io)?;
             for line in content.lines() {
                 let s = line.trim();
                 if s.is_empty() {
                     continue;
                 }
                 if let Ok(seed) = s.parse::<u64>() {
-                    let pen = simulate_one_round_mixed(seed, seat, diffs)?;
+                    let pen = simulate_one_round_mixed(
+                        seed,
+                        None,
+                        seat,
+                        diffs,
+                        RuleSet::STANDARD,
+                        &mut None,
+                    )?;
                     if include_stats {
                         let stats = crate::bot::search::last_stats();
                         let (nudges, nudge_trace) = collect_nudge_metrics(stats.as_ref());
@@ -1690,21 +3285,13 @@ pub fn run_cli_with_args(mut args: impl Iterator<Item = String>) -> Result<CliOu
                 }
             }
             if let Some(path) = telemetry_out {
-                if let Some(parent) = path.parent() {
-                    std::fs::create_dir_all(parent).map_err(CliError::Io)?;
-                }
-                let (path, summary) =
-                    crate::telemetry::hard::export(Some(path)).map_err(CliError::Io)?;
-                println!(
-                    "Telemetry written to {} (records: {})",
-                    path.display(),
-                    summary.record_count
-                );
+                write_hard_telemetry(path, output_opts.compress)?;
             }
+            output_opts.apply_retention(out_template.as_deref(), &vars)?;
             Ok(CliOutcome::Handled)
         }
         "--help" | "-h" => {
-            let help = "Available commands:\n  --export-snapshot <path> [seed] [seat]\n  --export-seed <path> [seed] [seat]\n  --import-snapshot <path> [--legacy-ok]\n  --show-weights\n  --explain-once <seed> <seat> [difficulty] [Hard flags]\n  --explain-batch <seat> <seed_start> <count> [difficulty] [Hard flags]\n  --explain-snapshot <path> <seat> [Hard flags]\n  --explain-pass-once <seed> <seat>\n  --explain-pass-batch <seat> <seed_start> <count>\n  --compare-once <seed> <seat> [Hard flags]\n  --compare-batch <seat> <seed_start> <count> [--out <path>] [--only-disagree] [Hard flags]\n  --explain-json <seed> <seat> <path> [difficulty] [Hard flags]\n  --bench-check <difficulty> <seat> <seed_start> <count> [Hard flags]\n  --match-batch <seat> <seed_start> <count> [difficultyA difficultyB] [--out <path>] [Hard flags]\n  --match-mixed <seat> <seed_start> <count> <mix> [--out <path>] [--stats] [Hard flags]\n  --match-mixed-file <seat> <mix> --seeds-file <path> [--out <path>] [--stats] [Hard flags]\n\nHard flags (can follow many commands):\n  --hard-deterministic             Enable deterministic mode (step-capped)\n  --hard-steps <n>                 Deterministic step cap for Hard\n  --hard-phaseb-topk <k>           Top-K candidates for continuation scoring\n  --hard-branch-limit <n>          Candidate branch limit (base ordering)\n  --hard-next-branch-limit <n>     Next-trick probe branch limit\n  --hard-time-cap-ms <ms>          Wall-clock cap (non-deterministic mode)\n  --hard-cutoff <margin>           Early cutoff margin for choose()\n  --hard-cont-boost-gap <n>        Gap threshold to boost continuation in near ties\n  --hard-cont-boost-factor <n>     Multiplier applied to continuation in near ties\n  --hard-det | --hard-det-enable   Enable determinization sampling (env-gated)\n  --hard-det-k <n>                 Number of determinization samples (K)\n  --hard-det-probe                 Widen next-trick probe under determinization\n  --hard-verbose                   Print verbose continuation parts (requires MDH_DEBUG_LOGS=1)\n  --help";
+            let help = "Available commands:\n  --seed <n>\n  --export-snapshot <path> [seed] [seat]\n  --export-seed <path> [seed] [seat]\n  --export-human-plays <out> [--from <path>]\n  --import-snapshot <path> [--legacy-ok]\n  --show-weights\n  --lint-telemetry <file.ndjson> [--max <n>]\n  --check-agent <difficulty | agent options>\n  --explain-once <seed> <seat> [difficulty] [--deal <hands>] [Hard flags]\n  --explain-batch <seat> <seed_start> <count> [difficulty] [Hard flags]\n  --debug-deal <seed> [difficulty] [Hard flags]\n  --explain-snapshot <path> <seat> [Hard flags]\n  --explain-pass (--deal <hands> | --seed <n>) --seat <seat> [--direction <dir>] [--scores <n,e,s,w>] [--difficulty <d>] [--top <n>] [--json]\n  --explain-pass-once <seed> <seat>\n  --explain-pass-batch <seat> <seed_start> <count>\n  --find-seed --predicate <name> [--difficulty <d> | --mix <xxxx>] [--max-seeds <n>] [--seed-start <n>] [--limit <n>] [--threads <n>] [--pass-count <3|4>] [Hard flags]\n  --compare-once <seed> <seat> [Hard flags]\n  --compare-batch <seat> <seed_start> <count> [--out <path>] [--only-disagree] [Hard flags]\n  --compare-runs <a.csv> <b.csv> [--allow-unpaired]\n  --decision-golden <path> [--bless] [--mix <xxxx>] [--seed-start <n>] [--count <n>]\n  --shadow-check <seed_start> <count> [primary shadow] [--out <path>] [Hard flags]\n  --soak [--minutes <n>] [--seed <n>] [--out <path>]\n  --explain-json <seed> <seat> <path> [difficulty] [Hard flags]\n  --bench-check <difficulty> <seat> <seed_start> <count> [Hard flags]\n  --match-batch <seat> <seed_start> <count> [difficultyA difficultyB] [--out <path>] [--run-id <id>] [--progress] [--max-duration <secs>] [--compress <zstd|gzip|none>] [--keep-last <n>] [Hard flags]\n  --match-mixed <seat> <seed_start> <count> <mix> [--out <path>] [--stats] [--baseline] [--qs-stats] [--tempo-stats] [--moon-stats] [--summary-out <path>] [--hand-outcomes <path.jsonl>] [--memoize] [--pass-count <3|4>] [--target-score <n>] [--jack-of-diamonds] [--shoot-the-sun] [--permutations <full|adaptive>] [--adaptive-spread <pph>] [--threads <n>] [--bootstrap <n>] [--bootstrap-seed <n>] [--group-field] [--plan] [--deals-file <path.jsonl>] [--agent <seat> <options>] [--ratings-out <path.json>] [--ratings-prior <path.json>] [--run-id <id>] [--progress] [--max-duration <secs>] [--compress <zstd|gzip|none>] [--keep-last <n>] [Hard flags]\n  --match-full <seed_start> <count> <mix> [--target-score <n>] [--jack-of-diamonds] [--shoot-the-sun] [--hand-outcomes <path.jsonl>] [--summary-out <path>] [--ratings-out <path.json>] [--ratings-prior <path.json>]\n  --match-mixed-file <seat> <mix> --seeds-file <path> [--out <path>] [--stats] [--run-id <id>] [--compress <zstd|gzip|none>] [--keep-last <n>] [Hard flags]\n\nHard flags (can follow many commands):\n  --hard-deterministic             Enable deterministic mode (step-capped)\n  --hard-steps <n>                 Deterministic step cap for Hard\n  --hard-phaseb-topk <k>           Top-K candidates for continuation scoring\n  --hard-branch-limit <n>          Candidate branch limit (base ordering)\n  --hard-next-branch-limit <n>     Next-trick probe branch limit\n  --hard-time-cap-ms <ms>          Wall-clock cap (non-deterministic mode)\n  --hard-cutoff <margin>           Early cutoff margin for choose()\n  --hard-cont-boost-gap <n>        Gap threshold to boost continuation in near ties\n  --hard-cont-boost-factor <n>     Multiplier applied to continuation in near ties\n  --hard-det | --hard-det-enable   Enable determinization sampling (env-gated)\n  --hard-det-k <n>                 Number of determinization samples (K)\n  --hard-det-probe                 Widen next-trick probe under determinization\n  --hard-verbose                   Print verbose continuation parts (requires MDH_DEBUG_LOGS=1)\n  --help";
             println!("{help}");
             show_info_box("mdhearts CLI", help);
             Ok(CliOutcome::Handled)
@@ -1797,12 +3384,20 @@ fn parse_seat(input: &str) -> Result<PlayerPosition, CliError> {
     }
 }
 
+fn parse_agent_options(flag: &'static str, spec: &str) -> Result<ExternalOptions, CliError> {
+    ExternalOptions::parse(spec).map_err(|err| CliError::InvalidValue {
+        flag,
+        value: err.to_string(),
+    })
+}
+
 fn parse_difficulty_opt(input: &str) -> Option<crate::bot::BotDifficulty> {
     match input.to_ascii_lowercase().as_str() {
         "easy" | "legacy" => Some(crate::bot::BotDifficulty::EasyLegacy),
         "normal" | "default" => Some(crate::bot::BotDifficulty::NormalHeuristic),
         "hard" | "future" => Some(crate::bot::BotDifficulty::FutureHard),
         "search" | "lookahead" => Some(crate::bot::BotDifficulty::SearchLookahead),
+        "rollout" => Some(crate::bot::BotDifficulty::Rollout),
         _ => None,
     }
 }
@@ -1883,6 +3478,25 @@ fn shared_flag_needs_value(flag: &str) -> bool {
     HARD_FLAGS_NEED_VALUE.contains(&flag) || THINK_FLAGS_NEED_VALUE.contains(&flag)
 }
 
+/// Expands `${VAR}`, `${run_id}` and `${date}` in a path taken from the command line.
+fn resolve_template_path(
+    path: Option<PathBuf>,
+    vars: &crate::paths::PathVars,
+) -> Result<Option<PathBuf>, CliError> {
+    path.map(|p| vars.expand_path(&p.to_string_lossy()))
+        .transpose()
+        .map_err(CliError::from)
+}
+
+/// `data/play.jsonl` -> `data/play.<tag>.<ext>`.
+fn dataset_split_path(out: &std::path::Path, tag: &str, ext: &str) -> PathBuf {
+    let stem = out
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "dataset".to_string());
+    out.with_file_name(format!("{stem}.{tag}.{ext}"))
+}
+
 fn is_shared_cli_flag(flag: &str) -> bool {
     flag.starts_with("--hard-") || flag.starts_with("--think-")
 }
@@ -2078,12 +3692,8 @@ fn simulate_one_round(
         let _ = controller.submit_auto_passes_for_others(seat);
         let _ = controller.resolve_passes();
     }
-    loop {
-        let totals = controller.penalties_this_round();
-        let sum: u32 = totals.iter().map(|&v| v as u32).sum();
-        if sum >= 26 {
-            break;
-        }
+    // Only the penalties are needed, so stop as soon as they are settled.
+    while !controller.is_round_decided() {
         let to_play = controller.expected_to_play();
         if !matches!(
             controller.autoplay_one_with_status(to_play.next()),
@@ -2099,37 +3709,1195 @@ fn simulate_one_round(
 
 fn simulate_one_round_mixed(
     seed: u64,
+    deal: Option<&Deal>,
     seat: PlayerPosition,
     diffs: [crate::bot::BotDifficulty; 4],
-) -> Result<u8, CliError> {
-    let mut controller =
-        crate::controller::GameController::new_with_seed(Some(seed), PlayerPosition::North);
-    // Passing: apply our seat difficulty for pass; others auto-pass with their seat difficulty
+    rules: RuleSet,
+    memo: &mut Option<crate::bot::DecisionMemo>,
+) -> Result<i32, CliError> {
+    let outcome = simulate_hand_outcome_mixed(seed, deal, seat, diffs, rules, memo)?;
+    Ok(outcome.bench_points(seat, &rules))
+}
+
+/// `memo`, when present, is lent to the hand's controller and handed back
+/// afterwards so cached decisions carry across hands. `deal`, when present,
+/// replaces the seed's cards; the seed still drives everything else.
+fn simulate_hand_outcome_mixed(
+    seed: u64,
+    deal: Option<&Deal>,
+    seat: PlayerPosition,
+    diffs: [crate::bot::BotDifficulty; 4],
+    rules: RuleSet,
+    memo: &mut Option<crate::bot::DecisionMemo>,
+) -> Result<crate::outcome::HandOutcome, CliError> {
+    simulate_hand_outcome_with_agents(
+        seed,
+        deal,
+        seat,
+        diffs,
+        rules,
+        memo,
+        &mut ExternalSeats::default(),
+    )
+}
+
+/// Submits every seat's pass, `seat` first. Bots pass at `seat`'s
+/// difficulty; each seat's own difficulty decides whether its moon-defense
+/// guards are noted in `outcome`.
+fn submit_passes_in_turn(
+    controller: &mut crate::controller::GameController,
+    external: &mut ExternalSeats,
+    seat: PlayerPosition,
+    diffs: [crate::bot::BotDifficulty; 4],
+    outcome: &mut crate::outcome::HandOutcome,
+) {
+    // Easy passes its first cards and never runs the moon-defense guards.
+    for passer in PlayerPosition::LOOP {
+        let difficulty = diffs[passer.index()];
+        if difficulty == crate::bot::BotDifficulty::EasyLegacy {
+            continue;
+        }
+        controller.set_bot_difficulty(difficulty);
+        if let Some(urgency) = crate::bot::block_shooter_urgency(&controller.bot_context(passer)) {
+            outcome.note_block_shooter_pass(passer, urgency);
+        }
+    }
+    for passer in pass_order(seat) {
+        if external.submit_pass(controller, passer) {
+            continue;
+        }
+        controller.set_bot_difficulty(external.fallback_for(passer, diffs[seat.index()]));
+        if let Some(cards) = controller.simple_pass_for(passer) {
+            let _ = controller.submit_pass(passer, cards);
+        }
+    }
+}
+
+/// [`submit_passes_in_turn`] with the four decisions made at once, one
+/// thread per seat, each from its own snapshot of the deal. A pass reads
+/// only the deal and the scores, never another seat's pass, so the same
+/// passes are submitted in the same order; outside engines just stop
+/// waiting on each other.
+fn submit_passes_concurrently(
+    controller: &mut crate::controller::GameController,
+    external: &mut ExternalSeats,
+    seat: PlayerPosition,
+    diffs: [crate::bot::BotDifficulty; 4],
+    outcome: &mut crate::outcome::HandOutcome,
+) {
+    let snapshot = controller.bot_snapshot();
+    let level = diffs[seat.index()];
+    let choices: Vec<(Option<u8>, Option<hearts_core::model::passing::PassCards>)> =
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = PlayerPosition::LOOP
+                .into_iter()
+                .zip(external.policies.iter_mut())
+                .map(|(passer, policy)| {
+                    let snapshot = snapshot.clone().make_thread_local();
+                    scope.spawn(move || {
+                        let difficulty = diffs[passer.index()];
+                        let urgency = (difficulty != crate::bot::BotDifficulty::EasyLegacy)
+                            .then(|| {
+                                crate::bot::block_shooter_urgency(
+                                    &snapshot.bot_context(passer, difficulty),
+                                )
+                            })
+                            .flatten();
+                        let ctx = snapshot.bot_context(passer, level);
+                        let hand = ctx.round.hand(passer);
+                        let cards = match policy {
+                            Some(policy) => policy.choose_pass(hand, &ctx),
+                            None => crate::bot::policy::PlannerPolicy::new(level)
+                                .choose_pass(hand, &ctx),
+                        };
+                        (urgency, cards)
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("pass worker"))
+                .collect()
+        });
+    for (passer, (urgency, _)) in PlayerPosition::LOOP.into_iter().zip(&choices) {
+        if let Some(urgency) = *urgency {
+            outcome.note_block_shooter_pass(passer, urgency);
+        }
+    }
+    for passer in pass_order(seat) {
+        let cards = choices[passer.index()].1;
+        if external.policies[passer.index()].is_some() {
+            if external.accept_pass(controller, passer, cards) {
+                continue;
+            }
+            // A refused engine pass falls back to the bot, as in turn.
+            controller.set_bot_difficulty(external.fallback_for(passer, level));
+            if let Some(cards) = controller.simple_pass_for(passer) {
+                let _ = controller.submit_pass(passer, cards);
+            }
+        } else if let Some(cards) = cards {
+            let _ = controller.submit_pass(passer, cards);
+        }
+    }
+}
+
+fn fallback_label(fallback: ExternalFallback) -> &'static str {
+    match fallback {
+        ExternalFallback::Mix => "mix",
+        ExternalFallback::Difficulty(difficulty) => crate::outcome::agent_label(difficulty),
+    }
+}
+
+/// `seat`, then the others clockwise from North.
+fn pass_order(seat: PlayerPosition) -> impl Iterator<Item = PlayerPosition> {
+    let others = PlayerPosition::LOOP.into_iter().filter(move |&p| p != seat);
+    std::iter::once(seat).chain(others)
+}
+
+/// Seats of a `--match-mixed` run handed to outside engines with `--agent`.
+/// When an engine fails a decision, or answers with a card or pass the
+/// rules refuse, its fallback (by default the seat's mix difficulty) decides
+/// instead, and the miss is counted and reported with the engine's reply.
+#[derive(Default)]
+struct ExternalSeats {
+    policies: [Option<ExternalPolicy>; 4],
+    fallbacks: [u32; 4],
+}
+
+impl ExternalSeats {
+    fn attach(&mut self, seat: PlayerPosition, options: ExternalOptions) {
+        self.policies[seat.index()] = Some(ExternalPolicy::new(options));
+    }
+
+    fn any(&self) -> bool {
+        self.policies.iter().any(Option::is_some)
+    }
+
+    /// Starts every engine before the first hand. One that fails to start
+    /// is warned about and tried again at its first decision.
+    fn start(&mut self) {
+        for seat in PlayerPosition::LOOP {
+            if let Some(policy) = self.policies[seat.index()].as_mut()
+                && let Err(err) = policy.start()
+            {
+                eprintln!("warning: external agent {seat:?} did not start: {err}");
+            }
+        }
+    }
+
+    /// Tells every engine that the hand dealt from `seed` begins.
+    fn begin_hand(&mut self, seed: u64) {
+        for seat in PlayerPosition::LOOP {
+            if let Some(policy) = self.policies[seat.index()].as_mut()
+                && let Err(err) = policy.begin_hand(seat, seed)
+            {
+                eprintln!(
+                    "warning: external agent {seat:?} ({}) missed the reset for seed {seed}: {err}",
+                    policy.name()
+                );
+            }
+        }
+    }
+
+    fn shutdown(&mut self) {
+        for policy in self.policies.iter_mut().flatten() {
+            policy.shutdown();
+        }
+    }
+
+    /// Submits the engine's pass for `seat`; `false` if the seat has no
+    /// engine or its pass was not accepted.
+    fn submit_pass(
+        &mut self,
+        controller: &mut crate::controller::GameController,
+        seat: PlayerPosition,
+    ) -> bool {
+        let Some(policy) = self.policies[seat.index()].as_mut() else {
+            return false;
+        };
+        let hand = controller.round().hand(seat).clone();
+        let cards = policy.choose_pass(&hand, &controller.bot_context(seat));
+        self.accept_pass(controller, seat, cards)
+    }
+
+    /// Submits a pass the engine for `seat` already chose, counting a
+    /// fallback if there is none or it is refused.
+    fn accept_pass(
+        &mut self,
+        controller: &mut crate::controller::GameController,
+        seat: PlayerPosition,
+        cards: Option<hearts_core::model::passing::PassCards>,
+    ) -> bool {
+        let accepted = cards.is_some_and(|cards| controller.submit_pass(seat, cards).is_ok());
+        if !accepted {
+            self.miss(seat);
+        }
+        accepted
+    }
+
+    /// Plays the engine's card for `seat`, or `None` if the seat has no
+    /// engine or its card was not accepted.
+    fn play(
+        &mut self,
+        controller: &mut crate::controller::GameController,
+        seat: PlayerPosition,
+        legal: &[hearts_core::model::card::Card],
+    ) -> Option<hearts_core::model::card::Card> {
+        let policy = self.policies[seat.index()].as_mut()?;
+        let card = policy
+            .choose_play(legal, &controller.bot_context(seat))
+            .filter(|&card| controller.play(seat, card).is_ok());
+        if card.is_none() {
+            self.miss(seat);
+        }
+        card
+    }
+
+    /// Counts a decision the engine at `seat` lost to its fallback and warns
+    /// with the reply it sent, if any.
+    fn miss(&mut self, seat: PlayerPosition) {
+        self.fallbacks[seat.index()] += 1;
+        let Some(policy) = self.policies[seat.index()].as_ref() else {
+            return;
+        };
+        let reason = policy.last_error().map_or_else(
+            || "the game refused its answer".to_string(),
+            |e| e.to_string(),
+        );
+        let mut warning = format!(
+            "warning: external agent {seat:?} ({}) fell back to {}: {reason}",
+            policy.name(),
+            fallback_label(policy.fallback())
+        );
+        if let Some(reply) = policy.refused_reply() {
+            warning.push_str(&format!("; reply: {reply}"));
+        }
+        eprintln!("{warning}");
+    }
+
+    /// The difficulty that decides for `seat` when its engine does not, given
+    /// the seat's difficulty `mix`.
+    fn fallback_for(
+        &self,
+        seat: PlayerPosition,
+        mix: crate::bot::BotDifficulty,
+    ) -> crate::bot::BotDifficulty {
+        self.policies[seat.index()]
+            .as_ref()
+            .map_or(mix, |policy| policy.fallback().difficulty(mix))
+    }
+
+    fn meta(&self) -> serde_json::Value {
+        self.seats()
+            .map(|(seat, policy, fallbacks)| {
+                serde_json::json!({
+                    "seat": format!("{seat:?}"),
+                    "name": policy.name(),
+                    "protocol": policy.protocol_version(),
+                    "fallbacks": fallbacks,
+                    "fallback": fallback_label(policy.fallback()),
+                    "reconnects": policy.reconnects(),
+                    "respawns": policy.respawns(),
+                })
+            })
+            .collect()
+    }
+
+    fn report(&self) -> Vec<String> {
+        self.seats()
+            .map(|(seat, policy, fallbacks)| {
+                let mut line = format!(
+                    "External agent {seat:?}: {} fallbacks={fallbacks} reconnects={} respawns={}",
+                    policy.name(),
+                    policy.reconnects(),
+                    policy.respawns()
+                );
+                if let Some(err) = policy.last_error() {
+                    line.push_str(&format!(" last_error=\"{err}\""));
+                }
+                line
+            })
+            .collect()
+    }
+
+    fn seats(&self) -> impl Iterator<Item = (PlayerPosition, &ExternalPolicy, u32)> {
+        PlayerPosition::LOOP.into_iter().filter_map(|seat| {
+            self.policies[seat.index()]
+                .as_ref()
+                .map(|policy| (seat, policy, self.fallbacks[seat.index()]))
+        })
+    }
+}
+
+fn simulate_hand_outcome_with_agents(
+    seed: u64,
+    deal: Option<&Deal>,
+    seat: PlayerPosition,
+    diffs: [crate::bot::BotDifficulty; 4],
+    rules: RuleSet,
+    memo: &mut Option<crate::bot::DecisionMemo>,
+    external: &mut ExternalSeats,
+) -> Result<crate::outcome::HandOutcome, CliError> {
+    let mut match_state = MatchState::with_rules(PlayerPosition::North, seed, rules);
+    if let Some(deal) = deal {
+        pin_deal(&mut match_state, deal);
+    }
+    let mut controller = crate::controller::GameController::new_from_match_state(match_state);
+    controller.set_decision_memo(memo.take());
+    let mut outcome = crate::outcome::HandOutcome::dealt(seed, controller.round());
+    external.begin_hand(seed);
     if controller.in_passing_phase() {
-        controller.set_bot_difficulty(diffs[seat.index()]);
-        if let Some(cards) = controller.simple_pass_for(seat) {
-            let _ = controller.submit_pass(seat, cards);
+        // Outside engines answer side by side; the built-in planners pass
+        // faster than a thread starts.
+        if external.any() {
+            submit_passes_concurrently(&mut controller, external, seat, diffs, &mut outcome);
+        } else {
+            submit_passes_in_turn(&mut controller, external, seat, diffs, &mut outcome);
         }
-        let _ = controller.submit_auto_passes_for_others(seat);
         let _ = controller.resolve_passes();
     }
+    outcome.note_passes_resolved(controller.round());
     loop {
-        let totals = controller.penalties_this_round();
-        let sum: u32 = totals.iter().map(|&v| v as u32).sum();
-        if sum >= 26 {
-            break;
-        }
         let to_play = controller.expected_to_play();
-        controller.set_bot_difficulty(diffs[to_play.index()]);
-        if !matches!(
-            controller.autoplay_one_with_status(to_play.next()),
-            crate::controller::AutoplayOutcome::Played(_, _)
-        ) {
+        let trick = controller.round().current_trick().clone();
+        let legal = controller.legal_moves(to_play);
+        // Once every penalty is taken the rest of the hand is played out
+        // without the agents, so the trace still covers all 13 tricks.
+        if controller.is_round_decided() {
+            match controller.fast_forward_one() {
+                Some((played_by, card)) => outcome.note_auto_play(&trick, played_by, card, &legal),
+                None => break,
+            }
+            continue;
+        }
+        if let Some(card) = external.play(&mut controller, to_play, &legal) {
+            outcome.note_play(&trick, to_play, card, &legal);
+            continue;
+        }
+        controller.set_bot_difficulty(external.fallback_for(to_play, diffs[to_play.index()]));
+        match controller.autoplay_one_with_status(to_play.next()) {
+            crate::controller::AutoplayOutcome::Played(played_by, card) => {
+                outcome.note_play(&trick, played_by, card, &legal);
+            }
+            _ => break,
+        }
+    }
+    outcome.finish(controller.round());
+    *memo = controller.take_decision_memo();
+    Ok(outcome)
+}
+
+/// How one `--match-full` match went, hand by hand.
+struct FullMatchResult {
+    record: crate::outcome::MatchRecord,
+    /// One [`crate::outcome::MatchRow::Hand`] per round played.
+    hands: Vec<crate::outcome::MatchRow>,
+}
+
+/// Plays the match for `seed` to its end, each seat at its own difficulty.
+fn simulate_full_match(
+    seed: u64,
+    diffs: [crate::bot::BotDifficulty; 4],
+    rules: RuleSet,
+) -> FullMatchResult {
+    use crate::outcome::{HandOutcome, HandOutcomeRecord, MatchRecord, MatchRow};
+    use crate::session::{GameSession, SessionCommand, SessionEvent};
+    let controller = crate::controller::GameController::new_from_match_state(
+        MatchState::with_rules(PlayerPosition::North, seed, rules),
+    );
+    let mut session = GameSession::new(controller).with_difficulties(diffs);
+    let mut record = MatchRecord {
+        seed,
+        rounds: 0,
+        seating: diffs.map(crate::outcome::agent_label),
+        totals: [0; 4],
+        placements: [1; 4],
+        winner: None,
+        moons: 0,
+        suns: 0,
+        points: [0; 4],
+    };
+    let mut hands = Vec::new();
+    // Every round adds at least 16 points, so a match is over long before this.
+    while record.winner.is_none() && record.rounds < 4 * rules.target_score {
+        let mut outcome = HandOutcome::dealt(seed, session.controller().round());
+        if session.controller().in_passing_phase() {
+            let _ = session.apply(SessionCommand::BotPasses { except: None });
+            let _ = session.apply(SessionCommand::ResolvePasses);
+        }
+        outcome.note_passes_resolved(session.controller().round());
+        while !session.controller().round_ready_for_scoring() {
+            let controller = session.controller();
+            let trick = controller.round().current_trick().clone();
+            let legal = controller.legal_moves(controller.expected_to_play());
+            match session.apply(SessionCommand::BotTurn).as_deref() {
+                Ok([SessionEvent::CardPlayed { seat, card }, ..]) => {
+                    outcome.note_play(&trick, *seat, *card, &legal);
+                }
+                _ => break,
+            }
+        }
+        outcome.finish(session.controller().round());
+        let Ok(events) = session.apply(SessionCommand::FinishRound) else {
             break;
+        };
+        for event in events {
+            match event {
+                SessionEvent::RoundScored { round, penalties } => {
+                    record.rounds += 1;
+                    if rules.shoot_the_sun && outcome.sun_shooter.is_some() {
+                        record.suns += 1;
+                    } else if penalties.contains(&26) {
+                        record.moons += 1;
+                    }
+                    for seat in PlayerPosition::LOOP {
+                        record.points[seat.index()] += outcome.bench_points(seat, &rules);
+                    }
+                    hands.push(MatchRow::Hand {
+                        round,
+                        totals: session.controller().standings(),
+                        record: HandOutcomeRecord::new(outcome.clone(), 0, diffs),
+                    });
+                }
+                SessionEvent::MatchOver { winner } => record.winner = Some(winner),
+                _ => {}
+            }
         }
     }
-    let totals = controller.penalties_this_round();
-    Ok(totals[seat.index()])
+    record.totals = session.controller().standings();
+    record.placements = crate::outcome::placements(record.totals);
+    FullMatchResult { record, hands }
+}
+
+fn write_hard_telemetry(
+    path: std::path::PathBuf,
+    compression: crate::outputs::Compression,
+) -> Result<(), CliError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(CliError::Io)?;
+    }
+    let (path, summary) =
+        crate::telemetry::hard::export_compressed(Some(path), compression).map_err(CliError::Io)?;
+    println!(
+        "Telemetry written to {} (records: {})",
+        path.display(),
+        summary.record_count
+    );
+    Ok(())
+}
+
+/// `--hand-outcomes`: one JSON line per hand and seating, written as the
+/// hands are played.
+struct HandOutcomeLog {
+    writer: Option<crate::outputs::OutputWriter>,
+    rows: usize,
+}
+
+impl HandOutcomeLog {
+    fn create(
+        path: Option<std::path::PathBuf>,
+        compression: crate::outputs::Compression,
+    ) -> Result<Self, CliError> {
+        let writer = match path {
+            Some(path) => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(CliError::Io)?;
+                }
+                Some(compression.create(&path)?)
+            }
+            None => None,
+        };
+        Ok(Self { writer, rows: 0 })
+    }
+
+    fn write(
+        &mut self,
+        outcome: &crate::outcome::HandOutcome,
+        perm: usize,
+        seating: [crate::bot::BotDifficulty; 4],
+    ) -> Result<(), CliError> {
+        if self.writer.is_none() {
+            return Ok(());
+        }
+        let record = crate::outcome::HandOutcomeRecord::new(outcome.clone(), perm, seating);
+        self.write_line(&record)?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Writes a `--match-full` row; only hand rows count toward the total.
+    fn write_match_row(&mut self, row: &crate::outcome::MatchRow) -> Result<(), CliError> {
+        if self.write_line(row)? && matches!(row, crate::outcome::MatchRow::Hand { .. }) {
+            self.rows += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns whether there was a log to write to.
+    fn write_line<T: serde::Serialize>(&mut self, row: &T) -> Result<bool, CliError> {
+        use std::io::Write as _;
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(false);
+        };
+        writeln!(writer, "{}", serde_json::to_string(row)?)?;
+        Ok(true)
+    }
+
+    fn finish(&mut self) -> Result<(), CliError> {
+        if let Some(writer) = self.writer.take() {
+            let path = writer.finish()?;
+            println!("Wrote {} hand outcomes to {}", self.rows, path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the first round of `match_state` with `deal`, keeping its
+/// passing direction and pass count.
+fn pin_deal(match_state: &mut MatchState, deal: &Deal) {
+    let round = RoundState::from_deal(
+        deal,
+        match_state.passing_direction(),
+        match_state.rules().pass_count,
+    );
+    match_state.set_round(round);
+}
+
+/// Removes `--deal <hands>` from `rest` and parses it.
+fn take_deal_flag(rest: &mut Vec<String>) -> Result<Option<Deal>, CliError> {
+    let Some(idx) = rest.iter().position(|arg| arg == "--deal") else {
+        return Ok(None);
+    };
+    rest.remove(idx);
+    if idx >= rest.len() {
+        return Err(CliError::MissingArgument("--deal <hands>"));
+    }
+    let text = rest.remove(idx);
+    Deal::parse(&text)
+        .map(Some)
+        .map_err(|err| CliError::InvalidValue {
+            flag: "--deal",
+            value: err.to_string(),
+        })
+}
+
+#[derive(serde::Deserialize)]
+struct DealLine {
+    deal: String,
+}
+
+/// Reads `--deals-file`: one JSON object per line with a `deal` string in
+/// [`Deal`]'s text form. Blank lines are skipped.
+fn load_deals_file(path: &std::path::Path) -> Result<Vec<Deal>, CliError> {
+    let text = std::fs::read_to_string(path).map_err(CliError::Io)?;
+    let mut deals = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let bad = |detail: String| CliError::InvalidValue {
+            flag: "--deals-file",
+            value: format!("{}:{}: {detail}", path.display(), idx + 1),
+        };
+        let parsed: DealLine = serde_json::from_str(line).map_err(|err| bad(err.to_string()))?;
+        deals.push(Deal::parse(&parsed.deal).map_err(|err| bad(err.to_string()))?);
+    }
+    Ok(deals)
+}
+
+/// Plays up to [`bench_plan::CALIBRATION_HANDS`](crate::bench_plan::CALIBRATION_HANDS)
+/// of the run's first deals at the given seating and times them; `None` when
+/// the run has no deals.
+fn calibrate_match_mixed(
+    seed_start: u64,
+    count: u64,
+    seat: PlayerPosition,
+    diffs: [crate::bot::BotDifficulty; 4],
+    rules: RuleSet,
+    mix: &str,
+    permuted: bool,
+) -> Result<Option<crate::bench_plan::Calibration>, CliError> {
+    let hands = count.min(crate::bench_plan::CALIBRATION_HANDS);
+    if hands == 0 {
+        return Ok(None);
+    }
+    let mut csv_bytes = 0usize;
+    let mut outcome_bytes = 0usize;
+    let started = std::time::Instant::now();
+    for seed in seed_start..seed_start + hands {
+        let outcome = simulate_hand_outcome_mixed(seed, None, seat, diffs, rules, &mut None)?;
+        let pen = outcome.bench_points(seat, &rules);
+        // Rows are sized in the layout the run writes; under permutations
+        // the seat's penalty stands in for all four columns.
+        csv_bytes += if permuted {
+            format!("{seed}, 0, {mix}, {pen}, {pen}, {pen}, {pen}, 24\n").len()
+        } else {
+            format!("{}, {:?}, {}, {}\n", seed, seat, mix, pen).len()
+        };
+        let record = crate::outcome::HandOutcomeRecord::new(outcome, 0, diffs);
+        outcome_bytes += serde_json::to_string(&record)?.len() + 1;
+    }
+    Ok(Some(crate::bench_plan::Calibration {
+        hands,
+        secs: started.elapsed().as_secs_f64(),
+        csv_row_bytes: csv_bytes as f64 / hands as f64,
+        outcome_row_bytes: outcome_bytes as f64 / hands as f64,
+    }))
+}
+
+struct MixedPermutationRun {
+    seat: PlayerPosition,
+    seed_start: u64,
+    count: u64,
+    diffs: [crate::bot::BotDifficulty; 4],
+    mode: crate::permutations::PermutationMode,
+    progress_opts: BatchProgressOptions,
+    out_path: Option<std::path::PathBuf>,
+    summary_out: Option<std::path::PathBuf>,
+    /// Field shorthand metadata recorded in the summary.
+    field: Option<serde_json::Value>,
+    rules: RuleSet,
+    /// Pinned deals from `--deals-file`, one per seed.
+    deals: Option<Vec<Deal>>,
+    /// Workers playing deals at once.
+    threads: usize,
+    /// Resamples per paired comparison.
+    bootstrap: u32,
+    bootstrap_seed: u64,
+}
+
+/// One deal of a permutation run: its seatings and the outcome of each.
+type PermutedDeal = (
+    crate::permutations::DealPermutations,
+    Vec<crate::outcome::HandOutcome>,
+);
+
+/// Plays deal `index` of `run` under its seat assignments.
+fn play_permuted_deal(
+    run: &MixedPermutationRun,
+    index: u64,
+    memo: &mut Option<crate::bot::DecisionMemo>,
+) -> Result<PermutedDeal, CliError> {
+    let seed = run.seed_start + index;
+    let deal = run.deals.as_ref().map(|deals| &deals[index as usize]);
+    let mut outcomes = Vec::new();
+    let played = crate::permutations::play_deal(seed, run.diffs, run.mode, |perm| {
+        let outcome = simulate_hand_outcome_mixed(seed, deal, run.seat, perm, run.rules, memo)?;
+        let pen = PlayerPosition::LOOP.map(|seat| outcome.bench_points(seat, &run.rules));
+        outcomes.push(outcome);
+        Ok::<_, CliError>(pen)
+    })?;
+    Ok((played, outcomes))
+}
+
+/// `--match-mixed --permutations`: plays every deal under several seat
+/// assignments of the mix, one CSV row per assignment, and reports per-agent
+/// PPH as the mean of per-deal means.
+///
+/// Deals are played on `run.threads` workers, each with its own decision
+/// memo, and written back in deal order, so the CSV, the hand-outcome log
+/// and the PPH are the same for any thread count. `--max-duration` stops at
+/// the first deal boundary past the limit; workers finish the deals they
+/// hold and those are dropped.
+fn run_match_mixed_permutations(
+    run: MixedPermutationRun,
+    memo: &mut Option<crate::bot::DecisionMemo>,
+    hand_outcomes: &mut HandOutcomeLog,
+    ratings: &mut crate::rating::Ratings,
+) -> Result<(), CliError> {
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+    let mut rows = vec!["seed,perm,mix,pen_n,pen_e,pen_s,pen_w,permutations".to_string()];
+    let mut deals = Vec::new();
+    let mut progress = BatchProgress::new(run.count, run.progress_opts, WallClock::start());
+    let meter = crate::resource::Meter::start();
+    let next_deal = AtomicU64::new(0);
+    let stop = AtomicBool::new(run.count > 0 && progress.should_stop());
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let worker_memos = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..run.threads.max(1))
+            .map(|_| {
+                let sender = sender.clone();
+                let (run, next_deal, stop) = (&run, &next_deal, &stop);
+                let mut memo = memo.as_ref().map(|_| crate::bot::DecisionMemo::new());
+                scope.spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        let index = next_deal.fetch_add(1, Ordering::Relaxed);
+                        if index >= run.count {
+                            break;
+                        }
+                        let played = play_permuted_deal(run, index, &mut memo);
+                        if sender.send((index, played)).is_err() {
+                            break;
+                        }
+                    }
+                    memo
+                })
+            })
+            .collect();
+        drop(sender);
+
+        // Deals finish out of order; each is written once all before it are.
+        let mut pending = std::collections::BTreeMap::new();
+        let mut result = Ok(());
+        'written: while let Ok((index, played)) = receiver.recv() {
+            pending.insert(index, played);
+            while let Some(played) = pending.remove(&(deals.len() as u64)) {
+                let (deal, outcomes) = match played {
+                    Ok(played) => played,
+                    Err(err) => {
+                        result = Err(err);
+                        break 'written;
+                    }
+                };
+                for (idx, ((perm, pen), outcome)) in deal.played.iter().zip(&outcomes).enumerate() {
+                    if let Err(err) = hand_outcomes.write(outcome, idx, *perm) {
+                        result = Err(err);
+                        break 'written;
+                    }
+                    ratings.record(&perm.map(crate::agent_id::AgentId::of), pen.map(i64::from));
+                    rows.push(format!(
+                        "{}, {}, {}, {}, {}, {}, {}, {}",
+                        deal.seed,
+                        idx,
+                        crate::permutations::mix_code(*perm),
+                        pen[0],
+                        pen[1],
+                        pen[2],
+                        pen[3],
+                        deal.played.len()
+                    ));
+                }
+                deals.push(deal);
+                if let Some(line) = progress.record_hand(rows.len() - 1) {
+                    eprintln!("{line}");
+                }
+                if (deals.len() as u64) < run.count && progress.should_stop() {
+                    break 'written;
+                }
+            }
+        }
+        stop.store(true, Ordering::Relaxed);
+        drop(receiver);
+        let memos: Vec<_> = workers
+            .into_iter()
+            .map(|worker| worker.join().expect("match worker panicked"))
+            .collect();
+        result.map(|()| memos)
+    })?;
+    if let Some(memo) = memo.as_mut() {
+        for worker_memo in worker_memos.into_iter().flatten() {
+            memo.absorb(worker_memo);
+        }
+    }
+    let usage = meter.finish();
+    if let Some(path) = run.out_path {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        std::fs::write(&path, rows.join("\n")).map_err(CliError::Io)?;
+        println!("Wrote mixed-match CSV to {}", path.display());
+        let played: usize = deals.iter().map(|d| d.played.len()).sum();
+        write_run_meta(
+            &path,
+            "match-mixed",
+            serde_json::json!({
+                "seat": format!("{:?}", run.seat),
+                "mix": crate::permutations::mix_code(run.diffs),
+                "difficulties": run.diffs.map(crate::outcome::agent_label),
+                "field": run.field.clone(),
+                "seeds": { "start": run.seed_start, "count": run.count },
+                "hands": deals.len(),
+                "permutations": { "mode": run.mode.label(), "played": played },
+                "rules": run.rules,
+                "resources": usage,
+            }),
+        )?;
+    } else {
+        for line in rows {
+            println!("{}", line);
+        }
+    }
+    let played: usize = deals.iter().map(|d| d.played.len()).sum();
+    let available: usize = deals.iter().map(|d| d.available).sum();
+    let pph = crate::permutations::per_deal_pph(&deals);
+    println!(
+        "Per-agent PPH (mean of per-deal means; permutations={} played={}/{} deals={}):",
+        run.mode.label(),
+        played,
+        available,
+        deals.len()
+    );
+    for (agent, value) in &pph {
+        println!("  {agent}: {value:.2}");
+    }
+    let paired = crate::permutations::paired_comparisons(&deals, run.bootstrap, run.bootstrap_seed);
+    if !paired.is_empty() {
+        println!(
+            "Paired comparisons (right minus left per deal; bootstrap={} seed={}):",
+            run.bootstrap, run.bootstrap_seed
+        );
+        print!("{}", crate::permutations::paired_markdown(&paired));
+    }
+    if let Some(note) = progress.truncation_note() {
+        println!("{note}");
+    }
+    if let Some(memo) = memo.as_ref() {
+        println!("{}", memo_summary_line(memo));
+    }
+    println!("{}", usage.summary_line());
+    if let Some(path) = run.summary_out {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(CliError::Io)?;
+        }
+        let summary = serde_json::json!({
+            "mix": crate::permutations::mix_code(run.diffs),
+            "seed_start": run.seed_start,
+            "hands": deals.len(),
+            "permutations": {
+                "mode": run.mode.label(),
+                "played": played,
+                "available": available,
+            },
+            "field": run.field,
+            "rules": run.rules,
+            "resources": usage,
+            "agent_pph": pph
+                .iter()
+                .map(|(agent, value)| serde_json::json!({ "agent": agent, "pph": value }))
+                .collect::<Vec<_>>(),
+            "paired": {
+                "resamples": run.bootstrap,
+                "seed": run.bootstrap_seed,
+                "comparisons": paired,
+            },
+            "ratings": ratings.table(),
+        });
+        let json = serde_json::to_string_pretty(&summary).map_err(CliError::Json)?;
+        std::fs::write(&path, json).map_err(CliError::Io)?;
+        println!("Wrote summary to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Ratings for a run of `unit` results, carried on from `--ratings-prior`
+/// when given.
+fn start_ratings(
+    unit: &'static str,
+    prior: Option<&std::path::Path>,
+) -> Result<crate::rating::Ratings, CliError> {
+    let Some(path) = prior else {
+        return Ok(crate::rating::Ratings::new(unit));
+    };
+    let raw = std::fs::read_to_string(path).map_err(CliError::Io)?;
+    let priors = serde_json::from_str(&raw).map_err(CliError::Json)?;
+    crate::rating::Ratings::with_priors(unit, priors).map_err(|err| CliError::InvalidValue {
+        flag: "--ratings-prior",
+        value: err.to_string(),
+    })
+}
+
+/// Prints the ratings table and writes `--ratings-out`.
+fn finish_ratings(ratings: &crate::rating::Ratings, out: Option<PathBuf>) -> Result<(), CliError> {
+    println!();
+    print!("{}", crate::rating::ratings_markdown(&ratings.table()));
+    if let Some(path) = out {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(CliError::Io)?;
+        }
+        let json = serde_json::to_string_pretty(&ratings.to_file()).map_err(CliError::Json)?;
+        std::fs::write(&path, json).map_err(CliError::Io)?;
+        println!("Wrote ratings to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Writes the `--compare-runs` metadata sidecar for the CSV at `out`.
+fn write_run_meta(
+    out: &std::path::Path,
+    command: &str,
+    run: serde_json::Value,
+) -> Result<(), CliError> {
+    let path = crate::run_meta::sidecar_path(out);
+    let meta = crate::run_meta::collect(command, run);
+    let json = serde_json::to_string_pretty(&meta).map_err(CliError::Json)?;
+    std::fs::write(&path, json).map_err(CliError::Io)?;
+    println!("Wrote run metadata to {}", path.display());
+    Ok(())
+}
+
+fn memo_summary_line(memo: &crate::bot::DecisionMemo) -> String {
+    let stats = memo.stats();
+    let rate = stats
+        .hit_rate()
+        .map_or_else(|| "-".to_string(), |r| format!("{:.1}%", r * 100.0));
+    format!(
+        "Decision memo: hits={} misses={} hit_rate={rate} entries={}",
+        stats.hits,
+        stats.misses,
+        memo.entry_count()
+    )
+}
+
+/// Elapsed-time source for batch runs; tests substitute a fake clock.
+trait BatchClock {
+    fn elapsed(&self) -> std::time::Duration;
+}
+
+struct WallClock(std::time::Instant);
+
+impl WallClock {
+    fn start() -> Self {
+        WallClock(std::time::Instant::now())
+    }
+}
+
+impl BatchClock for WallClock {
+    fn elapsed(&self) -> std::time::Duration {
+        self.0.elapsed()
+    }
+}
+
+/// `--progress` / `--max-duration` settings shared by the batch commands.
+#[derive(Debug, Clone, Copy)]
+struct BatchProgressOptions {
+    enabled: bool,
+    every: u64,
+    max_duration: Option<std::time::Duration>,
+}
+
+impl Default for BatchProgressOptions {
+    fn default() -> Self {
+        use std::io::IsTerminal;
+        Self {
+            enabled: std::io::stderr().is_terminal(),
+            every: 10,
+            max_duration: None,
+        }
+    }
+}
+
+impl BatchProgressOptions {
+    fn parse_flag(
+        &mut self,
+        flag: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<(), CliError> {
+        match flag {
+            "--progress" => self.enabled = true,
+            "--max-duration" => {
+                let raw = args
+                    .next()
+                    .ok_or(CliError::MissingArgument("--max-duration <secs>"))?;
+                self.max_duration =
+                    Some(parse_duration_secs(&raw).ok_or(CliError::InvalidValue {
+                        flag: "--max-duration",
+                        value: raw.clone(),
+                    })?);
+            }
+            other => return Err(CliError::UnknownCommand(other.to_string())),
+        }
+        Ok(())
+    }
+}
+
+/// `--compress` / `--keep-last` settings shared by the batch commands.
+#[derive(Debug, Clone, Copy, Default)]
+struct BatchOutputOptions {
+    compress: crate::outputs::Compression,
+    keep_last: Option<usize>,
+}
+
+impl BatchOutputOptions {
+    fn parse_flag(
+        &mut self,
+        flag: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<(), CliError> {
+        match flag {
+            "--compress" => {
+                let raw = args
+                    .next()
+                    .ok_or(CliError::MissingArgument("--compress <zstd|gzip|none>"))?;
+                self.compress =
+                    crate::outputs::Compression::parse(&raw).ok_or(CliError::InvalidValue {
+                        flag: "--compress",
+                        value: raw,
+                    })?;
+            }
+            "--keep-last" => {
+                let raw = args
+                    .next()
+                    .ok_or(CliError::MissingArgument("--keep-last <n>"))?;
+                self.keep_last = Some(raw.parse::<usize>().ok().filter(|n| *n > 0).ok_or(
+                    CliError::InvalidValue {
+                        flag: "--keep-last",
+                        value: raw,
+                    },
+                )?);
+            }
+            other => return Err(CliError::UnknownCommand(other.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Fails before the run starts when `--keep-last` has no run directory
+    /// in the raw `--out` template to prune.
+    fn check_retention(&self, out_template: Option<&str>) -> Result<(), CliError> {
+        if self.keep_last.is_none() {
+            return Ok(());
+        }
+        match out_template {
+            Some(template) if crate::outputs::names_run_directory(template) => Ok(()),
+            Some(template) => Err(CliError::InvalidValue {
+                flag: "--keep-last",
+                value: template.to_string(),
+            }),
+            None => Err(CliError::MissingArgument(
+                "--keep-last requires --out with a ${run_id} directory",
+            )),
+        }
+    }
+
+    /// Prunes older run directories once the run has completed.
+    fn apply_retention(
+        &self,
+        out_template: Option<&str>,
+        vars: &crate::paths::PathVars,
+    ) -> Result<(), CliError> {
+        let (Some(keep_last), Some(template)) = (self.keep_last, out_template) else {
+            return Ok(());
+        };
+        let removed = crate::outputs::prune_runs(template, vars, keep_last)?;
+        if !removed.is_empty() {
+            println!(
+                "Pruned {} older run director{} (keep-last={keep_last})",
+                removed.len(),
+                if removed.len() == 1 { "y" } else { "ies" }
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Accepts plain seconds or a `s`/`m`/`h` suffix (e.g. `90`, `90s`, `15m`).
+fn parse_duration_secs(raw: &str) -> Option<std::time::Duration> {
+    let raw = raw.trim().to_ascii_lowercase();
+    let (digits, scale) = match raw.chars().last()? {
+        's' => (&raw[..raw.len() - 1], 1),
+        'm' => (&raw[..raw.len() - 1], 60),
+        'h' => (&raw[..raw.len() - 1], 3600),
+        _ => (raw.as_str(), 1),
+    };
+    let value = digits.parse::<u64>().ok()?;
+    Some(std::time::Duration::from_secs(value.checked_mul(scale)?))
+}
+
+/// Tracks hands completed in a batch run, emits progress lines, and stops the
+/// run at a hand boundary once `--max-duration` has elapsed.
+struct BatchProgress<C: BatchClock> {
+    total: u64,
+    done: u64,
+    rows: usize,
+    options: BatchProgressOptions,
+    truncated: bool,
+    clock: C,
+}
+
+impl<C: BatchClock> BatchProgress<C> {
+    fn new(total: u64, options: BatchProgressOptions, clock: C) -> Self {
+        Self {
+            total,
+            done: 0,
+            rows: 0,
+            options,
+            truncated: false,
+            clock,
+        }
+    }
+
+    fn hands_done(&self) -> u64 {
+        self.done
+    }
+
+    fn should_stop(&mut self) -> bool {
+        if let Some(limit) = self.options.max_duration
+            && self.clock.elapsed() >= limit
+        {
+            self.truncated = true;
+        }
+        self.truncated
+    }
+
+    fn record_hand(&mut self, rows_written: usize) -> Option<String> {
+        self.done += 1;
+        self.rows = rows_written;
+        let every = self.options.every.max(1);
+        if !self.options.enabled || (!self.done.is_multiple_of(every) && self.done != self.total) {
+            return None;
+        }
+        Some(self.progress_line())
+    }
+
+    fn progress_line(&self) -> String {
+        let elapsed = self.clock.elapsed().as_secs_f64();
+        let per_min = if elapsed > 0.0 {
+            self.done as f64 * 60.0 / elapsed
+        } else {
+            0.0
+        };
+        let remaining = self.total.saturating_sub(self.done);
+        let eta = if self.done > 0 {
+            format!("{:.0}s", elapsed / self.done as f64 * remaining as f64)
+        } else {
+            "?".to_string()
+        };
+        format!(
+            "progress: {}/{} hands, {} rows, elapsed {:.1}s, {:.1} hands/min, ETA {}",
+            self.done, self.total, self.rows, elapsed, per_min, eta
+        )
+    }
+
+    fn truncation_note(&self) -> Option<String> {
+        self.truncated.then(|| {
+            format!(
+                "Run truncated by --max-duration after {} of {} hands",
+                self.done, self.total
+            )
+        })
+    }
+}
+
+/// Deal-strength reference: the points an all-Normal field leaves `seat` with on a deal.
+/// The seed fixes the deal, so each (seed, seat) pair is only played once per run.
+struct DealBaselineCache {
+    rules: RuleSet,
+    penalties: std::collections::HashMap<(u64, PlayerPosition), i32>,
+}
+
+impl DealBaselineCache {
+    fn new(rules: RuleSet) -> Self {
+        Self {
+            rules,
+            penalties: std::collections::HashMap::new(),
+        }
+    }
+
+    fn penalties_for(
+        &mut self,
+        seed: u64,
+        deal: Option<&Deal>,
+        seat: PlayerPosition,
+        memo: &mut Option<crate::bot::DecisionMemo>,
+    ) -> Result<i32, CliError> {
+        if let Some(&pen) = self.penalties.get(&(seed, seat)) {
+            return Ok(pen);
+        }
+        let reference = [crate::bot::BotDifficulty::NormalHeuristic; 4];
+        let pen = simulate_one_round_mixed(seed, deal, seat, reference, self.rules, memo)?;
+        self.penalties.insert((seed, seat), pen);
+        Ok(pen)
+    }
 }
 
 fn format_nudge_trace_summary(trace: Option<&Vec<(String, usize)>>) -> String {
@@ -2198,3 +4966,252 @@ fn hard_flags_summary() -> Option<String> {
         Some(items.join(" "))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    #[derive(Clone, Default)]
+    struct FakeClock(Rc<Cell<u64>>);
+
+    impl FakeClock {
+        fn advance_secs(&self, secs: u64) {
+            self.0.set(self.0.get() + secs);
+        }
+    }
+
+    impl BatchClock for FakeClock {
+        fn elapsed(&self) -> Duration {
+            Duration::from_secs(self.0.get())
+        }
+    }
+
+    #[test]
+    fn progress_reports_throughput_and_eta() {
+        let clock = FakeClock::default();
+        let options = BatchProgressOptions {
+            enabled: true,
+            every: 2,
+            max_duration: None,
+        };
+        let mut progress = BatchProgress::new(4, options, clock.clone());
+        clock.advance_secs(30);
+        assert_eq!(progress.record_hand(1), None);
+        clock.advance_secs(30);
+        let line = progress.record_hand(2).unwrap();
+        assert_eq!(
+            line,
+            "progress: 2/4 hands, 2 rows, elapsed 60.0s, 2.0 hands/min, ETA 60s"
+        );
+        assert!(progress.truncation_note().is_none());
+    }
+
+    #[test]
+    fn max_duration_stops_at_hand_boundary() {
+        let clock = FakeClock::default();
+        let options = BatchProgressOptions {
+            enabled: false,
+            every: 10,
+            max_duration: Some(Duration::from_secs(90)),
+        };
+        let mut progress = BatchProgress::new(10, options, clock.clone());
+        let mut played = 0;
+        while !progress.should_stop() {
+            clock.advance_secs(40);
+            assert_eq!(progress.record_hand(played + 1), None);
+            played += 1;
+        }
+        assert_eq!(played, 3);
+        assert_eq!(progress.hands_done(), 3);
+        assert_eq!(
+            progress.truncation_note().as_deref(),
+            Some("Run truncated by --max-duration after 3 of 10 hands")
+        );
+    }
+
+    #[test]
+    fn a_full_match_plays_to_its_target() {
+        let diffs = [crate::bot::BotDifficulty::NormalHeuristic; 4];
+        let rules = RuleSet::default().with_target_score(30).unwrap();
+        let FullMatchResult {
+            record: result,
+            hands,
+        } = simulate_full_match(2245, diffs, rules);
+        assert_eq!(hands.len(), result.rounds as usize);
+        let winner = result.winner.expect("the match ends");
+        assert!(result.totals.iter().any(|&total| total >= 30));
+        assert!(result.rounds >= 2, "{} rounds", result.rounds);
+        let lowest = result.totals.iter().copied().min().unwrap();
+        assert_eq!(result.totals[winner.index()], lowest);
+        assert_eq!(result.placements[winner.index()], 1);
+    }
+
+    #[test]
+    fn parse_duration_accepts_suffixes() {
+        assert_eq!(parse_duration_secs("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration_secs("15m"), Some(Duration::from_secs(900)));
+        assert_eq!(parse_duration_secs("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration_secs("soon"), None);
+    }
+
+    #[test]
+    fn match_mixed_hands_a_seat_to_a_websocket_agent() {
+        use crate::bot::policy::test_agent::{Agent, serve};
+        // The test agent passes its first cards and plays its first legal
+        // card, as Easy does, so an all-Easy table scores the same with West
+        // handed to it.
+        let server = serve(Agent::Plain, 1);
+        let dir = std::env::temp_dir().join(format!("mdh_ws_agent_{}", std::process::id()));
+        let run = |name: &str, agent: Option<&str>| {
+            let out = dir.join(name);
+            let mut args: Vec<String> = ["--match-mixed", "south", "40", "3", "eeee", "--out"]
+                .map(String::from)
+                .to_vec();
+            args.push(out.to_string_lossy().into_owned());
+            if let Some(url) = agent {
+                args.extend([
+                    "--agent".to_string(),
+                    "west".to_string(),
+                    format!("transport=websocket,url={url},timeout_ms=2000"),
+                ]);
+            }
+            assert!(matches!(
+                run_cli_with_args(args.into_iter()),
+                Ok(CliOutcome::Handled)
+            ));
+            let meta = std::fs::read_to_string(crate::run_meta::sidecar_path(&out)).unwrap();
+            let meta: serde_json::Value = serde_json::from_str(&meta).unwrap();
+            (std::fs::read_to_string(&out).unwrap(), meta)
+        };
+        let (local, _) = run("local.csv", None);
+        let (remote, meta) = run("remote.csv", Some(&server.url));
+        assert_eq!(remote, local);
+        let agents = &meta["external_agents"];
+        assert_eq!(agents[0]["seat"], "West");
+        assert_eq!(agents[0]["name"], "ws-echo");
+        assert_eq!(agents[0]["fallbacks"], 0);
+        // Three passes and at least one card a hand.
+        let requests = server.handle.join().unwrap();
+        assert!(requests >= 6, "{requests} requests");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn an_agent_answering_illegally_hands_every_decision_to_its_fallback() {
+        use crate::bot::policy::test_agent::{Agent, serve};
+        let server = serve(Agent::Illegal, 1);
+        let dir = std::env::temp_dir().join(format!("mdh_illegal_agent_{}", std::process::id()));
+        let out = dir.join("illegal.csv");
+        let mut args: Vec<String> = ["--match-mixed", "south", "40", "2", "nnnn", "--out"]
+            .map(String::from)
+            .to_vec();
+        args.push(out.to_string_lossy().into_owned());
+        args.extend([
+            "--agent".to_string(),
+            "west".to_string(),
+            format!("transport=websocket,url={},fallback=easy", server.url),
+        ]);
+        assert!(matches!(
+            run_cli_with_args(args.into_iter()),
+            Ok(CliOutcome::Handled)
+        ));
+        let meta = std::fs::read_to_string(crate::run_meta::sidecar_path(&out)).unwrap();
+        let meta: serde_json::Value = serde_json::from_str(&meta).unwrap();
+        let agent = &meta["external_agents"][0];
+        assert_eq!(agent["fallback"], "easy");
+        // Every request was refused: each pass and card fell back.
+        let requests = server.handle.join().unwrap() as u64;
+        assert_eq!(agent["fallbacks"].as_u64().unwrap(), requests);
+        assert!(requests >= 4, "{requests} requests");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Passes `seed`'s deal one way or the other and returns the hands
+    /// after the passes resolve, with the guard pressure noted.
+    fn pass_deal(
+        seed: u64,
+        seat: PlayerPosition,
+        diffs: [crate::bot::BotDifficulty; 4],
+        external: &mut ExternalSeats,
+        concurrently: bool,
+    ) -> (Vec<String>, [Option<u8>; 4]) {
+        let match_state = MatchState::with_rules(PlayerPosition::North, seed, RuleSet::default());
+        let mut controller = crate::controller::GameController::new_from_match_state(match_state);
+        let mut outcome = crate::outcome::HandOutcome::dealt(seed, controller.round());
+        assert!(controller.in_passing_phase());
+        if concurrently {
+            submit_passes_concurrently(&mut controller, external, seat, diffs, &mut outcome);
+        } else {
+            submit_passes_in_turn(&mut controller, external, seat, diffs, &mut outcome);
+        }
+        controller.resolve_passes().unwrap();
+        let hands = PlayerPosition::LOOP
+            .iter()
+            .map(|&p| controller.round().hand(p).to_string())
+            .collect();
+        (hands, outcome.block_shooter_passes)
+    }
+
+    #[test]
+    fn concurrent_passes_match_passing_in_turn() {
+        use crate::bot::BotDifficulty::*;
+        let mixes = [
+            [EasyLegacy; 4],
+            [NormalHeuristic; 4],
+            [FutureHard, EasyLegacy, NormalHeuristic, SearchLookahead],
+            [NormalHeuristic, FutureHard, EasyLegacy, FutureHard],
+        ];
+        for seed in 0..12u64 {
+            for (i, diffs) in mixes.iter().enumerate() {
+                let seat = PlayerPosition::LOOP[(seed as usize + i) % 4];
+                let mut none = ExternalSeats::default();
+                assert_eq!(
+                    pass_deal(seed, seat, *diffs, &mut none, true),
+                    pass_deal(seed, seat, *diffs, &mut none, false),
+                    "seed {seed} mix {i}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn slow_agents_are_asked_for_their_passes_side_by_side() {
+        use crate::bot::policy::test_agent::{Agent, InFlight, serve_gauged};
+        use std::sync::Arc;
+        let delay = Duration::from_millis(200);
+        let diffs = [crate::bot::BotDifficulty::EasyLegacy; 4];
+        // The most passes the four agents were answering at once.
+        let pass = |concurrently: bool| {
+            let in_flight = Arc::new(InFlight::default());
+            let servers: Vec<_> = (0..4)
+                .map(|_| serve_gauged(Agent::SlowPass(delay), 1, Arc::clone(&in_flight)))
+                .collect();
+            let mut external = ExternalSeats::default();
+            for (seat, server) in PlayerPosition::LOOP.into_iter().zip(&servers) {
+                let spec = format!("transport=websocket,url={},timeout_ms=5000", server.url);
+                external.attach(seat, ExternalOptions::parse(&spec).unwrap());
+            }
+            let passed = pass_deal(7, PlayerPosition::South, diffs, &mut external, concurrently);
+            assert_eq!(external.fallbacks, [0; 4]);
+            drop(external);
+            for server in servers {
+                assert_eq!(server.handle.join().unwrap(), 1);
+            }
+            (passed, in_flight.peak())
+        };
+        let (in_turn, sequential_peak) = pass(false);
+        let (side_by_side, concurrent_peak) = pass(true);
+        assert_eq!(side_by_side, in_turn);
+        // The agents pass their first cards, as Easy does.
+        let mut none = ExternalSeats::default();
+        assert_eq!(
+            pass_deal(7, PlayerPosition::South, diffs, &mut none, false),
+            in_turn
+        );
+        assert_eq!(sequential_peak, 1);
+        assert!(concurrent_peak > 1, "peak {concurrent_peak}");
+    }
+}
@@ -1,16 +1,26 @@
 #![cfg_attr(not(windows), allow(dead_code))]
 
+use crate::assist::MasterySummary;
 use crate::bot::MoonState;
 use crate::bot::{
-    BotContext, BotDifficulty, DecisionLimit, PassPlanner, PlayPlanner, UnseenTracker,
+    BotContext, BotDifficulty, DecisionLimit, DecisionMemo, PassPlanner, PlayPlanner,
+    UnseenTracker, WeightSet,
 };
+use crate::challenge::Challenge;
 use crate::debug::debug_enabled;
+use crate::identity::PlayerRoster;
+use crate::recording::{HumanPlayRecord, HumanRecorder};
+use crate::review::{ReviewConfig, RoundReview};
+use crate::saved_game::RoundLog;
 use hearts_core::game::match_state::MatchState;
+use hearts_core::game::serialization::RoundSnapshot;
 use hearts_core::model::card::Card;
-use hearts_core::model::passing::PassingDirection;
+use hearts_core::model::hand::Hand;
+use hearts_core::model::passing::{PassCards, PassingDirection};
 use hearts_core::model::player::PlayerPosition;
 use hearts_core::model::rank::Rank;
 use hearts_core::model::round::{PlayError, PlayOutcome, RoundPhase, RoundState};
+use hearts_core::model::rules_text::RulesText;
 use hearts_core::model::score::ScoreBoard;
 use hearts_core::model::suit::Suit;
 use std::time::{Duration, Instant};
@@ -103,6 +113,153 @@ impl ThinkConfig {
     }
 }
 
+/// Shortest time the completed trick stays on the table before it is swept,
+/// so the fourth card is always visible, even in fast mode.
+pub const MIN_TRICK_VIEW_MS: u64 = 250;
+
+/// How long a bot appears to think before its card is played.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ThinkDisplay {
+    /// Play as soon as the planner answers.
+    #[default]
+    Instant,
+    /// Scale a short pause with the number of legal cards.
+    Realistic,
+    /// Always wait at least this many milliseconds.
+    Fixed(u64),
+}
+
+impl ThinkDisplay {
+    pub fn from_env_value(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "none" | "instant" | "off" => Some(ThinkDisplay::Instant),
+            "realistic" => Some(ThinkDisplay::Realistic),
+            other => other.parse::<u64>().ok().map(ThinkDisplay::Fixed),
+        }
+    }
+}
+
+/// Where a completed trick is in its on-table lifetime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CollectStage {
+    /// All four cards shown; waiting for the delay or a click.
+    Showing,
+    /// Sweeping to the winner; progress in `0.0..1.0`.
+    Sweeping(f32),
+    Done,
+}
+
+/// Game pacing timings shared by the GUI turn loop. All durations are in
+/// milliseconds and every decision takes elapsed time from the caller, so the
+/// schedule can be driven by a fake clock in tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PacingConfig {
+    pub think_display: ThinkDisplay,
+    pub play_anim_ms: u64,
+    /// How long the completed trick stays up before it is swept away.
+    pub trick_clear_ms: u64,
+    pub collect_anim_ms: u64,
+    /// Sweep completed tricks automatically; otherwise wait for a click.
+    pub auto_collect: bool,
+    pub pass_out_ms: u64,
+    pub pass_pause_ms: u64,
+    pub pass_in_ms: u64,
+    pub pass_stagger_ms: u64,
+}
+
+impl Default for PacingConfig {
+    fn default() -> Self {
+        Self {
+            think_display: ThinkDisplay::Instant,
+            play_anim_ms: 260,
+            trick_clear_ms: 350,
+            collect_anim_ms: 320,
+            auto_collect: true,
+            pass_out_ms: 320,
+            pass_pause_ms: 220,
+            pass_in_ms: 320,
+            pass_stagger_ms: 60,
+        }
+    }
+}
+
+impl PacingConfig {
+    /// Everything as quick as it can go while still showing the full trick.
+    pub fn fast() -> Self {
+        Self {
+            think_display: ThinkDisplay::Instant,
+            play_anim_ms: 120,
+            trick_clear_ms: MIN_TRICK_VIEW_MS,
+            collect_anim_ms: 150,
+            auto_collect: true,
+            pass_out_ms: 160,
+            pass_pause_ms: 80,
+            pass_in_ms: 160,
+            pass_stagger_ms: 30,
+        }
+    }
+
+    pub fn is_fast(&self) -> bool {
+        *self == Self::fast()
+    }
+
+    pub fn from_env() -> Self {
+        let mut cfg = match std::env::var("MDH_PACING") {
+            Ok(raw) if raw.trim().eq_ignore_ascii_case("fast") => Self::fast(),
+            _ => Self::default(),
+        };
+        if let Ok(raw) = std::env::var("MDH_THINK_DISPLAY")
+            && let Some(display) = ThinkDisplay::from_env_value(&raw)
+        {
+            cfg.think_display = display;
+        }
+        if let Some(ms) = parse_env_u32("MDH_TRICK_CLEAR_MS") {
+            cfg.trick_clear_ms = ms as u64;
+        }
+        if let Some(auto) = parse_env_bool("MDH_AUTO_COLLECT") {
+            cfg.auto_collect = auto;
+        }
+        cfg.sanitized()
+    }
+
+    /// Clamps the trick-clear delay up to `MIN_TRICK_VIEW_MS`.
+    pub fn sanitized(mut self) -> Self {
+        self.trick_clear_ms = self.trick_clear_ms.max(MIN_TRICK_VIEW_MS);
+        self
+    }
+
+    /// Minimum time a bot with `legal_count` choices appears to think.
+    pub fn min_bot_think_ms(&self, legal_count: usize) -> u64 {
+        match self.think_display {
+            ThinkDisplay::Instant => 0,
+            ThinkDisplay::Fixed(ms) => ms,
+            ThinkDisplay::Realistic if legal_count <= 1 => 300,
+            ThinkDisplay::Realistic => (450 + 120 * (legal_count as u64 - 1)).min(1_500),
+        }
+    }
+
+    /// Stage of a completed trick shown for `elapsed_ms`; `clicked_at_ms` is
+    /// when the player clicked to collect it, if they have. The sweep never
+    /// starts before `MIN_TRICK_VIEW_MS`.
+    pub fn collect_stage(&self, elapsed_ms: u64, clicked_at_ms: Option<u64>) -> CollectStage {
+        let sweep_from = if self.auto_collect {
+            Some(self.trick_clear_ms)
+        } else {
+            clicked_at_ms
+        }
+        .map(|ms| ms.max(MIN_TRICK_VIEW_MS));
+        let Some(sweep_from) = sweep_from.filter(|&from| elapsed_ms >= from) else {
+            return CollectStage::Showing;
+        };
+        let moving = elapsed_ms - sweep_from;
+        if moving >= self.collect_anim_ms {
+            CollectStage::Done
+        } else {
+            CollectStage::Sweeping(moving as f32 / self.collect_anim_ms.max(1) as f32)
+        }
+    }
+}
+
 fn test_force_autoplay_timeout() -> bool {
     std::env::var("MDH_TEST_FORCE_AUTOP_TIMEOUT")
         .map(|v| {
@@ -118,6 +275,8 @@ pub struct BotSnapshot {
     scores: ScoreBoard,
     passing_direction: PassingDirection,
     tracker: UnseenTracker,
+    target_score: u32,
+    jack_of_diamonds: bool,
 }
 
 impl BotSnapshot {
@@ -127,6 +286,8 @@ impl BotSnapshot {
             scores: *match_state.scores(),
             passing_direction: match_state.passing_direction(),
             tracker: tracker.clone(),
+            target_score: match_state.target_score(),
+            jack_of_diamonds: match_state.rules().jack_of_diamonds,
         }
     }
 
@@ -146,6 +307,8 @@ impl BotSnapshot {
             difficulty,
         )
         .with_controller_bias_delta(bias_delta)
+        .with_target_score(self.target_score)
+        .with_jack_of_diamonds(self.jack_of_diamonds)
     }
 
     pub fn tracker(&self) -> &UnseenTracker {
@@ -160,6 +323,8 @@ pub struct BotThinkRequest {
     pub difficulty: BotDifficulty,
     pub snapshot: BotSnapshot,
     pub config: ThinkConfig,
+    /// Challenge-mode weights for this seat, if any.
+    pub weights: Option<WeightSet>,
 }
 
 #[derive(Debug)]
@@ -168,6 +333,8 @@ pub struct BotThinkResult {
     pub chosen: Option<Card>,
     pub elapsed: Duration,
     pub timed_out: bool,
+    /// The search missed its deadline and `chosen` is the heuristic pick.
+    pub fell_back: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -181,9 +348,30 @@ pub enum AutoplayOutcome {
 pub struct GameController {
     match_state: MatchState,
     last_trick: Option<TrickSummary>,
+    pass_events: Vec<PassEvent>,
+    // Opt-in cache of bot plays for batch runs; see `bot::memo`.
+    decision_memo: Option<DecisionMemo>,
+    // Cards each seat received this round, tagged with the round number.
+    received: Option<(u32, [Option<PassCards>; 4])>,
+    // Search decisions that fell back to the heuristic pick, with the round.
+    search_fallbacks: (u32, u32),
     bot_difficulty: BotDifficulty,
     unseen_tracker: UnseenTracker,
     think_config: ThinkConfig,
+    pacing: PacingConfig,
+    review_config: ReviewConfig,
+    // Full deal and plays for the round in progress, never exposed until the
+    // round is over.
+    round_record: Option<RecordedRound>,
+    // Every round of the match scored so far, for the replay viewer.
+    played_rounds: Vec<RecordedRound>,
+    pending_review: Option<RoundReview>,
+    challenge: Option<Challenge>,
+    // The human's plays, when they opted in to recording them.
+    human_recorder: Option<HumanRecorder>,
+    // The round as dealt and every step since, for saving the game.
+    round_log: RoundLog,
+    players: PlayerRoster,
 }
 
 impl GameController {
@@ -213,12 +401,26 @@ impl GameController {
         let mut unseen_tracker = UnseenTracker::new();
         unseen_tracker.reset_for_round(match_state.round());
         crate::telemetry::hard::reset();
+        let round_log = RoundLog::new(&match_state);
         let this = Self {
             match_state,
             last_trick: None,
+            pass_events: Vec::new(),
+            decision_memo: None,
+            received: None,
+            search_fallbacks: (0, 0),
             bot_difficulty: BotDifficulty::from_env(),
             unseen_tracker,
             think_config: ThinkConfig::from_env(),
+            pacing: PacingConfig::from_env(),
+            review_config: ReviewConfig::from_env(),
+            round_record: None,
+            played_rounds: Vec::new(),
+            pending_review: None,
+            challenge: Self::challenge_from_env(),
+            human_recorder: None,
+            round_log,
+            players: PlayerRoster::default(),
         };
         Self::dbg(&format!(
             "mdhearts: AI weights {} | hard {} | moon {}",
@@ -226,19 +428,34 @@ impl GameController {
             crate::bot::search::debug_hard_weights_string(),
             debug_moon_config_string()
         ));
+        this.log_round_start();
         this
     }
 
     pub fn new_from_match_state(match_state: hearts_core::game::match_state::MatchState) -> Self {
         let mut unseen_tracker = UnseenTracker::new();
-        unseen_tracker.reset_for_round(match_state.round());
+        unseen_tracker.rebuild_for_round(match_state.round());
         crate::telemetry::hard::reset();
+        let round_log = RoundLog::new(&match_state);
         let this = Self {
             match_state,
             last_trick: None,
+            pass_events: Vec::new(),
+            decision_memo: None,
+            received: None,
+            search_fallbacks: (0, 0),
             bot_difficulty: BotDifficulty::from_env(),
             unseen_tracker,
             think_config: ThinkConfig::from_env(),
+            pacing: PacingConfig::from_env(),
+            review_config: ReviewConfig::from_env(),
+            round_record: None,
+            played_rounds: Vec::new(),
+            pending_review: None,
+            challenge: Self::challenge_from_env(),
+            human_recorder: None,
+            round_log,
+            players: PlayerRoster::default(),
         };
         Self::dbg(&format!(
             "mdhearts: AI weights {} | hard {} | moon {}",
@@ -246,9 +463,51 @@ impl GameController {
             crate::bot::search::debug_hard_weights_string(),
             debug_moon_config_string()
         ));
+        this.log_round_start();
         this
     }
 
+    pub fn seed(&self) -> u64 {
+        self.match_state.seed()
+    }
+
+    /// Reproduction line for the about/debug surfaces; `mdhearts --seed N` replays the deals.
+    pub fn seed_summary(&self) -> String {
+        format!(
+            "Match seed {} \u{00b7} round {} (replay with --seed {})",
+            self.match_state.seed(),
+            self.match_state.round_number(),
+            self.match_state.seed()
+        )
+    }
+
+    fn log_round_start(&self) {
+        Self::dbg(&format!(
+            "mdhearts: round {} seed {} passing {} starting {:?}",
+            self.match_state.round_number(),
+            self.match_state.seed(),
+            self.match_state.passing_direction().as_str(),
+            self.match_state.round().starting_player()
+        ));
+        let memory = self.unseen_tracker.match_memory();
+        if memory.rounds_played() > 0 {
+            let seats: Vec<String> = PlayerPosition::LOOP
+                .iter()
+                .map(|&seat| {
+                    format!(
+                        "{:?} won {} lost {} moons {} place {}",
+                        seat,
+                        memory.rounds_won(seat),
+                        memory.rounds_lost(seat),
+                        memory.moons_suffered(seat),
+                        memory.placement(seat)
+                    )
+                })
+                .collect();
+            Self::dbg(&format!("mdhearts: match memory {}", seats.join(" | ")));
+        }
+    }
+
     pub fn bot_context(&self, seat: PlayerPosition) -> BotContext<'_> {
         let (scores, bias_delta) = biased_scores(self.match_state.scores(), seat);
         BotContext::new(
@@ -260,6 +519,24 @@ impl GameController {
             self.bot_difficulty,
         )
         .with_controller_bias_delta(bias_delta)
+        .with_target_score(self.match_state.target_score())
+        .with_jack_of_diamonds(self.match_state.rules().jack_of_diamonds)
+    }
+
+    /// What the bots see of the match, owned so decisions can be made on
+    /// other threads; see [`BotSnapshot::make_thread_local`].
+    pub fn bot_snapshot(&self) -> BotSnapshot {
+        BotSnapshot::capture(&self.match_state, &self.unseen_tracker)
+    }
+
+    /// Lends a decision memo to this controller; get it back with
+    /// `take_decision_memo` to carry it into the next hand.
+    pub fn set_decision_memo(&mut self, memo: Option<DecisionMemo>) {
+        self.decision_memo = memo;
+    }
+
+    pub fn take_decision_memo(&mut self) -> Option<DecisionMemo> {
+        self.decision_memo.take()
     }
 
     pub fn set_bot_difficulty(&mut self, difficulty: BotDifficulty) {
@@ -278,6 +555,94 @@ impl GameController {
         self.think_config = config;
     }
 
+    pub fn pacing(&self) -> PacingConfig {
+        self.pacing
+    }
+
+    pub fn set_pacing(&mut self, pacing: PacingConfig) {
+        self.pacing = pacing.sanitized();
+    }
+
+    #[cfg(test)]
+    pub fn set_review_config(&mut self, config: ReviewConfig) {
+        self.review_config = config;
+        if !config.enabled {
+            self.pending_review = None;
+        }
+    }
+
+    /// Review of the round that just ended, available until dismissed.
+    pub fn review(&self) -> Option<&RoundReview> {
+        self.pending_review.as_ref()
+    }
+
+    /// Drops the finished round's record (hidden hands included).
+    pub fn dismiss_review(&mut self) {
+        self.pending_review = None;
+    }
+
+    /// Every round of this match scored so far, deal and plays, oldest first.
+    pub fn played_rounds(&self) -> &[RecordedRound] {
+        &self.played_rounds
+    }
+
+    /// Captures the post-pass deal ahead of the round's first play.
+    fn begin_round_record_if_needed(&mut self) {
+        let round = self.match_state.round();
+        if round.tricks_completed() == 0 && round.current_trick().plays().is_empty() {
+            self.round_record = Some(RecordedRound {
+                start: RoundSnapshot::capture(round),
+                passing_direction: self.match_state.passing_direction(),
+                scores: *self.match_state.scores().standings(),
+                plays: Vec::new(),
+            });
+        }
+    }
+
+    /// Files the finished round for the replay and, with review on, builds
+    /// its review.
+    fn close_round_record(&mut self) {
+        let Some(record) = self.round_record.take() else {
+            return;
+        };
+        self.played_rounds.push(record.clone());
+        if !self.review_config.enabled {
+            return;
+        }
+        match RoundReview::build(record, self.bot_difficulty, &self.review_config) {
+            Ok(review) => self.pending_review = Some(review),
+            Err(err) => Self::dbg(&format!("mdhearts: round review unavailable: {err}")),
+        }
+    }
+
+    fn challenge_from_env() -> Option<Challenge> {
+        match Challenge::from_env() {
+            Ok(challenge) => challenge,
+            Err(err) => {
+                Self::dbg(&format!("mdhearts: challenge mode disabled: {err}"));
+                None
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn set_challenge(&mut self, challenge: Option<Challenge>) {
+        self.challenge = challenge;
+    }
+
+    pub fn challenge(&self) -> Option<&Challenge> {
+        self.challenge.as_ref()
+    }
+
+    /// Challenge-mode weights for `seat` this round; `None` means the usual weights.
+    pub fn weight_set_for(&self, seat: PlayerPosition) -> Option<WeightSet> {
+        let round_number = self.match_state.round_number();
+        self.challenge
+            .as_ref()
+            .and_then(|challenge| challenge.profile_for(round_number, seat))
+            .map(|profile| profile.weights)
+    }
+
     pub fn prepare_bot_think(&self, seat: PlayerPosition) -> Option<BotThinkRequest> {
         if self.in_passing_phase() {
             return None;
@@ -307,6 +672,7 @@ impl GameController {
             difficulty: self.bot_difficulty,
             snapshot,
             config: self.think_config,
+            weights: self.weight_set_for(seat),
         })
     }
 
@@ -345,32 +711,85 @@ impl GameController {
             TimeoutFallback::FirstLegal => legal.first().copied(),
             TimeoutFallback::HeuristicBest => {
                 let ctx = self.bot_context(seat);
-                match self.bot_difficulty {
-                    BotDifficulty::SearchLookahead | BotDifficulty::FutureHard => {
-                        crate::bot::PlayPlannerHard::choose(&legal, &ctx)
+                crate::bot::with_weight_set(self.weight_set_for(seat), || {
+                    match self.bot_difficulty {
+                        // The search's anytime answer; rerunning the search
+                        // here would stall the caller all over again.
+                        BotDifficulty::FutureHard => {
+                            crate::bot::PlayPlannerHard::choose(&legal, &ctx)
+                        }
+                        _ => PlayPlanner::choose(&legal, &ctx),
                     }
-                    _ => PlayPlanner::choose(&legal, &ctx),
-                }
+                })
                 .or_else(|| legal.first().copied())
             }
         }
     }
 
     pub fn match_over(&self) -> bool {
-        self.match_state
-            .scores()
-            .standings()
-            .iter()
-            .copied()
-            .any(|score| score >= 100)
+        self.match_state.is_match_over()
+    }
+
+    /// The score that ends this match.
+    pub fn target_score(&self) -> u32 {
+        self.match_state.target_score()
+    }
+
+    /// Sets the score that ends this match, e.g. for a new game from the
+    /// setup menu.
+    pub fn set_target_score(
+        &mut self,
+        target: u32,
+    ) -> Result<(), hearts_core::model::rules::RuleError> {
+        self.match_state.set_target_score(target)
+    }
+
+    pub fn rules(&self) -> &hearts_core::model::rules::RuleSet {
+        self.match_state.rules()
+    }
+
+    /// Why `seat` may not play `card` now, worded for the player, or `None`
+    /// when the play is legal.
+    pub fn play_refusal(&self, seat: PlayerPosition, card: Card) -> Option<String> {
+        let err = self.match_state.round().validate_play(seat, card).err()?;
+        Some(RulesText::new(*self.rules()).refusal(&err))
+    }
+
+    /// The round in progress as [`crate::saved_game::SavedGame`] saves it.
+    /// Who sits where, for everything shown about a seat.
+    pub fn players(&self) -> &PlayerRoster {
+        &self.players
+    }
+
+    pub fn set_players(&mut self, players: PlayerRoster) {
+        self.players = players;
+    }
+
+    pub fn seat_name(&self, seat: PlayerPosition) -> &str {
+        self.players.name(seat)
+    }
+
+    pub fn round_log(&self) -> &RoundLog {
+        &self.round_log
+    }
+
+    /// What the table remembers of the match's earlier rounds.
+    pub fn match_memory(&self) -> crate::bot::MatchMemory {
+        *self.unseen_tracker.match_memory()
+    }
+
+    /// Restores the memory of a saved match's earlier rounds.
+    pub fn set_match_memory(&mut self, memory: crate::bot::MatchMemory) {
+        self.unseen_tracker.set_match_memory(memory);
+    }
+
+    /// Turns the Omnibus J♦ bonus on or off from the next round scored.
+    pub fn set_jack_of_diamonds(&mut self, enabled: bool) {
+        self.match_state.set_jack_of_diamonds(enabled);
     }
 
     pub fn match_winner(&self) -> Option<PlayerPosition> {
-        if self.match_over() {
-            Some(self.match_state.scores().leading_player())
-        } else {
-            None
-        }
+        self.match_state.winner()
     }
 
     #[cfg(test)]
@@ -387,12 +806,12 @@ impl GameController {
             "Round {} | Passing: {} | Leader: {}",
             self.match_state.round_number(),
             passing,
-            leader
+            self.seat_name(leader)
         )
     }
 
     pub fn legal_moves(&self, seat: PlayerPosition) -> Vec<Card> {
-        self.match_state.round().legal_cards(seat)
+        self.match_state.round().legal_plays(seat)
     }
 
     pub fn play(&mut self, seat: PlayerPosition, card: Card) -> Result<PlayOutcome, PlayError> {
@@ -406,6 +825,7 @@ impl GameController {
                 .map(|p| (p.position, p.card))
                 .collect()
         };
+        self.begin_round_record_if_needed();
         let out = {
             let round = self.match_state.round_mut();
             round.play_card(seat, card)
@@ -413,14 +833,12 @@ impl GameController {
 
         let out = match out {
             Ok(value) => {
-                // Track card reveal
-                self.unseen_tracker.note_card_played(seat, card);
-                // If this was a follow where suit was not followed, we can deduce a void.
-                if let Some(lead_suit) = pre_plays.first().map(|p| p.1.suit)
-                    && card.suit != lead_suit
-                {
-                    self.unseen_tracker.note_void(seat, lead_suit);
+                if let Some(record) = self.round_record.as_mut() {
+                    record.plays.push((seat, card));
                 }
+                self.round_log.note_play(seat, card);
+                self.unseen_tracker
+                    .observe_latest_play(self.match_state.round());
                 value
             }
             Err(err) => return Err(err),
@@ -429,9 +847,6 @@ impl GameController {
         if let PlayOutcome::TrickCompleted { winner, penalties } = out {
             let mut plays = pre_plays;
             plays.push((seat, card));
-            let hearts_broken = self.match_state.round().hearts_broken();
-            self.unseen_tracker
-                .note_trick_completion(&plays, winner, penalties, hearts_broken);
             self.last_trick = Some(TrickSummary { winner, plays });
             // Update moon state heuristics for the winner and others.
             self.update_moon_states_after_trick(winner, penalties);
@@ -450,7 +865,7 @@ impl GameController {
         let cards_played = 52usize.saturating_sub(self.unseen_tracker.unseen_count());
         let totals = round.penalty_totals();
         let cfg = moon_cfg();
-        use hearts_core::model::rank::Rank;
+        use hearts_core::model::hand_profile::HandProfile;
         use hearts_core::model::suit::Suit;
 
         for seat in PlayerPosition::LOOP.iter().copied() {
@@ -471,12 +886,9 @@ impl GameController {
                     }
                     counts[seat.index()]
                 };
-                let hand = round.hand(seat);
-                let hearts_in_hand = hand.iter().filter(|c| c.suit == Suit::Hearts).count();
-                let control_hearts = hand
-                    .iter()
-                    .filter(|c| c.suit == Suit::Hearts && c.rank >= Rank::Ten)
-                    .count();
+                let profile = HandProfile::from_hand(round.hand(seat));
+                let hearts_in_hand = profile.suit_len(Suit::Hearts);
+                let control_hearts = profile.high_hearts();
                 if hearts_in_hand >= cfg.commit_min_hearts
                     && control_hearts >= cfg.commit_min_control_hearts
                 {
@@ -514,11 +926,7 @@ impl GameController {
                 let near_end = cards_played >= cfg.abort_near_end_cards_played_min;
                 let lost_control_recent =
                     cfg.abort_on_lost_control_clean && winner != seat && penalties == 0;
-                let hearts_left = round
-                    .hand(seat)
-                    .iter()
-                    .filter(|c| c.suit == Suit::Hearts)
-                    .count();
+                let hearts_left = HandProfile::from_hand(round.hand(seat)).suit_len(Suit::Hearts);
                 if others_hearts >= cfg.abort_others_hearts_min
                     || near_end
                     || lost_control_recent
@@ -542,27 +950,176 @@ impl GameController {
     pub fn submit_pass(
         &mut self,
         seat: PlayerPosition,
-        cards: [Card; 3],
+        cards: impl Into<PassCards>,
     ) -> Result<(), hearts_core::model::passing::PassingError> {
+        let cards = cards.into();
         let result = self.match_state.round_mut().submit_pass(seat, cards);
         if result.is_ok() {
             self.unseen_tracker.note_pass_selection(seat, &cards);
+            self.round_log.note_pass(seat, &cards);
+            if let RoundPhase::Passing(state) = self.match_state.round().phase()
+                && state.is_complete()
+            {
+                self.pass_events.push(PassEvent::AllSubmitted);
+            }
         }
         result
     }
 
     pub fn resolve_passes(&mut self) -> Result<(), hearts_core::model::passing::PassingError> {
-        self.match_state.round_mut().resolve_passes()
+        let pending = match self.match_state.round().phase() {
+            RoundPhase::Passing(state) => Some((state.direction(), *state.submissions())),
+            _ => None,
+        };
+        self.match_state.round_mut().resolve_passes()?;
+        self.round_log.note_resolve();
+        let Some((direction, submissions)) = pending else {
+            return Ok(());
+        };
+        self.pass_events.push(PassEvent::Resolved);
+        let mut received = [None; 4];
+        for from in PlayerPosition::LOOP {
+            let Some(cards) = submissions[from.index()] else {
+                continue;
+            };
+            let seat = direction.target(from);
+            received[seat.index()] = Some(cards);
+            self.unseen_tracker.note_pass_received(seat, from, cards);
+            self.pass_events
+                .push(PassEvent::Received { seat, from, cards });
+        }
+        self.received = Some((self.match_state.round_number(), received));
+        Ok(())
+    }
+
+    /// Counts a search decision this round that missed its deadline and
+    /// played the heuristic pick.
+    pub fn record_search_fallback(&mut self) {
+        let round = self.match_state.round_number();
+        let (counted_round, count) = self.search_fallbacks;
+        let count = if counted_round == round { count } else { 0 };
+        self.search_fallbacks = (round, count + 1);
+    }
+
+    /// Search decisions this round that fell back to the heuristic pick.
+    pub fn search_fallbacks_this_round(&self) -> u32 {
+        let (round, count) = self.search_fallbacks;
+        if round == self.match_state.round_number() {
+            count
+        } else {
+            0
+        }
+    }
+
+    /// Drains pass-phase events in the order they happened.
+    pub fn take_pass_events(&mut self) -> Vec<PassEvent> {
+        std::mem::take(&mut self.pass_events)
+    }
+
+    /// Every card `seat` received in this round's pass.
+    pub fn received_cards(&self, seat: PlayerPosition) -> Vec<Card> {
+        match &self.received {
+            Some((round_number, received)) if *round_number == self.match_state.round_number() => {
+                received[seat.index()].iter().flatten().copied().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Starts or stops recording the human's plays; see [`crate::recording`].
+    pub fn set_human_recorder(&mut self, recorder: Option<HumanRecorder>) {
+        self.human_recorder = recorder;
+    }
+
+    #[cfg(test)]
+    pub fn human_recorder(&self) -> Option<&HumanRecorder> {
+        self.human_recorder.as_ref()
+    }
+
+    /// Records that the human at `seat` is about to play `card`. Call it
+    /// before [`play`](Self::play), while the position is the one they chose
+    /// from.
+    pub fn record_human_play(&mut self, seat: PlayerPosition, card: Card) {
+        if self.human_recorder.is_none() {
+            return;
+        }
+        let record = HumanPlayRecord::observe(self, seat, card);
+        if let Some(recorder) = self.human_recorder.as_mut() {
+            recorder.note_play(record);
+        }
+    }
+
+    /// Received cards `seat` still holds, kept marked for the first
+    /// `RECEIVED_MARK_TRICKS` tricks of the round.
+    pub fn received_marks(&self, seat: PlayerPosition) -> Vec<Card> {
+        let round = self.match_state.round();
+        let Some((round_number, received)) = &self.received else {
+            return Vec::new();
+        };
+        if *round_number != self.match_state.round_number()
+            || round.tricks_completed() >= RECEIVED_MARK_TRICKS
+        {
+            return Vec::new();
+        }
+        let hand = round.hand(seat);
+        received[seat.index()]
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|card| hand.contains(*card))
+            .collect()
+    }
+
+    pub fn suit_tally(&self, seat: PlayerPosition, suit: Suit) -> SuitTally {
+        let round = self.match_state.round();
+        let played: Vec<Card> = round
+            .trick_history()
+            .iter()
+            .chain(std::iter::once(round.current_trick()))
+            .flat_map(|trick| trick.plays().iter().map(|play| play.card))
+            .filter(|card| card.suit == suit)
+            .collect();
+        let mut held: Vec<Card> = round
+            .hand(seat)
+            .iter()
+            .copied()
+            .filter(|card| card.suit == suit)
+            .collect();
+        held.sort_by_key(|card| card.rank);
+        let outstanding = Rank::ORDERED
+            .iter()
+            .map(|&rank| Card::new(rank, suit))
+            .filter(|card| !played.contains(card) && !held.contains(card))
+            .collect();
+        SuitTally {
+            suit,
+            played,
+            held,
+            outstanding,
+        }
+    }
+
+    /// Master claims for each suit in `Suit::ALL` order, from `seat`'s view.
+    pub fn suit_mastery(&self, seat: PlayerPosition) -> [MasterySummary; 4] {
+        Suit::ALL.map(|suit| MasterySummary::from_tally(&self.suit_tally(seat, suit)))
     }
 
     pub fn standings(&self) -> [u32; 4] {
         *self.match_state.scores().standings()
     }
 
+    pub fn scoreboard(&self) -> &ScoreBoard {
+        self.match_state.scores()
+    }
+
     pub fn round_number(&self) -> u32 {
         self.match_state.round_number()
     }
 
+    pub fn round(&self) -> &RoundState {
+        self.match_state.round()
+    }
+
     pub fn penalties_this_round(&self) -> [u8; 4] {
         self.match_state.round_penalties()
     }
@@ -579,10 +1136,27 @@ impl GameController {
         counts
     }
 
+    /// Round-start briefing for `seat`, offered once passing is done and until
+    /// the first trick is complete.
+    pub fn round_briefing(&self, seat: PlayerPosition) -> Option<String> {
+        let round = self.match_state.round();
+        if self.in_passing_phase() || !round.trick_history().is_empty() {
+            return None;
+        }
+        Some(crate::bot::briefing(&self.bot_context(seat)))
+    }
+
     pub fn passing_direction(&self) -> hearts_core::model::passing::PassingDirection {
         self.match_state.passing_direction()
     }
 
+    /// How much a lead in each suit plays into an opponent's concentration,
+    /// as `seat` sees it; see [`crate::bot::UnseenTracker::danger_suits`].
+    pub fn danger_suits(&self, seat: PlayerPosition) -> hearts_core::model::per_suit::PerSuit<u8> {
+        self.unseen_tracker
+            .danger_suits(seat, self.match_state.round())
+    }
+
     pub fn explain_candidates_for(&self, seat: PlayerPosition) -> Vec<(Card, i32)> {
         let legal = self.legal_moves(seat);
         let ctx = self.bot_context(seat);
@@ -604,11 +1178,12 @@ impl GameController {
         *self.match_state.round_mut() = round;
         self.match_state.scores_mut().set_totals(scores);
         self.unseen_tracker
-            .reset_for_round(self.match_state.round());
+            .rebuild_for_round(self.match_state.round());
+        self.round_log = RoundLog::new(&self.match_state);
     }
 
-    #[cfg(test)]
-    pub fn moon_state_for_test(&self, seat: PlayerPosition) -> crate::bot::MoonState {
+    /// The tracker's read of `seat`'s moon attempt.
+    pub fn moon_state(&self, seat: PlayerPosition) -> crate::bot::MoonState {
         self.unseen_tracker.moon_state(seat)
     }
 }
@@ -803,6 +1378,63 @@ fn debug_moon_config_string() -> String {
     )
 }
 
+/// Tricks for which cards received in the pass stay marked in the hand.
+pub const RECEIVED_MARK_TRICKS: usize = 2;
+
+/// Passing-phase milestones, queued by the controller and drained by the UI
+/// with `take_pass_events`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassEvent {
+    /// Every seat has submitted its pass; cards can start moving.
+    AllSubmitted,
+    /// Passes were exchanged and play can begin.
+    Resolved,
+    /// The cards `seat` received from `from`.
+    Received {
+        seat: PlayerPosition,
+        from: PlayerPosition,
+        cards: PassCards,
+    },
+}
+
+/// One suit from a seat's point of view this round, for the card-counting
+/// view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuitTally {
+    pub suit: Suit,
+    /// Played so far, in play order.
+    pub played: Vec<Card>,
+    /// In the seat's hand, low to high.
+    pub held: Vec<Card>,
+    /// Neither played nor held: somewhere in the other hands.
+    pub outstanding: Vec<Card>,
+}
+
+impl SuitTally {
+    pub fn summary(&self) -> String {
+        let list = |cards: &[Card]| {
+            if cards.is_empty() {
+                "-".to_string()
+            } else {
+                cards
+                    .iter()
+                    .map(Card::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+        };
+        format!(
+            "{} played: {}\nIn hand ({}): {}\nStill out ({}): {}",
+            self.played.len(),
+            list(&self.played),
+            self.held.len(),
+            list(&self.held),
+            self.outstanding.len(),
+            list(&self.outstanding)
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TrickSummary {
     pub winner: PlayerPosition,
@@ -863,6 +1495,26 @@ impl GameController {
             });
         }
         let mut last_bias_delta: Option<i32> = None;
+        let planned = !enforce_two && self.bot_difficulty != BotDifficulty::EasyLegacy;
+        if planned {
+            let commit = {
+                let ctx_probe = self.bot_context(seat);
+                crate::bot::determine_style(&ctx_probe) == crate::bot::BotStyle::AggressiveMoon
+            };
+            if commit {
+                self.unseen_tracker
+                    .set_moon_state(seat, MoonState::Committed);
+            }
+        }
+        let memo_key = (planned
+            && self.decision_memo.is_some()
+            && self.challenge.is_none()
+            && crate::bot::memo_allowed(self.bot_difficulty))
+        .then(|| crate::bot::memo_key(&self.bot_context(seat)));
+        let memo_hit = match (&memo_key, self.decision_memo.as_mut()) {
+            (Some(key), Some(memo)) => memo.lookup(key),
+            _ => None,
+        };
         let mut card_to_play = if enforce_two {
             let two = Card::new(Rank::Two, Suit::Clubs);
             if legal.contains(&two) {
@@ -870,41 +1522,38 @@ impl GameController {
             } else {
                 legal.first().copied()
             }
+        } else if let Some(card) = memo_hit {
+            last_bias_delta = self.bot_context(seat).controller_bias_delta;
+            Some(card)
         } else {
             match self.bot_difficulty {
                 BotDifficulty::EasyLegacy => legal.first().copied(),
                 BotDifficulty::SearchLookahead | BotDifficulty::FutureHard => {
-                    let commit = {
-                        let ctx_probe = self.bot_context(seat);
-                        crate::bot::determine_style(&ctx_probe)
-                            == crate::bot::BotStyle::AggressiveMoon
-                    };
-                    if commit {
-                        self.unseen_tracker
-                            .set_moon_state(seat, MoonState::Committed);
-                    }
                     let ctx = self.bot_context(seat);
-                    let result = crate::bot::PlayPlannerHard::choose_with_limit(
-                        &legal,
-                        &ctx,
-                        decision_limit.as_ref(),
-                    );
+                    let result = crate::bot::with_weight_set(self.weight_set_for(seat), || {
+                        crate::bot::PlayPlannerHard::choose_with_limit(
+                            &legal,
+                            &ctx,
+                            decision_limit.as_ref(),
+                        )
+                    });
+                    last_bias_delta = ctx.controller_bias_delta;
+                    result
+                }
+                BotDifficulty::Rollout => {
+                    let ctx = self.bot_context(seat);
+                    let policy = crate::bot::RolloutPolicy::new(self.match_state.seed());
+                    let result = crate::bot::with_weight_set(self.weight_set_for(seat), || {
+                        policy.choose_with_limit(&legal, &ctx, decision_limit.as_ref())
+                    });
                     last_bias_delta = ctx.controller_bias_delta;
                     result
                 }
                 _ => {
-                    let commit = {
-                        let ctx_probe = self.bot_context(seat);
-                        crate::bot::determine_style(&ctx_probe)
-                            == crate::bot::BotStyle::AggressiveMoon
-                    };
-                    if commit {
-                        self.unseen_tracker
-                            .set_moon_state(seat, MoonState::Committed);
-                    }
                     let ctx = self.bot_context(seat);
-                    let result =
-                        PlayPlanner::choose_with_limit(&legal, &ctx, decision_limit.as_ref());
+                    let result = crate::bot::with_weight_set(self.weight_set_for(seat), || {
+                        PlayPlanner::choose_with_limit(&legal, &ctx, decision_limit.as_ref())
+                    });
                     last_bias_delta = ctx.controller_bias_delta;
                     result
                 }
@@ -918,6 +1567,14 @@ impl GameController {
             .unwrap_or(false);
         let mut fallback_label: Option<&'static str> = None;
 
+        if memo_hit.is_none()
+            && !timed_out
+            && let (Some(key), Some(card), Some(memo)) =
+                (memo_key, card_to_play, self.decision_memo.as_mut())
+        {
+            memo.insert(key, card);
+        }
+
         if card_to_play.is_none() && timed_out {
             fallback_label = Some(self.think_config.fallback.label());
             card_to_play = self.timeout_fallback_card(seat);
@@ -958,11 +1615,33 @@ impl GameController {
                 timed_out,
                 fallback: fallback_label,
                 search_stats,
+                deep_search: crate::bot::search_deep::take_last_stats().map(Into::into),
                 controller_bias_delta: last_bias_delta,
             },
         );
 
-        if let Some(card) = card_to_play {
+        if let Some(chosen) = card_to_play {
+            debug_assert!(
+                legal.contains(&chosen),
+                "{seat:?} chose {chosen}, which is not among its legal plays {} \
+                 (difficulty {:?}, fallback {fallback_label:?})",
+                Hand::with_cards(legal.clone()),
+                self.bot_difficulty
+            );
+            // A release build keeps the game going on the lowest legal card.
+            let card = if legal.contains(&chosen) {
+                chosen
+            } else if let Some(lowest) = legal.iter().copied().min_by_key(|c| (c.rank, c.suit)) {
+                eprintln!(
+                    "mdhearts: {seat:?} chose {chosen}, which is not among its legal plays {} \
+                     (difficulty {:?}, fallback {fallback_label:?}); playing {lowest}",
+                    Hand::with_cards(legal.clone()),
+                    self.bot_difficulty
+                );
+                lowest
+            } else {
+                return AutoplayOutcome::NoLegal;
+            };
             Self::dbg(&format!("mdhearts: AI {:?} plays {}", seat, card));
             let _ = self.play(seat, card);
             AutoplayOutcome::Played(seat, card)
@@ -973,6 +1652,29 @@ impl GameController {
         }
     }
 
+    /// See [`RoundState::is_decided`]; judged under this match's rules.
+    pub fn is_round_decided(&self) -> bool {
+        self.match_state
+            .round()
+            .is_decided(self.match_state.rules())
+    }
+
+    /// Once the round is decided, plays the lowest legal card for the seat
+    /// to move without consulting its bot, so no think time or telemetry is
+    /// spent on tricks that cannot change the score.
+    pub fn fast_forward_one(&mut self) -> Option<(PlayerPosition, Card)> {
+        if !self.is_round_decided() {
+            return None;
+        }
+        let seat = self.expected_to_play();
+        let card = self
+            .legal_moves(seat)
+            .into_iter()
+            .min_by_key(|card| (card.rank, card.suit))?;
+        self.play(seat, card).ok()?;
+        Some((seat, card))
+    }
+
     pub fn autoplay_one(&mut self, stop_seat: PlayerPosition) -> Option<(PlayerPosition, Card)> {
         match self.autoplay_one_with_status(stop_seat) {
             AutoplayOutcome::Played(seat, card) => Some((seat, card)),
@@ -1007,15 +1709,16 @@ impl GameController {
             .collect()
     }
 
-    pub fn simple_pass_for(&self, seat: PlayerPosition) -> Option<[Card; 3]> {
+    /// Cards each seat passes this round under the match rules.
+    pub fn pass_count(&self) -> usize {
+        self.match_state.round().pass_count()
+    }
+
+    pub fn simple_pass_for(&self, seat: PlayerPosition) -> Option<PassCards> {
         let hand = self.match_state.round().hand(seat);
+        let count = self.pass_count();
         match self.bot_difficulty {
-            BotDifficulty::EasyLegacy => {
-                if hand.len() < 3 {
-                    return None;
-                }
-                Some([hand.cards()[0], hand.cards()[1], hand.cards()[2]])
-            }
+            BotDifficulty::EasyLegacy => hand.cards().get(..count).and_then(PassCards::from_slice),
             _ => {
                 let ctx = self.bot_context(seat);
                 PassPlanner::choose(hand, &ctx)
@@ -1043,28 +1746,313 @@ impl GameController {
         let round_num = self.match_state.round_number();
         let passing = self.match_state.passing_direction();
         let starting = self.match_state.round().starting_player();
+        let rules = *self.match_state.rules();
         self.match_state =
-            MatchState::with_seed_round_direction(seed, round_num, passing, starting);
+            MatchState::with_seed_round_direction_rules(seed, round_num, passing, starting, rules);
         self.unseen_tracker
             .reset_for_round(self.match_state.round());
+        self.round_log = RoundLog::new(&self.match_state);
+        self.round_record = None;
+        self.pending_review = None;
+        self.pass_events.clear();
+        self.received = None;
+        if let Some(recorder) = self.human_recorder.as_mut() {
+            recorder.discard_round();
+        }
+        self.log_round_start();
+    }
+
+    /// Every trick of the round has been played.
+    pub fn round_ready_for_scoring(&self) -> bool {
+        self.match_state.is_round_ready_for_scoring()
     }
 
     pub fn finish_round_if_ready(&mut self) -> Option<PlayerPosition> {
         if !self.match_state.is_round_ready_for_scoring() {
             return None;
         }
+        self.close_round_record();
+        let penalties = self.match_state.round_penalties();
+        if let Some(challenge) = self.challenge.as_mut() {
+            challenge.record_round(self.match_state.round_number(), penalties);
+        }
+        if let Some(recorder) = self.human_recorder.as_mut()
+            && let Err(err) = recorder.finish_round(penalties)
+        {
+            Self::dbg(&format!(
+                "mdhearts: could not record plays to {}: {err}",
+                recorder.path().display()
+            ));
+        }
         if let Some(winner) = self.match_state.finish_round_and_start_next() {
             return Some(winner);
         }
+        if crate::bot::match_memory_enabled() {
+            self.unseen_tracker
+                .note_round_scored(penalties, *self.match_state.scores().standings());
+        }
         self.unseen_tracker
             .reset_for_round(self.match_state.round());
+        self.round_log = RoundLog::new(&self.match_state);
+        self.pass_events.clear();
         crate::telemetry::hard::reset();
+        self.log_round_start();
         None
     }
 }
+/// One recorded round: the post-pass deal, the match scores going into it, and
+/// every card in play order.
+#[derive(Debug, Clone)]
+pub struct RecordedRound {
+    pub start: RoundSnapshot,
+    pub passing_direction: PassingDirection,
+    pub scores: [u32; 4],
+    pub plays: Vec<(PlayerPosition, Card)>,
+}
+
+/// Read-only replay over recorded rounds.
+///
+/// Unlike `GameController` there is no human seat: recorded plays are applied
+/// straight to the round, and any seat's hand can be shown face up.
+pub struct ReplayController {
+    rounds: Vec<RecordedRound>,
+    round_index: usize,
+    cursor: usize,
+    round: RoundState,
+    tracker: UnseenTracker,
+}
+
+impl ReplayController {
+    /// Validates every recorded play up front so stepping never fails later.
+    pub fn new(rounds: Vec<RecordedRound>) -> Result<Self, String> {
+        if rounds.is_empty() {
+            return Err("replay has no rounds".to_string());
+        }
+        for (idx, recorded) in rounds.iter().enumerate() {
+            let mut round = recorded.start.clone().restore(recorded.passing_direction)?;
+            for &(seat, card) in &recorded.plays {
+                round
+                    .play_card(seat, card)
+                    .map_err(|err| format!("round {idx}: {seat:?} {card}: {err:?}"))?;
+            }
+        }
+        let (round, tracker) = Self::start_of(&rounds[0]);
+        Ok(Self {
+            rounds,
+            round_index: 0,
+            cursor: 0,
+            round,
+            tracker,
+        })
+    }
+
+    fn start_of(recorded: &RecordedRound) -> (RoundState, UnseenTracker) {
+        let round = recorded
+            .start
+            .clone()
+            .restore(recorded.passing_direction)
+            .expect("validated in ReplayController::new");
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        (round, tracker)
+    }
+
+    pub fn round(&self) -> &RoundState {
+        &self.round
+    }
+
+    pub fn round_index(&self) -> usize {
+        self.round_index
+    }
+
+    pub fn round_count(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Number of plays applied so far in the current round.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn plays_in_round(&self) -> usize {
+        self.rounds[self.round_index].plays.len()
+    }
+
+    pub fn scores(&self) -> ScoreBoard {
+        let mut scores = ScoreBoard::new();
+        scores.set_totals(self.rounds[self.round_index].scores);
+        scores
+    }
+
+    /// All four hands, including opponents' (it is a replay).
+    pub fn face_up_hands(&self) -> [Vec<Card>; 4] {
+        std::array::from_fn(|idx| {
+            PlayerPosition::from_index(idx)
+                .map(|seat| self.round.hand(seat).cards().to_vec())
+                .unwrap_or_default()
+        })
+    }
+
+    /// The recorded play due at the cursor, if any.
+    pub fn next_play(&self) -> Option<(PlayerPosition, Card)> {
+        self.rounds[self.round_index]
+            .plays
+            .get(self.cursor)
+            .copied()
+    }
+
+    pub fn step_forward(&mut self) -> Option<(PlayerPosition, Card)> {
+        let (seat, card) = self.next_play()?;
+        Self::apply_play(&mut self.round, &mut self.tracker, seat, card)
+            .expect("validated in ReplayController::new");
+        self.cursor += 1;
+        Some((seat, card))
+    }
+
+    /// Plays a card and feeds the same reveals to the tracker as live play does.
+    fn apply_play(
+        round: &mut RoundState,
+        tracker: &mut UnseenTracker,
+        seat: PlayerPosition,
+        card: Card,
+    ) -> Result<PlayOutcome, PlayError> {
+        let outcome = round.play_card(seat, card)?;
+        tracker.observe_latest_play(round);
+        Ok(outcome)
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.seek(self.cursor - 1);
+        true
+    }
+
+    /// Advances to the end of the current trick (or the next one if already on a boundary).
+    pub fn step_forward_trick(&mut self) -> bool {
+        let mut moved = false;
+        while self.step_forward().is_some() {
+            moved = true;
+            if self.cursor.is_multiple_of(4) {
+                break;
+            }
+        }
+        moved
+    }
+
+    /// Rewinds to the start of the current trick (or the previous one if already on a boundary).
+    pub fn step_back_trick(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        let target = (self.cursor - 1) / 4 * 4;
+        self.seek(target);
+        true
+    }
+
+    /// Rebuilds the round from its start up to `cursor` plays.
+    pub fn seek(&mut self, cursor: usize) {
+        let (round, tracker) = Self::start_of(&self.rounds[self.round_index]);
+        self.round = round;
+        self.tracker = tracker;
+        self.cursor = 0;
+        let target = cursor.min(self.plays_in_round());
+        while self.cursor < target {
+            self.step_forward();
+        }
+    }
+
+    pub fn next_round(&mut self) -> bool {
+        if self.round_index + 1 >= self.rounds.len() {
+            return false;
+        }
+        self.round_index += 1;
+        self.seek(0);
+        true
+    }
+
+    pub fn previous_round(&mut self) -> bool {
+        if self.round_index == 0 {
+            return false;
+        }
+        self.round_index -= 1;
+        self.seek(0);
+        true
+    }
+
+    /// Bot advice for the seat about to make the next recorded play.
+    pub fn advice(&self, difficulty: BotDifficulty) -> Vec<(Card, i32)> {
+        let Some((seat, _)) = self.next_play() else {
+            return Vec::new();
+        };
+        let legal = self.round.legal_plays(seat);
+        let ctx = BotContext::new(
+            seat,
+            &self.round,
+            self.scores(),
+            self.rounds[self.round_index].passing_direction,
+            &self.tracker,
+            difficulty,
+        );
+        match difficulty {
+            BotDifficulty::SearchLookahead | BotDifficulty::FutureHard => {
+                crate::bot::PlayPlannerHard::explain_candidates(&legal, &ctx)
+            }
+            _ => PlayPlanner::explain_candidates(&legal, &ctx),
+        }
+    }
+
+    /// Plays `card` instead of the recorded play at the cursor and lets bots at
+    /// `difficulty` finish the round. Returns the resulting round penalties.
+    pub fn simulate_alternative(
+        &self,
+        card: Card,
+        difficulty: BotDifficulty,
+    ) -> Result<[u8; 4], String> {
+        let (seat, _) = self
+            .next_play()
+            .ok_or_else(|| "no recorded play at cursor".to_string())?;
+        let mut round = self.round.clone();
+        let mut tracker = self.tracker.clone();
+        Self::apply_play(&mut round, &mut tracker, seat, card)
+            .map_err(|err| format!("{seat:?} {card}: {err:?}"))?;
+        let scores = self.scores();
+        let passing = self.rounds[self.round_index].passing_direction;
+        loop {
+            match round.phase() {
+                RoundPhase::Playing => {}
+                RoundPhase::Complete => break,
+                RoundPhase::Passing(_) => return Err("round is still passing".to_string()),
+            }
+            let trick = round.current_trick();
+            let to_play = trick
+                .plays()
+                .last()
+                .map(|p| p.position.next())
+                .unwrap_or(trick.leader());
+            let legal = round.legal_plays(to_play);
+            let choice = {
+                let ctx = BotContext::new(to_play, &round, scores, passing, &tracker, difficulty);
+                match difficulty {
+                    BotDifficulty::SearchLookahead | BotDifficulty::FutureHard => {
+                        crate::bot::PlayPlannerHard::choose(&legal, &ctx)
+                    }
+                    _ => PlayPlanner::choose(&legal, &ctx),
+                }
+            }
+            .or_else(|| legal.first().copied())
+            .ok_or_else(|| format!("{to_play:?} has no legal card"))?;
+            Self::apply_play(&mut round, &mut tracker, to_play, choice)
+                .map_err(|err| format!("{to_play:?} {choice}: {err:?}"))?;
+        }
+        Ok(round.penalty_totals())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{GameController, TimeoutFallback};
+    use super::{GameController, PassEvent, RECEIVED_MARK_TRICKS, TimeoutFallback};
     use crate::bot::BotDifficulty;
     use hearts_core::model::card::Card;
     use hearts_core::model::passing::PassingDirection;
@@ -1096,6 +2084,465 @@ mod tests {
         }
     }
 
+    #[test]
+    fn same_seed_produces_identical_deals() {
+        let mut a = GameController::new_with_seed(Some(2024), PlayerPosition::North);
+        let mut b = GameController::new_with_seed(Some(2024), PlayerPosition::North);
+        assert_eq!(a.seed(), 2024);
+        assert!(a.seed_summary().contains("--seed 2024"));
+        for _ in 0..3 {
+            for seat in PlayerPosition::LOOP {
+                assert_eq!(a.hand(seat), b.hand(seat));
+            }
+            // Play both rounds out identically to reach the next deal.
+            for controller in [&mut a, &mut b] {
+                let seat = PlayerPosition::North;
+                if controller.in_passing_phase() {
+                    let cards = controller.simple_pass_for(seat).unwrap();
+                    controller.submit_pass(seat, cards).unwrap();
+                    controller.submit_auto_passes_for_others(seat).unwrap();
+                    controller.resolve_passes().unwrap();
+                }
+                while !controller.match_state.is_round_ready_for_scoring() {
+                    let to_play = controller.expected_to_play();
+                    let card = controller.legal_moves(to_play)[0];
+                    controller.play(to_play, card).unwrap();
+                }
+                assert!(controller.finish_round_if_ready().is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn a_refused_play_is_explained_in_the_rules_wording() {
+        let mut controller = GameController::new_with_seed(Some(2024), PlayerPosition::North);
+        let seat = PlayerPosition::North;
+        if controller.in_passing_phase() {
+            let cards = controller.simple_pass_for(seat).unwrap();
+            controller.submit_pass(seat, cards).unwrap();
+            controller.submit_auto_passes_for_others(seat).unwrap();
+            controller.resolve_passes().unwrap();
+        }
+        let leader = controller.expected_to_play();
+        let two = Card::new(Rank::Two, Suit::Clubs);
+        let other = controller
+            .hand(leader)
+            .into_iter()
+            .find(|&card| card != two)
+            .unwrap();
+        assert_eq!(controller.play_refusal(leader, two), None);
+        assert_eq!(
+            controller.play_refusal(leader, other).as_deref(),
+            Some("The first trick must be led with the two of clubs (variant: standard).")
+        );
+        assert_eq!(
+            controller.play_refusal(leader.next(), two).as_deref(),
+            Some("2C is not in your hand.")
+        );
+    }
+
+    #[test]
+    fn round_review_is_available_only_after_the_round_until_dismissed() {
+        use crate::review::ReviewConfig;
+
+        let mut controller = GameController::new_with_seed(Some(11), PlayerPosition::North);
+        controller.set_bot_difficulty(BotDifficulty::NormalHeuristic);
+        controller.set_review_config(ReviewConfig {
+            enabled: true,
+            margin_threshold: 0,
+            ..ReviewConfig::default()
+        });
+        let seat = PlayerPosition::South;
+        if controller.in_passing_phase() {
+            let cards = controller.simple_pass_for(seat).unwrap();
+            controller.submit_pass(seat, cards).unwrap();
+            controller.submit_auto_passes_for_others(seat).unwrap();
+            controller.resolve_passes().unwrap();
+        }
+        let dealt: Vec<Vec<Card>> = PlayerPosition::LOOP
+            .iter()
+            .map(|&s| controller.hand(s))
+            .collect();
+        // Always play the last legal card so South disagrees with the advice often.
+        while !controller.match_state.is_round_ready_for_scoring() {
+            assert!(controller.review().is_none());
+            let to_play = controller.expected_to_play();
+            let card = *controller.legal_moves(to_play).last().unwrap();
+            controller.play(to_play, card).unwrap();
+        }
+        let penalties = controller.penalties_this_round();
+        assert!(controller.finish_round_if_ready().is_none());
+
+        let review = controller.review().expect("review after round end");
+        assert_eq!(review.decisions().len(), 13);
+        assert!(review.decisions().iter().all(|d| d.seat == seat));
+        assert!(
+            review
+                .summary_text()
+                .contains("13 of your plays checked against the advice.")
+        );
+        for (idx, s) in PlayerPosition::LOOP.iter().enumerate() {
+            assert_eq!(review.dealt_hands()[s.index()], dealt[idx]);
+        }
+        assert_eq!(review.actual_penalties(), penalties);
+        for flag in review.flags() {
+            assert!(flag.margin() > 0);
+            let simulated = review.simulate_advised(flag).unwrap();
+            assert_eq!(simulated.iter().map(|&p| p as u32).sum::<u32>(), 26);
+        }
+
+        controller.dismiss_review();
+        assert!(controller.review().is_none());
+    }
+
+    #[test]
+    fn fast_forwarding_a_decided_round_keeps_its_penalties() {
+        let mut fast_forwarded = 0;
+        for seed in 1..=8 {
+            let deal = |seed| {
+                let mut controller =
+                    GameController::new_with_seed(Some(seed), PlayerPosition::North);
+                if controller.in_passing_phase() {
+                    controller
+                        .submit_auto_passes_for_others(PlayerPosition::South)
+                        .unwrap();
+                    let cards = controller.simple_pass_for(PlayerPosition::South).unwrap();
+                    controller
+                        .submit_pass(PlayerPosition::South, cards)
+                        .unwrap();
+                    controller.resolve_passes().unwrap();
+                }
+                controller
+            };
+            let play_last_legal = |controller: &mut GameController| {
+                let seat = controller.expected_to_play();
+                let card = *controller.legal_moves(seat).last().unwrap();
+                controller.play(seat, card).unwrap();
+            };
+
+            let mut full = deal(seed);
+            while !full.match_state.is_round_ready_for_scoring() {
+                play_last_legal(&mut full);
+            }
+            let mut quick = deal(seed);
+            while !quick.match_state.is_round_ready_for_scoring() {
+                if quick.is_round_decided() {
+                    let seat = quick.expected_to_play();
+                    let lowest = quick
+                        .legal_moves(seat)
+                        .into_iter()
+                        .min_by_key(|card| (card.rank, card.suit))
+                        .unwrap();
+                    assert_eq!(quick.fast_forward_one(), Some((seat, lowest)));
+                    fast_forwarded += 1;
+                } else {
+                    assert!(quick.fast_forward_one().is_none());
+                    play_last_legal(&mut quick);
+                }
+            }
+            assert_eq!(
+                quick.penalties_this_round(),
+                full.penalties_this_round(),
+                "seed {seed}"
+            );
+        }
+        assert!(fast_forwarded > 0);
+    }
+
+    #[test]
+    fn fast_pacing_still_shows_the_fourth_card() {
+        use super::{CollectStage, MIN_TRICK_VIEW_MS, PacingConfig};
+
+        let fast = PacingConfig::fast();
+        assert_eq!(fast.collect_stage(0, None), CollectStage::Showing);
+        assert_eq!(
+            fast.collect_stage(MIN_TRICK_VIEW_MS - 1, None),
+            CollectStage::Showing
+        );
+        assert_eq!(
+            fast.collect_stage(MIN_TRICK_VIEW_MS, None),
+            CollectStage::Sweeping(0.0)
+        );
+        assert_eq!(
+            fast.collect_stage(MIN_TRICK_VIEW_MS + fast.collect_anim_ms, None),
+            CollectStage::Done
+        );
+
+        // Even an explicit zero delay is clamped back up to the minimum view.
+        let mut rushed = PacingConfig::fast();
+        rushed.trick_clear_ms = 0;
+        let mut controller = GameController::new_with_seed(Some(1), PlayerPosition::North);
+        controller.set_pacing(rushed);
+        assert_eq!(controller.pacing().trick_clear_ms, MIN_TRICK_VIEW_MS);
+        assert!(controller.pacing().is_fast());
+    }
+
+    #[test]
+    fn manual_collect_waits_for_the_click() {
+        use super::{CollectStage, MIN_TRICK_VIEW_MS, PacingConfig};
+
+        let pacing = PacingConfig {
+            auto_collect: false,
+            ..PacingConfig::default()
+        };
+        assert_eq!(pacing.collect_stage(10_000, None), CollectStage::Showing);
+        assert_eq!(
+            pacing.collect_stage(2_000 + pacing.collect_anim_ms / 2, Some(2_000)),
+            CollectStage::Sweeping(0.5)
+        );
+        // A click straight after the fourth card still waits out the minimum view.
+        assert_eq!(
+            pacing.collect_stage(MIN_TRICK_VIEW_MS - 1, Some(0)),
+            CollectStage::Showing
+        );
+    }
+
+    #[test]
+    fn think_display_sets_minimum_bot_pause() {
+        use super::{PacingConfig, ThinkDisplay};
+
+        let mut pacing = PacingConfig::default();
+        assert_eq!(pacing.min_bot_think_ms(13), 0);
+        pacing.think_display = ThinkDisplay::Fixed(700);
+        assert_eq!(pacing.min_bot_think_ms(1), 700);
+        pacing.think_display = ThinkDisplay::Realistic;
+        assert!(pacing.min_bot_think_ms(1) < pacing.min_bot_think_ms(4));
+        assert_eq!(pacing.min_bot_think_ms(13), 1_500);
+        assert_eq!(
+            ThinkDisplay::from_env_value("Realistic"),
+            Some(ThinkDisplay::Realistic)
+        );
+        assert_eq!(
+            ThinkDisplay::from_env_value("400"),
+            Some(ThinkDisplay::Fixed(400))
+        );
+        assert_eq!(ThinkDisplay::from_env_value("slow"), None);
+    }
+
+    #[test]
+    fn round_briefing_is_offered_between_passing_and_the_first_trick() {
+        let mut controller = GameController::new_with_seed(Some(3), PlayerPosition::North);
+        let seat = PlayerPosition::South;
+        if controller.in_passing_phase() {
+            assert!(controller.round_briefing(seat).is_none());
+            let cards = controller.simple_pass_for(seat).unwrap();
+            controller.submit_pass(seat, cards).unwrap();
+            controller.submit_auto_passes_for_others(seat).unwrap();
+            controller.resolve_passes().unwrap();
+        }
+        assert!(!controller.round_briefing(seat).unwrap().is_empty());
+        for _ in 0..4 {
+            let to_play = controller.expected_to_play();
+            let card = controller.legal_moves(to_play)[0];
+            controller.play(to_play, card).unwrap();
+        }
+        assert!(controller.round_briefing(seat).is_none());
+    }
+
+    #[test]
+    fn pass_events_report_submission_resolution_and_received_cards() {
+        let mut controller = GameController::new_with_seed(Some(3), PlayerPosition::North);
+        assert!(controller.in_passing_phase());
+        let direction = controller.passing_direction();
+        let human = PlayerPosition::South;
+        let human_pass = controller.simple_pass_for(human).unwrap();
+        controller.submit_pass(human, human_pass).unwrap();
+        assert!(controller.take_pass_events().is_empty());
+        controller.submit_auto_passes_for_others(human).unwrap();
+        assert_eq!(controller.take_pass_events(), vec![PassEvent::AllSubmitted]);
+
+        controller.resolve_passes().unwrap();
+        let events = controller.take_pass_events();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0], PassEvent::Resolved);
+        let sender = PlayerPosition::LOOP
+            .into_iter()
+            .find(|&from| direction.target(from) == human)
+            .unwrap();
+        let received = events
+            .iter()
+            .find_map(|event| match event {
+                PassEvent::Received { seat, from, cards } if *seat == human => {
+                    Some((*from, *cards))
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(received.0, sender);
+        let mut marks = controller.received_marks(human);
+        let mut expected = received.1.to_vec();
+        marks.sort_by_key(|card| crate::bot::card_sort_key(*card));
+        expected.sort_by_key(|card| crate::bot::card_sort_key(*card));
+        assert_eq!(marks, expected);
+        assert!(controller.take_pass_events().is_empty());
+    }
+
+    #[test]
+    fn suit_tally_splits_every_card_of_the_suit() {
+        let mut controller = GameController::new_with_seed(Some(3), PlayerPosition::North);
+        let human = PlayerPosition::South;
+        while controller.in_passing_phase() {
+            let cards = controller.simple_pass_for(human).unwrap();
+            controller.submit_pass(human, cards).unwrap();
+            controller.submit_auto_passes_for_others(human).unwrap();
+            controller.resolve_passes().unwrap();
+        }
+        for _ in 0..5 {
+            let seat = controller.expected_to_play();
+            let card = controller.legal_moves(seat)[0];
+            controller.play(seat, card).unwrap();
+        }
+        let clubs = controller.suit_tally(human, Suit::Clubs);
+        assert!(clubs.played.contains(&Card::new(Rank::Two, Suit::Clubs)));
+        assert_eq!(
+            clubs.played.len() + clubs.held.len() + clubs.outstanding.len(),
+            13
+        );
+        assert!(
+            clubs
+                .held
+                .iter()
+                .all(|card| controller.hand(human).contains(card))
+        );
+        let summary = clubs.summary();
+        assert!(summary.starts_with(&format!("{} played: 2C", clubs.played.len())));
+    }
+
+    #[test]
+    fn received_marks_clear_after_two_tricks() {
+        let mut controller = GameController::new_with_seed(Some(3), PlayerPosition::North);
+        let human = PlayerPosition::South;
+        let cards = controller.simple_pass_for(human).unwrap();
+        controller.submit_pass(human, cards).unwrap();
+        controller.submit_auto_passes_for_others(human).unwrap();
+        controller.resolve_passes().unwrap();
+        let marked = controller.received_marks(human).len();
+        assert_eq!(marked, 3);
+        let mut tricks = 0;
+        while controller.round().tricks_completed() < RECEIVED_MARK_TRICKS {
+            let seat = controller.expected_to_play();
+            let card = controller.legal_moves(seat)[0];
+            controller.play(seat, card).unwrap();
+            tricks = controller.round().tricks_completed();
+            if tricks < RECEIVED_MARK_TRICKS {
+                assert!(controller.received_marks(human).len() <= marked);
+            }
+        }
+        assert_eq!(tricks, RECEIVED_MARK_TRICKS);
+        assert!(controller.received_marks(human).is_empty());
+        controller.restart_round();
+        assert!(controller.received_marks(human).is_empty());
+    }
+
+    #[test]
+    fn challenge_mode_attributes_finished_round_to_profiles() {
+        use crate::bot::WeightSet;
+        use crate::challenge::{Challenge, ChallengeProfile};
+
+        let mut controller = GameController::new_with_seed(Some(5), PlayerPosition::North);
+        controller.set_bot_difficulty(BotDifficulty::NormalHeuristic);
+        let profile = |label: &str| ChallengeProfile {
+            label: label.to_string(),
+            weights: WeightSet::parse("MDH_W_OFFSUIT_BONUS=1").unwrap(),
+        };
+        controller.set_challenge(Some(Challenge::new(
+            [profile("a"), profile("b")],
+            Some(PlayerPosition::South),
+        )));
+        assert!(controller.weight_set_for(PlayerPosition::South).is_none());
+        assert!(controller.weight_set_for(PlayerPosition::East).is_some());
+        let seat = PlayerPosition::South;
+        if controller.in_passing_phase() {
+            let cards = controller.simple_pass_for(seat).unwrap();
+            controller.submit_pass(seat, cards).unwrap();
+            controller.submit_auto_passes_for_others(seat).unwrap();
+            controller.resolve_passes().unwrap();
+        }
+        while !controller.match_state.is_round_ready_for_scoring() {
+            let to_play = controller.expected_to_play();
+            if to_play == seat {
+                let card = controller.legal_moves(seat)[0];
+                controller.play(seat, card).unwrap();
+            } else {
+                controller.autoplay_one(seat).unwrap();
+            }
+        }
+        let penalties = controller.penalties_this_round();
+        controller.finish_round_if_ready();
+        let stats = controller.challenge().unwrap().stats();
+        assert_eq!(stats.hands, [1, 2]);
+        assert_eq!(
+            stats.points,
+            [
+                penalties[PlayerPosition::North.index()] as u32,
+                (penalties[PlayerPosition::East.index()] + penalties[PlayerPosition::West.index()])
+                    as u32,
+            ]
+        );
+    }
+
+    #[test]
+    fn replay_matches_recorded_round_at_every_step() {
+        use super::{RecordedRound, ReplayController};
+        use hearts_core::game::serialization::RoundSnapshot;
+
+        let mut controller = GameController::new_with_seed(Some(7), PlayerPosition::North);
+        controller.set_bot_difficulty(BotDifficulty::NormalHeuristic);
+        let mut rounds = Vec::new();
+        let mut expected: Vec<Vec<RoundSnapshot>> = Vec::new();
+        for _ in 0..2 {
+            if controller.in_passing_phase() {
+                let seat = PlayerPosition::North;
+                let cards = controller.simple_pass_for(seat).unwrap();
+                controller.submit_pass(seat, cards).unwrap();
+                controller.submit_auto_passes_for_others(seat).unwrap();
+                controller.resolve_passes().unwrap();
+            }
+            let start = RoundSnapshot::capture(controller.match_state.round());
+            let scores = controller.standings();
+            let mut plays = Vec::new();
+            let mut states = vec![start.clone()];
+            while plays.len() < 52 {
+                let seat = controller.expected_to_play();
+                let card = controller.explain_candidates_for(seat)[0].0;
+                controller.play(seat, card).unwrap();
+                plays.push((seat, card));
+                states.push(RoundSnapshot::capture(controller.match_state.round()));
+            }
+            rounds.push(RecordedRound {
+                start,
+                passing_direction: controller.passing_direction(),
+                scores,
+                plays,
+            });
+            expected.push(states);
+            controller.finish_round_if_ready();
+        }
+
+        let mut replay = ReplayController::new(rounds).unwrap();
+        for (round_idx, states) in expected.iter().enumerate() {
+            assert_eq!(replay.round_index(), round_idx);
+            assert_eq!(RoundSnapshot::capture(replay.round()), states[0]);
+            for state in states.iter().skip(1) {
+                assert!(!replay.advice(BotDifficulty::NormalHeuristic).is_empty());
+                assert!(replay.step_forward().is_some());
+                assert_eq!(&RoundSnapshot::capture(replay.round()), state);
+            }
+            assert!(replay.step_forward().is_none());
+            assert!(replay.face_up_hands().iter().all(|hand| hand.is_empty()));
+            for step in (0..states.len() - 1).rev() {
+                assert!(replay.step_back());
+                assert_eq!(RoundSnapshot::capture(replay.round()), states[step]);
+            }
+            assert!(!replay.step_back());
+            assert!(replay.step_forward_trick());
+            assert_eq!(replay.cursor(), 4);
+            assert!(replay.step_back_trick());
+            assert_eq!(replay.cursor(), 0);
+            replay.next_round();
+        }
+    }
+
     #[test]
     fn easy_legacy_pass_returns_first_three() {
         let mut controller = GameController::new_with_seed(Some(42), PlayerPosition::North);
@@ -1106,7 +2553,7 @@ mod tests {
         assert!(hand.len() >= 3);
         let expected = [hand[0], hand[1], hand[2]];
         let actual = controller.simple_pass_for(seat).unwrap();
-        assert_eq!(actual, expected);
+        assert_eq!(actual, expected.into());
     }
 
     #[test]
@@ -1162,6 +2609,50 @@ mod tests {
         assert_eq!(last.think_limit_ms, Some(5));
     }
 
+    #[test]
+    fn search_out_of_time_plays_the_heuristic_pick() {
+        use crate::bot::{DecisionLimit, PlayPlanner, PlayPlannerHard};
+        use std::time::Instant;
+
+        let mut controller = GameController::new_with_seed(Some(2243), PlayerPosition::North);
+        controller.set_bot_difficulty(BotDifficulty::SearchLookahead);
+        if controller.in_passing_phase() {
+            let south_pass = controller.simple_pass_for(PlayerPosition::South).unwrap();
+            controller
+                .submit_pass(PlayerPosition::South, south_pass)
+                .unwrap();
+            controller
+                .submit_auto_passes_for_others(PlayerPosition::South)
+                .unwrap();
+            controller.resolve_passes().unwrap();
+        }
+        assert_eq!(controller.search_fallbacks_this_round(), 0);
+
+        for played in 1..=12 {
+            let seat = controller.expected_to_play();
+            let legal = controller.legal_moves(seat);
+            let (anytime, heuristic) = {
+                let ctx = controller.bot_context(seat);
+                // A budget that is spent before the search starts.
+                let limit = DecisionLimit {
+                    deadline: Some(Instant::now()),
+                    cancel: None,
+                };
+                (
+                    PlayPlannerHard::choose_anytime(&legal, &ctx, Some(&limit)),
+                    PlayPlanner::choose(&legal, &ctx),
+                )
+            };
+            assert!(anytime.fell_back, "play {played}: search had no time");
+            let card = anytime.card.unwrap();
+            assert!(legal.contains(&card), "play {played}: {card} is illegal");
+            assert_eq!(Some(card), heuristic, "play {played}");
+            controller.record_search_fallback();
+            controller.apply_bot_move(seat, card).unwrap();
+        }
+        assert_eq!(controller.search_fallbacks_this_round(), 12);
+    }
+
     #[test]
     fn autoplay_timeout_records_fallback() {
         let mut controller = GameController::new_with_seed(Some(98765), PlayerPosition::North);
@@ -1401,7 +2892,7 @@ mod tests {
         }
 
         assert_eq!(
-            controller.moon_state_for_test(PlayerPosition::South) as u8,
+            controller.moon_state(PlayerPosition::South) as u8,
             crate::bot::MoonState::Considering as u8
         );
 
@@ -1428,7 +2919,7 @@ mod tests {
         }
 
         assert_eq!(
-            controller.moon_state_for_test(PlayerPosition::South) as u8,
+            controller.moon_state(PlayerPosition::South) as u8,
             crate::bot::MoonState::Committed as u8
         );
 
@@ -1452,7 +2943,7 @@ mod tests {
         }
 
         assert_eq!(
-            controller.moon_state_for_test(PlayerPosition::South) as u8,
+            controller.moon_state(PlayerPosition::South) as u8,
             crate::bot::MoonState::Inactive as u8
         );
     }
@@ -1558,7 +3049,7 @@ mod tests {
         }
 
         assert_eq!(
-            controller.moon_state_for_test(PlayerPosition::South) as u8,
+            controller.moon_state(PlayerPosition::South) as u8,
             crate::bot::MoonState::Committed as u8,
             "should remain committed when abort-on-lost-control disabled"
         );
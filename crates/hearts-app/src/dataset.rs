@@ -1,10 +1,14 @@
-use crate::bot::{BotDifficulty, PlayPlanner, PlayPlannerHard, play_bias};
+use crate::bot::analysis::evaluate_plays;
+use crate::bot::{BotDifficulty, PlayPlannerHard, play_bias};
 use crate::controller::GameController;
+use hearts_core::model::card::Card;
 use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::round::RoundState;
 use hearts_core::model::suit::Suit;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct PlayCandidateRecord {
     pub card: String,
     pub base: i32,
@@ -18,7 +22,7 @@ pub struct PlayCandidateRecord {
     pub adviser_bias: i32,
 }
 
-#[derive(Serialize, Default)]
+#[derive(Clone, Serialize, Default)]
 pub struct ContinuationBreakdown {
     pub feed: i32,
     pub self_capture: i32,
@@ -31,7 +35,7 @@ pub struct ContinuationBreakdown {
     pub capped_delta: i32,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct PlaySampleRecord {
     pub seed: u64,
     pub seat: String,
@@ -43,6 +47,18 @@ pub struct PlaySampleRecord {
     pub penalties_on_trick: u8,
     pub belief_entropy_self: f32,
     pub candidates: Vec<PlayCandidateRecord>,
+    pub passing: String,
+    /// [`deal_fingerprint`] of the hands as dealt, before passing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deal: Option<String>,
+    /// Someone shot the moon in the round the sample came from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moon: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split: Option<Split>,
+    /// Set on rows produced by [`crate::augment`], naming the symmetry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symmetry: Option<String>,
 }
 
 pub fn collect_play_sample(
@@ -97,8 +113,8 @@ pub fn collect_play_sample(
             }
         }
         _ => {
-            let explained = PlayPlanner::explain_candidates(&legal, &ctx);
-            for (card, base) in explained.into_iter() {
+            for evaluation in evaluate_plays(&ctx) {
+                let (card, base) = (evaluation.card, evaluation.total);
                 let belief_prob = belief.card_probability(card);
                 let void_in_lead = lead_suit
                     .map(|s| ctx.tracker.is_void(seat, s))
@@ -139,9 +155,203 @@ pub fn collect_play_sample(
         penalties_on_trick,
         belief_entropy_self,
         candidates,
+        passing: round.passing_direction().as_str().to_string(),
+        deal: None,
+        moon: None,
+        split: None,
+        symmetry: None,
     })
 }
 
+/// Stable 64-bit FNV-1a hash of every seat's cards, held or already played,
+/// as 16 hex digits. Passing moves cards between seats, so take it straight
+/// after the deal.
+pub fn deal_fingerprint(round: &RoundState) -> String {
+    let mut cards: Vec<(usize, Card)> = Vec::with_capacity(52);
+    for seat in PlayerPosition::LOOP {
+        cards.extend(round.hand(seat).iter().map(|card| (seat.index(), *card)));
+    }
+    for trick in round.trick_history().iter().chain([round.current_trick()]) {
+        cards.extend(trick.plays().iter().map(|p| (p.position.index(), p.card)));
+    }
+    cards.sort_by_key(|&(seat, card)| (seat, card.suit as u8, card.rank.value()));
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (seat, card) in cards {
+        for byte in [seat as u8, card.suit as u8, card.rank.value()] {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Split {
+    Train,
+    Val,
+    Test,
+}
+
+impl Split {
+    pub const ALL: [Split; 3] = [Split::Train, Split::Val, Split::Test];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Split::Train => "train",
+            Split::Val => "val",
+            Split::Test => "test",
+        }
+    }
+}
+
+/// Share of deals per split, normalised to sum to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SplitRatios {
+    pub train: f64,
+    pub val: f64,
+    pub test: f64,
+}
+
+impl Default for SplitRatios {
+    fn default() -> Self {
+        Self {
+            train: 0.8,
+            val: 0.1,
+            test: 0.1,
+        }
+    }
+}
+
+impl SplitRatios {
+    /// Parses `train,val,test` weights such as `80,10,10` or `0.7,0.15,0.15`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let parts: Vec<f64> = raw
+            .split(',')
+            .map(|part| part.trim().parse::<f64>().ok())
+            .collect::<Option<_>>()?;
+        let [train, val, test] = parts[..] else {
+            return None;
+        };
+        let total = train + val + test;
+        if [train, val, test]
+            .iter()
+            .any(|v| !v.is_finite() || *v < 0.0)
+            || total <= 0.0
+        {
+            return None;
+        }
+        Some(Self {
+            train: train / total,
+            val: val / total,
+            test: test / total,
+        })
+    }
+
+    pub fn share(&self, split: Split) -> f64 {
+        match split {
+            Split::Train => self.train,
+            Split::Val => self.val,
+            Split::Test => self.test,
+        }
+    }
+}
+
+/// A deal listed in a partition manifest, with the strata it was balanced on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DealEntry {
+    pub fingerprint: String,
+    pub passing: String,
+    pub moon: bool,
+}
+
+impl DealEntry {
+    fn stratum(&self) -> (&str, bool) {
+        (&self.passing, self.moon)
+    }
+}
+
+/// Which deals went to which split. Later exports load it and only place
+/// deals it does not list yet, so a deal never changes split between runs.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PartitionManifest {
+    pub ratios: SplitRatios,
+    pub train: Vec<DealEntry>,
+    pub val: Vec<DealEntry>,
+    pub test: Vec<DealEntry>,
+}
+
+impl PartitionManifest {
+    pub fn new(ratios: SplitRatios) -> Self {
+        Self {
+            ratios,
+            ..Self::default()
+        }
+    }
+
+    pub fn entries(&self, split: Split) -> &[DealEntry] {
+        match split {
+            Split::Train => &self.train,
+            Split::Val => &self.val,
+            Split::Test => &self.test,
+        }
+    }
+
+    fn entries_mut(&mut self, split: Split) -> &mut Vec<DealEntry> {
+        match split {
+            Split::Train => &mut self.train,
+            Split::Val => &mut self.val,
+            Split::Test => &mut self.test,
+        }
+    }
+
+    pub fn split_of(&self, fingerprint: &str) -> Option<Split> {
+        Split::ALL.into_iter().find(|split| {
+            self.entries(*split)
+                .iter()
+                .any(|entry| entry.fingerprint == fingerprint)
+        })
+    }
+
+    /// Places every deal not already listed. Within each (passing direction,
+    /// moon) stratum a new deal goes to the split furthest below its target
+    /// share, counting the stratum's existing entries; ties go to train, then
+    /// val. Deals are taken in fingerprint order so the result does not
+    /// depend on the order they were generated in.
+    pub fn extend(&mut self, deals: &[DealEntry]) {
+        let mut listed: HashSet<String> = Split::ALL
+            .into_iter()
+            .flat_map(|split| self.entries(split).iter().map(|e| e.fingerprint.clone()))
+            .collect();
+        let mut fresh: Vec<&DealEntry> = Vec::new();
+        for deal in deals {
+            if listed.insert(deal.fingerprint.clone()) {
+                fresh.push(deal);
+            }
+        }
+        fresh.sort_by(|a, b| a.fingerprint.cmp(&b.fingerprint));
+        for deal in fresh {
+            let counts = Split::ALL.map(|split| {
+                self.entries(split)
+                    .iter()
+                    .filter(|entry| entry.stratum() == deal.stratum())
+                    .count() as f64
+            });
+            let total = counts.iter().sum::<f64>() + 1.0;
+            let mut best = Split::Train;
+            let mut best_deficit = f64::MIN;
+            for (split, count) in Split::ALL.into_iter().zip(counts) {
+                let deficit = self.ratios.share(split) * total - count;
+                if deficit > best_deficit + 1e-9 {
+                    best = split;
+                    best_deficit = deficit;
+                }
+            }
+            self.entries_mut(best).push(deal.clone());
+        }
+    }
+}
+
 trait SuitDisplay {
     fn to_string(self) -> String;
 }
@@ -151,3 +361,119 @@ impl SuitDisplay for Suit {
         format!("{}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{DealEntry, PartitionManifest, Split, SplitRatios, deal_fingerprint};
+    use hearts_core::model::deck::Deck;
+    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::round::RoundState;
+    use std::collections::HashSet;
+
+    fn deals(range: std::ops::Range<u64>) -> Vec<DealEntry> {
+        range
+            .map(|seed| {
+                let passing = PassingDirection::CYCLE[(seed % 4) as usize];
+                let round = RoundState::deal(
+                    &Deck::shuffled_with_seed(seed),
+                    PlayerPosition::North,
+                    passing,
+                );
+                DealEntry {
+                    fingerprint: deal_fingerprint(&round),
+                    passing: passing.as_str().to_string(),
+                    moon: seed % 9 == 0,
+                }
+            })
+            .collect()
+    }
+
+    fn assert_disjoint(manifest: &PartitionManifest) -> usize {
+        let mut seen = HashSet::new();
+        for split in Split::ALL {
+            for entry in manifest.entries(split) {
+                assert!(
+                    seen.insert(entry.fingerprint.clone()),
+                    "{} listed twice",
+                    entry.fingerprint
+                );
+            }
+        }
+        seen.len()
+    }
+
+    #[test]
+    fn ratios_parse_and_normalise() {
+        let ratios = SplitRatios::parse("70, 20, 10").unwrap();
+        assert!((ratios.train - 0.7).abs() < 1e-9);
+        assert!((ratios.test - 0.1).abs() < 1e-9);
+        assert_eq!(SplitRatios::parse("0.8,0.2"), None);
+        assert_eq!(SplitRatios::parse("1,-1,1"), None);
+        assert_eq!(SplitRatios::parse("0,0,0"), None);
+    }
+
+    #[test]
+    fn fingerprint_is_stable_and_tells_deals_apart() {
+        let round = |seed| {
+            RoundState::deal(
+                &Deck::shuffled_with_seed(seed),
+                PlayerPosition::North,
+                PassingDirection::Left,
+            )
+        };
+        assert_eq!(deal_fingerprint(&round(5)), deal_fingerprint(&round(5)));
+        assert_ne!(deal_fingerprint(&round(5)), deal_fingerprint(&round(6)));
+        assert_eq!(deal_fingerprint(&round(5)).len(), 16);
+    }
+
+    #[test]
+    fn splits_are_disjoint_and_strata_track_ratios() {
+        let ratios = SplitRatios::default();
+        let mut manifest = PartitionManifest::new(ratios);
+        let batch = deals(0..720);
+        manifest.extend(&batch);
+        manifest.extend(&batch);
+        assert_eq!(assert_disjoint(&manifest), 720);
+
+        let mut strata: Vec<(String, bool)> =
+            batch.iter().map(|d| (d.passing.clone(), d.moon)).collect();
+        strata.sort();
+        strata.dedup();
+        for (passing, moon) in strata {
+            let in_stratum = |split| {
+                manifest
+                    .entries(split)
+                    .iter()
+                    .filter(|e| e.passing == passing && e.moon == moon)
+                    .count() as f64
+            };
+            let total: f64 = Split::ALL.into_iter().map(in_stratum).sum();
+            for split in Split::ALL {
+                let share = in_stratum(split) / total;
+                assert!(
+                    (share - ratios.share(split)).abs() <= 1.0 / total + 1e-9,
+                    "{passing}/{moon} {split:?}: {share:.3} of {total}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn extending_keeps_earlier_assignments() {
+        let mut manifest = PartitionManifest::new(SplitRatios::parse("60,20,20").unwrap());
+        let first = deals(0..200);
+        manifest.extend(&first);
+        let before: Vec<Option<Split>> = first
+            .iter()
+            .map(|d| manifest.split_of(&d.fingerprint))
+            .collect();
+
+        // The later run regenerates some old deals next to new ones.
+        manifest.extend(&deals(150..400));
+        assert_eq!(assert_disjoint(&manifest), 400);
+        for (deal, split) in first.iter().zip(before) {
+            assert_eq!(manifest.split_of(&deal.fingerprint), split);
+        }
+    }
+}
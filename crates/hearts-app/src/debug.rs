@@ -1,9 +1,31 @@
 //! Shared debug utilities for MDHearts
 //!
 //! This module consolidates debug logging functionality that was previously
-//! duplicated across multiple modules.
+//! duplicated across multiple modules, the data behind the GUI debug
+//! overlay, and the `--debug-deal` text REPL.
 
-use std::sync::OnceLock;
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use crate::bot::analysis::evaluate_plays;
+use crate::bot::{
+    BotContext, BotDifficulty, PassPlanner, PlayPlanner, PlayPlannerHard, RolloutPolicy,
+    UnseenTracker,
+};
+use hearts_core::game::match_state::MatchState;
+use hearts_core::model::card::Card;
+use hearts_core::model::passing::PassCards;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rank::Rank;
+use hearts_core::model::round::{PlayOutcome, RoundPhase, RoundState};
+use hearts_core::model::score::ScoreBoard;
+use hearts_core::model::suit::Suit;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, Write};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
 
 /// Returns true if debug logging is enabled via MDH_DEBUG_LOGS environment variable.
 ///
@@ -18,9 +40,677 @@ pub fn debug_enabled() -> bool {
     })
 }
 
+/// Returns true if the GUI debug overlay is enabled via MDH_DEBUG_OVERLAY.
+///
+/// Accepts the same truthy values as [`debug_enabled`]; cached after first check.
+pub fn debug_overlay_enabled() -> bool {
+    static ON: OnceLock<bool> = OnceLock::new();
+    *ON.get_or_init(|| {
+        std::env::var("MDH_DEBUG_OVERLAY")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("on"))
+            .unwrap_or(false)
+    })
+}
+
+const EVAL_BAR_DEFAULT_ROLLOUTS: usize = 24;
+const EVAL_BAR_MAX_ROLLOUTS: usize = 512;
+/// Chance (percent) that a rollout seat plays a random legal card instead of
+/// the heuristic choice, so the rollouts don't all follow one line.
+const EVAL_BAR_EXPLORE_PCT: u32 = 15;
+
+fn eval_bar_rollouts() -> usize {
+    std::env::var("MDH_EVAL_BAR_ROLLOUTS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(EVAL_BAR_DEFAULT_ROLLOUTS)
+        .clamp(1, EVAL_BAR_MAX_ROLLOUTS)
+}
+
+/// Expected points each seat adds this round, from the true (all hands
+/// visible) state.
+///
+/// Plays out `MDH_EVAL_BAR_ROLLOUTS` rollouts (default 24) with the Normal
+/// heuristic planner plus a little seeded randomness, and averages the round
+/// scores with the moon rule applied. Deterministic for a given state and
+/// standings. Returns zeros outside the play phase.
+pub fn eval_bar(round: &RoundState, scores: &ScoreBoard) -> [f32; 4] {
+    eval_bar_with(round, scores, eval_bar_rollouts())
+}
+
+fn eval_bar_with(round: &RoundState, scores: &ScoreBoard, rollouts: usize) -> [f32; 4] {
+    if !matches!(round.phase(), RoundPhase::Playing) {
+        return [0.0; 4];
+    }
+    let mut tracker = UnseenTracker::new();
+    tracker.reset_for_round(round);
+    let seed = state_key(round, scores);
+    let mut totals = [0u64; 4];
+    for idx in 0..rollouts {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(idx as u64));
+        let penalties = rollout(round.clone(), tracker.clone(), scores, &mut rng);
+        let mut delta = ScoreBoard::new();
+        delta.apply_hand(penalties);
+        for (total, points) in totals.iter_mut().zip(delta.standings()) {
+            *total += u64::from(*points);
+        }
+    }
+    totals.map(|t| t as f32 / rollouts as f32)
+}
+
+fn rollout(
+    mut round: RoundState,
+    mut tracker: UnseenTracker,
+    scores: &ScoreBoard,
+    rng: &mut StdRng,
+) -> [u8; 4] {
+    let passing = round.passing_direction();
+    while round.tricks_completed() < 13 {
+        let trick = round.current_trick();
+        let seat = trick
+            .plays()
+            .last()
+            .map(|p| p.position.next())
+            .unwrap_or(trick.leader());
+        let lead_suit = trick.lead_suit();
+        let legal = round.legal_plays(seat);
+        let Some(&fallback) = legal.first() else {
+            break;
+        };
+        let card = if legal.len() > 1 && rng.gen_range(0..100) < EVAL_BAR_EXPLORE_PCT {
+            legal[rng.gen_range(0..legal.len())]
+        } else {
+            let ctx = BotContext::new(
+                seat,
+                &round,
+                *scores,
+                passing,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            PlayPlanner::choose(&legal, &ctx).unwrap_or(fallback)
+        };
+        if round.play_card(seat, card).is_err() {
+            break;
+        }
+        tracker.note_card_played(seat, card);
+        if let Some(lead) = lead_suit
+            && card.suit != lead
+        {
+            tracker.note_void(seat, lead);
+        }
+    }
+    round.penalty_totals()
+}
+
+/// Hash of everything the evaluation depends on: the cards played so far (in
+/// order) and the standings.
+fn state_key(round: &RoundState, scores: &ScoreBoard) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for trick in round
+        .trick_history()
+        .iter()
+        .chain(std::iter::once(round.current_trick()))
+    {
+        for play in trick.plays() {
+            (play.position.index(), play.card).hash(&mut hasher);
+        }
+    }
+    scores.standings().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Default)]
+struct EvalBarSlot {
+    key: Option<u64>,
+    values: Option<[f32; 4]>,
+    fresh: bool,
+}
+
+/// Cached [`eval_bar`] for the UI: [`EvalBar::refresh`] starts a background
+/// evaluation when the state changes and never blocks; results for a state
+/// that has since moved on are dropped.
+#[derive(Default)]
+pub struct EvalBar {
+    slot: Arc<Mutex<EvalBarSlot>>,
+}
+
+impl EvalBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues an evaluation of `round` unless one for this state is already
+    /// cached or running.
+    pub fn refresh(&self, round: &RoundState, scores: &ScoreBoard) {
+        let key = state_key(round, scores);
+        {
+            let Ok(mut slot) = self.slot.lock() else {
+                return;
+            };
+            if slot.key == Some(key) {
+                return;
+            }
+            slot.key = Some(key);
+        }
+        let slot = Arc::clone(&self.slot);
+        let round = round.clone();
+        let scores = *scores;
+        thread::spawn(move || {
+            let values = eval_bar(&round, &scores);
+            if let Ok(mut slot) = slot.lock()
+                && slot.key == Some(key)
+            {
+                slot.values = Some(values);
+                slot.fresh = true;
+            }
+        });
+    }
+
+    /// Latest finished evaluation; may lag the live state by a play or two.
+    pub fn latest(&self) -> Option<[f32; 4]> {
+        self.slot.lock().ok().and_then(|slot| slot.values)
+    }
+
+    /// True once per finished evaluation, so the UI knows to repaint.
+    pub fn take_fresh(&self) -> bool {
+        self.slot
+            .lock()
+            .map(|mut slot| std::mem::take(&mut slot.fresh))
+            .unwrap_or(false)
+    }
+}
+
+const REPL_HELP: &str = "Commands:
+  hands                    show every hand, the trick so far and the scores
+  step [n]                 let the bot make the next n decisions (default 1), with its candidate scores
+  play <card>              force <card> for the seat to play, e.g. play QS
+  goto <trick>             let the bots play until trick <trick> (1-13) is about to start
+  flag [NAME [value|off]]  list MDH_* variables, or set/clear MDH_NAME (weights are read once per process)
+  belief [seat]            dump the belief matrix for a seat (default: the seat to play)
+  help                     show this list
+  quit                     leave";
+
+/// A deal under the `--debug-deal` REPL. Works on the round, tracker and
+/// planners directly, without a `GameController`: passes are made by the bots
+/// up front, then every play goes through [`DebugSession::step`] or
+/// [`DebugSession::force`].
+pub struct DebugSession {
+    seed: u64,
+    round: RoundState,
+    scores: ScoreBoard,
+    tracker: UnseenTracker,
+    difficulty: BotDifficulty,
+}
+
+impl DebugSession {
+    pub fn new(seed: u64, difficulty: BotDifficulty) -> Self {
+        let state = MatchState::with_seed(PlayerPosition::North, seed);
+        let mut round = state.round().clone();
+        let scores = *state.scores();
+        let mut tracker = UnseenTracker::new();
+        tracker.reset_for_round(&round);
+        let passing = round.passing_direction();
+        if matches!(round.phase(), RoundPhase::Passing(_)) {
+            let mut picks = [None; 4];
+            for seat in PlayerPosition::LOOP {
+                let ctx = BotContext::new(seat, &round, scores, passing, &tracker, difficulty);
+                picks[seat.index()] = PassPlanner::choose(round.hand(seat), &ctx).or_else(|| {
+                    let cards = round.hand(seat).cards();
+                    cards
+                        .get(..round.pass_count())
+                        .and_then(PassCards::from_slice)
+                });
+            }
+            for seat in PlayerPosition::LOOP {
+                if let Some(cards) = picks[seat.index()] {
+                    let _ = round.submit_pass(seat, cards);
+                }
+            }
+            if round.resolve_passes().is_ok() {
+                for giver in PlayerPosition::LOOP {
+                    if let Some(cards) = picks[giver.index()] {
+                        tracker.note_pass_received(passing.target(giver), giver, cards);
+                    }
+                }
+            }
+        }
+        Self {
+            seed,
+            round,
+            scores,
+            tracker,
+            difficulty,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn round(&self) -> &RoundState {
+        &self.round
+    }
+
+    fn to_play(&self) -> PlayerPosition {
+        let trick = self.round.current_trick();
+        trick
+            .plays()
+            .last()
+            .map(|p| p.position.next())
+            .unwrap_or(trick.leader())
+    }
+
+    fn finished(&self) -> bool {
+        self.round.tricks_completed() >= 13
+    }
+
+    /// Reads commands from `input` until `quit` or end of input.
+    pub fn run(&mut self, input: impl BufRead, out: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "Debugging seed {} ({:?}, passing {}). Type 'help' for commands.",
+            self.seed,
+            self.difficulty,
+            self.round.passing_direction().as_str()
+        )?;
+        self.show(out)?;
+        for line in input.lines() {
+            let line = line?;
+            if !self.execute(line.trim(), out)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs one command; `false` once the user quits.
+    pub fn execute(&mut self, line: &str, out: &mut impl Write) -> io::Result<bool> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            return Ok(true);
+        };
+        let arg = words.next();
+        match command {
+            "quit" | "exit" | "q" => return Ok(false),
+            "help" | "?" => writeln!(out, "{REPL_HELP}")?,
+            "hands" | "show" => self.show(out)?,
+            "step" | "s" => {
+                let n = arg.and_then(|a| a.parse::<usize>().ok()).unwrap_or(1);
+                for _ in 0..n {
+                    if !self.step(out, true)? {
+                        break;
+                    }
+                }
+            }
+            "play" | "p" => match arg {
+                Some(code) => self.force(code, out)?,
+                None => writeln!(out, "usage: play <card>")?,
+            },
+            "goto" => match arg.and_then(|a| a.parse::<usize>().ok()) {
+                Some(trick @ 1..=13) => {
+                    while self.round.tricks_completed() + 1 < trick && self.step(out, false)? {}
+                    writeln!(out, "At trick {}.", self.round.tricks_completed() + 1)?;
+                }
+                _ => writeln!(out, "usage: goto <trick 1-13>")?,
+            },
+            "flag" => self.flag(arg, words.next(), out)?,
+            "belief" => {
+                let seat = match arg {
+                    Some(raw) => match parse_seat_name(raw) {
+                        Some(seat) => seat,
+                        None => {
+                            writeln!(out, "unknown seat '{raw}'")?;
+                            return Ok(true);
+                        }
+                    },
+                    None => self.to_play(),
+                };
+                self.belief(seat, out)?;
+            }
+            other => writeln!(out, "unknown command '{other}' (try 'help')")?,
+        }
+        Ok(true)
+    }
+
+    fn show(&self, out: &mut impl Write) -> io::Result<()> {
+        for seat in PlayerPosition::LOOP {
+            let marker = if !self.finished() && seat == self.to_play() {
+                " <- to play"
+            } else {
+                ""
+            };
+            let hand = self.round.hand(seat);
+            let cards = if hand.is_empty() {
+                "-".to_string()
+            } else {
+                hand.to_string()
+            };
+            writeln!(out, "{:<5} {}{}", seat.to_string(), cards, marker)?;
+        }
+        let plays: Vec<String> = self
+            .round
+            .current_trick()
+            .plays()
+            .iter()
+            .map(|p| format!("{}:{}", p.position, p.card))
+            .collect();
+        writeln!(
+            out,
+            "Trick {}: {}",
+            (self.round.tricks_completed() + 1).min(13),
+            if plays.is_empty() {
+                "(empty)".to_string()
+            } else {
+                plays.join(" ")
+            }
+        )?;
+        writeln!(
+            out,
+            "Penalties {:?} | scores {:?} | hearts broken: {}",
+            self.round.penalty_totals(),
+            self.scores.standings(),
+            self.round.hearts_broken()
+        )
+    }
+
+    /// Candidate scores for `seat`, best first.
+    fn explain(&self, seat: PlayerPosition, legal: &[Card]) -> Vec<(Card, i32)> {
+        let ctx = self.context(seat);
+        let mut scored = match self.difficulty {
+            BotDifficulty::EasyLegacy => legal.iter().map(|&card| (card, 0)).collect(),
+            BotDifficulty::FutureHard | BotDifficulty::SearchLookahead => {
+                PlayPlannerHard::explain_candidates(legal, &ctx)
+            }
+            BotDifficulty::Rollout => RolloutPolicy::new(self.seed).explain_candidates(legal, &ctx),
+            BotDifficulty::NormalHeuristic => evaluate_plays(&ctx)
+                .into_iter()
+                .map(|evaluation| (evaluation.card, evaluation.total))
+                .collect(),
+        };
+        scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        scored
+    }
+
+    fn context(&self, seat: PlayerPosition) -> BotContext<'_> {
+        BotContext::new(
+            seat,
+            &self.round,
+            self.scores,
+            self.round.passing_direction(),
+            &self.tracker,
+            self.difficulty,
+        )
+    }
+
+    /// One bot decision; `false` when the round is over.
+    fn step(&mut self, out: &mut impl Write, verbose: bool) -> io::Result<bool> {
+        if self.finished() {
+            writeln!(out, "The round is over.")?;
+            return Ok(false);
+        }
+        let seat = self.to_play();
+        let legal = self.round.legal_plays(seat);
+        let two_clubs = Card::new(Rank::Two, Suit::Clubs);
+        let card = if legal.contains(&two_clubs) && self.round.is_first_trick() {
+            two_clubs
+        } else {
+            let ctx = self.context(seat);
+            let choice = match self.difficulty {
+                BotDifficulty::EasyLegacy => None,
+                BotDifficulty::FutureHard | BotDifficulty::SearchLookahead => {
+                    PlayPlannerHard::choose(&legal, &ctx)
+                }
+                BotDifficulty::Rollout => RolloutPolicy::new(self.seed).choose(&legal, &ctx),
+                BotDifficulty::NormalHeuristic => PlayPlanner::choose(&legal, &ctx),
+            };
+            match choice.or_else(|| legal.first().copied()) {
+                Some(card) => card,
+                None => {
+                    writeln!(out, "{seat} has no legal card.")?;
+                    return Ok(false);
+                }
+            }
+        };
+        if verbose {
+            let scored = self.explain(seat, &legal);
+            let text: Vec<String> = scored
+                .iter()
+                .map(|(card, score)| format!("{card}={score}"))
+                .collect();
+            writeln!(out, "{seat} candidates: {}", text.join(" "))?;
+            if self.difficulty == BotDifficulty::NormalHeuristic
+                && let Some(evaluation) = evaluate_plays(&self.context(seat))
+                    .into_iter()
+                    .find(|evaluation| evaluation.card == card)
+            {
+                writeln!(out, "  {card}: {}", evaluation.parts_text())?;
+            }
+        }
+        self.apply(seat, card, out)?;
+        Ok(true)
+    }
+
+    fn force(&mut self, code: &str, out: &mut impl Write) -> io::Result<()> {
+        if self.finished() {
+            return writeln!(out, "The round is over.");
+        }
+        let seat = self.to_play();
+        let Some(card) = self
+            .round
+            .hand(seat)
+            .iter()
+            .copied()
+            .find(|card| card.to_string().eq_ignore_ascii_case(code))
+        else {
+            return writeln!(out, "{seat} does not hold '{code}'");
+        };
+        if !self.round.can_play_card(seat, card) {
+            return writeln!(out, "{card} is not legal for {seat} here");
+        }
+        self.apply(seat, card, out)
+    }
+
+    fn apply(&mut self, seat: PlayerPosition, card: Card, out: &mut impl Write) -> io::Result<()> {
+        let before: Vec<(PlayerPosition, Card)> = self
+            .round
+            .current_trick()
+            .plays()
+            .iter()
+            .map(|p| (p.position, p.card))
+            .collect();
+        let outcome = match self.round.play_card(seat, card) {
+            Ok(outcome) => outcome,
+            Err(err) => return writeln!(out, "{seat} cannot play {card}: {err:?}"),
+        };
+        self.tracker.note_card_played(seat, card);
+        if let Some(&(_, lead)) = before.first()
+            && card.suit != lead.suit
+        {
+            self.tracker.note_void(seat, lead.suit);
+        }
+        writeln!(out, "{seat} plays {card}")?;
+        if let PlayOutcome::TrickCompleted { winner, penalties } = outcome {
+            let mut plays = before;
+            plays.push((seat, card));
+            self.tracker.note_trick_completion(
+                &plays,
+                winner,
+                penalties,
+                self.round.hearts_broken(),
+            );
+            writeln!(out, "{winner} wins the trick ({penalties} points)")?;
+            if self.finished() {
+                writeln!(
+                    out,
+                    "Round over: penalties {:?}",
+                    self.round.penalty_totals()
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flag(
+        &self,
+        name: Option<&str>,
+        value: Option<&str>,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let Some(name) = name else {
+            let mut vars: Vec<(String, String)> = std::env::vars()
+                .filter(|(key, _)| key.starts_with("MDH_"))
+                .collect();
+            vars.sort();
+            if vars.is_empty() {
+                writeln!(out, "(no MDH_* variables set)")?;
+            }
+            for (key, value) in vars {
+                writeln!(out, "{key}={value}")?;
+            }
+            return Ok(());
+        };
+        let upper = name.to_ascii_uppercase();
+        let key = if upper.starts_with("MDH_") {
+            upper
+        } else {
+            format!("MDH_{upper}")
+        };
+        match value {
+            Some("off") => {
+                unsafe { std::env::remove_var(&key) }
+                writeln!(out, "{key} cleared")
+            }
+            other => {
+                let value = other.unwrap_or("1");
+                unsafe { std::env::set_var(&key, value) }
+                writeln!(out, "{key}={value}")
+            }
+        }
+    }
+
+    fn belief(&self, seat: PlayerPosition, out: &mut impl Write) -> io::Result<()> {
+        let belief = self.tracker.belief_state(seat);
+        writeln!(
+            out,
+            "Belief for {seat} (entropy {:.3}, moon {:.2}):",
+            belief.entropy(),
+            belief.moon_likelihood()
+        )?;
+        let header: Vec<String> = Rank::ORDERED
+            .iter()
+            .map(|r| format!("{:>5}", r.to_string()))
+            .collect();
+        writeln!(out, "  {}", header.join(""))?;
+        for suit in Suit::ALL {
+            let row: Vec<String> = Rank::ORDERED
+                .iter()
+                .map(|&rank| format!("{:>5.2}", belief.card_probability(Card::new(rank, suit))))
+                .collect();
+            writeln!(out, "{suit} {}", row.join(""))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_seat_name(raw: &str) -> Option<PlayerPosition> {
+    match raw.to_ascii_lowercase().as_str() {
+        "n" | "north" => Some(PlayerPosition::North),
+        "e" | "east" => Some(PlayerPosition::East),
+        "s" | "south" => Some(PlayerPosition::South),
+        "w" | "west" => Some(PlayerPosition::West),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Note: We don't test debug_enabled() directly because it reads env vars
     // and uses OnceLock caching which makes it difficult to test in isolation.
     // The function is simple enough that visual inspection suffices.
+    use super::{DebugSession, EvalBar, eval_bar, eval_bar_with};
+    use crate::bot::BotDifficulty;
+    use hearts_core::model::deck::Deck;
+    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::round::RoundState;
+    use hearts_core::model::score::ScoreBoard;
+    use std::time::{Duration, Instant};
+
+    fn deal(seed: u64, passing: PassingDirection) -> RoundState {
+        RoundState::deal(
+            &Deck::shuffled_with_seed(seed),
+            PlayerPosition::North,
+            passing,
+        )
+    }
+
+    #[test]
+    fn eval_bar_is_zero_outside_play() {
+        let round = deal(3, PassingDirection::Left);
+        assert_eq!(eval_bar_with(&round, &ScoreBoard::new(), 4), [0.0; 4]);
+    }
+
+    #[test]
+    fn each_rollout_hands_out_a_whole_round_of_points() {
+        let scores = ScoreBoard::new();
+        for seed in 0..6 {
+            let round = deal(seed, PassingDirection::Hold);
+            let sum: f32 = eval_bar_with(&round, &scores, 1).iter().sum();
+            assert!(sum == 26.0 || sum == 78.0, "seed {seed}: {sum}");
+        }
+    }
+
+    #[test]
+    fn eval_bar_is_deterministic() {
+        let round = deal(9, PassingDirection::Hold);
+        let scores = ScoreBoard::new();
+        assert_eq!(
+            eval_bar_with(&round, &scores, 6),
+            eval_bar_with(&round, &scores, 6)
+        );
+    }
+
+    #[test]
+    fn eval_bar_cache_fills_in_background() {
+        let round = deal(5, PassingDirection::Hold);
+        let scores = ScoreBoard::new();
+        let bar = EvalBar::new();
+        bar.refresh(&round, &scores);
+        bar.refresh(&round, &scores);
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while bar.latest().is_none() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(bar.latest(), Some(eval_bar(&round, &scores)));
+        assert!(bar.take_fresh());
+        assert!(!bar.take_fresh());
+    }
+
+    #[test]
+    fn scripted_debug_session_steps_forces_and_dumps_beliefs() {
+        let mut session = DebugSession::new(42, BotDifficulty::NormalHeuristic);
+        assert!(matches!(
+            session.round().phase(),
+            hearts_core::model::round::RoundPhase::Playing
+        ));
+        let two_clubs = session
+            .round()
+            .holder_of(hearts_core::model::card::Card::new(
+                hearts_core::model::rank::Rank::Two,
+                hearts_core::model::suit::Suit::Clubs,
+            ))
+            .unwrap();
+        let script = "hands\nplay 9Z\nstep\nstep 2\nbelief east\ngoto 3\nflag debug_repl_probe on\nbogus\nquit\nstep\n";
+        let mut out = Vec::new();
+        session.run(script.as_bytes(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Debugging seed 42"), "{text}");
+        assert!(text.contains("<- to play"));
+        assert!(text.contains("does not hold '9Z'"));
+        assert!(text.contains(&format!("{two_clubs} plays 2C")), "{text}");
+        assert!(text.contains("candidates: "));
+        assert!(text.contains("Belief for East (entropy"));
+        assert!(text.contains("At trick 3."));
+        assert!(text.contains("MDH_DEBUG_REPL_PROBE=on"));
+        assert!(text.contains("unknown command 'bogus'"));
+        assert_eq!(session.round().tricks_completed(), 2);
+        unsafe { std::env::remove_var("MDH_DEBUG_REPL_PROBE") }
+    }
 }
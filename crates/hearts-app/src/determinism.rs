@@ -0,0 +1,249 @@
+//! Decision streams for determinism goldens: every pass and play of seeded
+//! self-play rounds in one record format, compared against a blessed file.
+
+use crate::bot::BotDifficulty;
+use crate::controller::GameController;
+use crate::persist::{Migration, Persisted};
+use crate::session::{GameSession, SessionCommand, SessionEvent};
+use hearts_core::model::card::Card;
+use hearts_core::model::player::PlayerPosition;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+/// v2 renamed the v1 `version` field to the shared `schema_version`.
+pub const GOLDEN_VERSION: u32 = 2;
+
+/// Seat mix in N,E,S,W order using `e|n|h|s|r`, as in `--match-mixed`.
+pub fn parse_mix(mix: &str) -> Option<[BotDifficulty; 4]> {
+    let chars: Vec<char> = mix.chars().collect();
+    if chars.len() != 4 {
+        return None;
+    }
+    let mut diffs = [BotDifficulty::NormalHeuristic; 4];
+    for (slot, c) in diffs.iter_mut().zip(chars) {
+        *slot = match c.to_ascii_lowercase() {
+            'e' => BotDifficulty::EasyLegacy,
+            'n' => BotDifficulty::NormalHeuristic,
+            'h' => BotDifficulty::FutureHard,
+            's' => BotDifficulty::SearchLookahead,
+            'r' => BotDifficulty::Rollout,
+            _ => return None,
+        };
+    }
+    Some(diffs)
+}
+
+pub(crate) fn seat_letter(seat: PlayerPosition) -> char {
+    match seat {
+        PlayerPosition::North => 'N',
+        PlayerPosition::East => 'E',
+        PlayerPosition::South => 'S',
+        PlayerPosition::West => 'W',
+    }
+}
+
+/// Every decision of one round.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundDecisions {
+    pub seed: u64,
+    /// `"N:QS KH 2D"`-style pass per seat in N,E,S,W order; empty on hold hands.
+    pub passes: Vec<String>,
+    /// `"N:2C"`-style plays in the order they were made.
+    pub plays: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionGolden {
+    pub schema_version: u32,
+    pub mix: String,
+    pub seed_start: u64,
+    pub count: u64,
+    pub rounds: Vec<RoundDecisions>,
+}
+
+fn golden_v1_to_v2(map: &mut Map<String, Value>) -> Result<(), String> {
+    crate::persist::rename_key(map, "version", crate::persist::SCHEMA_KEY);
+    Ok(())
+}
+
+impl Persisted for DecisionGolden {
+    const SCHEMA_VERSION: u32 = GOLDEN_VERSION;
+
+    fn migrations() -> &'static [Migration] {
+        &[Migration {
+            from: 1,
+            apply: golden_v1_to_v2,
+        }]
+    }
+}
+
+fn play_text(seat: PlayerPosition, cards: &[Card]) -> String {
+    let cards: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+    format!("{}:{}", seat_letter(seat), cards.join(" "))
+}
+
+/// Plays the first round of the match for `seed`, each seat at its own
+/// difficulty, recording every pass and play from the session journal.
+pub fn record_round(seed: u64, diffs: [BotDifficulty; 4]) -> RoundDecisions {
+    let controller = GameController::new_with_seed(Some(seed), PlayerPosition::North);
+    let mut session = GameSession::new(controller).with_difficulties(diffs);
+    if session.controller().in_passing_phase() {
+        let _ = session.apply(SessionCommand::BotPasses { except: None });
+        let _ = session.apply(SessionCommand::ResolvePasses);
+    }
+    while session.controller().round().tricks_completed() < 13 {
+        match session.apply(SessionCommand::BotTurn).as_deref() {
+            Ok([SessionEvent::CardPlayed { .. }, ..]) => {}
+            _ => break,
+        }
+    }
+    let mut passes = Vec::new();
+    let mut plays = Vec::with_capacity(52);
+    for event in session.journal() {
+        match event {
+            SessionEvent::PassSubmitted { seat, cards } => passes.push(play_text(*seat, cards)),
+            SessionEvent::CardPlayed { seat, card } => plays.push(play_text(*seat, &[*card])),
+            _ => {}
+        }
+    }
+    RoundDecisions {
+        seed,
+        passes,
+        plays,
+    }
+}
+
+pub fn record_golden(mix: &str, seed_start: u64, count: u64) -> Result<DecisionGolden, String> {
+    let diffs = parse_mix(mix).ok_or_else(|| format!("invalid mix '{mix}' (use e|n|h|s|r x4)"))?;
+    let rounds = (seed_start..seed_start + count)
+        .map(|seed| record_round(seed, diffs))
+        .collect();
+    Ok(DecisionGolden {
+        schema_version: GOLDEN_VERSION,
+        mix: mix.to_string(),
+        seed_start,
+        count,
+        rounds,
+    })
+}
+
+/// First place the recorded decisions differ, described for a test failure.
+pub fn first_divergence(expected: &DecisionGolden, actual: &DecisionGolden) -> Option<String> {
+    if expected.mix != actual.mix
+        || expected.seed_start != actual.seed_start
+        || expected.count != actual.count
+    {
+        return Some(format!(
+            "run parameters differ: expected mix {} seeds {}+{}, got mix {} seeds {}+{}",
+            expected.mix,
+            expected.seed_start,
+            expected.count,
+            actual.mix,
+            actual.seed_start,
+            actual.count
+        ));
+    }
+    for (want, got) in expected.rounds.iter().zip(&actual.rounds) {
+        let streams = [
+            ("pass", &want.passes, &got.passes),
+            ("play", &want.plays, &got.plays),
+        ];
+        for (kind, want_list, got_list) in streams {
+            let max = want_list.len().max(got_list.len());
+            for idx in 0..max {
+                let (a, b) = (want_list.get(idx), got_list.get(idx));
+                if a != b {
+                    return Some(format!(
+                        "seed {} {kind} #{}: expected {}, got {}",
+                        want.seed,
+                        idx + 1,
+                        a.map_or("(none)", String::as_str),
+                        b.map_or("(none)", String::as_str)
+                    ));
+                }
+            }
+        }
+    }
+    if expected.rounds.len() != actual.rounds.len() {
+        return Some(format!(
+            "expected {} rounds, got {}",
+            expected.rounds.len(),
+            actual.rounds.len()
+        ));
+    }
+    None
+}
+
+/// Loads a golden, migrating files blessed at an older version.
+pub fn load_golden(path: &Path) -> Result<DecisionGolden, String> {
+    crate::persist::load_with_migrations(path).map_err(|err| err.to_string())
+}
+
+/// Re-records the golden's seeds and mix and reports the first divergence.
+pub fn check_golden(path: &Path) -> Result<(), String> {
+    let expected = load_golden(path)?;
+    let actual = record_golden(&expected.mix, expected.seed_start, expected.count)?;
+    match first_divergence(&expected, &actual) {
+        Some(diff) => Err(diff),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecisionGolden, GOLDEN_VERSION, RoundDecisions, first_divergence, parse_mix};
+    use crate::bot::BotDifficulty;
+
+    fn golden(plays: &[&str]) -> DecisionGolden {
+        DecisionGolden {
+            schema_version: GOLDEN_VERSION,
+            mix: "nnnn".to_string(),
+            seed_start: 3,
+            count: 1,
+            rounds: vec![RoundDecisions {
+                seed: 3,
+                passes: vec!["N:QS KH 2D".to_string()],
+                plays: plays.iter().map(|p| p.to_string()).collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn parse_mix_reads_seat_letters() {
+        assert_eq!(
+            parse_mix("enHS"),
+            Some([
+                BotDifficulty::EasyLegacy,
+                BotDifficulty::NormalHeuristic,
+                BotDifficulty::FutureHard,
+                BotDifficulty::SearchLookahead,
+            ])
+        );
+        assert_eq!(parse_mix("nnn"), None);
+        assert_eq!(parse_mix("nnnx"), None);
+    }
+
+    #[test]
+    fn divergence_points_at_the_first_changed_decision() {
+        let expected = golden(&["E:2C", "S:5C", "W:KC", "N:AC"]);
+        assert_eq!(first_divergence(&expected, &expected.clone()), None);
+        let changed = golden(&["E:2C", "S:5C", "W:9C", "N:AC"]);
+        assert_eq!(
+            first_divergence(&expected, &changed).as_deref(),
+            Some("seed 3 play #3: expected W:KC, got W:9C")
+        );
+        let short = golden(&["E:2C", "S:5C"]);
+        assert_eq!(
+            first_divergence(&expected, &short).as_deref(),
+            Some("seed 3 play #3: expected W:KC, got (none)")
+        );
+        let mut other_seeds = expected.clone();
+        other_seeds.seed_start = 4;
+        assert!(
+            first_divergence(&expected, &other_seeds)
+                .unwrap()
+                .starts_with("run parameters differ")
+        );
+    }
+}
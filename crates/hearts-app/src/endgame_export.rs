@@ -159,6 +159,7 @@ impl EndgameExport {
             phase: Some(match round.phase() {
                 RoundPhase::Playing => "Playing".to_string(),
                 RoundPhase::Passing(_) => "Passing".to_string(),
+                RoundPhase::Complete => "Complete".to_string(),
             }),
             hearts_broken: round.hearts_broken(),
             leader: format!("{:?}", round.current_trick().leader()),
@@ -0,0 +1,129 @@
+//! The `<candidate>+<count><field>` mix shorthand, e.g. `h+3n`: one candidate
+//! agent at the run's seat and a field of identical baseline opponents in the
+//! other seats.
+//!
+//! The shorthand expands to an ordinary N,E,S,W mix string before the mix is
+//! validated, so a field that does not fill exactly three seats fails the
+//! usual four-agent check. Field agents get distinct names (`baseline-1` to
+//! `baseline-3`, in seat order) in the run metadata, or share the single
+//! `baseline` name when grouped.
+
+use crate::agent_id::AgentId;
+use crate::bot::BotDifficulty;
+use hearts_core::model::player::PlayerPosition;
+
+/// Name shared by every field agent when they are grouped.
+pub const FIELD_GROUP: &str = "baseline";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldMix {
+    pub candidate: char,
+    pub field: char,
+    pub count: usize,
+}
+
+impl FieldMix {
+    /// Parses the shorthand; `None` for anything else, including plain
+    /// four-letter mixes. Agent letters are checked by the mix validation
+    /// after expansion.
+    pub fn parse(mix: &str) -> Option<Self> {
+        let (candidate, field) = mix.split_once('+')?;
+        let mut candidate_chars = candidate.chars();
+        let candidate = candidate_chars.next()?;
+        if candidate_chars.next().is_some() {
+            return None;
+        }
+        let field_letter = field.chars().last()?;
+        let count = field[..field.len() - field_letter.len_utf8()]
+            .parse::<usize>()
+            .ok()?;
+        Some(Self {
+            candidate,
+            field: field_letter,
+            count,
+        })
+    }
+
+    /// The N,E,S,W mix string with the candidate at `seat`.
+    pub fn expand(&self, seat: PlayerPosition) -> String {
+        let mut letters = vec![self.field; self.count];
+        letters.insert(seat.index().min(self.count), self.candidate);
+        letters.into_iter().collect()
+    }
+}
+
+/// Per-seat agent names for an expanded field mix: the candidate keeps its
+/// difficulty label and the field seats are `baseline-1..3`, or all
+/// `baseline` when `group` is set.
+pub fn agent_names(
+    diffs: [BotDifficulty; 4],
+    candidate: PlayerPosition,
+    group: bool,
+) -> [AgentId; 4] {
+    let mut next = 0;
+    PlayerPosition::LOOP.map(|seat| {
+        if seat == candidate {
+            AgentId::of(diffs[seat.index()])
+        } else if group {
+            AgentId::new(FIELD_GROUP).expect("field names are valid agent names")
+        } else {
+            next += 1;
+            AgentId::new(&format!("{FIELD_GROUP}-{next}"))
+                .expect("field names are valid agent names")
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldMix, agent_names};
+    use crate::bot::BotDifficulty;
+    use crate::determinism::parse_mix;
+    use hearts_core::model::player::PlayerPosition;
+
+    #[test]
+    fn shorthand_expands_around_the_candidate_seat() {
+        let field = FieldMix::parse("h+3n").unwrap();
+        assert_eq!(
+            field,
+            FieldMix {
+                candidate: 'h',
+                field: 'n',
+                count: 3
+            }
+        );
+        assert_eq!(field.expand(PlayerPosition::North), "hnnn");
+        assert_eq!(field.expand(PlayerPosition::South), "nnhn");
+        assert_eq!(field.expand(PlayerPosition::West), "nnnh");
+        assert_eq!(FieldMix::parse("nnhn"), None);
+        assert_eq!(FieldMix::parse("hh+3n"), None);
+        assert_eq!(FieldMix::parse("h+n"), None);
+    }
+
+    #[test]
+    fn short_or_long_fields_fail_four_agent_validation() {
+        for mix in ["h+2n", "h+4n", "h+3x"] {
+            let expanded = FieldMix::parse(mix).unwrap().expand(PlayerPosition::East);
+            assert_eq!(parse_mix(&expanded), None, "{mix} -> {expanded}");
+        }
+    }
+
+    #[test]
+    fn field_agents_are_named_in_seat_order_or_grouped() {
+        let diffs = parse_mix("nhnn").unwrap();
+        assert_eq!(
+            agent_names(diffs, PlayerPosition::East, false).map(String::from),
+            ["baseline-1", "hard", "baseline-2", "baseline-3"]
+        );
+        assert_eq!(
+            agent_names(diffs, PlayerPosition::East, true).map(String::from),
+            ["baseline", "hard", "baseline", "baseline"]
+        );
+        // A candidate of the field's own difficulty still stands apart.
+        let same = [BotDifficulty::NormalHeuristic; 4];
+        assert_eq!(
+            agent_names(same, PlayerPosition::North, true).map(String::from),
+            ["normal", "baseline", "baseline", "baseline"]
+        );
+    }
+}
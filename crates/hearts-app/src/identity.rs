@@ -0,0 +1,360 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! The names and avatars shown for the four seats.
+//!
+//! A seat is its own stable identity: renaming East changes what the table
+//! shows, never which bot plays there or how its difficulty is set. Until
+//! the player picks names in Game → Players, every seat goes by its compass
+//! name with no avatar, so the table reads as it always has.
+//!
+//! The roster lives in `mdhearts-players.json` next to the executable, and
+//! every saved game carries the roster it was played under, so a resumed
+//! match shows the names it started with.
+
+use crate::determinism::seat_letter;
+use crate::persist::{self, Migration, PersistError, Persisted};
+use hearts_core::model::player::PlayerPosition;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub const PLAYERS_FILE: &str = "mdhearts-players.json";
+
+/// Longest name, in characters, after trimming.
+pub const MAX_NAME_CHARS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Avatar {
+    Spade,
+    Heart,
+    Diamond,
+    Club,
+    Star,
+    Sun,
+    Knight,
+    Crown,
+}
+
+impl Avatar {
+    pub const ALL: [Avatar; 8] = [
+        Avatar::Spade,
+        Avatar::Heart,
+        Avatar::Diamond,
+        Avatar::Club,
+        Avatar::Star,
+        Avatar::Sun,
+        Avatar::Knight,
+        Avatar::Crown,
+    ];
+
+    /// The symbol drawn beside the seat's name.
+    pub const fn glyph(self) -> &'static str {
+        match self {
+            Avatar::Spade => "\u{2660}",
+            Avatar::Heart => "\u{2665}",
+            Avatar::Diamond => "\u{2666}",
+            Avatar::Club => "\u{2663}",
+            Avatar::Star => "\u{2605}",
+            Avatar::Sun => "\u{263C}",
+            Avatar::Knight => "\u{265E}",
+            Avatar::Crown => "\u{265B}",
+        }
+    }
+
+    /// The avatar after `avatar` in [`Avatar::ALL`], `None` after the last,
+    /// so a button can cycle through every choice and back to none.
+    pub fn next(avatar: Option<Avatar>) -> Option<Avatar> {
+        match avatar {
+            None => Some(Avatar::ALL[0]),
+            Some(current) => {
+                let idx = Avatar::ALL.iter().position(|&a| a == current)?;
+                Avatar::ALL.get(idx + 1).copied()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerIdentity {
+    pub name: String,
+    #[serde(default)]
+    pub avatar: Option<Avatar>,
+}
+
+impl PlayerIdentity {
+    pub fn new(name: impl Into<String>, avatar: Option<Avatar>) -> Self {
+        Self {
+            name: name.into(),
+            avatar,
+        }
+    }
+
+    /// The compass name, with no avatar.
+    pub fn default_for(seat: PlayerPosition) -> Self {
+        Self::new(seat.to_string(), None)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentityError {
+    Empty {
+        seat: PlayerPosition,
+    },
+    TooLong {
+        seat: PlayerPosition,
+        chars: usize,
+    },
+    ControlCharacter {
+        seat: PlayerPosition,
+    },
+    /// Two seats share a name, ignoring case.
+    Duplicate {
+        first: PlayerPosition,
+        second: PlayerPosition,
+    },
+}
+
+impl fmt::Display for IdentityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentityError::Empty { seat } => write!(f, "{seat} needs a name"),
+            IdentityError::TooLong { seat, chars } => write!(
+                f,
+                "{seat}'s name has {chars} characters; the limit is {MAX_NAME_CHARS}"
+            ),
+            IdentityError::ControlCharacter { seat } => {
+                write!(f, "{seat}'s name contains a control character")
+            }
+            IdentityError::Duplicate { first, second } => {
+                write!(f, "{first} and {second} cannot share a name")
+            }
+        }
+    }
+}
+
+/// The identities of all four seats, checked as a whole.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "[PlayerIdentity; 4]", into = "[PlayerIdentity; 4]")]
+pub struct PlayerRoster {
+    seats: [PlayerIdentity; 4],
+}
+
+impl PlayerRoster {
+    /// Trims every name and checks the four together, seats in N, E, S, W
+    /// order.
+    pub fn new(seats: [PlayerIdentity; 4]) -> Result<Self, IdentityError> {
+        let seats = seats.map(|identity| PlayerIdentity {
+            name: identity.name.trim().to_string(),
+            ..identity
+        });
+        for seat in PlayerPosition::LOOP {
+            let name = &seats[seat.index()].name;
+            let chars = name.chars().count();
+            if chars == 0 {
+                return Err(IdentityError::Empty { seat });
+            }
+            if chars > MAX_NAME_CHARS {
+                return Err(IdentityError::TooLong { seat, chars });
+            }
+            if name.chars().any(char::is_control) {
+                return Err(IdentityError::ControlCharacter { seat });
+            }
+        }
+        for (i, first) in PlayerPosition::LOOP.iter().enumerate() {
+            for second in &PlayerPosition::LOOP[i + 1..] {
+                let a = seats[first.index()].name.to_lowercase();
+                let b = seats[second.index()].name.to_lowercase();
+                if a == b {
+                    return Err(IdentityError::Duplicate {
+                        first: *first,
+                        second: *second,
+                    });
+                }
+            }
+        }
+        Ok(Self { seats })
+    }
+
+    pub fn identity(&self, seat: PlayerPosition) -> &PlayerIdentity {
+        &self.seats[seat.index()]
+    }
+
+    pub fn name(&self, seat: PlayerPosition) -> &str {
+        &self.seats[seat.index()].name
+    }
+
+    /// The avatar, or the seat's letter when it has none, for tight spots
+    /// like the score HUD.
+    pub fn badge(&self, seat: PlayerPosition) -> String {
+        match self.seats[seat.index()].avatar {
+            Some(avatar) => avatar.glyph().to_string(),
+            None => seat_letter(seat).to_string(),
+        }
+    }
+
+    pub fn seats(&self) -> &[PlayerIdentity; 4] {
+        &self.seats
+    }
+}
+
+impl Default for PlayerRoster {
+    fn default() -> Self {
+        Self {
+            seats: PlayerPosition::LOOP.map(PlayerIdentity::default_for),
+        }
+    }
+}
+
+impl TryFrom<[PlayerIdentity; 4]> for PlayerRoster {
+    type Error = IdentityError;
+
+    fn try_from(seats: [PlayerIdentity; 4]) -> Result<Self, Self::Error> {
+        Self::new(seats)
+    }
+}
+
+impl From<PlayerRoster> for [PlayerIdentity; 4] {
+    fn from(roster: PlayerRoster) -> Self {
+        roster.seats
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PlayersFile {
+    schema_version: u32,
+    seats: PlayerRoster,
+}
+
+impl Persisted for PlayersFile {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrations() -> &'static [Migration] {
+        &[]
+    }
+}
+
+/// Where the GUI keeps the roster: next to the executable, like the panic
+/// log.
+pub fn players_path() -> PathBuf {
+    match std::env::current_exe() {
+        Ok(mut exe) => {
+            exe.set_file_name(PLAYERS_FILE);
+            exe
+        }
+        Err(_) => PathBuf::from(PLAYERS_FILE),
+    }
+}
+
+pub fn save_players(path: &Path, roster: &PlayerRoster) -> Result<(), PersistError> {
+    let file = PlayersFile {
+        schema_version: PlayersFile::SCHEMA_VERSION,
+        seats: roster.clone(),
+    };
+    persist::save(path, &file)
+}
+
+/// The roster saved at `path`; the defaults when there is none yet.
+pub fn load_players(path: &Path) -> Result<PlayerRoster, PersistError> {
+    if !path.exists() {
+        return Ok(PlayerRoster::default());
+    }
+    let file: PlayersFile = persist::load_with_migrations(path)?;
+    Ok(file.seats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persist::quarantine_path;
+    use hearts_core::model::player::PlayerPosition::{East, North, South, West};
+    use std::fs;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("mdhearts_players_{name}.json"));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(quarantine_path(&path));
+        path
+    }
+
+    fn named(names: [&str; 4]) -> [PlayerIdentity; 4] {
+        names.map(|name| PlayerIdentity::new(name, None))
+    }
+
+    #[test]
+    fn the_defaults_are_the_compass_names() {
+        let roster = PlayerRoster::default();
+        assert_eq!(roster.name(North), "North");
+        assert_eq!(roster.name(West), "West");
+        assert_eq!(roster.badge(East), "E");
+        assert_eq!(
+            PlayerRoster::new(roster.seats().clone()),
+            Ok(roster.clone())
+        );
+    }
+
+    #[test]
+    fn names_are_trimmed_and_checked() {
+        let roster = PlayerRoster::new(named(["  Ada ", "Bix", "You", "Cleo"])).unwrap();
+        assert_eq!(roster.name(North), "Ada");
+
+        let cases = [
+            (
+                ["Ada", "   ", "You", "Cleo"],
+                IdentityError::Empty { seat: East },
+            ),
+            (
+                ["Ada", "Bix", "You", "Cleopatra the Great"],
+                IdentityError::TooLong {
+                    seat: West,
+                    chars: 19,
+                },
+            ),
+            (
+                ["Ada", "B\tix", "You", "Cleo"],
+                IdentityError::ControlCharacter { seat: East },
+            ),
+            (
+                ["Ada", "Bix", "ADA", "Cleo"],
+                IdentityError::Duplicate {
+                    first: North,
+                    second: South,
+                },
+            ),
+        ];
+        for (names, expected) in cases {
+            assert_eq!(PlayerRoster::new(named(names)), Err(expected), "{names:?}");
+        }
+        // Sixteen characters fit, counted as characters rather than bytes.
+        assert!(PlayerRoster::new(named(["Ada", "Bix", "You", "ÉÉÉÉÉÉÉÉÉÉÉÉÉÉÉÉ"])).is_ok());
+    }
+
+    #[test]
+    fn avatars_cycle_back_to_none() {
+        let mut avatar = None;
+        for expected in Avatar::ALL {
+            avatar = Avatar::next(avatar);
+            assert_eq!(avatar, Some(expected));
+        }
+        assert_eq!(Avatar::next(avatar), None);
+    }
+
+    #[test]
+    fn the_roster_round_trips_and_bad_files_are_refused() {
+        let path = temp_path("round_trip");
+        assert_eq!(load_players(&path).unwrap(), PlayerRoster::default());
+        let mut seats = named(["Ada", "Bix", "You", "Cleo"]);
+        seats[1].avatar = Some(Avatar::Knight);
+        let roster = PlayerRoster::new(seats).unwrap();
+        save_players(&path, &roster).unwrap();
+        assert_eq!(load_players(&path).unwrap(), roster);
+        assert_eq!(load_players(&path).unwrap().badge(East), "\u{265E}");
+
+        // A hand-edited file with a clash is moved aside like any bad file.
+        let text = fs::read_to_string(&path).unwrap().replace("Bix", "cleo");
+        fs::write(&path, text).unwrap();
+        let err = load_players(&path).unwrap_err().to_string();
+        assert!(err.contains("East and West cannot share a name"), "{err}");
+        assert!(quarantine_path(&path).exists());
+        let _ = fs::remove_file(quarantine_path(&path));
+    }
+}
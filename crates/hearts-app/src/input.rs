@@ -0,0 +1,349 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! Pointer input on the card table, resolved through a table of bindings.
+//!
+//! The window layer turns a mouse message into an [`InputEvent`] (the
+//! gesture and what it landed on) and asks the [`InputMapper`] what it means
+//! in the current [`InputPhase`]. Bindings are plain data, so they can later
+//! be loaded from settings; the first row that matches wins.
+//!
+//! Clicking a card already plays it without a confirm step, so a
+//! double-click is bound to the same immediate play. The second click of the
+//! pair arrives while the play animation runs and is dropped as busy input.
+
+use hearts_core::model::card::Card;
+use hearts_core::model::suit::Suit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    Click,
+    DoubleClick,
+    RightClick,
+    MiddleClick,
+    Wheel,
+}
+
+/// What the pointer was over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// A card in the player's hand.
+    Card(Card),
+    /// The table area between the hands.
+    Table,
+    Elsewhere,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Card,
+    Table,
+    Elsewhere,
+}
+
+impl Target {
+    pub fn kind(self) -> TargetKind {
+        match self {
+            Target::Card(_) => TargetKind::Card,
+            Target::Table => TargetKind::Table,
+            Target::Elsewhere => TargetKind::Elsewhere,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputPhase {
+    Passing,
+    Playing,
+    /// An animation, acknowledgement or finished match owns the table.
+    Busy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputEvent {
+    pub gesture: Gesture,
+    pub target: Target,
+    /// Wheel notches, positive away from the user; zero for buttons.
+    pub wheel_notches: i32,
+}
+
+impl InputEvent {
+    pub fn button(gesture: Gesture, target: Target) -> Self {
+        Self {
+            gesture,
+            target,
+            wheel_notches: 0,
+        }
+    }
+
+    pub fn wheel(notches: i32, target: Target) -> Self {
+        Self {
+            gesture: Gesture::Wheel,
+            target,
+            wheel_notches: notches,
+        }
+    }
+}
+
+/// What a binding does, before the event fills in the card or amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    TogglePassCard,
+    PlayCard,
+    CommitPass,
+    FanHand,
+    ShowSuitCounts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    TogglePassCard(Card),
+    PlayCard(Card),
+    CommitPass,
+    /// Widen (positive) or tighten the hand by this many notches.
+    FanHand(i32),
+    /// Open the card-counting view for this suit.
+    ShowSuitCounts(Suit),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    pub gesture: Gesture,
+    /// `None` matches every phase, including busy ones.
+    pub phase: Option<InputPhase>,
+    pub target: TargetKind,
+    pub command: Command,
+}
+
+const fn bind(
+    gesture: Gesture,
+    phase: Option<InputPhase>,
+    target: TargetKind,
+    command: Command,
+) -> Binding {
+    Binding {
+        gesture,
+        phase,
+        target,
+        command,
+    }
+}
+
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    bind(
+        Gesture::Click,
+        Some(InputPhase::Passing),
+        TargetKind::Card,
+        Command::TogglePassCard,
+    ),
+    bind(
+        Gesture::RightClick,
+        Some(InputPhase::Passing),
+        TargetKind::Card,
+        Command::TogglePassCard,
+    ),
+    bind(
+        Gesture::Click,
+        Some(InputPhase::Passing),
+        TargetKind::Table,
+        Command::CommitPass,
+    ),
+    bind(
+        Gesture::Click,
+        Some(InputPhase::Playing),
+        TargetKind::Card,
+        Command::PlayCard,
+    ),
+    bind(
+        Gesture::DoubleClick,
+        Some(InputPhase::Playing),
+        TargetKind::Card,
+        Command::PlayCard,
+    ),
+    bind(
+        Gesture::MiddleClick,
+        None,
+        TargetKind::Card,
+        Command::ShowSuitCounts,
+    ),
+    bind(Gesture::Wheel, None, TargetKind::Card, Command::FanHand),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMapper {
+    bindings: Vec<Binding>,
+}
+
+impl Default for InputMapper {
+    fn default() -> Self {
+        Self::new(DEFAULT_BINDINGS.to_vec())
+    }
+}
+
+impl InputMapper {
+    pub fn new(bindings: Vec<Binding>) -> Self {
+        Self { bindings }
+    }
+
+    pub fn resolve(&self, event: InputEvent, phase: InputPhase) -> Option<Action> {
+        let binding = self.bindings.iter().find(|binding| {
+            binding.gesture == event.gesture
+                && binding.target == event.target.kind()
+                && binding.phase.is_none_or(|p| p == phase)
+        })?;
+        let card = match event.target {
+            Target::Card(card) => Some(card),
+            _ => None,
+        };
+        match binding.command {
+            Command::TogglePassCard => card.map(Action::TogglePassCard),
+            Command::PlayCard => card.map(Action::PlayCard),
+            Command::CommitPass => Some(Action::CommitPass),
+            Command::FanHand => {
+                (event.wheel_notches != 0).then_some(Action::FanHand(event.wheel_notches))
+            }
+            Command::ShowSuitCounts => card.map(|card| Action::ShowSuitCounts(card.suit)),
+        }
+    }
+}
+
+/// Spacing factor for the player's hand, changed a step per wheel notch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HandFan(f32);
+
+impl Default for HandFan {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl HandFan {
+    pub const MIN: f32 = 0.6;
+    pub const MAX: f32 = 1.8;
+    pub const STEP: f32 = 0.1;
+
+    pub fn scrolled(self, notches: i32) -> Self {
+        Self((self.0 + notches as f32 * Self::STEP).clamp(Self::MIN, Self::MAX))
+    }
+
+    pub fn factor(self) -> f32 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Action, Binding, Command, DEFAULT_BINDINGS, Gesture, HandFan, InputEvent, InputMapper,
+        InputPhase, Target, TargetKind,
+    };
+    use hearts_core::model::card::Card;
+    use hearts_core::model::rank::Rank;
+    use hearts_core::model::suit::Suit;
+
+    fn queen() -> Card {
+        Card::new(Rank::Queen, Suit::Spades)
+    }
+
+    #[test]
+    fn default_bindings_map_each_gesture_by_phase() {
+        let mapper = InputMapper::default();
+        let on_card = |gesture| InputEvent::button(gesture, Target::Card(queen()));
+        let cases = [
+            (
+                on_card(Gesture::Click),
+                InputPhase::Passing,
+                Some(Action::TogglePassCard(queen())),
+            ),
+            (
+                on_card(Gesture::RightClick),
+                InputPhase::Passing,
+                Some(Action::TogglePassCard(queen())),
+            ),
+            (on_card(Gesture::RightClick), InputPhase::Playing, None),
+            (
+                on_card(Gesture::Click),
+                InputPhase::Playing,
+                Some(Action::PlayCard(queen())),
+            ),
+            (
+                on_card(Gesture::DoubleClick),
+                InputPhase::Playing,
+                Some(Action::PlayCard(queen())),
+            ),
+            (on_card(Gesture::DoubleClick), InputPhase::Passing, None),
+            (on_card(Gesture::Click), InputPhase::Busy, None),
+            (
+                on_card(Gesture::MiddleClick),
+                InputPhase::Busy,
+                Some(Action::ShowSuitCounts(Suit::Spades)),
+            ),
+            (
+                InputEvent::button(Gesture::Click, Target::Table),
+                InputPhase::Passing,
+                Some(Action::CommitPass),
+            ),
+            (
+                InputEvent::button(Gesture::Click, Target::Table),
+                InputPhase::Playing,
+                None,
+            ),
+            (
+                InputEvent::button(Gesture::MiddleClick, Target::Elsewhere),
+                InputPhase::Playing,
+                None,
+            ),
+            (
+                InputEvent::wheel(-2, Target::Card(queen())),
+                InputPhase::Playing,
+                Some(Action::FanHand(-2)),
+            ),
+            (
+                InputEvent::wheel(0, Target::Card(queen())),
+                InputPhase::Playing,
+                None,
+            ),
+            (
+                InputEvent::wheel(1, Target::Table),
+                InputPhase::Playing,
+                None,
+            ),
+        ];
+        for (event, phase, expected) in cases {
+            assert_eq!(
+                mapper.resolve(event, phase),
+                expected,
+                "{event:?} in {phase:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn first_matching_binding_wins_and_tables_can_be_replaced() {
+        let mut bindings = vec![Binding {
+            gesture: Gesture::Click,
+            phase: None,
+            target: TargetKind::Card,
+            command: Command::ShowSuitCounts,
+        }];
+        bindings.extend_from_slice(DEFAULT_BINDINGS);
+        let mapper = InputMapper::new(bindings);
+        let click = InputEvent::button(Gesture::Click, Target::Card(queen()));
+        assert_eq!(
+            mapper.resolve(click, InputPhase::Playing),
+            Some(Action::ShowSuitCounts(Suit::Spades))
+        );
+        assert_eq!(
+            InputMapper::new(Vec::new()).resolve(click, InputPhase::Playing),
+            None
+        );
+    }
+
+    #[test]
+    fn hand_fan_steps_and_clamps() {
+        let fan = HandFan::default();
+        assert_eq!(fan.factor(), 1.0);
+        assert!((fan.scrolled(2).factor() - 1.2).abs() < 1e-6);
+        assert_eq!(fan.scrolled(50).factor(), HandFan::MAX);
+        assert_eq!(fan.scrolled(-50).factor(), HandFan::MIN);
+        assert!((fan.scrolled(3).scrolled(-3).factor() - 1.0).abs() < 1e-6);
+    }
+}
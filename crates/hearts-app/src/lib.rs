@@ -1,7 +1,36 @@
+pub mod agent_id;
+pub mod assist;
+pub mod augment;
+pub mod bench_plan;
 pub mod bot;
+pub mod card_style;
+pub mod challenge;
 pub mod cli;
 pub mod controller;
 pub mod dataset;
 pub mod debug;
+pub mod determinism;
 pub mod endgame_export;
+pub mod field;
+pub mod identity;
+pub mod input;
+pub mod outcome;
+pub mod outputs;
+pub mod pass_explain;
+pub mod paths;
+pub mod permutations;
+pub mod persist;
+pub mod rating;
+pub mod recording;
+pub mod resource;
+pub mod review;
+pub mod run_meta;
+pub mod saved_game;
+pub mod scoreboard;
+pub mod seed_search;
+pub mod session;
+pub mod shadow;
+pub mod soak;
+pub mod tasks;
 pub mod telemetry;
+pub mod transcript;
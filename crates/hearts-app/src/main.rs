@@ -14,14 +14,43 @@ use windows::Win32::UI::WindowsAndMessaging::{MB_ICONERROR, MB_OK, MessageBoxW};
 #[cfg(windows)]
 use windows::core::{PCWSTR, w};
 
+mod agent_id;
+mod assist;
+mod augment;
+mod bench_plan;
 mod bot;
+mod card_style;
+mod challenge;
 mod cli;
 mod controller;
 mod dataset;
 mod debug;
+mod determinism;
 mod endgame_export;
+mod field;
+mod identity;
+mod input;
+mod outcome;
+mod outputs;
+mod pass_explain;
+mod paths;
+mod permutations;
+mod persist;
 mod platform;
+mod rating;
+mod recording;
+mod resource;
+mod review;
+mod run_meta;
+mod saved_game;
+mod scoreboard;
+mod seed_search;
+mod session;
+mod shadow;
+mod soak;
+mod tasks;
 mod telemetry;
+mod transcript;
 
 #[cfg(windows)]
 fn install_panic_hook() {
@@ -0,0 +1,1155 @@
+//! Per-hand outcome records from batch runs and the analytics built on them.
+
+use crate::agent_id::AgentId;
+use crate::bot::BotDifficulty;
+use hearts_core::model::card::Card;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rank::Rank;
+use hearts_core::model::round::RoundState;
+use hearts_core::model::rules::{JACK_OF_DIAMONDS, JACK_OF_DIAMONDS_BONUS, RuleSet};
+use hearts_core::model::suit::Suit;
+use hearts_core::model::trick::Trick;
+use serde::{Serialize, Serializer};
+
+pub const QUEEN_OF_SPADES: Card = Card::new(Rank::Queen, Suit::Spades);
+
+/// What happened in one simulated hand.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HandOutcome {
+    pub seed: u64,
+    pub penalties: [u8; 4],
+    /// Q♠ owner straight after the deal.
+    pub qs_dealt_to: Option<PlayerPosition>,
+    /// Q♠ owner once passes resolved (same as dealt on hold hands).
+    pub qs_after_pass: Option<PlayerPosition>,
+    /// Seat that won the trick containing Q♠.
+    pub qs_captured_by: Option<PlayerPosition>,
+    /// Seat that won the trick containing J♦; only scored under Omnibus.
+    #[serde(skip)]
+    pub jack_captured_by: Option<PlayerPosition>,
+    /// Seat that won all 13 tricks, whether or not the rules score the sun.
+    #[serde(skip)]
+    pub sun_shooter: Option<PlayerPosition>,
+    /// Every trick in play order; empty unless the plays were noted.
+    pub tricks: Vec<TrickTrace>,
+    /// Shooter pressure (0-100) behind each seat's pass, for the passes made
+    /// with the moon-defense guards on.
+    pub block_shooter_passes: [Option<u8>; 4],
+}
+
+/// One play with the trick state the seat saw when choosing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PlayTrace {
+    pub seat: PlayerPosition,
+    #[serde(serialize_with = "card_code")]
+    pub card: Card,
+    /// Following, with a legal card that beats the best card played so far.
+    pub could_have_won: bool,
+    /// Played by the fast-forward after the round was decided, not by the
+    /// seat's agent.
+    #[serde(skip_serializing_if = "is_false")]
+    pub auto: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrickTrace {
+    pub leader: PlayerPosition,
+    pub plays: Vec<PlayTrace>,
+    pub winner: Option<PlayerPosition>,
+    pub penalties: u8,
+}
+
+impl TrickTrace {
+    /// Best lead-suit card among the first `count` plays.
+    fn best_before(&self, count: usize) -> Option<Card> {
+        let lead = self.plays.first()?.card.suit;
+        self.plays[..count]
+            .iter()
+            .map(|play| play.card)
+            .filter(|card| card.suit == lead)
+            .max_by_key(|card| card.rank)
+    }
+}
+
+fn card_code<S: Serializer>(card: &Card, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(card)
+}
+
+/// `card` takes the lead from `best`, the best lead-suit card so far.
+fn beats(card: Card, best: Card) -> bool {
+    card.suit == best.suit && card.rank > best.rank
+}
+
+/// Whether any of `legal` would take the lead of `trick` as it stands.
+pub fn could_win(trick: &Trick, legal: &[Card]) -> bool {
+    let Some(lead) = trick.lead_suit() else {
+        return false;
+    };
+    let best = trick
+        .plays()
+        .iter()
+        .map(|play| play.card)
+        .filter(|card| card.suit == lead)
+        .max_by_key(|card| card.rank);
+    best.is_some_and(|best| legal.iter().any(|&card| beats(card, best)))
+}
+
+impl HandOutcome {
+    /// Starts a record from the freshly dealt round; fill in the rest with
+    /// `note_passes_resolved` and `finish`.
+    pub fn dealt(seed: u64, round: &RoundState) -> Self {
+        Self {
+            seed,
+            penalties: [0; 4],
+            qs_dealt_to: round.holder_of(QUEEN_OF_SPADES),
+            qs_after_pass: None,
+            qs_captured_by: None,
+            jack_captured_by: None,
+            sun_shooter: None,
+            tricks: Vec::new(),
+            block_shooter_passes: [None; 4],
+        }
+    }
+
+    /// Records that `seat` passed against a suspected shooter at `urgency`.
+    pub fn note_block_shooter_pass(&mut self, seat: PlayerPosition, urgency: u8) {
+        self.block_shooter_passes[seat.index()] = Some(urgency);
+    }
+
+    /// Records `seat` playing `card` from `legal` to `trick`, the current
+    /// trick as it stood before the play.
+    pub fn note_play(&mut self, trick: &Trick, seat: PlayerPosition, card: Card, legal: &[Card]) {
+        self.push_play(trick, seat, card, legal, false);
+    }
+
+    /// Like `note_play`, for a card the fast-forward picked.
+    pub fn note_auto_play(
+        &mut self,
+        trick: &Trick,
+        seat: PlayerPosition,
+        card: Card,
+        legal: &[Card],
+    ) {
+        self.push_play(trick, seat, card, legal, true);
+    }
+
+    fn push_play(
+        &mut self,
+        trick: &Trick,
+        seat: PlayerPosition,
+        card: Card,
+        legal: &[Card],
+        auto: bool,
+    ) {
+        if trick.plays().is_empty() {
+            self.tricks.push(TrickTrace {
+                leader: seat,
+                plays: Vec::with_capacity(4),
+                winner: None,
+                penalties: 0,
+            });
+        }
+        if let Some(current) = self.tricks.last_mut() {
+            current.plays.push(PlayTrace {
+                seat,
+                card,
+                could_have_won: could_win(trick, legal),
+                auto,
+            });
+        }
+    }
+
+    pub fn note_passes_resolved(&mut self, round: &RoundState) {
+        self.qs_after_pass = round.holder_of(QUEEN_OF_SPADES);
+    }
+
+    /// Seat that took all 26 points, if one did.
+    pub fn moon_shooter(&self) -> Option<PlayerPosition> {
+        PlayerPosition::LOOP
+            .into_iter()
+            .find(|seat| self.penalties[seat.index()] == 26)
+    }
+
+    /// `seat`'s penalties, less the J♦ bonus when `rules` play it: the
+    /// per-hand figure the benches average. Moon shots count as taken, as in
+    /// the raw penalties.
+    pub fn bench_points(&self, seat: PlayerPosition, rules: &RuleSet) -> i32 {
+        let penalties = i32::from(self.penalties[seat.index()]);
+        if rules.jack_of_diamonds && self.jack_captured_by == Some(seat) {
+            penalties - i32::from(JACK_OF_DIAMONDS_BONUS)
+        } else {
+            penalties
+        }
+    }
+
+    pub fn finish(&mut self, round: &RoundState) {
+        if self.qs_after_pass.is_none() {
+            self.qs_after_pass = self.qs_dealt_to;
+        }
+        self.qs_captured_by = round.captured_by(QUEEN_OF_SPADES);
+        self.jack_captured_by = round.captured_by(JACK_OF_DIAMONDS);
+        self.penalties = round.penalty_totals();
+        self.sun_shooter = round.sun_shooter();
+        for (trace, trick) in self.tricks.iter_mut().zip(round.trick_history()) {
+            trace.winner = trick.winner();
+            trace.penalties = trick.penalty_total();
+        }
+    }
+}
+
+/// A moon or sun shot and the agent that made it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MoonShooter {
+    pub seat: PlayerPosition,
+    pub agent: &'static str,
+}
+
+/// One `--hand-outcomes` row: a hand played under one seating of the mix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HandOutcomeRecord {
+    /// Index of the seating among the deal's permutations; 0 when the deal
+    /// is played once.
+    pub perm: usize,
+    /// Agent at each seat, N, E, S, W.
+    pub seating: [&'static str; 4],
+    pub moon_shooter: Option<MoonShooter>,
+    pub sun_shooter: Option<MoonShooter>,
+    #[serde(flatten)]
+    pub outcome: HandOutcome,
+}
+
+impl HandOutcomeRecord {
+    pub fn new(outcome: HandOutcome, perm: usize, seating: [BotDifficulty; 4]) -> Self {
+        let seating = seating.map(agent_label);
+        let shooter = |seat: PlayerPosition| MoonShooter {
+            seat,
+            agent: seating[seat.index()],
+        };
+        Self {
+            perm,
+            seating,
+            moon_shooter: outcome.moon_shooter().map(shooter),
+            sun_shooter: outcome.sun_shooter.map(shooter),
+            outcome,
+        }
+    }
+}
+
+pub fn agent_label(difficulty: BotDifficulty) -> &'static str {
+    match difficulty {
+        BotDifficulty::EasyLegacy => "easy",
+        BotDifficulty::NormalHeuristic => "normal",
+        BotDifficulty::FutureHard => "hard",
+        BotDifficulty::SearchLookahead => "search",
+        BotDifficulty::Rollout => "rollout",
+    }
+}
+
+/// Q♠ handling for one agent, pooled over every seat it played.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueenStats {
+    pub agent: AgentId,
+    pub hands: u32,
+    pub dealt: u32,
+    pub passed: u32,
+    pub received: u32,
+    pub captured: u32,
+    /// Held Q♠ after the pass and another seat captured it.
+    pub dumped: u32,
+    pub avg_points_dealt_queen: Option<f64>,
+    pub avg_points_without_queen: Option<f64>,
+}
+
+/// Aggregates Q♠ stats per agent; `agents[seat]` names who played each seat.
+/// Agents are listed in seat order of first appearance.
+pub fn queen_stats(outcomes: &[HandOutcome], agents: &[AgentId; 4]) -> Vec<QueenStats> {
+    let mut order: Vec<&AgentId> = Vec::new();
+    for agent in agents {
+        if !order.contains(&agent) {
+            order.push(agent);
+        }
+    }
+    order
+        .into_iter()
+        .map(|agent| {
+            let mut stats = QueenStats {
+                agent: agent.clone(),
+                hands: 0,
+                dealt: 0,
+                passed: 0,
+                received: 0,
+                captured: 0,
+                dumped: 0,
+                avg_points_dealt_queen: None,
+                avg_points_without_queen: None,
+            };
+            let (mut with_points, mut with_hands) = (0u32, 0u32);
+            let (mut without_points, mut without_hands) = (0u32, 0u32);
+            for outcome in outcomes {
+                for seat in PlayerPosition::LOOP {
+                    if agents[seat.index()] != *agent {
+                        continue;
+                    }
+                    let points = outcome.penalties[seat.index()] as u32;
+                    let dealt = outcome.qs_dealt_to == Some(seat);
+                    let held = outcome.qs_after_pass == Some(seat);
+                    stats.hands += 1;
+                    if dealt {
+                        stats.dealt += 1;
+                        with_points += points;
+                        with_hands += 1;
+                        if !held {
+                            stats.passed += 1;
+                        }
+                    } else {
+                        without_points += points;
+                        without_hands += 1;
+                        if held {
+                            stats.received += 1;
+                        }
+                    }
+                    match outcome.qs_captured_by {
+                        Some(taker) if taker == seat => stats.captured += 1,
+                        Some(_) if held => stats.dumped += 1,
+                        _ => {}
+                    }
+                }
+            }
+            stats.avg_points_dealt_queen =
+                (with_hands > 0).then(|| with_points as f64 / with_hands as f64);
+            stats.avg_points_without_queen =
+                (without_hands > 0).then(|| without_points as f64 / without_hands as f64);
+            stats
+        })
+        .collect()
+}
+
+pub fn queen_stats_markdown(stats: &[QueenStats]) -> String {
+    let avg = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{v:.2}"));
+    let mut out = String::from(
+        "| agent | hands | dealt Q♠ | passed | received | captured | dumped | avg pts (dealt Q♠) | avg pts (no Q♠) |\n\
+         |---|---:|---:|---:|---:|---:|---:|---:|---:|\n",
+    );
+    for row in stats {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            row.agent,
+            row.hands,
+            row.dealt,
+            row.passed,
+            row.received,
+            row.captured,
+            row.dumped,
+            avg(row.avg_points_dealt_queen),
+            avg(row.avg_points_without_queen)
+        ));
+    }
+    out
+}
+
+/// Lead control for one agent, pooled over every seat it played.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TempoStats {
+    pub agent: AgentId,
+    pub hands: u32,
+    pub tricks_led: u32,
+    pub tricks_followed: u32,
+    /// Follows where some legal card would have taken the lead.
+    pub could_have_won: u32,
+    /// Of those, plays under the best card so far.
+    pub ducked: u32,
+    pub duck_rate: Option<f64>,
+    pub avg_points_led: Option<f64>,
+    pub avg_points_followed: Option<f64>,
+}
+
+/// Aggregates tempo stats per agent from the noted trick traces, like
+/// [`queen_stats`]. Fast-forwarded plays are left out.
+pub fn tempo_stats(outcomes: &[HandOutcome], agents: &[AgentId; 4]) -> Vec<TempoStats> {
+    let mut order: Vec<&AgentId> = Vec::new();
+    for agent in agents {
+        if !order.contains(&agent) {
+            order.push(agent);
+        }
+    }
+    let ratio = |num: u32, den: u32| (den > 0).then(|| num as f64 / den as f64);
+    order
+        .into_iter()
+        .map(|agent| {
+            let mut stats = TempoStats {
+                agent: agent.clone(),
+                hands: 0,
+                tricks_led: 0,
+                tricks_followed: 0,
+                could_have_won: 0,
+                ducked: 0,
+                duck_rate: None,
+                avg_points_led: None,
+                avg_points_followed: None,
+            };
+            let (mut led_points, mut followed_points) = (0u32, 0u32);
+            for outcome in outcomes {
+                stats.hands += agents.iter().filter(|&a| a == agent).count() as u32;
+                for trick in &outcome.tricks {
+                    for (idx, play) in trick.plays.iter().enumerate() {
+                        if play.auto || agents[play.seat.index()] != *agent {
+                            continue;
+                        }
+                        let taken = if trick.winner == Some(play.seat) {
+                            trick.penalties as u32
+                        } else {
+                            0
+                        };
+                        if idx == 0 {
+                            stats.tricks_led += 1;
+                            led_points += taken;
+                            continue;
+                        }
+                        stats.tricks_followed += 1;
+                        followed_points += taken;
+                        if play.could_have_won {
+                            stats.could_have_won += 1;
+                        }
+                        let under = trick
+                            .best_before(idx)
+                            .is_some_and(|best| !beats(play.card, best));
+                        if play.could_have_won && under {
+                            stats.ducked += 1;
+                        }
+                    }
+                }
+            }
+            stats.duck_rate = ratio(stats.ducked, stats.could_have_won);
+            stats.avg_points_led = ratio(led_points, stats.tricks_led);
+            stats.avg_points_followed = ratio(followed_points, stats.tricks_followed);
+            stats
+        })
+        .collect()
+}
+
+pub fn tempo_stats_markdown(stats: &[TempoStats]) -> String {
+    let avg = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{v:.2}"));
+    let mut out = String::from(
+        "| agent | hands | led | followed | could win | ducked | duck rate | avg pts (led) | avg pts (followed) |\n\
+         |---|---:|---:|---:|---:|---:|---:|---:|---:|\n",
+    );
+    for row in stats {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            row.agent,
+            row.hands,
+            row.tricks_led,
+            row.tricks_followed,
+            row.could_have_won,
+            row.ducked,
+            avg(row.duck_rate),
+            avg(row.avg_points_led),
+            avg(row.avg_points_followed)
+        ));
+    }
+    out
+}
+
+/// Hearts a seat must take, with no points anywhere else, before it counts as
+/// going for the moon.
+pub const MOON_THREAT_HEARTS: u32 = 5;
+
+/// A moon attempt read off a hand's trick trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MoonAttempt {
+    pub shooter: PlayerPosition,
+    /// The shooter took all 26 points.
+    pub shot: bool,
+    /// The shooter won every trick as well: a sun.
+    pub sun: bool,
+    /// Winner of the first scoring trick the shooter did not take.
+    pub broken_by: Option<PlayerPosition>,
+}
+
+impl HandOutcome {
+    /// The hand's moon attempt: the seat that took [`MOON_THREAT_HEARTS`]
+    /// hearts before any other seat scored. `None` when the points were split
+    /// first, or when the plays were not noted.
+    pub fn moon_attempt(&self) -> Option<MoonAttempt> {
+        let mut scored: Option<PlayerPosition> = None;
+        let mut hearts = 0u32;
+        let mut shooter: Option<PlayerPosition> = None;
+        for trick in &self.tricks {
+            let Some(winner) = trick.winner else {
+                continue;
+            };
+            if trick.penalties == 0 {
+                continue;
+            }
+            if let Some(shooter) = shooter {
+                if winner != shooter {
+                    return Some(MoonAttempt {
+                        shooter,
+                        shot: false,
+                        sun: false,
+                        broken_by: Some(winner),
+                    });
+                }
+                continue;
+            }
+            if scored.is_some_and(|seat| seat != winner) {
+                return None;
+            }
+            scored = Some(winner);
+            hearts += trick
+                .plays
+                .iter()
+                .filter(|play| play.card.suit == Suit::Hearts)
+                .count() as u32;
+            if hearts >= MOON_THREAT_HEARTS {
+                shooter = Some(winner);
+            }
+        }
+        shooter.map(|shooter| MoonAttempt {
+            shooter,
+            shot: self.penalties[shooter.index()] == 26,
+            sun: self.sun_shooter == Some(shooter),
+            broken_by: None,
+        })
+    }
+}
+
+/// Moon defense for one agent: how often opponents at its table got a moon
+/// attempt home, how often it was the seat that broke one, and how hard it
+/// passed against suspected shooters.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MoonDefenseStats {
+    pub agent: AgentId,
+    /// Hands the agent played at least one seat of.
+    pub hands: u32,
+    /// Moon attempts by seats the agent did not play.
+    pub opponent_attempts: u32,
+    pub opponent_shots: u32,
+    /// Opponent shots that won every trick too, counted apart from the moons.
+    pub opponent_suns: u32,
+    pub moon_success_rate: Option<f64>,
+    /// Opponent attempts broken by one of the agent's seats.
+    pub breaks: u32,
+    pub break_rate: Option<f64>,
+    /// Passes made with the moon-defense guards on.
+    pub block_passes: u32,
+    pub avg_block_urgency: Option<f64>,
+}
+
+/// Aggregates moon-defense stats per agent, like [`queen_stats`]. Attempts
+/// come from [`HandOutcome::moon_attempt`], so they need the trick traces.
+pub fn moon_defense_stats(
+    outcomes: &[HandOutcome],
+    agents: &[AgentId; 4],
+) -> Vec<MoonDefenseStats> {
+    let mut order: Vec<&AgentId> = Vec::new();
+    for agent in agents {
+        if !order.contains(&agent) {
+            order.push(agent);
+        }
+    }
+    let ratio = |num: u32, den: u32| (den > 0).then(|| num as f64 / den as f64);
+    order
+        .into_iter()
+        .map(|agent| {
+            let mut stats = MoonDefenseStats {
+                agent: agent.clone(),
+                hands: 0,
+                opponent_attempts: 0,
+                opponent_shots: 0,
+                opponent_suns: 0,
+                moon_success_rate: None,
+                breaks: 0,
+                break_rate: None,
+                block_passes: 0,
+                avg_block_urgency: None,
+            };
+            let plays = |seat: PlayerPosition| agents[seat.index()] == *agent;
+            let mut urgency_total = 0u32;
+            for outcome in outcomes {
+                stats.hands += 1;
+                for seat in PlayerPosition::LOOP.into_iter().filter(|&seat| plays(seat)) {
+                    if let Some(urgency) = outcome.block_shooter_passes[seat.index()] {
+                        stats.block_passes += 1;
+                        urgency_total += urgency as u32;
+                    }
+                }
+                let Some(attempt) = outcome.moon_attempt() else {
+                    continue;
+                };
+                if plays(attempt.shooter) {
+                    continue;
+                }
+                stats.opponent_attempts += 1;
+                if attempt.shot {
+                    stats.opponent_shots += 1;
+                }
+                if attempt.sun {
+                    stats.opponent_suns += 1;
+                }
+                if attempt.broken_by.is_some_and(plays) {
+                    stats.breaks += 1;
+                }
+            }
+            stats.moon_success_rate = ratio(stats.opponent_shots, stats.opponent_attempts);
+            stats.break_rate = ratio(stats.breaks, stats.opponent_attempts);
+            stats.avg_block_urgency = ratio(urgency_total, stats.block_passes);
+            stats
+        })
+        .collect()
+}
+
+pub fn moon_defense_markdown(stats: &[MoonDefenseStats]) -> String {
+    let avg = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{v:.2}"));
+    let mut out = String::from(
+        "| agent | hands | opp. attempts | opp. moons | opp. suns | moon rate | breaks | break rate | block passes | avg urgency |\n\
+         |---|---:|---:|---:|---:|---:|---:|---:|---:|---:|\n",
+    );
+    for row in stats {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            row.agent,
+            row.hands,
+            row.opponent_attempts,
+            row.opponent_shots,
+            row.opponent_suns,
+            avg(row.moon_success_rate),
+            row.breaks,
+            avg(row.break_rate),
+            row.block_passes,
+            avg(row.avg_block_urgency)
+        ));
+    }
+    out
+}
+
+/// Placing of each seat by match total, lowest total first. Seats level on
+/// points share the better placing, so a tie for first reads 1, 1, 3, 4.
+pub fn placements(totals: [u32; 4]) -> [u8; 4] {
+    totals.map(|total| 1 + totals.iter().filter(|&&other| other < total).count() as u8)
+}
+
+/// How one `--match-full` match ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatchRecord {
+    pub seed: u64,
+    pub rounds: u32,
+    /// Agent at each seat, N, E, S, W.
+    pub seating: [&'static str; 4],
+    pub totals: [u32; 4],
+    pub placements: [u8; 4],
+    /// `None` only if play stalled before the match ended.
+    pub winner: Option<PlayerPosition>,
+    pub moons: u32,
+    /// Hands one seat won every trick of, under the sun rule; these are not
+    /// counted in `moons`.
+    pub suns: u32,
+    /// Bench points per seat summed over the hands (see
+    /// [`HandOutcome::bench_points`]), which is what PPH is taken from.
+    #[serde(skip)]
+    pub points: [i32; 4],
+}
+
+/// One `--match-full --hand-outcomes` line, tagged by `row`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "row", rename_all = "snake_case")]
+pub enum MatchRow {
+    /// A hand of the match; `totals` are the match totals once it is scored.
+    Hand {
+        round: u32,
+        totals: [u32; 4],
+        #[serde(flatten)]
+        record: HandOutcomeRecord,
+    },
+    /// The match summary, written after its last hand.
+    Match(MatchRecord),
+}
+
+/// Match results for one agent, pooled over every seat it played.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MatchStats {
+    pub agent: AgentId,
+    /// Matches times the seats the agent held in each.
+    pub seats: u32,
+    pub wins: u32,
+    pub win_rate: Option<f64>,
+    pub avg_placement: Option<f64>,
+    pub hands: u32,
+    pub pph: Option<f64>,
+}
+
+/// Aggregates match stats per agent, like [`queen_stats`]. A win is a seat
+/// finishing first outright or level on the fewest points.
+pub fn match_stats(matches: &[MatchRecord], agents: &[AgentId; 4]) -> Vec<MatchStats> {
+    let mut order: Vec<&AgentId> = Vec::new();
+    for agent in agents {
+        if !order.contains(&agent) {
+            order.push(agent);
+        }
+    }
+    let ratio = |num: f64, den: u32| (den > 0).then(|| num / den as f64);
+    order
+        .into_iter()
+        .map(|agent| {
+            let mut stats = MatchStats {
+                agent: agent.clone(),
+                seats: 0,
+                wins: 0,
+                win_rate: None,
+                avg_placement: None,
+                hands: 0,
+                pph: None,
+            };
+            let (mut placement_total, mut points) = (0u32, 0i64);
+            for record in matches {
+                for seat in PlayerPosition::LOOP {
+                    if agents[seat.index()] != *agent {
+                        continue;
+                    }
+                    let placement = record.placements[seat.index()];
+                    stats.seats += 1;
+                    stats.hands += record.rounds;
+                    if placement == 1 {
+                        stats.wins += 1;
+                    }
+                    placement_total += placement as u32;
+                    points += i64::from(record.points[seat.index()]);
+                }
+            }
+            stats.win_rate = ratio(stats.wins as f64, stats.seats);
+            stats.avg_placement = ratio(placement_total as f64, stats.seats);
+            stats.pph = ratio(points as f64, stats.hands);
+            stats
+        })
+        .collect()
+}
+
+pub fn match_stats_markdown(stats: &[MatchStats]) -> String {
+    let avg = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{v:.2}"));
+    let mut out = String::from(
+        "| agent | seats | wins | win rate | avg placement | hands | PPH |\n\
+         |---|---:|---:|---:|---:|---:|---:|\n",
+    );
+    for row in stats {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            row.agent,
+            row.seats,
+            row.wins,
+            avg(row.win_rate),
+            avg(row.avg_placement),
+            row.hands,
+            avg(row.pph)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        HandOutcome, HandOutcomeRecord, MatchRecord, MatchRow, MoonAttempt, MoonShooter, PlayTrace,
+        TrickTrace, match_stats, match_stats_markdown, moon_defense_markdown, moon_defense_stats,
+        placements, queen_stats, queen_stats_markdown, tempo_stats,
+    };
+    use crate::agent_id::AgentId;
+    use crate::bot::BotDifficulty::{EasyLegacy, FutureHard, NormalHeuristic};
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::player::PlayerPosition::{East, North, South, West};
+    use hearts_core::testkit::{RoundBuilder, card, cards, to_play};
+
+    fn ids(names: [&str; 4]) -> [AgentId; 4] {
+        names.map(|name| AgentId::new(name).unwrap())
+    }
+
+    fn outcome(
+        penalties: [u8; 4],
+        dealt: PlayerPosition,
+        after: PlayerPosition,
+        taker: PlayerPosition,
+    ) -> HandOutcome {
+        HandOutcome {
+            seed: 0,
+            penalties,
+            qs_dealt_to: Some(dealt),
+            qs_after_pass: Some(after),
+            qs_captured_by: Some(taker),
+            jack_captured_by: None,
+            sun_shooter: None,
+            tricks: Vec::new(),
+            block_shooter_passes: [None; 4],
+        }
+    }
+
+    #[test]
+    fn pass_receive_capture_and_dump_are_attributed() {
+        let outcomes = vec![
+            // North passes Q♠ to East, East dumps it on South.
+            outcome([0, 2, 20, 4], North, East, South),
+            // West keeps Q♠ and eats it.
+            outcome([1, 0, 0, 25], West, West, West),
+        ];
+        let stats = queen_stats(&outcomes, &ids(["hard", "normal", "normal", "normal"]));
+        assert_eq!(stats.len(), 2);
+        let hard = &stats[0];
+        assert_eq!(hard.agent, "hard");
+        assert_eq!(
+            (hard.hands, hard.dealt, hard.passed, hard.received),
+            (2, 1, 1, 0)
+        );
+        assert_eq!((hard.captured, hard.dumped), (0, 0));
+        assert_eq!(hard.avg_points_dealt_queen, Some(0.0));
+        assert_eq!(hard.avg_points_without_queen, Some(1.0));
+
+        let normal = &stats[1];
+        assert_eq!(
+            (normal.hands, normal.dealt, normal.passed, normal.received),
+            (6, 1, 0, 1)
+        );
+        assert_eq!((normal.captured, normal.dumped), (2, 1));
+        assert_eq!(normal.avg_points_dealt_queen, Some(25.0));
+        assert_eq!(normal.avg_points_without_queen, Some(26.0 / 5.0));
+    }
+
+    #[test]
+    fn records_name_the_moon_shooter_by_agent() {
+        let shot = HandOutcomeRecord::new(
+            outcome([0, 0, 26, 0], South, South, South),
+            2,
+            [NormalHeuristic, NormalHeuristic, FutureHard, EasyLegacy],
+        );
+        assert_eq!(shot.seating, ["normal", "normal", "hard", "easy"]);
+        assert_eq!(
+            shot.moon_shooter,
+            Some(MoonShooter {
+                seat: South,
+                agent: "hard"
+            })
+        );
+        let row = serde_json::to_value(&shot).unwrap();
+        assert_eq!(row["perm"], 2);
+        assert_eq!(row["penalties"], serde_json::json!([0, 0, 26, 0]));
+        assert_eq!(row["moon_shooter"]["agent"], "hard");
+        assert!(row["sun_shooter"].is_null());
+
+        let mut sun = outcome([0, 0, 26, 0], South, South, South);
+        sun.sun_shooter = Some(South);
+        let sun = HandOutcomeRecord::new(
+            sun,
+            0,
+            [NormalHeuristic, NormalHeuristic, FutureHard, EasyLegacy],
+        );
+        assert_eq!(sun.sun_shooter, sun.moon_shooter);
+        assert_eq!(
+            serde_json::to_value(&sun).unwrap()["sun_shooter"]["seat"],
+            "South"
+        );
+
+        let split = HandOutcomeRecord::new(
+            outcome([13, 13, 0, 0], South, South, North),
+            0,
+            [NormalHeuristic; 4],
+        );
+        assert_eq!(split.moon_shooter, None);
+    }
+
+    #[test]
+    fn markdown_has_a_row_per_agent() {
+        let outcomes = vec![outcome([13, 0, 0, 0], South, South, North)];
+        let stats = queen_stats(&outcomes, &ids(["normal"; 4]));
+        let table = queen_stats_markdown(&stats);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2], "| normal | 4 | 1 | 0 | 0 | 1 | 1 | 0.00 | 4.33 |");
+    }
+
+    #[test]
+    fn tempo_stats_count_leads_ducks_and_points() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(North, "2C 6D 9H")
+            .seat_hand(East, "3C 5D AD")
+            .seat_hand(South, "4C KD 4H")
+            .seat_hand(West, "5C 8D 6H")
+            .build();
+        let mut outcome = HandOutcome::dealt(1, &round);
+        // West leads 8♦ after the opening trick and East ducks it with 5♦
+        // despite holding A♦; South wins and leads hearts, which North
+        // overtakes.
+        for played in cards("2C 3C 4C 5C | 8D 6D 5D KD | 4H 6H 9H AD") {
+            let seat = to_play(&round);
+            let legal = round.legal_plays(seat);
+            outcome.note_play(round.current_trick(), seat, played, &legal);
+            round.play_card(seat, played).unwrap();
+        }
+        outcome.finish(&round);
+        assert_eq!(outcome.tricks.len(), 3);
+        assert_eq!(outcome.tricks[2].winner, Some(North));
+        let duck = outcome.tricks[1].plays[2];
+        assert_eq!((duck.seat, duck.card), (East, card("5D")));
+        assert!(duck.could_have_won);
+
+        let stats = tempo_stats(&[outcome], &ids(["hard", "normal", "normal", "normal"]));
+        let hard = &stats[0];
+        assert_eq!(
+            (hard.hands, hard.tricks_led, hard.tricks_followed),
+            (1, 1, 2)
+        );
+        assert_eq!((hard.could_have_won, hard.ducked), (1, 0));
+        assert_eq!(hard.avg_points_led, Some(0.0));
+        assert_eq!(hard.avg_points_followed, Some(1.5));
+        let normal = &stats[1];
+        assert_eq!(
+            (normal.hands, normal.tricks_led, normal.tricks_followed),
+            (3, 2, 7)
+        );
+        assert_eq!((normal.could_have_won, normal.ducked), (6, 1));
+        assert_eq!(normal.duck_rate, Some(1.0 / 6.0));
+        assert_eq!(normal.avg_points_followed, Some(0.0));
+    }
+
+    #[test]
+    fn fast_forwarded_plays_are_marked_and_skipped_by_tempo_stats() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(North, "2C 6D")
+            .seat_hand(East, "3C 5D")
+            .seat_hand(South, "4C KD")
+            .seat_hand(West, "5C 8D")
+            .build();
+        let mut outcome = HandOutcome::dealt(1, &round);
+        for (idx, played) in cards("2C 3C 4C 5C 8D 6D 5D KD").into_iter().enumerate() {
+            let seat = to_play(&round);
+            let legal = round.legal_plays(seat);
+            if idx < 4 {
+                outcome.note_play(round.current_trick(), seat, played, &legal);
+            } else {
+                outcome.note_auto_play(round.current_trick(), seat, played, &legal);
+            }
+            round.play_card(seat, played).unwrap();
+        }
+        outcome.finish(&round);
+        assert!(outcome.tricks[1].plays.iter().all(|play| play.auto));
+        let json = serde_json::to_value(&outcome).unwrap();
+        assert!(json["tricks"][0]["plays"][0].get("auto").is_none());
+        assert_eq!(json["tricks"][1]["plays"][0]["auto"], true);
+
+        let stats = tempo_stats(&[outcome], &ids(["normal"; 4]));
+        assert_eq!((stats[0].tricks_led, stats[0].tricks_followed), (1, 3));
+        assert_eq!((stats[0].could_have_won, stats[0].ducked), (3, 0));
+    }
+
+    /// A trick `winner` led and won, with `hearts` hearts among its plays and
+    /// `penalties` points in all.
+    fn scoring_trick(winner: PlayerPosition, hearts: usize, penalties: u8) -> TrickTrace {
+        let mut seat = winner;
+        let plays = (0..4)
+            .map(|idx| {
+                let code = if idx < hearts {
+                    ["2H", "3H", "4H", "5H"][idx]
+                } else {
+                    "2C"
+                };
+                let play = PlayTrace {
+                    seat,
+                    card: card(code),
+                    could_have_won: false,
+                    auto: false,
+                };
+                seat = seat.next();
+                play
+            })
+            .collect();
+        TrickTrace {
+            leader: winner,
+            plays,
+            winner: Some(winner),
+            penalties,
+        }
+    }
+
+    fn traced(penalties: [u8; 4], tricks: Vec<TrickTrace>) -> HandOutcome {
+        HandOutcome {
+            tricks,
+            ..outcome(penalties, North, North, North)
+        }
+    }
+
+    #[test]
+    fn moon_defense_joins_attempts_breaks_and_block_passes() {
+        // West runs five hearts, then North takes the first point off it.
+        let mut broken = traced(
+            [1, 0, 0, 25],
+            vec![
+                scoring_trick(West, 2, 2),
+                scoring_trick(West, 2, 15),
+                scoring_trick(West, 1, 1),
+                scoring_trick(North, 1, 1),
+                scoring_trick(West, 4, 7),
+            ],
+        );
+        broken.note_block_shooter_pass(North, 60);
+        // East takes everything.
+        let mut shot = traced(
+            [0, 26, 0, 0],
+            vec![
+                scoring_trick(East, 4, 4),
+                scoring_trick(East, 4, 17),
+                scoring_trick(East, 4, 4),
+                scoring_trick(East, 1, 1),
+            ],
+        );
+        // Every trick of the hand, so a sun.
+        shot.sun_shooter = Some(East);
+        shot.note_block_shooter_pass(North, 100);
+        shot.note_block_shooter_pass(East, 40);
+        // The points split before anyone gathers five hearts.
+        let split = traced(
+            [3, 0, 23, 0],
+            vec![scoring_trick(North, 3, 3), scoring_trick(South, 4, 23)],
+        );
+        // North's own attempt, broken by East.
+        let own = traced(
+            [20, 6, 0, 0],
+            vec![
+                scoring_trick(North, 4, 17),
+                scoring_trick(North, 3, 3),
+                scoring_trick(East, 4, 6),
+            ],
+        );
+
+        assert_eq!(
+            broken.moon_attempt(),
+            Some(MoonAttempt {
+                shooter: West,
+                shot: false,
+                sun: false,
+                broken_by: Some(North),
+            })
+        );
+        assert_eq!(
+            shot.moon_attempt(),
+            Some(MoonAttempt {
+                shooter: East,
+                shot: true,
+                sun: true,
+                broken_by: None,
+            })
+        );
+        assert_eq!(split.moon_attempt(), None);
+
+        let outcomes = [broken, shot, split, own];
+        let stats = moon_defense_stats(&outcomes, &ids(["hard", "normal", "normal", "normal"]));
+        let hard = &stats[0];
+        assert_eq!(hard.hands, 4);
+        assert_eq!(
+            (
+                hard.opponent_attempts,
+                hard.opponent_shots,
+                hard.opponent_suns,
+                hard.breaks
+            ),
+            (2, 1, 1, 1)
+        );
+        assert_eq!(
+            (hard.moon_success_rate, hard.break_rate),
+            (Some(0.5), Some(0.5))
+        );
+        assert_eq!((hard.block_passes, hard.avg_block_urgency), (2, Some(80.0)));
+        let normal = &stats[1];
+        assert_eq!(
+            (
+                normal.opponent_attempts,
+                normal.opponent_shots,
+                normal.breaks
+            ),
+            (1, 0, 1)
+        );
+        assert_eq!(
+            (normal.block_passes, normal.avg_block_urgency),
+            (1, Some(40.0))
+        );
+
+        let table = moon_defense_markdown(&stats);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "| hard | 4 | 2 | 1 | 1 | 0.50 | 1 | 0.50 | 2 | 80.00 |"
+        );
+        let json = serde_json::to_value(&stats[1]).unwrap();
+        assert_eq!(json["break_rate"], 1.0);
+    }
+
+    #[test]
+    fn ties_share_the_better_placing() {
+        assert_eq!(placements([40, 102, 67, 88]), [1, 4, 2, 3]);
+        assert_eq!(placements([55, 55, 90, 101]), [1, 1, 3, 4]);
+        assert_eq!(placements([20, 60, 60, 100]), [1, 2, 2, 4]);
+    }
+
+    #[test]
+    fn match_stats_pool_wins_placings_and_pph_per_agent() {
+        let seating = ["hard", "normal", "normal", "normal"];
+        let record = |totals: [u32; 4], rounds: u32, winner| MatchRecord {
+            seed: 0,
+            rounds,
+            seating,
+            totals,
+            placements: placements(totals),
+            winner: Some(winner),
+            moons: 0,
+            suns: 0,
+            points: totals.map(|total| total as i32),
+        };
+        let matches = vec![
+            record([40, 102, 67, 88], 8, North),
+            record([81, 100, 45, 81], 10, South),
+        ];
+        let stats = match_stats(&matches, &ids(seating));
+        let hard = &stats[0];
+        assert_eq!((hard.seats, hard.wins, hard.hands), (2, 1, 18));
+        assert_eq!(hard.win_rate, Some(0.5));
+        assert_eq!(hard.avg_placement, Some(1.5));
+        assert_eq!(hard.pph, Some(121.0 / 18.0));
+        let normal = &stats[1];
+        assert_eq!((normal.seats, normal.wins, normal.hands), (6, 1, 54));
+        assert_eq!(
+            normal.avg_placement,
+            Some((4 + 2 + 3 + 4 + 1 + 2) as f64 / 6.0)
+        );
+
+        let table = match_stats_markdown(&stats);
+        assert_eq!(
+            table.lines().nth(2),
+            Some("| hard | 2 | 1 | 0.50 | 1.50 | 18 | 6.72 |")
+        );
+
+        let hand = MatchRow::Hand {
+            round: 3,
+            totals: [10, 20, 30, 40],
+            record: HandOutcomeRecord::new(
+                outcome([0, 0, 26, 0], South, South, South),
+                0,
+                [
+                    FutureHard,
+                    NormalHeuristic,
+                    NormalHeuristic,
+                    NormalHeuristic,
+                ],
+            ),
+        };
+        let row = serde_json::to_value(&hand).unwrap();
+        assert_eq!(row["row"], "hand");
+        assert_eq!(row["round"], 3);
+        assert_eq!(row["penalties"], serde_json::json!([0, 0, 26, 0]));
+        assert_eq!(row["moon_shooter"]["agent"], "normal");
+        let row = serde_json::to_value(MatchRow::Match(matches[0].clone())).unwrap();
+        assert_eq!(row["row"], "match");
+        assert_eq!(row["placements"], serde_json::json!([1, 4, 2, 3]));
+        assert_eq!(row["winner"], "North");
+        assert!(row.get("points").is_none());
+    }
+}
@@ -0,0 +1,303 @@
+//! Output streams for batch artifacts: optional gzip/zstd compression of the
+//! JSONL streams, transparent decompression when reading them back, and
+//! retention of run directories named by a `run_id` template.
+
+use crate::paths::{PathError, PathVars};
+use flate2::Compression as GzLevel;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "none" => Some(Compression::None),
+            "gzip" | "gz" => Some(Compression::Gzip),
+            "zstd" | "zst" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// `play.jsonl` -> `play.jsonl.zst`; unchanged without compression or
+    /// when the path already carries the extension.
+    pub fn apply_extension(&self, path: &Path) -> PathBuf {
+        let ext = match self {
+            Compression::None => return path.to_path_buf(),
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+        };
+        if path.extension().is_some_and(|e| e == ext) {
+            return path.to_path_buf();
+        }
+        let mut name = path.as_os_str().to_owned();
+        name.push(".");
+        name.push(ext);
+        PathBuf::from(name)
+    }
+
+    /// Creates `path` (with the compression extension applied) for writing.
+    pub fn create(&self, path: &Path) -> io::Result<OutputWriter> {
+        let path = self.apply_extension(path);
+        let file = BufWriter::new(File::create(&path)?);
+        let inner = match self {
+            Compression::None => Inner::Plain(file),
+            Compression::Gzip => Inner::Gzip(GzEncoder::new(file, GzLevel::default())),
+            Compression::Zstd => Inner::Zstd(zstd::Encoder::new(file, ZSTD_LEVEL)?),
+        };
+        Ok(OutputWriter { path, inner })
+    }
+}
+
+enum Inner {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+/// A possibly compressed output file. Call [`OutputWriter::finish`] so the
+/// compressed trailer is written; dropping it leaves the stream truncated.
+pub struct OutputWriter {
+    path: PathBuf,
+    inner: Inner,
+}
+
+impl OutputWriter {
+    /// Writes the trailer and returns the path actually written, including
+    /// any compression extension.
+    pub fn finish(self) -> io::Result<PathBuf> {
+        let mut file = match self.inner {
+            Inner::Plain(file) => file,
+            Inner::Gzip(encoder) => encoder.finish()?,
+            Inner::Zstd(encoder) => encoder.finish()?,
+        };
+        file.flush()?;
+        Ok(self.path)
+    }
+}
+
+impl Write for OutputWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(file) => file.write(buf),
+            Inner::Gzip(encoder) => encoder.write(buf),
+            Inner::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            Inner::Plain(file) => file.flush(),
+            Inner::Gzip(encoder) => encoder.flush(),
+            Inner::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Opens `path` for reading, decompressing gzip or zstd content detected by
+/// its magic bytes rather than its extension.
+pub fn open_reader(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    let mut file = BufReader::new(File::open(path)?);
+    let head = file.fill_buf()?;
+    let reader: Box<dyn BufRead> = if head.starts_with(&ZSTD_MAGIC) {
+        Box::new(BufReader::new(zstd::Decoder::with_buffer(file)?))
+    } else if head.starts_with(&GZIP_MAGIC) {
+        Box::new(BufReader::new(MultiGzDecoder::new(file)))
+    } else {
+        Box::new(file)
+    };
+    Ok(reader)
+}
+
+/// [`fs::read_to_string`] that also accepts compressed files.
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    let mut contents = String::new();
+    open_reader(path)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+#[derive(Debug)]
+pub enum RetentionError {
+    /// The template has no directory component naming the run.
+    NoRunDirectory(String),
+    Path(PathError),
+    Io(io::Error),
+}
+
+impl std::fmt::Display for RetentionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetentionError::NoRunDirectory(template) => write!(
+                f,
+                "'{template}' has no directory containing ${{run_id}} to prune"
+            ),
+            RetentionError::Path(err) => err.fmt(f),
+            RetentionError::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+/// Index of the first directory (not the final file name) mentioning the run id.
+fn run_dir_index(components: &[&str]) -> Option<usize> {
+    components
+        .iter()
+        .position(|c| c.contains("{run_id}"))
+        .filter(|&idx| idx + 1 < components.len())
+}
+
+/// Whether `template` names a per-run directory that [`prune_runs`] can prune.
+pub fn names_run_directory(template: &str) -> bool {
+    let components: Vec<&str> = template.split(['/', '\\']).collect();
+    run_dir_index(&components).is_some()
+}
+
+/// Keeps the newest `keep_last` run directories named by `template` (the
+/// current run always among them) and deletes the rest. The run directory is
+/// the first path component containing `${run_id}` or `{run_id}`; siblings
+/// count as runs when they match its text around the run id. Returns the
+/// directories removed.
+pub fn prune_runs(
+    template: &str,
+    vars: &PathVars,
+    keep_last: usize,
+) -> Result<Vec<PathBuf>, RetentionError> {
+    let components: Vec<&str> = template.split(['/', '\\']).collect();
+    let no_dir = || RetentionError::NoRunDirectory(template.to_string());
+    let idx = run_dir_index(&components).ok_or_else(no_dir)?;
+    let component = components[idx];
+    let marker = if component.contains("${run_id}") {
+        "${run_id}"
+    } else {
+        "{run_id}"
+    };
+    let (prefix, suffix) = component.split_once(marker).ok_or_else(no_dir)?;
+    let prefix = vars.expand(prefix).map_err(RetentionError::Path)?;
+    let suffix = vars.expand(suffix).map_err(RetentionError::Path)?;
+    let parent = if idx == 0 {
+        PathBuf::from(".")
+    } else {
+        vars.expand_path(&components[..idx].join("/"))
+            .map_err(RetentionError::Path)?
+    };
+    let current = format!("{prefix}{}{suffix}", vars.run_id);
+
+    let mut runs = Vec::new();
+    for entry in fs::read_dir(&parent).map_err(RetentionError::Io)? {
+        let entry = entry.map_err(RetentionError::Io)?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let is_run = name.len() > prefix.len() + suffix.len()
+            && name.starts_with(&prefix)
+            && name.ends_with(&suffix);
+        if !is_run || name == current || !entry.path().is_dir() {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|m| m.modified()).ok();
+        runs.push((modified, name, entry.path()));
+    }
+    // Newest first; run ids stamped with the date sort the same way by name.
+    runs.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+    let mut removed = Vec::new();
+    for (_, _, path) in runs.into_iter().skip(keep_last.saturating_sub(1)) {
+        fs::remove_dir_all(&path).map_err(RetentionError::Io)?;
+        removed.push(path);
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Compression, names_run_directory, open_reader, prune_runs, read_to_string};
+    use crate::paths::PathVars;
+    use std::io::{BufRead, Write};
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("mdhearts_outputs_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn compressed_streams_read_back_transparently() {
+        let dir = temp_dir("roundtrip");
+        let lines: Vec<String> = (0..200).map(|i| format!("{{\"seed\":{i}}}")).collect();
+        for compression in [Compression::None, Compression::Gzip, Compression::Zstd] {
+            let mut writer = compression.create(&dir.join("rows.jsonl")).unwrap();
+            for line in &lines {
+                writeln!(writer, "{line}").unwrap();
+            }
+            let path = writer.finish().unwrap();
+            let expected = match compression {
+                Compression::None => "rows.jsonl",
+                Compression::Gzip => "rows.jsonl.gz",
+                Compression::Zstd => "rows.jsonl.zst",
+            };
+            assert_eq!(path, dir.join(expected));
+            let read: Vec<String> = open_reader(&path)
+                .unwrap()
+                .lines()
+                .map(Result::unwrap)
+                .collect();
+            assert_eq!(read, lines, "{compression:?}");
+        }
+        let plain = std::fs::metadata(dir.join("rows.jsonl")).unwrap().len();
+        let zst = std::fs::metadata(dir.join("rows.jsonl.zst")).unwrap().len();
+        assert!(zst < plain / 2, "zstd {zst} vs plain {plain}");
+        assert_eq!(
+            Compression::Zstd.apply_extension(&dir.join("a.jsonl.zst")),
+            dir.join("a.jsonl.zst")
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn retention_keeps_the_newest_runs_and_the_current_one() {
+        let dir = temp_dir("retention");
+        for id in ["r1", "r2", "r3", "r4"] {
+            std::fs::create_dir_all(dir.join(format!("run-{id}"))).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        std::fs::create_dir_all(dir.join("notes")).unwrap();
+        let vars = PathVars {
+            run_id: "r2".to_string(),
+            date: "20261014".to_string(),
+        };
+        let template = format!("{}/run-${{run_id}}/match.csv", dir.display());
+        let removed = prune_runs(&template, &vars, 2).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(dir.join("run-r2").is_dir());
+        assert!(dir.join("run-r4").is_dir());
+        assert!(!dir.join("run-r1").exists());
+        assert!(!dir.join("run-r3").exists());
+        assert!(dir.join("notes").is_dir());
+
+        let flat = format!("{}/match-${{run_id}}.csv", dir.display());
+        assert!(!names_run_directory(&flat));
+        assert!(prune_runs(&flat, &vars, 2).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn plain_files_are_read_unchanged() {
+        let dir = temp_dir("plain");
+        let path = dir.join("telemetry.ndjson");
+        std::fs::write(&path, "{}\n{}\n").unwrap();
+        assert_eq!(read_to_string(&path).unwrap(), "{}\n{}\n");
+        std::fs::write(&path, "").unwrap();
+        assert_eq!(read_to_string(&path).unwrap(), "");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
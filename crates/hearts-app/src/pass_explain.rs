@@ -0,0 +1,229 @@
+//! Post-hoc explanation of a pass decision.
+//!
+//! `--explain-pass` rebuilds the position a seat passed from — the dealt
+//! hands, the direction, the scores and the difficulty — and reruns the pass
+//! planner on it with the breakdown kept. The planner reads nothing but
+//! those inputs and the pass weights, and the weights are part of the
+//! output, so the same request shows the decision the bot made.
+//!
+//! A deal is written as the four hands in N, E, S, W order separated by
+//! `/`, each hand as space-separated card codes:
+//! `2C 5C 9D ... / 3C KD ... / ... / ...`.
+
+use crate::bot::analysis::evaluate_passes;
+use crate::bot::{
+    BotContext, BotDifficulty, PassExplanation, PassPlanner, PassWeights, UnseenTracker,
+};
+use hearts_core::model::card::{Card, CardList};
+use hearts_core::model::deal::Deal;
+use hearts_core::model::hand::Hand;
+use hearts_core::model::passing::{PassingDirection, PassingState};
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::round::{RoundPhase, RoundState};
+use hearts_core::model::score::ScoreBoard;
+use std::fmt::Write as _;
+
+/// Reads a deal string (see the module docs) into the four hands.
+pub fn parse_deal(text: &str) -> Result<[Vec<Card>; 4], String> {
+    let deal = Deal::parse(text).map_err(|err| err.to_string())?;
+    Ok(PlayerPosition::LOOP.map(|seat| deal.hand(seat).cards().to_vec()))
+}
+
+/// The deal string for `hands`, each sorted by suit then rank.
+pub fn deal_string(hands: &[Vec<Card>; 4]) -> String {
+    let hands: Vec<String> = hands
+        .iter()
+        .map(|hand| Hand::with_cards(hand.clone()).to_string())
+        .collect();
+    hands.join(" / ")
+}
+
+/// Everything a pass decision depends on.
+#[derive(Debug, Clone)]
+pub struct PassRequest {
+    pub hands: [Vec<Card>; 4],
+    pub seat: PlayerPosition,
+    pub direction: PassingDirection,
+    pub scores: [u32; 4],
+    pub difficulty: BotDifficulty,
+    pub weights: PassWeights,
+}
+
+/// Reruns the planner for `request`, keeping `alternatives` runners-up.
+/// `None` when the direction does not pass.
+pub fn explain(request: &PassRequest, alternatives: usize) -> Option<PassExplanation> {
+    if !request.direction.requires_selection() {
+        return None;
+    }
+    let hands = request.hands.clone().map(Hand::with_cards);
+    let phase = RoundPhase::Passing(PassingState::new(request.direction));
+    let round = RoundState::from_hands(hands, request.seat, request.direction, phase);
+    let mut tracker = UnseenTracker::new();
+    tracker.reset_for_round(&round);
+    let mut scores = ScoreBoard::new();
+    scores.set_totals(request.scores);
+    let ctx = BotContext::new(
+        request.seat,
+        &round,
+        scores,
+        request.direction,
+        &tracker,
+        request.difficulty,
+    );
+    let ranked = evaluate_passes(&ctx, request.weights);
+    PassPlanner::explain_ranked(
+        round.hand(request.seat),
+        &ctx,
+        request.weights,
+        ranked,
+        alternatives,
+    )
+}
+
+/// Human-readable form of `explanation`.
+pub fn render_text(explanation: &PassExplanation) -> String {
+    let mut out = String::new();
+    let chosen = &explanation.chosen;
+    let _ = writeln!(
+        out,
+        "Pass for {:?} ({}, style {}): {}  score {}",
+        explanation.seat,
+        explanation.direction,
+        explanation.style,
+        CardList(&chosen.cards),
+        chosen.total
+    );
+    let _ = writeln!(
+        out,
+        "  to leader: {}  to trailing: {}  candidates: {}  weights: to_leader_penalty={} hearts_void_plan={} heartbreak_keep={}",
+        explanation.passing_to_leader,
+        explanation.passing_to_trailing,
+        explanation.candidates,
+        explanation.weights.to_leader_penalty,
+        explanation.weights.hearts_void_plan,
+        explanation.weights.heartbreak_keep
+    );
+    for scored in &chosen.per_card {
+        let parts: Vec<String> = scored
+            .parts
+            .iter()
+            .map(|part| format!("{}={}", part.name, part.delta))
+            .collect();
+        let _ = writeln!(
+            out,
+            "  {:>3} {:>7}  {}",
+            scored.card.to_string(),
+            scored.total,
+            parts.join(" ")
+        );
+    }
+    for part in &chosen.set_parts {
+        let _ = writeln!(out, "  set {:>7}  {}", part.delta, part.name);
+    }
+    let guards = chosen.guards();
+    if guards.is_empty() {
+        let _ = writeln!(out, "Guards: none");
+    } else {
+        let _ = writeln!(out, "Guards: {}", guards.join(", "));
+    }
+    if !explanation.alternatives.is_empty() {
+        let _ = writeln!(out, "Alternatives:");
+        for (rank, alt) in explanation.alternatives.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "  {}. {}  score {} ({:+})",
+                rank + 2,
+                CardList(&alt.cards),
+                alt.total,
+                alt.total - chosen.total
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PassRequest, deal_string, explain, parse_deal, render_text};
+    use crate::bot::{BotContext, BotDifficulty, PassPlanner, PassWeights, UnseenTracker};
+    use hearts_core::game::match_state::MatchState;
+    use hearts_core::model::passing::PassingDirection;
+    use hearts_core::model::player::PlayerPosition;
+
+    fn dealt(seed: u64) -> [Vec<hearts_core::model::card::Card>; 4] {
+        let state = MatchState::with_seed(PlayerPosition::North, seed);
+        PlayerPosition::LOOP.map(|seat| state.round().hand(seat).cards().to_vec())
+    }
+
+    #[test]
+    fn deal_strings_round_trip_and_name_the_bad_card() {
+        let hands = dealt(5);
+        let text = deal_string(&hands);
+        let parsed = parse_deal(&text).unwrap();
+        assert_eq!(deal_string(&parsed), text);
+
+        let first = hands[0][0].to_string();
+        let duplicated = text.replacen(&hands[1][0].to_string(), &first, 1);
+        let err = parse_deal(&duplicated).unwrap_err();
+        assert!(err.contains(&first) && err.contains("North"), "{err}");
+        assert!(parse_deal("2C / 3C").unwrap_err().contains("4 hands"));
+        assert!(
+            parse_deal(&text.replacen(&first, "1X", 1))
+                .unwrap_err()
+                .contains("1X")
+        );
+    }
+
+    #[test]
+    fn explanation_reproduces_the_planner_choice() {
+        let hands = dealt(17);
+        let mut state = MatchState::with_seed(PlayerPosition::North, 17);
+        state.scores_mut().set_totals([12, 40, 3, 25]);
+        let round = state.round();
+        assert_eq!(round.passing_direction(), PassingDirection::Left);
+        for seat in PlayerPosition::LOOP {
+            let mut tracker = UnseenTracker::new();
+            tracker.reset_for_round(round);
+            let ctx = BotContext::new(
+                seat,
+                round,
+                *state.scores(),
+                PassingDirection::Left,
+                &tracker,
+                BotDifficulty::NormalHeuristic,
+            );
+            let chosen = PassPlanner::choose(round.hand(seat), &ctx).unwrap();
+            let request = PassRequest {
+                hands: hands.clone(),
+                seat,
+                direction: PassingDirection::Left,
+                scores: [12, 40, 3, 25],
+                difficulty: BotDifficulty::NormalHeuristic,
+                weights: PassWeights::default(),
+            };
+            let explanation = explain(&request, 5).unwrap();
+            assert_eq!(explanation.chosen.cards, chosen, "{seat:?}");
+            assert_eq!(explanation.alternatives.len(), 5);
+            let per_card: i32 = explanation.chosen.per_card.iter().map(|c| c.total).sum();
+            let set: i32 = explanation.chosen.set_parts.iter().map(|p| p.delta).sum();
+            assert_eq!(explanation.chosen.total, per_card + set);
+            assert!(
+                explanation
+                    .alternatives
+                    .iter()
+                    .all(|alt| alt.total <= explanation.chosen.total)
+            );
+            assert!(render_text(&explanation).contains("Alternatives:"));
+        }
+
+        let hold = PassRequest {
+            hands,
+            seat: PlayerPosition::North,
+            direction: PassingDirection::Hold,
+            scores: [0; 4],
+            difficulty: BotDifficulty::NormalHeuristic,
+            weights: PassWeights::default(),
+        };
+        assert!(explain(&hold, 5).is_none());
+    }
+}
@@ -0,0 +1,238 @@
+//! Interpolation for path-like settings (CLI output paths, seed lists, weight
+//! files), resolved once when the setting is read.
+//!
+//! `${NAME}` expands to `run_id`, `date`, or else the environment variable
+//! `NAME`; variable values are expanded in turn, so `MDH_OUT=${HOME}/runs`
+//! works. The bare `{run_id}` and `{date}` forms are accepted as well. Any
+//! other text, including backslashes in Windows paths, is kept as written.
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many levels of variables-inside-variables are followed before the
+/// expansion is treated as a cycle.
+const MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    MissingVar { name: String, template: String },
+    Unterminated(String),
+    Cycle { name: String, template: String },
+}
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathError::MissingVar { name, template } => {
+                write!(
+                    f,
+                    "environment variable {name} is not set (in '{template}')"
+                )
+            }
+            PathError::Unterminated(template) => {
+                write!(f, "unterminated '${{' in '{template}'")
+            }
+            PathError::Cycle { name, template } => {
+                write!(f, "variable {name} expands into itself (in '{template}')")
+            }
+        }
+    }
+}
+
+/// Values for the built-in `run_id` and `date` variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathVars {
+    pub run_id: String,
+    /// UTC date as `YYYYMMDD`.
+    pub date: String,
+}
+
+impl PathVars {
+    /// Vars for a run started now; `run_id` defaults to `YYYYMMDD-HHMMSS` (UTC).
+    pub fn now(run_id: Option<String>) -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let (date, time) = utc_stamp(secs);
+        Self {
+            run_id: run_id.unwrap_or_else(|| format!("{date}-{time}")),
+            date,
+        }
+    }
+
+    pub fn expand(&self, template: &str) -> Result<String, PathError> {
+        self.expand_with(template, &|name| std::env::var(name).ok())
+    }
+
+    pub fn expand_path(&self, path: &str) -> Result<PathBuf, PathError> {
+        self.expand(path).map(PathBuf::from)
+    }
+
+    fn expand_with(
+        &self,
+        template: &str,
+        env: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<String, PathError> {
+        self.expand_at(template, template, env, 0)
+    }
+
+    fn expand_at(
+        &self,
+        text: &str,
+        template: &str,
+        env: &dyn Fn(&str) -> Option<String>,
+        depth: usize,
+    ) -> Result<String, PathError> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            let dollar = rest[..start].ends_with('$');
+            out.push_str(&rest[..start - usize::from(dollar)]);
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else {
+                if dollar {
+                    return Err(PathError::Unterminated(template.to_string()));
+                }
+                out.push_str(&rest[start..]);
+                return Ok(out);
+            };
+            let name = &after[..end];
+            rest = &after[end + 1..];
+            match (name, dollar) {
+                ("run_id", _) => out.push_str(&self.run_id),
+                ("date", _) => out.push_str(&self.date),
+                (_, false) => {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                }
+                (_, true) => {
+                    let value = env(name).ok_or_else(|| PathError::MissingVar {
+                        name: name.to_string(),
+                        template: template.to_string(),
+                    })?;
+                    if depth >= MAX_DEPTH {
+                        return Err(PathError::Cycle {
+                            name: name.to_string(),
+                            template: template.to_string(),
+                        });
+                    }
+                    out.push_str(&self.expand_at(&value, template, env, depth + 1)?);
+                }
+            }
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// Expands an environment-configured file path for a setting that has no run
+/// of its own, so `run_id` is the session start stamp.
+pub fn expand_env_path(path: &str) -> Result<PathBuf, PathError> {
+    PathVars::now(None).expand_path(path)
+}
+
+/// `(YYYYMMDD, HHMMSS)` for a UNIX timestamp in seconds.
+fn utc_stamp(secs: u64) -> (String, String) {
+    let days = (secs / 86_400) as i64;
+    let tod = secs % 86_400;
+    // Civil-from-days (proleptic Gregorian), counting eras from 0000-03-01.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (
+        format!("{year:04}{month:02}{day:02}"),
+        format!("{:02}{:02}{:02}", tod / 3600, (tod % 3600) / 60, tod % 60),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PathError, PathVars, utc_stamp};
+
+    fn vars() -> PathVars {
+        PathVars {
+            run_id: "r42".to_string(),
+            date: "20261014".to_string(),
+        }
+    }
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "MDH_ROOT" => Some("${HOME}/runs".to_string()),
+            "HOME" => Some("/home/dev".to_string()),
+            "USERPROFILE" => Some(r"C:\Users\dev".to_string()),
+            "LOOP" => Some("${LOOP}".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn builtins_and_nested_variables_expand() {
+        assert_eq!(
+            vars().expand_with("${MDH_ROOT}/${run_id}/out-${date}.csv", &env),
+            Ok("/home/dev/runs/r42/out-20261014.csv".to_string())
+        );
+        assert_eq!(
+            vars().expand_with("plots/{run_id}/{other}", &env),
+            Ok("plots/r42/{other}".to_string())
+        );
+        assert_eq!(
+            vars().expand_with("no vars {here", &env),
+            Ok("no vars {here".to_string())
+        );
+    }
+
+    #[test]
+    fn windows_paths_keep_backslashes() {
+        assert_eq!(
+            vars().expand_with(r"${USERPROFILE}\mdhearts\{run_id}\match.csv", &env),
+            Ok(r"C:\Users\dev\mdhearts\r42\match.csv".to_string())
+        );
+        assert_eq!(
+            vars().expand_with(r"D:\data\${run_id}", &env),
+            Ok(r"D:\data\r42".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_unterminated_and_cyclic_variables_are_errors() {
+        let err = vars().expand_with("${MDH_NOPE}/x", &env).unwrap_err();
+        assert_eq!(
+            err,
+            PathError::MissingVar {
+                name: "MDH_NOPE".to_string(),
+                template: "${MDH_NOPE}/x".to_string(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "environment variable MDH_NOPE is not set (in '${MDH_NOPE}/x')"
+        );
+        assert_eq!(
+            vars().expand_with("out/${run_id", &env),
+            Err(PathError::Unterminated("out/${run_id".to_string()))
+        );
+        assert!(matches!(
+            vars().expand_with("${LOOP}", &env),
+            Err(PathError::Cycle { .. })
+        ));
+    }
+
+    #[test]
+    fn utc_stamp_formats_calendar_dates() {
+        assert_eq!(utc_stamp(0), ("19700101".to_string(), "000000".to_string()));
+        // 2024-02-29T23:59:58Z
+        assert_eq!(
+            utc_stamp(1_709_251_198),
+            ("20240229".to_string(), "235958".to_string())
+        );
+    }
+}
@@ -0,0 +1,491 @@
+//! Seat permutations for mixed-agent evaluation.
+//!
+//! A deal played once per distinct seat assignment of the mix cancels the
+//! luck of who sat where. Every permutation list starts with the balanced
+//! block: the four rotations of the mix, which put each agent in each seat
+//! exactly once. Adaptive mode plays that block and stops when the agents
+//! score close together on the deal, since more permutations barely move the
+//! estimate there.
+//!
+//! Deals can end up with different permutation counts, so per-agent PPH is
+//! the mean of per-deal means: each deal counts once however many rows it
+//! produced.
+//!
+//! The same per-deal means pair up agents for a paired comparison: each
+//! deal contributes one difference, and a seeded bootstrap over deals gives
+//! the confidence interval and p-value, so a rerun reports the same numbers.
+
+use crate::agent_id::AgentId;
+use crate::bot::BotDifficulty;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+/// Rotations played before adaptive mode may stop.
+pub const BALANCED_BLOCK: usize = 4;
+
+/// Default bootstrap resamples per paired comparison.
+pub const DEFAULT_BOOTSTRAP_RESAMPLES: u32 = 2000;
+
+/// Two-sided level below which a paired comparison is flagged.
+pub const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Default between-agent PPH spread below which adaptive mode stops after
+/// the balanced block.
+pub const DEFAULT_ADAPTIVE_SPREAD: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PermutationMode {
+    /// Every distinct seat assignment (24 for four different agents).
+    Full,
+    /// The balanced block, then the rest only if the per-deal spread between
+    /// agents is at least `spread` points.
+    Adaptive { spread: f64 },
+}
+
+impl PermutationMode {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "full" => Some(PermutationMode::Full),
+            "adaptive" => Some(PermutationMode::Adaptive {
+                spread: DEFAULT_ADAPTIVE_SPREAD,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PermutationMode::Full => "full",
+            PermutationMode::Adaptive { .. } => "adaptive",
+        }
+    }
+}
+
+/// Distinct seat assignments of `mix` (N,E,S,W), balanced block first and the
+/// remainder in lexicographic order of seat indices. Duplicate agents collapse
+/// assignments, so `nnnh` has only its four rotations.
+pub fn seat_permutations(mix: [BotDifficulty; 4]) -> Vec<[BotDifficulty; 4]> {
+    let mut order: Vec<[usize; 4]> = (0..4)
+        .map(|r| [r, (r + 1) % 4, (r + 2) % 4, (r + 3) % 4])
+        .collect();
+    for a in 0..4 {
+        for b in 0..4 {
+            for c in 0..4 {
+                for d in 0..4 {
+                    let idx = [a, b, c, d];
+                    let distinct = (0..4).all(|i| (i + 1..4).all(|j| idx[i] != idx[j]));
+                    if distinct && !order.contains(&idx) {
+                        order.push(idx);
+                    }
+                }
+            }
+        }
+    }
+    let mut perms: Vec<[BotDifficulty; 4]> = Vec::with_capacity(order.len());
+    for idx in order {
+        let perm = idx.map(|i| mix[i]);
+        if !perms.contains(&perm) {
+            perms.push(perm);
+        }
+    }
+    perms
+}
+
+/// The `e|n|h|s|r` mix string for a seat assignment.
+pub fn mix_code(mix: [BotDifficulty; 4]) -> String {
+    mix.iter()
+        .map(|difficulty| match difficulty {
+            BotDifficulty::EasyLegacy => 'e',
+            BotDifficulty::NormalHeuristic => 'n',
+            BotDifficulty::FutureHard => 'h',
+            BotDifficulty::SearchLookahead => 's',
+            BotDifficulty::Rollout => 'r',
+        })
+        .collect()
+}
+
+/// One deal's permutations and the points each produced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DealPermutations {
+    pub seed: u64,
+    pub played: Vec<([BotDifficulty; 4], [i32; 4])>,
+    /// Distinct permutations the mix has; `played.len()` may be fewer.
+    pub available: usize,
+}
+
+impl DealPermutations {
+    /// Mean points per hand for each agent over the seats it held on this
+    /// deal, in order of first appearance.
+    pub fn agent_means(&self) -> Vec<(AgentId, f64)> {
+        let mut sums: Vec<(AgentId, i32, u32)> = Vec::new();
+        for (perm, penalties) in &self.played {
+            for (difficulty, pen) in perm.iter().zip(penalties) {
+                let label = AgentId::of(*difficulty);
+                match sums.iter_mut().find(|(agent, _, _)| *agent == label) {
+                    Some((_, total, seats)) => {
+                        *total += *pen;
+                        *seats += 1;
+                    }
+                    None => sums.push((label, *pen, 1)),
+                }
+            }
+        }
+        sums.into_iter()
+            .map(|(agent, total, seats)| (agent, f64::from(total) / f64::from(seats)))
+            .collect()
+    }
+
+    /// Highest minus lowest agent mean on this deal.
+    pub fn spread(&self) -> f64 {
+        let means = self.agent_means();
+        let max = means.iter().map(|(_, m)| *m).fold(f64::MIN, f64::max);
+        let min = means.iter().map(|(_, m)| *m).fold(f64::MAX, f64::min);
+        if means.is_empty() { 0.0 } else { max - min }
+    }
+}
+
+/// Plays `seed` under `mode`, calling `play` once per seat assignment.
+pub fn play_deal<E>(
+    seed: u64,
+    mix: [BotDifficulty; 4],
+    mode: PermutationMode,
+    mut play: impl FnMut([BotDifficulty; 4]) -> Result<[i32; 4], E>,
+) -> Result<DealPermutations, E> {
+    let perms = seat_permutations(mix);
+    let mut deal = DealPermutations {
+        seed,
+        played: Vec::with_capacity(perms.len()),
+        available: perms.len(),
+    };
+    for (idx, perm) in perms.into_iter().enumerate() {
+        if idx == BALANCED_BLOCK
+            && let PermutationMode::Adaptive { spread } = mode
+            && deal.spread() < spread
+        {
+            break;
+        }
+        let penalties = play(perm)?;
+        deal.played.push((perm, penalties));
+    }
+    Ok(deal)
+}
+
+/// Per-agent PPH as the mean of per-deal means, in order of first appearance.
+pub fn per_deal_pph(deals: &[DealPermutations]) -> Vec<(AgentId, f64)> {
+    let mut sums: Vec<(AgentId, f64, u32)> = Vec::new();
+    for deal in deals {
+        for (label, mean) in deal.agent_means() {
+            match sums.iter_mut().find(|(agent, _, _)| *agent == label) {
+                Some((_, total, count)) => {
+                    *total += mean;
+                    *count += 1;
+                }
+                None => sums.push((label, mean, 1)),
+            }
+        }
+    }
+    sums.into_iter()
+        .map(|(agent, total, count)| (agent, total / f64::from(count)))
+        .collect()
+}
+
+/// Paired per-deal comparison of two agents, `right` against `left`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PairedComparison {
+    pub left: AgentId,
+    pub right: AgentId,
+    /// Deals both agents played.
+    pub deals: usize,
+    /// Mean of `right`'s per-deal PPH minus `left`'s; negative means `right`
+    /// took fewer points.
+    pub mean_delta: f64,
+    /// Bootstrap 95% percentile interval for `mean_delta`.
+    pub ci_low: f64,
+    pub ci_high: f64,
+    /// Two-sided bootstrap p-value for no difference.
+    pub p_value: f64,
+    pub significant: bool,
+}
+
+/// Paired comparisons for every pair of agents, in order of first
+/// appearance. Each pair resamples its deals `resamples` times from an RNG
+/// seeded with `seed`, so the result depends only on the deals and the seed.
+pub fn paired_comparisons(
+    deals: &[DealPermutations],
+    resamples: u32,
+    seed: u64,
+) -> Vec<PairedComparison> {
+    let means: Vec<Vec<(AgentId, f64)>> = deals.iter().map(|d| d.agent_means()).collect();
+    let agents: Vec<AgentId> = per_deal_pph(deals).into_iter().map(|(a, _)| a).collect();
+    let mut out = Vec::new();
+    for (i, left) in agents.iter().enumerate() {
+        for right in &agents[i + 1..] {
+            let find = |deal: &[(AgentId, f64)], agent| {
+                deal.iter()
+                    .find(|(label, _)| label == agent)
+                    .map(|(_, m)| *m)
+            };
+            let deltas: Vec<f64> = means
+                .iter()
+                .filter_map(|deal| Some(find(deal, right)? - find(deal, left)?))
+                .collect();
+            if deltas.is_empty() {
+                continue;
+            }
+            let mut rng = StdRng::seed_from_u64(seed);
+            out.push(bootstrap_pair(
+                left.clone(),
+                right.clone(),
+                &deltas,
+                resamples,
+                &mut rng,
+            ));
+        }
+    }
+    out
+}
+
+fn bootstrap_pair(
+    left: AgentId,
+    right: AgentId,
+    deltas: &[f64],
+    resamples: u32,
+    rng: &mut StdRng,
+) -> PairedComparison {
+    let n = deltas.len();
+    let mean_delta = deltas.iter().sum::<f64>() / n as f64;
+    let mut boot: Vec<f64> = (0..resamples.max(1))
+        .map(|_| (0..n).map(|_| deltas[rng.gen_range(0..n)]).sum::<f64>() / n as f64)
+        .collect();
+    boot.sort_by(f64::total_cmp);
+    let quantile = |q: f64| boot[((boot.len() - 1) as f64 * q).round() as usize];
+    let (ci_low, ci_high) = (quantile(0.025), quantile(0.975));
+    // Resampled means centred on zero stand in for "no difference"; the
+    // p-value is the share at least as far from zero as the observed mean.
+    let extreme = boot
+        .iter()
+        .filter(|m| (*m - mean_delta).abs() >= mean_delta.abs() - 1e-12)
+        .count();
+    let p_value = (extreme as f64 + 1.0) / (boot.len() as f64 + 1.0);
+    PairedComparison {
+        left,
+        right,
+        deals: n,
+        mean_delta,
+        ci_low,
+        ci_high,
+        p_value,
+        significant: p_value < SIGNIFICANCE_LEVEL && (ci_low > 0.0 || ci_high < 0.0),
+    }
+}
+
+pub fn paired_markdown(rows: &[PairedComparison]) -> String {
+    let mut out = String::from(
+        "| left | right | deals | mean delta | 95% CI | p | significant |\n\
+         |---|---|---:|---:|---|---:|---|\n",
+    );
+    for row in rows {
+        out.push_str(&format!(
+            "| {} | {} | {} | {:+.3} | [{:+.3}, {:+.3}] | {:.4} | {} |\n",
+            row.left,
+            row.right,
+            row.deals,
+            row.mean_delta,
+            row.ci_low,
+            row.ci_high,
+            row.p_value,
+            if row.significant { "**yes**" } else { "no" }
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BALANCED_BLOCK, DealPermutations, PermutationMode, paired_comparisons, paired_markdown,
+        per_deal_pph, play_deal, seat_permutations,
+    };
+    use crate::agent_id::AgentId;
+    use crate::bot::BotDifficulty;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::convert::Infallible;
+
+    const E: BotDifficulty = BotDifficulty::EasyLegacy;
+    const N: BotDifficulty = BotDifficulty::NormalHeuristic;
+    const H: BotDifficulty = BotDifficulty::FutureHard;
+    const S: BotDifficulty = BotDifficulty::SearchLookahead;
+
+    #[test]
+    fn balanced_block_seats_every_agent_everywhere_once() {
+        let perms = seat_permutations([N, H, E, S]);
+        assert_eq!(perms.len(), 24);
+        for seat in 0..4 {
+            let seen: Vec<BotDifficulty> =
+                perms[..BALANCED_BLOCK].iter().map(|p| p[seat]).collect();
+            for agent in [N, H, E, S] {
+                assert!(seen.contains(&agent), "seat {seat} never had {agent:?}");
+            }
+        }
+        assert_eq!(seat_permutations([N, N, N, H]).len(), 4);
+        assert_eq!(seat_permutations([N, N, H, H]).len(), 6);
+        assert_eq!(seat_permutations([N, N, N, N]), vec![[N, N, N, N]]);
+    }
+
+    #[test]
+    fn adaptive_stops_only_on_flat_deals() {
+        let flat = play_deal(
+            1,
+            [N, H, N, H],
+            PermutationMode::Adaptive { spread: 1.0 },
+            |_| Ok::<_, Infallible>([6, 7, 6, 7]),
+        )
+        .unwrap();
+        assert_eq!(flat.played.len(), BALANCED_BLOCK);
+        assert_eq!(flat.available, 6);
+
+        // Hard always takes the queen: a wide spread keeps going.
+        let wide = play_deal(
+            2,
+            [N, H, N, H],
+            PermutationMode::Adaptive { spread: 1.0 },
+            |perm| Ok::<_, Infallible>(perm.map(|d| if d == H { 13 } else { 0 })),
+        )
+        .unwrap();
+        assert_eq!(wide.played.len(), 6);
+    }
+
+    #[test]
+    fn pph_weights_each_deal_once() {
+        let short = DealPermutations {
+            seed: 1,
+            played: vec![([N, H, N, H], [0, 26, 0, 0]); 4],
+            available: 6,
+        };
+        let long = DealPermutations {
+            seed: 2,
+            played: vec![([N, H, N, H], [0, 0, 0, 0]); 24],
+            available: 24,
+        };
+        let pph = per_deal_pph(&[short, long]);
+        // Hard averages 13 on the first deal and 0 on the second; pooling
+        // rows instead would give 52/56.
+        assert_eq!(pph, vec![(AgentId::of(N), 0.0), (AgentId::of(H), 6.5)]);
+    }
+
+    /// Synthetic deals: a flat share where every agent scores alike, and
+    /// contested deals with strong seat effects, an agent edge and noise.
+    fn synthetic_penalties(seed: u64, perm: [BotDifficulty; 4], salt: u64) -> [i32; 4] {
+        let mut deal_rng = StdRng::seed_from_u64(seed);
+        if deal_rng.gen_range(0..100) < 60 {
+            return [6, 7, 6, 7];
+        }
+        let seat_effect: [f64; 4] = std::array::from_fn(|_| deal_rng.gen_range(0.0..12.0));
+        let mut noise_rng = StdRng::seed_from_u64(seed ^ salt.wrapping_mul(0x9E37_79B9));
+        std::array::from_fn(|seat| {
+            let edge = match perm[seat] {
+                N => 1.5,
+                H => 0.0,
+                E => 4.0,
+                S => -0.5,
+                BotDifficulty::Rollout => 0.5,
+            };
+            let noise: f64 = noise_rng.gen_range(-3.0..3.0);
+            (seat_effect[seat] + edge + noise).clamp(0.0, 26.0).round() as i32
+        })
+    }
+
+    #[test]
+    fn adaptive_estimate_converges_to_full_estimate() {
+        let mix = [N, H, E, S];
+        let run = |mode| {
+            let deals: Vec<DealPermutations> = (0..600u64)
+                .map(|seed| {
+                    let mut salt = 0u64;
+                    play_deal(seed, mix, mode, |perm| {
+                        salt += 1;
+                        Ok::<_, Infallible>(synthetic_penalties(seed, perm, salt))
+                    })
+                    .unwrap()
+                })
+                .collect();
+            let played: usize = deals.iter().map(|d| d.played.len()).sum();
+            (per_deal_pph(&deals), played)
+        };
+        let (full, full_played) = run(PermutationMode::Full);
+        let (adaptive, adaptive_played) = run(PermutationMode::Adaptive { spread: 1.0 });
+        assert_eq!(full_played, 600 * 24);
+        assert!(
+            adaptive_played * 3 < full_played * 2,
+            "adaptive played {adaptive_played}"
+        );
+        for ((agent, f), (other, a)) in full.iter().zip(&adaptive) {
+            assert_eq!(agent, other);
+            assert!((f - a).abs() < 0.25, "{agent}: full {f:.3} adaptive {a:.3}");
+        }
+        // The ordering of agents survives the shortcut.
+        let rank = |pph: &[(AgentId, f64)]| {
+            let mut sorted = pph.to_vec();
+            sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+            sorted
+                .into_iter()
+                .map(|(agent, _)| agent.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rank(&full), rank(&adaptive));
+    }
+
+    #[test]
+    fn a_steady_one_point_edge_is_significant_and_reproducible() {
+        let mut rng = StdRng::seed_from_u64(2267);
+        let deals: Vec<DealPermutations> = (0..80u64)
+            .map(|seed| {
+                let base = rng.gen_range(2..20);
+                let noise = rng.gen_range(0..2);
+                DealPermutations {
+                    seed,
+                    played: vec![
+                        (
+                            [N, H, N, H],
+                            [base + noise, base - 1, base, base - 1 + noise]
+                        );
+                        4
+                    ],
+                    available: 6,
+                }
+            })
+            .collect();
+        let rows = paired_comparisons(&deals, 1000, 7);
+        assert_eq!(rows.len(), 1);
+        let row = &rows[0];
+        assert_eq!(
+            (&row.left, &row.right, row.deals),
+            (&AgentId::of(N), &AgentId::of(H), 80)
+        );
+        assert!((row.mean_delta + 1.0).abs() < 1e-9, "{row:?}");
+        assert!(row.ci_high < 0.0 && row.p_value < 0.01, "{row:?}");
+        assert!(row.significant);
+        assert_eq!(paired_comparisons(&deals, 1000, 7), rows);
+        assert!(paired_markdown(&rows).contains("| normal | hard | 80 | -1.000 |"));
+
+        // Same agents, an edge that changes sign from deal to deal.
+        let level: Vec<DealPermutations> = (0..80u64)
+            .map(|seed| {
+                let edge = if seed % 2 == 0 { 1 } else { -1 };
+                DealPermutations {
+                    seed,
+                    played: vec![([N, H, N, H], [6, 6 + edge, 6, 6 + edge]); 4],
+                    available: 6,
+                }
+            })
+            .collect();
+        let row = &paired_comparisons(&level, 1000, 7)[0];
+        assert_eq!(row.mean_delta, 0.0);
+        assert!(
+            !row.significant && row.ci_low < 0.0 && row.ci_high > 0.0,
+            "{row:?}"
+        );
+    }
+}
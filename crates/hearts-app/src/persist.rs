@@ -0,0 +1,299 @@
+//! Versioned JSON files that survive format changes.
+//!
+//! Every persisted document carries a top-level `schema_version`. Loading
+//! reads it (documents written before the field existed count as version 1),
+//! runs the type's registered migrations one version at a time, keeps the
+//! original as `<file>.v<N>.bak` and rewrites the file at the current
+//! version. A file that is not valid JSON, or does not match the type once
+//! migrated, is renamed to `<file>.bad` so the next start sees a clean slate
+//! instead of failing on the same file again. Files from a newer build are
+//! left alone.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const SCHEMA_KEY: &str = "schema_version";
+
+/// One step from version `from` to `from + 1`, editing the document's
+/// top-level object in place. `schema_version` is updated by the loader.
+#[derive(Clone, Copy)]
+pub struct Migration {
+    pub from: u32,
+    pub apply: fn(&mut Map<String, Value>) -> Result<(), String>,
+}
+
+/// A type stored with [`save`] and read with [`load_with_migrations`].
+pub trait Persisted: Serialize + DeserializeOwned {
+    const SCHEMA_VERSION: u32;
+
+    /// Steps from every earlier version, in any order.
+    fn migrations() -> &'static [Migration];
+}
+
+#[derive(Debug)]
+pub enum PersistError {
+    Io(io::Error),
+    /// The file could not be read as the type and was moved aside.
+    Corrupt {
+        path: PathBuf,
+        quarantined: PathBuf,
+        reason: String,
+    },
+    /// Written by a newer build; left untouched.
+    TooNew {
+        path: PathBuf,
+        version: u32,
+        supported: u32,
+    },
+    /// No migration is registered from `from`; the file is left untouched.
+    NoMigration {
+        path: PathBuf,
+        from: u32,
+    },
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Io(err) => err.fmt(f),
+            PersistError::Corrupt {
+                path,
+                quarantined,
+                reason,
+            } => write!(
+                f,
+                "{} is unreadable ({reason}); moved to {}",
+                path.display(),
+                quarantined.display()
+            ),
+            PersistError::TooNew {
+                path,
+                version,
+                supported,
+            } => write!(
+                f,
+                "{} has schema_version {version}, newer than the supported {supported}",
+                path.display()
+            ),
+            PersistError::NoMigration { path, from } => write!(
+                f,
+                "{} has schema_version {from} and no migration from it",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for PersistError {
+    fn from(err: io::Error) -> Self {
+        PersistError::Io(err)
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// `golden.json` -> `golden.json.v1.bak`.
+pub fn backup_path(path: &Path, version: u32) -> PathBuf {
+    with_suffix(path, &format!(".v{version}.bak"))
+}
+
+/// `golden.json` -> `golden.json.bad`.
+pub fn quarantine_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bad")
+}
+
+fn quarantine(path: &Path, reason: String) -> PersistError {
+    let quarantined = quarantine_path(path);
+    match fs::rename(path, &quarantined) {
+        Ok(()) => PersistError::Corrupt {
+            path: path.to_path_buf(),
+            quarantined,
+            reason,
+        },
+        Err(err) => PersistError::Io(err),
+    }
+}
+
+/// Writes `value` as pretty JSON. Types with their own `schema_version`
+/// field are written as they are, in field order; others get the current
+/// version added.
+pub fn save<T: Persisted>(path: &Path, value: &T) -> Result<(), PersistError> {
+    let json = match serde_json::to_value(value).map_err(io::Error::other)? {
+        Value::Object(mut map) if !map.contains_key(SCHEMA_KEY) => {
+            map.insert(SCHEMA_KEY.into(), Value::from(T::SCHEMA_VERSION));
+            serde_json::to_string_pretty(&map)
+        }
+        _ => serde_json::to_string_pretty(value),
+    }
+    .map_err(io::Error::other)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Reads `path` as `T`, migrating and rewriting older versions first. See
+/// the module docs for backups and quarantine.
+pub fn load_with_migrations<T: Persisted>(path: &Path) -> Result<T, PersistError> {
+    let raw = fs::read_to_string(path)?;
+    let mut doc: Value = match serde_json::from_str(&raw) {
+        Ok(doc) => doc,
+        Err(err) => return Err(quarantine(path, err.to_string())),
+    };
+    let Value::Object(map) = &mut doc else {
+        return Err(quarantine(path, "not a JSON object".to_string()));
+    };
+    let found = match map.get(SCHEMA_KEY) {
+        None => 1,
+        Some(value) => match value.as_u64().and_then(|v| u32::try_from(v).ok()) {
+            Some(version) => version,
+            None => return Err(quarantine(path, format!("{SCHEMA_KEY} is {value}"))),
+        },
+    };
+    if found > T::SCHEMA_VERSION {
+        return Err(PersistError::TooNew {
+            path: path.to_path_buf(),
+            version: found,
+            supported: T::SCHEMA_VERSION,
+        });
+    }
+    for version in found..T::SCHEMA_VERSION {
+        let step = T::migrations()
+            .iter()
+            .find(|step| step.from == version)
+            .ok_or_else(|| PersistError::NoMigration {
+                path: path.to_path_buf(),
+                from: version,
+            })?;
+        if let Err(reason) = (step.apply)(map) {
+            return Err(quarantine(path, format!("migrating v{version}: {reason}")));
+        }
+        map.insert(SCHEMA_KEY.into(), Value::from(version + 1));
+    }
+    let value: T = match serde_json::from_value(doc) {
+        Ok(value) => value,
+        Err(err) => return Err(quarantine(path, err.to_string())),
+    };
+    if found < T::SCHEMA_VERSION {
+        fs::write(backup_path(path, found), &raw)?;
+        save(path, &value)?;
+    }
+    Ok(value)
+}
+
+/// Moves `from` to `to`; a no-op when `from` is absent.
+pub fn rename_key(map: &mut Map<String, Value>, from: &str, to: &str) {
+    if let Some(value) = map.remove(from) {
+        map.insert(to.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Migration, PersistError, Persisted, backup_path, load_with_migrations, quarantine_path,
+        rename_key, save,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Value};
+
+    /// v1 `{"name"}`, v2 renamed it to `label`, v3 added `volume`.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Profile {
+        schema_version: u32,
+        label: String,
+        volume: u8,
+    }
+
+    fn v1_to_v2(map: &mut Map<String, Value>) -> Result<(), String> {
+        rename_key(map, "name", "label");
+        Ok(())
+    }
+
+    fn v2_to_v3(map: &mut Map<String, Value>) -> Result<(), String> {
+        map.insert("volume".into(), Value::from(80));
+        Ok(())
+    }
+
+    impl Persisted for Profile {
+        const SCHEMA_VERSION: u32 = 3;
+
+        fn migrations() -> &'static [Migration] {
+            &[
+                Migration {
+                    from: 2,
+                    apply: v2_to_v3,
+                },
+                Migration {
+                    from: 1,
+                    apply: v1_to_v2,
+                },
+            ]
+        }
+    }
+
+    fn temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("mdhearts_persist_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("profile.json");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn older_files_migrate_in_order_and_keep_a_backup() {
+        let expected = Profile {
+            schema_version: 3,
+            label: "ana".to_string(),
+            volume: 80,
+        };
+        for (version, contents) in [
+            (1, r#"{"name":"ana"}"#),
+            (2, r#"{"schema_version":2,"label":"ana"}"#),
+        ] {
+            let path = temp_file(&format!("v{version}"), contents);
+            assert_eq!(load_with_migrations::<Profile>(&path).unwrap(), expected);
+            let backup = std::fs::read_to_string(backup_path(&path, version)).unwrap();
+            assert_eq!(backup, contents);
+            // The rewrite is current, so a second load changes nothing.
+            assert_eq!(load_with_migrations::<Profile>(&path).unwrap(), expected);
+        }
+        let path = temp_file("current", "{}");
+        save(&path, &expected).unwrap();
+        assert_eq!(load_with_migrations::<Profile>(&path).unwrap(), expected);
+        assert!(!backup_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn corrupt_files_are_quarantined_and_newer_ones_left_alone() {
+        for (name, contents) in [
+            ("truncated", r#"{"schema_version":3,"lab"#),
+            (
+                "wrong_shape",
+                r#"{"schema_version":3,"label":7,"volume":1}"#,
+            ),
+        ] {
+            let path = temp_file(name, contents);
+            let err = load_with_migrations::<Profile>(&path).unwrap_err();
+            assert!(matches!(err, PersistError::Corrupt { .. }), "{name}: {err}");
+            assert!(!path.exists());
+            assert_eq!(
+                std::fs::read_to_string(quarantine_path(&path)).unwrap(),
+                contents
+            );
+        }
+        let newer = r#"{"schema_version":9,"label":"ana","volume":1}"#;
+        let path = temp_file("newer", newer);
+        let err = load_with_migrations::<Profile>(&path).unwrap_err();
+        assert!(matches!(err, PersistError::TooNew { version: 9, .. }));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), newer);
+    }
+}
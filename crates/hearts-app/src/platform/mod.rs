@@ -1,3 +1,4 @@
+pub mod startup;
 #[cfg(windows)]
 pub mod win32;
 #[cfg(all(windows, feature = "winui-host"))]
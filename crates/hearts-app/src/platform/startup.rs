@@ -0,0 +1,186 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! Checks the GUI runs before it creates a window.
+//!
+//! On a stripped-down Windows install, Direct2D, DirectWrite or the imaging
+//! component can be missing. The window then fails before the panic hook can
+//! say why, and the game just exits. `win32::run` probes each component first
+//! and reports the first one that fails in a plain message box. The message
+//! names the component and points at the command-line modes, which need none
+//! of them.
+//!
+//! The probes take their loaders as arguments, so the error paths can be
+//! exercised anywhere. Only the Windows calls plugged into them are
+//! platform-specific.
+
+use std::fmt;
+
+/// Libraries loaded at startup and the component each one provides, in the
+/// order they are probed.
+pub const REQUIRED_LIBRARIES: [(&str, &str); 4] = [
+    ("ole32.dll", "COM"),
+    ("d2d1.dll", "Direct2D"),
+    ("dwrite.dll", "DirectWrite"),
+    ("windowscodecs.dll", "Windows Imaging Component"),
+];
+
+/// A self-play run that needs nothing the window does.
+pub const CLI_FALLBACK: &str = "mdhearts --match-mixed south 1 10 nnnn";
+
+/// Why the GUI could not start. Codes are the HRESULTs the failing call
+/// returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupError {
+    /// `library` could not be loaded.
+    MissingLibrary {
+        library: &'static str,
+        component: &'static str,
+        code: i32,
+    },
+    /// COM could not be initialised on the UI thread.
+    Com { code: i32 },
+    /// The component's library loaded but its factory could not be created.
+    Factory { component: &'static str, code: i32 },
+}
+
+impl StartupError {
+    pub fn component(&self) -> &'static str {
+        match *self {
+            StartupError::MissingLibrary { component, .. } => component,
+            StartupError::Com { .. } => "COM",
+            StartupError::Factory { component, .. } => component,
+        }
+    }
+
+    /// Full message box text: what failed, and how to play without the
+    /// window.
+    pub fn message(&self) -> String {
+        format!(
+            "mdhearts could not start its window: {self}.\n\n\
+             The game window needs {}, which this Windows installation does not \
+             provide.\n\n\
+             The command-line modes do not need it. For example, this plays ten \
+             self-play hands:\n\n    {CLI_FALLBACK}",
+            self.component()
+        )
+    }
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // HRESULTs read as unsigned hex everywhere else, e.g. 0x8007007E.
+        let hex = |code: i32| format!("0x{:08X}", code as u32);
+        match *self {
+            StartupError::MissingLibrary {
+                library,
+                component,
+                code,
+            } => write!(
+                f,
+                "{component} ({library}) could not be loaded ({})",
+                hex(code)
+            ),
+            StartupError::Com { code } => {
+                write!(f, "COM could not be initialised ({})", hex(code))
+            }
+            StartupError::Factory { component, code } => {
+                write!(
+                    f,
+                    "the {component} factory could not be created ({})",
+                    hex(code)
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for StartupError {}
+
+/// Loads each of [`REQUIRED_LIBRARIES`] with `load`, stopping at the first
+/// that fails.
+pub fn probe_libraries(
+    mut load: impl FnMut(&'static str) -> Result<(), i32>,
+) -> Result<(), StartupError> {
+    for (library, component) in REQUIRED_LIBRARIES {
+        load(library).map_err(|code| StartupError::MissingLibrary {
+            library,
+            component,
+            code,
+        })?;
+    }
+    Ok(())
+}
+
+/// Runs `create` for `component`'s factory, reporting a failure as
+/// [`StartupError::Factory`].
+pub fn probe_factory<T>(
+    component: &'static str,
+    create: impl FnOnce() -> Result<T, i32>,
+) -> Result<T, StartupError> {
+    create().map_err(|code| StartupError::Factory { component, code })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// HRESULT_FROM_WIN32(ERROR_MOD_NOT_FOUND).
+    const MOD_NOT_FOUND: i32 = 0x8007_007E_u32 as i32;
+
+    #[test]
+    fn first_missing_library_is_reported() {
+        let mut tried = Vec::new();
+        let err = probe_libraries(|library| {
+            tried.push(library);
+            if library == "dwrite.dll" {
+                Err(MOD_NOT_FOUND)
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap_err();
+        assert_eq!(tried, ["ole32.dll", "d2d1.dll", "dwrite.dll"]);
+        assert_eq!(
+            err,
+            StartupError::MissingLibrary {
+                library: "dwrite.dll",
+                component: "DirectWrite",
+                code: MOD_NOT_FOUND,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "DirectWrite (dwrite.dll) could not be loaded (0x8007007E)"
+        );
+        assert!(probe_libraries(|_| Ok(())).is_ok());
+    }
+
+    #[test]
+    fn factory_failures_name_the_component() {
+        let err = probe_factory("Direct2D", || Err::<(), _>(0x8000_4005_u32 as i32)).unwrap_err();
+        assert_eq!(err.component(), "Direct2D");
+        assert_eq!(
+            err.to_string(),
+            "the Direct2D factory could not be created (0x80004005)"
+        );
+        assert_eq!(probe_factory("DirectWrite", || Ok(7)), Ok(7));
+        assert_eq!(
+            StartupError::Com { code: 1 }.to_string(),
+            "COM could not be initialised (0x00000001)"
+        );
+    }
+
+    #[test]
+    fn message_explains_the_failure_and_the_cli_fallback() {
+        let err = StartupError::MissingLibrary {
+            library: "d2d1.dll",
+            component: "Direct2D",
+            code: MOD_NOT_FOUND,
+        };
+        let message = err.message();
+        assert!(message.starts_with(
+            "mdhearts could not start its window: Direct2D (d2d1.dll) could not be loaded (0x8007007E)."
+        ));
+        assert!(message.contains("needs Direct2D"));
+        assert!(message.ends_with(CLI_FALLBACK));
+    }
+}
@@ -11,17 +11,38 @@ use std::thread;
 use std::time::{Duration, Instant};
 use windows_numerics::{Matrix3x2, Vector2};
 
+use crate::assist::{AssistLevel, Mastery, MasterySummary};
 use crate::bot::{DecisionLimit, PlayPlanner, PlayPlannerHard};
+use crate::card_style::{CardStyle, CardStyleOption, Emphasis, Rgba};
 use crate::controller::{
-    BotThinkRequest, BotThinkResult, GameController, ThinkConfig, TimeoutFallback,
+    BotThinkRequest, BotThinkResult, CollectStage, GameController, PacingConfig, ReplayController,
+    ThinkConfig, TimeoutFallback,
 };
-use crate::debug::debug_enabled;
+use crate::debug::{EvalBar, debug_enabled, debug_overlay_enabled};
+use crate::identity::{
+    Avatar, PlayerIdentity, PlayerRoster, load_players, players_path, save_players,
+};
+use crate::input::{Action, Gesture, HandFan, InputEvent, InputMapper, InputPhase, Target};
+use crate::platform::startup::{StartupError, probe_factory, probe_libraries};
+use crate::recording::{HumanRecorder, PRIVACY_NOTE, delete_recording, recording_path};
+use crate::review::RoundReview;
+use crate::saved_game::{load_game, save_game, saved_game_path};
+use crate::scoreboard::{ScoreDisplay, ScoreboardView};
+use crate::session::{GameSession, SessionCommand, SessionEvent};
+use crate::tasks::{CancelToken, TaskEvent, TaskRunner};
+use crate::transcript::{load_replay, save_transcript, transcript_path};
 use hearts_core::model::card::Card as ModelCard;
+use hearts_core::model::passing::PassCards;
 use hearts_core::model::player::PlayerPosition;
 use hearts_core::model::rank::Rank;
+use hearts_core::model::rules::{DEFAULT_TARGET_SCORE, RuleSet};
+use hearts_core::model::rules_text::RulesText;
+use hearts_core::model::score::ScoreBoard;
 use hearts_core::model::suit::Suit;
 
-use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, RECT, WPARAM};
+use windows::Win32::Foundation::{
+    FreeLibrary, HINSTANCE, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM,
+};
 use windows::Win32::Graphics::Direct2D::Common::D2D1_ALPHA_MODE_PREMULTIPLIED;
 use windows::Win32::Graphics::Direct2D::Common::{
     D2D_RECT_F, D2D_SIZE_U, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
@@ -34,7 +55,7 @@ use windows::Win32::Graphics::Direct2D::{
     D2D1_FACTORY_TYPE_MULTI_THREADED, D2D1_HWND_RENDER_TARGET_PROPERTIES, D2D1_LAYER_OPTIONS_NONE,
     D2D1_LAYER_PARAMETERS, D2D1_PRESENT_OPTIONS_NONE, D2D1_RENDER_TARGET_PROPERTIES,
     D2D1_ROUNDED_RECT, D2D1CreateFactory, ID2D1Bitmap, ID2D1Factory, ID2D1Geometry,
-    ID2D1HwndRenderTarget,
+    ID2D1HwndRenderTarget, ID2D1SolidColorBrush,
 };
 use windows::Win32::Graphics::DirectWrite::{
     DWRITE_FACTORY_TYPE_SHARED, DWRITE_FONT_STRETCH_NORMAL, DWRITE_FONT_STYLE_NORMAL,
@@ -46,7 +67,10 @@ use windows::Win32::Graphics::DirectWrite::{
     IDWriteTextLayout,
 };
 use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
-use windows::Win32::Graphics::Gdi::{BeginPaint, EndPaint, HBRUSH, InvalidateRect, PAINTSTRUCT};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint, COLOR_BTNFACE, DEFAULT_GUI_FONT, EndPaint, GetStockObject, GetSysColorBrush,
+    HBRUSH, InvalidateRect, PAINTSTRUCT, ScreenToClient,
+};
 use windows::Win32::Graphics::Imaging::{
     CLSID_WICImagingFactory, GUID_WICPixelFormat32bppPBGRA, IWICBitmapFrameDecode,
     IWICFormatConverter, IWICImagingFactory, IWICStream, WICBitmapDitherTypeNone,
@@ -57,32 +81,39 @@ use windows::Win32::System::Com::{
     CoInitializeEx,
 };
 use windows::Win32::System::Diagnostics::Debug::OutputDebugStringW;
-use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::LibraryLoader::{
+    GetModuleHandleW, LOAD_LIBRARY_SEARCH_SYSTEM32, LoadLibraryExW,
+};
 use windows::Win32::System::Registry::{
     HKEY, HKEY_CURRENT_USER, KEY_QUERY_VALUE, KEY_SET_VALUE, REG_BINARY, REG_OPTION_NON_VOLATILE,
     RRF_RT_REG_BINARY, RegCloseKey, RegCreateKeyExW, RegGetValueW, RegSetValueExW,
 };
 use windows::Win32::UI::Controls::SetScrollInfo;
 use windows::Win32::UI::HiDpi::{GetDpiForSystem, GetDpiForWindow};
+use windows::Win32::UI::Input::KeyboardAndMouse::SetFocus;
 use windows::Win32::UI::WindowsAndMessaging::{
-    ACCEL, AdjustWindowRectEx, AppendMenuW, CREATESTRUCTW, CS_DBLCLKS, CS_HREDRAW, CS_VREDRAW,
-    CW_USEDEFAULT, CheckMenuRadioItem, CreateAcceleratorTableW, CreateMenu, CreatePopupMenu,
-    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, DrawMenuBar, FCONTROL,
-    FVIRTKEY, GWLP_USERDATA, GetClientRect, GetMenu, GetMessageW, GetScrollInfo, GetSubMenu,
-    GetSystemMetrics, GetWindowLongPtrW, GetWindowPlacement, GetWindowRect, HACCEL, HCURSOR, HMENU,
-    IDC_APPSTARTING, IDC_ARROW, IDI_APPLICATION, IsWindow, LoadCursorW, LoadIconW,
-    MB_ICONINFORMATION, MB_OK, MF_BYCOMMAND, MF_POPUP, MF_SEPARATOR, MF_STRING, MSG, MessageBoxW,
-    PM_REMOVE, PeekMessageW, PostQuitMessage, RegisterClassExW, SB_BOTTOM, SB_LINEDOWN, SB_LINEUP,
-    SB_PAGEDOWN, SB_PAGEUP, SB_THUMBPOSITION, SB_THUMBTRACK, SB_TOP, SB_VERT, SCROLLBAR_COMMAND,
-    SCROLLINFO, SIF_ALL, SIF_PAGE, SIF_POS, SIF_RANGE, SM_CXSCREEN, SM_CYSCREEN, SPI_GETWORKAREA,
-    SW_SHOW, SW_SHOWMINIMIZED, SW_SHOWNORMAL, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER,
-    SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, SetCursor, SetForegroundWindow, SetMenu,
-    SetWindowLongPtrW, SetWindowPlacement, SetWindowPos, SetWindowTextW, ShowWindow,
-    SystemParametersInfoW, TranslateAcceleratorW, TranslateMessage, WINDOW_EX_STYLE,
-    WINDOWPLACEMENT, WM_CLOSE, WM_COMMAND, WM_DESTROY, WM_DPICHANGED, WM_ERASEBKGND, WM_KEYDOWN,
-    WM_LBUTTONDBLCLK, WM_LBUTTONDOWN, WM_MOUSEWHEEL, WM_NCCREATE, WM_NCDESTROY, WM_PAINT, WM_QUIT,
-    WM_SETCURSOR, WM_SIZE, WM_TIMER, WM_VSCROLL, WNDCLASSEXW, WS_MAXIMIZEBOX, WS_MINIMIZEBOX,
-    WS_OVERLAPPEDWINDOW, WS_VSCROLL, WaitMessage,
+    ACCEL, AdjustWindowRectEx, AppendMenuW, BS_DEFPUSHBUTTON, BS_PUSHBUTTON, CREATESTRUCTW,
+    CS_DBLCLKS, CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, CheckMenuItem, CheckMenuRadioItem,
+    CreateAcceleratorTableW, CreateMenu, CreatePopupMenu, CreateWindowExW, DefWindowProcW,
+    DestroyWindow, DispatchMessageW, DrawMenuBar, ES_AUTOHSCROLL, FCONTROL, FVIRTKEY,
+    GWLP_USERDATA, GetClientRect, GetMenu, GetMessageW, GetScrollInfo, GetSubMenu,
+    GetSystemMetrics, GetWindowLongPtrW, GetWindowPlacement, GetWindowRect, GetWindowTextW, HACCEL,
+    HCURSOR, HMENU, IDC_APPSTARTING, IDC_ARROW, IDI_APPLICATION, IDYES, IsDialogMessageW, IsWindow,
+    LoadCursorW, LoadIconW, MB_ICONERROR, MB_ICONINFORMATION, MB_ICONQUESTION, MB_OK, MB_YESNO,
+    MF_BYCOMMAND, MF_CHECKED, MF_POPUP, MF_SEPARATOR, MF_STRING, MF_UNCHECKED, MSG, MessageBoxW,
+    PM_REMOVE, PeekMessageW, PostMessageW, PostQuitMessage, RegisterClassExW, SB_BOTTOM,
+    SB_LINEDOWN, SB_LINEUP, SB_PAGEDOWN, SB_PAGEUP, SB_THUMBPOSITION, SB_THUMBTRACK, SB_TOP,
+    SB_VERT, SCROLLBAR_COMMAND, SCROLLINFO, SIF_ALL, SIF_PAGE, SIF_POS, SIF_RANGE, SM_CXSCREEN,
+    SM_CYSCREEN, SPI_GETWORKAREA, SW_SHOW, SW_SHOWMINIMIZED, SW_SHOWNORMAL, SWP_NOACTIVATE,
+    SWP_NOSIZE, SWP_NOZORDER, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, SendMessageW, SetCursor,
+    SetForegroundWindow, SetMenu, SetWindowLongPtrW, SetWindowPlacement, SetWindowPos,
+    SetWindowTextW, ShowWindow, SystemParametersInfoW, TranslateAcceleratorW, TranslateMessage,
+    WINDOW_EX_STYLE, WINDOW_STYLE, WINDOWPLACEMENT, WM_APP, WM_CLOSE, WM_COMMAND, WM_CREATE,
+    WM_DESTROY, WM_DPICHANGED, WM_ERASEBKGND, WM_KEYDOWN, WM_LBUTTONDBLCLK, WM_LBUTTONDOWN,
+    WM_MBUTTONDOWN, WM_MOUSEWHEEL, WM_NCCREATE, WM_NCDESTROY, WM_PAINT, WM_QUIT, WM_RBUTTONDOWN,
+    WM_SETCURSOR, WM_SETFONT, WM_SIZE, WM_TIMER, WM_VSCROLL, WNDCLASSEXW, WS_BORDER, WS_CAPTION,
+    WS_CHILD, WS_MAXIMIZEBOX, WS_MINIMIZEBOX, WS_OVERLAPPED, WS_OVERLAPPEDWINDOW, WS_SYSMENU,
+    WS_TABSTOP, WS_VISIBLE, WS_VSCROLL, WaitMessage,
 };
 use windows::core::{Interface, PCWSTR, Result, w};
 
@@ -92,12 +123,20 @@ const E_INVALID_DATA_HR: i32 = 0x8007_000D_u32 as i32;
 const VK_F2: u32 = 0x71;
 const VK_RETURN: u32 = 0x0D;
 const VK_ESCAPE: u32 = 0x1B;
+const VK_PRIOR: u32 = 0x21;
+const VK_NEXT: u32 = 0x22;
+const VK_LEFT: u32 = 0x25;
 const VK_UP: u32 = 0x26;
+const VK_RIGHT: u32 = 0x27;
 const VK_DOWN: u32 = 0x28;
+const WHEEL_DELTA: i32 = 120;
+// Posted by a worker when a background task has a result to poll.
+const WM_APP_TASK_DONE: u32 = WM_APP + 1;
 
 const ID_GAME_NEW: u32 = 1001;
 const ID_GAME_RESTART: u32 = 1002;
 const ID_GAME_EXIT: u32 = 1003;
+const ID_GAME_REPLAY: u32 = 1004;
 const ID_OPTIONS_CARD_BACK: u32 = 1201;
 const ID_OPTIONS_DIFFICULTY_EASY: u32 = 1210;
 const ID_OPTIONS_DIFFICULTY_NORMAL: u32 = 1211;
@@ -107,6 +146,20 @@ const ID_OPTIONS_THINK_LIMIT_5: u32 = 1220;
 const ID_OPTIONS_THINK_LIMIT_10: u32 = 1221;
 const ID_OPTIONS_THINK_LIMIT_15: u32 = 1222;
 const ID_OPTIONS_THINK_LIMIT_NONE: u32 = 1223;
+const ID_OPTIONS_FAST_MODE: u32 = 1230;
+const ID_OPTIONS_SCORE_DETAILED: u32 = 1240;
+const ID_OPTIONS_ASSIST_COUNTING: u32 = 1241;
+const ID_OPTIONS_TARGET_50: u32 = 1250;
+const ID_OPTIONS_TARGET_75: u32 = 1251;
+const ID_OPTIONS_TARGET_100: u32 = 1252;
+const ID_OPTIONS_TARGET_150: u32 = 1253;
+const ID_OPTIONS_RECORD_PLAYS: u32 = 1260;
+const ID_OPTIONS_DELETE_RECORDING: u32 = 1261;
+const ID_OPTIONS_JACK_OF_DIAMONDS: u32 = 1262;
+const ID_OPTIONS_PLAYERS: u32 = 1270;
+const ID_OPTIONS_FOUR_COLOR: u32 = 1280;
+const ID_OPTIONS_SUIT_LETTERS: u32 = 1281;
+const ID_OPTIONS_HIGH_CONTRAST: u32 = 1282;
 const ID_HELP_ABOUT: u32 = 1301;
 const ID_HELP_RULES: u32 = 1302;
 const IDI_APPICON: u16 = 501;
@@ -118,6 +171,12 @@ const REG_VALUE_WINDOW_PLACEMENT: &str = "WindowPlacement";
 const REG_VALUE_CARD_BACK: &str = "CardBack";
 const REG_VALUE_BOT_DIFFICULTY: &str = "BotDifficulty";
 const REG_VALUE_THINK_LIMIT: &str = "ThinkLimitMs";
+const REG_VALUE_SCORE_DISPLAY: &str = "ScoreDisplay";
+const REG_VALUE_ASSIST_LEVEL: &str = "AssistLevel";
+const REG_VALUE_MATCH_TARGET: &str = "MatchTarget";
+const REG_VALUE_RECORD_PLAYS: &str = "RecordPlays";
+const REG_VALUE_JACK_OF_DIAMONDS: &str = "JackOfDiamonds";
+const REG_VALUE_CARD_STYLE: &str = "CardStyle";
 const MIN_WINDOW_WIDTH: i32 = 720;
 const MIN_WINDOW_HEIGHT: i32 = 540;
 
@@ -356,9 +415,64 @@ const fn make_int_resource(id: u16) -> PCWSTR {
     PCWSTR(id as usize as *const u16)
 }
 
-pub fn run() -> Result<()> {
+/// Initialises COM on this thread and checks every component the window
+/// draws with, so a missing one is reported by name instead of failing
+/// inside `AppState::new`.
+fn probe_startup() -> std::result::Result<(), StartupError> {
+    probe_libraries(|library| {
+        let name = string_to_wide_z(library);
+        let module =
+            unsafe { LoadLibraryExW(PCWSTR(name.as_ptr()), None, LOAD_LIBRARY_SEARCH_SYSTEM32) }
+                .map_err(|err| err.code().0)?;
+        unsafe {
+            let _ = FreeLibrary(module);
+        }
+        Ok(())
+    })?;
+    unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED | COINIT_DISABLE_OLE1DDE) }
+        .ok()
+        .map_err(|err| StartupError::Com { code: err.code().0 })?;
+    probe_factory("Direct2D", || unsafe {
+        D2D1CreateFactory::<ID2D1Factory>(
+            D2D1_FACTORY_TYPE_MULTI_THREADED,
+            Some(&D2D1_FACTORY_OPTIONS::default()),
+        )
+        .map_err(|err| err.code().0)
+    })?;
+    probe_factory("DirectWrite", || unsafe {
+        DWriteCreateFactory::<IDWriteFactory>(DWRITE_FACTORY_TYPE_SHARED)
+            .map_err(|err| err.code().0)
+    })?;
+    probe_factory("Windows Imaging Component", || unsafe {
+        CoCreateInstance::<_, IWICImagingFactory>(
+            &CLSID_WICImagingFactory,
+            None,
+            CLSCTX_INPROC_SERVER,
+        )
+        .map_err(|err| err.code().0)
+    })?;
+    Ok(())
+}
+
+fn show_startup_error(err: &StartupError) {
+    debug_out("mdhearts: startup failed: ", &err.to_string());
+    let body = string_to_wide_z(&err.message());
     unsafe {
-        CoInitializeEx(None, COINIT_APARTMENTTHREADED | COINIT_DISABLE_OLE1DDE).ok()?;
+        MessageBoxW(
+            None,
+            PCWSTR(body.as_ptr()),
+            w!("mdhearts cannot start"),
+            MB_OK | MB_ICONERROR,
+        );
+    }
+}
+
+pub fn run() -> Result<()> {
+    // A missing component is reported and the process exits normally; the
+    // message box is the whole error report.
+    if let Err(err) = probe_startup() {
+        show_startup_error(&err);
+        return Ok(());
     }
 
     let module = unsafe { GetModuleHandleW(None)? };
@@ -435,10 +549,18 @@ pub fn run() -> Result<()> {
         ..Default::default()
     });
     update_think_limit_menu(hwnd, limit_cfg);
+    update_fast_mode_menu(hwnd, PacingConfig::from_env());
+    update_score_display_menu(hwnd, load_score_display().unwrap_or_default());
+    update_assist_menu(hwnd, load_assist_level().unwrap_or_default());
+    update_match_target_menu(hwnd, load_match_target().unwrap_or(DEFAULT_TARGET_SCORE));
+    update_record_plays_menu(hwnd, load_record_plays());
+    update_jack_of_diamonds_menu(hwnd, load_jack_of_diamonds());
+    update_card_style_menu(hwnd, load_card_style().unwrap_or_default());
     restore_window_placement(hwnd);
     unsafe {
         let _ = ShowWindow(hwnd, windows::Win32::UI::WindowsAndMessaging::SW_SHOW);
     }
+    offer_resume(hwnd);
 
     let mut msg = MSG::default();
     unsafe {
@@ -459,7 +581,7 @@ struct AppState {
     dwrite: IDWriteFactory,
     text_format: IDWriteTextFormat,
     render_target: Option<ID2D1HwndRenderTarget>,
-    controller: GameController,
+    session: GameSession,
     passing_select: Vec<ModelCard>,
     wic: IWICImagingFactory,
     cards_bitmap: Option<ID2D1Bitmap>,
@@ -476,6 +598,29 @@ struct AppState {
     dpi: DpiScale,
     thinking: Option<ThinkingState>,
     timeout_banner: Option<TimeoutBanner>,
+    review_open: bool, // round review box is up; suppress re-showing on later ticks
+    briefing_dismissed: Option<(u64, u32)>, // (seed, round) whose briefing the user hid
+    refusal: Option<String>, // why the last card clicked could not be played, until the next input
+    auto_finish: Option<((u64, u32), bool)>, // (seed, round) offered auto-finish, and the answer
+    eval_bar: Option<EvalBar>, // MDH_DEBUG_OVERLAY only
+    score_display: ScoreDisplay,
+    assist_level: AssistLevel,
+    // Game → Card Display: suit colors, suit letters and high contrast.
+    card_style: CardStyle,
+    // Target score for new matches, from Game → Match Target.
+    target_score: u32,
+    // Game → Record My Plays; every new controller gets a recorder.
+    record_plays: bool,
+    // Game → Omnibus (Jack of Diamonds -10) for new matches.
+    jack_of_diamonds: bool,
+    // Game → Players: names and avatars for new matches.
+    players: PlayerRoster,
+    // Long computations such as the review's alternatives; Esc cancels.
+    tasks: TaskRunner<String>,
+    // Game → Watch Replay; the live game waits underneath until Esc.
+    replay: Option<ReplayView>,
+    input: InputMapper,
+    hand_fan: HandFan, // mouse-wheel spacing of the South hand
     cursor_arrow: HCURSOR,
     cursor_wait: HCURSOR,
 }
@@ -534,8 +679,8 @@ struct CollectAnim {
     winner: PlayerPosition,
     cards: Vec<(PlayerPosition, ModelCard)>,
     start: std::time::Instant,
-    delay_ms: u64,
-    dur_ms: u64,
+    // ms after `start` when the player clicked to collect (manual collect only)
+    clicked_at_ms: Option<u64>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -558,9 +703,10 @@ struct PassAnim {
     phase: PassPhase,
     start: std::time::Instant,
     from_seat: PlayerPosition,
-    south_before: Vec<ModelCard>,
     // move our chosen 3 out (faces)
     out: Vec<PassSprite>,
+    // bot passes that skip South, face down and alongside ours
+    bot_out: Vec<PassSprite>,
     // then move 3 in to South (backs)
     inn: Vec<PassSprite>,
     // timings
@@ -577,6 +723,99 @@ struct ThinkingState {
     handle: Option<thread::JoinHandle<()>>,
     rx: mpsc::Receiver<BotThinkResult>,
     timed_out: bool,
+    // pacing: hold the answer until the bot has "thought" this long
+    min_think_ms: u64,
+    ready: Option<BotThinkResult>,
+}
+
+/// The last match's rounds, stepped through with every hand face up.
+struct ReplayView {
+    replay: ReplayController,
+    show_advice: bool,
+    // Advice for the play at the cursor, kept while `show_advice` is on.
+    advice: Vec<(ModelCard, i32)>,
+}
+
+impl ReplayView {
+    fn new(replay: ReplayController) -> Self {
+        Self {
+            replay,
+            show_advice: false,
+            advice: Vec::new(),
+        }
+    }
+
+    /// Steps, seeks or toggles advice for `key`. Returns false for keys the
+    /// viewer does not use.
+    fn handle_key(&mut self, key: u32, difficulty: crate::bot::BotDifficulty) -> bool {
+        match key {
+            VK_RIGHT => {
+                self.replay.step_forward();
+            }
+            VK_LEFT => {
+                self.replay.step_back();
+            }
+            VK_DOWN => {
+                self.replay.step_forward_trick();
+            }
+            VK_UP => {
+                self.replay.step_back_trick();
+            }
+            VK_NEXT => {
+                self.replay.next_round();
+            }
+            VK_PRIOR => {
+                self.replay.previous_round();
+            }
+            key if key == u32::from(b'A') => self.show_advice = !self.show_advice,
+            _ => return false,
+        }
+        self.refresh_advice(difficulty);
+        true
+    }
+
+    fn refresh_advice(&mut self, difficulty: crate::bot::BotDifficulty) {
+        self.advice = if self.show_advice {
+            let mut advice = self.replay.advice(difficulty);
+            advice.sort_by(|a, b| b.1.cmp(&a.1));
+            advice
+        } else {
+            Vec::new()
+        };
+    }
+
+    fn header_text(&self) -> String {
+        let replay = &self.replay;
+        let scores = replay.scores();
+        let totals = scores.standings();
+        format!(
+            "Replay: round {} of {}, card {} of {} | Scores N:{} E:{} S:{} W:{}",
+            replay.round_index() + 1,
+            replay.round_count(),
+            replay.cursor(),
+            replay.plays_in_round(),
+            totals[PlayerPosition::North.index()],
+            totals[PlayerPosition::East.index()],
+            totals[PlayerPosition::South.index()],
+            totals[PlayerPosition::West.index()]
+        )
+    }
+
+    /// The advice panel's text, or `None` while advice is hidden.
+    fn advice_text(&self) -> Option<String> {
+        if !self.show_advice {
+            return None;
+        }
+        let Some((seat, played)) = self.replay.next_play() else {
+            return Some("Round over: no decision to advise on.".to_string());
+        };
+        let mut text = format!("Advice for {seat:?}, who played {played}:");
+        for (card, score) in self.advice.iter().take(5) {
+            let mark = if *card == played { "  (played)" } else { "" };
+            text.push_str(&format!("\n{card}  {score:+}{mark}"));
+        }
+        Some(text)
+    }
 }
 
 struct TimeoutBanner {
@@ -612,6 +851,7 @@ fn spawn_bot_worker(
             difficulty,
             snapshot,
             config,
+            weights,
         } = request;
         let start = Instant::now();
         let deadline = if config.max_duration.is_zero() {
@@ -632,22 +872,19 @@ fn spawn_bot_worker(
             }
         }
         let mut controller_bias_delta: Option<i32> = None;
+        let mut fell_back = false;
         if choice.is_none() {
             let ctx = snapshot.bot_context(seat, difficulty);
-            choice = match difficulty {
+            choice = crate::bot::with_weight_set(weights, || match difficulty {
                 crate::bot::BotDifficulty::SearchLookahead => {
-                    let picked =
-                        PlayPlannerHard::choose_with_limit(&legal, &ctx, decision_limit.as_ref());
-                    controller_bias_delta = ctx.controller_bias_delta;
-                    picked
-                }
-                _ => {
-                    let picked =
-                        PlayPlanner::choose_with_limit(&legal, &ctx, decision_limit.as_ref());
-                    controller_bias_delta = ctx.controller_bias_delta;
-                    picked
+                    let anytime =
+                        PlayPlannerHard::choose_anytime(&legal, &ctx, decision_limit.as_ref());
+                    fell_back = anytime.fell_back;
+                    anytime.card
                 }
-            };
+                _ => PlayPlanner::choose_with_limit(&legal, &ctx, decision_limit.as_ref()),
+            });
+            controller_bias_delta = ctx.controller_bias_delta;
         }
         if choice.is_none() {
             fallback_label = Some("first_legal");
@@ -659,7 +896,9 @@ fn spawn_bot_worker(
             .map(|limit| limit.expired())
             .unwrap_or(false)
             || (!config.max_duration.is_zero() && elapsed >= config.max_duration);
-        if timed_out {
+        if fell_back {
+            fallback_label = Some("heuristic_anytime");
+        } else if timed_out {
             fallback_label = Some(config.fallback.label());
         }
         if timed_out && fallback_label.is_none() {
@@ -688,6 +927,7 @@ fn spawn_bot_worker(
                 timed_out,
                 fallback: fallback_label,
                 search_stats,
+                deep_search: crate::bot::search_deep::take_last_stats().map(Into::into),
                 controller_bias_delta,
             },
         );
@@ -696,20 +936,12 @@ fn spawn_bot_worker(
             chosen: choice,
             elapsed,
             timed_out,
+            fell_back,
         };
         let _ = tx.send(result);
     })
 }
 
-fn seat_name(seat: PlayerPosition) -> &'static str {
-    match seat {
-        PlayerPosition::North => "North",
-        PlayerPosition::East => "East",
-        PlayerPosition::South => "South",
-        PlayerPosition::West => "West",
-    }
-}
-
 fn fallback_description(fallback: Option<TimeoutFallback>) -> &'static str {
     match fallback {
         Some(TimeoutFallback::HeuristicBest) => "Using heuristic fallback",
@@ -743,7 +975,8 @@ impl AppState {
             format.SetParagraphAlignment(DWRITE_PARAGRAPH_ALIGNMENT_CENTER)?;
             format
         };
-        let controller = GameController::new_with_seed(None, PlayerPosition::North);
+        let controller =
+            GameController::new_with_seed(crate::cli::startup_seed(), PlayerPosition::North);
         let cursor_arrow = unsafe { LoadCursorW(None, IDC_ARROW).unwrap_or_default() };
         let cursor_wait = unsafe { LoadCursorW(None, IDC_APPSTARTING).unwrap_or(cursor_arrow) };
         let wic: IWICImagingFactory =
@@ -763,7 +996,7 @@ impl AppState {
             dwrite,
             text_format,
             render_target: None,
-            controller,
+            session: GameSession::new(controller),
             passing_select: Vec::new(),
             wic,
             cards_bitmap: None,
@@ -780,16 +1013,45 @@ impl AppState {
             dpi: DpiScale::uniform(initial_dpi),
             thinking: None,
             timeout_banner: None,
+            review_open: false,
+            briefing_dismissed: None,
+            refusal: None,
+            auto_finish: None,
+            eval_bar: debug_overlay_enabled().then(EvalBar::new),
+            score_display: load_score_display().unwrap_or_default(),
+            assist_level: load_assist_level().unwrap_or_default(),
+            card_style: load_card_style().unwrap_or_default(),
+            target_score: load_match_target().unwrap_or(DEFAULT_TARGET_SCORE),
+            record_plays: load_record_plays(),
+            jack_of_diamonds: load_jack_of_diamonds(),
+            players: load_roster(),
+            tasks: TaskRunner::new(),
+            replay: None,
+            input: InputMapper::default(),
+            hand_fan: HandFan::default(),
             cursor_arrow,
             cursor_wait,
         };
+        let _ = this
+            .session
+            .controller_mut()
+            .set_target_score(this.target_score);
+        this.session
+            .controller_mut()
+            .set_jack_of_diamonds(this.jack_of_diamonds);
+        this.session
+            .controller_mut()
+            .set_players(this.players.clone());
+        this.session
+            .controller_mut()
+            .set_human_recorder(human_recorder(this.record_plays));
         match load_bot_difficulty() {
             Some(saved) => {
-                this.controller.set_bot_difficulty(saved);
+                this.session.set_bot_difficulty(saved);
             }
             None => {
                 let def = crate::bot::BotDifficulty::EasyLegacy;
-                this.controller.set_bot_difficulty(def);
+                this.session.set_bot_difficulty(def);
                 save_bot_difficulty(def);
             }
         }
@@ -797,9 +1059,9 @@ impl AppState {
             this.card_back = saved;
         }
         if let Some(limit) = load_think_limit() {
-            let mut config = this.controller.think_config();
+            let mut config = this.session.controller().think_config();
             config.max_duration = limit;
-            this.controller.set_think_config(config);
+            this.session.controller_mut().set_think_config(config);
         }
         this.apply_text_dpi();
         Ok(this)
@@ -850,7 +1112,7 @@ impl AppState {
     }
 
     fn start_thinking_if_needed(&mut self, hwnd: HWND) {
-        if self.controller.match_over() {
+        if self.session.controller().match_over() {
             return;
         }
         if self.thinking.is_some()
@@ -858,15 +1120,15 @@ impl AppState {
             || self.collect.is_some()
             || self.pass.is_some()
             || self.await_pass_ack.is_some()
-            || self.controller.in_passing_phase()
+            || self.session.controller().in_passing_phase()
         {
             return;
         }
-        let turn = self.controller.expected_to_play();
+        let turn = self.session.controller().expected_to_play();
         if turn == PlayerPosition::South {
             return;
         }
-        let Some(request) = self.controller.prepare_bot_think(turn) else {
+        let Some(request) = self.session.controller().prepare_bot_think(turn) else {
             return;
         };
         let (tx, rx) = mpsc::channel();
@@ -878,6 +1140,11 @@ impl AppState {
             Some(start + request.config.max_duration)
         };
         let limit_duration = request.config.max_duration;
+        let min_think_ms = self
+            .session
+            .controller()
+            .pacing()
+            .min_bot_think_ms(request.legal.len());
         let worker_cancel = Arc::clone(&cancel);
         let handle = spawn_bot_worker(request, worker_cancel, tx);
         let limit_desc = if limit_duration.is_zero() {
@@ -897,20 +1164,45 @@ impl AppState {
             handle: Some(handle),
             rx,
             timed_out: false,
+            min_think_ms,
+            ready: None,
         });
         unsafe {
             let _ = InvalidateRect(Some(hwnd), None, true);
         }
     }
 
+    /// Queues a fresh eval-bar evaluation when the overlay is on; returns true
+    /// if a finished one is waiting to be drawn.
+    fn refresh_eval_bar(&self) -> bool {
+        let Some(bar) = &self.eval_bar else {
+            return false;
+        };
+        let mut scores = ScoreBoard::new();
+        scores.set_totals(self.session.controller().standings());
+        bar.refresh(self.session.controller().round(), &scores);
+        bar.take_fresh()
+    }
+
     fn poll_thinking(&mut self, hwnd: HWND) {
         let mut deliver: Option<BotThinkResult> = None;
         if let Some(mut thinking) = self.thinking.take() {
-            if let Ok(result) = thinking.rx.try_recv() {
+            if thinking.ready.is_none()
+                && let Ok(result) = thinking.rx.try_recv()
+            {
                 if let Some(handle) = thinking.handle.take() {
                     let _ = handle.join();
                 }
-                deliver = Some(result);
+                thinking.ready = Some(result);
+            }
+            if let Some(result) = thinking.ready.take() {
+                let shown_ms = thinking.started_at.elapsed().as_millis() as u64;
+                if shown_ms >= thinking.min_think_ms {
+                    deliver = Some(result);
+                } else {
+                    thinking.ready = Some(result);
+                    self.thinking = Some(thinking);
+                }
             } else {
                 let now = Instant::now();
                 let timed_out = thinking
@@ -933,13 +1225,13 @@ impl AppState {
     }
 
     fn apply_bot_result(&mut self, hwnd: HWND, result: BotThinkResult) {
-        if result.seat != self.controller.expected_to_play() {
+        if result.seat != self.session.controller().expected_to_play() {
             debug_out(
                 "mdhearts: ",
                 &format!(
                     "Ignoring bot result for {:?}; expected {:?}",
                     result.seat,
-                    self.controller.expected_to_play()
+                    self.session.controller().expected_to_play()
                 ),
             );
             return;
@@ -953,21 +1245,25 @@ impl AppState {
                 result.timed_out
             ),
         );
+        if result.fell_back {
+            self.session.controller_mut().record_search_fallback();
+        }
         if let Some(card) = result.chosen {
-            match self.controller.apply_bot_move(result.seat, card) {
-                Ok((seat, played)) => {
+            let seat = result.seat;
+            match self.session.apply(SessionCommand::BotPlay { seat, card }) {
+                Ok(_) => {
                     if result.timed_out {
                         self.timeout_banner = Some(TimeoutBanner::new(
                             seat,
-                            Some(self.controller.think_config().fallback),
+                            Some(self.session.controller().think_config().fallback),
                         ));
                     }
-                    self.begin_bot_animation(hwnd, seat, played);
+                    self.begin_bot_animation(hwnd, seat, card);
                 }
                 Err(err) => {
                     debug_out(
                         "mdhearts: ",
-                        &format!("apply_bot_move error for {:?}: {:?}", result.seat, err),
+                        &format!("bot move error for {:?}: {}", seat, err),
                     );
                 }
             }
@@ -976,10 +1272,10 @@ impl AppState {
     }
 
     fn apply_timeout_fallback(&mut self, hwnd: HWND, seat: PlayerPosition) {
-        if self.controller.match_over() {
+        if self.session.controller().match_over() {
             return;
         }
-        let fallback_kind = self.controller.think_config().fallback;
+        let fallback_kind = self.session.controller().think_config().fallback;
         debug_out(
             "mdhearts: ",
             &format!("timeout fallback for {:?} {:?}", seat, fallback_kind),
@@ -993,7 +1289,7 @@ impl AppState {
                 None
             }
             _ => {
-                let chosen = self.controller.timeout_fallback_card(seat);
+                let chosen = self.session.controller().timeout_fallback_card(seat);
                 if chosen.is_none() {
                     debug_out(
                         "mdhearts: ",
@@ -1003,16 +1299,23 @@ impl AppState {
                 chosen
             }
         };
+        if card.is_some()
+            && fallback_kind == TimeoutFallback::HeuristicBest
+            && self.session.controller().bot_difficulty()
+                == crate::bot::BotDifficulty::SearchLookahead
+        {
+            self.session.controller_mut().record_search_fallback();
+        }
         if let Some(card) = card {
-            match self.controller.apply_bot_move(seat, card) {
-                Ok((seat, played)) => {
-                    self.begin_bot_animation(hwnd, seat, played);
+            match self.session.apply(SessionCommand::BotPlay { seat, card }) {
+                Ok(_) => {
+                    self.begin_bot_animation(hwnd, seat, card);
                     self.timeout_banner = Some(TimeoutBanner::new(seat, Some(fallback_kind)));
                 }
                 Err(err) => {
                     debug_out(
                         "mdhearts: ",
-                        &format!("Timeout fallback play error for {:?}: {:?}", seat, err),
+                        &format!("Timeout fallback play error for {:?}: {}", seat, err),
                     );
                     self.timeout_banner = Some(TimeoutBanner::new(seat, Some(fallback_kind)));
                 }
@@ -1029,7 +1332,7 @@ impl AppState {
     fn begin_bot_animation(&mut self, hwnd: HWND, seat: PlayerPosition, card: ModelCard) {
         let size = client_size(hwnd);
         let layout = self.layout_size(size);
-        let from = approx_from_rect_for_seat(layout, &self.controller, seat);
+        let from = approx_from_rect_for_seat(layout, self.session.controller(), seat);
         let to = compute_trick_rect_for(layout, seat);
         self.anim = Some(PlayAnim {
             seat,
@@ -1037,7 +1340,7 @@ impl AppState {
             from,
             to,
             start: Instant::now(),
-            dur_ms: 260,
+            dur_ms: self.session.controller().pacing().play_anim_ms,
         });
         unsafe {
             let _ = InvalidateRect(Some(hwnd), None, true);
@@ -1055,22 +1358,26 @@ impl AppState {
     }
 
     fn status_header_text(&mut self) -> String {
-        if self.controller.match_over()
-            && let Some(winner) = self.controller.match_winner()
+        if self.session.controller().match_over()
+            && let Some(winner) = self.session.controller().match_winner()
         {
-            let seat = seat_name(winner);
-            let scores = self.controller.standings();
-            return format!(
+            let seat = self.session.controller().seat_name(winner);
+            let scores = self.session.controller().standings();
+            let mut text = format!(
                 "{seat} wins the match! Final scores N:{} E:{} S:{} W:{} — choose Game → New to play again.",
                 scores[PlayerPosition::North.index()],
                 scores[PlayerPosition::East.index()],
                 scores[PlayerPosition::South.index()],
                 scores[PlayerPosition::West.index()]
             );
+            if let Some(challenge) = self.session.controller().challenge() {
+                text.push_str(&format!(" | {}", challenge.summary()));
+            }
+            return text;
         }
-        let base = self.controller.status_text();
+        let base = self.session.controller().status_text();
         if let Some(thinking) = self.thinking.as_ref() {
-            let seat = seat_name(thinking.seat);
+            let seat = self.session.controller().seat_name(thinking.seat);
             let elapsed = thinking.started_at.elapsed().as_secs();
             let mut text = if base.is_empty() {
                 format!("{seat} is thinking... {}s", elapsed)
@@ -1090,7 +1397,7 @@ impl AppState {
             if let Some(banner) = self.timeout_banner.as_mut()
                 && !banner.status_shown
             {
-                let seat = seat_name(banner.seat);
+                let seat = self.session.controller().seat_name(banner.seat);
                 let extra = fallback_description(banner.fallback);
                 if text.is_empty() {
                     text = format!("{seat} timeout: {extra}");
@@ -1104,17 +1411,20 @@ impl AppState {
     }
 
     fn default_hint_text(&self) -> Cow<'static, str> {
-        if self.controller.match_over() {
-            if let Some(winner) = self.controller.match_winner() {
+        if self.session.controller().match_over() {
+            if let Some(winner) = self.session.controller().match_winner() {
                 return Cow::Owned(format!(
                     "{} wins the match! Select Game → New to play again.",
-                    seat_name(winner)
+                    self.session.controller().seat_name(winner)
                 ));
             }
             return Cow::Borrowed("Match complete. Select Game → New to play again.");
         }
         if self.pass.is_some() {
-            Cow::Borrowed("Select three cards to pass, then press Enter or click the table.")
+            Cow::Owned(format!(
+                "Select {} cards to pass, then press Enter or click the table.",
+                self.session.controller().pass_count()
+            ))
         } else if let Some(list) = &self.await_pass_ack {
             if list.is_empty() {
                 Cow::Borrowed("")
@@ -1122,27 +1432,93 @@ impl AppState {
                 Cow::Borrowed("New cards received - click anywhere to add them to your hand.")
             }
         } else if let Some(coll) = self.collect.as_ref() {
-            let who = seat_name(coll.winner);
-            let tricks = self.controller.tricks_won_this_round()[coll.winner.index()];
+            let who = self.session.controller().seat_name(coll.winner);
+            let tricks = self.session.controller().tricks_won_this_round()[coll.winner.index()];
             Cow::Owned(format!("{who} wins (tricks won so far: {tricks})"))
-        } else if self.controller.in_passing_phase() {
+        } else if self.session.controller().in_passing_phase() {
             Cow::Owned(format!(
-                "Passing: select 3 cards ({} selected) and press Enter",
+                "Passing: select {} cards ({} selected) and press Enter",
+                self.session.controller().pass_count(),
                 self.passing_select.len()
             ))
+        } else if let Some(briefing) = self.briefing_text() {
+            Cow::Owned(format!("{briefing} (Esc to hide)"))
         } else {
-            let turn = self.controller.expected_to_play();
+            let turn = self.session.controller().expected_to_play();
             if turn == PlayerPosition::South {
                 Cow::Borrowed("Your turn: click a highlighted card")
             } else {
-                Cow::Owned(format!("Waiting for {}...", seat_name(turn)))
+                Cow::Owned(format!(
+                    "Waiting for {}...",
+                    self.session.controller().seat_name(turn)
+                ))
+            }
+        }
+    }
+
+    fn briefing_key(&self) -> (u64, u32) {
+        (
+            self.session.controller().seed(),
+            self.session.controller().round_number(),
+        )
+    }
+
+    /// Round-start briefing for the human until dismissed or the first trick ends.
+    fn briefing_text(&self) -> Option<String> {
+        if self.briefing_dismissed == Some(self.briefing_key()) {
+            return None;
+        }
+        self.session
+            .controller()
+            .round_briefing(PlayerPosition::South)
+    }
+
+    fn dismiss_briefing(&mut self) -> bool {
+        if self.briefing_text().is_none() {
+            return false;
+        }
+        self.briefing_dismissed = Some(self.briefing_key());
+        true
+    }
+
+    /// Advances a decided round: plays it out if the user accepted
+    /// auto-finish, otherwise lets the bots think as usual. Returns true when
+    /// the offer has not been made for this round yet.
+    fn advance_decided_round(&mut self, hwnd: HWND) -> bool {
+        let key = self.briefing_key();
+        match self.auto_finish {
+            Some((asked, true)) if asked == key => {
+                if let Ok(events) = self.session.apply(SessionCommand::FastForward)
+                    && let Some(&SessionEvent::CardPlayed { seat, card }) = events.first()
+                {
+                    self.begin_bot_animation(hwnd, seat, card);
+                }
+                false
+            }
+            Some((asked, false)) if asked == key => {
+                self.start_thinking_if_needed(hwnd);
+                false
+            }
+            _ => {
+                // Timers keep firing under the prompt, so mark it offered now.
+                self.auto_finish = Some((key, false));
+                true
             }
         }
     }
 
     fn bottom_hint_text(&mut self) -> Cow<'static, str> {
+        if let Some(refusal) = &self.refusal {
+            return Cow::Owned(refusal.clone());
+        }
+        if let (Some(label), Some(elapsed)) = (self.tasks.busy(), self.tasks.busy_for()) {
+            let elapsed = elapsed.as_secs();
+            return Cow::Owned(format!(
+                "{label}... {elapsed}s so far. Press Esc to cancel."
+            ));
+        }
         if let Some(thinking) = self.thinking.as_ref() {
-            let seat = seat_name(thinking.seat);
+            let seat = self.session.controller().seat_name(thinking.seat);
             let elapsed = thinking.started_at.elapsed().as_secs();
             let max_secs = thinking
                 .deadline
@@ -1167,7 +1543,7 @@ impl AppState {
             Cow::Owned(text)
         } else if let Some(banner) = self.timeout_banner.as_mut() {
             if !banner.hint_shown {
-                let seat = seat_name(banner.seat);
+                let seat = self.session.controller().seat_name(banner.seat);
                 let extra = fallback_description(banner.fallback);
                 banner.hint_shown = true;
                 return Cow::Owned(format!("{seat} timed out. {extra}."));
@@ -1296,21 +1672,157 @@ impl AppState {
         Ok(())
     }
 
+    fn input_phase(&self) -> InputPhase {
+        if self.session.controller().match_over()
+            || self.replay.is_some()
+            || self.collect.is_some()
+            || self.anim.is_some()
+            || self.pass.is_some()
+            || self.await_pass_ack.is_some()
+        {
+            InputPhase::Busy
+        } else if self.session.controller().in_passing_phase() {
+            InputPhase::Passing
+        } else {
+            InputPhase::Playing
+        }
+    }
+
+    /// What client pixel `(x, y)` is over, with the card's rectangle when it
+    /// is a card in the South hand.
+    fn pointer_target(&self, hwnd: HWND, x: i32, y: i32) -> (Target, Option<D2D_RECT_F>) {
+        let layout = self.layout_size(client_size(hwnd));
+        let south_hand = self.session.controller().hand(PlayerPosition::South);
+        let rects = compute_south_hand_rects(layout, south_hand.len(), self.hand_fan);
+        let (scale_x, scale_y) = self.inv_dpi_scales();
+        let xf = x as f32 * scale_x;
+        let yf = y as f32 * scale_y;
+        let passing = self.session.controller().in_passing_phase();
+        // Iterate right-to-left so the visually topmost card wins in overlaps
+        for (card, rect) in south_hand.iter().zip(rects.iter()).rev() {
+            let mut r = *rect;
+            // During passing, selected cards are lifted; reflect that in hit testing
+            if passing && self.passing_select.contains(card) {
+                let dy = (r.bottom - r.top) * 0.18;
+                r.top -= dy;
+                r.bottom -= dy;
+            }
+            if xf >= r.left && xf <= r.right && yf >= r.top && yf <= r.bottom {
+                return (Target::Card(*card), Some(r));
+            }
+        }
+        let table = D2D_RECT_F {
+            left: layout.width * 0.08,
+            top: layout.height * 0.12,
+            right: layout.width * 0.92,
+            bottom: layout.height * 0.88,
+        };
+        if xf >= table.left && xf <= table.right && yf >= table.top && yf <= table.bottom {
+            (Target::Table, None)
+        } else {
+            (Target::Elsewhere, None)
+        }
+    }
+
+    /// Carries out a mapped input action. Returns the card-count text to show
+    /// once the caller has let go of the state.
+    fn perform_input(
+        &mut self,
+        hwnd: HWND,
+        action: Action,
+        card_rect: Option<D2D_RECT_F>,
+    ) -> Option<(Suit, String)> {
+        self.refusal = None;
+        match action {
+            Action::TogglePassCard(card) => {
+                if let Some(pos) = self.passing_select.iter().position(|c| *c == card) {
+                    self.passing_select.remove(pos);
+                } else if self.passing_select.len() < self.session.controller().pass_count() {
+                    self.passing_select.push(card);
+                }
+            }
+            Action::PlayCard(card) => {
+                let from = card_rect?;
+                if !self
+                    .session
+                    .controller()
+                    .legal_moves_set(PlayerPosition::South)
+                    .contains(&card)
+                {
+                    self.refusal = self
+                        .session
+                        .controller()
+                        .play_refusal(PlayerPosition::South, card);
+                    unsafe {
+                        let _ = InvalidateRect(Some(hwnd), None, true);
+                    }
+                    return None;
+                }
+                debug_out("mdhearts: ", &format!("South plays {}", card));
+                let layout = self.layout_size(client_size(hwnd));
+                let to = compute_trick_rect_for(layout, PlayerPosition::South);
+                self.session
+                    .controller_mut()
+                    .record_human_play(PlayerPosition::South, card);
+                let _ = self.session.apply(SessionCommand::HumanPlay {
+                    seat: PlayerPosition::South,
+                    card,
+                });
+                self.anim = Some(PlayAnim {
+                    seat: PlayerPosition::South,
+                    card,
+                    from,
+                    to,
+                    start: std::time::Instant::now(),
+                    dur_ms: self.session.controller().pacing().play_anim_ms,
+                });
+            }
+            Action::CommitPass => {
+                // Commits once every card is selected.
+                self.try_start_pass_animation(hwnd);
+                return None;
+            }
+            Action::FanHand(notches) => {
+                self.hand_fan = self.hand_fan.scrolled(notches);
+            }
+            Action::ShowSuitCounts(suit) => {
+                let tally = self
+                    .session
+                    .controller()
+                    .suit_tally(PlayerPosition::South, suit);
+                let mut text = tally.summary();
+                if self.assist_level.shows_mastery() {
+                    let mastery = MasterySummary::from_tally(&tally);
+                    text.push_str("\n\n");
+                    text.push_str(&mastery.claim());
+                }
+                return Some((suit, text));
+            }
+        }
+        unsafe {
+            let _ = InvalidateRect(Some(hwnd), None, true);
+        }
+        None
+    }
+
     fn try_start_pass_animation(&mut self, hwnd: HWND) {
-        if !(self.controller.in_passing_phase()
-            && self.passing_select.len() == 3
+        if !(self.session.controller().in_passing_phase()
+            && self.passing_select.len() == self.session.controller().pass_count()
             && self.pass.is_none())
         {
             return;
         }
+        let pass_count = self.session.controller().pass_count();
         let size = client_size(hwnd);
         let layout = self.layout_size(size);
-        let south_before = self.controller.hand(PlayerPosition::South);
-        let south_rects = compute_south_hand_rects(layout, south_before.len());
+        let south_before = self.session.controller().hand(PlayerPosition::South);
+        let south_rects = compute_south_hand_rects(layout, south_before.len(), self.hand_fan);
+        let pacing = self.session.controller().pacing();
         let mut out_sprites: Vec<PassSprite> = Vec::new();
-        let dir = self.controller.passing_direction();
+        let dir = self.session.controller().passing_direction();
         let to_seat = dir.target(PlayerPosition::South);
-        let base_to_rect = compute_collect_target_rect_for(layout, &self.controller, to_seat);
+        let base_to_rect =
+            compute_collect_target_rect_for(layout, self.session.controller(), to_seat);
         let to_rect = match to_seat {
             PlayerPosition::East | PlayerPosition::West => ensure_portrait_centered(base_to_rect),
             _ => base_to_rect,
@@ -1324,7 +1836,7 @@ impl AppState {
                 dest.right += offset;
                 dest.top += offset * 0.2;
                 dest.bottom += offset * 0.2;
-                let delay = (iout as u64) * 60;
+                let delay = (iout as u64) * pacing.pass_stagger_ms;
                 let jx = (iout as f32 - 1.0) * 1.2;
                 let jy = (1.0 - (iout as f32 - 1.0).abs()) * 0.6;
                 out_sprites.push(PassSprite {
@@ -1337,16 +1849,19 @@ impl AppState {
                 });
             }
         }
-        if out_sprites.len() != 3 {
+        if out_sprites.len() != pass_count {
             let mut fallback = Vec::new();
-            let center =
-                compute_collect_target_rect_for(layout, &self.controller, PlayerPosition::South);
-            for (i, c) in self.passing_select.iter().take(3).enumerate() {
+            let center = compute_collect_target_rect_for(
+                layout,
+                self.session.controller(),
+                PlayerPosition::South,
+            );
+            for (i, c) in self.passing_select.iter().take(pass_count).enumerate() {
                 let mut dest = to_rect;
                 let off = (i as f32 - 1.0) * 8.0;
                 dest.left += off;
                 dest.right += off;
-                let delay = (i as u64) * 60;
+                let delay = (i as u64) * pacing.pass_stagger_ms;
                 let jx = (i as f32 - 1.0) * 1.2;
                 let jy = (1.0 - (i as f32 - 1.0).abs()) * 0.6;
                 fallback.push(PassSprite {
@@ -1365,12 +1880,16 @@ impl AppState {
             .copied()
             .find(|&s| dir.target(s) == PlayerPosition::South)
             .unwrap_or(PlayerPosition::North);
-        let from_rect = compute_collect_target_rect_for(layout, &self.controller, from_seat);
-        let to_rect_in =
-            compute_collect_target_rect_for(layout, &self.controller, PlayerPosition::South);
+        let from_rect =
+            compute_collect_target_rect_for(layout, self.session.controller(), from_seat);
+        let to_rect_in = compute_collect_target_rect_for(
+            layout,
+            self.session.controller(),
+            PlayerPosition::South,
+        );
         let mut inn_sprites: Vec<PassSprite> = Vec::new();
-        let incoming_cards = self.controller.simple_pass_for(from_seat);
-        for i in 0..3 {
+        let incoming_cards = self.session.controller().simple_pass_for(from_seat);
+        for i in 0..pass_count {
             let mut fr = from_rect;
             let mut tr = to_rect_in;
             let off = (i as f32 - 1.0) * 8.0;
@@ -1378,8 +1897,8 @@ impl AppState {
             fr.right += off;
             tr.left += off;
             tr.right += off;
-            let delay = (i as u64) * 60;
-            let face = incoming_cards.map(|arr| arr[i]);
+            let delay = (i as u64) * pacing.pass_stagger_ms;
+            let face = incoming_cards.and_then(|cards| cards.get(i).copied());
             let jx = (i as f32 - 1.0) * 1.2;
             let jy = (1.0 - (i as f32 - 1.0).abs()) * 0.6;
             inn_sprites.push(PassSprite {
@@ -1391,33 +1910,66 @@ impl AppState {
                 jy,
             });
         }
-        let cards = [
-            self.passing_select[0],
-            self.passing_select[1],
-            self.passing_select[2],
-        ];
+        let Some(cards) = PassCards::from_slice(&self.passing_select) else {
+            return;
+        };
+        let names: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
         debug_out(
             "mdhearts: ",
-            &format!("Submitting pass: {}, {}, {}", cards[0], cards[1], cards[2]),
+            &format!("Submitting pass: {}", names.join(", ")),
         );
-        if self
-            .controller
-            .submit_pass(PlayerPosition::South, cards)
-            .is_ok()
-        {
-            let _ = self
-                .controller
-                .submit_auto_passes_for_others(PlayerPosition::South);
+        if let Ok(mut events) = self.session.apply(SessionCommand::HumanPass {
+            seat: PlayerPosition::South,
+            cards,
+        }) {
+            if let Ok(more) = self.session.apply(SessionCommand::BotPasses {
+                except: Some(PlayerPosition::South),
+            }) {
+                events.extend(more);
+            }
+            // Every pass moves at once, and only once all four are in.
+            let mut bot_sprites: Vec<PassSprite> = Vec::new();
+            if events.contains(&SessionEvent::PassesComplete) {
+                for seat in PlayerPosition::LOOP {
+                    let target = dir.target(seat);
+                    if seat == PlayerPosition::South || target == PlayerPosition::South {
+                        continue;
+                    }
+                    let fr =
+                        compute_collect_target_rect_for(layout, self.session.controller(), seat);
+                    let tr =
+                        compute_collect_target_rect_for(layout, self.session.controller(), target);
+                    for i in 0..pass_count as u32 {
+                        let off = (i as f32 - 1.0) * 8.0;
+                        bot_sprites.push(PassSprite {
+                            from: D2D_RECT_F {
+                                left: fr.left + off,
+                                right: fr.right + off,
+                                ..fr
+                            },
+                            to: D2D_RECT_F {
+                                left: tr.left + off,
+                                right: tr.right + off,
+                                ..tr
+                            },
+                            card: None,
+                            delay_ms: (i as u64) * pacing.pass_stagger_ms,
+                            jx: (i as f32 - 1.0) * 1.2,
+                            jy: (1.0 - (i as f32 - 1.0).abs()) * 0.6,
+                        });
+                    }
+                }
+            }
             self.pass = Some(PassAnim {
                 phase: PassPhase::Outgoing,
                 start: std::time::Instant::now(),
                 from_seat,
-                south_before,
                 out: out_sprites,
+                bot_out: bot_sprites,
                 inn: inn_sprites,
-                out_dur_ms: 320,
-                pause_ms: 220,
-                in_dur_ms: 320,
+                out_dur_ms: pacing.pass_out_ms,
+                pause_ms: pacing.pass_pause_ms,
+                in_dur_ms: pacing.pass_in_ms,
             });
             debug_out("mdhearts: ", "Pass animation scheduled");
             unsafe {
@@ -1580,19 +2132,25 @@ impl AppState {
         }
 
         debug_out("mdhearts: ", "draw: begin");
+        if self.replay.is_some() {
+            return self.draw_replay(hwnd, &rt, layout);
+        }
         let status = self.status_header_text();
         // Touch methods to avoid dead_code warnings until used in UI
-        let _ = self.controller.standings();
-        let _ = self.controller.trick_leader();
-        let _ = self.controller.trick_plays();
+        let _ = self.session.controller().standings();
+        let _ = self.session.controller().trick_leader();
+        let _ = self.session.controller().trick_plays();
         // Title bar update
         let title = string_to_wide_z(&format!("MD Hearts - {}", status));
         unsafe {
             let _ = SetWindowTextW(hwnd, PCWSTR(title.as_ptr()));
         }
 
-        let south_hand = self.controller.hand(PlayerPosition::South);
-        let south_legal_set = self.controller.legal_moves_set(PlayerPosition::South);
+        let south_hand = self.session.controller().hand(PlayerPosition::South);
+        let south_legal_set = self
+            .session
+            .controller()
+            .legal_moves_set(PlayerPosition::South);
         let south_labels: Vec<Vec<u16>> = south_hand.iter().map(|&c| card_label_wide(c)).collect();
         let south_legal: Vec<bool> = south_hand
             .iter()
@@ -1681,9 +2239,9 @@ impl AppState {
                 self.card_back_bitmap.as_ref()
             };
             let back = self.card_back_bitmap.as_ref(); // unrotated for North
-            let north_count = self.controller.hand(PlayerPosition::North).len();
-            let east_count = self.controller.hand(PlayerPosition::East).len();
-            let west_count = self.controller.hand(PlayerPosition::West).len();
+            let north_count = self.session.controller().hand(PlayerPosition::North).len();
+            let east_count = self.session.controller().hand(PlayerPosition::East).len();
+            let west_count = self.session.controller().hand(PlayerPosition::West).len();
             let back_fallback = rt.CreateSolidColorBrush(
                 &D2D1_COLOR_F {
                     r: 0.15,
@@ -1767,8 +2325,49 @@ impl AppState {
             debug_out("mdhearts: ", "draw: ensure_cards_bitmap");
             self.ensure_cards_bitmap(&rt)?;
             let atlas_bmp_opt = self.cards_bitmap.clone();
+            let card_style = self.card_style;
+            let dwrite = self.dwrite.clone();
+            let outline = |emphasis: Emphasis| -> Result<(ID2D1SolidColorBrush, f32)> {
+                Ok((
+                    rgba_brush(&rt, card_style.emphasis_color(emphasis))?,
+                    card_style.stroke(emphasis),
+                ))
+            };
             let recv_list = self.await_pass_ack.clone();
-            let rects = compute_south_hand_rects(layout, south_labels.len());
+            let recv_marks = self
+                .session
+                .controller()
+                .received_marks(PlayerPosition::South);
+            // Full counting: mark South's top card in each suit it is master
+            // or beatable in.
+            let mastery = if self.assist_level.shows_mastery() {
+                Some(
+                    self.session
+                        .controller()
+                        .suit_mastery(PlayerPosition::South),
+                )
+            } else {
+                None
+            };
+            let master_brush = rt.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 0.2,
+                    g: 0.75,
+                    b: 0.35,
+                    a: 1.0,
+                },
+                None,
+            )?;
+            let beatable_brush = rt.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 0.95,
+                    g: 0.6,
+                    b: 0.1,
+                    a: 1.0,
+                },
+                None,
+            )?;
+            let rects = compute_south_hand_rects(layout, south_labels.len(), self.hand_fan);
             for (i, rect) in rects.iter().enumerate() {
                 let selected = self
                     .passing_select
@@ -1781,7 +2380,7 @@ impl AppState {
 
                 // Raise selected cards during passing, but keep normal draw order
                 let mut draw_rect = *rect;
-                if (selected && self.controller.in_passing_phase()) || recv_highlight {
+                if (selected && self.session.controller().in_passing_phase()) || recv_highlight {
                     let h = rect.bottom - rect.top;
                     let dy = h * 0.18; // modest lift within the hand band
                     draw_rect.top -= dy;
@@ -1791,7 +2390,7 @@ impl AppState {
                 let dest = snap_rect(draw_rect);
 
                 // Subtle shadow for popped cards (will still layer under later cards)
-                if (selected && self.controller.in_passing_phase()) || recv_highlight {
+                if (selected && self.session.controller().in_passing_phase()) || recv_highlight {
                     let mut sh = dest;
                     sh.left += 2.0;
                     sh.right += 2.0;
@@ -1813,21 +2412,18 @@ impl AppState {
                     radiusY: radius,
                 };
                 let mut drew_face = false;
-                if let Some(ref bmp) = atlas_bmp_opt
-                    && let Some(card) = south_hand.get(i).copied()
-                    && let Some(src) = self.atlas.src_rect_for(card)
-                {
-                    let src = inset_rect(src, 0.5, 0.5);
-                    draw_bitmap_with_round_corners(
+                if let Some(card) = south_hand.get(i).copied() {
+                    drew_face = draw_card_face(
                         &factory,
                         &rt,
+                        &dwrite,
+                        card_style,
+                        atlas_bmp_opt.as_ref().map(|bmp| (bmp, &self.atlas)),
                         &rounded,
-                        bmp,
-                        Some(&src),
+                        card,
                         1.0,
                         D2D1_BITMAP_INTERPOLATION_MODE_NEAREST_NEIGHBOR,
                     )?;
-                    drew_face = true;
                 }
                 if !drew_face {
                     let placeholder = rt.CreateSolidColorBrush(
@@ -1842,25 +2438,74 @@ impl AppState {
                     rt.FillRoundedRectangle(&rounded, &placeholder);
                 }
                 let legal = *south_legal.get(i).unwrap_or(&false);
-                let border_brush =
-                    if (selected && self.controller.in_passing_phase()) || recv_highlight {
-                        &sel_brush
+                let (border_brush, stroke) = outline(
+                    if (selected && self.session.controller().in_passing_phase()) || recv_highlight
+                    {
+                        Emphasis::Selected
                     } else if legal {
-                        &border
+                        Emphasis::Plain
                     } else {
-                        &text_brush
+                        Emphasis::Unplayable
+                    },
+                )?;
+                rt.DrawRoundedRectangle(&rounded, &border_brush, stroke, None);
+                // Corner badge on received cards for the first tricks
+                if !recv_highlight
+                    && let Some(card) = south_hand.get(i)
+                    && recv_marks.contains(card)
+                {
+                    let w = dest.right - dest.left;
+                    let d = (w * 0.16).max(6.0);
+                    let badge = D2D1_ROUNDED_RECT {
+                        rect: D2D_RECT_F {
+                            left: dest.right - d - 3.0,
+                            top: dest.top + 3.0,
+                            right: dest.right - 3.0,
+                            bottom: dest.top + 3.0 + d,
+                        },
+                        radiusX: d * 0.5,
+                        radiusY: d * 0.5,
                     };
-                rt.DrawRoundedRectangle(&rounded, border_brush, 2.0, None);
+                    rt.FillRoundedRectangle(&badge, &sel_brush);
+                }
+                // Master claim dot, top left: filled when nothing unseen
+                // beats the card, outlined when something does.
+                if let Some(mastery) = &mastery
+                    && let Some(card) = south_hand.get(i).copied()
+                {
+                    let claim = &mastery[card.suit.index()];
+                    if claim.top == Some(card) {
+                        let w = dest.right - dest.left;
+                        let d = (w * 0.14).max(6.0);
+                        let dot = D2D1_ROUNDED_RECT {
+                            rect: D2D_RECT_F {
+                                left: dest.left + 3.0,
+                                top: dest.top + 3.0,
+                                right: dest.left + 3.0 + d,
+                                bottom: dest.top + 3.0 + d,
+                            },
+                            radiusX: d * 0.5,
+                            radiusY: d * 0.5,
+                        };
+                        match claim.state {
+                            Mastery::Master => rt.FillRoundedRectangle(&dot, &master_brush),
+                            Mastery::Beatable => {
+                                rt.DrawRoundedRectangle(&dot, &beatable_brush, 1.5, None)
+                            }
+                            Mastery::Void => {}
+                        }
+                    }
+                }
             }
 
             // Current trick (center cards face-up). If the trick just completed
             // and the last-card animation is still running (collect not yet started),
             // draw the prior trick's cards so they don't pop away.
-            if let Some(ref bmp) = atlas_bmp_opt {
-                let mut plays = self.controller.trick_plays();
+            if atlas_bmp_opt.is_some() || !card_style.uses_atlas() {
+                let mut plays = self.session.controller().trick_plays();
                 if plays.is_empty()
                     && self.collect.is_none()
-                    && let Some(summary) = self.controller.last_trick()
+                    && let Some(summary) = self.session.controller().last_trick()
                 {
                     plays = summary.plays.clone();
                 }
@@ -1871,57 +2516,60 @@ impl AppState {
                     {
                         continue;
                     }
-                    if let Some(src) = self.atlas.src_rect_for(card) {
-                        let dest = snap_rect(compute_trick_rect_for(layout, pos));
-                        let radius = card_corner_radius(&dest);
-                        let rounded = D2D1_ROUNDED_RECT {
-                            rect: dest,
-                            radiusX: radius,
-                            radiusY: radius,
-                        };
-                        let src = inset_rect(src, 0.5, 0.5);
-                        draw_bitmap_with_round_corners(
-                            &factory,
-                            &rt,
-                            &rounded,
-                            bmp,
-                            Some(&src),
-                            1.0,
-                            D2D1_BITMAP_INTERPOLATION_MODE_NEAREST_NEIGHBOR,
-                        )?;
-                        let hl = if Some(pos) == leading_so_far {
-                            &text_brush
-                        } else {
-                            &border
-                        };
-                        rt.DrawRoundedRectangle(&rounded, hl, 2.0, None);
-                    }
-                }
-            }
-
-            // Active animation overlay (draw last so it is visible)
-            if let (Some(bmp), Some(anim)) = (atlas_bmp_opt.as_ref(), &self.anim) {
-                let t = (std::time::Instant::now() - anim.start).as_millis() as u64;
-                let u = (t as f32 / anim.dur_ms as f32).clamp(0.0, 1.0);
-                let dest = snap_rect(lerp_rect(anim.from, anim.to, ease_out(u)));
-                if let Some(src) = self.atlas.src_rect_for(anim.card) {
+                    let dest = snap_rect(compute_trick_rect_for(layout, pos));
                     let radius = card_corner_radius(&dest);
                     let rounded = D2D1_ROUNDED_RECT {
                         rect: dest,
                         radiusX: radius,
                         radiusY: radius,
                     };
-                    let src = inset_rect(src, 0.5, 0.5);
-                    draw_bitmap_with_round_corners(
+                    if draw_card_face(
                         &factory,
                         &rt,
+                        &dwrite,
+                        card_style,
+                        atlas_bmp_opt.as_ref().map(|bmp| (bmp, &self.atlas)),
                         &rounded,
-                        bmp,
-                        Some(&src),
+                        card,
                         1.0,
-                        D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
-                    )?;
-                    rt.DrawRoundedRectangle(&rounded, &border, 2.0, None);
+                        D2D1_BITMAP_INTERPOLATION_MODE_NEAREST_NEIGHBOR,
+                    )? {
+                        let (hl, stroke) = outline(if Some(pos) == leading_so_far {
+                            Emphasis::Leading
+                        } else {
+                            Emphasis::Plain
+                        })?;
+                        rt.DrawRoundedRectangle(&rounded, &hl, stroke, None);
+                    }
+                }
+            }
+
+            // Active animation overlay (draw last so it is visible)
+            if let Some(anim) = &self.anim
+                && (atlas_bmp_opt.is_some() || !card_style.uses_atlas())
+            {
+                let t = (std::time::Instant::now() - anim.start).as_millis() as u64;
+                let u = (t as f32 / anim.dur_ms as f32).clamp(0.0, 1.0);
+                let dest = snap_rect(lerp_rect(anim.from, anim.to, ease_out(u)));
+                let radius = card_corner_radius(&dest);
+                let rounded = D2D1_ROUNDED_RECT {
+                    rect: dest,
+                    radiusX: radius,
+                    radiusY: radius,
+                };
+                if draw_card_face(
+                    &factory,
+                    &rt,
+                    &dwrite,
+                    card_style,
+                    atlas_bmp_opt.as_ref().map(|bmp| (bmp, &self.atlas)),
+                    &rounded,
+                    anim.card,
+                    1.0,
+                    D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
+                )? {
+                    let (edge, stroke) = outline(Emphasis::Plain)?;
+                    rt.DrawRoundedRectangle(&rounded, &edge, stroke, None);
                 }
                 if t >= anim.dur_ms {
                     self.anim = None;
@@ -1929,47 +2577,59 @@ impl AppState {
             }
 
             // Trick collect overlay: pause, then sweep cards to winner
-            if let (Some(bmp), Some(coll)) = (atlas_bmp_opt.as_ref(), &self.collect) {
+            if let Some(coll) = &self.collect
+                && (atlas_bmp_opt.is_some() || !card_style.uses_atlas())
+            {
                 let elapsed = (std::time::Instant::now() - coll.start).as_millis() as u64;
-                let moving = elapsed.saturating_sub(coll.delay_ms);
-                let u = (moving as f32 / coll.dur_ms as f32).clamp(0.0, 1.0);
+                let stage = self
+                    .session
+                    .controller()
+                    .pacing()
+                    .collect_stage(elapsed, coll.clicked_at_ms);
                 let base_to =
-                    compute_collect_target_rect_for(layout, &self.controller, coll.winner);
+                    compute_collect_target_rect_for(layout, self.session.controller(), coll.winner);
                 let to_rect = match coll.winner {
                     PlayerPosition::East | PlayerPosition::West => {
                         ensure_portrait_centered(base_to)
                     }
                     _ => base_to,
                 };
+                // The winning card keeps its emphasis until the sweep starts.
                 for (seat, card) in &coll.cards {
-                    if let Some(src) = self.atlas.src_rect_for(*card) {
-                        let from_rect = compute_trick_rect_for(layout, *seat);
-                        let dest = if elapsed < coll.delay_ms {
-                            from_rect
+                    let from_rect = compute_trick_rect_for(layout, *seat);
+                    let dest = match stage {
+                        CollectStage::Showing => from_rect,
+                        CollectStage::Sweeping(u) => lerp_rect(from_rect, to_rect, ease_out(u)),
+                        CollectStage::Done => to_rect,
+                    };
+                    let dest = snap_rect(dest);
+                    let radius = card_corner_radius(&dest);
+                    let rounded = D2D1_ROUNDED_RECT {
+                        rect: dest,
+                        radiusX: radius,
+                        radiusY: radius,
+                    };
+                    if draw_card_face(
+                        &factory,
+                        &rt,
+                        &dwrite,
+                        card_style,
+                        atlas_bmp_opt.as_ref().map(|bmp| (bmp, &self.atlas)),
+                        &rounded,
+                        *card,
+                        1.0,
+                        D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
+                    )? {
+                        let emphasis = if stage == CollectStage::Showing && coll.winner == *seat {
+                            Emphasis::Leading
                         } else {
-                            lerp_rect(from_rect, to_rect, ease_out(u))
-                        };
-                        let dest = snap_rect(dest);
-                        let radius = card_corner_radius(&dest);
-                        let rounded = D2D1_ROUNDED_RECT {
-                            rect: dest,
-                            radiusX: radius,
-                            radiusY: radius,
+                            Emphasis::Plain
                         };
-                        let src = inset_rect(src, 0.5, 0.5);
-                        draw_bitmap_with_round_corners(
-                            &factory,
-                            &rt,
-                            &rounded,
-                            bmp,
-                            Some(&src),
-                            1.0,
-                            D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
-                        )?;
-                        rt.DrawRoundedRectangle(&rounded, &border, 2.0, None);
+                        let (edge, stroke) = outline(emphasis)?;
+                        rt.DrawRoundedRectangle(&rounded, &edge, stroke, None);
                     }
                 }
-                if elapsed >= coll.delay_ms + coll.dur_ms {
+                if stage == CollectStage::Done {
                     self.collect = None;
                 }
             }
@@ -1978,7 +2638,8 @@ impl AppState {
             // Preload back bitmaps we may need to avoid nested &mut borrows
             let (need_back, need_back_rot) = if let Some(p) = self.pass.as_ref() {
                 (
-                    matches!(p.phase, PassPhase::Incoming),
+                    matches!(p.phase, PassPhase::Incoming)
+                        || (p.phase == PassPhase::Outgoing && !p.bot_out.is_empty()),
                     matches!(p.phase, PassPhase::Incoming)
                         && self.rotate_sides
                         && (p.from_seat == PlayerPosition::East
@@ -1997,16 +2658,47 @@ impl AppState {
                 let now = std::time::Instant::now();
                 match pass.phase {
                     PassPhase::Outgoing => {
+                        // Bot-to-bot passes travel face down
+                        if let Some(back) = self.card_back_bitmap.as_ref() {
+                            for s in &pass.bot_out {
+                                let elapsed = (now - pass.start).as_millis() as u64;
+                                let moved = elapsed.saturating_sub(s.delay_ms);
+                                let u = (moved as f32 / pass.out_dur_ms as f32).clamp(0.0, 1.0);
+                                if u <= 0.0 {
+                                    continue;
+                                }
+                                let mut rect = lerp_rect(s.from, s.to, ease_out(u));
+                                rect.left += s.jx;
+                                rect.right += s.jx;
+                                rect.top += s.jy;
+                                rect.bottom += s.jy;
+                                let dest = snap_rect(rect);
+                                let radius = card_corner_radius(&dest);
+                                let rounded = D2D1_ROUNDED_RECT {
+                                    rect: dest,
+                                    radiusX: radius,
+                                    radiusY: radius,
+                                };
+                                draw_bitmap_with_round_corners(
+                                    &factory,
+                                    &rt,
+                                    &rounded,
+                                    back,
+                                    None,
+                                    1.0,
+                                    D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
+                                )?;
+                                rt.DrawRoundedRectangle(&rounded, &border, 2.0, None);
+                            }
+                        }
                         // Per-sprite stagger via delay_ms
                         // Draw our selected faces flying out
-                        if let Some(bmp) = atlas_bmp_opt.as_ref() {
+                        if atlas_bmp_opt.is_some() || !card_style.uses_atlas() {
                             for s in &pass.out {
                                 let elapsed = (now - pass.start).as_millis() as u64;
                                 let moved = elapsed.saturating_sub(s.delay_ms);
                                 let u = (moved as f32 / pass.out_dur_ms as f32).clamp(0.0, 1.0);
-                                if let Some(card) = s.card
-                                    && let Some(src) = self.atlas.src_rect_for(card)
-                                {
+                                if let Some(card) = s.card {
                                     if u <= 0.0 {
                                         continue;
                                     }
@@ -2022,22 +2714,31 @@ impl AppState {
                                         radiusX: radius,
                                         radiusY: radius,
                                     };
-                                    let src = inset_rect(src, 0.5, 0.5);
-                                    draw_bitmap_with_round_corners(
+                                    if draw_card_face(
                                         &factory,
                                         &rt,
+                                        &dwrite,
+                                        card_style,
+                                        atlas_bmp_opt.as_ref().map(|bmp| (bmp, &self.atlas)),
                                         &rounded,
-                                        bmp,
-                                        Some(&src),
+                                        card,
                                         1.0,
                                         D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
-                                    )?;
-                                    rt.DrawRoundedRectangle(&rounded, &border, 2.0, None);
+                                    )? {
+                                        let (edge, stroke) = outline(Emphasis::Plain)?;
+                                        rt.DrawRoundedRectangle(&rounded, &edge, stroke, None);
+                                    }
                                 }
                             }
                         }
                         // Advance when the last stagger finishes
-                        let max_delay = pass.out.iter().map(|s| s.delay_ms).max().unwrap_or(0);
+                        let max_delay = pass
+                            .out
+                            .iter()
+                            .chain(&pass.bot_out)
+                            .map(|s| s.delay_ms)
+                            .max()
+                            .unwrap_or(0);
                         if (now - pass.start).as_millis() as u64 >= max_delay + pass.out_dur_ms {
                             pass.phase = PassPhase::Pause;
                             pass.start = now;
@@ -2114,35 +2815,39 @@ impl AppState {
                                         D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
                                     )?;
                                 }
-                                if let (Some(bmp), Some(card)) = (atlas_bmp_opt.as_ref(), s.card)
-                                    && let Some(src) = self.atlas.src_rect_for(card)
-                                {
-                                    let src = inset_rect(src, 0.5, 0.5);
-                                    draw_bitmap_with_round_corners(
+                                if let Some(card) = s.card {
+                                    draw_card_face(
                                         &factory,
                                         &rt,
+                                        &dwrite,
+                                        card_style,
+                                        atlas_bmp_opt.as_ref().map(|bmp| (bmp, &self.atlas)),
                                         &rounded,
-                                        bmp,
-                                        Some(&src),
+                                        card,
                                         t.max(0.2),
                                         D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
                                     )?;
                                 }
                             }
-                            rt.DrawRoundedRectangle(&rounded, &border, 2.0, None);
+                            let (edge, stroke) = outline(Emphasis::Plain)?;
+                            rt.DrawRoundedRectangle(&rounded, &edge, stroke, None);
                         }
                         if (now - pass.start).as_millis() as u64 >= max_delay + pass.in_dur_ms {
                             // Finalize passes in the model now that animation is done
-                            let _ = self.controller.resolve_passes();
-                            // Identify received cards by diffing South hand after resolve vs before
-                            let after = self.controller.hand(PlayerPosition::South);
-                            let mut recd: Vec<ModelCard> = Vec::new();
-                            for c in after.iter().copied() {
-                                if !pass.south_before.contains(&c) {
-                                    recd.push(c);
-                                }
-                            }
-                            self.await_pass_ack = if recd.is_empty() { None } else { Some(recd) };
+                            let recd = self
+                                .session
+                                .apply(SessionCommand::ResolvePasses)
+                                .unwrap_or_default()
+                                .into_iter()
+                                .find_map(|event| match event {
+                                    SessionEvent::CardsReceived { seat, cards, .. }
+                                        if seat == PlayerPosition::South =>
+                                    {
+                                        Some(cards.to_vec())
+                                    }
+                                    _ => None,
+                                });
+                            self.await_pass_ack = recd;
                             self.passing_select.clear();
                             self.pass = None;
                         }
@@ -2168,41 +2873,24 @@ impl AppState {
                 DWRITE_MEASURING_MODE::default(),
             );
 
-            // Small HUD: scores, hand points, and tricks
-            let scores = self.controller.standings();
-            let hand = self.controller.penalties_this_round();
-            let tricks = self.controller.tricks_won_this_round();
-            let rows_specs = [
-                (
-                    "Scores",
-                    [
-                        scores[PlayerPosition::North.index()] as i32,
-                        scores[PlayerPosition::East.index()] as i32,
-                        scores[PlayerPosition::South.index()] as i32,
-                        scores[PlayerPosition::West.index()] as i32,
-                    ],
-                ),
-                (
-                    "This Hand",
-                    [
-                        hand[PlayerPosition::North.index()] as i32,
-                        hand[PlayerPosition::East.index()] as i32,
-                        hand[PlayerPosition::South.index()] as i32,
-                        hand[PlayerPosition::West.index()] as i32,
-                    ],
-                ),
-                (
-                    "Tricks",
-                    [
-                        tricks[PlayerPosition::North.index()] as i32,
-                        tricks[PlayerPosition::East.index()] as i32,
-                        tricks[PlayerPosition::South.index()] as i32,
-                        tricks[PlayerPosition::West.index()] as i32,
-                    ],
-                ),
-            ];
+            // Small HUD: scores (and, when detailed, last round and points
+            // to the match target), hand points, and tricks
+            let score_view = ScoreboardView::from_scores(
+                self.session.controller().scoreboard(),
+                self.session.controller().target_score(),
+            );
+            let hand = self.session.controller().penalties_this_round();
+            let tricks = self.session.controller().tricks_won_this_round();
+            let mut rows_specs = score_view.rows(self.score_display);
+            let score_rows = rows_specs.len();
+            rows_specs.push((
+                "This Hand".to_string(),
+                hand.map(|points| points.to_string()),
+            ));
+            rows_specs.push(("Tricks".to_string(), tricks.map(|count| count.to_string())));
             const SOUTH_INDEX: usize = 2;
-            let seat_labels = ["N", "E", "S", "W"];
+            let seat_labels =
+                PlayerPosition::LOOP.map(|seat| self.session.controller().players().badge(seat));
             let column_gap = 14.0_f32;
             let value_gap = 6.0_f32;
             let pad_x = 12.0_f32;
@@ -2248,10 +2936,9 @@ impl AppState {
                 let mut row_height = metrics.height.max(seat_label_height);
                 label_width = label_width.max(metrics.width);
                 let mut value_layouts = Vec::with_capacity(4);
-                for (idx, value) in values.iter().enumerate() {
-                    let text = value.to_string();
+                for (idx, text) in values.iter().enumerate() {
                     let (value_layout, value_metrics) =
-                        self.create_hud_layout(&text, 80.0, DWRITE_TEXT_ALIGNMENT_TRAILING)?;
+                        self.create_hud_layout(text, 80.0, DWRITE_TEXT_ALIGNMENT_TRAILING)?;
                     if idx == SOUTH_INDEX {
                         let range = DWRITE_TEXT_RANGE {
                             startPosition: 0,
@@ -2321,8 +3008,26 @@ impl AppState {
                 },
                 None,
             )?;
-            rt.FillRectangle(&rect, &hud_bg);
-
+            let leader_brush = rt.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 0.45,
+                    g: 0.90,
+                    b: 0.50,
+                    a: 1.0,
+                },
+                None,
+            )?;
+            let danger_brush = rt.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 1.0,
+                    g: 0.40,
+                    b: 0.35,
+                    a: 1.0,
+                },
+                None,
+            )?;
+            rt.FillRectangle(&rect, &hud_bg);
+
             let label_x = rect.left + pad_x;
             for (row_idx, row) in rows.iter().enumerate() {
                 let y = rect.top + pad_y + row_offsets[row_idx];
@@ -2351,7 +3056,12 @@ impl AppState {
                     );
                     let (value_layout, _) = &row.values[seat_idx];
                     value_layout.SetMaxWidth(value_widths[seat_idx] + 2.0)?;
-                    let value_brush = if seat_idx == SOUTH_INDEX {
+                    let seat_score = &score_view.seats[seat_idx];
+                    let value_brush = if row_idx < score_rows && seat_score.danger {
+                        &danger_brush
+                    } else if row_idx < score_rows && seat_score.leading {
+                        &leader_brush
+                    } else if seat_idx == SOUTH_INDEX {
                         &south_brush
                     } else {
                         &text_brush
@@ -2366,6 +3076,64 @@ impl AppState {
                 }
             }
 
+            // Debug overlay: expected points each seat adds this round, one bar
+            // per seat under the score HUD (full height = 26 points).
+            if let Some(values) = self.eval_bar.as_ref().and_then(EvalBar::latest) {
+                let bar_gap = 6.0_f32;
+                let bar_h = 36.0_f32;
+                let bar_w = (rect.right - rect.left - bar_gap * 5.0) / 4.0;
+                let panel = D2D_RECT_F {
+                    left: rect.left,
+                    top: rect.bottom + bar_gap,
+                    right: rect.right,
+                    bottom: rect.bottom + bar_gap * 2.0 + bar_h,
+                };
+                rt.FillRectangle(&panel, &hud_bg);
+                let bar_brush = rt.CreateSolidColorBrush(
+                    &D2D1_COLOR_F {
+                        r: 0.85,
+                        g: 0.30,
+                        b: 0.30,
+                        a: 0.9,
+                    },
+                    None,
+                )?;
+                for (seat_idx, value) in values.iter().enumerate() {
+                    let left = panel.left + bar_gap + seat_idx as f32 * (bar_w + bar_gap);
+                    let filled = bar_h * (value / 26.0).clamp(0.0, 1.0);
+                    let bar = D2D_RECT_F {
+                        left,
+                        top: panel.bottom - bar_gap - filled,
+                        right: left + bar_w,
+                        bottom: panel.bottom - bar_gap,
+                    };
+                    let brush = if seat_idx == SOUTH_INDEX {
+                        &south_brush
+                    } else {
+                        &bar_brush
+                    };
+                    rt.FillRectangle(&bar, brush);
+                }
+                let fallbacks = self.session.controller().search_fallbacks_this_round();
+                let label = format!("Search fallbacks this round: {fallbacks}");
+                let label_rect = D2D_RECT_F {
+                    left: panel.left,
+                    top: panel.bottom + bar_gap,
+                    right: panel.right,
+                    bottom: panel.bottom + bar_gap + bar_h * 0.6,
+                };
+                rt.FillRectangle(&label_rect, &hud_bg);
+                let label_wide = string_to_wide(&label);
+                rt.DrawText(
+                    label_wide.as_slice(),
+                    &self.text_format,
+                    &label_rect,
+                    &text_brush,
+                    Default::default(),
+                    DWRITE_MEASURING_MODE::default(),
+                );
+            }
+
             // On-screen hint for current action
             let hint = self.bottom_hint_text();
             // Place hint below the South hand dynamically
@@ -2405,6 +3173,254 @@ impl AppState {
         self.maybe_clear_timeout_banner();
         Ok(())
     }
+    /// The replay viewer: every hand face up, the trick on the table, and
+    /// the advice panel when it is on.
+    fn draw_replay(
+        &mut self,
+        hwnd: HWND,
+        rt: &ID2D1HwndRenderTarget,
+        layout: LayoutSize,
+    ) -> Result<()> {
+        let Some(view) = self.replay.as_ref() else {
+            return Ok(());
+        };
+        let header = view.header_text();
+        let advice = view.advice_text();
+        let hands = view.replay.face_up_hands();
+        let next = view.replay.next_play().map(|(_, card)| card);
+        let advised = view.advice.first().map(|&(card, _)| card);
+        let round = view.replay.round();
+        // A finished trick stays on the table until the next card is played.
+        let trick: Vec<(PlayerPosition, ModelCard)> = {
+            let current = round.current_trick().plays();
+            let shown = if current.is_empty() {
+                round
+                    .trick_history()
+                    .last()
+                    .map(|trick| trick.plays())
+                    .unwrap_or_default()
+            } else {
+                current
+            };
+            shown
+                .iter()
+                .map(|play| (play.position, play.card))
+                .collect()
+        };
+        let title = string_to_wide_z(&format!("MD Hearts - {header}"));
+        unsafe {
+            let _ = SetWindowTextW(hwnd, PCWSTR(title.as_ptr()));
+        }
+        self.ensure_cards_bitmap(rt)?;
+        let atlas_bmp_opt = self.cards_bitmap.clone();
+        let factory = self.factory.clone();
+        let dwrite = self.dwrite.clone();
+        let card_style = self.card_style;
+        let width = layout.width;
+        let height = layout.height;
+        // Side hands lie on their edge, so their faces are turned a quarter.
+        let face = |rect: D2D_RECT_F, card: ModelCard, angle: f32, emphasis: Emphasis| {
+            let dest = snap_rect(rect);
+            let radius = card_corner_radius(&dest);
+            let rounded = D2D1_ROUNDED_RECT {
+                rect: dest,
+                radiusX: radius,
+                radiusY: radius,
+            };
+            unsafe {
+                if angle != 0.0 {
+                    let center = Vector2::new(
+                        0.5 * (dest.left + dest.right),
+                        0.5 * (dest.top + dest.bottom),
+                    );
+                    rt.SetTransform(&Matrix3x2::rotation_around(angle, center));
+                }
+                let drew = draw_card_face(
+                    &factory,
+                    rt,
+                    &dwrite,
+                    card_style,
+                    atlas_bmp_opt.as_ref().map(|bmp| (bmp, &self.atlas)),
+                    &rounded,
+                    card,
+                    1.0,
+                    D2D1_BITMAP_INTERPOLATION_MODE_NEAREST_NEIGHBOR,
+                );
+                if let Ok(true) = drew {
+                    let edge = rgba_brush(rt, card_style.emphasis_color(emphasis))?;
+                    rt.DrawRoundedRectangle(&rounded, &edge, card_style.stroke(emphasis), None);
+                }
+                if angle != 0.0 {
+                    rt.SetTransform(&Matrix3x2::identity());
+                }
+                drew.map(|_| ())
+            }
+        };
+        let emphasis = |card: ModelCard| {
+            if Some(card) == next {
+                Emphasis::Selected
+            } else if Some(card) == advised {
+                Emphasis::Leading
+            } else {
+                Emphasis::Plain
+            }
+        };
+
+        unsafe {
+            rt.BeginDraw();
+            rt.Clear(Some(&D2D1_COLOR_F {
+                r: 0.05,
+                g: 0.15,
+                b: 0.10,
+                a: 1.0,
+            }));
+            let table = D2D_RECT_F {
+                left: width * 0.08,
+                top: height * 0.12,
+                right: width * 0.92,
+                bottom: height * 0.88,
+            };
+            let felt = rt.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 0.06,
+                    g: 0.22,
+                    b: 0.12,
+                    a: 1.0,
+                },
+                None,
+            )?;
+            let border = rt.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 0.2,
+                    g: 0.5,
+                    b: 0.3,
+                    a: 1.0,
+                },
+                None,
+            )?;
+            let text_brush = rt.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 1.0,
+                    g: 1.0,
+                    b: 1.0,
+                    a: 0.95,
+                },
+                None,
+            )?;
+            let panel_bg = rt.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 0.0,
+                    g: 0.0,
+                    b: 0.0,
+                    a: 0.35,
+                },
+                None,
+            )?;
+            rt.FillRectangle(&table, &felt);
+            rt.DrawRectangle(&table, &border, 2.0, None);
+
+            let north = &hands[PlayerPosition::North.index()];
+            for (rect, &card) in compute_north_hand_rects(layout, north.len())
+                .into_iter()
+                .zip(north)
+            {
+                face(rect, card, 0.0, emphasis(card))?;
+            }
+            let east = &hands[PlayerPosition::East.index()];
+            for (rect, &card) in compute_east_hand_rects(layout, east.len())
+                .into_iter()
+                .zip(east)
+            {
+                face(ensure_portrait_centered(rect), card, 90.0, emphasis(card))?;
+            }
+            let west = &hands[PlayerPosition::West.index()];
+            for (rect, &card) in compute_west_hand_rects(layout, west.len())
+                .into_iter()
+                .zip(west)
+            {
+                face(ensure_portrait_centered(rect), card, -90.0, emphasis(card))?;
+            }
+            let south = &hands[PlayerPosition::South.index()];
+            for (rect, &card) in compute_south_hand_rects(layout, south.len(), self.hand_fan)
+                .into_iter()
+                .zip(south)
+            {
+                face(rect, card, 0.0, emphasis(card))?;
+            }
+            let leading_so_far = current_trick_leader_so_far(&trick);
+            for &(seat, card) in &trick {
+                let emphasis = if Some(seat) == leading_so_far {
+                    Emphasis::Leading
+                } else {
+                    Emphasis::Plain
+                };
+                face(compute_trick_rect_for(layout, seat), card, 0.0, emphasis)?;
+            }
+
+            let header_rect = D2D_RECT_F {
+                left: 0.0,
+                top: height * 0.02,
+                right: width,
+                bottom: height * 0.08,
+            };
+            let header_wide = string_to_wide(&header);
+            rt.DrawText(
+                header_wide.as_slice(),
+                &self.text_format,
+                &header_rect,
+                &text_brush,
+                Default::default(),
+                DWRITE_MEASURING_MODE::default(),
+            );
+
+            if let Some(advice) = advice {
+                let lines = advice.lines().count() as f32;
+                let panel = D2D_RECT_F {
+                    left: width * 0.68,
+                    top: height * 0.09,
+                    right: width * 0.98,
+                    bottom: height * 0.09 + 28.0 * lines + 12.0,
+                };
+                rt.FillRectangle(&panel, &panel_bg);
+                let advice_wide = string_to_wide(&advice);
+                rt.DrawText(
+                    advice_wide.as_slice(),
+                    &self.text_format,
+                    &panel,
+                    &text_brush,
+                    Default::default(),
+                    DWRITE_MEASURING_MODE::default(),
+                );
+            }
+
+            let hint_rect = D2D_RECT_F {
+                left: 0.0,
+                top: height * 0.945,
+                right: width,
+                bottom: height * 0.995,
+            };
+            let hint = string_to_wide(
+                "Left/Right: card | Up/Down: trick | PgUp/PgDn: round | A: advice | Esc: back to the game",
+            );
+            rt.DrawText(
+                hint.as_slice(),
+                &self.text_format,
+                &hint_rect,
+                &text_brush,
+                Default::default(),
+                DWRITE_MEASURING_MODE::default(),
+            );
+
+            if let Err(err) = rt.EndDraw(None, None) {
+                if err.code().0 == D2DERR_RECREATE_TARGET {
+                    self.render_target = None;
+                } else {
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 fn init_menu_and_accels(hwnd: HWND) -> HACCEL {
@@ -2426,6 +3442,14 @@ fn init_menu_and_accels(hwnd: HWND) -> HACCEL {
             w!("&Restart Round\tF5"),
         )
     };
+    let _ = unsafe {
+        AppendMenuW(
+            game,
+            MF_STRING,
+            ID_GAME_REPLAY as usize,
+            w!("&Watch Replay\tCtrl+R"),
+        )
+    };
     // Difficulty submenu
     let difficulty = unsafe { CreatePopupMenu().expect("difficulty") };
     let _ = unsafe {
@@ -2505,6 +3529,98 @@ fn init_menu_and_accels(hwnd: HWND) -> HACCEL {
         )
     };
 
+    // Match target submenu
+    let match_target = unsafe { CreatePopupMenu().expect("match_target") };
+    let _ = unsafe {
+        AppendMenuW(
+            match_target,
+            MF_STRING,
+            ID_OPTIONS_TARGET_50 as usize,
+            w!("&Short Game (50)"),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            match_target,
+            MF_STRING,
+            ID_OPTIONS_TARGET_75 as usize,
+            w!("&Medium Game (75)"),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            match_target,
+            MF_STRING,
+            ID_OPTIONS_TARGET_100 as usize,
+            w!("&Full Game (100)"),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            match_target,
+            MF_STRING,
+            ID_OPTIONS_TARGET_150 as usize,
+            w!("&Long Game (150)"),
+        )
+    };
+    let _ = unsafe { AppendMenuW(game, MF_POPUP, match_target.0 as usize, w!("Match &Target")) };
+    let _ = unsafe {
+        AppendMenuW(
+            game,
+            MF_STRING,
+            ID_OPTIONS_JACK_OF_DIAMONDS as usize,
+            w!("&Omnibus (Jack of Diamonds -10)"),
+        )
+    };
+
+    let _ = unsafe {
+        AppendMenuW(
+            game,
+            MF_STRING,
+            ID_OPTIONS_FAST_MODE as usize,
+            w!("&Fast Mode"),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            game,
+            MF_STRING,
+            ID_OPTIONS_SCORE_DETAILED as usize,
+            w!("&Detailed Scores"),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            game,
+            MF_STRING,
+            ID_OPTIONS_ASSIST_COUNTING as usize,
+            w!("Full &Counting Assist"),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            game,
+            MF_STRING,
+            ID_OPTIONS_RECORD_PLAYS as usize,
+            w!("&Record My Plays"),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            game,
+            MF_STRING,
+            ID_OPTIONS_DELETE_RECORDING as usize,
+            w!("De&lete Recorded Plays..."),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            game,
+            MF_STRING,
+            ID_OPTIONS_PLAYERS as usize,
+            w!("&Players..."),
+        )
+    };
     let _ = unsafe {
         AppendMenuW(
             game,
@@ -2513,24 +3629,50 @@ fn init_menu_and_accels(hwnd: HWND) -> HACCEL {
             w!("&Card Back..."),
         )
     };
-    let _ = unsafe { AppendMenuW(game, MF_SEPARATOR, 0, None) };
-    let _ = unsafe { AppendMenuW(game, MF_STRING, ID_GAME_EXIT as usize, w!("E&xit")) };
-    let _ = unsafe { AppendMenuW(hmenu, MF_POPUP, game.0 as usize, w!("&Game")) };
-
-    let help = unsafe { CreatePopupMenu().expect("help") };
-    let _ = unsafe { AppendMenuW(help, MF_STRING, ID_HELP_RULES as usize, w!("&Rules...")) };
+    let card_display = unsafe { CreatePopupMenu().expect("card display") };
     let _ = unsafe {
         AppendMenuW(
-            help,
+            card_display,
             MF_STRING,
-            ID_HELP_ABOUT as usize,
-            w!("&About MD Hearts..."),
+            ID_OPTIONS_FOUR_COLOR as usize,
+            w!("&Four-Color Suits"),
         )
     };
-    let _ = unsafe { AppendMenuW(hmenu, MF_POPUP, help.0 as usize, w!("&Help")) };
-    let _ = unsafe { SetMenu(hwnd, Some(hmenu)) };
-    let _ = unsafe { DrawMenuBar(hwnd) };
-
+    let _ = unsafe {
+        AppendMenuW(
+            card_display,
+            MF_STRING,
+            ID_OPTIONS_SUIT_LETTERS as usize,
+            w!("Suit &Letters"),
+        )
+    };
+    let _ = unsafe {
+        AppendMenuW(
+            card_display,
+            MF_STRING,
+            ID_OPTIONS_HIGH_CONTRAST as usize,
+            w!("&High Contrast"),
+        )
+    };
+    let _ = unsafe { AppendMenuW(game, MF_POPUP, card_display.0 as usize, w!("Card D&isplay")) };
+    let _ = unsafe { AppendMenuW(game, MF_SEPARATOR, 0, None) };
+    let _ = unsafe { AppendMenuW(game, MF_STRING, ID_GAME_EXIT as usize, w!("E&xit")) };
+    let _ = unsafe { AppendMenuW(hmenu, MF_POPUP, game.0 as usize, w!("&Game")) };
+
+    let help = unsafe { CreatePopupMenu().expect("help") };
+    let _ = unsafe { AppendMenuW(help, MF_STRING, ID_HELP_RULES as usize, w!("&Rules...")) };
+    let _ = unsafe {
+        AppendMenuW(
+            help,
+            MF_STRING,
+            ID_HELP_ABOUT as usize,
+            w!("&About MD Hearts..."),
+        )
+    };
+    let _ = unsafe { AppendMenuW(hmenu, MF_POPUP, help.0 as usize, w!("&Help")) };
+    let _ = unsafe { SetMenu(hwnd, Some(hmenu)) };
+    let _ = unsafe { DrawMenuBar(hwnd) };
+
     let accels = [
         ACCEL {
             fVirt: FVIRTKEY | FCONTROL,
@@ -2542,6 +3684,11 @@ fn init_menu_and_accels(hwnd: HWND) -> HACCEL {
             key: 0x74,
             cmd: ID_GAME_RESTART as u16,
         },
+        ACCEL {
+            fVirt: FVIRTKEY | FCONTROL,
+            key: b'R' as u16,
+            cmd: ID_GAME_REPLAY as u16,
+        },
     ];
     unsafe { CreateAcceleratorTableW(&accels).expect("accel") }
 }
@@ -2559,7 +3706,8 @@ fn update_difficulty_menu(hwnd: HWND, difficulty: crate::bot::BotDifficulty) {
                         crate::bot::BotDifficulty::EasyLegacy => ID_OPTIONS_DIFFICULTY_EASY,
                         crate::bot::BotDifficulty::NormalHeuristic => ID_OPTIONS_DIFFICULTY_NORMAL,
                         crate::bot::BotDifficulty::FutureHard => ID_OPTIONS_DIFFICULTY_HARD,
-                        crate::bot::BotDifficulty::SearchLookahead => ID_OPTIONS_DIFFICULTY_SEARCH,
+                        crate::bot::BotDifficulty::SearchLookahead
+                        | crate::bot::BotDifficulty::Rollout => ID_OPTIONS_DIFFICULTY_SEARCH,
                     };
                     let _ = CheckMenuRadioItem(
                         diff_menu,
@@ -2609,6 +3757,278 @@ fn update_think_limit_menu(hwnd: HWND, config: ThinkConfig) {
     }
 }
 
+fn update_fast_mode_menu(hwnd: HWND, pacing: PacingConfig) {
+    unsafe {
+        let top = GetMenu(hwnd);
+        if !top.0.is_null() {
+            let game = GetSubMenu(top, 0);
+            if !game.0.is_null() {
+                let check = if pacing.is_fast() {
+                    MF_CHECKED
+                } else {
+                    MF_UNCHECKED
+                };
+                let _ = CheckMenuItem(game, ID_OPTIONS_FAST_MODE, (MF_BYCOMMAND | check).0);
+                let _ = DrawMenuBar(hwnd);
+            }
+        }
+    }
+}
+
+fn update_score_display_menu(hwnd: HWND, display: ScoreDisplay) {
+    unsafe {
+        let top = GetMenu(hwnd);
+        if !top.0.is_null() {
+            let game = GetSubMenu(top, 0);
+            if !game.0.is_null() {
+                let check = if display == ScoreDisplay::Detailed {
+                    MF_CHECKED
+                } else {
+                    MF_UNCHECKED
+                };
+                let _ = CheckMenuItem(game, ID_OPTIONS_SCORE_DETAILED, (MF_BYCOMMAND | check).0);
+                let _ = DrawMenuBar(hwnd);
+            }
+        }
+    }
+}
+
+fn update_match_target_menu(hwnd: HWND, target: u32) {
+    unsafe {
+        let top = GetMenu(hwnd);
+        if !top.0.is_null() {
+            // "Match Target" follows "Thinking Limit" under "Game" (index 4)
+            let game = GetSubMenu(top, 0);
+            if !game.0.is_null() {
+                let target_menu = GetSubMenu(game, 4);
+                if !target_menu.0.is_null() {
+                    // A custom target from the registry checks no preset.
+                    let selected = match target {
+                        50 => Some(ID_OPTIONS_TARGET_50),
+                        75 => Some(ID_OPTIONS_TARGET_75),
+                        100 => Some(ID_OPTIONS_TARGET_100),
+                        150 => Some(ID_OPTIONS_TARGET_150),
+                        _ => None,
+                    };
+                    for id in [
+                        ID_OPTIONS_TARGET_50,
+                        ID_OPTIONS_TARGET_75,
+                        ID_OPTIONS_TARGET_100,
+                        ID_OPTIONS_TARGET_150,
+                    ] {
+                        let _ = CheckMenuItem(target_menu, id, (MF_BYCOMMAND | MF_UNCHECKED).0);
+                    }
+                    if let Some(selected) = selected {
+                        let _ = CheckMenuRadioItem(
+                            target_menu,
+                            ID_OPTIONS_TARGET_50,
+                            ID_OPTIONS_TARGET_150,
+                            selected,
+                            MF_BYCOMMAND.0,
+                        );
+                    }
+                    let _ = DrawMenuBar(hwnd);
+                }
+            }
+        }
+    }
+}
+
+fn update_assist_menu(hwnd: HWND, level: AssistLevel) {
+    unsafe {
+        let top = GetMenu(hwnd);
+        if !top.0.is_null() {
+            let game = GetSubMenu(top, 0);
+            if !game.0.is_null() {
+                let check = if level.shows_mastery() {
+                    MF_CHECKED
+                } else {
+                    MF_UNCHECKED
+                };
+                let _ = CheckMenuItem(game, ID_OPTIONS_ASSIST_COUNTING, (MF_BYCOMMAND | check).0);
+                let _ = DrawMenuBar(hwnd);
+            }
+        }
+    }
+}
+
+fn update_card_style_menu(hwnd: HWND, style: CardStyle) {
+    unsafe {
+        let top = GetMenu(hwnd);
+        if !top.0.is_null() {
+            let game = GetSubMenu(top, 0);
+            if !game.0.is_null() {
+                // MF_BYCOMMAND finds the items inside the Card Display popup.
+                for (option, id) in [
+                    (CardStyleOption::FourColor, ID_OPTIONS_FOUR_COLOR),
+                    (CardStyleOption::SuitLetters, ID_OPTIONS_SUIT_LETTERS),
+                    (CardStyleOption::HighContrast, ID_OPTIONS_HIGH_CONTRAST),
+                ] {
+                    let check = if style.is_set(option) {
+                        MF_CHECKED
+                    } else {
+                        MF_UNCHECKED
+                    };
+                    let _ = CheckMenuItem(game, id, (MF_BYCOMMAND | check).0);
+                }
+                let _ = DrawMenuBar(hwnd);
+            }
+        }
+    }
+}
+
+fn update_record_plays_menu(hwnd: HWND, enabled: bool) {
+    unsafe {
+        let top = GetMenu(hwnd);
+        if !top.0.is_null() {
+            let game = GetSubMenu(top, 0);
+            if !game.0.is_null() {
+                let check = if enabled { MF_CHECKED } else { MF_UNCHECKED };
+                let _ = CheckMenuItem(game, ID_OPTIONS_RECORD_PLAYS, (MF_BYCOMMAND | check).0);
+                let _ = DrawMenuBar(hwnd);
+            }
+        }
+    }
+}
+
+fn update_jack_of_diamonds_menu(hwnd: HWND, enabled: bool) {
+    unsafe {
+        let top = GetMenu(hwnd);
+        if !top.0.is_null() {
+            let game = GetSubMenu(top, 0);
+            if !game.0.is_null() {
+                let check = if enabled { MF_CHECKED } else { MF_UNCHECKED };
+                let _ = CheckMenuItem(game, ID_OPTIONS_JACK_OF_DIAMONDS, (MF_BYCOMMAND | check).0);
+                let _ = DrawMenuBar(hwnd);
+            }
+        }
+    }
+}
+
+/// A recorder for a new controller, when the player opted in.
+fn human_recorder(enabled: bool) -> Option<HumanRecorder> {
+    enabled.then(|| HumanRecorder::new(recording_path()))
+}
+
+/// Offers the game saved when the window last closed. The save is removed
+/// once answered; the game on the table is saved again at the next close.
+fn offer_resume(hwnd: HWND) {
+    let path = saved_game_path();
+    if !path.exists() {
+        return;
+    }
+    let answer = unsafe {
+        MessageBoxW(
+            Some(hwnd),
+            w!("Resume the game you left unfinished?"),
+            w!("Resume Game"),
+            MB_YESNO | MB_ICONQUESTION,
+        )
+    };
+    if answer == IDYES {
+        match load_game(&path) {
+            Ok(mut controller) => {
+                if let Some(cell) = state_cell(hwnd) {
+                    let mut state = cell.borrow_mut();
+                    state.cancel_thinking();
+                    controller.set_pacing(state.session.controller().pacing());
+                    controller.set_bot_difficulty(state.session.controller().bot_difficulty());
+                    controller.set_think_config(state.session.controller().think_config());
+                    controller.set_human_recorder(human_recorder(state.record_plays));
+                    state.session = GameSession::new(controller);
+                    state.passing_select.clear();
+                }
+                unsafe {
+                    let _ = InvalidateRect(Some(hwnd), None, true);
+                }
+            }
+            Err(err) => {
+                // Left in place: a save from a newer build still belongs to it.
+                let text =
+                    string_to_wide_z(&format!("The saved game could not be resumed.\n\n{err}"));
+                unsafe {
+                    MessageBoxW(
+                        Some(hwnd),
+                        PCWSTR(text.as_ptr()),
+                        w!("Resume Game"),
+                        MB_OK | MB_ICONERROR,
+                    );
+                }
+                return;
+            }
+        }
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+/// Saves the match for the next start; a finished one leaves no save.
+fn save_unfinished_game(controller: &GameController) {
+    let path = saved_game_path();
+    if controller.match_over() {
+        let _ = std::fs::remove_file(&path);
+    } else if let Err(err) = save_game(&path, controller) {
+        debug_out(
+            "mdhearts: ",
+            &format!("could not save the game to {}: {err}", path.display()),
+        );
+    }
+}
+
+/// Keeps the match's scored rounds for Game → Watch Replay.
+fn save_match_transcript(controller: &GameController) {
+    let rounds = controller.played_rounds();
+    if rounds.is_empty() {
+        // A game resumed mid-round has no record of it; keep the last one.
+        return;
+    }
+    let path = transcript_path();
+    if let Err(err) = save_transcript(&path, rounds) {
+        debug_out(
+            "mdhearts: ",
+            &format!("could not save the replay to {}: {err}", path.display()),
+        );
+    }
+}
+
+/// Game → Watch Replay: opens the last match's scored rounds over the table.
+fn open_replay(hwnd: HWND) {
+    let path = transcript_path();
+    if !path.exists() {
+        unsafe {
+            MessageBoxW(
+                Some(hwnd),
+                w!("There is no replay yet. One is kept once a round has been scored."),
+                w!("Watch Replay"),
+                MB_OK | MB_ICONINFORMATION,
+            );
+        }
+        return;
+    }
+    match load_replay(&path) {
+        Ok(replay) => {
+            if let Some(cell) = state_cell(hwnd) {
+                let mut state = cell.borrow_mut();
+                state.cancel_thinking();
+                state.replay = Some(ReplayView::new(replay));
+            }
+            unsafe {
+                let _ = InvalidateRect(Some(hwnd), None, true);
+            }
+        }
+        Err(err) => {
+            let text = string_to_wide_z(&format!("The replay could not be opened.\n\n{err}"));
+            unsafe {
+                MessageBoxW(
+                    Some(hwnd),
+                    PCWSTR(text.as_ptr()),
+                    w!("Watch Replay"),
+                    MB_OK | MB_ICONERROR,
+                );
+            }
+        }
+    }
+}
+
 unsafe extern "system" fn window_proc(
     hwnd: HWND,
     msg: u32,
@@ -2619,7 +4039,7 @@ unsafe extern "system" fn window_proc(
         WM_SETCURSOR => {
             if let Some(cell) = state_cell(hwnd) {
                 let state = cell.borrow();
-                let cursor = if state.thinking.is_some() {
+                let cursor = if state.thinking.is_some() || state.tasks.busy().is_some() {
                     state.cursor_wait
                 } else {
                     state.cursor_arrow
@@ -2637,6 +4057,15 @@ unsafe extern "system" fn window_proc(
                 debug_out("mdhearts: ", "WM_NCCREATE -> AppState::new OK");
                 let initial_dpi = unsafe { GetDpiForWindow(hwnd) };
                 state.set_dpi(DpiScale::uniform(initial_dpi));
+                let raw = hwnd.0 as usize;
+                state.tasks.set_notify(move || unsafe {
+                    let _ = PostMessageW(
+                        Some(HWND(raw as *mut c_void)),
+                        WM_APP_TASK_DONE,
+                        WPARAM(0),
+                        LPARAM(0),
+                    );
+                });
                 let boxed = Box::new(RefCell::new(state));
                 let ptr = Box::into_raw(boxed);
                 unsafe {
@@ -2647,7 +4076,7 @@ unsafe extern "system" fn window_proc(
                         windows::Win32::UI::WindowsAndMessaging::SetTimer(Some(hwnd), 1, 16, None);
                 }
                 if let Some(cell) = state_cell(hwnd) {
-                    let diff = cell.borrow().controller.bot_difficulty();
+                    let diff = cell.borrow().session.controller().bot_difficulty();
                     update_difficulty_menu(hwnd, diff);
                 }
                 LRESULT(1)
@@ -2702,18 +4131,51 @@ unsafe extern "system" fn window_proc(
             if let Some(cell) = state_cell(hwnd) {
                 let mut state = cell.borrow_mut();
                 let key = wparam.0 as u32;
+                if state.replay.is_some() {
+                    let difficulty = state.session.controller().bot_difficulty();
+                    let redraw = if key == VK_ESCAPE {
+                        state.replay = None;
+                        true
+                    } else {
+                        state
+                            .replay
+                            .as_mut()
+                            .is_some_and(|view| view.handle_key(key, difficulty))
+                    };
+                    if redraw {
+                        unsafe {
+                            let _ = InvalidateRect(Some(hwnd), None, true);
+                        }
+                    }
+                    return LRESULT(0);
+                }
                 if key == VK_F2 {
                     state.cancel_thinking();
-                    state.controller = GameController::new_with_seed(None, PlayerPosition::North);
+                    let mut controller = GameController::new_with_seed(None, PlayerPosition::North);
+                    controller.set_pacing(state.session.controller().pacing());
+                    let _ = controller.set_target_score(state.target_score);
+                    controller.set_jack_of_diamonds(state.jack_of_diamonds);
+                    controller.set_players(state.players.clone());
+                    controller.set_human_recorder(human_recorder(state.record_plays));
+                    state.session = GameSession::new(controller);
                     unsafe {
                         let _ = InvalidateRect(Some(hwnd), None, true);
                     }
                 } else if key == VK_ESCAPE {
-                    if !state.passing_select.is_empty() {
+                    if state.tasks.cancel().is_some() {
+                        while state.tasks.poll().is_some() {}
+                        unsafe {
+                            let _ = InvalidateRect(Some(hwnd), None, true);
+                        }
+                    } else if !state.passing_select.is_empty() {
                         state.passing_select.clear();
                         unsafe {
                             let _ = InvalidateRect(Some(hwnd), None, true);
                         }
+                    } else if state.dismiss_briefing() {
+                        unsafe {
+                            let _ = InvalidateRect(Some(hwnd), None, true);
+                        }
                     }
                 } else if key == VK_RETURN {
                     state.try_start_pass_animation(hwnd);
@@ -2722,14 +4184,18 @@ unsafe extern "system" fn window_proc(
             LRESULT(0)
         }
         WM_LBUTTONDOWN => {
-            let x = (lparam.0 & 0xFFFF) as i16 as i32;
-            let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as i32;
             if let Some(cell) = state_cell(hwnd) {
                 let mut state = cell.borrow_mut();
-                if state.controller.match_over() {
+                if state.session.controller().match_over() || state.replay.is_some() {
+                    return LRESULT(0);
+                }
+                if let Some(coll) = state.collect.as_mut() {
+                    if coll.clicked_at_ms.is_none() {
+                        coll.clicked_at_ms = Some(coll.start.elapsed().as_millis() as u64);
+                    }
                     return LRESULT(0);
                 }
-                if state.collect.is_some() || state.anim.is_some() || state.pass.is_some() {
+                if state.anim.is_some() || state.pass.is_some() {
                     return LRESULT(0);
                 }
                 // If awaiting pass acknowledgement, a click anywhere will accept and continue
@@ -2740,132 +4206,87 @@ unsafe extern "system" fn window_proc(
                     }
                     return LRESULT(0);
                 }
-                let size = client_size(hwnd);
-                let layout = state.layout_size(size);
-                let south_hand = state.controller.hand(PlayerPosition::South);
-                let rects = compute_south_hand_rects(layout, south_hand.len());
-                let (scale_x, scale_y) = state.inv_dpi_scales();
-                let xf = x as f32 * scale_x;
-                let yf = y as f32 * scale_y;
-                let mut consumed = false;
-                // Iterate right-to-left so the visually topmost card wins in overlaps
-                for i in (0..rects.len()).rev() {
-                    let mut r = rects[i];
-                    // During passing, selected cards are lifted; reflect that in hit testing
-                    if state.controller.in_passing_phase()
-                        && let Some(card) = south_hand.get(i)
-                        && state.passing_select.iter().any(|c| c == card)
-                    {
-                        let h = r.bottom - r.top;
-                        let dy = h * 0.18;
-                        r.top -= dy;
-                        r.bottom -= dy;
-                    }
-                    if xf >= r.left && xf <= r.right && yf >= r.top && yf <= r.bottom {
-                        if state.controller.in_passing_phase() {
-                            if let Some(card) = south_hand.get(i).copied() {
-                                if let Some(pos) =
-                                    state.passing_select.iter().position(|c| *c == card)
-                                {
-                                    state.passing_select.remove(pos);
-                                } else if state.passing_select.len() < 3 {
-                                    state.passing_select.push(card);
-                                }
-                                unsafe {
-                                    let _ = InvalidateRect(Some(hwnd), None, true);
-                                }
-                            }
-                        } else {
-                            let legal = state.controller.legal_moves_set(PlayerPosition::South);
-                            if let Some(card) = south_hand.get(i).copied()
-                                && legal.contains(&card)
-                            {
-                                debug_out("mdhearts: ", &format!("South plays {}", card));
-                                let from = r;
-                                let to = compute_trick_rect_for(layout, PlayerPosition::South);
-                                let _ = state.controller.play(PlayerPosition::South, card);
-                                state.anim = Some(PlayAnim {
-                                    seat: PlayerPosition::South,
-                                    card,
-                                    from,
-                                    to,
-                                    start: std::time::Instant::now(),
-                                    dur_ms: 260,
-                                });
-                                unsafe {
-                                    let _ = InvalidateRect(Some(hwnd), None, true);
-                                }
-                            }
-                        }
-                        consumed = true;
-                        break;
-                    }
-                }
-                // If not clicking on a card: clicking central table commits pass when 3 selected
-                if !consumed
-                    && state.controller.in_passing_phase()
-                    && state.passing_select.len() == 3
-                    && state.pass.is_none()
-                {
-                    let layout = state.layout_size(size);
-                    let width = layout.width;
-                    let height = layout.height;
-                    let table = D2D_RECT_F {
-                        left: width * 0.08,
-                        top: height * 0.12,
-                        right: width * 0.92,
-                        bottom: height * 0.88,
-                    };
-                    if xf >= table.left
-                        && xf <= table.right
-                        && yf >= table.top
-                        && yf <= table.bottom
-                    {
-                        state.try_start_pass_animation(hwnd);
-                    }
-                }
             }
-            LRESULT(0)
+            let (x, y) = lparam_point(lparam);
+            handle_pointer(
+                hwnd,
+                InputEvent::button(Gesture::Click, Target::Elsewhere),
+                x,
+                y,
+            )
+        }
+        WM_LBUTTONDBLCLK | WM_RBUTTONDOWN | WM_MBUTTONDOWN => {
+            let gesture = match msg {
+                WM_LBUTTONDBLCLK => Gesture::DoubleClick,
+                WM_RBUTTONDOWN => Gesture::RightClick,
+                _ => Gesture::MiddleClick,
+            };
+            let (x, y) = lparam_point(lparam);
+            handle_pointer(hwnd, InputEvent::button(gesture, Target::Elsewhere), x, y)
+        }
+        WM_MOUSEWHEEL => {
+            let delta = ((wparam.0 >> 16) & 0xFFFF) as i16 as i32;
+            // Wheel messages carry screen coordinates.
+            let (x, y) = lparam_point(lparam);
+            let mut point = POINT { x, y };
+            unsafe {
+                let _ = ScreenToClient(hwnd, &mut point);
+            }
+            let event = InputEvent::wheel(delta / WHEEL_DELTA, Target::Elsewhere);
+            handle_pointer(hwnd, event, point.x, point.y)
         }
         WM_TIMER => {
+            let mut review_text: Option<(String, bool)> = None;
+            let mut offer_finish = false;
             if let Some(cell) = state_cell(hwnd) {
                 let mut state = cell.borrow_mut();
+                if state.replay.is_some() {
+                    // The game waits, bots included, while the replay is up.
+                    return LRESULT(0);
+                }
                 debug_out(
                     "mdhearts: ",
                     &format!(
                         "TICK anim={} collect={} passing={}",
                         state.anim.is_some(),
                         state.collect.is_some(),
-                        state.controller.in_passing_phase()
+                        state.session.controller().in_passing_phase()
                     ),
                 );
                 // First, if a trick just completed and no animation is running, start the collect
                 if state.anim.is_none()
                     && state.collect.is_none()
-                    && let Some(summary) = state.controller.take_last_trick_summary()
+                    && let Ok(events) = state.session.apply(SessionCommand::AdvanceTrick)
+                    && let Some(SessionEvent::TrickCollected { winner, plays }) =
+                        events.into_iter().next()
                 {
-                    debug_out(
-                        "mdhearts: ",
-                        &format!("Collect start winner {:?}", summary.winner),
-                    );
+                    debug_out("mdhearts: ", &format!("Collect start winner {:?}", winner));
                     state.collect = Some(CollectAnim {
-                        winner: summary.winner,
-                        cards: summary.plays,
+                        winner,
+                        cards: plays,
                         start: std::time::Instant::now(),
-                        delay_ms: 350,
-                        dur_ms: 320,
+                        clicked_at_ms: None,
                     });
                 }
                 state.poll_thinking(hwnd);
+                if state.refresh_eval_bar() {
+                    unsafe {
+                        let _ = InvalidateRect(Some(hwnd), None, false);
+                    }
+                }
                 // Only advance AI if not passing, no play/collect animation, and not awaiting pass acknowledgement
                 if state.anim.is_none()
                     && state.collect.is_none()
                     && state.pass.is_none()
                     && state.await_pass_ack.is_none()
-                    && !state.controller.in_passing_phase()
+                    && !state.session.controller().in_passing_phase()
                     && state.thinking.is_none()
                 {
-                    state.start_thinking_if_needed(hwnd);
+                    if state.session.controller().is_round_decided() {
+                        offer_finish = state.advance_decided_round(hwnd);
+                    } else {
+                        state.start_thinking_if_needed(hwnd);
+                    }
                 }
                 // After collect completes, finish the round if we just played the 13th trick
                 if state.anim.is_none()
@@ -2873,39 +4294,70 @@ unsafe extern "system" fn window_proc(
                     && state.await_pass_ack.is_none()
                     && state.pass.is_none()
                     && state.thinking.is_none()
-                    && !state.controller.match_over()
+                    && !state.session.controller().match_over()
                 {
                     debug_out("mdhearts: ", "Collect end / ready check");
-                    if let Some(winner) = state.controller.finish_round_if_ready() {
-                        debug_out(
-                            "mdhearts: ",
-                            &format!("Match over detected; winner {:?}", winner),
-                        );
-                        state.cancel_thinking();
-                        state.timeout_banner = None;
+                    if let Ok(events) = state.session.apply(SessionCommand::FinishRound) {
+                        save_match_transcript(state.session.controller());
+                        if let Some(&SessionEvent::MatchOver { winner }) = events.last() {
+                            debug_out(
+                                "mdhearts: ",
+                                &format!("Match over detected; winner {:?}", winner),
+                            );
+                            state.cancel_thinking();
+                            state.timeout_banner = None;
+                        }
+                    }
+                    if !state.review_open
+                        && let Some(review) = state.session.controller().review()
+                    {
+                        review_text = Some((review.summary_text(), !review.flags().is_empty()));
+                        state.review_open = true;
                     }
                 }
                 unsafe {
                     let _ = InvalidateRect(Some(hwnd), None, true);
                 }
             }
+            if offer_finish {
+                offer_auto_finish(hwnd);
+            }
+            if let Some((text, has_flags)) = review_text {
+                show_round_review(hwnd, text, has_flags);
+            }
             LRESULT(0)
         }
         WM_COMMAND => {
             let id = (wparam.0 & 0xFFFF) as u32;
             let mut card_back_request: Option<CardBackId> = None;
+            let mut players_request: Option<PlayerRoster> = None;
             let mut difficulty_request: Option<crate::bot::BotDifficulty> = None;
             let mut think_limit_request: Option<Duration> = None;
-            let mut show_about = false;
-            let mut show_rules = false;
+            let mut fast_mode_request: Option<PacingConfig> = None;
+            let mut score_display_request: Option<ScoreDisplay> = None;
+            let mut assist_level_request: Option<AssistLevel> = None;
+            let mut card_style_request: Option<CardStyle> = None;
+            let mut target_request: Option<u32> = None;
+            let mut record_plays_request: Option<bool> = None;
+            let mut jack_request: Option<bool> = None;
+            let mut delete_recording_request = false;
+            let mut replay_request = false;
+            let mut about_seed: Option<String> = None;
+            let mut show_rules = None;
             if let Some(cell) = state_cell(hwnd) {
                 {
                     let mut state = cell.borrow_mut();
                     match id {
                         ID_GAME_NEW => {
                             state.cancel_thinking();
-                            state.controller =
+                            let mut controller =
                                 GameController::new_with_seed(None, PlayerPosition::North);
+                            controller.set_pacing(state.session.controller().pacing());
+                            let _ = controller.set_target_score(state.target_score);
+                            controller.set_jack_of_diamonds(state.jack_of_diamonds);
+                            controller.set_players(state.players.clone());
+                            controller.set_human_recorder(human_recorder(state.record_plays));
+                            state.session = GameSession::new(controller);
                             state.passing_select.clear();
                             unsafe {
                                 let _ = InvalidateRect(Some(hwnd), None, true);
@@ -2913,11 +4365,15 @@ unsafe extern "system" fn window_proc(
                         }
                         ID_GAME_RESTART => {
                             state.cancel_thinking();
-                            state.controller.restart_round();
+                            let _ = state.session.apply(SessionCommand::RestartRound);
+                            state.auto_finish = None;
                             unsafe {
                                 let _ = InvalidateRect(Some(hwnd), None, true);
                             }
                         }
+                        ID_GAME_REPLAY => {
+                            replay_request = true;
+                        }
                         ID_GAME_EXIT => {
                             state.cancel_thinking();
                             unsafe { PostQuitMessage(0) }
@@ -2925,6 +4381,9 @@ unsafe extern "system" fn window_proc(
                         ID_OPTIONS_CARD_BACK => {
                             card_back_request = Some(state.card_back);
                         }
+                        ID_OPTIONS_PLAYERS => {
+                            players_request = Some(state.players.clone());
+                        }
                         ID_OPTIONS_DIFFICULTY_EASY => {
                             difficulty_request = Some(crate::bot::BotDifficulty::EasyLegacy);
                         }
@@ -2953,21 +4412,82 @@ unsafe extern "system" fn window_proc(
                             state.cancel_thinking();
                             think_limit_request = Some(Duration::ZERO);
                         }
-                        ID_HELP_RULES => {
-                            show_rules = true;
-                        }
-                        ID_HELP_ABOUT => {
-                            show_about = true;
+                        ID_OPTIONS_FAST_MODE => {
+                            let pacing = if state.session.controller().pacing().is_fast() {
+                                PacingConfig::default()
+                            } else {
+                                PacingConfig::fast()
+                            };
+                            state.session.controller_mut().set_pacing(pacing);
+                            fast_mode_request = Some(pacing);
                         }
-                        _ => {}
-                    }
-                }
-                if let Some(current) = card_back_request {
-                    match CardBackDialog::run(hwnd, current) {
-                        Ok(Some(choice)) => {
-                            let mut state = cell.borrow_mut();
-                            state.set_card_back(choice);
-                            save_card_back(choice);
+                        ID_OPTIONS_SCORE_DETAILED => {
+                            state.score_display = state.score_display.toggled();
+                            score_display_request = Some(state.score_display);
+                            unsafe {
+                                let _ = InvalidateRect(Some(hwnd), None, true);
+                            }
+                        }
+                        ID_OPTIONS_ASSIST_COUNTING => {
+                            state.assist_level = state.assist_level.toggled();
+                            assist_level_request = Some(state.assist_level);
+                            unsafe {
+                                let _ = InvalidateRect(Some(hwnd), None, true);
+                            }
+                        }
+                        ID_OPTIONS_FOUR_COLOR
+                        | ID_OPTIONS_SUIT_LETTERS
+                        | ID_OPTIONS_HIGH_CONTRAST => {
+                            let option = match id {
+                                ID_OPTIONS_FOUR_COLOR => CardStyleOption::FourColor,
+                                ID_OPTIONS_SUIT_LETTERS => CardStyleOption::SuitLetters,
+                                _ => CardStyleOption::HighContrast,
+                            };
+                            state.card_style = state.card_style.toggled(option);
+                            card_style_request = Some(state.card_style);
+                            unsafe {
+                                let _ = InvalidateRect(Some(hwnd), None, true);
+                            }
+                        }
+                        ID_OPTIONS_TARGET_50 => {
+                            target_request = Some(50);
+                        }
+                        ID_OPTIONS_TARGET_75 => {
+                            target_request = Some(75);
+                        }
+                        ID_OPTIONS_TARGET_100 => {
+                            target_request = Some(100);
+                        }
+                        ID_OPTIONS_TARGET_150 => {
+                            target_request = Some(150);
+                        }
+                        ID_OPTIONS_RECORD_PLAYS => {
+                            state.record_plays = !state.record_plays;
+                            let recorder = human_recorder(state.record_plays);
+                            state.session.controller_mut().set_human_recorder(recorder);
+                            record_plays_request = Some(state.record_plays);
+                        }
+                        ID_OPTIONS_DELETE_RECORDING => {
+                            delete_recording_request = true;
+                        }
+                        ID_OPTIONS_JACK_OF_DIAMONDS => {
+                            jack_request = Some(!state.jack_of_diamonds);
+                        }
+                        ID_HELP_RULES => {
+                            show_rules = Some(*state.session.controller().rules());
+                        }
+                        ID_HELP_ABOUT => {
+                            about_seed = Some(state.session.controller().seed_summary());
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(current) = card_back_request {
+                    match CardBackDialog::run(hwnd, current) {
+                        Ok(Some(choice)) => {
+                            let mut state = cell.borrow_mut();
+                            state.set_card_back(choice);
+                            save_card_back(choice);
                             unsafe {
                                 let _ = InvalidateRect(Some(hwnd), None, true);
                             }
@@ -2978,11 +4498,32 @@ unsafe extern "system" fn window_proc(
                         }
                     }
                 }
+                if let Some(current) = players_request {
+                    match PlayersDialog::run(hwnd, &current) {
+                        Ok(Some(players)) => {
+                            {
+                                let mut state = cell.borrow_mut();
+                                state.session.controller_mut().set_players(players.clone());
+                                state.players = players.clone();
+                            }
+                            if let Err(err) = save_players(&players_path(), &players) {
+                                debug_out("mdhearts: ", &format!("Players not saved: {err}"));
+                            }
+                            unsafe {
+                                let _ = InvalidateRect(Some(hwnd), None, true);
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            debug_out("mdhearts: ", &format!("Players dialog error: {:?}", err));
+                        }
+                    }
+                }
                 if let Some(diff) = difficulty_request {
                     {
                         let mut state = cell.borrow_mut();
                         state.cancel_thinking();
-                        state.controller.set_bot_difficulty(diff);
+                        state.session.set_bot_difficulty(diff);
                     }
                     save_bot_difficulty(diff);
                     update_difficulty_menu(hwnd, diff);
@@ -2990,26 +4531,121 @@ unsafe extern "system" fn window_proc(
                 if let Some(limit) = think_limit_request {
                     let config = {
                         let mut state = cell.borrow_mut();
-                        let mut config = state.controller.think_config();
+                        let mut config = state.session.controller().think_config();
                         config.max_duration = limit;
-                        state.controller.set_think_config(config);
+                        state.session.controller_mut().set_think_config(config);
                         config
                     };
                     save_think_limit(limit);
                     update_think_limit_menu(hwnd, config);
                 }
+                if let Some(pacing) = fast_mode_request {
+                    update_fast_mode_menu(hwnd, pacing);
+                }
+                if let Some(display) = score_display_request {
+                    save_score_display(display);
+                    update_score_display_menu(hwnd, display);
+                }
+                if let Some(level) = assist_level_request {
+                    save_assist_level(level);
+                    update_assist_menu(hwnd, level);
+                }
+                if let Some(style) = card_style_request {
+                    save_card_style(style);
+                    update_card_style_menu(hwnd, style);
+                }
+                if let Some(target) = target_request {
+                    {
+                        let mut state = cell.borrow_mut();
+                        state.target_score = target;
+                        // A match with points on the board keeps its target;
+                        // the new one starts with the next game.
+                        if state.session.controller().standings() == [0; 4] {
+                            let _ = state.session.controller_mut().set_target_score(target);
+                        }
+                        unsafe {
+                            let _ = InvalidateRect(Some(hwnd), None, true);
+                        }
+                    }
+                    save_match_target(target);
+                    update_match_target_menu(hwnd, target);
+                }
+                if let Some(enabled) = jack_request {
+                    {
+                        let mut state = cell.borrow_mut();
+                        state.jack_of_diamonds = enabled;
+                        // Like the target, the rule changes with the next
+                        // game once a match has points on the board.
+                        if state.session.controller().standings() == [0; 4] {
+                            state.session.controller_mut().set_jack_of_diamonds(enabled);
+                        }
+                    }
+                    save_jack_of_diamonds(enabled);
+                    update_jack_of_diamonds_menu(hwnd, enabled);
+                }
+                if let Some(enabled) = record_plays_request {
+                    save_record_plays(enabled);
+                    update_record_plays_menu(hwnd, enabled);
+                    if enabled {
+                        let note = string_to_wide_z(PRIVACY_NOTE);
+                        unsafe {
+                            MessageBoxW(
+                                Some(hwnd),
+                                PCWSTR(note.as_ptr()),
+                                w!("Record My Plays"),
+                                MB_OK | MB_ICONINFORMATION,
+                            );
+                        }
+                    }
+                }
+                if delete_recording_request {
+                    delete_recorded_plays(hwnd);
+                }
+            }
+            if let Some(rules) = show_rules {
+                show_rules_dialog(hwnd, rules);
+            }
+            if replay_request {
+                open_replay(hwnd);
+            }
+            if let Some(seed_line) = about_seed {
+                show_about_dialog(hwnd, seed_line);
+            }
+            LRESULT(0)
+        }
+        WM_APP_TASK_DONE => {
+            let mut finished = Vec::new();
+            if let Some(cell) = state_cell(hwnd) {
+                let mut state = cell.borrow_mut();
+                while let Some(event) = state.tasks.poll() {
+                    if let TaskEvent::Finished { value, .. } = event {
+                        finished.push(value);
+                    }
+                }
             }
-            if show_rules {
-                show_rules_dialog(hwnd);
+            unsafe {
+                let _ = InvalidateRect(Some(hwnd), None, true);
             }
-            if show_about {
-                show_about_dialog(hwnd);
+            // The borrow is released: the box runs its own message loop.
+            for text in finished {
+                let text_w = string_to_wide_z(&text);
+                unsafe {
+                    MessageBoxW(
+                        Some(hwnd),
+                        PCWSTR(text_w.as_ptr()),
+                        w!("Round Review - Alternatives"),
+                        MB_OK | MB_ICONINFORMATION,
+                    );
+                }
             }
             LRESULT(0)
         }
         WM_DESTROY => {
             if let Some(cell) = state_cell(hwnd) {
-                cell.borrow_mut().cancel_thinking();
+                let mut state = cell.borrow_mut();
+                state.cancel_thinking();
+                state.tasks.cancel();
+                save_unfinished_game(state.session.controller());
             }
             save_window_placement(hwnd);
             unsafe {
@@ -3076,20 +4712,19 @@ fn debug_out(prefix: &str, msg: &str) {
     }
 }
 
-fn show_rules_dialog(owner: HWND) {
-    let text = "Hearts is played to avoid taking penalty cards.\r
-\r
-Basics:\r
-- Pass three cards at the start of each hand (Left, Right, Across, Hold rotation).\r
-- The player holding the Two of Clubs must lead the first trick with that card.\r
-- Players must follow suit when able; otherwise they may discard any card.\r
-- No Hearts or the Queen of Spades may be led until Hearts are broken (a Heart or the Queen of Spades is discarded).\r
-- Each Heart scores 1 point; the Queen of Spades scores 13 points.\r
-- Capturing all penalty cards (26 points) shoots the moon: everyone else gains 26 points or your own score drops by 26, depending on your house rule.\r
-- The hand ends after 13 tricks; when a score reaches 100 the match ends and the lowest total wins.\r
-\r
-Tip: Watch the status bar for passing direction and the current trick leader.";
-    let body = string_to_wide_z(text);
+fn show_rules_dialog(owner: HWND, rules: RuleSet) {
+    let rules_text = RulesText::new(rules);
+    let mut text = format!(
+        "Hearts is played to avoid taking penalty cards.\r\n\r\nRules (variant: {}):\r\n",
+        rules_text.variant()
+    );
+    for rule in rules_text.help() {
+        text.push_str(&format!("- {rule}\r\n"));
+    }
+    text.push_str(
+        "\r\nTip: Watch the status bar for passing direction and the current trick leader.",
+    );
+    let body = string_to_wide_z(&text);
     unsafe {
         MessageBoxW(
             Some(owner),
@@ -3100,8 +4735,180 @@ Tip: Watch the status bar for passing direction and the current trick leader.";
     }
 }
 
-fn show_about_dialog(owner: HWND) {
-    if let Err(err) = AboutDialog::show(owner) {
+fn lparam_point(lparam: LPARAM) -> (i32, i32) {
+    let x = (lparam.0 & 0xFFFF) as i16 as i32;
+    let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as i32;
+    (x, y)
+}
+
+/// Resolves `event` at client pixel `(x, y)` through the input mapper and
+/// performs the action. The event's target is filled in from the hit test.
+fn handle_pointer(hwnd: HWND, event: InputEvent, x: i32, y: i32) -> LRESULT {
+    let counts = {
+        let Some(cell) = state_cell(hwnd) else {
+            return LRESULT(0);
+        };
+        let mut state = cell.borrow_mut();
+        let (target, card_rect) = state.pointer_target(hwnd, x, y);
+        let event = InputEvent { target, ..event };
+        let phase = state.input_phase();
+        match state.input.resolve(event, phase) {
+            Some(action) => state.perform_input(hwnd, action, card_rect),
+            None => None,
+        }
+    };
+    // The message box runs its own loop, so the state borrow is released first.
+    if let Some((suit, text)) = counts {
+        show_suit_counts(hwnd, suit, text);
+    }
+    LRESULT(0)
+}
+
+fn show_suit_counts(owner: HWND, suit: Suit, text: String) {
+    let body = string_to_wide_z(&text.replace('\n', "\r\n"));
+    let title = string_to_wide_z(&format!("{suit:?} - Card Count"));
+    unsafe {
+        MessageBoxW(
+            Some(owner),
+            PCWSTR(body.as_ptr()),
+            PCWSTR(title.as_ptr()),
+            MB_OK | MB_ICONINFORMATION,
+        );
+    }
+}
+
+/// Offered once a round when every penalty card has been taken; "Yes" plays
+/// the remaining tricks with the lowest legal cards, South's included.
+/// Game → Delete Recorded Plays: removes the recording after asking.
+fn delete_recorded_plays(owner: HWND) {
+    let path = recording_path();
+    let question = string_to_wide_z(&format!(
+        "Delete every recorded play in {}?",
+        path.display()
+    ));
+    let answer = unsafe {
+        MessageBoxW(
+            Some(owner),
+            PCWSTR(question.as_ptr()),
+            w!("Delete Recorded Plays"),
+            MB_YESNO | MB_ICONQUESTION,
+        )
+    };
+    if answer != IDYES {
+        return;
+    }
+    if let Some(cell) = state_cell(owner) {
+        let mut state = cell.borrow_mut();
+        let recorder = human_recorder(state.record_plays);
+        state.session.controller_mut().set_human_recorder(recorder);
+    }
+    let message = match delete_recording(&path) {
+        Ok(true) => "The recorded plays were deleted.".to_string(),
+        Ok(false) => "There were no recorded plays to delete.".to_string(),
+        Err(err) => format!("The recorded plays could not be deleted: {err}"),
+    };
+    let message = string_to_wide_z(&message);
+    unsafe {
+        MessageBoxW(
+            Some(owner),
+            PCWSTR(message.as_ptr()),
+            w!("Delete Recorded Plays"),
+            MB_OK | MB_ICONINFORMATION,
+        );
+    }
+}
+
+fn offer_auto_finish(owner: HWND) {
+    let answer = unsafe {
+        MessageBoxW(
+            Some(owner),
+            w!("All the points have been taken.\r\nPlay out the rest of the hand automatically?"),
+            w!("Auto-finish"),
+            MB_YESNO | MB_ICONQUESTION,
+        )
+    };
+    if answer == IDYES
+        && let Some(cell) = state_cell(owner)
+    {
+        let mut state = cell.borrow_mut();
+        if let Some((_, accepted)) = state.auto_finish.as_mut() {
+            *accepted = true;
+        }
+    }
+}
+
+/// Ghost-hands review after a round. With flagged plays, "Yes" simulates the
+/// advised card for each one on a worker; the answer arrives as
+/// `WM_APP_TASK_DONE` and Esc cancels it. The round record is dropped when the
+/// box closes.
+fn show_round_review(owner: HWND, text: String, has_flags: bool) {
+    let mut body = text.replace('\n', "\r\n");
+    let style = if has_flags {
+        body.push_str("\r\nSimulate the advised alternatives?");
+        MB_YESNO | MB_ICONINFORMATION
+    } else {
+        MB_OK | MB_ICONINFORMATION
+    };
+    let body_w = string_to_wide_z(&body);
+    let answer = unsafe {
+        MessageBoxW(
+            Some(owner),
+            PCWSTR(body_w.as_ptr()),
+            w!("Round Review"),
+            style,
+        )
+    };
+    let Some(cell) = state_cell(owner) else {
+        return;
+    };
+    let mut state = cell.borrow_mut();
+    if answer == IDYES
+        && let Some(review) = state.session.controller().review().cloned()
+    {
+        state
+            .tasks
+            .spawn("Simulating the advised alternatives", move |token| {
+                simulate_alternatives(&review, token)
+            });
+    }
+    state.session.controller_mut().dismiss_review();
+    state.review_open = false;
+    drop(state);
+    unsafe {
+        let _ = InvalidateRect(Some(owner), None, true);
+    }
+}
+
+/// One line per flagged play with the points the advised card would have
+/// left the seat; stops early once `token` is cancelled.
+fn simulate_alternatives(review: &RoundReview, token: &CancelToken) -> String {
+    let seat = review.seat();
+    let actual = review.actual_penalties()[seat.index()];
+    let mut lines = format!("You took {actual} points this round.\r\n\r\n");
+    for flag in review.flags() {
+        if token.is_cancelled() {
+            break;
+        }
+        let line = match review.simulate_advised(flag) {
+            Ok(penalties) => format!(
+                "Trick {}: {} instead of {} -> {} points\r\n",
+                flag.trick_number(),
+                flag.best,
+                flag.played,
+                penalties[seat.index()]
+            ),
+            Err(err) => format!(
+                "Trick {}: simulation failed ({err})\r\n",
+                flag.trick_number()
+            ),
+        };
+        lines.push_str(&line);
+    }
+    lines
+}
+
+fn show_about_dialog(owner: HWND, seed_line: String) {
+    if let Err(err) = AboutDialog::show(owner, seed_line) {
         debug_out("mdhearts: ", &format!("About dialog error: {:?}", err));
     }
     unsafe {
@@ -3114,8 +4921,8 @@ fn show_about_dialog(owner: HWND) {
 struct AboutDialog;
 
 impl AboutDialog {
-    fn show(owner: HWND) -> Result<()> {
-        AboutDialogState::launch(owner)
+    fn show(owner: HWND, seed_line: String) -> Result<()> {
+        AboutDialogState::launch(owner, seed_line)
     }
 }
 
@@ -3130,6 +4937,7 @@ struct AboutDialogState {
     atlas: AtlasMeta,
     version: &'static str,
     build_date: &'static str,
+    seed_line: String,
     dpi: DpiScale,
 }
 
@@ -3225,7 +5033,7 @@ impl AboutDialogState {
         }
     }
 
-    fn launch(owner: HWND) -> Result<()> {
+    fn launch(owner: HWND, seed_line: String) -> Result<()> {
         static CLASS: std::sync::Once = std::sync::Once::new();
         let class_name = w!("MDHEARTS_ABOUT");
         CLASS.call_once(|| {
@@ -3255,7 +5063,7 @@ impl AboutDialogState {
             }
         });
 
-        let state = RefCell::new(Self::new()?);
+        let state = RefCell::new(Self::new(seed_line)?);
         let boxed = Box::new(state);
         let ptr = Box::into_raw(boxed);
 
@@ -3268,7 +5076,7 @@ impl AboutDialogState {
             }
         };
         let base_width = 580;
-        let base_height = 360;
+        let base_height = 380;
         let scaled_width = (base_width * dpi_scale.x as i32 + 48) / 96;
         let scaled_height = (base_height * dpi_scale.y as i32 + 48) / 96;
         let mut window_rect = RECT {
@@ -3332,7 +5140,7 @@ impl AboutDialogState {
         Ok(())
     }
 
-    fn new() -> Result<Self> {
+    fn new(seed_line: String) -> Result<Self> {
         let factory: ID2D1Factory = unsafe {
             D2D1CreateFactory::<ID2D1Factory>(
                 D2D1_FACTORY_TYPE_MULTI_THREADED,
@@ -3384,6 +5192,7 @@ impl AboutDialogState {
             atlas,
             version: env!("CARGO_PKG_VERSION"),
             build_date: option_env!("MDH_BUILD_DATE").unwrap_or("unknown build"),
+            seed_line,
             dpi: DpiScale::uniform(initial_dpi),
         };
         this.apply_text_dpi();
@@ -3628,6 +5437,7 @@ impl AboutDialogState {
 
             let body_text = format!(
                 "Build {build}
+{seed}
 A handcrafted take on the classic card game Hearts.
 
 Tips
@@ -3637,6 +5447,7 @@ Tips
 
 Built with Rust, Direct2D, and plenty of card shuffling.",
                 build = self.build_date,
+                seed = self.seed_line,
             );
             let body = string_to_wide(&body_text);
             let body_rect = D2D_RECT_F {
@@ -3878,16 +5689,376 @@ fn save_card_back(id: CardBackId) {
             PCWSTR(value_name.as_ptr()),
             Some(0),
             REG_BINARY,
-            Some(&data),
+            Some(&data),
+        );
+        let _ = RegCloseKey(hkey);
+    }
+}
+
+fn load_card_back() -> Option<CardBackId> {
+    unsafe {
+        let subkey = string_to_wide_z(REG_SUBKEY_APP);
+        let value = string_to_wide_z(REG_VALUE_CARD_BACK);
+        let mut raw: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        if RegGetValueW(
+            HKEY_CURRENT_USER,
+            PCWSTR(subkey.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            RRF_RT_REG_BINARY,
+            None,
+            Some((&mut raw as *mut u32).cast()),
+            Some(&mut size),
+        )
+        .is_err()
+            || size < std::mem::size_of::<u32>() as u32
+        {
+            return None;
+        }
+        CardBackId::from_u32(raw)
+    }
+}
+
+fn save_think_limit(limit: Duration) {
+    unsafe {
+        let key_path = string_to_wide_z(REG_SUBKEY_APP);
+        let mut hkey = HKEY::default();
+        if RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key_path.as_ptr()),
+            Some(0),
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_SET_VALUE | KEY_QUERY_VALUE,
+            None,
+            &mut hkey,
+            None,
+        )
+        .is_err()
+        {
+            return;
+        }
+        let value_name = string_to_wide_z(REG_VALUE_THINK_LIMIT);
+        let millis = if limit.is_zero() {
+            0u32
+        } else {
+            limit.as_millis().min(u32::MAX as u128) as u32
+        };
+        let bytes = millis.to_le_bytes();
+        let _ = RegSetValueExW(
+            hkey,
+            PCWSTR(value_name.as_ptr()),
+            Some(0),
+            REG_BINARY,
+            Some(&bytes),
+        );
+        let _ = RegCloseKey(hkey);
+    }
+}
+
+fn save_score_display(display: ScoreDisplay) {
+    unsafe {
+        let key_path = string_to_wide_z(REG_SUBKEY_APP);
+        let mut hkey = HKEY::default();
+        if RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key_path.as_ptr()),
+            Some(0),
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_SET_VALUE | KEY_QUERY_VALUE,
+            None,
+            &mut hkey,
+            None,
+        )
+        .is_err()
+        {
+            return;
+        }
+        let value_name = string_to_wide_z(REG_VALUE_SCORE_DISPLAY);
+        let bytes = display.as_u32().to_le_bytes();
+        let _ = RegSetValueExW(
+            hkey,
+            PCWSTR(value_name.as_ptr()),
+            Some(0),
+            REG_BINARY,
+            Some(&bytes),
+        );
+        let _ = RegCloseKey(hkey);
+    }
+}
+
+fn load_score_display() -> Option<ScoreDisplay> {
+    unsafe {
+        let subkey = string_to_wide_z(REG_SUBKEY_APP);
+        let value = string_to_wide_z(REG_VALUE_SCORE_DISPLAY);
+        let mut raw: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        if RegGetValueW(
+            HKEY_CURRENT_USER,
+            PCWSTR(subkey.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            RRF_RT_REG_BINARY,
+            None,
+            Some((&mut raw as *mut u32).cast()),
+            Some(&mut size),
+        )
+        .is_err()
+            || size < std::mem::size_of::<u32>() as u32
+        {
+            return None;
+        }
+        ScoreDisplay::from_u32(raw)
+    }
+}
+
+fn save_card_style(style: CardStyle) {
+    unsafe {
+        let key_path = string_to_wide_z(REG_SUBKEY_APP);
+        let mut hkey = HKEY::default();
+        if RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key_path.as_ptr()),
+            Some(0),
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_SET_VALUE | KEY_QUERY_VALUE,
+            None,
+            &mut hkey,
+            None,
+        )
+        .is_err()
+        {
+            return;
+        }
+        let value_name = string_to_wide_z(REG_VALUE_CARD_STYLE);
+        let bytes = style.as_u32().to_le_bytes();
+        let _ = RegSetValueExW(
+            hkey,
+            PCWSTR(value_name.as_ptr()),
+            Some(0),
+            REG_BINARY,
+            Some(&bytes),
+        );
+        let _ = RegCloseKey(hkey);
+    }
+}
+
+fn load_card_style() -> Option<CardStyle> {
+    unsafe {
+        let subkey = string_to_wide_z(REG_SUBKEY_APP);
+        let value = string_to_wide_z(REG_VALUE_CARD_STYLE);
+        let mut raw: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        if RegGetValueW(
+            HKEY_CURRENT_USER,
+            PCWSTR(subkey.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            RRF_RT_REG_BINARY,
+            None,
+            Some((&mut raw as *mut u32).cast()),
+            Some(&mut size),
+        )
+        .is_err()
+            || size < std::mem::size_of::<u32>() as u32
+        {
+            return None;
+        }
+        CardStyle::from_u32(raw)
+    }
+}
+
+fn save_assist_level(level: AssistLevel) {
+    unsafe {
+        let key_path = string_to_wide_z(REG_SUBKEY_APP);
+        let mut hkey = HKEY::default();
+        if RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key_path.as_ptr()),
+            Some(0),
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_SET_VALUE | KEY_QUERY_VALUE,
+            None,
+            &mut hkey,
+            None,
+        )
+        .is_err()
+        {
+            return;
+        }
+        let value_name = string_to_wide_z(REG_VALUE_ASSIST_LEVEL);
+        let bytes = level.as_u32().to_le_bytes();
+        let _ = RegSetValueExW(
+            hkey,
+            PCWSTR(value_name.as_ptr()),
+            Some(0),
+            REG_BINARY,
+            Some(&bytes),
+        );
+        let _ = RegCloseKey(hkey);
+    }
+}
+
+fn save_record_plays(enabled: bool) {
+    unsafe {
+        let key_path = string_to_wide_z(REG_SUBKEY_APP);
+        let mut hkey = HKEY::default();
+        if RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key_path.as_ptr()),
+            Some(0),
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_SET_VALUE | KEY_QUERY_VALUE,
+            None,
+            &mut hkey,
+            None,
+        )
+        .is_err()
+        {
+            return;
+        }
+        let value_name = string_to_wide_z(REG_VALUE_RECORD_PLAYS);
+        let bytes = u32::from(enabled).to_le_bytes();
+        let _ = RegSetValueExW(
+            hkey,
+            PCWSTR(value_name.as_ptr()),
+            Some(0),
+            REG_BINARY,
+            Some(&bytes),
+        );
+        let _ = RegCloseKey(hkey);
+    }
+}
+
+/// Recording is off unless the player turned it on.
+fn load_record_plays() -> bool {
+    unsafe {
+        let subkey = string_to_wide_z(REG_SUBKEY_APP);
+        let value = string_to_wide_z(REG_VALUE_RECORD_PLAYS);
+        let mut raw: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        if RegGetValueW(
+            HKEY_CURRENT_USER,
+            PCWSTR(subkey.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            RRF_RT_REG_BINARY,
+            None,
+            Some((&mut raw as *mut u32).cast()),
+            Some(&mut size),
+        )
+        .is_err()
+            || size < std::mem::size_of::<u32>() as u32
+        {
+            return false;
+        }
+        raw == 1
+    }
+}
+
+fn save_jack_of_diamonds(enabled: bool) {
+    unsafe {
+        let key_path = string_to_wide_z(REG_SUBKEY_APP);
+        let mut hkey = HKEY::default();
+        if RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key_path.as_ptr()),
+            Some(0),
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_SET_VALUE | KEY_QUERY_VALUE,
+            None,
+            &mut hkey,
+            None,
+        )
+        .is_err()
+        {
+            return;
+        }
+        let value_name = string_to_wide_z(REG_VALUE_JACK_OF_DIAMONDS);
+        let bytes = u32::from(enabled).to_le_bytes();
+        let _ = RegSetValueExW(
+            hkey,
+            PCWSTR(value_name.as_ptr()),
+            Some(0),
+            REG_BINARY,
+            Some(&bytes),
+        );
+        let _ = RegCloseKey(hkey);
+    }
+}
+
+/// The saved roster, or the compass names when it cannot be read.
+fn load_roster() -> PlayerRoster {
+    load_players(&players_path()).unwrap_or_else(|err| {
+        debug_out("mdhearts: ", &format!("Players not loaded: {err}"));
+        PlayerRoster::default()
+    })
+}
+
+/// The Omnibus rule is off unless the player turned it on.
+fn load_jack_of_diamonds() -> bool {
+    unsafe {
+        let subkey = string_to_wide_z(REG_SUBKEY_APP);
+        let value = string_to_wide_z(REG_VALUE_JACK_OF_DIAMONDS);
+        let mut raw: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        if RegGetValueW(
+            HKEY_CURRENT_USER,
+            PCWSTR(subkey.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            RRF_RT_REG_BINARY,
+            None,
+            Some((&mut raw as *mut u32).cast()),
+            Some(&mut size),
+        )
+        .is_err()
+            || size < std::mem::size_of::<u32>() as u32
+        {
+            return false;
+        }
+        raw == 1
+    }
+}
+
+fn save_match_target(target: u32) {
+    unsafe {
+        let key_path = string_to_wide_z(REG_SUBKEY_APP);
+        let mut hkey = HKEY::default();
+        if RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key_path.as_ptr()),
+            Some(0),
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_SET_VALUE | KEY_QUERY_VALUE,
+            None,
+            &mut hkey,
+            None,
+        )
+        .is_err()
+        {
+            return;
+        }
+        let value_name = string_to_wide_z(REG_VALUE_MATCH_TARGET);
+        let bytes = target.to_le_bytes();
+        let _ = RegSetValueExW(
+            hkey,
+            PCWSTR(value_name.as_ptr()),
+            Some(0),
+            REG_BINARY,
+            Some(&bytes),
         );
         let _ = RegCloseKey(hkey);
     }
 }
 
-fn load_card_back() -> Option<CardBackId> {
+/// The saved match target, if it is one the rules accept.
+fn load_match_target() -> Option<u32> {
     unsafe {
         let subkey = string_to_wide_z(REG_SUBKEY_APP);
-        let value = string_to_wide_z(REG_VALUE_CARD_BACK);
+        let value = string_to_wide_z(REG_VALUE_MATCH_TARGET);
         let mut raw: u32 = 0;
         let mut size = std::mem::size_of::<u32>() as u32;
         if RegGetValueW(
@@ -3904,44 +6075,34 @@ fn load_card_back() -> Option<CardBackId> {
         {
             return None;
         }
-        CardBackId::from_u32(raw)
+        RuleSet::STANDARD
+            .with_target_score(raw)
+            .ok()
+            .map(|rules| rules.target_score)
     }
 }
 
-fn save_think_limit(limit: Duration) {
+fn load_assist_level() -> Option<AssistLevel> {
     unsafe {
-        let key_path = string_to_wide_z(REG_SUBKEY_APP);
-        let mut hkey = HKEY::default();
-        if RegCreateKeyExW(
+        let subkey = string_to_wide_z(REG_SUBKEY_APP);
+        let value = string_to_wide_z(REG_VALUE_ASSIST_LEVEL);
+        let mut raw: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        if RegGetValueW(
             HKEY_CURRENT_USER,
-            PCWSTR(key_path.as_ptr()),
-            Some(0),
-            None,
-            REG_OPTION_NON_VOLATILE,
-            KEY_SET_VALUE | KEY_QUERY_VALUE,
-            None,
-            &mut hkey,
+            PCWSTR(subkey.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            RRF_RT_REG_BINARY,
             None,
+            Some((&mut raw as *mut u32).cast()),
+            Some(&mut size),
         )
         .is_err()
+            || size < std::mem::size_of::<u32>() as u32
         {
-            return;
+            return None;
         }
-        let value_name = string_to_wide_z(REG_VALUE_THINK_LIMIT);
-        let millis = if limit.is_zero() {
-            0u32
-        } else {
-            limit.as_millis().min(u32::MAX as u128) as u32
-        };
-        let bytes = millis.to_le_bytes();
-        let _ = RegSetValueExW(
-            hkey,
-            PCWSTR(value_name.as_ptr()),
-            Some(0),
-            REG_BINARY,
-            Some(&bytes),
-        );
-        let _ = RegCloseKey(hkey);
+        AssistLevel::from_u32(raw)
     }
 }
 
@@ -4055,6 +6216,7 @@ fn save_bot_difficulty(value: crate::bot::BotDifficulty) {
                 crate::bot::BotDifficulty::NormalHeuristic => 1,
                 crate::bot::BotDifficulty::FutureHard => 2,
                 crate::bot::BotDifficulty::SearchLookahead => 3,
+                crate::bot::BotDifficulty::Rollout => 4,
             };
             let bytes = raw.to_le_bytes();
             let _ = RegSetValueExW(
@@ -4094,6 +6256,7 @@ fn load_bot_difficulty() -> Option<crate::bot::BotDifficulty> {
                 1 => Some(crate::bot::BotDifficulty::NormalHeuristic),
                 2 => Some(crate::bot::BotDifficulty::FutureHard),
                 3 => Some(crate::bot::BotDifficulty::SearchLookahead),
+                4 => Some(crate::bot::BotDifficulty::Rollout),
                 _ => None,
             };
         }
@@ -4298,13 +6461,13 @@ fn rank_from_name(name: &str) -> Option<hearts_core::model::rank::Rank> {
     }
 }
 
-fn compute_south_hand_rects(size: LayoutSize, count: usize) -> Vec<D2D_RECT_F> {
+fn compute_south_hand_rects(size: LayoutSize, count: usize, fan: HandFan) -> Vec<D2D_RECT_F> {
     let width = size.width;
     let height = size.height;
     let min_edge = width.min(height);
     let card_w = (min_edge * 0.14).clamp(80.0, 180.0);
     let card_h = card_w * 1.4;
-    let spacing = card_w * 0.5;
+    let spacing = card_w * 0.5 * fan.factor();
     let total_w = card_w + spacing * (count.saturating_sub(1) as f32);
     let left_start = (width - total_w) * 0.5;
     let top = height - card_h - (height * 0.06);
@@ -4508,7 +6671,7 @@ fn compute_collect_target_rect_for(
     match seat {
         PlayerPosition::South => {
             let count = controller.hand(PlayerPosition::South).len().max(1);
-            let rects = compute_south_hand_rects(size, count);
+            let rects = compute_south_hand_rects(size, count, HandFan::default());
             rects
                 .get(count / 2)
                 .copied()
@@ -5583,6 +7746,360 @@ fn card_back_state_cell(hwnd: HWND) -> Option<&'static RefCell<CardBackDialogSta
     card_back_state_ptr(hwnd).map(|p| unsafe { &*p })
 }
 
+/// The seats Game → Players edits, in the order they sit around the table
+/// from the human's left. South keeps its name.
+const PLAYERS_DIALOG_SEATS: [PlayerPosition; 3] = [
+    PlayerPosition::West,
+    PlayerPosition::North,
+    PlayerPosition::East,
+];
+// IsDialogMessageW sends IDOK for Enter and IDCANCEL for Esc.
+const ID_PLAYERS_OK: usize = 1;
+const ID_PLAYERS_CANCEL: usize = 2;
+const ID_PLAYERS_DEFAULTS: usize = 3;
+const ID_PLAYERS_NAME_BASE: usize = 100;
+const ID_PLAYERS_AVATAR_BASE: usize = 110;
+
+struct PlayersDialogState {
+    initial: PlayerRoster,
+    avatars: [Option<Avatar>; 3],
+    names: [HWND; 3],
+    avatar_buttons: [HWND; 3],
+    result_sink: Rc<RefCell<Option<PlayerRoster>>>,
+}
+
+struct PlayersDialog;
+
+impl PlayersDialog {
+    fn run(owner: HWND, current: &PlayerRoster) -> Result<Option<PlayerRoster>> {
+        static CLASS: std::sync::Once = std::sync::Once::new();
+        let class_name = w!("MDHEARTS_PLAYERS");
+        CLASS.call_once(|| {
+            let hmodule = unsafe { GetModuleHandleW(None).unwrap_or_default() };
+            let hinstance = HINSTANCE(hmodule.0);
+            let icon = unsafe {
+                LoadIconW(Some(hinstance), make_int_resource(IDI_APPICON))
+                    .unwrap_or_else(|_| LoadIconW(None, IDI_APPLICATION).unwrap_or_default())
+            };
+            let cursor = unsafe { LoadCursorW(None, IDC_ARROW).unwrap_or_default() };
+            let wc = WNDCLASSEXW {
+                cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+                style: CS_HREDRAW | CS_VREDRAW,
+                lpfnWndProc: Some(players_window_proc),
+                cbClsExtra: 0,
+                cbWndExtra: std::mem::size_of::<*const RefCell<PlayersDialogState>>() as i32,
+                hInstance: hinstance,
+                hIcon: icon,
+                hCursor: cursor,
+                hbrBackground: unsafe { GetSysColorBrush(COLOR_BTNFACE) },
+                lpszMenuName: PCWSTR::null(),
+                lpszClassName: class_name,
+                hIconSm: icon,
+            };
+            unsafe {
+                let _ = RegisterClassExW(&wc);
+            }
+        });
+
+        let result_sink = Rc::new(RefCell::new(None));
+        let state = RefCell::new(PlayersDialogState {
+            initial: current.clone(),
+            avatars: PLAYERS_DIALOG_SEATS.map(|seat| current.identity(seat).avatar),
+            names: [HWND::default(); 3],
+            avatar_buttons: [HWND::default(); 3],
+            result_sink: result_sink.clone(),
+        });
+        let ptr = Box::into_raw(Box::new(state));
+
+        let dpi = unsafe {
+            if !owner.0.is_null() {
+                GetDpiForWindow(owner)
+            } else {
+                GetDpiForSystem()
+            }
+        } as i32;
+        let mut window_rect = RECT {
+            left: 0,
+            top: 0,
+            right: scale_px(344, dpi),
+            bottom: scale_px(176, dpi),
+        };
+        let style = WS_OVERLAPPED | WS_CAPTION | WS_SYSMENU;
+        unsafe {
+            let _ = AdjustWindowRectEx(&mut window_rect, style, false, WINDOW_EX_STYLE::default());
+        }
+        let hwnd_res = unsafe {
+            CreateWindowExW(
+                WINDOW_EX_STYLE::default(),
+                class_name,
+                w!("Players"),
+                style,
+                CW_USEDEFAULT,
+                CW_USEDEFAULT,
+                window_rect.right - window_rect.left,
+                window_rect.bottom - window_rect.top,
+                Some(owner),
+                None,
+                None,
+                Some(ptr.cast::<c_void>()),
+            )
+        };
+        let hwnd = match hwnd_res {
+            Ok(h) => h,
+            Err(err) => {
+                unsafe {
+                    let _ = Box::from_raw(ptr);
+                }
+                return Err(err);
+            }
+        };
+
+        unsafe {
+            center_window(owner, hwnd);
+            let _ = ShowWindow(hwnd, SW_SHOW);
+            if let Some(cell) = players_state_cell(hwnd) {
+                let first = cell.borrow().names[0];
+                let _ = SetFocus(Some(first));
+            }
+        }
+
+        unsafe {
+            let mut msg = MSG::default();
+            while IsWindow(Some(hwnd)).as_bool() {
+                if PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE).as_bool() {
+                    if msg.message == WM_QUIT {
+                        PostQuitMessage(msg.wParam.0 as i32);
+                        break;
+                    }
+                    // Tab between the fields, Enter for OK and Esc for Cancel.
+                    if !IsDialogMessageW(hwnd, &msg).as_bool() {
+                        let _ = TranslateMessage(&msg);
+                        DispatchMessageW(&msg);
+                    }
+                } else {
+                    let _ = WaitMessage();
+                }
+            }
+        }
+
+        Ok(result_sink.borrow().clone())
+    }
+}
+
+const fn scale_px(value: i32, dpi: i32) -> i32 {
+    (value * dpi + 48) / 96
+}
+
+fn avatar_label(avatar: Option<Avatar>) -> &'static str {
+    avatar.map_or("None", Avatar::glyph)
+}
+
+impl PlayersDialogState {
+    fn create_controls(&mut self, hwnd: HWND) {
+        let dpi = unsafe { GetDpiForWindow(hwnd) } as i32;
+        let px = |value: i32| scale_px(value, dpi);
+        let font = unsafe { GetStockObject(DEFAULT_GUI_FONT) };
+        let child = |class: PCWSTR, text: &str, style: WINDOW_STYLE, rect: [i32; 4], id: usize| {
+            let text = string_to_wide_z(text);
+            let created = unsafe {
+                CreateWindowExW(
+                    WINDOW_EX_STYLE::default(),
+                    class,
+                    PCWSTR(text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | style,
+                    px(rect[0]),
+                    px(rect[1]),
+                    px(rect[2]),
+                    px(rect[3]),
+                    Some(hwnd),
+                    Some(HMENU(id as *mut c_void)),
+                    None,
+                    None,
+                )
+            };
+            let control = created.unwrap_or_default();
+            unsafe {
+                SendMessageW(
+                    control,
+                    WM_SETFONT,
+                    Some(WPARAM(font.0 as usize)),
+                    Some(LPARAM(1)),
+                );
+            }
+            control
+        };
+        let button = WS_TABSTOP | WINDOW_STYLE(BS_PUSHBUTTON as u32);
+        for (idx, &seat) in PLAYERS_DIALOG_SEATS.iter().enumerate() {
+            let top = 16 + idx as i32 * 34;
+            child(
+                w!("STATIC"),
+                &seat.to_string(),
+                WINDOW_STYLE::default(),
+                [16, top + 4, 56, 20],
+                0,
+            );
+            self.names[idx] = child(
+                w!("EDIT"),
+                self.initial.name(seat),
+                WS_BORDER | WS_TABSTOP | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                [76, top, 180, 24],
+                ID_PLAYERS_NAME_BASE + idx,
+            );
+            self.avatar_buttons[idx] = child(
+                w!("BUTTON"),
+                avatar_label(self.avatars[idx]),
+                button,
+                [264, top, 64, 24],
+                ID_PLAYERS_AVATAR_BASE + idx,
+            );
+        }
+        child(
+            w!("BUTTON"),
+            "&Defaults",
+            button,
+            [16, 136, 80, 26],
+            ID_PLAYERS_DEFAULTS,
+        );
+        child(
+            w!("BUTTON"),
+            "OK",
+            WS_TABSTOP | WINDOW_STYLE(BS_DEFPUSHBUTTON as u32),
+            [160, 136, 80, 26],
+            ID_PLAYERS_OK,
+        );
+        child(
+            w!("BUTTON"),
+            "Cancel",
+            button,
+            [248, 136, 80, 26],
+            ID_PLAYERS_CANCEL,
+        );
+    }
+
+    fn cycle_avatar(&mut self, idx: usize) {
+        self.avatars[idx] = Avatar::next(self.avatars[idx]);
+        let label = string_to_wide_z(avatar_label(self.avatars[idx]));
+        unsafe {
+            let _ = SetWindowTextW(self.avatar_buttons[idx], PCWSTR(label.as_ptr()));
+        }
+    }
+
+    fn reset_to_defaults(&mut self) {
+        for (idx, &seat) in PLAYERS_DIALOG_SEATS.iter().enumerate() {
+            let name = string_to_wide_z(&seat.to_string());
+            unsafe {
+                let _ = SetWindowTextW(self.names[idx], PCWSTR(name.as_ptr()));
+            }
+            self.avatars[idx] = None;
+            let label = string_to_wide_z(avatar_label(self.avatars[idx]));
+            unsafe {
+                let _ = SetWindowTextW(self.avatar_buttons[idx], PCWSTR(label.as_ptr()));
+            }
+        }
+    }
+
+    /// The roster as entered, checked as a whole.
+    fn entered(&self) -> std::result::Result<PlayerRoster, String> {
+        let mut seats = self.initial.seats().clone();
+        for (idx, &seat) in PLAYERS_DIALOG_SEATS.iter().enumerate() {
+            let mut buffer = [0u16; 256];
+            let len = unsafe { GetWindowTextW(self.names[idx], &mut buffer) } as usize;
+            let name = String::from_utf16_lossy(&buffer[..len]);
+            seats[seat.index()] = PlayerIdentity::new(name, self.avatars[idx]);
+        }
+        PlayerRoster::new(seats).map_err(|err| err.to_string())
+    }
+}
+
+unsafe extern "system" fn players_window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_NCCREATE => unsafe {
+            let cs = &*(lparam.0 as *const CREATESTRUCTW);
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, cs.lpCreateParams as isize);
+            LRESULT(1)
+        },
+        WM_CREATE => {
+            if let Some(cell) = players_state_cell(hwnd) {
+                cell.borrow_mut().create_controls(hwnd);
+            }
+            LRESULT(0)
+        }
+        WM_COMMAND => {
+            let id = wparam.0 & 0xFFFF;
+            let Some(cell) = players_state_cell(hwnd) else {
+                return LRESULT(0);
+            };
+            match id {
+                ID_PLAYERS_OK => {
+                    let entered = cell.borrow().entered();
+                    match entered {
+                        Ok(roster) => {
+                            *cell.borrow().result_sink.borrow_mut() = Some(roster);
+                            unsafe {
+                                let _ = DestroyWindow(hwnd);
+                            }
+                        }
+                        Err(err) => {
+                            let text = string_to_wide_z(&err);
+                            unsafe {
+                                MessageBoxW(
+                                    Some(hwnd),
+                                    PCWSTR(text.as_ptr()),
+                                    w!("Players"),
+                                    MB_OK | MB_ICONERROR,
+                                );
+                            }
+                        }
+                    }
+                }
+                ID_PLAYERS_CANCEL => unsafe {
+                    let _ = DestroyWindow(hwnd);
+                },
+                ID_PLAYERS_DEFAULTS => cell.borrow_mut().reset_to_defaults(),
+                _ if (ID_PLAYERS_AVATAR_BASE..ID_PLAYERS_AVATAR_BASE + 3).contains(&id) => {
+                    cell.borrow_mut().cycle_avatar(id - ID_PLAYERS_AVATAR_BASE);
+                }
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        WM_CLOSE => {
+            unsafe {
+                let _ = DestroyWindow(hwnd);
+            }
+            LRESULT(0)
+        }
+        WM_NCDESTROY => {
+            if let Some(ptr) = players_state_ptr(hwnd) {
+                unsafe {
+                    let _ = Box::from_raw(ptr);
+                    SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0);
+                }
+            }
+            LRESULT(0)
+        }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
+
+fn players_state_ptr(hwnd: HWND) -> Option<*mut RefCell<PlayersDialogState>> {
+    let ptr = unsafe { GetWindowLongPtrW(hwnd, GWLP_USERDATA) };
+    if ptr == 0 {
+        None
+    } else {
+        Some(ptr as *mut RefCell<PlayersDialogState>)
+    }
+}
+
+fn players_state_cell(hwnd: HWND) -> Option<&'static RefCell<PlayersDialogState>> {
+    players_state_ptr(hwnd).map(|p| unsafe { &*p })
+}
+
 fn translate_rect_y(mut rect: D2D_RECT_F, offset: f32) -> D2D_RECT_F {
     rect.top += offset;
     rect.bottom += offset;
@@ -5632,6 +8149,121 @@ fn draw_bitmap_with_round_corners(
     Ok(())
 }
 
+fn rgba_brush(rt: &ID2D1HwndRenderTarget, color: Rgba) -> Result<ID2D1SolidColorBrush> {
+    let [r, g, b, a] = color;
+    unsafe { rt.CreateSolidColorBrush(&D2D1_COLOR_F { r, g, b, a }, None) }
+}
+
+fn card_face_format(
+    dwrite: &IDWriteFactory,
+    size: f32,
+    style: CardStyle,
+    alignment: DWRITE_TEXT_ALIGNMENT,
+) -> Result<IDWriteTextFormat> {
+    let weight = if style.high_contrast {
+        DWRITE_FONT_WEIGHT_BOLD
+    } else {
+        DWRITE_FONT_WEIGHT_SEMI_BOLD
+    };
+    unsafe {
+        let format = dwrite.CreateTextFormat(
+            w!("Segoe UI"),
+            None,
+            weight,
+            DWRITE_FONT_STYLE_NORMAL,
+            DWRITE_FONT_STRETCH_NORMAL,
+            size.max(1.0),
+            w!("en-us"),
+        )?;
+        format.SetTextAlignment(alignment)?;
+        format.SetParagraphAlignment(if alignment == DWRITE_TEXT_ALIGNMENT_CENTER {
+            DWRITE_PARAGRAPH_ALIGNMENT_CENTER
+        } else {
+            DWRITE_PARAGRAPH_ALIGNMENT_NEAR
+        })?;
+        format.SetWordWrapping(DWRITE_WORD_WRAPPING_NO_WRAP)?;
+        Ok(format)
+    }
+}
+
+/// Draws the face of `card` into `rounded`. The classic style copies it
+/// from the atlas; any other [`CardStyle`] paints it at this size: the
+/// index in the top-left corner and the suit in the middle, both in the
+/// suit's color. Returns false when there was no atlas face to copy.
+#[allow(clippy::too_many_arguments)]
+fn draw_card_face(
+    factory: &ID2D1Factory,
+    rt: &ID2D1HwndRenderTarget,
+    dwrite: &IDWriteFactory,
+    style: CardStyle,
+    atlas: Option<(&ID2D1Bitmap, &AtlasMeta)>,
+    rounded: &D2D1_ROUNDED_RECT,
+    card: ModelCard,
+    opacity: f32,
+    mode: D2D1_BITMAP_INTERPOLATION_MODE,
+) -> Result<bool> {
+    if style.uses_atlas() {
+        let Some((bmp, src)) = atlas.and_then(|(bmp, meta)| Some((bmp, meta.src_rect_for(card)?)))
+        else {
+            return Ok(false);
+        };
+        let src = inset_rect(src, 0.5, 0.5);
+        draw_bitmap_with_round_corners(factory, rt, rounded, bmp, Some(&src), opacity, mode)?;
+        return Ok(true);
+    }
+    let dest = rounded.rect;
+    let metrics = style.face_metrics(dest.right - dest.left);
+    let fade = |[r, g, b, a]: Rgba| [r, g, b, a * opacity];
+    let paper = rgba_brush(rt, fade([1.0, 1.0, 1.0, 1.0]))?;
+    let edge = rgba_brush(rt, fade(style.edge_color()))?;
+    let ink = rgba_brush(rt, fade(style.suit_color(card.suit)))?;
+    let index_format = card_face_format(
+        dwrite,
+        metrics.index_height,
+        style,
+        DWRITE_TEXT_ALIGNMENT_LEADING,
+    )?;
+    let pip_format = card_face_format(
+        dwrite,
+        metrics.pip_height,
+        style,
+        DWRITE_TEXT_ALIGNMENT_CENTER,
+    )?;
+    let index_rect = D2D_RECT_F {
+        left: dest.left + metrics.inset,
+        top: dest.top + metrics.inset * 0.5,
+        right: dest.right - metrics.inset,
+        bottom: dest.top + metrics.inset + metrics.index_height * 1.4,
+    };
+    let pip_rect = D2D_RECT_F {
+        top: index_rect.bottom,
+        ..dest
+    };
+    let index = string_to_wide(&style.index_label(card));
+    let pip = string_to_wide(style.suit_glyph(card.suit));
+    unsafe {
+        rt.FillRoundedRectangle(rounded, &paper);
+        rt.DrawRoundedRectangle(rounded, &edge, metrics.edge, None);
+        rt.DrawText(
+            index.as_slice(),
+            &index_format,
+            &index_rect,
+            &ink,
+            D2D1_DRAW_TEXT_OPTIONS_NONE,
+            DWRITE_MEASURING_MODE::default(),
+        );
+        rt.DrawText(
+            pip.as_slice(),
+            &pip_format,
+            &pip_rect,
+            &ink,
+            D2D1_DRAW_TEXT_OPTIONS_NONE,
+            DWRITE_MEASURING_MODE::default(),
+        );
+    }
+    Ok(true)
+}
+
 impl AtlasMeta {
     fn load_from_assets() -> std::result::Result<Self, Box<dyn std::error::Error>> {
         let text = std::fs::read_to_string("assets/cards.json")?;
@@ -0,0 +1,321 @@
+//! Per-agent strength ratings from bench results, so a run ends with one
+//! number per agent that can be tracked from night to night.
+//!
+//! Every result is a four-way free-for-all: a hand ranked by bench points,
+//! or a match ranked by placing, fewest first. Ratings follow the Weng-Lin
+//! Bradley-Terry update (the one OpenSkill uses). Each agent has a mean `mu`
+//! and an uncertainty `sigma`, and a result moves both from every pairwise
+//! comparison against the other agents at the table. The reported rating is
+//! the conservative `mu - 3 * sigma`.
+//!
+//! An agent holding several seats in one result is compared only with the
+//! other agents; its own seats carry nothing about its strength. A run can
+//! start from a previous run's `ratings.json` instead of the default prior;
+//! its agent names are checked like any other [`AgentId`], and must differ
+//! ignoring case.
+
+use crate::agent_id::{AgentId, AgentIdError, check_distinct};
+use serde::{Deserialize, Serialize};
+
+/// Mean of an unrated agent.
+pub const DEFAULT_MU: f64 = 25.0;
+/// Uncertainty of an unrated agent.
+pub const DEFAULT_SIGMA: f64 = DEFAULT_MU / 3.0;
+/// Spread of a single result around an agent's strength.
+const BETA: f64 = DEFAULT_SIGMA / 2.0;
+/// Uncertainty added before each result, so `sigma` never settles at zero
+/// and long-tracked ratings can still move.
+const TAU: f64 = DEFAULT_SIGMA / 100.0;
+/// Floor on the factor `sigma` shrinks by in one result.
+const KAPPA: f64 = 1e-4;
+
+/// Name recorded in `ratings.json` for the update rule.
+pub const MODEL: &str = "weng-lin-bradley-terry";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentRating {
+    pub agent: AgentId,
+    pub mu: f64,
+    pub sigma: f64,
+    /// `mu - 3 * sigma`; written for readers, recomputed on load.
+    pub rating: f64,
+    /// Seats rated, over every run the rating was carried through.
+    pub games: u32,
+}
+
+impl AgentRating {
+    fn new(agent: &AgentId) -> Self {
+        Self {
+            agent: agent.clone(),
+            mu: DEFAULT_MU,
+            sigma: DEFAULT_SIGMA,
+            rating: DEFAULT_MU - 3.0 * DEFAULT_SIGMA,
+            games: 0,
+        }
+    }
+}
+
+/// The `ratings.json` layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatingsFile {
+    pub model: String,
+    /// What one result was: `hand` or `match`.
+    pub unit: String,
+    pub agents: Vec<AgentRating>,
+}
+
+/// Ratings being updated over a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ratings {
+    unit: &'static str,
+    agents: Vec<AgentRating>,
+}
+
+impl Ratings {
+    /// Every agent at the default prior; `unit` names what a result is.
+    pub fn new(unit: &'static str) -> Self {
+        Self {
+            unit,
+            agents: Vec::new(),
+        }
+    }
+
+    /// Starts from a previous run's ratings. Agents it lacks start at the
+    /// default prior; agents this run never seats are carried through.
+    pub fn with_priors(unit: &'static str, priors: RatingsFile) -> Result<Self, AgentIdError> {
+        check_distinct(priors.agents.iter().map(|prior| &prior.agent))?;
+        let agents = priors
+            .agents
+            .into_iter()
+            .map(|prior| AgentRating {
+                rating: prior.mu - 3.0 * prior.sigma,
+                ..prior
+            })
+            .collect();
+        Ok(Self { unit, agents })
+    }
+
+    /// A prior spelled in another case is the same agent.
+    fn index_of(&mut self, agent: &AgentId) -> usize {
+        match self
+            .agents
+            .iter()
+            .position(|rated| rated.agent.same_agent(agent))
+        {
+            Some(idx) => idx,
+            None => {
+                self.agents.push(AgentRating::new(agent));
+                self.agents.len() - 1
+            }
+        }
+    }
+
+    /// Rates one result: `agents` at each seat, N, E, S, W, and each seat's
+    /// `standing`, lower being better. Equal standings count as a draw.
+    pub fn record(&mut self, agents: &[AgentId; 4], standings: [i64; 4]) {
+        let seats = agents.each_ref().map(|agent| self.index_of(agent));
+        // Every seat is compared against ratings from before the result.
+        let before: Vec<(f64, f64)> = self
+            .agents
+            .iter()
+            .map(|rated| (rated.mu, rated.sigma.hypot(TAU)))
+            .collect();
+        let mut moves = vec![(0.0, 1.0, false); self.agents.len()];
+        for (i, &agent) in seats.iter().enumerate() {
+            let (mu, sigma) = before[agent];
+            let variance = sigma * sigma;
+            let (mut omega, mut delta, mut opponents) = (0.0, 0.0, false);
+            for (q, &other) in seats.iter().enumerate() {
+                if other == agent {
+                    continue;
+                }
+                opponents = true;
+                let (other_mu, other_sigma) = before[other];
+                let c = (variance + other_sigma * other_sigma + 2.0 * BETA * BETA).sqrt();
+                let p = 1.0 / (1.0 + ((other_mu - mu) / c).exp());
+                let score = match standings[i].cmp(&standings[q]) {
+                    std::cmp::Ordering::Less => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Greater => 0.0,
+                };
+                omega += variance / c * (score - p);
+                delta += (sigma / c) * variance / (c * c) * p * (1.0 - p);
+            }
+            let (mu_move, shrink, faced) = &mut moves[agent];
+            *mu_move += omega;
+            *shrink *= (1.0 - delta).max(KAPPA);
+            *faced |= opponents;
+            self.agents[agent].games += 1;
+        }
+        for (idx, (mu_move, shrink, faced)) in moves.into_iter().enumerate() {
+            if !faced {
+                continue;
+            }
+            let (mu, sigma) = before[idx];
+            let rated = &mut self.agents[idx];
+            rated.mu = mu + mu_move;
+            rated.sigma = sigma * shrink.sqrt();
+            rated.rating = rated.mu - 3.0 * rated.sigma;
+        }
+    }
+
+    /// Agents by rating, best first.
+    pub fn table(&self) -> Vec<AgentRating> {
+        let mut table = self.agents.clone();
+        table.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+        table
+    }
+
+    pub fn to_file(&self) -> RatingsFile {
+        RatingsFile {
+            model: MODEL.to_string(),
+            unit: self.unit.to_string(),
+            agents: self.table(),
+        }
+    }
+}
+
+pub fn ratings_markdown(table: &[AgentRating]) -> String {
+    let mut out = String::from(
+        "| agent | rating | mu | sigma | games |\n\
+         |---|---:|---:|---:|---:|\n",
+    );
+    for row in table {
+        out.push_str(&format!(
+            "| {} | {:.2} | {:.2} | {:.2} | {} |\n",
+            row.agent, row.rating, row.mu, row.sigma, row.games
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AgentRating, DEFAULT_MU, DEFAULT_SIGMA, Ratings, RatingsFile, ratings_markdown};
+    use crate::agent_id::{AgentId, AgentIdError};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn ids(names: [&str; 4]) -> [AgentId; 4] {
+        names.map(|name| AgentId::new(name).unwrap())
+    }
+
+    fn rated(ratings: &Ratings, agent: &str) -> Option<AgentRating> {
+        ratings.table().into_iter().find(|row| row.agent == agent)
+    }
+
+    #[test]
+    fn an_agent_that_never_takes_a_point_rates_highest() {
+        let mut rng = StdRng::seed_from_u64(2266);
+        let mut ratings = Ratings::new("hand");
+        let mut seating = ["ace", "easy", "normal", "normal"];
+        for hand in 0..200 {
+            seating.rotate_left(1);
+            let standings = seating.map(|agent| match agent {
+                "ace" => 0,
+                "easy" => 20 + rng.gen_range(0..6),
+                _ => rng.gen_range(1..20),
+            });
+            ratings.record(&ids(seating), standings);
+            if hand == 0 {
+                assert!(rated(&ratings, "ace").unwrap().mu > DEFAULT_MU);
+            }
+        }
+        let table = ratings.table();
+        let order: Vec<&str> = table.iter().map(|row| row.agent.as_str()).collect();
+        assert_eq!(order, ["ace", "normal", "easy"]);
+        let ace = rated(&ratings, "ace").unwrap();
+        assert!(ace.sigma < DEFAULT_SIGMA / 2.0, "{ace:?}");
+        assert_eq!(ace.games, 200);
+        // Normal held two seats a hand.
+        assert_eq!(rated(&ratings, "normal").unwrap().games, 400);
+        let markdown = ratings_markdown(&table);
+        assert!(markdown.lines().nth(2).unwrap().starts_with("| ace |"));
+    }
+
+    #[test]
+    fn an_agent_alone_at_the_table_or_level_with_its_rival_does_not_move() {
+        let mut ratings = Ratings::new("hand");
+        ratings.record(&ids(["normal"; 4]), [0, 5, 8, 13]);
+        let normal = rated(&ratings, "normal").unwrap();
+        assert_eq!((normal.mu, normal.sigma), (DEFAULT_MU, DEFAULT_SIGMA));
+        ratings.record(&ids(["hard", "easy", "hard", "easy"]), [6, 6, 6, 6]);
+        let (hard, easy) = (
+            rated(&ratings, "hard").unwrap(),
+            rated(&ratings, "easy").unwrap(),
+        );
+        assert_eq!(hard.mu, DEFAULT_MU);
+        assert_eq!(easy.mu, DEFAULT_MU);
+        assert!(hard.sigma < DEFAULT_SIGMA);
+    }
+
+    #[test]
+    fn priors_carry_a_rating_on_from_a_previous_run() {
+        let results: Vec<([&str; 4], [i64; 4])> = (0..60)
+            .map(|i| {
+                let seating = if i % 2 == 0 {
+                    ["hard", "easy", "normal", "easy"]
+                } else {
+                    ["easy", "normal", "easy", "hard"]
+                };
+                (seating, [i % 3, (i * 7) % 5, (i * 11) % 4, 3])
+            })
+            .collect();
+        let mut whole = Ratings::new("match");
+        for (agents, standings) in &results {
+            whole.record(&ids(*agents), *standings);
+        }
+        let mut first = Ratings::new("match");
+        for (agents, standings) in &results[..30] {
+            first.record(&ids(*agents), *standings);
+        }
+        let json = serde_json::to_string(&first.to_file()).unwrap();
+        let file: RatingsFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(file.unit, "match");
+        let mut resumed = Ratings::with_priors("match", file).unwrap();
+        for (agents, standings) in &results[30..] {
+            resumed.record(&ids(*agents), *standings);
+        }
+        for (a, b) in whole.table().iter().zip(resumed.table()) {
+            assert_eq!(a.agent, b.agent);
+            assert_eq!(a.games, b.games);
+            assert!((a.mu - b.mu).abs() < 1e-9, "{a:?} {b:?}");
+            assert!((a.sigma - b.sigma).abs() < 1e-9, "{a:?} {b:?}");
+        }
+        // Agents the new run does not seat keep their prior.
+        let mut idle = Ratings::with_priors("match", first.to_file()).unwrap();
+        idle.record(&ids(["rollout"; 4]), [0; 4]);
+        assert_eq!(rated(&idle, "hard"), rated(&first, "hard"));
+    }
+
+    #[test]
+    fn priors_must_name_each_agent_once_ignoring_case() {
+        let mut file = Ratings::new("hand").to_file();
+        let mut ratings = Ratings::new("hand");
+        ratings.record(&ids(["hard", "easy", "hard", "easy"]), [0, 6, 2, 9]);
+        file.agents = ratings.table();
+        let mut shouted = file.agents[0].clone();
+        shouted.agent = AgentId::new("HARD").unwrap();
+        file.agents.push(shouted);
+        assert_eq!(
+            Ratings::with_priors("hand", file.clone()),
+            Err(AgentIdError::Duplicate {
+                first: "hard".to_string(),
+                second: "HARD".to_string(),
+            })
+        );
+        // A hand-edited name with a trailing space never gets that far.
+        let json = serde_json::to_string(&file)
+            .unwrap()
+            .replacen("\"easy\"", "\"easy \"", 1);
+        assert!(serde_json::from_str::<RatingsFile>(&json).is_err());
+
+        // One prior spelled in another case carries on the run's agent.
+        file.agents.pop();
+        file.agents[0].agent = AgentId::new("Hard").unwrap();
+        let mut resumed = Ratings::with_priors("hand", file).unwrap();
+        resumed.record(&ids(["hard", "easy", "hard", "easy"]), [0, 6, 2, 9]);
+        assert_eq!(resumed.table().len(), 2);
+        assert_eq!(rated(&resumed, "Hard").unwrap().games, 4);
+    }
+}
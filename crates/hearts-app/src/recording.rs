@@ -0,0 +1,284 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! Opt-in recording of the human seat's plays, as imitation data.
+//!
+//! Each play becomes one JSON line: what the seat could see when it chose
+//! (its hand, the legal cards, the trick so far, every earlier trick, the
+//! match scores and the pass it received), the card it played and, once the
+//! round is scored, the points it took. Nothing hidden from the seat is
+//! written. The lines of a round are held until it is scored, so a restarted
+//! or abandoned round leaves nothing behind.
+//!
+//! Recording is off until the player turns on Game → Record My Plays. The
+//! file stays next to the executable; `--export-human-plays` copies it and
+//! Game → Delete Recorded Plays removes it.
+
+use crate::controller::GameController;
+use crate::determinism::seat_letter;
+use hearts_core::model::card::Card;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::trick::Trick;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// The `source` of every recorded row. Self-play exports from
+/// `--export-play-dataset` carry none.
+pub const HUMAN_SOURCE: &str = "human";
+
+pub const RECORDING_FILE: &str = "mdhearts-human-plays.jsonl";
+
+/// Shown once when recording is turned on.
+pub const PRIVACY_NOTE: &str = "Recording saves every card you play, with what you could see \
+     when you played it, to a file next to mdhearts.exe. It never leaves this computer. \
+     Turn recording off at any time; Game \u{2192} Delete Recorded Plays removes the file.";
+
+/// One play by the human seat.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HumanPlayRecord {
+    /// Always [`HUMAN_SOURCE`].
+    pub source: String,
+    pub seed: u64,
+    pub round: u32,
+    pub seat: String,
+    pub passing: String,
+    pub trick_index: usize,
+    /// The seat's hand before the play.
+    pub hand: Vec<String>,
+    pub legal: Vec<String>,
+    /// The trick so far as `seat:card`, e.g. `W:KD`, in play order.
+    pub trick: Vec<String>,
+    /// Every completed trick of the round, written the same way.
+    pub history: Vec<Vec<String>>,
+    pub hearts_broken: bool,
+    /// Match totals going into the round, N,E,S,W.
+    pub scores: [u32; 4],
+    /// The cards the seat received in this round's pass.
+    pub received: Vec<String>,
+    pub chosen: String,
+    /// Points the seat took in the round; filled in when it is scored.
+    pub round_points: Option<u8>,
+    /// Someone shot the moon in the round.
+    pub moon: Option<bool>,
+}
+
+impl HumanPlayRecord {
+    /// What `seat` can see in `controller` as it plays `card`.
+    pub fn observe(controller: &GameController, seat: PlayerPosition, card: Card) -> Self {
+        let round = controller.round();
+        let codes = |cards: &[Card]| cards.iter().map(Card::to_string).collect::<Vec<_>>();
+        Self {
+            source: HUMAN_SOURCE.to_string(),
+            seed: controller.seed(),
+            round: controller.round_number(),
+            seat: seat.to_string(),
+            passing: round.passing_direction().as_str().to_string(),
+            trick_index: round.trick_history().len(),
+            hand: codes(&controller.hand(seat)),
+            legal: codes(&controller.legal_moves(seat)),
+            trick: trick_codes(round.current_trick()),
+            history: round.trick_history().iter().map(trick_codes).collect(),
+            hearts_broken: round.hearts_broken(),
+            scores: controller.standings(),
+            received: codes(&controller.received_cards(seat)),
+            chosen: card.to_string(),
+            round_points: None,
+            moon: None,
+        }
+    }
+}
+
+fn trick_codes(trick: &Trick) -> Vec<String> {
+    trick
+        .plays()
+        .iter()
+        .map(|play| format!("{}:{}", seat_letter(play.position), play.card))
+        .collect()
+}
+
+/// Collects the human's plays for the round in progress and appends them to
+/// the recording when it is scored.
+#[derive(Debug, Clone)]
+pub struct HumanRecorder {
+    path: PathBuf,
+    pending: Vec<HumanPlayRecord>,
+}
+
+impl HumanRecorder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn note_play(&mut self, record: HumanPlayRecord) {
+        self.pending.push(record);
+    }
+
+    /// Plays waiting for the round to be scored.
+    #[cfg(test)]
+    pub fn pending(&self) -> &[HumanPlayRecord] {
+        &self.pending
+    }
+
+    /// Fills in the round's outcome from its raw `penalties` and appends the
+    /// pending plays; returns how many were written.
+    pub fn finish_round(&mut self, penalties: [u8; 4]) -> io::Result<usize> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let moon = penalties.contains(&26);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let written = self.pending.len();
+        for mut record in self.pending.drain(..) {
+            let seat = PlayerPosition::LOOP
+                .into_iter()
+                .find(|seat| seat.to_string() == record.seat);
+            record.round_points = seat.map(|seat| penalties[seat.index()]);
+            record.moon = Some(moon);
+            writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        }
+        Ok(written)
+    }
+
+    /// Drops the round's plays, e.g. when it is restarted.
+    pub fn discard_round(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// Where the GUI keeps the recording: next to the executable, like the
+/// panic log.
+pub fn recording_path() -> PathBuf {
+    match std::env::current_exe() {
+        Ok(mut exe) => {
+            exe.set_file_name(RECORDING_FILE);
+            exe
+        }
+        Err(_) => PathBuf::from(RECORDING_FILE),
+    }
+}
+
+/// Every row of the recording at `path`, oldest first.
+pub fn load_recording(path: &Path) -> io::Result<Vec<HumanPlayRecord>> {
+    let file = fs::File::open(path)?;
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(serde_json::from_str(&line)?);
+    }
+    Ok(records)
+}
+
+/// Removes the recording at `path`; `false` when there was none.
+pub fn delete_recording(path: &Path) -> io::Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hearts_core::model::player::PlayerPosition::South;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("mdhearts_recording_{name}.jsonl"));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    /// Plays one round with South's cards chosen through the recorder, as
+    /// the window does, and scores it.
+    fn play_recorded_round(controller: &mut GameController) {
+        if controller.in_passing_phase() {
+            let cards = controller.simple_pass_for(South).unwrap();
+            controller.submit_pass(South, cards).unwrap();
+            controller.submit_auto_passes_for_others(South).unwrap();
+            controller.resolve_passes().unwrap();
+        }
+        while !controller.round_ready_for_scoring() {
+            let seat = controller.expected_to_play();
+            let card = controller.legal_moves(seat)[0];
+            if seat == South {
+                controller.record_human_play(seat, card);
+            }
+            controller.play(seat, card).unwrap();
+        }
+    }
+
+    #[test]
+    fn a_scored_round_is_written_with_its_outcome() {
+        let path = temp_path("scored");
+        let mut controller = GameController::new_with_seed(Some(2250), PlayerPosition::North);
+        controller.set_human_recorder(Some(HumanRecorder::new(&path)));
+        play_recorded_round(&mut controller);
+        let penalties = controller.penalties_this_round();
+        assert!(!path.exists(), "nothing is written before scoring");
+        controller.finish_round_if_ready();
+
+        let records = load_recording(&path).unwrap();
+        assert_eq!(records.len(), 13);
+        assert!(records.iter().all(|r| r.source == HUMAN_SOURCE));
+        assert!(records.iter().all(|r| r.seat == South.to_string()));
+        assert!(
+            records
+                .iter()
+                .all(|r| r.round_points == Some(penalties[South.index()]))
+        );
+        for (idx, record) in records.iter().enumerate() {
+            assert_eq!(record.trick_index, idx);
+            assert_eq!(record.history.len(), idx);
+            assert_eq!(record.hand.len(), 13 - idx);
+            assert!(record.legal.contains(&record.chosen));
+            assert_eq!(record.received.len(), 3);
+        }
+        // The next round appends to the same file.
+        play_recorded_round(&mut controller);
+        controller.finish_round_if_ready();
+        assert_eq!(load_recording(&path).unwrap().len(), 26);
+        assert!(delete_recording(&path).unwrap());
+        assert!(!delete_recording(&path).unwrap());
+    }
+
+    #[test]
+    fn a_restarted_round_leaves_nothing_behind() {
+        let path = temp_path("restarted");
+        let mut controller = GameController::new_with_seed(Some(2250), PlayerPosition::North);
+        controller.set_human_recorder(Some(HumanRecorder::new(&path)));
+        play_recorded_round(&mut controller);
+        controller.restart_round();
+        assert!(controller.human_recorder().unwrap().pending().is_empty());
+        controller.set_human_recorder(None);
+        play_recorded_round(&mut controller);
+        controller.finish_round_if_ready();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn rows_round_trip_through_json() {
+        let controller = GameController::new_with_seed(Some(7), PlayerPosition::North);
+        let card = controller.hand(South)[0];
+        let record = HumanPlayRecord::observe(&controller, South, card);
+        let line = serde_json::to_string(&record).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["source"], "human");
+        assert_eq!(value["round_points"], serde_json::Value::Null);
+        let back: HumanPlayRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(back, record);
+        assert!(record.received.is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Resource usage of a batch run, for sizing large sweeps.
+//!
+//! [`Meter::start`] is taken before a run's first hand and [`Meter::finish`]
+//! after its last. Wall-clock and CPU time cover just that span; peak
+//! resident memory is the process high-water mark, which the OS does not let
+//! us reset. CPU and memory come from `getrusage` on Unix and
+//! `GetProcessTimes`/`GetProcessMemoryInfo` on Windows; elsewhere they are
+//! `None`.
+
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    pub wall_secs: f64,
+    pub user_cpu_secs: Option<f64>,
+    pub system_cpu_secs: Option<f64>,
+    pub peak_rss_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Probe {
+    user_cpu_secs: Option<f64>,
+    system_cpu_secs: Option<f64>,
+    peak_rss_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Meter {
+    started: Instant,
+    at_start: Probe,
+}
+
+impl Meter {
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            at_start: probe(),
+        }
+    }
+
+    pub fn finish(&self) -> ResourceUsage {
+        let now = probe();
+        let spent = |end: Option<f64>, start: Option<f64>| Some((end? - start?).max(0.0));
+        ResourceUsage {
+            wall_secs: self.started.elapsed().as_secs_f64(),
+            user_cpu_secs: spent(now.user_cpu_secs, self.at_start.user_cpu_secs),
+            system_cpu_secs: spent(now.system_cpu_secs, self.at_start.system_cpu_secs),
+            peak_rss_bytes: now.peak_rss_bytes,
+        }
+    }
+}
+
+fn secs(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| format!("{v:.2}s"))
+}
+
+fn mib(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+impl ResourceUsage {
+    /// `Resources: wall 12.40s, cpu 11.90s user + 0.20s sys, peak RSS 45.1 MiB`.
+    pub fn summary_line(&self) -> String {
+        let rss = self
+            .peak_rss_bytes
+            .map_or_else(|| "-".to_string(), |bytes| format!("{:.1} MiB", mib(bytes)));
+        format!(
+            "Resources: wall {:.2}s, cpu {} user + {} sys, peak RSS {rss}",
+            self.wall_secs,
+            secs(self.user_cpu_secs),
+            secs(self.system_cpu_secs),
+        )
+    }
+
+    /// Change from `self` to `other` (B - A), for `--compare-runs`.
+    pub fn delta_line(&self, other: &ResourceUsage) -> String {
+        let diff = |a: Option<f64>, b: Option<f64>| {
+            a.zip(b)
+                .map_or_else(|| "-".to_string(), |(a, b)| format!("{:+.2}s", b - a))
+        };
+        let rss = self.peak_rss_bytes.zip(other.peak_rss_bytes).map_or_else(
+            || "-".to_string(),
+            |(a, b)| format!("{:+.1} MiB", mib(b) - mib(a)),
+        );
+        format!(
+            "Resources (B - A): wall {}, cpu user {}, cpu sys {}, peak RSS {rss}",
+            diff(Some(self.wall_secs), Some(other.wall_secs)),
+            diff(self.user_cpu_secs, other.user_cpu_secs),
+            diff(self.system_cpu_secs, other.system_cpu_secs),
+        )
+    }
+}
+
+#[cfg(unix)]
+fn probe() -> Probe {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+    // SAFETY: getrusage only writes the struct it is handed.
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return Probe::default();
+    }
+    // SAFETY: the call succeeded, so the struct is initialised.
+    let usage = unsafe { usage.assume_init() };
+    let secs = |tv: libc::timeval| tv.tv_sec as f64 + tv.tv_usec as f64 / 1e6;
+    // ru_maxrss is in bytes on macOS and kilobytes everywhere else.
+    let unit = if cfg!(target_os = "macos") { 1 } else { 1024 };
+    Probe {
+        user_cpu_secs: Some(secs(usage.ru_utime)),
+        system_cpu_secs: Some(secs(usage.ru_stime)),
+        peak_rss_bytes: u64::try_from(usage.ru_maxrss).ok().map(|v| v * unit),
+    }
+}
+
+#[cfg(windows)]
+fn probe() -> Probe {
+    use windows::Win32::Foundation::FILETIME;
+    use windows::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use windows::Win32::System::Threading::{GetCurrentProcess, GetProcessTimes};
+
+    // SAFETY: the pseudo-handle needs no closing and the out-params are
+    // locals of the sizes the calls expect.
+    unsafe {
+        let process = GetCurrentProcess();
+        let mut counters = PROCESS_MEMORY_COUNTERS::default();
+        let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        let peak_rss_bytes = GetProcessMemoryInfo(process, &mut counters, size)
+            .ok()
+            .map(|()| counters.PeakWorkingSetSize as u64);
+        let (mut created, mut exited) = (FILETIME::default(), FILETIME::default());
+        let (mut kernel, mut user) = (FILETIME::default(), FILETIME::default());
+        // FILETIME counts 100 ns ticks.
+        let secs = |ft: FILETIME| {
+            ((u64::from(ft.dwHighDateTime) << 32) | u64::from(ft.dwLowDateTime)) as f64 / 1e7
+        };
+        let times = GetProcessTimes(process, &mut created, &mut exited, &mut kernel, &mut user)
+            .ok()
+            .map(|()| (secs(user), secs(kernel)));
+        Probe {
+            user_cpu_secs: times.map(|(user, _)| user),
+            system_cpu_secs: times.map(|(_, kernel)| kernel),
+            peak_rss_bytes,
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn probe() -> Probe {
+    Probe::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Meter, ResourceUsage};
+
+    #[test]
+    #[cfg(any(unix, windows))]
+    fn supported_platforms_report_memory_and_cpu() {
+        let meter = Meter::start();
+        let mut buf = vec![0u8; 1 << 20];
+        for (idx, byte) in buf.iter_mut().enumerate() {
+            *byte = idx as u8;
+        }
+        let usage = meter.finish();
+        assert!(buf.iter().map(|&b| u64::from(b)).sum::<u64>() > 0);
+        assert!(usage.peak_rss_bytes.unwrap() > 0);
+        assert!(usage.user_cpu_secs.unwrap() >= 0.0);
+        assert!(usage.system_cpu_secs.is_some());
+        assert!(usage.wall_secs > 0.0);
+    }
+
+    #[test]
+    fn lines_show_missing_probes_as_dashes() {
+        let a = ResourceUsage {
+            wall_secs: 10.0,
+            user_cpu_secs: Some(9.5),
+            system_cpu_secs: None,
+            peak_rss_bytes: Some(40 * 1024 * 1024),
+        };
+        let b = ResourceUsage {
+            wall_secs: 12.5,
+            user_cpu_secs: Some(11.0),
+            system_cpu_secs: Some(0.1),
+            peak_rss_bytes: Some(44 * 1024 * 1024),
+        };
+        assert_eq!(
+            a.summary_line(),
+            "Resources: wall 10.00s, cpu 9.50s user + - sys, peak RSS 40.0 MiB"
+        );
+        assert_eq!(
+            a.delta_line(&b),
+            "Resources (B - A): wall +2.50s, cpu user +1.50s, cpu sys -, peak RSS +4.0 MiB"
+        );
+    }
+}
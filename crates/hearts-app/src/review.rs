@@ -0,0 +1,334 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+
+//! Post-round "ghost hands" review: reveal every hand as dealt and flag the
+//! human's plays where the advice engine strongly preferred another card.
+
+use crate::bot::BotDifficulty;
+use crate::controller::{RecordedRound, ReplayController};
+use hearts_core::model::card::Card;
+use hearts_core::model::hand::Hand;
+use hearts_core::model::player::PlayerPosition;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReviewConfig {
+    /// Record rounds and offer the review screen when they end.
+    pub enabled: bool,
+    /// Seat whose decisions are reviewed.
+    pub seat: PlayerPosition,
+    /// Flag a play when the best advised score beats it by more than this.
+    pub margin_threshold: i32,
+    /// Keep only the largest misplays.
+    pub max_flags: usize,
+}
+
+impl Default for ReviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            seat: PlayerPosition::South,
+            margin_threshold: 2_000,
+            max_flags: 5,
+        }
+    }
+}
+
+impl ReviewConfig {
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+        if let Ok(raw) = std::env::var("MDH_REVIEW") {
+            let lower = raw.trim().to_ascii_lowercase();
+            cfg.enabled = matches!(lower.as_str(), "1" | "true" | "on");
+        }
+        if let Ok(raw) = std::env::var("MDH_REVIEW_MARGIN")
+            && let Ok(margin) = raw.trim().parse::<i32>()
+        {
+            cfg.margin_threshold = margin.max(0);
+        }
+        if let Ok(raw) = std::env::var("MDH_REVIEW_MAX_FLAGS")
+            && let Ok(max) = raw.trim().parse::<usize>()
+        {
+            cfg.max_flags = max;
+        }
+        cfg
+    }
+}
+
+/// One reviewed decision: what was played and the advice for that position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReviewedDecision {
+    /// Index into the round's play order.
+    pub play_index: usize,
+    pub seat: PlayerPosition,
+    pub played: Card,
+    pub advice: Vec<(Card, i32)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MisplayFlag {
+    pub play_index: usize,
+    pub played: Card,
+    pub played_score: i32,
+    pub best: Card,
+    pub best_score: i32,
+}
+
+impl MisplayFlag {
+    pub fn margin(&self) -> i32 {
+        self.best_score.saturating_sub(self.played_score)
+    }
+
+    /// 1-based trick number, for display.
+    pub fn trick_number(&self) -> usize {
+        self.play_index / 4 + 1
+    }
+}
+
+/// Flags decisions whose advised best card beats the played card by more than
+/// `config.margin_threshold`. The largest `config.max_flags` misplays are kept
+/// and returned in play order. Decisions without advice for the played card are
+/// skipped; ties for best keep the first advised card.
+pub fn flag_misplays(decisions: &[ReviewedDecision], config: &ReviewConfig) -> Vec<MisplayFlag> {
+    let mut flags: Vec<MisplayFlag> = decisions
+        .iter()
+        .filter_map(|decision| {
+            let played_score = decision
+                .advice
+                .iter()
+                .find(|(card, _)| *card == decision.played)
+                .map(|&(_, score)| score)?;
+            let (best, best_score) = decision.advice.iter().copied().fold(
+                None,
+                |acc: Option<(Card, i32)>, (card, score)| match acc {
+                    Some((_, top)) if top >= score => acc,
+                    _ => Some((card, score)),
+                },
+            )?;
+            let flag = MisplayFlag {
+                play_index: decision.play_index,
+                played: decision.played,
+                played_score,
+                best,
+                best_score,
+            };
+            (best != decision.played && flag.margin() > config.margin_threshold).then_some(flag)
+        })
+        .collect();
+    flags.sort_by_key(|flag| (std::cmp::Reverse(flag.margin()), flag.play_index));
+    flags.truncate(config.max_flags);
+    flags.sort_by_key(|flag| flag.play_index);
+    flags
+}
+
+/// Everything the review screen needs for one finished round. Owned by the
+/// controller only between the end of the round and dismissal.
+#[derive(Clone, Debug)]
+pub struct RoundReview {
+    record: RecordedRound,
+    seat: PlayerPosition,
+    difficulty: BotDifficulty,
+    decisions: Vec<ReviewedDecision>,
+    flags: Vec<MisplayFlag>,
+}
+
+impl RoundReview {
+    /// Replays the round to collect advice for each of `config.seat`'s plays.
+    pub fn build(
+        record: RecordedRound,
+        difficulty: BotDifficulty,
+        config: &ReviewConfig,
+    ) -> Result<Self, String> {
+        let mut replay = ReplayController::new(vec![record.clone()])?;
+        let mut decisions = Vec::new();
+        while let Some((seat, played)) = replay.next_play() {
+            if seat == config.seat {
+                decisions.push(ReviewedDecision {
+                    play_index: replay.cursor(),
+                    seat,
+                    played,
+                    advice: replay.advice(difficulty),
+                });
+            }
+            replay.step_forward();
+        }
+        let flags = flag_misplays(&decisions, config);
+        Ok(Self {
+            record,
+            seat: config.seat,
+            difficulty,
+            decisions,
+            flags,
+        })
+    }
+
+    pub fn seat(&self) -> PlayerPosition {
+        self.seat
+    }
+
+    #[cfg(test)]
+    pub fn decisions(&self) -> &[ReviewedDecision] {
+        &self.decisions
+    }
+
+    pub fn flags(&self) -> &[MisplayFlag] {
+        &self.flags
+    }
+
+    /// All four hands as they stood after passing, before the first lead.
+    pub fn dealt_hands(&self) -> [Vec<Card>; 4] {
+        self.replay_at(0).face_up_hands()
+    }
+
+    /// Replay positioned just before play `play_index`, for stepping through
+    /// the round from a flagged decision.
+    pub fn replay_at(&self, play_index: usize) -> ReplayController {
+        let mut replay =
+            ReplayController::new(vec![self.record.clone()]).expect("validated in build");
+        replay.seek(play_index);
+        replay
+    }
+
+    /// Round penalties if the flagged play had been the advised card instead,
+    /// with bots finishing the round at the review difficulty.
+    pub fn simulate_advised(&self, flag: &MisplayFlag) -> Result<[u8; 4], String> {
+        self.replay_at(flag.play_index)
+            .simulate_alternative(flag.best, self.difficulty)
+    }
+
+    /// Penalties actually taken this round.
+    pub fn actual_penalties(&self) -> [u8; 4] {
+        let mut replay = self.replay_at(0);
+        while replay.step_forward().is_some() {}
+        replay.round().penalty_totals()
+    }
+
+    /// Plain-text body for the review screen.
+    pub fn summary_text(&self) -> String {
+        let mut out = String::from("Hands as dealt (after passing):\n");
+        for (idx, cards) in self.dealt_hands().iter().enumerate() {
+            let Some(seat) = PlayerPosition::from_index(idx) else {
+                continue;
+            };
+            let hand = Hand::with_cards(cards.clone());
+            out.push_str(&format!("  {seat}: {hand}\n"));
+        }
+        out.push('\n');
+        out.push_str(&format!(
+            "{} of your plays checked against the advice.\n",
+            self.decisions.len()
+        ));
+        if self.flags.is_empty() {
+            out.push_str("No plays flagged - the advice engine agreed closely with you.\n");
+            return out;
+        }
+        out.push_str("Flagged plays:\n");
+        for flag in &self.flags {
+            out.push_str(&format!(
+                "  Trick {}: played {} ({}); advice preferred {} ({}), margin {}\n",
+                flag.trick_number(),
+                flag.played,
+                flag.played_score,
+                flag.best,
+                flag.best_score,
+                flag.margin()
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MisplayFlag, ReviewConfig, ReviewedDecision, flag_misplays};
+    use hearts_core::model::card::Card;
+    use hearts_core::model::player::PlayerPosition;
+    use hearts_core::model::rank::Rank;
+    use hearts_core::model::suit::Suit;
+
+    fn card(rank: Rank, suit: Suit) -> Card {
+        Card::new(rank, suit)
+    }
+
+    fn decision(play_index: usize, played: Card, advice: &[(Card, i32)]) -> ReviewedDecision {
+        ReviewedDecision {
+            play_index,
+            seat: PlayerPosition::South,
+            played,
+            advice: advice.to_vec(),
+        }
+    }
+
+    fn config(margin_threshold: i32, max_flags: usize) -> ReviewConfig {
+        ReviewConfig {
+            enabled: true,
+            seat: PlayerPosition::South,
+            margin_threshold,
+            max_flags,
+        }
+    }
+
+    #[test]
+    fn flags_only_margins_above_threshold() {
+        let two = card(Rank::Two, Suit::Clubs);
+        let king = card(Rank::King, Suit::Clubs);
+        let decisions = vec![
+            decision(0, king, &[(two, 500), (king, -1_600)]),
+            decision(4, king, &[(two, 500), (king, -1_500)]),
+        ];
+        let flags = flag_misplays(&decisions, &config(2_000, 5));
+        assert_eq!(
+            flags,
+            vec![MisplayFlag {
+                play_index: 0,
+                played: king,
+                played_score: -1_600,
+                best: two,
+                best_score: 500,
+            }]
+        );
+        assert_eq!(flags[0].margin(), 2_100);
+    }
+
+    #[test]
+    fn agreeing_and_forced_plays_are_never_flagged() {
+        let two = card(Rank::Two, Suit::Hearts);
+        let ace = card(Rank::Ace, Suit::Hearts);
+        let decisions = vec![
+            decision(0, ace, &[(two, -900), (ace, 800)]),
+            decision(1, two, &[(two, -5_000)]),
+            decision(2, two, &[(two, 100), (ace, 100)]),
+        ];
+        assert!(flag_misplays(&decisions, &config(0, 5)).is_empty());
+    }
+
+    #[test]
+    fn decisions_without_advice_for_played_card_are_skipped() {
+        let two = card(Rank::Two, Suit::Spades);
+        let ace = card(Rank::Ace, Suit::Spades);
+        let decisions = vec![decision(0, ace, &[(two, 9_000)]), decision(1, ace, &[])];
+        assert!(flag_misplays(&decisions, &config(0, 5)).is_empty());
+    }
+
+    #[test]
+    fn keeps_largest_misplays_in_play_order() {
+        let low = card(Rank::Three, Suit::Diamonds);
+        let high = card(Rank::Ace, Suit::Diamonds);
+        let decisions: Vec<ReviewedDecision> = [(0, 3_000), (4, 9_000), (8, 1_000), (12, 6_000)]
+            .iter()
+            .map(|&(idx, best)| decision(idx, high, &[(low, best), (high, 0)]))
+            .collect();
+        let flags = flag_misplays(&decisions, &config(500, 2));
+        let indices: Vec<usize> = flags.iter().map(|flag| flag.play_index).collect();
+        assert_eq!(indices, vec![4, 12]);
+        assert_eq!(flags[1].trick_number(), 4);
+    }
+
+    #[test]
+    fn ties_for_best_keep_first_advised_card() {
+        let a = card(Rank::Four, Suit::Clubs);
+        let b = card(Rank::Five, Suit::Clubs);
+        let played = card(Rank::Queen, Suit::Clubs);
+        let decisions = vec![decision(3, played, &[(a, 700), (b, 700), (played, 0)])];
+        let flags = flag_misplays(&decisions, &config(100, 5));
+        assert_eq!(flags[0].best, a);
+    }
+}
@@ -0,0 +1,325 @@
+//! Run metadata written next to batch CSVs, and the comparison of two runs.
+//!
+//! `--match-batch` and `--match-mixed` write `<out stem>.run_meta.json`
+//! beside the CSV: versions, crate features, every `MDH_*` variable (the
+//! Hard CLI flags land there too), difficulties, seeds, hands and the
+//! permutation mode. `--compare-runs` diffs the two documents field by field
+//! before comparing results, because two runs that differ in any of these
+//! are not measuring the same thing. The `resources` block (wall-clock, CPU,
+//! peak memory) is measured rather than configured, so it is reported as a
+//! delta instead of as drift.
+
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub const RUN_META_VERSION: u32 = 1;
+
+/// Top-level keys that differ between any two runs and are left out of
+/// [`diff`].
+pub const MEASURED_KEYS: &[&str] = &["resources"];
+
+/// `runs/a/match.csv` -> `runs/a/match.run_meta.json`.
+pub fn sidecar_path(out: &Path) -> PathBuf {
+    let stem = out
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "run".to_string());
+    out.with_file_name(format!("{stem}.run_meta.json"))
+}
+
+/// Metadata for a run of `command`; `run` holds the command's own fields
+/// (`seat`, `difficulties`, `seeds`, `hands`, `permutations`, ...).
+pub fn collect(command: &str, run: Value) -> Value {
+    let mut env: Vec<(String, String)> = std::env::vars()
+        .filter(|(key, _)| key.starts_with("MDH_"))
+        .collect();
+    env.sort();
+    let mut meta = Map::new();
+    meta.insert(
+        crate::persist::SCHEMA_KEY.into(),
+        Value::from(RUN_META_VERSION),
+    );
+    meta.insert("command".into(), Value::from(command));
+    meta.insert(
+        "versions".into(),
+        serde_json::json!({
+            "mdhearts": env!("CARGO_PKG_VERSION"),
+            "hearts_core": hearts_core::AppInfo::version(),
+        }),
+    );
+    meta.insert(
+        "features".into(),
+        serde_json::json!({ "winui-host": cfg!(feature = "winui-host") }),
+    );
+    meta.insert(
+        "env".into(),
+        Value::Object(env.into_iter().map(|(k, v)| (k, Value::from(v))).collect()),
+    );
+    if let Value::Object(fields) = run {
+        meta.extend(fields);
+    }
+    Value::Object(meta)
+}
+
+/// One field that differs between two metadata documents; `None` on a side
+/// that lacks it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    pub path: String,
+    pub left: Option<Value>,
+    pub right: Option<Value>,
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "(missing)".to_string(),
+        };
+        write!(
+            f,
+            "{}: {} vs {}",
+            self.path,
+            show(&self.left),
+            show(&self.right)
+        )
+    }
+}
+
+/// Every leaf path whose value differs, in key order, except
+/// [`MEASURED_KEYS`]. Objects are compared key by key and arrays element by
+/// element; `[n]` marks an array index.
+pub fn diff(left: &Value, right: &Value) -> Vec<FieldDiff> {
+    let configured = |meta: &Value| {
+        let mut meta = meta.clone();
+        if let Value::Object(fields) = &mut meta {
+            for key in MEASURED_KEYS {
+                fields.remove(*key);
+            }
+        }
+        meta
+    };
+    let mut out = Vec::new();
+    diff_into(
+        "",
+        Some(&configured(left)),
+        Some(&configured(right)),
+        &mut out,
+    );
+    out
+}
+
+fn diff_into(path: &str, left: Option<&Value>, right: Option<&Value>, out: &mut Vec<FieldDiff>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    match (left, right) {
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                diff_into(&child(key), a.get(key), b.get(key), out);
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b))) => {
+            for idx in 0..a.len().max(b.len()) {
+                diff_into(&format!("{path}[{idx}]"), a.get(idx), b.get(idx), out);
+            }
+        }
+        (a, b) if a != b => out.push(FieldDiff {
+            path: path.to_string(),
+            left: a.cloned(),
+            right: b.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+/// Per-seed result of a batch CSV: the `delta` column of `--match-batch`,
+/// the `pen` column of `--match-mixed`, or the `pen_<seat>` column of a
+/// permutation run, averaged over a seed's rows.
+pub fn per_seed_values(csv: &str, seat: Option<&str>) -> Option<Vec<(u64, f64)>> {
+    let mut lines = csv.lines();
+    let header: Vec<&str> = lines.next()?.split(',').map(str::trim).collect();
+    let seat_column = seat.map(|seat| {
+        let initial = seat.chars().next().unwrap_or('n').to_ascii_lowercase();
+        format!("pen_{initial}")
+    });
+    let column = ["delta", "pen"]
+        .iter()
+        .find_map(|name| header.iter().position(|h| h == name))
+        .or_else(|| {
+            let seat_column = seat_column.as_deref()?;
+            header.iter().position(|h| *h == seat_column)
+        })?;
+    let mut sums: Vec<(u64, f64, u32)> = Vec::new();
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+        let seed = cells.first()?.parse::<u64>().ok()?;
+        let value = cells.get(column)?.parse::<f64>().ok()?;
+        match sums.last_mut() {
+            Some((last, total, rows)) if *last == seed => {
+                *total += value;
+                *rows += 1;
+            }
+            _ => sums.push((seed, value, 1)),
+        }
+    }
+    Some(
+        sums.into_iter()
+            .map(|(seed, total, rows)| (seed, total / f64::from(rows)))
+            .collect(),
+    )
+}
+
+/// Mean difference (right minus left) with its standard error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Significance {
+    pub paired: bool,
+    pub n: usize,
+    pub mean_delta: f64,
+    pub std_err: f64,
+}
+
+impl Significance {
+    pub fn z(&self) -> f64 {
+        if self.std_err > 0.0 {
+            self.mean_delta / self.std_err
+        } else {
+            0.0
+        }
+    }
+
+    /// Beyond the 95% two-sided normal threshold.
+    pub fn significant(&self) -> bool {
+        self.z().abs() >= 1.96
+    }
+}
+
+fn mean_var(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let var = if values.len() > 1 {
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+    } else {
+        0.0
+    };
+    (mean, var)
+}
+
+/// Paired test over the seeds both runs played; `None` when they share none.
+pub fn paired(left: &[(u64, f64)], right: &[(u64, f64)]) -> Option<Significance> {
+    let deltas: Vec<f64> = left
+        .iter()
+        .filter_map(|(seed, a)| {
+            right
+                .iter()
+                .find(|(other, _)| other == seed)
+                .map(|(_, b)| b - a)
+        })
+        .collect();
+    if deltas.is_empty() {
+        return None;
+    }
+    let (mean, var) = mean_var(&deltas);
+    Some(Significance {
+        paired: true,
+        n: deltas.len(),
+        mean_delta: mean,
+        std_err: (var / deltas.len() as f64).sqrt(),
+    })
+}
+
+/// Welch-style comparison of the two runs' means, ignoring seeds.
+pub fn unpaired(left: &[(u64, f64)], right: &[(u64, f64)]) -> Option<Significance> {
+    if left.is_empty() || right.is_empty() {
+        return None;
+    }
+    let values = |rows: &[(u64, f64)]| rows.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+    let (mean_a, var_a) = mean_var(&values(left));
+    let (mean_b, var_b) = mean_var(&values(right));
+    Some(Significance {
+        paired: false,
+        n: left.len().min(right.len()),
+        mean_delta: mean_b - mean_a,
+        std_err: (var_a / left.len() as f64 + var_b / right.len() as f64).sqrt(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldDiff, diff, paired, per_seed_values, sidecar_path, unpaired};
+    use serde_json::json;
+    use std::path::Path;
+
+    #[test]
+    fn nested_differences_are_reported_by_path() {
+        let left = json!({
+            "versions": { "mdhearts": "1.0.1" },
+            "env": { "MDH_HARD_DETERMINISTIC": "1" },
+            "difficulties": ["normal", "hard"],
+            "seeds": { "start": 100, "count": 50 },
+            "resources": { "wall_secs": 10.0 },
+        });
+        let right = json!({
+            "versions": { "mdhearts": "1.0.2" },
+            "env": { "MDH_HARD_DETERMINISTIC": "1", "MDH_W_HIGH_SPADE_LIABILITY": "300" },
+            "difficulties": ["normal", "search"],
+            "seeds": { "start": 100, "count": 50 },
+            "resources": { "wall_secs": 12.5 },
+        });
+        let paths: Vec<String> = diff(&left, &right).into_iter().map(|d| d.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "difficulties[1]",
+                "env.MDH_W_HIGH_SPADE_LIABILITY",
+                "versions.mdhearts"
+            ]
+        );
+        assert!(diff(&left, &left).is_empty());
+        let missing = FieldDiff {
+            path: "env.MDH_X".to_string(),
+            left: None,
+            right: Some(json!("1")),
+        };
+        assert_eq!(missing.to_string(), "env.MDH_X: (missing) vs \"1\"");
+    }
+
+    #[test]
+    fn per_seed_values_read_every_batch_layout() {
+        let batch = "seed,seat,diffA,diffB,a_pen,b_pen,delta\n1, North, N, H, 4, 0, -4\n2, North, N, H, 0, 13, 13";
+        assert_eq!(
+            per_seed_values(batch, None).unwrap(),
+            [(1, -4.0), (2, 13.0)]
+        );
+        let perms = "seed,perm,mix,pen_n,pen_e,pen_s,pen_w,permutations\n5, 0, nhnn, 0, 26, 0, 0, 2\n5, 1, hnnn, 10, 3, 13, 0, 2";
+        assert_eq!(per_seed_values(perms, Some("East")).unwrap(), [(5, 14.5)]);
+        assert_eq!(per_seed_values(perms, None), None);
+        assert_eq!(
+            sidecar_path(Path::new("runs/a/match.csv")),
+            Path::new("runs/a/match.run_meta.json")
+        );
+    }
+
+    #[test]
+    fn paired_test_uses_shared_seeds_only() {
+        let left = [(1, 4.0), (2, 6.0), (3, 5.0), (9, 100.0)];
+        let right = [(1, 3.0), (2, 5.0), (3, 4.0)];
+        let sig = paired(&left, &right).unwrap();
+        assert!(sig.paired);
+        assert_eq!(sig.n, 3);
+        assert_eq!(sig.mean_delta, -1.0);
+        assert_eq!(sig.std_err, 0.0);
+        assert!(paired(&left, &[(7, 1.0)]).is_none());
+        let loose = unpaired(&left, &[(7, 1.0), (8, 3.0)]).unwrap();
+        assert!(!loose.paired);
+        assert_eq!(loose.mean_delta, 2.0 - 28.75);
+    }
+}
@@ -0,0 +1,331 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! The match in progress, saved when the window closes and offered back at
+//! the next start.
+//!
+//! A save holds the round as it was dealt, before any pass, and every pass
+//! and card since. Resuming deals the same round from the match seed and
+//! replays them through a fresh [`GameController`], the way
+//! [`crate::session::GameSession::rebuild`] replays a journal. The card
+//! tracker, voids, moon reads and received passes come out exactly as they
+//! were, and the seed's later deals are untouched, so play continues as if
+//! the window had never closed. What the table remembers of earlier rounds,
+//! and the names the seats went by, are saved alongside.
+//!
+//! The file is a [`Persisted`] document, so one written by a newer build is
+//! refused with a clear error and an unreadable one is moved aside.
+
+use crate::bot::MatchMemory;
+use crate::controller::GameController;
+use crate::identity::PlayerRoster;
+use crate::persist::{self, Migration, PersistError, Persisted};
+use hearts_core::game::match_state::MatchState;
+use hearts_core::game::serialization::MatchSnapshot;
+use hearts_core::model::card::Card;
+use hearts_core::model::passing::{PassCards, PassingDirection};
+use hearts_core::model::player::PlayerPosition;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const SAVED_GAME_FILE: &str = "mdhearts-saved-game.json";
+
+/// One step taken in the round, in the order it was taken.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RoundAction {
+    Pass {
+        seat: PlayerPosition,
+        cards: Vec<String>,
+    },
+    ResolvePasses,
+    Play {
+        seat: PlayerPosition,
+        card: String,
+    },
+}
+
+/// The round in progress as the controller keeps it for saving.
+#[derive(Debug, Clone)]
+pub struct RoundLog {
+    start: MatchSnapshot,
+    actions: Vec<RoundAction>,
+}
+
+impl RoundLog {
+    /// Starts a log for the round `match_state` has just dealt.
+    pub fn new(match_state: &MatchState) -> Self {
+        Self {
+            start: MatchSnapshot::capture_full(match_state),
+            actions: Vec::new(),
+        }
+    }
+
+    pub fn note_pass(&mut self, seat: PlayerPosition, cards: &PassCards) {
+        self.actions.push(RoundAction::Pass {
+            seat,
+            cards: cards.iter().map(Card::to_string).collect(),
+        });
+    }
+
+    pub fn note_resolve(&mut self) {
+        self.actions.push(RoundAction::ResolvePasses);
+    }
+
+    pub fn note_play(&mut self, seat: PlayerPosition, card: Card) {
+        self.actions.push(RoundAction::Play {
+            seat,
+            card: card.to_string(),
+        });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub schema_version: u32,
+    /// The match as the round in progress was dealt, under the match's
+    /// current rules.
+    pub start: MatchSnapshot,
+    pub actions: Vec<RoundAction>,
+    pub memory: MatchMemory,
+    /// Saves from before names could be changed show the compass names.
+    #[serde(default)]
+    pub players: PlayerRoster,
+}
+
+impl Persisted for SavedGame {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrations() -> &'static [Migration] {
+        &[]
+    }
+}
+
+impl SavedGame {
+    pub fn capture(controller: &GameController) -> Self {
+        let log = controller.round_log();
+        let mut start = log.start.clone();
+        start.rules = *controller.rules();
+        Self {
+            schema_version: Self::SCHEMA_VERSION,
+            start,
+            actions: log.actions.clone(),
+            memory: controller.match_memory(),
+            players: controller.players().clone(),
+        }
+    }
+
+    /// Deals the saved round again and replays it onto a new controller.
+    pub fn resume(self) -> Result<GameController, String> {
+        let direction = self
+            .start
+            .passing_direction
+            .parse::<PassingDirection>()
+            .map_err(|_| format!("unknown passing direction {}", self.start.passing_direction))?;
+        let round = self.start.round.clone().ok_or("the save has no round")?;
+        // Checked here so `restore_full` cannot fail.
+        round.restore(direction)?;
+        let mut controller = GameController::new_from_match_state(self.start.restore_full());
+        controller.set_match_memory(self.memory);
+        controller.set_players(self.players);
+        for (idx, action) in self.actions.iter().enumerate() {
+            let step = |err: String| format!("step {}: {err}", idx + 1);
+            match action {
+                RoundAction::Pass { seat, cards } => {
+                    let cards = parse_cards(cards).map_err(step)?;
+                    let cards = PassCards::from_slice(&cards)
+                        .ok_or_else(|| step(format!("{} passed cards", cards.len())))?;
+                    controller
+                        .submit_pass(*seat, cards)
+                        .map_err(|err| step(format!("{seat:?} pass: {err:?}")))?;
+                }
+                RoundAction::ResolvePasses => {
+                    controller
+                        .resolve_passes()
+                        .map_err(|err| step(format!("resolving passes: {err:?}")))?;
+                }
+                RoundAction::Play { seat, card } => {
+                    let card = card
+                        .parse::<Card>()
+                        .map_err(|err| step(format!("bad card: {err}")))?;
+                    controller
+                        .play(*seat, card)
+                        .map_err(|err| step(format!("{seat:?} {card}: {err:?}")))?;
+                }
+            }
+        }
+        Ok(controller)
+    }
+}
+
+fn parse_cards(codes: &[String]) -> Result<Vec<Card>, String> {
+    codes
+        .iter()
+        .map(|code| {
+            code.parse::<Card>()
+                .map_err(|err| format!("bad card: {err}"))
+        })
+        .collect()
+}
+
+/// Where the GUI keeps the save: next to the executable, like the panic log.
+pub fn saved_game_path() -> PathBuf {
+    match std::env::current_exe() {
+        Ok(mut exe) => {
+            exe.set_file_name(SAVED_GAME_FILE);
+            exe
+        }
+        Err(_) => PathBuf::from(SAVED_GAME_FILE),
+    }
+}
+
+pub fn save_game(path: &Path, controller: &GameController) -> Result<(), PersistError> {
+    persist::save(path, &SavedGame::capture(controller))
+}
+
+/// The controller for the game saved at `path`.
+pub fn load_game(path: &Path) -> Result<GameController, String> {
+    let saved: SavedGame = persist::load_with_migrations(path).map_err(|err| err.to_string())?;
+    saved
+        .resume()
+        .map_err(|err| format!("{} cannot be resumed: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::BotDifficulty;
+    use crate::identity::{Avatar, PlayerIdentity};
+    use crate::persist::quarantine_path;
+    use crate::session::{GameSession, SessionCommand};
+    use hearts_core::model::player::PlayerPosition::{North, South};
+    use std::fs;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("mdhearts_saved_game_{name}.json"));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(quarantine_path(&path));
+        path
+    }
+
+    fn session(controller: GameController) -> GameSession {
+        let mut session = GameSession::new(controller);
+        for seat in PlayerPosition::LOOP {
+            session.set_difficulty(seat, BotDifficulty::NormalHeuristic);
+        }
+        session
+    }
+
+    /// Plays `plays` cards from the start of the round, passing first.
+    fn play_into_round(controller: GameController, plays: usize) -> GameController {
+        let mut session = session(controller);
+        if session.controller().in_passing_phase() {
+            session
+                .apply(SessionCommand::BotPasses { except: None })
+                .unwrap();
+            session.apply(SessionCommand::ResolvePasses).unwrap();
+        }
+        for _ in 0..plays {
+            session.apply(SessionCommand::BotTurn).unwrap();
+        }
+        session.into_controller()
+    }
+
+    /// Plays the rest of the round and deals the next one.
+    fn finish_round(controller: GameController) -> GameController {
+        let mut session = session(controller);
+        while !session.controller().round_ready_for_scoring() {
+            session.apply(SessionCommand::BotTurn).unwrap();
+        }
+        session.apply(SessionCommand::FinishRound).unwrap();
+        session.into_controller()
+    }
+
+    fn table(controller: &GameController) -> Vec<Vec<Card>> {
+        PlayerPosition::LOOP
+            .iter()
+            .map(|&seat| controller.hand(seat))
+            .collect()
+    }
+
+    #[test]
+    fn a_resumed_round_plays_on_like_the_original() {
+        let path = temp_path("mid_trick");
+        let mut controller = GameController::new_with_seed(Some(2253), North);
+        controller.set_target_score(75).unwrap();
+        let controller = play_into_round(finish_round(play_into_round(controller, 0)), 6);
+        save_game(&path, &controller).unwrap();
+        let resumed = load_game(&path).unwrap();
+
+        assert_eq!(resumed.round_number(), 2);
+        assert_eq!(resumed.standings(), controller.standings());
+        assert!(controller.scoreboard().last_round().is_some());
+        assert_eq!(
+            resumed.scoreboard().last_round(),
+            controller.scoreboard().last_round()
+        );
+        assert_eq!(resumed.target_score(), 75);
+        assert_eq!(table(&resumed), table(&controller));
+        let seat = controller.expected_to_play();
+        assert_eq!(resumed.expected_to_play(), seat);
+        assert_eq!(resumed.legal_moves(seat), controller.legal_moves(seat));
+        assert_eq!(
+            resumed.received_cards(South),
+            controller.received_cards(South)
+        );
+        for seat in PlayerPosition::LOOP {
+            assert_eq!(resumed.moon_state(seat), controller.moon_state(seat));
+        }
+        // The bots and the seed's next deal carry on unchanged.
+        let original = finish_round(controller);
+        let resumed = finish_round(resumed);
+        assert_eq!(resumed.standings(), original.standings());
+        assert_eq!(table(&resumed), table(&original));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_save_before_any_pass_resumes_at_the_deal() {
+        let path = temp_path("at_deal");
+        let mut controller = GameController::new_with_seed(Some(7), North);
+        let mut seats = PlayerPosition::LOOP.map(PlayerIdentity::default_for);
+        seats[North.index()] = PlayerIdentity::new("Ada", Some(Avatar::Crown));
+        controller.set_players(PlayerRoster::new(seats).unwrap());
+        save_game(&path, &controller).unwrap();
+        let resumed = load_game(&path).unwrap();
+        assert!(resumed.in_passing_phase());
+        assert_eq!(table(&resumed), table(&controller));
+        assert_eq!(resumed.seat_name(North), "Ada");
+        assert_eq!(resumed.players(), controller.players());
+
+        // A save written before names existed resumes with the compass names.
+        let mut old = serde_json::to_value(SavedGame::capture(&controller)).unwrap();
+        old.as_object_mut().unwrap().remove("players");
+        fs::write(&path, old.to_string()).unwrap();
+        let resumed = load_game(&path).unwrap();
+        assert_eq!(resumed.seat_name(North), "North");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn saves_from_a_newer_build_or_damaged_ones_are_refused() {
+        let path = temp_path("newer");
+        let controller = GameController::new_with_seed(Some(7), North);
+        let mut saved = serde_json::to_value(SavedGame::capture(&controller)).unwrap();
+        saved["schema_version"] = 9.into();
+        fs::write(&path, saved.to_string()).unwrap();
+        let err = load_game(&path).err().expect("the save is refused");
+        assert!(err.contains("schema_version 9"), "{err}");
+        assert!(path.exists(), "a newer save is left for the newer build");
+
+        let path = temp_path("damaged");
+        let mut saved = SavedGame::capture(&play_into_round(controller, 2));
+        saved.actions.swap(4, 5);
+        persist::save(&path, &saved).unwrap();
+        let err = load_game(&path).err().expect("the save is refused");
+        assert!(err.contains("cannot be resumed: step 5"), "{err}");
+
+        fs::write(&path, r#"{"schema_version":1,"start":"#).unwrap();
+        let err = load_game(&path).err().expect("the save is refused");
+        assert!(err.contains("unreadable"), "{err}");
+        assert!(quarantine_path(&path).exists());
+    }
+}
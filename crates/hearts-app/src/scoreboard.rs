@@ -0,0 +1,186 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! What the score HUD shows for each seat, worked out from the
+//! [`ScoreBoard`] alone so the window only has to draw it.
+//!
+//! The classic HUD shows totals. The detailed one adds the last round's
+//! points and the points left before the match ends at its target (100 in
+//! the standard game). The seat or seats with the lowest total are marked as
+//! leading, unless every seat is level. A seat within 26 of the target (74 or
+//! more at 100) is in danger: one bad round would end the match.
+
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::score::ScoreBoard;
+
+/// Points a single bad round can add.
+pub const ROUND_POINTS: u32 = 26;
+
+/// Lowest total a single bad round can carry to `target`.
+pub const fn danger_score(target: u32) -> u32 {
+    target.saturating_sub(ROUND_POINTS)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScoreDisplay {
+    #[default]
+    Classic,
+    Detailed,
+}
+
+impl ScoreDisplay {
+    pub const fn as_u32(self) -> u32 {
+        match self {
+            ScoreDisplay::Classic => 0,
+            ScoreDisplay::Detailed => 1,
+        }
+    }
+
+    pub const fn from_u32(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(ScoreDisplay::Classic),
+            1 => Some(ScoreDisplay::Detailed),
+            _ => None,
+        }
+    }
+
+    pub const fn toggled(self) -> Self {
+        match self {
+            ScoreDisplay::Classic => ScoreDisplay::Detailed,
+            ScoreDisplay::Detailed => ScoreDisplay::Classic,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeatScore {
+    pub total: u32,
+    /// Points scored in the last round, negative after a J♦ bonus; `None`
+    /// before the first one.
+    pub last_round: Option<i32>,
+    /// Points left before the match ends, zero once it has.
+    pub to_target: u32,
+    pub leading: bool,
+    pub danger: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreboardView {
+    pub seats: [SeatScore; 4],
+    pub target: u32,
+}
+
+impl ScoreboardView {
+    pub fn from_scores(scores: &ScoreBoard, target: u32) -> Self {
+        let totals = scores.standings();
+        let low = totals.iter().copied().min().unwrap_or(0);
+        let level = totals.iter().all(|&total| total == low);
+        let last = scores.last_round();
+        let seats = PlayerPosition::LOOP.map(|seat| {
+            let total = totals[seat.index()];
+            SeatScore {
+                total,
+                last_round: last.map(|deltas| deltas[seat.index()]),
+                to_target: target.saturating_sub(total),
+                leading: !level && total == low,
+                danger: (danger_score(target)..target).contains(&total),
+            }
+        });
+        Self { seats, target }
+    }
+
+    #[cfg(test)]
+    pub fn seat(&self, seat: PlayerPosition) -> &SeatScore {
+        &self.seats[seat.index()]
+    }
+
+    #[cfg(test)]
+    pub fn any_danger(&self) -> bool {
+        self.seats.iter().any(|seat| seat.danger)
+    }
+
+    /// HUD rows as label and per-seat cells, N, E, S, W.
+    pub fn rows(&self, display: ScoreDisplay) -> Vec<(String, [String; 4])> {
+        let cells = |cell: fn(&SeatScore) -> String| self.seats.each_ref().map(cell);
+        let mut rows = vec![("Scores".to_string(), cells(|seat| seat.total.to_string()))];
+        if display == ScoreDisplay::Detailed {
+            rows.push((
+                "Last Round".to_string(),
+                cells(|seat| match seat.last_round {
+                    Some(points) => format!("{points:+}"),
+                    None => "-".to_string(),
+                }),
+            ));
+            rows.push((
+                format!("To {}", self.target),
+                cells(|seat| seat.to_target.to_string()),
+            ));
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(totals: [u32; 4]) -> ScoreBoard {
+        let mut scores = ScoreBoard::new();
+        scores.set_totals(totals);
+        scores
+    }
+
+    #[test]
+    fn danger_starts_one_bad_round_from_the_target() {
+        let view = ScoreboardView::from_scores(&board([73, 74, 99, 100]), 100);
+        let danger: Vec<bool> = view.seats.iter().map(|seat| seat.danger).collect();
+        assert_eq!(danger, [false, true, true, false]);
+        assert_eq!(view.seat(PlayerPosition::South).to_target, 1);
+        assert_eq!(view.seat(PlayerPosition::West).to_target, 0);
+        assert!(view.any_danger());
+        assert!(!ScoreboardView::from_scores(&board([0, 10, 73, 40]), 100).any_danger());
+    }
+
+    #[test]
+    fn lowest_totals_lead_unless_everyone_is_level() {
+        let view = ScoreboardView::from_scores(&board([12, 30, 12, 50]), 100);
+        let leading: Vec<bool> = view.seats.iter().map(|seat| seat.leading).collect();
+        assert_eq!(leading, [true, false, true, false]);
+        let level = ScoreboardView::from_scores(&ScoreBoard::new(), 100);
+        assert!(level.seats.iter().all(|seat| !seat.leading));
+    }
+
+    #[test]
+    fn detailed_rows_add_last_round_and_points_to_go() {
+        let mut scores = ScoreBoard::new();
+        let fresh = ScoreboardView::from_scores(&scores, 100);
+        assert_eq!(fresh.rows(ScoreDisplay::Classic).len(), 1);
+        assert_eq!(fresh.rows(ScoreDisplay::Detailed)[1].1[0], "-");
+
+        scores.apply_hand([0, 26, 0, 0]);
+        let rows = ScoreboardView::from_scores(&scores, 100).rows(ScoreDisplay::Detailed);
+        let labels: Vec<&str> = rows.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, ["Scores", "Last Round", "To 100"]);
+        assert_eq!(rows[0].1, ["26", "0", "26", "26"]);
+        assert_eq!(rows[1].1, ["+26", "+0", "+26", "+26"]);
+        assert_eq!(rows[2].1, ["74", "100", "74", "74"]);
+    }
+
+    #[test]
+    fn a_short_game_counts_down_to_its_own_target() {
+        let view = ScoreboardView::from_scores(&board([10, 23, 24, 49]), 50);
+        let danger: Vec<bool> = view.seats.iter().map(|seat| seat.danger).collect();
+        assert_eq!(danger, [false, false, true, true]);
+        assert_eq!(view.seat(PlayerPosition::West).to_target, 1);
+        let rows = view.rows(ScoreDisplay::Detailed);
+        assert_eq!(rows[2].0, "To 50");
+        assert_eq!(rows[2].1, ["40", "27", "26", "1"]);
+    }
+
+    #[test]
+    fn display_setting_round_trips() {
+        for display in [ScoreDisplay::Classic, ScoreDisplay::Detailed] {
+            assert_eq!(ScoreDisplay::from_u32(display.as_u32()), Some(display));
+            assert_eq!(display.toggled().toggled(), display);
+        }
+        assert_eq!(ScoreDisplay::from_u32(7), None);
+    }
+}
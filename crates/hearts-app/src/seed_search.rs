@@ -0,0 +1,369 @@
+//! `--find-seed`: scans seeds for natural deals where the bots show a given
+//! behaviour, for reproducing heuristic bugs.
+//!
+//! Each seed is dealt and played out headlessly like a `--match-mixed` hand.
+//! While it plays, [`HandFacts`] gathers what the predicates read: the
+//! controller's moon states after every play, the final penalties, who held
+//! and who took Q♠, and the pass planner's explanation of every pass. Seeds
+//! are played in parallel batches, but matches are reported in seed order and
+//! a `limit` keeps the lowest matching seeds, so the output does not depend on
+//! the thread count.
+
+use crate::bot::{BotDifficulty, MoonState, PassPlanner, PassWeights};
+use crate::controller::{AutoplayOutcome, GameController};
+use crate::outcome::QUEEN_OF_SPADES;
+use hearts_core::game::match_state::MatchState;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rules::RuleSet;
+use std::fmt::Write as _;
+
+/// Seeds handed to the workers at a time.
+const BATCH: u64 = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Predicate {
+    /// A seat's moon state reached `Committed` during the hand.
+    MoonAttempt,
+    /// A seat took all 26 points.
+    MoonSuccess,
+    /// The seat holding Q♠ after the pass won the trick it fell on.
+    QSpadeSelfCapture,
+    /// A guard penalised a card the planner passed anyway, because every
+    /// alternative scored worse.
+    GuardedPassFallbackUsed,
+}
+
+impl Predicate {
+    pub const ALL: [Predicate; 4] = [
+        Predicate::MoonAttempt,
+        Predicate::MoonSuccess,
+        Predicate::QSpadeSelfCapture,
+        Predicate::GuardedPassFallbackUsed,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Predicate::MoonAttempt => "moon_attempt",
+            Predicate::MoonSuccess => "moon_success",
+            Predicate::QSpadeSelfCapture => "q_spade_self_capture",
+            Predicate::GuardedPassFallbackUsed => "guarded_pass_fallback_used",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Predicate> {
+        Self::ALL
+            .into_iter()
+            .find(|predicate| predicate.name() == name)
+    }
+
+    /// Seats that showed the behaviour in `facts`, in seat order.
+    pub fn seats(self, facts: &HandFacts) -> Vec<PlayerPosition> {
+        match self {
+            Predicate::MoonAttempt => facts.moon_committed.clone(),
+            Predicate::MoonSuccess => PlayerPosition::LOOP
+                .into_iter()
+                .filter(|seat| facts.penalties[seat.index()] == 26)
+                .collect(),
+            Predicate::QSpadeSelfCapture => facts
+                .qs_after_pass
+                .filter(|&holder| facts.qs_captured_by == Some(holder))
+                .into_iter()
+                .collect(),
+            Predicate::GuardedPassFallbackUsed => {
+                facts.pass_guards.iter().map(|(seat, _)| *seat).collect()
+            }
+        }
+    }
+}
+
+/// What the predicates can see of one played hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandFacts {
+    pub seed: u64,
+    pub penalties: [u8; 4],
+    pub moon_committed: Vec<PlayerPosition>,
+    pub qs_after_pass: Option<PlayerPosition>,
+    pub qs_captured_by: Option<PlayerPosition>,
+    /// Seats whose chosen pass kept a guard penalty, with the guards that fired.
+    pub pass_guards: Vec<(PlayerPosition, Vec<String>)>,
+}
+
+/// Deals `seed` under `rules` and plays it out with `diffs` (N,E,S,W).
+pub fn play_hand(
+    seed: u64,
+    diffs: [BotDifficulty; 4],
+    rules: RuleSet,
+    weights: PassWeights,
+) -> HandFacts {
+    let mut controller = GameController::new_from_match_state(MatchState::with_rules(
+        PlayerPosition::North,
+        seed,
+        rules,
+    ));
+    let mut pass_guards = Vec::new();
+    if controller.in_passing_phase() {
+        for seat in PlayerPosition::LOOP {
+            controller.set_bot_difficulty(diffs[seat.index()]);
+            if diffs[seat.index()] != BotDifficulty::EasyLegacy {
+                let ctx = controller.bot_context(seat);
+                let guards = PassPlanner::explain(controller.round().hand(seat), &ctx, weights, 0)
+                    .map(|explanation| {
+                        explanation
+                            .chosen
+                            .per_card
+                            .iter()
+                            .flat_map(|scored| {
+                                scored
+                                    .parts
+                                    .iter()
+                                    .filter(|part| part.is_guard() && part.delta < 0)
+                                    .map(move |part| format!("{} on {}", part.name, scored.card))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                if !guards.is_empty() {
+                    pass_guards.push((seat, guards));
+                }
+            }
+            if let Some(cards) = controller.simple_pass_for(seat) {
+                let _ = controller.submit_pass(seat, cards);
+            }
+        }
+        let _ = controller.resolve_passes();
+    }
+    let qs_after_pass = controller.round().holder_of(QUEEN_OF_SPADES);
+    let mut moon_committed: Vec<PlayerPosition> = Vec::new();
+    loop {
+        if controller.is_round_decided() {
+            if controller.fast_forward_one().is_none() {
+                break;
+            }
+            continue;
+        }
+        let to_play = controller.expected_to_play();
+        controller.set_bot_difficulty(diffs[to_play.index()]);
+        if !matches!(
+            controller.autoplay_one_with_status(to_play.next()),
+            AutoplayOutcome::Played(_, _)
+        ) {
+            break;
+        }
+        for seat in PlayerPosition::LOOP {
+            if controller.moon_state(seat) == MoonState::Committed
+                && !moon_committed.contains(&seat)
+            {
+                moon_committed.push(seat);
+            }
+        }
+    }
+    moon_committed.sort_by_key(|seat| seat.index());
+    HandFacts {
+        seed,
+        penalties: controller.penalties_this_round(),
+        moon_committed,
+        qs_after_pass,
+        qs_captured_by: controller.round().captured_by(QUEEN_OF_SPADES),
+        pass_guards,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    pub predicate: Predicate,
+    pub diffs: [BotDifficulty; 4],
+    pub rules: RuleSet,
+    pub seed_start: u64,
+    pub max_seeds: u64,
+    /// Stop once this many matches are found.
+    pub limit: Option<usize>,
+    pub threads: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedMatch {
+    pub seats: Vec<PlayerPosition>,
+    pub facts: HandFacts,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchReport {
+    pub predicate: Predicate,
+    pub scanned: u64,
+    /// Matching seeds in ascending order.
+    pub matches: Vec<SeedMatch>,
+}
+
+impl SearchReport {
+    /// Matches per seat in N,E,S,W order; a hand counts once per seat.
+    pub fn seat_counts(&self) -> [usize; 4] {
+        let mut counts = [0usize; 4];
+        for found in &self.matches {
+            for seat in &found.seats {
+                counts[seat.index()] += 1;
+            }
+        }
+        counts
+    }
+
+    pub fn match_rate(&self) -> f64 {
+        if self.scanned == 0 {
+            0.0
+        } else {
+            self.matches.len() as f64 / self.scanned as f64
+        }
+    }
+}
+
+/// Plays seeds from `seed_start` until `max_seeds` are scanned or `limit`
+/// matches are found.
+pub fn search(config: &SearchConfig) -> SearchReport {
+    let weights = PassWeights::from_env();
+    let threads = config.threads.max(1);
+    let end = config.seed_start.saturating_add(config.max_seeds);
+    let mut matches = Vec::new();
+    let mut scanned = 0u64;
+    let mut next = config.seed_start;
+    while next < end {
+        let batch_end = next.saturating_add(BATCH).min(end);
+        let seeds: Vec<u64> = (next..batch_end).collect();
+        let chunk = seeds.len().div_ceil(threads);
+        let mut found: Vec<SeedMatch> = std::thread::scope(|scope| {
+            let workers: Vec<_> = seeds
+                .chunks(chunk)
+                .map(|part| {
+                    scope.spawn(move || {
+                        part.iter()
+                            .filter_map(|&seed| {
+                                let facts = play_hand(seed, config.diffs, config.rules, weights);
+                                let seats = config.predicate.seats(&facts);
+                                (!seats.is_empty()).then_some(SeedMatch { seats, facts })
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("seed search worker panicked"))
+                .collect()
+        });
+        found.sort_by_key(|found| found.facts.seed);
+        matches.extend(found);
+        scanned += batch_end - next;
+        next = batch_end;
+        if let Some(limit) = config.limit
+            && matches.len() >= limit
+        {
+            // Report just the seeds up to the limit-th match, as a serial
+            // scan would have.
+            matches.truncate(limit);
+            if let Some(last) = matches.last() {
+                scanned = last.facts.seed - config.seed_start + 1;
+            }
+            break;
+        }
+    }
+    SearchReport {
+        predicate: config.predicate,
+        scanned,
+        matches,
+    }
+}
+
+pub fn render_text(report: &SearchReport) -> String {
+    let mut out = String::new();
+    for found in &report.matches {
+        let seats: Vec<String> = found.seats.iter().map(|seat| seat.to_string()).collect();
+        let penalties: Vec<String> = found
+            .facts
+            .penalties
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let _ = write!(
+            out,
+            "seed {} seats={} penalties={}",
+            found.facts.seed,
+            seats.join(","),
+            penalties.join(",")
+        );
+        if report.predicate == Predicate::GuardedPassFallbackUsed {
+            let guards: Vec<String> = found
+                .facts
+                .pass_guards
+                .iter()
+                .map(|(seat, guards)| format!("{seat}: {}", guards.join("; ")))
+                .collect();
+            let _ = write!(out, " guards=[{}]", guards.join(" | "));
+        }
+        out.push('\n');
+    }
+    let counts = report.seat_counts();
+    let _ = writeln!(
+        out,
+        "{}: {} of {} seeds matched ({:.2}%); by seat N={} E={} S={} W={}",
+        report.predicate.name(),
+        report.matches.len(),
+        report.scanned,
+        report.match_rate() * 100.0,
+        counts[0],
+        counts[1],
+        counts[2],
+        counts[3]
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HandFacts, Predicate, SearchConfig, search};
+    use crate::bot::BotDifficulty;
+    use hearts_core::model::player::PlayerPosition::{East, North, South};
+    use hearts_core::model::rules::RuleSet;
+
+    fn facts() -> HandFacts {
+        HandFacts {
+            seed: 7,
+            penalties: [0, 26, 0, 0],
+            moon_committed: vec![East],
+            qs_after_pass: Some(South),
+            qs_captured_by: Some(East),
+            pass_guards: vec![(North, vec!["two_of_clubs_keep on 2C".to_string()])],
+        }
+    }
+
+    #[test]
+    fn predicates_parse_by_name_and_pick_out_seats() {
+        for predicate in Predicate::ALL {
+            assert_eq!(Predicate::parse(predicate.name()), Some(predicate));
+        }
+        assert_eq!(Predicate::parse("moon"), None);
+        let facts = facts();
+        assert_eq!(Predicate::MoonAttempt.seats(&facts), [East]);
+        assert_eq!(Predicate::MoonSuccess.seats(&facts), [East]);
+        assert!(Predicate::QSpadeSelfCapture.seats(&facts).is_empty());
+        assert_eq!(Predicate::GuardedPassFallbackUsed.seats(&facts), [North]);
+    }
+
+    #[test]
+    fn results_do_not_depend_on_thread_count() {
+        let config = |threads| SearchConfig {
+            predicate: Predicate::QSpadeSelfCapture,
+            diffs: [BotDifficulty::NormalHeuristic; 4],
+            rules: RuleSet::STANDARD,
+            seed_start: 10,
+            max_seeds: 40,
+            limit: Some(3),
+            threads,
+        };
+        let serial = search(&config(1));
+        let parallel = search(&config(4));
+        assert_eq!(serial.matches, parallel.matches);
+        assert_eq!(serial.scanned, parallel.scanned);
+        assert_eq!(serial.matches.len(), 3);
+        let seeds: Vec<u64> = serial.matches.iter().map(|m| m.facts.seed).collect();
+        assert!(seeds.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(serial.scanned, seeds[2] - 10 + 1);
+    }
+}
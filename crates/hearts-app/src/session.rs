@@ -0,0 +1,683 @@
+#![cfg_attr(not(all(test, windows)), allow(dead_code))]
+//! Event-sourced game flow on top of [`GameController`].
+//!
+//! A front-end drives a [`GameSession`] with [`SessionCommand`]s and reads
+//! back the [`SessionEvent`]s each command produced. It does not call the
+//! controller's mutators and then poll its state to work out what changed.
+//! Bots play through commands too, at the difficulty the session keeps for
+//! their seat, so a front-end with a mixed table needs no difficulty juggling.
+//!
+//! Every event is also appended to the session's journal. The journal holds
+//! each pass and card played, so [`GameSession::rebuild`] can replay it onto
+//! a fresh controller for the same seed without asking any bot again. That
+//! is enough for undo, replays, or a network peer catching up.
+
+use crate::bot::BotDifficulty;
+use crate::controller::{AutoplayOutcome, GameController, PassEvent};
+use hearts_core::model::card::Card;
+use hearts_core::model::passing::{PassCards, PassingDirection, PassingError};
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::round::{PlayError, PlayOutcome, RoundPhase};
+use std::fmt;
+
+/// One step of game flow a front-end asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionCommand {
+    /// A pass chosen by the player at `seat`.
+    HumanPass {
+        seat: PlayerPosition,
+        cards: PassCards,
+    },
+    /// Every seat but `except` that has not passed yet passes at its own
+    /// difficulty.
+    BotPasses { except: Option<PlayerPosition> },
+    /// Exchanges the submitted passes.
+    ResolvePasses,
+    /// A card chosen by the player at `seat`.
+    HumanPlay { seat: PlayerPosition, card: Card },
+    /// The seat to move plays at its own difficulty.
+    BotTurn,
+    /// A card a bot chose away from the session, such as on a think thread
+    /// or as its timeout fallback.
+    BotPlay { seat: PlayerPosition, card: Card },
+    /// Once the round is decided, the seat to move plays its lowest card
+    /// without asking its bot.
+    FastForward,
+    /// Clears the completed trick from the table.
+    AdvanceTrick,
+    /// Scores a finished round and deals the next one.
+    FinishRound,
+    /// Redeals the current round from the start.
+    RestartRound,
+}
+
+/// Something that happened, in the order it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionEvent {
+    PassSubmitted {
+        seat: PlayerPosition,
+        cards: PassCards,
+    },
+    /// Every seat has passed, so the cards can start moving.
+    PassesComplete,
+    PassesResolved,
+    CardsReceived {
+        seat: PlayerPosition,
+        from: PlayerPosition,
+        cards: PassCards,
+    },
+    CardPlayed {
+        seat: PlayerPosition,
+        card: Card,
+    },
+    /// The bot ran out of think time and its timeout fallback played no card.
+    BotTimedOut {
+        seat: PlayerPosition,
+    },
+    TrickCompleted {
+        winner: PlayerPosition,
+        penalties: u8,
+    },
+    TrickCollected {
+        winner: PlayerPosition,
+        plays: Vec<(PlayerPosition, Card)>,
+    },
+    RoundScored {
+        round: u32,
+        penalties: [u8; 4],
+    },
+    RoundStarted {
+        round: u32,
+        direction: PassingDirection,
+    },
+    RoundRestarted {
+        round: u32,
+    },
+    MatchOver {
+        winner: PlayerPosition,
+    },
+}
+
+impl SessionEvent {
+    /// The command that reproduces this event on replay. Events that follow
+    /// from another one, like a trick completing after its last card, have
+    /// none.
+    fn replay_command(&self) -> Option<SessionCommand> {
+        match *self {
+            SessionEvent::PassSubmitted { seat, cards } => {
+                Some(SessionCommand::HumanPass { seat, cards })
+            }
+            SessionEvent::PassesResolved => Some(SessionCommand::ResolvePasses),
+            SessionEvent::CardPlayed { seat, card } => {
+                Some(SessionCommand::HumanPlay { seat, card })
+            }
+            SessionEvent::TrickCollected { .. } => Some(SessionCommand::AdvanceTrick),
+            SessionEvent::RoundScored { .. } => Some(SessionCommand::FinishRound),
+            SessionEvent::RoundRestarted { .. } => Some(SessionCommand::RestartRound),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionError {
+    Pass(PassingError),
+    Play(PlayError),
+    /// The command does not fit the current phase of the round.
+    WrongPhase(&'static str),
+    /// Replaying a journal produced different events from the recorded ones.
+    Diverged {
+        index: usize,
+    },
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Pass(err) => write!(f, "pass rejected: {err:?}"),
+            SessionError::Play(err) => write!(f, "play rejected: {err:?}"),
+            SessionError::WrongPhase(what) => write!(f, "{what}"),
+            SessionError::Diverged { index } => {
+                write!(f, "journal replay diverged at event {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl From<PassingError> for SessionError {
+    fn from(err: PassingError) -> Self {
+        SessionError::Pass(err)
+    }
+}
+
+impl From<PlayError> for SessionError {
+    fn from(err: PlayError) -> Self {
+        SessionError::Play(err)
+    }
+}
+
+pub struct GameSession {
+    controller: GameController,
+    difficulties: [BotDifficulty; 4],
+    journal: Vec<SessionEvent>,
+}
+
+impl GameSession {
+    /// Wraps `controller`; every seat plays at its current bot difficulty.
+    pub fn new(controller: GameController) -> Self {
+        let difficulty = controller.bot_difficulty();
+        Self {
+            controller,
+            difficulties: [difficulty; 4],
+            journal: Vec::new(),
+        }
+    }
+
+    pub fn with_difficulties(mut self, difficulties: [BotDifficulty; 4]) -> Self {
+        self.difficulties = difficulties;
+        self
+    }
+
+    pub fn set_difficulty(&mut self, seat: PlayerPosition, difficulty: BotDifficulty) {
+        self.difficulties[seat.index()] = difficulty;
+    }
+
+    pub fn difficulty(&self, seat: PlayerPosition) -> BotDifficulty {
+        self.difficulties[seat.index()]
+    }
+
+    /// Read access for rendering; game flow goes through [`apply`](Self::apply).
+    pub fn controller(&self) -> &GameController {
+        &self.controller
+    }
+
+    /// For settings and bookkeeping the journal does not hold, like pacing,
+    /// think time or the play recorder. Passes, plays and rounds go through
+    /// [`apply`](Self::apply).
+    pub fn controller_mut(&mut self) -> &mut GameController {
+        &mut self.controller
+    }
+
+    /// Sets every seat, and the controller's own difficulty that think
+    /// requests are prepared with, to `difficulty`.
+    pub fn set_bot_difficulty(&mut self, difficulty: BotDifficulty) {
+        self.difficulties = [difficulty; 4];
+        self.controller.set_bot_difficulty(difficulty);
+    }
+
+    /// Every event so far, oldest first.
+    pub fn journal(&self) -> &[SessionEvent] {
+        &self.journal
+    }
+
+    pub fn into_controller(self) -> GameController {
+        self.controller
+    }
+
+    /// Replays `journal` onto `controller`, which must hold the same deal
+    /// the journal started from. Bots are not consulted, so the result does
+    /// not depend on their difficulty or think time, and bot timeouts, which
+    /// changed nothing, are dropped.
+    pub fn rebuild(
+        controller: GameController,
+        journal: &[SessionEvent],
+    ) -> Result<Self, SessionError> {
+        let mut session = Self::new(controller);
+        for command in journal.iter().filter_map(SessionEvent::replay_command) {
+            session.apply(command)?;
+        }
+        let mut recorded = journal
+            .iter()
+            .filter(|event| !matches!(event, SessionEvent::BotTimedOut { .. }));
+        let mut replayed = session.journal.iter();
+        let mut index = 0;
+        loop {
+            match (replayed.next(), recorded.next()) {
+                (None, None) => return Ok(session),
+                (left, right) if left == right => index += 1,
+                _ => return Err(SessionError::Diverged { index }),
+            }
+        }
+    }
+
+    /// Runs `command` and returns the events it produced, which are also
+    /// appended to the journal. A rejected command changes nothing.
+    pub fn apply(&mut self, command: SessionCommand) -> Result<Vec<SessionEvent>, SessionError> {
+        let mut events = Vec::new();
+        match command {
+            SessionCommand::HumanPass { seat, cards } => {
+                self.controller.submit_pass(seat, cards)?;
+                events.push(SessionEvent::PassSubmitted { seat, cards });
+                self.push_passes_complete(&mut events);
+            }
+            SessionCommand::BotPasses { except } => {
+                if !self.controller.in_passing_phase() {
+                    return Err(SessionError::WrongPhase("no passes are due"));
+                }
+                let submitted = match self.controller.round().phase() {
+                    RoundPhase::Passing(state) => *state.submissions(),
+                    RoundPhase::Playing | RoundPhase::Complete => [None; 4],
+                };
+                for seat in PlayerPosition::LOOP {
+                    if Some(seat) == except || submitted[seat.index()].is_some() {
+                        continue;
+                    }
+                    self.controller
+                        .set_bot_difficulty(self.difficulties[seat.index()]);
+                    if let Some(cards) = self.controller.simple_pass_for(seat) {
+                        self.controller.submit_pass(seat, cards)?;
+                        events.push(SessionEvent::PassSubmitted { seat, cards });
+                    }
+                }
+                self.push_passes_complete(&mut events);
+            }
+            SessionCommand::ResolvePasses => {
+                if !self.controller.in_passing_phase() {
+                    return Err(SessionError::WrongPhase("no passes to resolve"));
+                }
+                self.controller.resolve_passes()?;
+                for event in self.controller.take_pass_events() {
+                    match event {
+                        PassEvent::Resolved => events.push(SessionEvent::PassesResolved),
+                        PassEvent::Received { seat, from, cards } => {
+                            events.push(SessionEvent::CardsReceived { seat, from, cards })
+                        }
+                        PassEvent::AllSubmitted => {}
+                    }
+                }
+            }
+            SessionCommand::HumanPlay { seat, card } => {
+                let outcome = self.controller.play(seat, card)?;
+                self.push_play(&mut events, seat, card, outcome);
+            }
+            SessionCommand::BotTurn => {
+                let seat = self.expect_turn()?;
+                self.controller
+                    .set_bot_difficulty(self.difficulties[seat.index()]);
+                let completed = self.controller.round().tricks_completed();
+                match self.controller.autoplay_one_with_status(seat.next()) {
+                    AutoplayOutcome::Played(seat, card) => {
+                        events.push(SessionEvent::CardPlayed { seat, card });
+                        self.push_trick_completion(&mut events, completed);
+                    }
+                    AutoplayOutcome::SkippedTimeout => {
+                        events.push(SessionEvent::BotTimedOut { seat })
+                    }
+                    AutoplayOutcome::NoLegal | AutoplayOutcome::NotExpected => {
+                        return Err(SessionError::WrongPhase("no bot move is due"));
+                    }
+                }
+            }
+            SessionCommand::BotPlay { seat, card } => {
+                let completed = self.controller.round().tricks_completed();
+                self.controller.apply_bot_move(seat, card)?;
+                events.push(SessionEvent::CardPlayed { seat, card });
+                self.push_trick_completion(&mut events, completed);
+            }
+            SessionCommand::FastForward => {
+                self.expect_turn()?;
+                let completed = self.controller.round().tricks_completed();
+                let (seat, card) = self
+                    .controller
+                    .fast_forward_one()
+                    .ok_or(SessionError::WrongPhase("the round is not decided yet"))?;
+                events.push(SessionEvent::CardPlayed { seat, card });
+                self.push_trick_completion(&mut events, completed);
+            }
+            SessionCommand::AdvanceTrick => {
+                let summary = self
+                    .controller
+                    .take_last_trick_summary()
+                    .ok_or(SessionError::WrongPhase("no completed trick to collect"))?;
+                events.push(SessionEvent::TrickCollected {
+                    winner: summary.winner,
+                    plays: summary.plays,
+                });
+            }
+            SessionCommand::FinishRound => {
+                if self.controller.match_over() {
+                    return Err(SessionError::WrongPhase("the match is over"));
+                }
+                if !self.controller.round_ready_for_scoring() {
+                    return Err(SessionError::WrongPhase("the round is still being played"));
+                }
+                let round = self.controller.round_number();
+                let penalties = self.controller.penalties_this_round();
+                let winner = self.controller.finish_round_if_ready();
+                events.push(SessionEvent::RoundScored { round, penalties });
+                match winner {
+                    Some(winner) => events.push(SessionEvent::MatchOver { winner }),
+                    None => events.push(SessionEvent::RoundStarted {
+                        round: self.controller.round_number(),
+                        direction: self.controller.passing_direction(),
+                    }),
+                }
+            }
+            SessionCommand::RestartRound => {
+                self.controller.restart_round();
+                events.push(SessionEvent::RoundRestarted {
+                    round: self.controller.round_number(),
+                });
+            }
+        }
+        self.journal.extend(events.iter().cloned());
+        Ok(events)
+    }
+
+    fn expect_turn(&self) -> Result<PlayerPosition, SessionError> {
+        if self.controller.in_passing_phase() {
+            return Err(SessionError::WrongPhase("passes are still due"));
+        }
+        if self.controller.round_ready_for_scoring() {
+            return Err(SessionError::WrongPhase("every trick has been played"));
+        }
+        Ok(self.controller.expected_to_play())
+    }
+
+    fn push_passes_complete(&mut self, events: &mut Vec<SessionEvent>) {
+        if self
+            .controller
+            .take_pass_events()
+            .contains(&PassEvent::AllSubmitted)
+        {
+            events.push(SessionEvent::PassesComplete);
+        }
+    }
+
+    fn push_play(
+        &self,
+        events: &mut Vec<SessionEvent>,
+        seat: PlayerPosition,
+        card: Card,
+        outcome: PlayOutcome,
+    ) {
+        events.push(SessionEvent::CardPlayed { seat, card });
+        if let PlayOutcome::TrickCompleted { winner, penalties } = outcome {
+            events.push(SessionEvent::TrickCompleted { winner, penalties });
+        }
+    }
+
+    /// Bot and fast-forward plays go through the controller without
+    /// returning the outcome, so a completed trick is read off the history.
+    fn push_trick_completion(&self, events: &mut Vec<SessionEvent>, completed_before: usize) {
+        let round = self.controller.round();
+        if round.tricks_completed() == completed_before {
+            return;
+        }
+        if let Some(trick) = round.trick_history().last()
+            && let Some(winner) = trick.winner()
+        {
+            events.push(SessionEvent::TrickCompleted {
+                winner,
+                penalties: trick.penalty_total(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hearts_core::model::rules::{JACK_OF_DIAMONDS, RuleSet};
+
+    fn session(seed: u64) -> GameSession {
+        let mut controller = GameController::new_with_seed(Some(seed), PlayerPosition::North);
+        controller.set_bot_difficulty(BotDifficulty::NormalHeuristic);
+        GameSession::new(controller)
+    }
+
+    fn play_round(session: &mut GameSession) {
+        if session.controller().in_passing_phase() {
+            session
+                .apply(SessionCommand::BotPasses { except: None })
+                .unwrap();
+            session.apply(SessionCommand::ResolvePasses).unwrap();
+        }
+        while !session.controller().round_ready_for_scoring() {
+            let events = session.apply(SessionCommand::BotTurn).unwrap();
+            if matches!(events.last(), Some(SessionEvent::TrickCompleted { .. })) {
+                session.apply(SessionCommand::AdvanceTrick).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn a_round_runs_headless_from_deal_to_score() {
+        let mut session = session(11);
+        let human = PlayerPosition::South;
+        let cards = session.controller().simple_pass_for(human).unwrap();
+        let submitted = session
+            .apply(SessionCommand::HumanPass { seat: human, cards })
+            .unwrap();
+        assert_eq!(
+            submitted,
+            [SessionEvent::PassSubmitted { seat: human, cards }]
+        );
+        let others = session
+            .apply(SessionCommand::BotPasses {
+                except: Some(human),
+            })
+            .unwrap();
+        assert_eq!(others.len(), 4);
+        assert_eq!(others[3], SessionEvent::PassesComplete);
+        let resolved = session.apply(SessionCommand::ResolvePasses).unwrap();
+        assert_eq!(resolved[0], SessionEvent::PassesResolved);
+        assert_eq!(resolved.len(), 5);
+        assert!(matches!(
+            session.controller().round().phase(),
+            RoundPhase::Playing
+        ));
+
+        play_round(&mut session);
+        let journal = session.journal();
+        let plays = journal
+            .iter()
+            .filter(|event| matches!(event, SessionEvent::CardPlayed { .. }))
+            .count();
+        let tricks = journal
+            .iter()
+            .filter(|event| matches!(event, SessionEvent::TrickCompleted { .. }))
+            .count();
+        assert_eq!((plays, tricks), (52, 13));
+
+        let scored = session.apply(SessionCommand::FinishRound).unwrap();
+        let SessionEvent::RoundScored { round, penalties } = scored[0] else {
+            panic!("expected a score first: {scored:?}");
+        };
+        assert_eq!(round, 1);
+        assert_eq!(penalties.iter().map(|&p| p as u32).sum::<u32>(), 26);
+        assert!(matches!(
+            scored[1],
+            SessionEvent::RoundStarted { round: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn rejected_commands_leave_the_journal_alone() {
+        let mut session = session(12);
+        assert_eq!(
+            session.apply(SessionCommand::BotTurn),
+            Err(SessionError::WrongPhase("passes are still due"))
+        );
+        assert!(matches!(
+            session.apply(SessionCommand::FinishRound),
+            Err(SessionError::WrongPhase(_))
+        ));
+        session
+            .apply(SessionCommand::BotPasses { except: None })
+            .unwrap();
+        session.apply(SessionCommand::ResolvePasses).unwrap();
+        let seat = session.controller().expected_to_play();
+        let wrong = session
+            .controller()
+            .hand(seat.next())
+            .into_iter()
+            .next()
+            .unwrap();
+        let before = session.journal().len();
+        assert!(matches!(
+            session.apply(SessionCommand::HumanPlay {
+                seat: seat.next(),
+                card: wrong,
+            }),
+            Err(SessionError::Play(PlayError::OutOfTurn { .. }))
+        ));
+        assert_eq!(
+            session.apply(SessionCommand::AdvanceTrick),
+            Err(SessionError::WrongPhase("no completed trick to collect"))
+        );
+        assert_eq!(session.journal().len(), before);
+    }
+
+    #[test]
+    fn seats_play_at_their_own_difficulty() {
+        let mut session = session(13).with_difficulties([
+            BotDifficulty::EasyLegacy,
+            BotDifficulty::NormalHeuristic,
+            BotDifficulty::EasyLegacy,
+            BotDifficulty::NormalHeuristic,
+        ]);
+        let north = session.controller().hand(PlayerPosition::North);
+        session
+            .apply(SessionCommand::BotPasses { except: None })
+            .unwrap();
+        // Easy passes its first cards in hand order.
+        let easy_pass = match &session.journal()[0] {
+            SessionEvent::PassSubmitted { seat, cards } => {
+                assert_eq!(*seat, PlayerPosition::North);
+                *cards
+            }
+            other => panic!("unexpected {other:?}"),
+        };
+        assert_eq!(&easy_pass[..], &north[..3]);
+        session.set_difficulty(PlayerPosition::East, BotDifficulty::EasyLegacy);
+        assert_eq!(
+            session.difficulty(PlayerPosition::East),
+            BotDifficulty::EasyLegacy
+        );
+    }
+
+    #[test]
+    fn cards_chosen_off_the_session_are_journaled_like_bot_turns() {
+        let mut session = session(17);
+        session
+            .apply(SessionCommand::BotPasses { except: None })
+            .unwrap();
+        session.apply(SessionCommand::ResolvePasses).unwrap();
+        let seat = session.controller().expected_to_play().next();
+        let card = session.controller().hand(seat).into_iter().next().unwrap();
+        assert!(matches!(
+            session.apply(SessionCommand::BotPlay { seat, card }),
+            Err(SessionError::Play(PlayError::OutOfTurn { .. }))
+        ));
+        let mut played = Vec::new();
+        for _ in 0..4 {
+            let seat = session.controller().expected_to_play();
+            let card = session.controller().legal_moves(seat)[0];
+            let events = session
+                .apply(SessionCommand::BotPlay { seat, card })
+                .unwrap();
+            assert_eq!(events[0], SessionEvent::CardPlayed { seat, card });
+            played.push((seat, card));
+        }
+        let Some(&SessionEvent::TrickCompleted { winner, .. }) = session.journal().last() else {
+            panic!("expected a completed trick: {:?}", session.journal());
+        };
+        let collected = session.apply(SessionCommand::AdvanceTrick).unwrap();
+        assert_eq!(
+            collected,
+            [SessionEvent::TrickCollected {
+                winner,
+                plays: played
+            }]
+        );
+    }
+
+    #[test]
+    fn fast_forward_waits_for_a_decided_round() {
+        let mut session = session(16);
+        session
+            .apply(SessionCommand::BotPasses { except: None })
+            .unwrap();
+        session.apply(SessionCommand::ResolvePasses).unwrap();
+        assert_eq!(
+            session.apply(SessionCommand::FastForward),
+            Err(SessionError::WrongPhase("the round is not decided yet"))
+        );
+        while !session.controller().is_round_decided() {
+            session.apply(SessionCommand::BotTurn).unwrap();
+        }
+        while !session.controller().round_ready_for_scoring() {
+            session.apply(SessionCommand::FastForward).unwrap();
+        }
+        let controller = session.into_controller();
+        assert_eq!(controller.round().tricks_completed(), 13);
+    }
+
+    #[test]
+    fn fast_forward_under_the_omnibus_rule_waits_for_the_jack() {
+        let mut controller = GameController::new_with_seed(Some(21), PlayerPosition::North);
+        controller.set_bot_difficulty(BotDifficulty::NormalHeuristic);
+        controller.set_jack_of_diamonds(true);
+        let mut session = GameSession::new(controller);
+        session
+            .apply(SessionCommand::BotPasses { except: None })
+            .unwrap();
+        session.apply(SessionCommand::ResolvePasses).unwrap();
+        let round = |session: &GameSession| session.controller().round().clone();
+        while !round(&session).is_decided(&RuleSet::STANDARD) {
+            session.apply(SessionCommand::BotTurn).unwrap();
+        }
+        // Every heart and Q♠ is gone, but J♦ can still move ten points.
+        assert_eq!(round(&session).captured_by(JACK_OF_DIAMONDS), None);
+        while round(&session).captured_by(JACK_OF_DIAMONDS).is_none() {
+            assert_eq!(
+                session.apply(SessionCommand::FastForward),
+                Err(SessionError::WrongPhase("the round is not decided yet"))
+            );
+            session.apply(SessionCommand::BotTurn).unwrap();
+        }
+        while !session.controller().round_ready_for_scoring() {
+            session.apply(SessionCommand::FastForward).unwrap();
+        }
+    }
+
+    #[test]
+    fn journal_rebuilds_the_same_game_without_bots() {
+        let mut original = session(14);
+        play_round(&mut original);
+        original.apply(SessionCommand::FinishRound).unwrap();
+        original
+            .apply(SessionCommand::BotPasses { except: None })
+            .unwrap();
+        original.apply(SessionCommand::ResolvePasses).unwrap();
+        for _ in 0..5 {
+            original.apply(SessionCommand::BotTurn).unwrap();
+        }
+
+        let mut fresh = GameController::new_with_seed(Some(14), PlayerPosition::North);
+        // Easy would play differently; replay must not ask it.
+        fresh.set_bot_difficulty(BotDifficulty::EasyLegacy);
+        let rebuilt = GameSession::rebuild(fresh, original.journal()).unwrap();
+        assert_eq!(rebuilt.journal(), original.journal());
+        assert_eq!(
+            rebuilt.controller().standings(),
+            original.controller().standings()
+        );
+        for seat in PlayerPosition::LOOP {
+            assert_eq!(
+                rebuilt.controller().hand(seat),
+                original.controller().hand(seat)
+            );
+        }
+        assert_eq!(
+            rebuilt.controller().trick_plays(),
+            original.controller().trick_plays()
+        );
+
+        let other_deal = GameController::new_with_seed(Some(15), PlayerPosition::North);
+        assert!(GameSession::rebuild(other_deal, original.journal()).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! Shadow check: play rounds with one difficulty and, at every decision, ask a
+//! second difficulty what it would have done from the identical context.
+//! Disagreements are kept with enough context to reproduce and reconcile them.
+
+use crate::bot::{
+    BotContext, BotDifficulty, PassPlanner, PlayPlanner, PlayPlannerHard, RolloutPolicy,
+    card_sort_key,
+};
+use crate::controller::{AutoplayOutcome, GameController};
+use hearts_core::model::card::{Card, CardList};
+use hearts_core::model::passing::PassCards;
+use hearts_core::model::player::PlayerPosition;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShadowPhase {
+    Pass,
+    /// Tricks 1-4.
+    Early,
+    /// Tricks 5-9.
+    Mid,
+    /// Tricks 10-13.
+    Late,
+}
+
+impl ShadowPhase {
+    pub const ALL: [ShadowPhase; 4] = [
+        ShadowPhase::Pass,
+        ShadowPhase::Early,
+        ShadowPhase::Mid,
+        ShadowPhase::Late,
+    ];
+
+    /// Phase for a play made during `trick` (1-based).
+    pub fn for_trick(trick: usize) -> Self {
+        match trick {
+            0..=4 => ShadowPhase::Early,
+            5..=9 => ShadowPhase::Mid,
+            _ => ShadowPhase::Late,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ShadowPhase::Pass => "pass",
+            ShadowPhase::Early => "early",
+            ShadowPhase::Mid => "mid",
+            ShadowPhase::Late => "late",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// One decision where the two difficulties chose differently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShadowDisagreement {
+    pub seed: u64,
+    pub seat: PlayerPosition,
+    pub phase: ShadowPhase,
+    /// 1-based trick number; 0 for passes.
+    pub trick: usize,
+    pub primary: String,
+    pub shadow: String,
+    /// Seat's hand before the decision.
+    pub hand: String,
+    /// Cards already in the current trick, in play order.
+    pub trick_so_far: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShadowSummary {
+    pub decisions: [u32; 4],
+    pub disagreements: [u32; 4],
+}
+
+impl ShadowSummary {
+    fn record(&mut self, phase: ShadowPhase, agree: bool) {
+        self.decisions[phase.index()] += 1;
+        if !agree {
+            self.disagreements[phase.index()] += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: &ShadowSummary) {
+        for idx in 0..4 {
+            self.decisions[idx] += other.decisions[idx];
+            self.disagreements[idx] += other.disagreements[idx];
+        }
+    }
+
+    pub fn rate(&self, phase: ShadowPhase) -> Option<f64> {
+        let decisions = self.decisions[phase.index()];
+        (decisions > 0).then(|| self.disagreements[phase.index()] as f64 / decisions as f64)
+    }
+
+    pub fn total_rate(&self) -> Option<f64> {
+        let decisions: u32 = self.decisions.iter().sum();
+        let disagreements: u32 = self.disagreements.iter().sum();
+        (decisions > 0).then(|| disagreements as f64 / decisions as f64)
+    }
+
+    pub fn markdown(&self, primary: BotDifficulty, shadow: BotDifficulty) -> String {
+        let rate = |value: Option<f64>| {
+            value.map_or_else(|| "-".to_string(), |v| format!("{:.1}%", v * 100.0))
+        };
+        let mut out = format!(
+            "Shadow check: primary={} shadow={}\n| phase | decisions | disagreements | rate |\n|---|---:|---:|---:|\n",
+            crate::outcome::agent_label(primary),
+            crate::outcome::agent_label(shadow)
+        );
+        for phase in ShadowPhase::ALL {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                phase.label(),
+                self.decisions[phase.index()],
+                self.disagreements[phase.index()],
+                rate(self.rate(phase))
+            ));
+        }
+        out.push_str(&format!(
+            "| all | {} | {} | {} |\n",
+            self.decisions.iter().sum::<u32>(),
+            self.disagreements.iter().sum::<u32>(),
+            rate(self.total_rate())
+        ));
+        out
+    }
+}
+
+fn sorted_pass(cards: PassCards) -> PassCards {
+    let mut sorted = cards.to_vec();
+    sorted.sort_by_key(|card| card_sort_key(*card));
+    PassCards::from_slice(&sorted).unwrap_or(cards)
+}
+
+/// Pass `difficulty` would choose from `ctx`; Easy takes the first cards of
+/// the hand, as the controller does.
+pub(crate) fn pass_for(ctx: &BotContext<'_>) -> Option<PassCards> {
+    let hand = ctx.hand();
+    match ctx.difficulty {
+        BotDifficulty::EasyLegacy => hand
+            .cards()
+            .get(..ctx.round.pass_count())
+            .and_then(PassCards::from_slice),
+        _ => PassPlanner::choose(hand, ctx),
+    }
+}
+
+/// Play the planner for `ctx.difficulty` would choose, without time limits.
+fn play_for(legal: &[Card], ctx: &BotContext<'_>) -> Option<Card> {
+    planner_play(legal, ctx).or_else(|| legal.first().copied())
+}
+
+/// Play the planner for `ctx.difficulty` chooses; `None` when it has no
+/// answer.
+pub(crate) fn planner_play(legal: &[Card], ctx: &BotContext<'_>) -> Option<Card> {
+    match ctx.difficulty {
+        BotDifficulty::EasyLegacy => legal.first().copied(),
+        BotDifficulty::SearchLookahead | BotDifficulty::FutureHard => {
+            PlayPlannerHard::choose(legal, ctx)
+        }
+        BotDifficulty::Rollout => RolloutPolicy::new(0).choose(legal, ctx),
+        BotDifficulty::NormalHeuristic => PlayPlanner::choose(legal, ctx),
+    }
+}
+
+/// Plays the round for `seed` with every seat at `primary`, querying `shadow`
+/// before each pass and play. Returns the per-phase tally and disagreements.
+pub fn shadow_check_round(
+    seed: u64,
+    primary: BotDifficulty,
+    shadow: BotDifficulty,
+) -> (ShadowSummary, Vec<ShadowDisagreement>) {
+    let mut controller = GameController::new_with_seed(Some(seed), PlayerPosition::North);
+    controller.set_bot_difficulty(primary);
+    let mut summary = ShadowSummary::default();
+    let mut disagreements = Vec::new();
+
+    if controller.in_passing_phase() {
+        for seat in PlayerPosition::LOOP {
+            let Some(chosen) = controller.simple_pass_for(seat) else {
+                continue;
+            };
+            let mut ctx = controller.bot_context(seat);
+            ctx.difficulty = shadow;
+            let shadow_pass = pass_for(&ctx).map(sorted_pass);
+            let chosen = sorted_pass(chosen);
+            let agree = shadow_pass == Some(chosen);
+            summary.record(ShadowPhase::Pass, agree);
+            if !agree {
+                disagreements.push(ShadowDisagreement {
+                    seed,
+                    seat,
+                    phase: ShadowPhase::Pass,
+                    trick: 0,
+                    primary: CardList(&chosen).to_string(),
+                    shadow: shadow_pass
+                        .map_or_else(|| "(none)".to_string(), |p| CardList(&p).to_string()),
+                    hand: CardList(&controller.hand(seat)).to_string(),
+                    trick_so_far: String::new(),
+                });
+            }
+            let _ = controller.submit_pass(seat, chosen);
+        }
+        let _ = controller.resolve_passes();
+    }
+
+    while controller.round().tricks_completed() < 13 {
+        let seat = controller.expected_to_play();
+        let trick = controller.round().tricks_completed() + 1;
+        let legal = controller.legal_moves(seat);
+        let hand = CardList(&controller.hand(seat)).to_string();
+        let trick_so_far = controller
+            .trick_plays()
+            .iter()
+            .map(|(_, card)| card.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let shadow_card = {
+            let mut ctx = controller.bot_context(seat);
+            ctx.difficulty = shadow;
+            play_for(&legal, &ctx)
+        };
+        let AutoplayOutcome::Played(_, played) = controller.autoplay_one_with_status(seat.next())
+        else {
+            break;
+        };
+        let phase = ShadowPhase::for_trick(trick);
+        let agree = shadow_card == Some(played);
+        summary.record(phase, agree);
+        if !agree {
+            disagreements.push(ShadowDisagreement {
+                seed,
+                seat,
+                phase,
+                trick,
+                primary: played.to_string(),
+                shadow: shadow_card.map_or_else(|| "(none)".to_string(), |c| c.to_string()),
+                hand,
+                trick_so_far,
+            });
+        }
+    }
+    (summary, disagreements)
+}
+
+pub fn disagreements_csv(rows: &[ShadowDisagreement]) -> String {
+    let mut out = String::from("seed,seat,phase,trick,primary,shadow,hand,trick_so_far\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{:?},{},{},{},{},{},{}\n",
+            row.seed,
+            row.seat,
+            row.phase.label(),
+            row.trick,
+            row.primary,
+            row.shadow,
+            row.hand,
+            row.trick_so_far
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShadowPhase, ShadowSummary, shadow_check_round};
+    use crate::bot::BotDifficulty;
+
+    #[test]
+    fn a_difficulty_never_disagrees_with_itself() {
+        let (summary, disagreements) = shadow_check_round(
+            7,
+            BotDifficulty::NormalHeuristic,
+            BotDifficulty::NormalHeuristic,
+        );
+        assert!(disagreements.is_empty());
+        assert_eq!(summary.disagreements, [0; 4]);
+        let plays: u32 = summary.decisions[1..].iter().sum();
+        assert_eq!(plays, 52);
+    }
+
+    #[test]
+    fn easy_against_normal_reports_disagreements_with_context() {
+        let (summary, disagreements) =
+            shadow_check_round(7, BotDifficulty::EasyLegacy, BotDifficulty::NormalHeuristic);
+        assert!(!disagreements.is_empty());
+        let counted: u32 = summary.disagreements.iter().sum();
+        assert_eq!(counted as usize, disagreements.len());
+        for row in &disagreements {
+            assert_ne!(row.primary, row.shadow);
+            assert!(!row.hand.is_empty());
+            if row.phase != ShadowPhase::Pass {
+                assert!((1..=13).contains(&row.trick));
+            }
+        }
+    }
+
+    #[test]
+    fn summary_rates_and_table() {
+        let mut summary = ShadowSummary::default();
+        summary.record(ShadowPhase::Pass, false);
+        summary.record(ShadowPhase::Pass, true);
+        summary.record(ShadowPhase::Late, true);
+        assert_eq!(summary.rate(ShadowPhase::Pass), Some(0.5));
+        assert_eq!(summary.rate(ShadowPhase::Mid), None);
+        assert_eq!(summary.total_rate(), Some(1.0 / 3.0));
+        let table = summary.markdown(BotDifficulty::NormalHeuristic, BotDifficulty::FutureHard);
+        assert!(table.contains("| pass | 2 | 1 | 50.0% |"));
+        assert!(table.contains("| mid | 0 | 0 | - |"));
+        assert!(table.contains("| all | 3 | 1 | 33.3% |"));
+    }
+}
@@ -0,0 +1,659 @@
+//! `--soak`: a timed health run for CI and release candidates. Five stages
+//! share the time budget in turn, each repeating seeded cases until its
+//! slice runs out:
+//!
+//! - planner consistency: random mid-round positions, where every heuristic
+//!   difficulty must answer with a legal card, and with the same card again
+//!   when its decisions are reproducible;
+//! - bench: decision latency of each heuristic difficulty on such positions;
+//! - belief calibration: the tracker's holding probabilities against the
+//!   real deal before every play of a round;
+//! - snapshot round-trip: random states through the round snapshot and the
+//!   saved game, which must come back as they went in;
+//! - pass optimizer: pathological hands in every passing direction, with
+//!   three- and four-card passes, which must get a well-formed pass.
+//!
+//! Every case is reproducible from its seed, which failures keep. A case
+//! that panics fails and the run goes on. Calibration is reported rather
+//! than judged: only an impossible belief, such as ruling out the seat that
+//! holds the card, is a failure.
+
+use crate::bot::{BotContext, BotDifficulty, UnseenTracker, memo_allowed};
+use crate::controller::GameController;
+use crate::outcome::agent_label;
+use crate::saved_game::SavedGame;
+use crate::shadow::{pass_for, planner_play};
+use hearts_core::game::serialization::RoundSnapshot;
+use hearts_core::model::card::{Card, CardList};
+use hearts_core::model::card_set::CardSet;
+use hearts_core::model::hand::Hand;
+use hearts_core::model::passing::{PassingDirection, PassingState};
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::round::{RoundPhase, RoundState};
+use hearts_core::model::score::ScoreBoard;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::time::{Duration, Instant};
+
+pub const SOAK_SCHEMA_VERSION: u32 = 1;
+
+const HEURISTICS: [BotDifficulty; 3] = [
+    BotDifficulty::EasyLegacy,
+    BotDifficulty::NormalHeuristic,
+    BotDifficulty::FutureHard,
+];
+
+/// Failures kept per stage; the count covers them all.
+const KEPT_FAILURES: usize = 50;
+
+const CALIBRATION_BUCKETS: usize = 10;
+
+/// North hands the pass planner rarely meets; the rest of the deal is
+/// shuffled from the case seed.
+const PASS_HANDS: [(&str, &str); 6] = [
+    ("all hearts", "2H 3H 4H 5H 6H 7H 8H 9H 10H JH QH KH AH"),
+    ("all spades", "2S 3S 4S 5S 6S 7S 8S 9S 10S JS QS KS AS"),
+    ("top cards", "QC KC AC JD QD KD AD QS KS AS QH KH AH"),
+    ("low cards", "2C 3C 4C 5C 2D 3D 4D 2S 3S 4S 2H 3H 4H"),
+    ("bare queen", "QS 2H 3H 4H 5H 6H 7H 8H 9H 10H JH QH KH"),
+    ("two suits", "2C 2D 3D 4D 5D 6D 7D 8D 9D 10D JD QD KD"),
+];
+
+const PASS_DIRECTIONS: [PassingDirection; 3] = [
+    PassingDirection::Left,
+    PassingDirection::Right,
+    PassingDirection::Across,
+];
+
+const PASS_COUNTS: [usize; 2] = [3, 4];
+
+/// Level scores, and North one bad hand from losing.
+const PASS_SCORES: [[u32; 4]; 2] = [[0, 0, 0, 0], [92, 30, 45, 60]];
+
+#[derive(Debug, Clone, Copy)]
+pub struct SoakConfig {
+    pub budget: Duration,
+    /// Seeds the case seeds; the report keeps it.
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SoakReport {
+    pub schema_version: u32,
+    pub seed: u64,
+    pub budget_secs: f64,
+    pub elapsed_secs: f64,
+    pub failures: u32,
+    pub stages: Vec<StageReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StageReport {
+    pub name: &'static str,
+    pub runs: u32,
+    pub elapsed_secs: f64,
+    pub failures: u32,
+    /// The first failures, up to [`KEPT_FAILURES`].
+    pub failure_samples: Vec<SoakFailure>,
+    pub latency: Vec<LatencySummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calibration: Option<CalibrationSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SoakFailure {
+    pub seed: u64,
+    pub problem: String,
+}
+
+/// Nearest-rank percentiles of one timed operation, in microseconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LatencySummary {
+    pub label: String,
+    pub samples: usize,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+    pub max_us: u64,
+}
+
+impl LatencySummary {
+    pub fn from_samples(label: &str, samples: &mut [u64]) -> Self {
+        samples.sort_unstable();
+        let rank = |q: f64| {
+            let idx = ((samples.len() as f64) * q).ceil() as usize;
+            samples.get(idx.saturating_sub(1)).copied().unwrap_or(0)
+        };
+        Self {
+            label: label.to_string(),
+            samples: samples.len(),
+            p50_us: rank(0.50),
+            p95_us: rank(0.95),
+            p99_us: rank(0.99),
+            max_us: samples.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationSummary {
+    pub predictions: u64,
+    /// Mean squared error of the predictions; 0 is perfect.
+    pub brier: f64,
+    /// Buckets by predicted probability; empty ones are left out.
+    pub buckets: Vec<CalibrationBucket>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub predictions: u64,
+    pub mean_predicted: f64,
+    /// How often the seat did hold the card.
+    pub observed: f64,
+}
+
+#[derive(Debug, Default)]
+struct Calibration {
+    predictions: u64,
+    squared_error: f64,
+    /// Predictions, summed probability and holdings per bucket.
+    buckets: [(u64, f64, u64); CALIBRATION_BUCKETS],
+}
+
+impl Calibration {
+    fn record(&mut self, probability: f32, held: bool) {
+        let p = f64::from(probability);
+        let actual = if held { 1.0 } else { 0.0 };
+        self.predictions += 1;
+        self.squared_error += (p - actual) * (p - actual);
+        let idx = ((p * CALIBRATION_BUCKETS as f64) as usize).min(CALIBRATION_BUCKETS - 1);
+        let bucket = &mut self.buckets[idx];
+        bucket.0 += 1;
+        bucket.1 += p;
+        bucket.2 += u64::from(held);
+    }
+
+    fn summary(&self) -> Option<CalibrationSummary> {
+        if self.predictions == 0 {
+            return None;
+        }
+        let width = 1.0 / CALIBRATION_BUCKETS as f64;
+        let buckets = self
+            .buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, (count, _, _))| *count > 0)
+            .map(|(idx, &(count, sum, held))| CalibrationBucket {
+                lower: idx as f64 * width,
+                upper: (idx + 1) as f64 * width,
+                predictions: count,
+                mean_predicted: sum / count as f64,
+                observed: held as f64 / count as f64,
+            })
+            .collect();
+        Some(CalibrationSummary {
+            predictions: self.predictions,
+            brier: self.squared_error / self.predictions as f64,
+            buckets,
+        })
+    }
+}
+
+struct Stage {
+    name: &'static str,
+    runs: u32,
+    failures: u32,
+    kept: Vec<SoakFailure>,
+    latencies: Vec<(String, Vec<u64>)>,
+    calibration: Calibration,
+}
+
+impl Stage {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            runs: 0,
+            failures: 0,
+            kept: Vec::new(),
+            latencies: Vec::new(),
+            calibration: Calibration::default(),
+        }
+    }
+
+    fn fail(&mut self, seed: u64, problem: String) {
+        self.failures += 1;
+        if self.kept.len() < KEPT_FAILURES {
+            self.kept.push(SoakFailure { seed, problem });
+        }
+    }
+
+    /// Runs `op`, adding its latency to the samples for `label`.
+    fn time<T>(&mut self, label: &str, op: impl FnOnce() -> T) -> T {
+        let t0 = Instant::now();
+        let out = op();
+        let micros = t0.elapsed().as_micros().min(u128::from(u64::MAX)) as u64;
+        match self.latencies.iter_mut().find(|(name, _)| name == label) {
+            Some((_, samples)) => samples.push(micros),
+            None => self.latencies.push((label.to_string(), vec![micros])),
+        }
+        out
+    }
+
+    fn finish(self, elapsed: Duration) -> StageReport {
+        StageReport {
+            name: self.name,
+            runs: self.runs,
+            elapsed_secs: elapsed.as_secs_f64(),
+            failures: self.failures,
+            failure_samples: self.kept,
+            latency: self
+                .latencies
+                .into_iter()
+                .map(|(label, mut samples)| LatencySummary::from_samples(&label, &mut samples))
+                .collect(),
+            calibration: self.calibration.summary(),
+        }
+    }
+}
+
+type Case = fn(&mut Stage, u64);
+
+const STAGES: [(&str, Case); 5] = [
+    ("planner_consistency", planner_case),
+    ("bench", bench_case),
+    ("belief_calibration", belief_case),
+    ("snapshot_roundtrip", snapshot_case),
+    ("pass_optimizer", pass_case),
+];
+
+/// Runs the stages in turn, each until its share of the budget is spent.
+/// Every stage runs at least one case.
+pub fn run(config: &SoakConfig) -> SoakReport {
+    let start = Instant::now();
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut stages = Vec::with_capacity(STAGES.len());
+    for (idx, (name, case)) in STAGES.into_iter().enumerate() {
+        let share = (idx + 1) as f64 / STAGES.len() as f64;
+        let deadline = start + config.budget.mul_f64(share);
+        let stage_start = Instant::now();
+        let mut stage = Stage::new(name);
+        loop {
+            let seed: u64 = rng.r#gen();
+            stage.runs += 1;
+            if catch_unwind(AssertUnwindSafe(|| case(&mut stage, seed))).is_err() {
+                stage.fail(seed, "panicked".to_string());
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+        stages.push(stage.finish(stage_start.elapsed()));
+    }
+    SoakReport {
+        schema_version: SOAK_SCHEMA_VERSION,
+        seed: config.seed,
+        budget_secs: config.budget.as_secs_f64(),
+        elapsed_secs: start.elapsed().as_secs_f64(),
+        failures: stages.iter().map(|stage| stage.failures).sum(),
+        stages,
+    }
+}
+
+impl SoakReport {
+    pub fn is_healthy(&self) -> bool {
+        self.failures == 0
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut out = format!(
+            "Soak: seed={} {:.1}s, {} failures\n| stage | runs | failures |\n|---|---:|---:|\n",
+            self.seed, self.elapsed_secs, self.failures
+        );
+        for stage in &self.stages {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                stage.name, stage.runs, stage.failures
+            ));
+        }
+        out.push_str(
+            "\n| stage | operation | samples | p50_us | p95_us | p99_us | max_us |\n|---|---|---:|---:|---:|---:|---:|\n",
+        );
+        for stage in &self.stages {
+            for latency in &stage.latency {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} | {} |\n",
+                    stage.name,
+                    latency.label,
+                    latency.samples,
+                    latency.p50_us,
+                    latency.p95_us,
+                    latency.p99_us,
+                    latency.max_us
+                ));
+            }
+        }
+        for stage in &self.stages {
+            if let Some(calibration) = &stage.calibration {
+                out.push_str(&format!(
+                    "\nBelief calibration: brier={:.4} over {} predictions\n",
+                    calibration.brier, calibration.predictions
+                ));
+            }
+            for failure in &stage.failure_samples {
+                out.push_str(&format!(
+                    "FAIL {} seed={}: {}\n",
+                    stage.name, failure.seed, failure.problem
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// The round for `seed` as dealt, before the passes.
+fn dealt(seed: u64) -> GameController {
+    GameController::new_with_seed(Some(seed), PlayerPosition::North)
+}
+
+/// The round for `seed` once every seat has passed.
+fn passed(seed: u64) -> GameController {
+    let mut controller = dealt(seed);
+    if controller.in_passing_phase() {
+        for seat in PlayerPosition::LOOP {
+            if let Some(cards) = controller.simple_pass_for(seat) {
+                let _ = controller.submit_pass(seat, cards);
+            }
+        }
+        let _ = controller.resolve_passes();
+    }
+    controller
+}
+
+/// The round for `seed` after the passes and up to 47 plays, so at least
+/// one decision is left.
+fn mid_round(seed: u64) -> GameController {
+    let mut controller = passed(seed);
+    for _ in 0..seed % 48 {
+        let seat = controller.expected_to_play();
+        if controller.autoplay_one(seat.next()).is_none() {
+            break;
+        }
+    }
+    controller
+}
+
+fn planner_case(stage: &mut Stage, seed: u64) {
+    let controller = mid_round(seed);
+    let seat = controller.expected_to_play();
+    let legal = controller.legal_moves(seat);
+    if legal.is_empty() {
+        stage.fail(seed, format!("{seat:?} has no legal card"));
+        return;
+    }
+    for difficulty in HEURISTICS {
+        let mut ctx = controller.bot_context(seat);
+        ctx.difficulty = difficulty;
+        let label = agent_label(difficulty);
+        let Some(card) = planner_play(&legal, &ctx) else {
+            stage.fail(seed, format!("{label}: no card for {seat:?}"));
+            continue;
+        };
+        if !legal.contains(&card) {
+            stage.fail(
+                seed,
+                format!(
+                    "{label}: {seat:?} chose {card}, not in {}",
+                    CardList(&legal)
+                ),
+            );
+            continue;
+        }
+        if memo_allowed(difficulty) {
+            let again = planner_play(&legal, &ctx);
+            if again != Some(card) {
+                stage.fail(
+                    seed,
+                    format!(
+                        "{label}: {seat:?} chose {card}, then {again:?} from the same position"
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn bench_case(stage: &mut Stage, seed: u64) {
+    let controller = mid_round(seed);
+    let seat = controller.expected_to_play();
+    let legal = controller.legal_moves(seat);
+    for difficulty in HEURISTICS {
+        let mut ctx = controller.bot_context(seat);
+        ctx.difficulty = difficulty;
+        stage.time(agent_label(difficulty), || planner_play(&legal, &ctx));
+    }
+}
+
+fn belief_case(stage: &mut Stage, seed: u64) {
+    let mut controller = passed(seed);
+    while controller.round().tricks_completed() < 13 {
+        let observer = controller.expected_to_play();
+        check_beliefs(stage, seed, &controller, observer);
+        if controller.autoplay_one(observer.next()).is_none() {
+            break;
+        }
+    }
+}
+
+/// Scores what `observer` believes of every card it cannot see.
+fn check_beliefs(
+    stage: &mut Stage,
+    seed: u64,
+    controller: &GameController,
+    observer: PlayerPosition,
+) {
+    let round = controller.round();
+    let tracker = controller.bot_context(observer).tracker;
+    let trick = round.tricks_completed() + 1;
+    for card in CardSet::DECK {
+        if !tracker.is_unseen(card) || round.hand(observer).contains(card) {
+            continue;
+        }
+        let mut total = 0.0f32;
+        for seat in PlayerPosition::LOOP {
+            if seat == observer {
+                continue;
+            }
+            let p = tracker.holding_probability(observer, seat, card);
+            let held = round.hand(seat).contains(card);
+            if !(0.0..=1.0).contains(&p) {
+                stage.fail(
+                    seed,
+                    format!("trick {trick}: {observer:?} gives {seat:?} {card} at {p}"),
+                );
+            } else if held && p == 0.0 {
+                stage.fail(
+                    seed,
+                    format!("trick {trick}: {observer:?} rules out {seat:?} holding {card}"),
+                );
+            }
+            stage.calibration.record(p.clamp(0.0, 1.0), held);
+            total += p;
+        }
+        if (total - 1.0).abs() > 1e-3 {
+            stage.fail(
+                seed,
+                format!("trick {trick}: {observer:?} places {card} with total {total:.3}"),
+            );
+        }
+    }
+}
+
+fn snapshot_case(stage: &mut Stage, seed: u64) {
+    // One case in eight is saved in the passing phase.
+    let controller = if seed.is_multiple_of(8) {
+        dealt(seed)
+    } else {
+        mid_round(seed)
+    };
+    let before = RoundSnapshot::capture(controller.round());
+    let direction = controller.passing_direction();
+    let restored = stage.time("round snapshot", || {
+        let json = serde_json::to_string(&before).map_err(|err| err.to_string())?;
+        let parsed: RoundSnapshot = serde_json::from_str(&json).map_err(|err| err.to_string())?;
+        parsed.restore(direction)
+    });
+    match restored {
+        Err(err) => stage.fail(seed, format!("round snapshot: {err}")),
+        Ok(round) if RoundSnapshot::capture(&round) != before => {
+            stage.fail(seed, "round snapshot came back different".to_string());
+        }
+        Ok(_) => {}
+    }
+
+    let saved = SavedGame::capture(&controller);
+    let resumed = stage.time("saved game", || {
+        let json = serde_json::to_string(&saved).map_err(|err| err.to_string())?;
+        let parsed: SavedGame = serde_json::from_str(&json).map_err(|err| err.to_string())?;
+        parsed.resume()
+    });
+    let resumed = match resumed {
+        Ok(resumed) => resumed,
+        Err(err) => {
+            stage.fail(seed, format!("saved game: {err}"));
+            return;
+        }
+    };
+    if RoundSnapshot::capture(resumed.round()) != before {
+        stage.fail(seed, "saved game resumed a different round".to_string());
+    }
+    if resumed.standings() != controller.standings() {
+        stage.fail(seed, "saved game resumed different scores".to_string());
+    }
+    if !controller.in_passing_phase() {
+        let seat = controller.expected_to_play();
+        if resumed.expected_to_play() != seat
+            || resumed.legal_moves(seat) != controller.legal_moves(seat)
+        {
+            stage.fail(
+                seed,
+                format!("saved game resumed a different turn for {seat:?}"),
+            );
+        }
+    }
+}
+
+fn pass_case(stage: &mut Stage, seed: u64) {
+    // The seed picks the hand, direction, count, scores and difficulty.
+    let mut pick = seed as usize;
+    let mut next = |len: usize| {
+        let idx = pick % len;
+        pick /= len;
+        idx
+    };
+    let (name, text) = PASS_HANDS[next(PASS_HANDS.len())];
+    let direction = PASS_DIRECTIONS[next(PASS_DIRECTIONS.len())];
+    let count = PASS_COUNTS[next(PASS_COUNTS.len())];
+    let totals = PASS_SCORES[next(PASS_SCORES.len())];
+    let difficulty = HEURISTICS[next(HEURISTICS.len())];
+
+    let hand = Hand::parse(text).expect("pass corpus hands parse");
+    let mut rest: Vec<Card> = (!hand.as_set()).iter().collect();
+    rest.shuffle(&mut StdRng::seed_from_u64(seed));
+    let mut hands: [Hand; 4] = Default::default();
+    hands[0] = hand.clone();
+    for (slot, cards) in hands[1..].iter_mut().zip(rest.chunks(13)) {
+        *slot = Hand::with_cards(cards.to_vec());
+    }
+    let phase = RoundPhase::Passing(PassingState::with_count(direction, count));
+    let round = RoundState::from_hands(hands, PlayerPosition::North, direction, phase);
+    let mut tracker = UnseenTracker::new();
+    tracker.reset_for_round(&round);
+    let mut scores = ScoreBoard::new();
+    scores.set_totals(totals);
+    let ctx = BotContext::new(
+        PlayerPosition::North,
+        &round,
+        scores,
+        direction,
+        &tracker,
+        difficulty,
+    );
+
+    let label = agent_label(difficulty);
+    let case = format!("{name} passing {count} {}", direction.as_str());
+    let Some(pass) = stage.time(&format!("{label} pass"), || pass_for(&ctx)) else {
+        stage.fail(seed, format!("{label}: no pass from {case}"));
+        return;
+    };
+    let cards = pass.as_slice();
+    let distinct: CardSet = cards.iter().collect();
+    if cards.len() != count
+        || distinct.len() != count
+        || !cards.iter().all(|card| hand.contains(*card))
+    {
+        stage.fail(
+            seed,
+            format!("{label}: passed {} from {case}", CardList(cards)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LatencySummary, STAGES, SoakConfig, run};
+    use std::time::Duration;
+
+    #[test]
+    fn percentiles_take_the_nearest_rank() {
+        let mut samples: Vec<u64> = (1..=200).rev().collect();
+        let summary = LatencySummary::from_samples("op", &mut samples);
+        assert_eq!(
+            (
+                summary.p50_us,
+                summary.p95_us,
+                summary.p99_us,
+                summary.max_us
+            ),
+            (100, 190, 198, 200)
+        );
+        let empty = LatencySummary::from_samples("none", &mut []);
+        assert_eq!((empty.samples, empty.p99_us), (0, 0));
+    }
+
+    #[test]
+    fn a_short_soak_runs_every_stage_cleanly() {
+        let report = run(&SoakConfig {
+            budget: Duration::from_secs(3),
+            seed: 2264,
+        });
+        assert!(report.is_healthy(), "{}", report.markdown());
+        assert_eq!(report.stages.len(), STAGES.len());
+        for stage in &report.stages {
+            assert!(stage.runs > 0, "{} never ran", stage.name);
+        }
+        let latency_labels = |name: &str| -> Vec<String> {
+            let stage = report.stages.iter().find(|s| s.name == name).unwrap();
+            stage.latency.iter().map(|l| l.label.clone()).collect()
+        };
+        assert_eq!(latency_labels("bench"), ["easy", "normal", "hard"]);
+        assert_eq!(
+            latency_labels("snapshot_roundtrip"),
+            ["round snapshot", "saved game"]
+        );
+        let calibration = report.stages[2]
+            .calibration
+            .as_ref()
+            .expect("beliefs scored");
+        assert!(calibration.predictions > 0);
+        assert!(calibration.brier < 0.25, "brier {}", calibration.brier);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["stages"][0]["name"], "planner_consistency");
+    }
+}
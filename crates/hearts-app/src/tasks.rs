@@ -0,0 +1,298 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! Long computations off the UI thread, one at a time.
+//!
+//! [`TaskRunner::spawn`] runs the work on a worker thread with a
+//! [`CancelToken`] it can check between steps. The UI thread drains results
+//! with [`TaskRunner::poll`], typically when the notifier set with
+//! [`TaskRunner::set_notify`] posts it a message. A task that is cancelled,
+//! or replaced by a newer one, is reported once as
+//! [`TaskEvent::Cancelled`]; its result is dropped if it still arrives.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Shared flag a task checks to stop early.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskEvent<T> {
+    Finished { id: TaskId, value: T },
+    Cancelled { id: TaskId },
+}
+
+struct RunningTask {
+    id: TaskId,
+    label: &'static str,
+    token: CancelToken,
+    started_at: Instant,
+}
+
+type Notify = Arc<dyn Fn() + Send + Sync>;
+
+pub struct TaskRunner<T> {
+    next_id: u64,
+    running: Option<RunningTask>,
+    cancelled: Vec<TaskId>,
+    tx: mpsc::Sender<(TaskId, T)>,
+    rx: mpsc::Receiver<(TaskId, T)>,
+    notify: Option<Notify>,
+}
+
+impl<T: Send + 'static> TaskRunner<T> {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            next_id: 0,
+            running: None,
+            cancelled: Vec::new(),
+            tx,
+            rx,
+            notify: None,
+        }
+    }
+
+    /// Called on the worker thread after each result is sent, e.g. to post
+    /// the window a message.
+    pub fn set_notify(&mut self, notify: impl Fn() + Send + Sync + 'static) {
+        self.notify = Some(Arc::new(notify));
+    }
+
+    /// Starts `work`, cancelling the task already running, if any.
+    pub fn spawn(
+        &mut self,
+        label: &'static str,
+        work: impl FnOnce(&CancelToken) -> T + Send + 'static,
+    ) -> TaskId {
+        self.cancel();
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+        let token = CancelToken::new();
+        let worker_token = token.clone();
+        let tx = self.tx.clone();
+        let notify = self.notify.clone();
+        thread::spawn(move || {
+            let value = work(&worker_token);
+            let _ = tx.send((id, value));
+            if let Some(notify) = notify {
+                notify();
+            }
+        });
+        self.running = Some(RunningTask {
+            id,
+            label,
+            token,
+            started_at: Instant::now(),
+        });
+        id
+    }
+
+    /// Cancels the running task; `None` when there was none.
+    pub fn cancel(&mut self) -> Option<TaskId> {
+        let task = self.running.take()?;
+        task.token.cancel();
+        self.cancelled.push(task.id);
+        Some(task.id)
+    }
+
+    /// Label of the running task, for a busy indicator.
+    pub fn busy(&self) -> Option<&'static str> {
+        self.running.as_ref().map(|task| task.label)
+    }
+
+    pub fn busy_for(&self) -> Option<Duration> {
+        self.running.as_ref().map(|task| task.started_at.elapsed())
+    }
+
+    /// The next event without waiting: cancellations first, then the
+    /// running task's result. Results of cancelled tasks are dropped.
+    pub fn poll(&mut self) -> Option<TaskEvent<T>> {
+        if !self.cancelled.is_empty() {
+            return Some(TaskEvent::Cancelled {
+                id: self.cancelled.remove(0),
+            });
+        }
+        while let Ok((id, value)) = self.rx.try_recv() {
+            if let Some(event) = self.accept(id, value) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    /// Like [`TaskRunner::poll`], waiting up to `timeout` for the running
+    /// task to finish.
+    #[cfg(test)]
+    pub fn poll_timeout(&mut self, timeout: Duration) -> Option<TaskEvent<T>> {
+        if let Some(event) = self.poll() {
+            return Some(event);
+        }
+        let deadline = Instant::now() + timeout;
+        while self.running.is_some() {
+            let left = deadline.saturating_duration_since(Instant::now());
+            let Ok((id, value)) = self.rx.recv_timeout(left) else {
+                return None;
+            };
+            if let Some(event) = self.accept(id, value) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    fn accept(&mut self, id: TaskId, value: T) -> Option<TaskEvent<T>> {
+        if self.running.as_ref().is_some_and(|task| task.id == id) {
+            self.running = None;
+            Some(TaskEvent::Finished { id, value })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Send + 'static> Default for TaskRunner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for TaskRunner<T> {
+    fn drop(&mut self) {
+        if let Some(task) = &self.running {
+            task.token.cancel();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::GameController;
+    use crate::session::{GameSession, SessionCommand};
+    use hearts_core::model::player::PlayerPosition;
+
+    const WAIT: Duration = Duration::from_secs(30);
+
+    /// Plays the first round of `seed` with four bots; the kind of work the
+    /// window hands to a task.
+    fn play_round(seed: u64) -> [u8; 4] {
+        let controller = GameController::new_with_seed(Some(seed), PlayerPosition::North);
+        let mut session = GameSession::new(controller);
+        let _ = session.apply(SessionCommand::BotPasses { except: None });
+        let _ = session.apply(SessionCommand::ResolvePasses);
+        while !session.controller().round_ready_for_scoring() {
+            session.apply(SessionCommand::BotTurn).unwrap();
+        }
+        session.controller().penalties_this_round()
+    }
+
+    /// A runner whose notifier reports on a channel, so a test can tell
+    /// when a worker has sent its result.
+    fn notified_runner() -> (TaskRunner<[u8; 4]>, mpsc::Receiver<()>) {
+        let (done_tx, done_rx) = mpsc::channel();
+        let mut runner = TaskRunner::new();
+        runner.set_notify(move || {
+            let _ = done_tx.send(());
+        });
+        (runner, done_rx)
+    }
+
+    #[test]
+    fn a_finished_task_delivers_its_result() {
+        let mut runner = TaskRunner::new();
+        let id = runner.spawn("Playing", |_| play_round(2252));
+        assert_eq!(runner.busy(), Some("Playing"));
+        let event = runner.poll_timeout(WAIT);
+        assert_eq!(
+            event,
+            Some(TaskEvent::Finished {
+                id,
+                value: play_round(2252)
+            })
+        );
+        assert_eq!(runner.busy(), None);
+        assert_eq!(runner.poll(), None);
+    }
+
+    #[test]
+    fn a_result_arriving_after_cancel_is_dropped() {
+        let (mut runner, done) = notified_runner();
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        // The work ignores its token, so it finishes after the cancel.
+        let id = runner.spawn("Playing", move |_| {
+            let _ = gate_rx.recv();
+            play_round(7)
+        });
+        assert_eq!(runner.cancel(), Some(id));
+        assert_eq!(runner.busy(), None);
+        gate_tx.send(()).unwrap();
+        done.recv_timeout(WAIT).unwrap();
+        assert_eq!(runner.poll(), Some(TaskEvent::Cancelled { id }));
+        assert_eq!(runner.poll(), None);
+        assert_eq!(runner.poll_timeout(Duration::from_millis(10)), None);
+    }
+
+    #[test]
+    fn a_newer_task_replaces_the_running_one() {
+        let (mut runner, done) = notified_runner();
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        let first = runner.spawn("First", move |_| {
+            let _ = gate_rx.recv();
+            play_round(1)
+        });
+        let second = runner.spawn("Second", |_| play_round(2));
+        assert_eq!(runner.busy(), Some("Second"));
+        assert_eq!(runner.poll(), Some(TaskEvent::Cancelled { id: first }));
+        // The stale result lands while the newer task is still wanted.
+        gate_tx.send(()).unwrap();
+        done.recv_timeout(WAIT).unwrap();
+        done.recv_timeout(WAIT).unwrap();
+        assert_eq!(
+            runner.poll(),
+            Some(TaskEvent::Finished {
+                id: second,
+                value: play_round(2)
+            })
+        );
+        assert_eq!(runner.poll(), None);
+    }
+
+    #[test]
+    fn cooperative_work_stops_at_the_cancel() {
+        let (mut runner, done) = notified_runner();
+        let id = runner.spawn("Rounds", |token| {
+            let mut seed = 0;
+            let mut totals = [0u8; 4];
+            while !token.is_cancelled() {
+                totals = play_round(seed);
+                seed += 1;
+            }
+            totals
+        });
+        assert_eq!(runner.cancel(), Some(id));
+        // The worker sees the token and returns instead of running forever.
+        done.recv_timeout(WAIT).unwrap();
+        assert_eq!(runner.poll(), Some(TaskEvent::Cancelled { id }));
+        assert_eq!(runner.poll(), None);
+    }
+}
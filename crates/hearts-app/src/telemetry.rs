@@ -1,15 +1,19 @@
 use crate::bot::UnseenTracker;
 use crate::bot::search::Stats as SearchStats;
+use crate::outputs::Compression;
 use hearts_core::model::player::PlayerPosition;
 use parking_lot::RwLock;
-use serde::Serialize;
-use std::fs::{self, File};
+use serde::{Deserialize, Serialize};
+use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Bumped whenever `HardTelemetryRecord` changes shape; readers reject other versions.
+pub const HARD_TELEMETRY_SCHEMA: u32 = 2;
+
 static HARD_SINK: OnceLock<TelemetrySink> = OnceLock::new();
 static DECISION_COUNTER: AtomicU64 = AtomicU64::new(1);
 
@@ -19,8 +23,10 @@ pub struct TelemetrySink {
     records: RwLock<Vec<HardTelemetryRecord>>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct HardTelemetryRecord {
+    pub schema: u32,
     pub timestamp_ms: u128,
     pub decision_index: u64,
     pub seat: String,
@@ -44,12 +50,15 @@ pub struct HardTelemetryRecord {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search_stats: Option<SearchTelemetrySnapshot>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub deep_search: Option<DeepSearchTelemetry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub controller_bias_delta: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct SearchTelemetrySnapshot {
     pub scanned: usize,
     pub scanned_phase_a: usize,
@@ -70,7 +79,22 @@ pub struct SearchTelemetrySnapshot {
     pub controller_bias_delta: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// The work of a decision the deep search made; see
+/// [`crate::bot::search_deep::SearchStats`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeepSearchTelemetry {
+    pub nodes: usize,
+    pub depth_reached: u8,
+    pub tt_entries: usize,
+    pub tt_hits: usize,
+    pub tt_misses: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tt_hit_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct MixHintBiasSnapshot {
     pub snnh_feed_bonus_hits: u32,
     pub snnh_capture_guard_hits: u32,
@@ -92,6 +116,7 @@ pub struct PostDecisionData<'a> {
     pub timed_out: bool,
     pub fallback: Option<&'a str>,
     pub search_stats: Option<SearchTelemetrySnapshot>,
+    pub deep_search: Option<DeepSearchTelemetry>,
     pub controller_bias_delta: Option<i32>,
 }
 
@@ -128,6 +153,16 @@ impl TelemetrySink {
     pub fn export_ndjson(
         &self,
         destination: Option<PathBuf>,
+    ) -> io::Result<(PathBuf, HardTelemetrySummary)> {
+        self.export_ndjson_compressed(destination, Compression::None)
+    }
+
+    /// [`Self::export_ndjson`] through `compression`; the returned path
+    /// carries the compression extension.
+    pub fn export_ndjson_compressed(
+        &self,
+        destination: Option<PathBuf>,
+        compression: Compression,
     ) -> io::Result<(PathBuf, HardTelemetrySummary)> {
         let records = self.snapshot();
         let summary = HardTelemetrySummary::from_records(&records);
@@ -135,12 +170,12 @@ impl TelemetrySink {
         if let Some(parent) = target.parent() {
             fs::create_dir_all(parent)?;
         }
-        let mut file = File::create(&target)?;
+        let mut file = compression.create(&target)?;
         for record in &records {
             let json = serde_json::to_string(record).map_err(io::Error::other)?;
             writeln!(file, "{json}")?;
         }
-        drop(file);
+        let target = file.finish()?;
         if let Some(parent) = target.parent() {
             enforce_retention(parent, self.retention)?;
         }
@@ -160,6 +195,7 @@ impl HardTelemetryRecord {
         let entropy = tracker.belief_entropy();
         let metrics = tracker.belief_cache_metrics();
         HardTelemetryRecord {
+            schema: HARD_TELEMETRY_SCHEMA,
             timestamp_ms,
             decision_index,
             seat: seat.to_string(),
@@ -174,6 +210,7 @@ impl HardTelemetryRecord {
             elapsed_ms: None,
             timed_out: None,
             search_stats: None,
+            deep_search: None,
             fallback: None,
             notes: None,
             controller_bias_delta: None,
@@ -203,6 +240,19 @@ impl SearchTelemetrySnapshot {
     }
 }
 
+impl From<crate::bot::search_deep::SearchStats> for DeepSearchTelemetry {
+    fn from(stats: crate::bot::search_deep::SearchStats) -> Self {
+        Self {
+            nodes: stats.nodes,
+            depth_reached: stats.depth_reached,
+            tt_entries: stats.tt_entries,
+            tt_hits: stats.tt_hits,
+            tt_misses: stats.tt_misses,
+            tt_hit_rate: stats.hit_rate(),
+        }
+    }
+}
+
 impl From<crate::bot::play::MixHintBiasStats> for MixHintBiasSnapshot {
     fn from(stats: crate::bot::play::MixHintBiasStats) -> Self {
         Self {
@@ -246,6 +296,67 @@ impl HardTelemetrySummary {
     }
 }
 
+/// Result of strictly validating an exported NDJSON telemetry file.
+#[derive(Debug, Clone, Default)]
+pub struct TelemetryLintReport {
+    pub total_lines: usize,
+    pub valid: usize,
+    /// Malformed line counts keyed by the event's `phase` ("pre", "post", or "unknown").
+    pub malformed: std::collections::BTreeMap<String, usize>,
+    /// The first offending lines as (1-based line number, reason).
+    pub offending: Vec<(usize, String)>,
+}
+
+impl TelemetryLintReport {
+    pub fn malformed_total(&self) -> usize {
+        self.malformed.values().sum()
+    }
+
+    pub fn summary_line(&self) -> String {
+        if self.malformed.is_empty() {
+            return format!("{} records, all valid", self.valid);
+        }
+        let parts: Vec<String> = self
+            .malformed
+            .iter()
+            .map(|(phase, count)| format!("skipped {count} malformed {phase} events"))
+            .collect();
+        format!("{} valid records; {}", self.valid, parts.join(", "))
+    }
+}
+
+/// Parses each line as a `HardTelemetryRecord` at the current schema version,
+/// keeping at most `max_offending` sample failures.
+pub fn lint_ndjson(contents: &str, max_offending: usize) -> TelemetryLintReport {
+    let mut report = TelemetryLintReport::default();
+    for (idx, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        report.total_lines += 1;
+        let reason = match serde_json::from_str::<HardTelemetryRecord>(line) {
+            Ok(record) if record.schema == HARD_TELEMETRY_SCHEMA => {
+                report.valid += 1;
+                continue;
+            }
+            Ok(record) => format!(
+                "schema {} (expected {})",
+                record.schema, HARD_TELEMETRY_SCHEMA
+            ),
+            Err(err) => err.to_string(),
+        };
+        let phase = serde_json::from_str::<serde_json::Value>(line)
+            .ok()
+            .and_then(|value| value.get("phase")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| "unknown".to_string());
+        *report.malformed.entry(phase).or_insert(0) += 1;
+        if report.offending.len() < max_offending {
+            report.offending.push((idx + 1, reason));
+        }
+    }
+    report
+}
+
 fn now_millis() -> u128 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -299,7 +410,7 @@ fn enforce_retention(dir: &Path, retention: usize) -> io::Result<()> {
     if entries.len() <= retention {
         return Ok(());
     }
-    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.sort_by_key(|entry| entry.0);
     let excess = entries.len().saturating_sub(retention);
     if excess == 0 {
         return Ok(());
@@ -378,6 +489,7 @@ pub mod hard {
         record.timed_out = Some(data.timed_out);
         record.fallback = data.fallback.map(|f| f.to_string());
         record.search_stats = data.search_stats;
+        record.deep_search = data.deep_search;
         record.controller_bias_delta = data.controller_bias_delta;
         with_active_sink(|sink| sink.push(record));
     }
@@ -386,6 +498,13 @@ pub mod hard {
         sink().export_ndjson(destination)
     }
 
+    pub fn export_compressed(
+        destination: Option<PathBuf>,
+        compression: Compression,
+    ) -> io::Result<(PathBuf, HardTelemetrySummary)> {
+        sink().export_ndjson_compressed(destination, compression)
+    }
+
     #[allow(dead_code)]
     pub fn summary() -> HardTelemetrySummary {
         sink().summarize()
@@ -426,6 +545,7 @@ mod tests {
     fn summary_accumulates_entropy_and_hits() {
         let records = vec![
             HardTelemetryRecord {
+                schema: HARD_TELEMETRY_SCHEMA,
                 timestamp_ms: 1,
                 decision_index: 1,
                 seat: "North".to_string(),
@@ -440,11 +560,13 @@ mod tests {
                 elapsed_ms: None,
                 timed_out: None,
                 search_stats: None,
+                deep_search: None,
                 fallback: None,
                 notes: None,
                 controller_bias_delta: None,
             },
             HardTelemetryRecord {
+                schema: HARD_TELEMETRY_SCHEMA,
                 timestamp_ms: 2,
                 decision_index: 2,
                 seat: "East".to_string(),
@@ -459,6 +581,7 @@ mod tests {
                 elapsed_ms: None,
                 timed_out: None,
                 search_stats: None,
+                deep_search: None,
                 fallback: None,
                 notes: None,
                 controller_bias_delta: None,
@@ -470,10 +593,44 @@ mod tests {
         assert!((summary.cache_hit_rate - (3.0 / 4.0)).abs() < 1e-6);
     }
 
+    #[test]
+    fn lint_accepts_exported_records_and_counts_malformed() {
+        use crate::bot::UnseenTracker;
+
+        let tracker = UnseenTracker::new();
+        let pre =
+            HardTelemetryRecord::from_tracker(PlayerPosition::North, &tracker, None, Some("pre"));
+        let mut post =
+            HardTelemetryRecord::from_tracker(PlayerPosition::East, &tracker, None, Some("post"));
+        post.schema = HARD_TELEMETRY_SCHEMA + 1;
+        let contents = [
+            serde_json::to_string(&pre).unwrap(),
+            serde_json::to_string(&post).unwrap(),
+            r#"{"phase":"post","seat":"West"}"#.to_string(),
+            "not json".to_string(),
+        ]
+        .join("\n");
+
+        let report = lint_ndjson(&contents, 2);
+        assert_eq!(report.total_lines, 4);
+        assert_eq!(report.valid, 1);
+        assert_eq!(report.malformed.get("post"), Some(&2));
+        assert_eq!(report.malformed.get("unknown"), Some(&1));
+        assert_eq!(report.malformed_total(), 3);
+        assert_eq!(report.offending.len(), 2);
+        assert_eq!(report.offending[0].0, 2);
+        assert!(
+            report
+                .summary_line()
+                .contains("skipped 2 malformed post events")
+        );
+    }
+
     #[test]
     fn telemetry_sink_enforces_retention_on_push() {
         let sink = TelemetrySink::new(2);
         let make_record = |idx: u64| HardTelemetryRecord {
+            schema: HARD_TELEMETRY_SCHEMA,
             timestamp_ms: idx as u128,
             decision_index: idx,
             seat: "North".to_string(),
@@ -489,6 +646,7 @@ mod tests {
             timed_out: None,
             fallback: None,
             search_stats: None,
+            deep_search: None,
             notes: None,
             controller_bias_delta: None,
         };
@@ -527,6 +685,7 @@ mod tests {
 
         let sink = TelemetrySink::new(5);
         let record = HardTelemetryRecord {
+            schema: HARD_TELEMETRY_SCHEMA,
             timestamp_ms: 100,
             decision_index: 1,
             seat: "West".to_string(),
@@ -542,6 +701,7 @@ mod tests {
             timed_out: None,
             fallback: None,
             search_stats: None,
+            deep_search: None,
             notes: None,
             controller_bias_delta: None,
         };
@@ -560,7 +720,7 @@ mod tests {
         // Create dummy files to test retention
         for i in 3..=10 {
             let p = temp_dir.join(format!("dummy{}.ndjson", i));
-            File::create(&p).unwrap();
+            fs::File::create(&p).unwrap();
             // Sleep briefly to ensure mtime differs? Windows mtime resolution is coarse.
             // We'll rely on enforce_retention logic which uses modified time.
             // However, enforce_retention sorts.
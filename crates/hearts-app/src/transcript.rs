@@ -0,0 +1,225 @@
+#![cfg_attr(not(windows), allow(dead_code))]
+//! The rounds of the last match, saved for the replay viewer.
+//!
+//! The GUI rewrites the transcript each time a round is scored. Each round
+//! holds its deal after the pass, the match scores going into it and every
+//! card in play order, which is all a [`ReplayController`] needs to step
+//! through it with all four hands face up.
+//!
+//! The file is a [`Persisted`] document, like the saved game.
+
+use crate::controller::{RecordedRound, ReplayController};
+use crate::persist::{self, Migration, PersistError, Persisted};
+use hearts_core::game::serialization::RoundSnapshot;
+use hearts_core::model::card::Card;
+use hearts_core::model::passing::PassingDirection;
+use hearts_core::model::player::PlayerPosition;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const TRANSCRIPT_FILE: &str = "mdhearts-last-match.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptPlay {
+    pub seat: PlayerPosition,
+    pub card: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptRound {
+    /// The deal after the pass, before the first card.
+    pub start: RoundSnapshot,
+    pub passing_direction: String,
+    /// Match totals going into the round, North, East, South, West.
+    pub scores: [u32; 4],
+    pub plays: Vec<TranscriptPlay>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub schema_version: u32,
+    pub rounds: Vec<TranscriptRound>,
+}
+
+impl Persisted for Transcript {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn migrations() -> &'static [Migration] {
+        &[]
+    }
+}
+
+impl Transcript {
+    pub fn capture(rounds: &[RecordedRound]) -> Self {
+        let rounds = rounds
+            .iter()
+            .map(|round| TranscriptRound {
+                start: round.start.clone(),
+                passing_direction: round.passing_direction.as_str().to_string(),
+                scores: round.scores,
+                plays: round
+                    .plays
+                    .iter()
+                    .map(|&(seat, card)| TranscriptPlay {
+                        seat,
+                        card: card.to_string(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self {
+            schema_version: Self::SCHEMA_VERSION,
+            rounds,
+        }
+    }
+
+    pub fn into_rounds(self) -> Result<Vec<RecordedRound>, String> {
+        self.rounds
+            .into_iter()
+            .enumerate()
+            .map(|(idx, round)| {
+                let at = |err: String| format!("round {}: {err}", idx + 1);
+                let passing_direction = round
+                    .passing_direction
+                    .parse::<PassingDirection>()
+                    .map_err(|_| {
+                        at(format!(
+                            "unknown passing direction {}",
+                            round.passing_direction
+                        ))
+                    })?;
+                let plays = round
+                    .plays
+                    .iter()
+                    .map(|play| {
+                        play.card
+                            .parse::<Card>()
+                            .map(|card| (play.seat, card))
+                            .map_err(|err| at(format!("bad card: {err}")))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(RecordedRound {
+                    start: round.start,
+                    passing_direction,
+                    scores: round.scores,
+                    plays,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Where the GUI keeps the transcript: next to the executable, like the
+/// saved game.
+pub fn transcript_path() -> PathBuf {
+    match std::env::current_exe() {
+        Ok(mut exe) => {
+            exe.set_file_name(TRANSCRIPT_FILE);
+            exe
+        }
+        Err(_) => PathBuf::from(TRANSCRIPT_FILE),
+    }
+}
+
+pub fn save_transcript(path: &Path, rounds: &[RecordedRound]) -> Result<(), PersistError> {
+    persist::save(path, &Transcript::capture(rounds))
+}
+
+/// A replay of the match whose transcript is at `path`.
+pub fn load_replay(path: &Path) -> Result<ReplayController, String> {
+    let transcript: Transcript =
+        persist::load_with_migrations(path).map_err(|err| err.to_string())?;
+    transcript
+        .into_rounds()
+        .and_then(ReplayController::new)
+        .map_err(|err| format!("{} cannot be replayed: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::BotDifficulty;
+    use crate::controller::GameController;
+    use crate::persist::quarantine_path;
+    use crate::session::{GameSession, SessionCommand};
+    use std::fs;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("mdhearts_transcript_{name}.json"));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(quarantine_path(&path));
+        path
+    }
+
+    /// Plays `rounds` whole rounds with Normal bots in every seat.
+    fn played_match(seed: u64, rounds: usize) -> GameController {
+        let mut controller = GameController::new_with_seed(Some(seed), PlayerPosition::North);
+        controller.set_bot_difficulty(BotDifficulty::NormalHeuristic);
+        let mut session = GameSession::new(controller);
+        for _ in 0..rounds {
+            if session.controller().in_passing_phase() {
+                session
+                    .apply(SessionCommand::BotPasses { except: None })
+                    .unwrap();
+                session.apply(SessionCommand::ResolvePasses).unwrap();
+            }
+            while !session.controller().round_ready_for_scoring() {
+                session.apply(SessionCommand::BotTurn).unwrap();
+            }
+            session.apply(SessionCommand::FinishRound).unwrap();
+        }
+        session.into_controller()
+    }
+
+    #[test]
+    fn a_saved_match_replays_every_card() {
+        let path = temp_path("round_trip");
+        let controller = played_match(2191, 3);
+        let rounds = controller.played_rounds();
+        assert_eq!(rounds.len(), 3);
+        save_transcript(&path, rounds).unwrap();
+
+        let mut replay = load_replay(&path).unwrap();
+        assert_eq!(replay.round_count(), 3);
+        for (idx, recorded) in rounds.iter().enumerate() {
+            assert_eq!(replay.round_index(), idx);
+            assert_eq!(replay.scores().standings(), &recorded.scores);
+            let mut seen = Vec::new();
+            while let Some(play) = replay.step_forward() {
+                seen.push(play);
+            }
+            assert_eq!(seen, recorded.plays);
+            assert_eq!(replay.round().penalty_totals().iter().sum::<u8>(), 26);
+            replay.next_round();
+        }
+        // Each round starts from the scores the previous one ended on.
+        assert_eq!(rounds[0].scores, [0; 4]);
+        assert_ne!(rounds[2].scores, [0; 4]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn damaged_transcripts_are_refused() {
+        let path = temp_path("damaged");
+        let controller = played_match(7, 1);
+        let mut transcript = Transcript::capture(controller.played_rounds());
+        transcript.rounds[0].plays.swap(0, 1);
+        persist::save(&path, &transcript).unwrap();
+        let err = load_replay(&path).err().expect("the transcript is refused");
+        assert!(err.contains("cannot be replayed: round 0"), "{err}");
+
+        transcript.rounds[0].plays[0].card = "1Z".to_string();
+        persist::save(&path, &transcript).unwrap();
+        let err = load_replay(&path).err().expect("the transcript is refused");
+        assert!(err.contains("round 1: bad card"), "{err}");
+
+        persist::save(&path, &Transcript::capture(&[])).unwrap();
+        let err = load_replay(&path).err().expect("the transcript is refused");
+        assert!(err.contains("no rounds"), "{err}");
+
+        fs::write(&path, r#"{"schema_version":1,"rounds":"#).unwrap();
+        let err = load_replay(&path).err().expect("the transcript is refused");
+        assert!(err.contains("unreadable"), "{err}");
+        assert!(quarantine_path(&path).exists());
+    }
+}
@@ -9,7 +9,10 @@ fn test_cli_help() {
     cmd.arg("--help")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Available commands:"));
+        .stdout(predicate::str::contains("Available commands:"))
+        .stdout(predicate::str::contains(
+            "[--plan] [--deals-file <path.jsonl>]",
+        ));
 }
 
 #[test]
@@ -70,3 +73,193 @@ fn test_invalid_arg() {
         .success()
         .stdout(predicate::str::contains("Unknown command: --invalid-flag"));
 }
+
+#[test]
+#[allow(deprecated)]
+fn test_debug_deal_repl() {
+    let mut cmd = Command::cargo_bin("mdhearts").unwrap();
+    cmd.args(["--debug-deal", "77", "normal"])
+        .write_stdin("step 5\nbelief north\nhands\nquit\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Debugging seed 77"))
+        .stdout(predicate::str::contains("wins the trick"))
+        .stdout(predicate::str::contains("Belief for North"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_explain_pass_from_deal_string() {
+    let output = Command::cargo_bin("mdhearts")
+        .unwrap()
+        .args(["--explain-pass", "--seed", "17", "--seat", "west", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let by_seed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(by_seed["alternatives"].as_array().unwrap().len(), 5);
+    assert_eq!(by_seed["chosen"]["cards"].as_array().unwrap().len(), 3);
+
+    // The recorded deal string reproduces the same decision.
+    let deal = by_seed["deal"].as_str().unwrap();
+    let output = Command::cargo_bin("mdhearts")
+        .unwrap()
+        .args([
+            "--explain-pass",
+            "--deal",
+            deal,
+            "--seat",
+            "west",
+            "--direction",
+            "left",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    let by_deal: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(by_deal["chosen"], by_seed["chosen"]);
+
+    let mut cmd = Command::cargo_bin("mdhearts").unwrap();
+    cmd.args([
+        "--explain-pass",
+        "--seed",
+        "17",
+        "--seat",
+        "west",
+        "--top",
+        "2",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Pass for West (Left"))
+    .stdout(predicate::str::contains("Guards:"))
+    .stdout(predicate::str::contains("  3. "));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_soak_writes_a_health_report() {
+    let temp_dir = std::env::temp_dir().join("mdhearts_test_cli");
+    fs::create_dir_all(&temp_dir).unwrap();
+    let file_path = temp_dir.join("soak.json");
+    let _ = fs::remove_file(&file_path);
+
+    let mut cmd = Command::cargo_bin("mdhearts").unwrap();
+    cmd.args([
+        "--soak",
+        "--minutes",
+        "0.02",
+        "--seed",
+        "5",
+        "--out",
+        file_path.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("| pass_optimizer |"));
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&file_path).unwrap()).unwrap();
+    assert_eq!(report["seed"], 5);
+    assert_eq!(report["failures"], 0);
+    assert_eq!(report["stages"].as_array().unwrap().len(), 5);
+    let _ = fs::remove_file(&file_path);
+
+    let mut cmd = Command::cargo_bin("mdhearts").unwrap();
+    cmd.args(["--soak", "--minutes", "0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Invalid value for --minutes"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_match_mixed_against_the_reference_agent() {
+    let temp_dir = std::env::temp_dir().join(format!("mdhearts_ref_agent_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    let out = temp_dir.join("ref.csv");
+    let agent = env!("CARGO_BIN_EXE_mdhearts-ref-agent");
+    let mut cmd = Command::cargo_bin("mdhearts").unwrap();
+    cmd.args(["--match-mixed", "south", "500", "10", "nnnn", "--out"])
+        .arg(&out)
+        .args(["--agent", "west"])
+        .arg(format!(
+            "transport=stdio,command={agent} --name ref-west,timeout_ms=5000,fallback=easy,\
+             cwd={},env=MDH_REF_AGENT_LOG=requests.log",
+            temp_dir.display()
+        ))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "External agent West: ref-west fallbacks=0 reconnects=0 respawns=0",
+        ))
+        .stderr(predicate::str::contains("warning:").not());
+
+    let csv = fs::read_to_string(&out).unwrap();
+    assert_eq!(csv.lines().count(), 11, "{csv}");
+    let meta: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp_dir.join("ref.run_meta.json")).unwrap())
+            .unwrap();
+    let agents = &meta["external_agents"];
+    assert_eq!(agents[0]["name"], "ref-west");
+    assert_eq!(agents[0]["protocol"], 3);
+    assert_eq!(agents[0]["fallback"], "easy");
+    assert_eq!(agents[0]["fallbacks"], 0);
+
+    // One process for the run: one hello, a reset per hand, one shutdown.
+    let log = fs::read_to_string(temp_dir.join("requests.log")).unwrap();
+    let count = |kind: &str| log.lines().filter(|line| *line == kind).count();
+    assert_eq!(count("hello"), 1, "{log}");
+    assert_eq!(count("reset"), 10, "{log}");
+    assert_eq!(count("shutdown"), 1, "{log}");
+    assert!(count("pass_request") > 0, "{log}");
+    assert!(count("play_request") > 0, "{log}");
+    assert_eq!(count("pass") + count("play"), 0, "{log}");
+    assert_eq!(log.lines().last(), Some("shutdown"));
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_match_mixed_respawns_a_crashed_agent_once() {
+    let temp_dir =
+        std::env::temp_dir().join(format!("mdhearts_ref_agent_crash_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    let summary = temp_dir.join("summary.json");
+    let agent = env!("CARGO_BIN_EXE_mdhearts-ref-agent");
+    let mut cmd = Command::cargo_bin("mdhearts").unwrap();
+    cmd.args([
+        "--match-mixed",
+        "south",
+        "500",
+        "3",
+        "nnnn",
+        "--summary-out",
+    ])
+    .arg(&summary)
+    .args(["--agent", "east"])
+    .arg(format!(
+        "transport=stdio,command={agent} --crash-after 20,timeout_ms=5000,cwd={},\
+             env=MDH_REF_AGENT_LOG=requests.log",
+        temp_dir.display()
+    ))
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "External agent East: mdhearts-ref-agent fallbacks=0 reconnects=1 respawns=1",
+    ));
+
+    // The request the crash swallowed went to the new process.
+    let summary: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&summary).unwrap()).unwrap();
+    let agents = &summary["external_agents"];
+    assert_eq!(agents[0]["seat"], "East");
+    assert_eq!(agents[0]["respawns"], 1);
+    assert_eq!(agents[0]["fallbacks"], 0);
+    let log = fs::read_to_string(temp_dir.join("requests.log")).unwrap();
+    assert_eq!(log.lines().filter(|line| *line == "hello").count(), 2);
+    assert_eq!(log.lines().filter(|line| *line == "reset").count(), 3);
+    let _ = fs::remove_dir_all(&temp_dir);
+}
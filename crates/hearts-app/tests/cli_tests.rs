@@ -1,4 +1,4 @@
-use hearts_app::cli::{CliOutcome, run_cli_with_args};
+use hearts_app::cli::{CliError, CliOutcome, run_cli_with_args};
 use std::env;
 
 #[test]
@@ -15,6 +15,17 @@ fn test_cli_unknown_command() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_cli_seed_starts_interactive_match() {
+    let args = vec!["--seed".to_string(), "4242".to_string()];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::NotHandled)));
+    assert_eq!(hearts_app::cli::startup_seed(), Some(4242));
+
+    let bad = vec!["--seed".to_string(), "abc".to_string()];
+    assert!(run_cli_with_args(bad.into_iter()).is_err());
+}
+
 #[test]
 fn test_cli_show_weights() {
     let args = vec!["--show-weights".to_string()];
@@ -107,6 +118,104 @@ fn test_explain_once() {
     assert!(matches!(result, Ok(CliOutcome::Handled)));
 }
 
+/// A deal giving each seat one whole suit, starting with `first` for North.
+fn suited_deal(first: usize) -> String {
+    let suits = ["C", "D", "S", "H"];
+    let ranks = [
+        "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A",
+    ];
+    (0..4)
+        .map(|seat| {
+            let suit = suits[(first + seat) % 4];
+            ranks
+                .iter()
+                .map(|rank| format!("{rank}{suit}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+#[test]
+fn test_explain_once_with_deal() {
+    let args = vec![
+        "--explain-once".to_string(),
+        "777".to_string(),
+        "south".to_string(),
+        "normal".to_string(),
+        "--deal".to_string(),
+        suited_deal(0),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let duplicated = suited_deal(0).replacen("2D", "2C", 1);
+    let args = vec![
+        "--explain-once".to_string(),
+        "777".to_string(),
+        "south".to_string(),
+        "--deal".to_string(),
+        duplicated,
+    ];
+    let Err(CliError::InvalidValue { flag, value }) = run_cli_with_args(args.into_iter()) else {
+        panic!("expected --deal to be rejected");
+    };
+    assert_eq!(flag, "--deal");
+    assert_eq!(value, "2C is dealt to both North and East");
+}
+
+#[test]
+fn test_match_mixed_plays_deals_file() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_deals_file");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let deals = temp_dir.join("deals.jsonl");
+    let lines: Vec<String> = [0, 1]
+        .iter()
+        .map(|&first| serde_json::json!({ "deal": suited_deal(first) }).to_string())
+        .collect();
+    std::fs::write(&deals, lines.join("\n")).unwrap();
+    let out = temp_dir.join("mixed.csv");
+
+    let run = |count: &str, deals: &std::path::Path| {
+        let args = vec![
+            "--match-mixed".to_string(),
+            "south".to_string(),
+            "100".to_string(),
+            count.to_string(),
+            "nnnn".to_string(),
+            "--deals-file".to_string(),
+            deals.to_string_lossy().to_string(),
+            "--out".to_string(),
+            out.to_string_lossy().to_string(),
+        ];
+        run_cli_with_args(args.into_iter())
+    };
+    assert!(matches!(run("2", &deals), Ok(CliOutcome::Handled)));
+    let csv = std::fs::read_to_string(&out).unwrap();
+    assert_eq!(csv.lines().filter(|l| l.starts_with("10")).count(), 2);
+
+    let Err(CliError::InvalidValue { value, .. }) = run("3", &deals) else {
+        panic!("expected a short deals file to be rejected");
+    };
+    assert!(value.contains("holds 2 deals"), "{value}");
+
+    let bad = temp_dir.join("bad.jsonl");
+    let missing = suited_deal(0).replacen(" AH", "", 1);
+    std::fs::write(
+        &bad,
+        format!("{}\n{}\n", lines[0], serde_json::json!({ "deal": missing })),
+    )
+    .unwrap();
+    let Err(CliError::InvalidValue { value, .. }) = run("2", &bad) else {
+        panic!("expected a bad deal to be rejected");
+    };
+    assert!(value.ends_with(":2: no seat holds AH"), "{value}");
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
 #[test]
 fn test_explain_pass_once() {
     // --explain-pass-once <seed> <seat>
@@ -143,6 +252,675 @@ fn test_compare_batch() {
     let _ = std::fs::remove_dir_all(&temp_dir);
 }
 
+#[test]
+fn test_shadow_check_writes_disagreements() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_shadow");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let path = temp_dir.join("shadow.csv");
+
+    let args = vec![
+        "--shadow-check".to_string(),
+        "1000".to_string(),
+        "1".to_string(),
+        "easy".to_string(),
+        "normal".to_string(),
+        "--out".to_string(),
+        path.to_string_lossy().to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("seed,seat,phase,trick,primary,shadow,hand,trick_so_far")
+    );
+    assert!(lines.all(|line| line.starts_with("1000,")));
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_baseline_columns() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_baseline");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let path = temp_dir.join("mixed.csv");
+
+    let args = vec![
+        "--match-mixed".to_string(),
+        "south".to_string(),
+        "2000".to_string(),
+        "2".to_string(),
+        "nnnn".to_string(),
+        "--baseline".to_string(),
+        "--out".to_string(),
+        path.to_string_lossy().to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("seed,seat,mix,pen,baseline,above_baseline")
+    );
+    // An all-Normal field is its own reference, so every row sits exactly on the baseline.
+    for line in lines {
+        assert!(line.ends_with(", 0"), "unexpected row: {line}");
+    }
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_plan_writes_nothing() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_plan");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    let out = temp_dir.join("mixed.csv");
+    let outcomes = temp_dir.join("hands.jsonl");
+
+    let args = vec![
+        "--match-mixed".to_string(),
+        "south".to_string(),
+        "2000".to_string(),
+        "500".to_string(),
+        "nnnh".to_string(),
+        "--permutations".to_string(),
+        "full".to_string(),
+        "--out".to_string(),
+        out.to_string_lossy().to_string(),
+        "--hand-outcomes".to_string(),
+        outcomes.to_string_lossy().to_string(),
+        "--plan".to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+    assert!(!out.exists());
+    assert!(!outcomes.exists());
+    assert!(!temp_dir.exists());
+
+    let bad = vec![
+        "--match-mixed".to_string(),
+        "south".to_string(),
+        "2000".to_string(),
+        "5".to_string(),
+        "nnnh".to_string(),
+        "--permutations".to_string(),
+        "full".to_string(),
+        "--baseline".to_string(),
+        "--plan".to_string(),
+    ];
+    assert!(run_cli_with_args(bad.into_iter()).is_err());
+}
+
+#[test]
+fn test_match_mixed_queen_of_spades_summary() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_qs_stats");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let path = temp_dir.join("summary.json");
+
+    let args = vec![
+        "--match-mixed".to_string(),
+        "south".to_string(),
+        "3100".to_string(),
+        "3".to_string(),
+        "nnen".to_string(),
+        "--qs-stats".to_string(),
+        "--summary-out".to_string(),
+        path.to_string_lossy().to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let summary: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(summary["hands"], 3);
+    let agents = summary["queen_of_spades"].as_array().unwrap();
+    assert_eq!(agents.len(), 2);
+    assert_eq!(agents[0]["agent"], "normal");
+    assert_eq!(agents[0]["hands"], 9);
+    assert_eq!(agents[1]["agent"], "easy");
+    // Q♠ is dealt exactly once per hand across all agents.
+    let dealt: u64 = agents.iter().map(|a| a["dealt"].as_u64().unwrap()).sum();
+    assert_eq!(dealt, 3);
+    // Every trick has one leader and three followers; a hand stops once
+    // all 26 points are out, so it may play fewer than 13 tricks.
+    let tempo = summary["tempo"].as_array().unwrap();
+    let led: u64 = tempo
+        .iter()
+        .map(|a| a["tricks_led"].as_u64().unwrap())
+        .sum();
+    let followed: u64 = tempo
+        .iter()
+        .map(|a| a["tricks_followed"].as_u64().unwrap())
+        .sum();
+    assert!(led > 0 && led <= 3 * 13);
+    assert_eq!(followed, 3 * led);
+    // Both agents sat at every hand; the easy seat never counts a block pass.
+    let moon = summary["moon_defense"].as_array().unwrap();
+    assert_eq!(moon.len(), 2);
+    assert!(moon.iter().all(|a| a["hands"] == 3));
+    assert_eq!(moon[1]["block_passes"], 0);
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_short_game_records_target() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_target_score");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let out = temp_dir.join("match.csv");
+    let summary = temp_dir.join("summary.json");
+
+    let args = vec![
+        "--match-mixed".to_string(),
+        "north".to_string(),
+        "3300".to_string(),
+        "2".to_string(),
+        "nnnn".to_string(),
+        "--target-score".to_string(),
+        "50".to_string(),
+        "--out".to_string(),
+        out.to_string_lossy().to_string(),
+        "--summary-out".to_string(),
+        summary.to_string_lossy().to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let read = |path: &std::path::Path| -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    };
+    assert_eq!(read(&summary)["rules"]["target_score"], 50);
+    let meta = read(&temp_dir.join("match.run_meta.json"));
+    assert_eq!(meta["rules"]["target_score"], 50);
+    assert_eq!(meta["rules"]["pass_count"], 3);
+
+    let args = [
+        "--match-mixed",
+        "north",
+        "1",
+        "1",
+        "nnnn",
+        "--target-score",
+        "20",
+    ];
+    let result = run_cli_with_args(args.iter().map(|s| s.to_string()));
+    assert!(matches!(
+        result,
+        Err(CliError::InvalidValue {
+            flag: "--target-score",
+            ..
+        })
+    ));
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_four_card_pass_records_rules() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_pass_count");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let out = temp_dir.join("match.csv");
+    let summary = temp_dir.join("summary.json");
+
+    let args = vec![
+        "--match-mixed".to_string(),
+        "north".to_string(),
+        "3200".to_string(),
+        "2".to_string(),
+        "nnen".to_string(),
+        "--pass-count".to_string(),
+        "4".to_string(),
+        "--out".to_string(),
+        out.to_string_lossy().to_string(),
+        "--summary-out".to_string(),
+        summary.to_string_lossy().to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let read = |path: &std::path::Path| -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    };
+    assert_eq!(read(&summary)["rules"]["pass_count"], 4);
+    let meta = read(&temp_dir.join("match.run_meta.json"));
+    assert_eq!(meta["rules"]["pass_count"], 4);
+
+    let args = [
+        "--match-mixed",
+        "north",
+        "1",
+        "1",
+        "nnnn",
+        "--pass-count",
+        "5",
+    ];
+    let result = run_cli_with_args(args.iter().map(|s| s.to_string()));
+    assert!(matches!(
+        result,
+        Err(CliError::InvalidValue {
+            flag: "--pass-count",
+            ..
+        })
+    ));
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_memoize_reuses_baseline_decisions() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_memoize");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let run = |memoize: bool| {
+        let tag = if memoize { "memo" } else { "plain" };
+        let csv = temp_dir.join(format!("{tag}.csv"));
+        let summary = temp_dir.join(format!("{tag}.json"));
+        let mut args = vec![
+            "--match-mixed".to_string(),
+            "east".to_string(),
+            "3200".to_string(),
+            "2".to_string(),
+            "nnnn".to_string(),
+            "--baseline".to_string(),
+            "--out".to_string(),
+            csv.to_string_lossy().to_string(),
+            "--summary-out".to_string(),
+            summary.to_string_lossy().to_string(),
+        ];
+        if memoize {
+            args.push("--memoize".to_string());
+        }
+        let result = run_cli_with_args(args.into_iter());
+        assert!(matches!(result, Ok(CliOutcome::Handled)));
+        let summary: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&summary).unwrap()).unwrap();
+        (std::fs::read_to_string(&csv).unwrap(), summary)
+    };
+
+    let (plain_csv, plain) = run(false);
+    let (memo_csv, memo) = run(true);
+    assert_eq!(plain_csv, memo_csv);
+    assert!(plain["memo"].is_null());
+    // The all-Normal baseline replays each all-Normal deal exactly.
+    let hits = memo["memo"]["hits"].as_u64().unwrap();
+    assert!(hits > 0);
+    assert_eq!(hits, memo["memo"]["misses"].as_u64().unwrap());
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_adaptive_permutations() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_permutations");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let csv_path = temp_dir.join("perms.csv");
+    let summary_path = temp_dir.join("summary.json");
+
+    let args = vec![
+        "--match-mixed".to_string(),
+        "south".to_string(),
+        "3400".to_string(),
+        "3".to_string(),
+        "nnee".to_string(),
+        "--permutations".to_string(),
+        "adaptive".to_string(),
+        "--bootstrap".to_string(),
+        "200".to_string(),
+        "--out".to_string(),
+        csv_path.to_string_lossy().to_string(),
+        "--summary-out".to_string(),
+        summary_path.to_string_lossy().to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("seed,perm,mix,pen_n,pen_e,pen_s,pen_w,permutations")
+    );
+    let rows: Vec<Vec<String>> = lines
+        .map(|line| line.split(", ").map(str::to_string).collect())
+        .collect();
+    for seed in ["3400", "3401", "3402"] {
+        let deal: Vec<&Vec<String>> = rows.iter().filter(|r| r[0] == seed).collect();
+        // Balanced block first; six assignments exist for two agents of each.
+        assert!(
+            deal.len() == 4 || deal.len() == 6,
+            "seed {seed}: {}",
+            deal.len()
+        );
+        assert!(deal.iter().all(|r| r[7] == deal.len().to_string()));
+    }
+
+    let summary: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+    assert_eq!(summary["hands"], 3);
+    assert_eq!(summary["permutations"]["mode"], "adaptive");
+    assert_eq!(summary["permutations"]["available"], 18);
+    assert_eq!(summary["permutations"]["played"], rows.len());
+    let agents = summary["agent_pph"].as_array().unwrap();
+    assert_eq!(agents[0]["agent"], "normal");
+    assert_eq!(agents[1]["agent"], "easy");
+    assert_eq!(summary["paired"]["resamples"], 200);
+    assert_eq!(summary["paired"]["seed"], 3400);
+    let paired = summary["paired"]["comparisons"].as_array().unwrap();
+    assert_eq!(paired.len(), 1);
+    assert_eq!(paired[0]["left"], "normal");
+    assert_eq!(paired[0]["right"], "easy");
+    assert_eq!(paired[0]["deals"], 3);
+    let delta = paired[0]["mean_delta"].as_f64().unwrap();
+    let pph = |i: usize| agents[i]["pph"].as_f64().unwrap();
+    assert!((delta - (pph(1) - pph(0))).abs() < 1e-9);
+    assert!(paired[0]["ci_low"].as_f64().unwrap() <= delta);
+
+    let conflicting = vec![
+        "--match-mixed".to_string(),
+        "south".to_string(),
+        "3400".to_string(),
+        "1".to_string(),
+        "nnee".to_string(),
+        "--permutations".to_string(),
+        "full".to_string(),
+        "--baseline".to_string(),
+    ];
+    assert!(run_cli_with_args(conflicting.into_iter()).is_err());
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_hand_outcomes_read_back() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_hand_outcomes");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let csv_path = temp_dir.join("perms.csv");
+    let outcomes_path = temp_dir.join("outcomes.jsonl");
+
+    let args = vec![
+        "--match-mixed".to_string(),
+        "south".to_string(),
+        "3450".to_string(),
+        "2".to_string(),
+        "nnee".to_string(),
+        "--permutations".to_string(),
+        "full".to_string(),
+        "--out".to_string(),
+        csv_path.to_string_lossy().to_string(),
+        "--hand-outcomes".to_string(),
+        outcomes_path.to_string_lossy().to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    let csv_rows: Vec<Vec<String>> = csv
+        .lines()
+        .skip(1)
+        .map(|line| line.split(", ").map(str::to_string).collect())
+        .collect();
+    let jsonl = std::fs::read_to_string(&outcomes_path).unwrap();
+    let records: Vec<serde_json::Value> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    // One row per (hand, permutation), in the CSV's order.
+    assert_eq!(records.len(), 12);
+    assert_eq!(records.len(), csv_rows.len());
+    for (record, row) in records.iter().zip(&csv_rows) {
+        assert_eq!(record["seed"].to_string(), row[0]);
+        assert_eq!(record["perm"].to_string(), row[1]);
+        let penalties: Vec<u64> = record["penalties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p.as_u64().unwrap())
+            .collect();
+        assert_eq!(penalties.iter().sum::<u64>(), 26);
+        let csv_pens: Vec<u64> = row[3..7].iter().map(|p| p.parse().unwrap()).collect();
+        assert_eq!(penalties, csv_pens);
+        let seating: String = record["seating"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|agent| agent.as_str().unwrap().chars().next().unwrap())
+            .collect();
+        assert_eq!(seating, row[2]);
+        let shot = penalties.iter().position(|&p| p == 26);
+        assert_eq!(record["moon_shooter"].is_null(), shot.is_none());
+        if let Some(idx) = shot {
+            assert_eq!(record["moon_shooter"]["agent"], record["seating"][idx]);
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_permutations_match_across_thread_counts() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_threads");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+
+    let run = |threads: &str| {
+        let csv_path = temp_dir.join(format!("perms_{threads}.csv"));
+        let outcomes_path = temp_dir.join(format!("outcomes_{threads}.jsonl"));
+        let summary_path = temp_dir.join(format!("summary_{threads}.json"));
+        let args = vec![
+            "--match-mixed".to_string(),
+            "south".to_string(),
+            "3470".to_string(),
+            "6".to_string(),
+            "nnee".to_string(),
+            "--permutations".to_string(),
+            "adaptive".to_string(),
+            "--threads".to_string(),
+            threads.to_string(),
+            "--memoize".to_string(),
+            "--out".to_string(),
+            csv_path.to_string_lossy().to_string(),
+            "--hand-outcomes".to_string(),
+            outcomes_path.to_string_lossy().to_string(),
+            "--summary-out".to_string(),
+            summary_path.to_string_lossy().to_string(),
+        ];
+        let result = run_cli_with_args(args.into_iter());
+        assert!(matches!(result, Ok(CliOutcome::Handled)));
+        let mut summary: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+        // Wall-clock and CPU use are the only fields allowed to differ.
+        summary.as_object_mut().unwrap().remove("resources");
+        (
+            std::fs::read(&csv_path).unwrap(),
+            std::fs::read(&outcomes_path).unwrap(),
+            summary,
+        )
+    };
+    let (serial_csv, serial_outcomes, serial_summary) = run("1");
+    let (parallel_csv, parallel_outcomes, parallel_summary) = run("4");
+    assert!(!serial_outcomes.is_empty());
+    assert!(serial_outcomes == parallel_outcomes, "hand outcomes differ");
+    assert!(serial_csv == parallel_csv, "CSV rows differ");
+    assert_eq!(serial_summary, parallel_summary);
+
+    let serial_only = vec![
+        "--match-mixed".to_string(),
+        "south".to_string(),
+        "3470".to_string(),
+        "1".to_string(),
+        "nnee".to_string(),
+        "--threads".to_string(),
+        "2".to_string(),
+    ];
+    assert!(run_cli_with_args(serial_only.into_iter()).is_err());
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_field_shorthand() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_field");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let summary_path = temp_dir.join("summary.json");
+    let run = |mix: &str, group: bool| {
+        let mut args = vec![
+            "--match-mixed".to_string(),
+            "east".to_string(),
+            "3500".to_string(),
+            "2".to_string(),
+            mix.to_string(),
+            "--summary-out".to_string(),
+            summary_path.to_string_lossy().to_string(),
+        ];
+        if group {
+            args.push("--group-field".to_string());
+        }
+        run_cli_with_args(args.into_iter())
+    };
+
+    assert!(matches!(run("e+3n", false), Ok(CliOutcome::Handled)));
+    let summary: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+    assert_eq!(summary["mix"], "nenn");
+    assert_eq!(
+        summary["agents"],
+        serde_json::json!(["baseline-1", "easy", "baseline-2", "baseline-3"])
+    );
+    assert_eq!(summary["field"]["difficulty"], "normal");
+    assert_eq!(summary["field"]["count"], 3);
+    assert_eq!(summary["queen_of_spades"].as_array().unwrap().len(), 4);
+
+    assert!(matches!(run("e+3n", true), Ok(CliOutcome::Handled)));
+    let summary: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+    assert_eq!(summary["field"]["grouped"], true);
+    let rows = summary["queen_of_spades"].as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["agent"], "baseline");
+    assert_eq!(rows[0]["hands"], 6);
+    assert_eq!(rows[1]["agent"], "easy");
+
+    // The expanded mix still has to seat exactly four agents.
+    assert!(run("e+2n", false).is_err());
+    assert!(run("nenn", true).is_err());
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_export_play_dataset_stratified_splits() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_dataset_splits");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let out = temp_dir.join("play.jsonl");
+    let export = |seed_start: &str| {
+        let args = vec![
+            "--export-play-dataset".to_string(),
+            "south".to_string(),
+            seed_start.to_string(),
+            "8".to_string(),
+            "normal".to_string(),
+            out.to_string_lossy().to_string(),
+            "--balance-passing".to_string(),
+            "--split".to_string(),
+            "50,25,25".to_string(),
+        ];
+        let result = run_cli_with_args(args.into_iter());
+        assert!(matches!(result, Ok(CliOutcome::Handled)));
+        let manifest: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(temp_dir.join("play.manifest.json")).unwrap(),
+        )
+        .unwrap();
+        let mut assigned = std::collections::HashMap::new();
+        for split in ["train", "val", "test"] {
+            for entry in manifest[split].as_array().unwrap() {
+                let fingerprint = entry["fingerprint"].as_str().unwrap().to_string();
+                assert!(assigned.insert(fingerprint, split).is_none());
+            }
+        }
+        assigned
+    };
+
+    let first = export("5000");
+    assert_eq!(first.len(), 8);
+    let mut passing = std::collections::HashSet::new();
+    for split in ["train", "val", "test"] {
+        let body = std::fs::read_to_string(temp_dir.join(format!("play.{split}.jsonl"))).unwrap();
+        for line in body.lines() {
+            let sample: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(sample["split"], split);
+            assert_eq!(first[sample["deal"].as_str().unwrap()], split);
+            passing.insert(sample["passing"].as_str().unwrap().to_string());
+        }
+    }
+    assert_eq!(passing.len(), 4);
+
+    // Overlapping seeds: the four shared deals keep their splits.
+    let second = export("5004");
+    assert_eq!(second.len(), 12);
+    for (fingerprint, split) in &first {
+        assert_eq!(second[fingerprint], *split);
+    }
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_match_mixed_interpolates_output_paths() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_interpolate");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    unsafe {
+        env::set_var("MDH_CLI_TEST_RUNS", temp_dir.to_string_lossy().to_string());
+    }
+    let args = vec![
+        "--match-mixed".to_string(),
+        "east".to_string(),
+        "3300".to_string(),
+        "1".to_string(),
+        "nnnn".to_string(),
+        "--out".to_string(),
+        "${MDH_CLI_TEST_RUNS}/${run_id}/match.csv".to_string(),
+        "--summary-out".to_string(),
+        "${MDH_CLI_TEST_RUNS}/{run_id}/summary.json".to_string(),
+        "--run-id".to_string(),
+        "r7".to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+    assert!(temp_dir.join("r7").join("match.csv").exists());
+    assert!(temp_dir.join("r7").join("summary.json").exists());
+
+    let missing = vec![
+        "--match-batch".to_string(),
+        "east".to_string(),
+        "3300".to_string(),
+        "1".to_string(),
+        "normal".to_string(),
+        "normal".to_string(),
+        "--out".to_string(),
+        "${MDH_CLI_TEST_UNSET_DIR}/match.csv".to_string(),
+    ];
+    let err = run_cli_with_args(missing.into_iter()).err().unwrap();
+    assert!(
+        err.to_string()
+            .contains("MDH_CLI_TEST_UNSET_DIR is not set"),
+        "{err}"
+    );
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
 #[test]
 fn test_export_play_dataset() {
     let temp_dir = env::temp_dir().join("mdhearts_cli_test_dataset");
@@ -165,3 +943,501 @@ fn test_export_play_dataset() {
 
     let _ = std::fs::remove_dir_all(&temp_dir);
 }
+
+#[test]
+fn test_compressed_outputs_and_run_retention() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_outputs");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    let runs = temp_dir.join("runs");
+    for old in ["r1", "r2"] {
+        std::fs::create_dir_all(runs.join(old)).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    let template = runs.join("${run_id}");
+    let args = vec![
+        "--match-mixed".to_string(),
+        "north".to_string(),
+        "3600".to_string(),
+        "1".to_string(),
+        "nnnn".to_string(),
+        "--run-id".to_string(),
+        "r3".to_string(),
+        "--out".to_string(),
+        template.join("match.csv").to_string_lossy().to_string(),
+        "--telemetry-out".to_string(),
+        template.join("hard.ndjson").to_string_lossy().to_string(),
+        "--compress".to_string(),
+        "zstd".to_string(),
+        "--keep-last".to_string(),
+        "2".to_string(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+    assert!(runs.join("r3").join("match.csv").exists());
+    assert!(runs.join("r2").is_dir());
+    assert!(!runs.join("r1").exists());
+
+    // The telemetry stream is written compressed and reads back through the linter.
+    let telemetry = runs.join("r3").join("hard.ndjson.zst");
+    let bytes = std::fs::read(&telemetry).unwrap();
+    assert_eq!(&bytes[..4], &[0x28, 0xb5, 0x2f, 0xfd]);
+    let lint = vec![
+        "--lint-telemetry".to_string(),
+        telemetry.to_string_lossy().to_string(),
+    ];
+    assert!(matches!(
+        run_cli_with_args(lint.into_iter()),
+        Ok(CliOutcome::Handled)
+    ));
+
+    let out = temp_dir.join("play.jsonl");
+    let export = vec![
+        "--export-play-dataset".to_string(),
+        "south".to_string(),
+        "3600".to_string(),
+        "3".to_string(),
+        "normal".to_string(),
+        out.to_string_lossy().to_string(),
+        "--compress".to_string(),
+        "gzip".to_string(),
+    ];
+    assert!(matches!(
+        run_cli_with_args(export.into_iter()),
+        Ok(CliOutcome::Handled)
+    ));
+    let compressed = temp_dir.join("play.jsonl.gz");
+    let contents = hearts_app::outputs::read_to_string(&compressed).unwrap();
+    let samples: Vec<serde_json::Value> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(samples.len(), 3);
+
+    // Retention needs a per-run directory to prune.
+    let flat = vec![
+        "--match-mixed".to_string(),
+        "north".to_string(),
+        "3600".to_string(),
+        "1".to_string(),
+        "nnnn".to_string(),
+        "--out".to_string(),
+        temp_dir
+            .join("match-${run_id}.csv")
+            .to_string_lossy()
+            .to_string(),
+        "--keep-last".to_string(),
+        "2".to_string(),
+    ];
+    assert!(run_cli_with_args(flat.into_iter()).is_err());
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_export_play_dataset_augment_symmetries() {
+    let temp_dir = std::env::temp_dir().join("mdhearts_cli_augment");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let out = temp_dir.join("play.jsonl");
+    let export = vec![
+        "--export-play-dataset".to_string(),
+        "south".to_string(),
+        "3650".to_string(),
+        "4".to_string(),
+        "normal".to_string(),
+        out.to_string_lossy().to_string(),
+        "--augment-symmetries".to_string(),
+    ];
+    assert!(matches!(
+        run_cli_with_args(export.into_iter()),
+        Ok(CliOutcome::Handled)
+    ));
+    let rows: Vec<serde_json::Value> = std::fs::read_to_string(&out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let canonical: Vec<&serde_json::Value> = rows
+        .iter()
+        .filter(|row| row.get("symmetry").is_none())
+        .collect();
+    assert_eq!(canonical.len(), 4);
+    for pair in rows.windows(2) {
+        if pair[1]["symmetry"] != "swap_minors" {
+            continue;
+        }
+        assert_eq!(pair[0]["deal"], pair[1]["deal"]);
+        let swap = |card: &str| match card.chars().last() {
+            Some('C') => card.replace('C', "D"),
+            Some('D') => card.replace('D', "C"),
+            _ => card.to_string(),
+        };
+        let original: Vec<String> = pair[0]["candidates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| swap(c["card"].as_str().unwrap()))
+            .collect();
+        let swapped: Vec<&str> = pair[1]["candidates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["card"].as_str().unwrap())
+            .collect();
+        assert_eq!(original, swapped);
+    }
+    assert!(rows.len() > canonical.len());
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_compare_runs_reports_configuration_drift() {
+    let temp_dir = std::env::temp_dir().join("mdhearts_cli_compare_runs");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let run = |name: &str, seed_start: &str, b: &str| {
+        let out = temp_dir.join(format!("{name}.csv"));
+        let args = vec![
+            "--match-batch".to_string(),
+            "west".to_string(),
+            seed_start.to_string(),
+            "3".to_string(),
+            "normal".to_string(),
+            b.to_string(),
+            "--out".to_string(),
+            out.to_string_lossy().to_string(),
+        ];
+        assert!(matches!(
+            run_cli_with_args(args.into_iter()),
+            Ok(CliOutcome::Handled)
+        ));
+        out
+    };
+    let a = run("a", "3700", "normal");
+    let b = run("b", "3700", "easy");
+    let shifted = run("c", "3710", "normal");
+
+    let meta: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(temp_dir.join("a.run_meta.json")).unwrap())
+            .unwrap();
+    assert_eq!(meta["command"], "match-batch");
+    assert_eq!(meta["seeds"]["start"], 3700);
+    assert_eq!(meta["difficulties"][1], "normal");
+    assert!(meta["versions"]["mdhearts"].is_string());
+    assert!(meta["env"].is_object());
+    assert!(meta["resources"]["wall_secs"].as_f64().unwrap() > 0.0);
+
+    let diffs = hearts_app::run_meta::diff(
+        &meta,
+        &serde_json::from_str(&std::fs::read_to_string(temp_dir.join("b.run_meta.json")).unwrap())
+            .unwrap(),
+    );
+    let paths: Vec<&str> = diffs.iter().map(|d| d.path.as_str()).collect();
+    // Other tests in this binary set MDH_* variables, so `env` may drift too.
+    assert!(paths.contains(&"difficulties[1]"), "{paths:?}");
+    assert!(
+        paths
+            .iter()
+            .all(|p| *p == "difficulties[1]" || p.starts_with("env."))
+    );
+
+    let compare = |left: &std::path::Path, right: &std::path::Path, extra: &[&str]| {
+        let mut args = vec![
+            "--compare-runs".to_string(),
+            left.to_string_lossy().to_string(),
+            right.to_string_lossy().to_string(),
+        ];
+        args.extend(extra.iter().map(|s| s.to_string()));
+        run_cli_with_args(args.into_iter())
+    };
+    assert!(matches!(compare(&a, &b, &[]), Ok(CliOutcome::Handled)));
+    assert!(matches!(
+        compare(&a, &shifted, &[]),
+        Ok(CliOutcome::Handled)
+    ));
+    assert!(matches!(
+        compare(&a, &shifted, &["--allow-unpaired"]),
+        Ok(CliOutcome::Handled)
+    ));
+    assert!(compare(&a, &temp_dir.join("missing.csv"), &[]).is_err());
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_cli_find_seed() {
+    let args = [
+        "--find-seed",
+        "--predicate",
+        "q_spade_self_capture",
+        "--max-seeds",
+        "8",
+        "--threads",
+        "2",
+    ]
+    .map(String::from);
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let bad = ["--find-seed", "--predicate", "moon"].map(String::from);
+    assert!(matches!(
+        run_cli_with_args(bad.into_iter()),
+        Err(CliError::InvalidValue {
+            flag: "--predicate",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_match_full_rejects_an_unknown_mix() {
+    let args = ["--match-full", "1", "1", "nnxn"].map(String::from);
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(
+        result,
+        Err(CliError::InvalidValue {
+            flag: "--match-full",
+            ..
+        })
+    ));
+    let args = ["--match-full", "1", "1", "nnnn", "--target-score", "10"].map(String::from);
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(
+        result,
+        Err(CliError::InvalidValue {
+            flag: "--target-score",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_export_human_plays() {
+    let temp_dir = env::temp_dir().join("mdhearts_cli_test_human_plays");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let from = temp_dir.join("plays.jsonl");
+    let out = temp_dir.join("export").join("plays.jsonl");
+
+    let args = [
+        "--export-human-plays".to_string(),
+        out.to_string_lossy().to_string(),
+        "--from".to_string(),
+        from.to_string_lossy().to_string(),
+    ];
+    let result = run_cli_with_args(args.clone().into_iter());
+    assert!(matches!(result, Err(CliError::Io(_))));
+
+    std::fs::write(&from, "not json\n").unwrap();
+    let result = run_cli_with_args(args.clone().into_iter());
+    assert!(result.is_err());
+    assert!(!out.exists());
+
+    std::fs::write(&from, "").unwrap();
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+    assert!(out.exists());
+}
+
+#[test]
+fn test_match_full_plays_omnibus() {
+    let args = ["--match-full", "3", "1", "nnnn", "--jack-of-diamonds"].map(String::from);
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+}
+
+#[test]
+fn test_match_full_and_mixed_play_the_sun_rule() {
+    let dir = env::temp_dir().join(format!("mdh_sun_rule_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let full = dir.join("full.json");
+    let args = vec![
+        "--match-full".to_string(),
+        "3".to_string(),
+        "1".to_string(),
+        "nnnn".to_string(),
+        "--shoot-the-sun".to_string(),
+        "--summary-out".to_string(),
+        full.to_string_lossy().into_owned(),
+    ];
+    assert!(matches!(
+        run_cli_with_args(args.into_iter()),
+        Ok(CliOutcome::Handled)
+    ));
+    let summary: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&full).unwrap()).unwrap();
+    assert_eq!(summary["rules"]["shoot_the_sun"], true);
+    assert!(summary["suns"].is_u64());
+
+    let mixed = dir.join("mixed.json");
+    let run_mixed = |sun: bool| {
+        let mut args = vec![
+            "--match-mixed".to_string(),
+            "south".to_string(),
+            "1".to_string(),
+            "2".to_string(),
+            "nnnn".to_string(),
+            "--summary-out".to_string(),
+            mixed.to_string_lossy().into_owned(),
+        ];
+        if sun {
+            args.push("--shoot-the-sun".to_string());
+        }
+        assert!(matches!(
+            run_cli_with_args(args.into_iter()),
+            Ok(CliOutcome::Handled)
+        ));
+        let summary: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&mixed).unwrap()).unwrap();
+        summary
+    };
+    // Sun shooters are counted per seat only when the rule is on.
+    assert_eq!(run_mixed(true)["suns"].as_array().map(Vec::len), Some(4));
+    assert!(run_mixed(false)["suns"].is_null());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_match_full_logs_hands_and_match_rows() {
+    let dir = env::temp_dir().join(format!("mdh_match_full_rows_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let rows_path = dir.join("hands.jsonl");
+    let summary_path = dir.join("summary.json");
+    let args = vec![
+        "--match-full".to_string(),
+        "7".to_string(),
+        "2".to_string(),
+        "nnne".to_string(),
+        "--hand-outcomes".to_string(),
+        rows_path.to_string_lossy().into_owned(),
+        "--summary-out".to_string(),
+        summary_path.to_string_lossy().into_owned(),
+    ];
+    let result = run_cli_with_args(args.into_iter());
+    assert!(matches!(result, Ok(CliOutcome::Handled)));
+
+    let rows: Vec<serde_json::Value> = std::fs::read_to_string(&rows_path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let matches: Vec<&serde_json::Value> = rows.iter().filter(|r| r["row"] == "match").collect();
+    assert_eq!(matches.len(), 2);
+    let mut hands = 0;
+    for row in &rows {
+        if row["row"] == "match" {
+            // Each match row closes its hands, whose last totals are final.
+            let last = &rows[rows.iter().position(|r| r == row).unwrap() - 1];
+            assert_eq!(last["row"], "hand");
+            assert_eq!(last["totals"], row["totals"]);
+            assert_eq!(last["round"], row["rounds"]);
+            let totals: Vec<u64> = row["totals"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t.as_u64().unwrap())
+                .collect();
+            assert!(totals.iter().any(|&t| t >= 100));
+            let winner = ["North", "East", "South", "West"]
+                .iter()
+                .position(|seat| row["winner"] == *seat)
+                .unwrap();
+            assert_eq!(row["placements"][winner], 1);
+        } else {
+            hands += 1;
+            assert_eq!(
+                row["seating"],
+                serde_json::json!(["normal", "normal", "normal", "easy"])
+            );
+            assert_eq!(row["tricks"].as_array().unwrap().len(), 13);
+        }
+    }
+
+    let summary: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+    assert_eq!(summary["matches"], 2);
+    assert_eq!(summary["hands"], hands);
+    let agents = summary["agents"].as_array().unwrap();
+    assert_eq!(agents.len(), 2);
+    assert_eq!(agents[0]["agent"], "normal");
+    assert_eq!(agents[0]["seats"], 6);
+    assert_eq!(agents[1]["agent"], "easy");
+    assert_eq!(agents[1]["hands"], hands);
+    let wins: u64 = agents.iter().map(|a| a["wins"].as_u64().unwrap()).sum();
+    assert!(wins >= 2);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_match_mixed_ratings_carry_on_from_a_prior() {
+    let dir = env::temp_dir().join(format!("mdh_match_ratings_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let first = dir.join("first.json");
+    let second = dir.join("second.json");
+    let summary = dir.join("summary.json");
+    let run = |seed: &str, extra: &[&std::path::Path]| {
+        let mut args: Vec<String> = ["--match-mixed", "south", seed, "3", "nnee"]
+            .map(String::from)
+            .to_vec();
+        for (flag, path) in ["--ratings-out", "--ratings-prior", "--summary-out"]
+            .iter()
+            .zip(extra)
+        {
+            args.push(flag.to_string());
+            args.push(path.to_string_lossy().into_owned());
+        }
+        let result = run_cli_with_args(args.into_iter());
+        assert!(matches!(result, Ok(CliOutcome::Handled)));
+    };
+    let read = |path: &std::path::Path| -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    };
+    run("10", &[&first]);
+    let ratings = read(&first);
+    assert_eq!(ratings["unit"], "hand");
+    let agents = ratings["agents"].as_array().unwrap();
+    assert_eq!(agents.len(), 2);
+    for agent in agents {
+        // Two seats a hand over three hands.
+        assert_eq!(agent["games"], 6);
+        let (mu, sigma) = (
+            agent["mu"].as_f64().unwrap(),
+            agent["sigma"].as_f64().unwrap(),
+        );
+        assert!((agent["rating"].as_f64().unwrap() - (mu - 3.0 * sigma)).abs() < 1e-9);
+        assert!(sigma < 25.0 / 3.0);
+    }
+
+    run("13", &[&second, &first, &summary]);
+    let carried = read(&second);
+    for agent in carried["agents"].as_array().unwrap() {
+        assert_eq!(agent["games"], 12);
+    }
+    assert_eq!(read(&summary)["ratings"], carried["agents"]);
+
+    // A prior naming one agent twice, in two cases, would split its rating.
+    let clash = dir.join("clash.json");
+    let mut clashing = carried.clone();
+    let mut shouted = clashing["agents"][0].clone();
+    let name = shouted["agent"].as_str().unwrap().to_string();
+    shouted["agent"] = serde_json::json!(name.to_uppercase());
+    clashing["agents"].as_array_mut().unwrap().push(shouted);
+    std::fs::write(&clash, clashing.to_string()).unwrap();
+    let args = [
+        "--match-mixed",
+        "south",
+        "16",
+        "1",
+        "nnee",
+        "--ratings-prior",
+    ]
+    .map(String::from)
+    .into_iter()
+    .chain([clash.to_string_lossy().into_owned()]);
+    let Err(CliError::InvalidValue { flag, value }) = run_cli_with_args(args) else {
+        panic!("a clashing prior was accepted");
+    };
+    assert_eq!(flag, "--ratings-prior");
+    let clashing_names = format!("{name:?} and {:?}", name.to_uppercase());
+    assert!(value.contains(&clashing_names), "{value}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
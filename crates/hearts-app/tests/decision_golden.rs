@@ -0,0 +1,27 @@
+use hearts_app::determinism::{check_golden, load_golden, record_golden};
+use std::path::PathBuf;
+
+fn golden_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/decision_golden.json")
+}
+
+#[test]
+fn seeded_self_play_matches_blessed_decisions() {
+    // Re-bless after an intended behavior change with:
+    //   mdhearts --decision-golden crates/hearts-app/tests/fixtures/decision_golden.json --bless
+    if let Err(diff) = check_golden(&golden_path()) {
+        panic!("decision stream changed: {diff}");
+    }
+}
+
+#[test]
+fn golden_covers_fifty_full_rounds() {
+    let golden = load_golden(&golden_path()).expect("golden loads");
+    assert_eq!(golden.count, 50);
+    assert_eq!(golden.rounds.len(), 50);
+    assert!(golden.rounds.iter().all(|round| round.plays.len() == 52));
+    // Recording twice in one process gives the same stream.
+    let once = record_golden(&golden.mix, golden.seed_start, 2).unwrap();
+    let twice = record_golden(&golden.mix, golden.seed_start, 2).unwrap();
+    assert_eq!(once, twice);
+}
@@ -60,9 +60,11 @@ fn west_seed_1141_expected_tops_under_adaptive_and_sampling() {
         .map(|(c, _)| *c)
         .unwrap();
 
+    // Normal discarded 3♠ before the exit preservation term; it now keeps
+    // its only low spade under Q♠ and sheds A♦.
     assert_eq!(
         n_top,
-        Card::new(Rank::Three, Suit::Spades),
+        Card::new(Rank::Ace, Suit::Diamonds),
         "Normal top changed; update golden if intentional"
     );
     let expected = [
@@ -135,9 +137,11 @@ fn west_seed_1082_expected_tops_under_adaptive_and_sampling() {
         Card::new(Rank::Ten, Suit::Spades),
         "Normal top changed; update golden if intentional"
     );
+    // Hard's lines are played out with Normal's scoring, which now keeps
+    // the lowest spade as the exit: 5♠ goes where 2♠ used to.
     let expected = [
         Card::new(Rank::Jack, Suit::Diamonds),
-        Card::new(Rank::Two, Suit::Spades),
+        Card::new(Rank::Five, Suit::Spades),
     ];
     assert!(
         expected.contains(&h_top),
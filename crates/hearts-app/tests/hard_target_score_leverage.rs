@@ -0,0 +1,54 @@
+use hearts_app::bot::{BotContext, BotDifficulty, PlayPlannerHard, UnseenTracker, search};
+use hearts_app::controller::GameController;
+use hearts_core::model::passing::PassingDirection;
+use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::score::ScoreBoard;
+
+// East on 45 is within 90% of a 50-point target but only at 45% of 100, so
+// Hard's search leverage (and with it the width of its search) must rise in
+// the short game for the same position.
+#[test]
+fn leverage_measures_the_leader_against_the_match_target() {
+    // Leverage is measured by the step search, behind the deep search.
+    unsafe { std::env::set_var("MDH_SEARCH_DEEPER_ENABLED", "0") };
+    let seat = PlayerPosition::West;
+    let mut controller = GameController::new_with_seed(Some(2244), PlayerPosition::North);
+    if controller.in_passing_phase() {
+        let _ = controller.submit_auto_passes_for_others(seat);
+        if let Some(cards) = controller.simple_pass_for(seat) {
+            let _ = controller.submit_pass(seat, cards);
+        }
+        let _ = controller.resolve_passes();
+    }
+    // Past the first trick, to a West decision with a real choice.
+    while controller.expected_to_play() != seat
+        || controller.round().is_first_trick()
+        || controller.legal_moves(seat).len() < 2
+    {
+        let next = controller.expected_to_play();
+        controller.autoplay_one(next.next()).expect("play goes on");
+    }
+    let round = controller.round().clone();
+    let mut tracker = UnseenTracker::new();
+    tracker.reset_for_round(&round);
+    let mut scores = ScoreBoard::new();
+    scores.set_totals([10, 45, 20, 15]);
+    let legal = controller.legal_moves(seat);
+
+    let leverage = |target: u32| {
+        let ctx = BotContext::new(
+            seat,
+            &round,
+            scores,
+            PassingDirection::Hold,
+            &tracker,
+            BotDifficulty::FutureHard,
+        )
+        .with_target_score(target);
+        PlayPlannerHard::choose(&legal, &ctx).expect("a card");
+        search::last_stats().expect("search ran").leverage_score
+    };
+    let standard = leverage(100);
+    let short = leverage(50);
+    assert_eq!(short, standard + 30, "standard={standard} short={short}");
+}
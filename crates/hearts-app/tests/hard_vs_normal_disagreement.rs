@@ -78,7 +78,7 @@ fn hard_vs_normal_disagree_on_seed_1040_west() {
 }
 
 #[test]
-fn hard_vs_normal_disagree_on_seed_1080_south() {
+fn hard_and_normal_agree_on_seed_1080_south() {
     let seed: u64 = 1080;
     let seat = PlayerPosition::South;
 
@@ -125,11 +125,13 @@ fn hard_vs_normal_disagree_on_seed_1080_south() {
         .map(|(c, _)| *c)
         .unwrap();
 
-    assert_ne!(
-        n_top, h_top,
-        "Expected a disagreement between Normal and Hard top choices (seed {} {:?})",
-        seed, seat
-    );
+    // South is void in clubs on the first trick. Hard used to discard 7♠,
+    // South's only spade below 8 with 10♠ and J♠ behind it; the exit
+    // preservation term now keeps 7♠ as the way out of spades, so Hard sheds
+    // J♠ as Normal does.
+    let jack_spades = Card::new(Rank::Jack, Suit::Spades);
+    assert_eq!(n_top, jack_spades, "Normal top changed for seed {seed}");
+    assert_eq!(h_top, jack_spades, "Hard top changed for seed {seed}");
 }
 
 #[test]
@@ -186,8 +188,8 @@ fn hard_vs_normal_disagree_on_seed_1145_north() {
 }
 
 #[test]
-fn hard_vs_normal_disagree_on_seed_2031_east() {
-    let seed: u64 = 2031;
+fn hard_vs_normal_disagree_on_seed_2044_east() {
+    let seed: u64 = 2044;
     let seat = PlayerPosition::East;
 
     let mut normal = GameController::new_with_seed(Some(seed), PlayerPosition::North);
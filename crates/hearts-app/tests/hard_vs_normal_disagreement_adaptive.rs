@@ -1,8 +1,11 @@
 use hearts_app::controller::GameController;
+use hearts_core::model::card::Card;
 use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rank::Rank;
+use hearts_core::model::suit::Suit;
 
 #[test]
-fn west_seed_1141_disagreement_under_adaptive_and_sampling() {
+fn west_seed_1141_agreement_under_adaptive_and_sampling() {
     // Enable adaptive limits, third-opponent branching, and sampling to reproduce this curated disagreement.
     unsafe {
         std::env::set_var("MDH_HARD_ADAPTIVE", "1");
@@ -57,9 +60,10 @@ fn west_seed_1141_disagreement_under_adaptive_and_sampling() {
         .map(|(c, _)| *c)
         .unwrap();
 
-    assert_ne!(
-        n_top, h_top,
-        "Expected disagreement at seed {} {:?}",
-        seed, seat
-    );
+    // West is void in clubs on the first trick. Normal used to discard 3♠,
+    // its only spade below 8 with Q♠ behind it, where Hard shed A♦. The exit
+    // preservation term keeps 3♠ to get out of spades, so both shed A♦.
+    let ace_diamonds = Card::new(Rank::Ace, Suit::Diamonds);
+    assert_eq!(n_top, ace_diamonds, "Normal top changed at seed {seed}");
+    assert_eq!(h_top, ace_diamonds, "Hard top changed at seed {seed}");
 }
@@ -1,7 +1,11 @@
 use hearts_app::controller::GameController;
+use hearts_core::model::card::Card;
 use hearts_core::model::player::PlayerPosition;
+use hearts_core::model::rank::Rank;
+use hearts_core::model::suit::Suit;
 
-fn assert_disagree(seed: u64, seat: PlayerPosition) {
+/// Normal's and Hard's top choices at `seed`/`seat`, deterministically.
+fn tops(seed: u64, seat: PlayerPosition) -> (Card, Card) {
     unsafe {
         std::env::set_var("MDH_HARD_DETERMINISTIC", "1");
         std::env::set_var("MDH_HARD_TEST_STEPS", "80");
@@ -48,16 +52,20 @@ fn assert_disagree(seed: u64, seat: PlayerPosition) {
         .map(|(c, _)| c)
         .unwrap();
 
+    unsafe {
+        std::env::remove_var("MDH_HARD_DETERMINISTIC");
+        std::env::remove_var("MDH_HARD_TEST_STEPS");
+    }
+    (n_top, h_top)
+}
+
+fn assert_disagree(seed: u64, seat: PlayerPosition) {
+    let (n_top, h_top) = tops(seed, seat);
     assert_ne!(
         n_top, h_top,
         "Expected Normal and Hard to disagree deterministically on {}/{:?}",
         seed, seat
     );
-
-    unsafe {
-        std::env::remove_var("MDH_HARD_DETERMINISTIC");
-        std::env::remove_var("MDH_HARD_TEST_STEPS");
-    }
 }
 
 #[test]
@@ -71,6 +79,12 @@ fn hard_vs_normal_disagree_seed_1145_north() {
 }
 
 #[test]
-fn hard_vs_normal_disagree_seed_1080_south() {
-    assert_disagree(1080, PlayerPosition::South);
+fn hard_and_normal_agree_seed_1080_south() {
+    // Hard used to discard 7♠ here, South's only spade below 8 with 10♠ and
+    // J♠ behind it. The exit preservation term keeps it, and both shed J♠.
+    let jack_spades = Card::new(Rank::Jack, Suit::Spades);
+    assert_eq!(
+        tops(1080, PlayerPosition::South),
+        (jack_spades, jack_spades)
+    );
 }
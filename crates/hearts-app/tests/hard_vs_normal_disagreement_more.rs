@@ -26,7 +26,7 @@ fn top_card_for(controller: &mut GameController, seat: PlayerPosition) -> Card {
 }
 
 #[test]
-fn west_seed_1082_normal_10s_hard_2s() {
+fn west_seed_1082_normal_10s_hard_5s() {
     let seed: u64 = 1082;
     let seat = PlayerPosition::West;
     // Normal
@@ -44,7 +44,10 @@ fn west_seed_1082_normal_10s_hard_2s() {
         seed, seat
     );
     assert_eq!(n_top, Card::new(Rank::Ten, Suit::Spades));
-    assert_eq!(h_top, Card::new(Rank::Two, Suit::Spades));
+    // Hard discarded 2♠ before the exit preservation term. Its lines are
+    // played out with Normal's scoring, which now keeps the lowest spade to
+    // get out of the suit later, so 5♠ goes instead.
+    assert_eq!(h_top, Card::new(Rank::Five, Suit::Spades));
 }
 
 #[test]
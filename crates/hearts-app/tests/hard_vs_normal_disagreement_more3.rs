@@ -218,13 +218,14 @@ fn hard_vs_normal_disagree_on_seed_1159_north() {
     let normal_top = top_for(seed, seat, false);
     let hard_top = top_for(seed, seat, true);
 
+    // Both now shed the thinly guarded K♠ while Q♠ is still out.
     let normal_expected = Card {
-        rank: Rank::Two,
-        suit: Suit::Diamonds,
+        rank: Rank::King,
+        suit: Suit::Spades,
     };
     let hard_expected = Card {
-        rank: Rank::Two,
-        suit: Suit::Diamonds,
+        rank: Rank::King,
+        suit: Suit::Spades,
     };
     assert_golden(
         seed,
@@ -264,17 +265,20 @@ fn hard_vs_normal_disagree_on_seed_1363_east() {
 
 #[test]
 fn hard_vs_normal_disagree_on_seed_1367_east() {
-    // From compare-batch disagreements: East 1367 where Normal=JS vs Hard=7S
+    // From compare-batch disagreements: East 1367 where Normal=JS vs Hard=7S.
+    // Both then discarded 4♦ on the first trick; it is East's only diamond
+    // below 8 with Q♦ behind it, so the exit preservation term keeps it and
+    // both shed 8♦.
     let seed: u64 = 1367;
     let seat = PlayerPosition::East;
     let normal_top = top_for(seed, seat, false);
     let hard_top = top_for(seed, seat, true);
     let normal_expected = Card {
-        rank: Rank::Four,
+        rank: Rank::Eight,
         suit: Suit::Diamonds,
     };
     let hard_expected = Card {
-        rank: Rank::Four,
+        rank: Rank::Eight,
         suit: Suit::Diamonds,
     };
     assert_golden(
@@ -68,10 +68,12 @@ fn exact_flip_seed_2031_east() {
     // After fixes, Hard AI now chooses 9♦ (different but valid choice).
     // This test verifies both produce valid choices and documents the specific choices
     // for regression testing.
+    // Normal used to discard 9♠ here; East's Q♠ is short of guards, so it now
+    // keeps the spade and sheds A♦.
 
     assert_eq!(
         n_top,
-        Card::new(Rank::Nine, Suit::Spades),
+        Card::new(Rank::Ace, Suit::Diamonds),
         "Normal top changed for 2031/East"
     );
     assert_eq!(
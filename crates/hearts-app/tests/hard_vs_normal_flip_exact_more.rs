@@ -102,12 +102,14 @@ fn exact_flip_seed_1145_north() {
 
 #[test]
 fn exact_flip_seed_1080_south() {
-    // From deterministic compare: Normal=J♠, Hard=7♠
+    // From deterministic compare: Normal=J♠, Hard=7♠. In this flow both
+    // then played 5♦, South's only diamond below 8 with Q♦ and A♦ behind
+    // it; the exit preservation term keeps it and both play 8♦.
     assert_exact_flip(
         1080,
         PlayerPosition::South,
-        Card::new(Rank::Five, Suit::Diamonds),
-        Card::new(Rank::Five, Suit::Diamonds),
+        Card::new(Rank::Eight, Suit::Diamonds),
+        Card::new(Rank::Eight, Suit::Diamonds),
     );
 }
 
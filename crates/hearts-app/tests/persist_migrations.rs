@@ -0,0 +1,49 @@
+use hearts_app::determinism::{GOLDEN_VERSION, load_golden};
+use hearts_app::persist::{backup_path, quarantine_path};
+use std::path::PathBuf;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/persist")
+        .join(name)
+}
+
+/// Copies a fixture into its own temp dir, since loading rewrites old files.
+fn scratch_copy(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mdhearts_persist_fixture_{name}"));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::copy(fixture(name), &path).unwrap();
+    path
+}
+
+#[test]
+fn every_historical_golden_version_loads_as_current() {
+    let current = load_golden(&scratch_copy("decision_golden_v2.json")).unwrap();
+    assert_eq!(current.schema_version, GOLDEN_VERSION);
+    for version in 1..GOLDEN_VERSION {
+        let name = format!("decision_golden_v{version}.json");
+        let path = scratch_copy(&name);
+        assert_eq!(load_golden(&path).unwrap(), current, "{name}");
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path, version)).unwrap(),
+            std::fs::read_to_string(fixture(&name)).unwrap()
+        );
+        let rewritten = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            rewritten,
+            std::fs::read_to_string(fixture("decision_golden_v2.json")).unwrap()
+        );
+    }
+}
+
+#[test]
+fn corrupt_golden_is_quarantined() {
+    let path = scratch_copy("decision_golden_v2.json");
+    std::fs::write(&path, "{\"schema_version\": 2, \"mix\": ").unwrap();
+    let err = load_golden(&path).unwrap_err();
+    assert!(err.contains("moved to"), "{err}");
+    assert!(!path.exists());
+    assert!(quarantine_path(&path).exists());
+}
@@ -110,7 +110,7 @@ fn hard_guard_round_leader_saturated_blocks_feed() {
         BotDifficulty::FutureHard,
     );
 
-    let legal = round.legal_cards(seat);
+    let legal = round.legal_plays(seat);
     assert!(legal.iter().any(|c| c.suit == Suit::Hearts));
 
     let _ = PlayPlannerHard::choose(&legal, &ctx);
@@ -222,7 +222,7 @@ fn hard_flat_scores_uses_round_leader_penalties_gt0() {
         &tracker,
         BotDifficulty::FutureHard,
     );
-    let legal = round.legal_cards(seat);
+    let legal = round.legal_plays(seat);
     let choice = PlayPlannerHard::choose(&legal, &ctx).expect("hard choice");
     // Expect to shed lowest heart (feeding leader), typical outcome is 2♥.
     assert_eq!(choice.suit, Suit::Hearts);
@@ -2,7 +2,8 @@ use crate::model::deck::Deck;
 use crate::model::passing::PassingDirection;
 use crate::model::player::PlayerPosition;
 use crate::model::round::{RoundPhase, RoundState};
-use crate::model::score::ScoreBoard;
+use crate::model::rules::{JACK_OF_DIAMONDS, RuleError, RuleSet};
+use crate::model::score::{ScoreBoard, hand_points};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 
@@ -14,9 +15,12 @@ pub struct MatchState {
     current_round: RoundState,
     rng: StdRng,
     seed: u64,
+    rules: RuleSet,
 }
 
 impl MatchState {
+    /// Uses a random seed; needs `std`. Without it, pass a seed to `with_seed`.
+    #[cfg(feature = "std")]
     pub fn new(starting_player: PlayerPosition) -> Self {
         let seed: u64 = rand::random();
         Self::with_seed_round_direction(seed, 1, PassingDirection::CYCLE[0], starting_player)
@@ -26,11 +30,38 @@ impl MatchState {
         Self::with_seed_round_direction(seed, 1, PassingDirection::CYCLE[0], starting_player)
     }
 
+    /// A seeded match played under house `rules` instead of the standard game.
+    pub fn with_rules(starting_player: PlayerPosition, seed: u64, rules: RuleSet) -> Self {
+        Self::with_seed_round_direction_rules(
+            seed,
+            1,
+            PassingDirection::CYCLE[0],
+            starting_player,
+            rules,
+        )
+    }
+
     pub fn with_seed_round_direction(
         seed: u64,
         round_number: u32,
         direction: PassingDirection,
         starting_player: PlayerPosition,
+    ) -> Self {
+        Self::with_seed_round_direction_rules(
+            seed,
+            round_number,
+            direction,
+            starting_player,
+            RuleSet::STANDARD,
+        )
+    }
+
+    pub fn with_seed_round_direction_rules(
+        seed: u64,
+        round_number: u32,
+        direction: PassingDirection,
+        starting_player: PlayerPosition,
+        rules: RuleSet,
     ) -> Self {
         let normalized_round = round_number.max(1);
         let mut rng = StdRng::seed_from_u64(seed);
@@ -45,7 +76,8 @@ impl MatchState {
             .position(|d| *d == direction)
             .unwrap_or(0);
 
-        let current_round = RoundState::deal(&deck, starting_player, direction);
+        let current_round =
+            RoundState::deal_with_pass_count(&deck, starting_player, direction, rules.pass_count);
 
         Self {
             scores: ScoreBoard::new(),
@@ -54,6 +86,7 @@ impl MatchState {
             current_round,
             rng,
             seed,
+            rules,
         }
     }
 
@@ -62,11 +95,12 @@ impl MatchState {
             .passing_direction
             .parse::<PassingDirection>()
             .unwrap_or(PassingDirection::Left);
-        let mut state = MatchState::with_seed_round_direction(
+        let mut state = MatchState::with_seed_round_direction_rules(
             snapshot.seed,
             snapshot.round_number,
             direction,
             snapshot.round_starting_player,
+            snapshot.rules,
         );
         state.scores_mut().set_totals(snapshot.scores);
         state
@@ -76,6 +110,53 @@ impl MatchState {
         self.seed
     }
 
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// The score that ends the match.
+    pub fn target_score(&self) -> u32 {
+        self.rules.target_score
+    }
+
+    /// Changes the target of the match in progress, e.g. before its first
+    /// round from a setup screen.
+    pub fn set_target_score(&mut self, target: u32) -> Result<(), RuleError> {
+        self.rules = self.rules.with_target_score(target)?;
+        Ok(())
+    }
+
+    /// Turns the Omnibus J♦ bonus on or off for the rounds still to be
+    /// scored.
+    pub fn set_jack_of_diamonds(&mut self, enabled: bool) {
+        self.rules = self.rules.with_jack_of_diamonds(enabled);
+    }
+
+    /// A seat has reached the target score and one seat alone has the
+    /// lowest total. A tie for the lowest plays on, hand by hand, until it
+    /// breaks.
+    pub fn is_match_over(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    /// The seat with the unique lowest total once a seat has reached the
+    /// target; `None` while the match goes on.
+    pub fn winner(&self) -> Option<PlayerPosition> {
+        let standings = self.scores.standings();
+        let target = self.rules.target_score;
+        if !standings.iter().any(|&score| score >= target) {
+            return None;
+        }
+        let lowest = standings.iter().copied().min()?;
+        let mut leaders = PlayerPosition::LOOP
+            .into_iter()
+            .filter(|seat| standings[seat.index()] == lowest);
+        match (leaders.next(), leaders.next()) {
+            (Some(winner), None) => Some(winner),
+            _ => None,
+        }
+    }
+
     pub fn scores(&self) -> &ScoreBoard {
         &self.scores
     }
@@ -104,16 +185,28 @@ impl MatchState {
         self.passing_index
     }
 
+    /// Penalty cards each seat took this round, before any moon shot or
+    /// bonus is scored.
     pub fn round_penalties(&self) -> [u8; 4] {
         self.current_round.penalty_totals()
     }
 
+    /// What each seat scores for the round under the match's rules.
+    pub fn round_points(&self) -> [i32; 4] {
+        hand_points(
+            self.current_round.penalty_totals(),
+            self.current_round.captured_by(JACK_OF_DIAMONDS),
+            self.current_round.sun_shooter(),
+            &self.rules,
+        )
+    }
+
     pub fn finish_round_and_start_next(&mut self) -> Option<PlayerPosition> {
-        let penalties = self.current_round.penalty_totals();
-        self.scores.apply_hand(penalties);
+        let points = self.round_points();
+        self.scores.apply_points(points);
 
-        if self.scores.standings().iter().any(|&score| score >= 100) {
-            return Some(self.scores.leading_player());
+        if let Some(winner) = self.winner() {
+            return Some(winner);
         }
 
         self.round_number += 1;
@@ -123,7 +216,12 @@ impl MatchState {
         let next_starting_player = self.current_round.starting_player().next();
 
         let deck = Deck::shuffled(&mut self.rng);
-        self.current_round = RoundState::deal(&deck, next_starting_player, next_passing);
+        self.current_round = RoundState::deal_with_pass_count(
+            &deck,
+            next_starting_player,
+            next_passing,
+            self.rules.pass_count,
+        );
         None
     }
 
@@ -136,8 +234,7 @@ impl MatchState {
     }
 
     pub fn is_round_ready_for_scoring(&self) -> bool {
-        matches!(self.current_round.phase(), RoundPhase::Playing)
-            && self.current_round.tricks_completed() == 13
+        matches!(self.current_round.phase(), RoundPhase::Complete)
     }
 
     const fn passing_sequence() -> &'static [PassingDirection; 4] {
@@ -152,6 +249,7 @@ mod tests {
     use crate::model::passing::PassingDirection;
     use crate::model::player::PlayerPosition;
     use crate::model::rank::Rank;
+    use crate::model::rules::{RuleError, RuleSet};
     use crate::model::suit::Suit;
 
     #[test]
@@ -217,4 +315,116 @@ mod tests {
         // When a match ends we keep the existing round state so players can review it.
         assert_eq!(match_state.round_number(), 1);
     }
+
+    /// Plays the match's round out with each seat's first legal card.
+    fn play_out(match_state: &mut MatchState) {
+        let round = match_state.round_mut();
+        while round.tricks_completed() < 13 {
+            let seat = crate::testkit::to_play(round);
+            let card = round.legal_plays(seat)[0];
+            round.play_card(seat, card).unwrap();
+        }
+    }
+
+    #[test]
+    fn the_jack_of_diamonds_taker_scores_ten_fewer_under_omnibus() {
+        use crate::model::rules::JACK_OF_DIAMONDS;
+        let start = |rules| {
+            MatchState::with_seed_round_direction_rules(
+                7,
+                4,
+                PassingDirection::Hold,
+                PlayerPosition::North,
+                rules,
+            )
+        };
+        let mut standard = start(RuleSet::STANDARD);
+        let mut omnibus = start(RuleSet::STANDARD.with_jack_of_diamonds(true));
+        play_out(&mut standard);
+        play_out(&mut omnibus);
+        let taker = omnibus.round().captured_by(JACK_OF_DIAMONDS).unwrap();
+        assert_eq!(standard.round_penalties(), omnibus.round_penalties());
+        assert_eq!(
+            omnibus.round_points()[taker.index()],
+            standard.round_points()[taker.index()] - 10
+        );
+        standard.scores_mut().set_totals([40; 4]);
+        omnibus.scores_mut().set_totals([40; 4]);
+        standard.finish_round_and_start_next();
+        omnibus.finish_round_and_start_next();
+        for seat in PlayerPosition::LOOP {
+            let offset = if seat == taker { 10 } else { 0 };
+            assert_eq!(
+                omnibus.scores().score(seat) + offset,
+                standard.scores().score(seat)
+            );
+        }
+    }
+
+    #[test]
+    fn a_short_game_ends_at_its_target() {
+        let rules = RuleSet::STANDARD.with_target_score(50).unwrap();
+        let mut match_state = MatchState::with_rules(PlayerPosition::North, 0, rules);
+        match_state.scores_mut().set_totals([20, 49, 30, 10]);
+        assert!(!match_state.is_match_over());
+        assert_eq!(match_state.finish_round_and_start_next(), None);
+        match_state.scores_mut().set_totals([20, 50, 30, 10]);
+        assert!(match_state.is_match_over());
+        assert_eq!(
+            match_state.set_target_score(5),
+            Err(RuleError::TargetScore(5))
+        );
+        match_state.set_target_score(75).unwrap();
+        assert!(!match_state.is_match_over());
+        assert_eq!(match_state.rules().target_score, 75);
+    }
+
+    /// Plays whole rounds, passing each seat's first cards, until the match
+    /// ends; returns the number of rounds and the winner.
+    fn play_match(rules: RuleSet) -> (u32, PlayerPosition) {
+        let mut match_state = MatchState::with_rules(PlayerPosition::North, 2252, rules);
+        loop {
+            let round = match_state.round_mut();
+            if round.passing_direction().requires_selection() {
+                for seat in PlayerPosition::LOOP {
+                    let hand = round.hand(seat).cards();
+                    let pass = [hand[0], hand[1], hand[2]];
+                    round.submit_pass(seat, pass).unwrap();
+                }
+                round.resolve_passes().unwrap();
+            }
+            play_out(&mut match_state);
+            if let Some(winner) = match_state.finish_round_and_start_next() {
+                return (match_state.round_number(), winner);
+            }
+        }
+    }
+
+    #[test]
+    fn a_fifty_point_match_ends_early() {
+        let short = RuleSet::STANDARD.with_target_score(50).unwrap();
+        let (short_rounds, _) = play_match(short);
+        let (full_rounds, _) = play_match(RuleSet::STANDARD);
+        assert!(
+            short_rounds < full_rounds,
+            "{short_rounds} vs {full_rounds}"
+        );
+    }
+
+    #[test]
+    fn a_tie_for_lowest_plays_another_hand() {
+        let mut match_state = MatchState::with_seed(PlayerPosition::North, 0);
+        match_state.scores_mut().set_totals([60, 104, 60, 90]);
+        assert!(!match_state.is_match_over());
+        assert_eq!(match_state.winner(), None);
+        assert_eq!(match_state.finish_round_and_start_next(), None);
+        assert_eq!(match_state.round_number(), 2);
+        // The extra hand breaks the tie.
+        match_state.scores_mut().set_totals([60, 104, 63, 90]);
+        assert!(match_state.is_match_over());
+        assert_eq!(match_state.winner(), Some(PlayerPosition::North));
+        // A tie above the lowest total does not hold the match up.
+        match_state.scores_mut().set_totals([40, 104, 60, 60]);
+        assert_eq!(match_state.winner(), Some(PlayerPosition::North));
+    }
 }
@@ -1,12 +1,15 @@
 use super::match_state::MatchState;
 use crate::model::card::Card;
 use crate::model::hand::Hand;
-use crate::model::passing::{PassingDirection, PassingState};
+use crate::model::passing::{DEFAULT_PASS_COUNT, PassCards, PassingDirection, PassingState};
 use crate::model::player::PlayerPosition;
-use crate::model::rank::Rank;
 use crate::model::round::{RoundPhase, RoundState};
-use crate::model::suit::Suit;
+use crate::model::rules::RuleSet;
 use crate::model::trick::Trick;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -20,6 +23,12 @@ pub struct MatchSnapshot {
     pub round: Option<RoundSnapshot>,
     #[serde(default)]
     pub passing_index: Option<usize>,
+    #[serde(default)]
+    pub rules: RuleSet,
+    /// What the last hand did to `scores`; see
+    /// [`crate::model::score::ScoreBoard::last_round`].
+    #[serde(default)]
+    pub last_round: Option<[i32; 4]>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -35,9 +44,12 @@ pub struct RoundSnapshot {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum RoundPhaseSnapshot {
     Passing {
-        submissions: Box<[Option<[String; 3]>; 4]>,
+        submissions: Box<[Option<Vec<String>>; 4]>,
+        #[serde(default = "default_pass_count")]
+        pass_count: usize,
     },
     Playing,
+    Complete,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -62,6 +74,8 @@ impl MatchSnapshot {
             round_starting_player: state.round().starting_player(),
             round: None,
             passing_index: None,
+            rules: *state.rules(),
+            last_round: state.scores().last_round(),
         }
     }
 
@@ -74,6 +88,8 @@ impl MatchSnapshot {
             round_starting_player: state.round().starting_player(),
             round: Some(RoundSnapshot::capture(state.round())),
             passing_index: Some(state.passing_index()),
+            rules: *state.rules(),
+            last_round: state.scores().last_round(),
         }
     }
 
@@ -82,13 +98,15 @@ impl MatchSnapshot {
             .passing_direction
             .parse::<PassingDirection>()
             .unwrap_or(PassingDirection::Left);
-        let mut state = MatchState::with_seed_round_direction(
+        let mut state = MatchState::with_seed_round_direction_rules(
             self.seed,
             self.round_number,
             direction,
             self.round_starting_player,
+            self.rules,
         );
         state.scores_mut().set_totals(self.scores);
+        state.scores_mut().set_last_round(self.last_round);
         state
     }
 
@@ -97,13 +115,15 @@ impl MatchSnapshot {
             .passing_direction
             .parse::<PassingDirection>()
             .unwrap_or(PassingDirection::Left);
-        let mut state = MatchState::with_seed_round_direction(
+        let mut state = MatchState::with_seed_round_direction_rules(
             self.seed,
             self.round_number,
             direction,
             self.round_starting_player,
+            self.rules,
         );
         state.scores_mut().set_totals(self.scores);
+        state.scores_mut().set_last_round(self.last_round);
 
         if let Some(round_snapshot) = self.round.clone() {
             let restored_round = round_snapshot
@@ -141,7 +161,7 @@ impl MatchSnapshot {
 
 impl RoundSnapshot {
     pub fn capture(round: &RoundState) -> Self {
-        let mut hands: [Vec<String>; 4] = std::array::from_fn(|_| Vec::new());
+        let mut hands: [Vec<String>; 4] = core::array::from_fn(|_| Vec::new());
         for seat in PlayerPosition::LOOP.iter().copied() {
             let mut cards: Vec<Card> = round.hand(seat).cards().to_vec();
             sort_cards(&mut cards);
@@ -157,15 +177,18 @@ impl RoundSnapshot {
 
         let phase = match round.phase() {
             RoundPhase::Playing => RoundPhaseSnapshot::Playing,
+            RoundPhase::Complete => RoundPhaseSnapshot::Complete,
             RoundPhase::Passing(state) => {
-                let mut submissions: [Option<[String; 3]>; 4] = std::array::from_fn(|_| None);
+                let mut submissions: [Option<Vec<String>>; 4] = core::array::from_fn(|_| None);
                 for seat in PlayerPosition::LOOP.iter().copied() {
                     if let Some(cards) = state.submissions()[seat.index()] {
-                        submissions[seat.index()] = Some(cards.map(card_to_string));
+                        submissions[seat.index()] =
+                            Some(cards.into_iter().map(card_to_string).collect());
                     }
                 }
                 RoundPhaseSnapshot::Passing {
                     submissions: Box::new(submissions),
+                    pass_count: state.count(),
                 }
             }
         };
@@ -181,7 +204,7 @@ impl RoundSnapshot {
     }
 
     pub fn restore(self, passing_direction: PassingDirection) -> Result<RoundState, String> {
-        let hands_cards: [Vec<Card>; 4] = std::array::from_fn(|idx| {
+        let hands_cards: [Vec<Card>; 4] = core::array::from_fn(|idx| {
             self.hands[idx]
                 .iter()
                 .map(|s| parse_card(s))
@@ -189,7 +212,7 @@ impl RoundSnapshot {
                 .unwrap_or_default()
         });
 
-        let hands: [Hand; 4] = std::array::from_fn(|idx| {
+        let hands: [Hand; 4] = core::array::from_fn(|idx| {
             let mut cards = hands_cards[idx].clone();
             sort_cards(&mut cards);
             Hand::with_cards(cards)
@@ -208,11 +231,26 @@ impl RoundSnapshot {
 
         let phase = match self.phase {
             RoundPhaseSnapshot::Playing => RoundPhase::Playing,
-            RoundPhaseSnapshot::Passing { submissions } => {
-                let state = PassingState::with_submissions(
-                    passing_direction,
-                    (*submissions).map(|opt| opt.map(|arr| arr.map(|s| parse_card(&s).unwrap()))),
-                );
+            RoundPhaseSnapshot::Complete => RoundPhase::Complete,
+            RoundPhaseSnapshot::Passing {
+                submissions,
+                pass_count,
+            } => {
+                let mut parsed: [Option<PassCards>; 4] = [None; 4];
+                for (slot, codes) in parsed.iter_mut().zip(submissions.iter()) {
+                    if let Some(codes) = codes {
+                        let cards = codes
+                            .iter()
+                            .map(|code| parse_card(code))
+                            .collect::<Option<Vec<_>>>()
+                            .ok_or_else(|| "invalid passed card".to_string())?;
+                        *slot = Some(
+                            PassCards::from_slice(&cards)
+                                .ok_or_else(|| format!("{} passed cards", cards.len()))?,
+                        );
+                    }
+                }
+                let state = PassingState::with_submissions(passing_direction, pass_count, parsed);
                 // ensure submissions with None stay None; already set via map
                 RoundPhase::Passing(state.clone())
             }
@@ -259,39 +297,18 @@ impl TrickSnapshot {
     }
 }
 
+const fn default_pass_count() -> usize {
+    DEFAULT_PASS_COUNT
+}
+
 fn card_to_string(card: Card) -> String {
     card.to_string()
 }
 
-fn parse_card(code: &str) -> Option<Card> {
-    if code.len() < 2 {
-        return None;
-    }
-    let (rank_str, suit_char) = code.split_at(code.len() - 1);
-    let suit = match suit_char.chars().next()? {
-        'C' | 'c' => Suit::Clubs,
-        'D' | 'd' => Suit::Diamonds,
-        'S' | 's' => Suit::Spades,
-        'H' | 'h' => Suit::Hearts,
-        _ => return None,
-    };
-    let rank = match rank_str {
-        "A" | "a" => Rank::Ace,
-        "K" | "k" => Rank::King,
-        "Q" | "q" => Rank::Queen,
-        "J" | "j" => Rank::Jack,
-        "10" => Rank::Ten,
-        "9" | "09" => Rank::Nine,
-        "8" | "08" => Rank::Eight,
-        "7" | "07" => Rank::Seven,
-        "6" | "06" => Rank::Six,
-        "5" | "05" => Rank::Five,
-        "4" | "04" => Rank::Four,
-        "3" | "03" => Rank::Three,
-        "2" | "02" => Rank::Two,
-        _ => return None,
-    };
-    Some(Card::new(rank, suit))
+/// Reads a card code such as `QS`, `10h` or `2C`, as written by snapshots;
+/// see [`Card`]'s `FromStr` for the accepted forms.
+pub fn parse_card(code: &str) -> Option<Card> {
+    code.parse().ok()
 }
 
 fn sort_cards(cards: &mut [Card]) {
@@ -304,10 +321,11 @@ mod tests {
     use crate::game::match_state::MatchState;
     use crate::model::card::Card;
     use crate::model::hand::Hand;
-    use crate::model::passing::{PassingDirection, PassingState};
+    use crate::model::passing::{PassCards, PassingDirection, PassingState};
     use crate::model::player::PlayerPosition;
     use crate::model::rank::Rank;
     use crate::model::round::{RoundPhase, RoundState};
+    use crate::model::rules::RuleSet;
     use crate::model::suit::Suit;
     use crate::model::trick::Trick;
 
@@ -349,6 +367,19 @@ mod tests {
         assert_eq!(snapshot.round_number, 2);
         assert_eq!(snapshot.scores, [0, 1, 2, 3]);
         assert_eq!(snapshot.passing_direction, "Left");
+        assert_eq!(snapshot.rules, RuleSet::STANDARD);
+        assert_eq!(snapshot.last_round, None);
+    }
+
+    #[test]
+    fn the_last_hand_survives_a_snapshot() {
+        let mut state = MatchState::with_seed(PlayerPosition::North, 5);
+        state.scores_mut().set_totals([4, 20, 0, 9]);
+        state.scores_mut().apply_points([-9, 6, 0, 20]);
+        let json = MatchSnapshot::to_json(&state).unwrap();
+        let restored = MatchSnapshot::from_json(&json).unwrap().restore();
+        assert_eq!(restored.scores().standings(), &[0, 26, 0, 29]);
+        assert_eq!(restored.scores().last_round(), Some([-4, 6, 0, 20]));
     }
 
     #[test]
@@ -406,6 +437,29 @@ mod tests {
         assert_eq!(restored_round.starting_player(), PlayerPosition::North);
     }
 
+    #[test]
+    fn four_card_pass_rules_survive_a_snapshot() {
+        let rules = RuleSet::STANDARD.with_pass_count(4).unwrap();
+        let mut state = MatchState::with_rules(PlayerPosition::North, 11, rules);
+        let cards = state.round().hand(PlayerPosition::East).cards()[..4].to_vec();
+        state
+            .round_mut()
+            .submit_pass(PlayerPosition::East, PassCards::from_slice(&cards).unwrap())
+            .unwrap();
+
+        let json = MatchSnapshot::to_json_full(&state).unwrap();
+        let restored = MatchSnapshot::from_json(&json).unwrap().restore_full();
+        assert_eq!(restored.rules(), &rules);
+        let RoundPhase::Passing(passing) = restored.round().phase() else {
+            panic!("still passing");
+        };
+        assert_eq!(passing.count(), 4);
+        assert_eq!(
+            passing.submissions()[PlayerPosition::East.index()].map(|c| c.len()),
+            Some(4)
+        );
+    }
+
     #[test]
     fn trick_snapshot_roundtrip() {
         let mut trick = Trick::new(PlayerPosition::North);
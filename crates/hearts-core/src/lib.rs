@@ -1,6 +1,15 @@
 #![deny(warnings)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("hearts-core needs at least the `alloc` feature");
+
+extern crate alloc;
+
 pub mod game;
 pub mod model;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
 
 pub struct AppInfo;
 
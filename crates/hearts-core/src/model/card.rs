@@ -1,6 +1,8 @@
-﻿use crate::model::rank::Rank;
+use crate::model::rank::Rank;
 use crate::model::suit::Suit;
+use alloc::string::{String, ToString};
 use core::fmt;
+use core::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Card {
@@ -21,6 +23,10 @@ impl Card {
         matches!(self.rank, Rank::Queen) && matches!(self.suit, Suit::Spades)
     }
 
+    pub const fn is_jack_of_diamonds(self) -> bool {
+        matches!(self.rank, Rank::Jack) && matches!(self.suit, Suit::Diamonds)
+    }
+
     pub fn penalty_value(self) -> u8 {
         if self.is_queen_of_spades() {
             13
@@ -32,15 +38,140 @@ impl Card {
     }
 }
 
+/// Writes the card code: rank then suit letter, e.g. `QS`, `10H`, `2C`.
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}{}", self.rank, self.suit)
     }
 }
 
+/// Writes card codes separated by spaces, in the order given; `Hand`'s
+/// `Display` for cards that are not a hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardList<'a>(pub &'a [Card]);
+
+impl fmt::Display for CardList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, card) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{card}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a card code did not parse; each variant holds the code as given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardParseError {
+    /// Too short to hold a rank and a suit.
+    Malformed(String),
+    UnknownRank(String),
+    UnknownSuit(String),
+}
+
+impl fmt::Display for CardParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardParseError::Malformed(code) => write!(f, "'{code}' is not a card code"),
+            CardParseError::UnknownRank(code) => write!(f, "'{code}' has an unknown rank"),
+            CardParseError::UnknownSuit(code) => write!(f, "'{code}' has an unknown suit"),
+        }
+    }
+}
+
+impl core::error::Error for CardParseError {}
+
+/// Reads a card code in any case. Tens are `10` or `T`; a leading zero
+/// (`09S`) is accepted for ranks below ten.
+impl FromStr for Card {
+    type Err = CardParseError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let suit_char = code
+            .chars()
+            .last()
+            .filter(|_| code.chars().count() >= 2)
+            .ok_or_else(|| CardParseError::Malformed(code.to_string()))?;
+        let rank_str = &code[..code.len() - suit_char.len_utf8()];
+        let suit = match suit_char.to_ascii_uppercase() {
+            'C' => Suit::Clubs,
+            'D' => Suit::Diamonds,
+            'S' => Suit::Spades,
+            'H' => Suit::Hearts,
+            _ => return Err(CardParseError::UnknownSuit(code.to_string())),
+        };
+        let digits = rank_str
+            .strip_prefix('0')
+            .filter(|rest| rest.len() == 1 && rest.as_bytes()[0].is_ascii_digit());
+        let rank = match digits.unwrap_or(rank_str).to_ascii_uppercase().as_str() {
+            "A" => Rank::Ace,
+            "K" => Rank::King,
+            "Q" => Rank::Queen,
+            "J" => Rank::Jack,
+            "10" | "T" => Rank::Ten,
+            "9" => Rank::Nine,
+            "8" => Rank::Eight,
+            "7" => Rank::Seven,
+            "6" => Rank::Six,
+            "5" => Rank::Five,
+            "4" => Rank::Four,
+            "3" => Rank::Three,
+            "2" => Rank::Two,
+            _ => return Err(CardParseError::UnknownRank(code.to_string())),
+        };
+        Ok(Card::new(rank, suit))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Card, Rank, Suit};
+    use super::{Card, CardList, CardParseError, Rank, Suit};
+    use alloc::string::ToString;
+
+    #[test]
+    fn codes_round_trip_in_any_case() {
+        for code in ["QS", "10H", "2C", "AD"] {
+            let card: Card = code.parse().unwrap();
+            assert_eq!(card.to_string(), code);
+            assert_eq!(code.to_ascii_lowercase().parse::<Card>(), Ok(card));
+        }
+        assert_eq!("th".parse(), Ok(Card::new(Rank::Ten, Suit::Hearts)));
+        assert_eq!("09s".parse(), Ok(Card::new(Rank::Nine, Suit::Spades)));
+    }
+
+    #[test]
+    fn malformed_codes_say_what_is_wrong() {
+        assert!(matches!(
+            "Q".parse::<Card>(),
+            Err(CardParseError::Malformed(_))
+        ));
+        assert!(matches!(
+            "".parse::<Card>(),
+            Err(CardParseError::Malformed(_))
+        ));
+        assert_eq!(
+            "QX".parse::<Card>(),
+            Err(CardParseError::UnknownSuit("QX".to_string()))
+        );
+        assert_eq!(
+            "1S".parse::<Card>(),
+            Err(CardParseError::UnknownRank("1S".to_string()))
+        );
+        let err = "010S".parse::<Card>().unwrap_err();
+        assert_eq!(err.to_string(), "'010S' has an unknown rank");
+    }
+
+    #[test]
+    fn a_card_list_keeps_its_order() {
+        let cards = [
+            Card::new(Rank::Ten, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Clubs),
+        ];
+        assert_eq!(CardList(&cards).to_string(), "10H 2C");
+        assert_eq!(CardList(&[]).to_string(), "");
+    }
 
     #[test]
     fn queen_of_spades_identified() {
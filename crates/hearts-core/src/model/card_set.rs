@@ -0,0 +1,277 @@
+use crate::model::card::Card;
+use crate::model::hand::Hand;
+use crate::model::rank::Rank;
+use crate::model::suit::Suit;
+use core::fmt;
+use core::ops::{BitAnd, BitOr, Not, Sub};
+
+const SUIT_MASK: u64 = (1 << 13) - 1;
+
+/// A set of cards as one bit per card, thirteen bits per suit in
+/// [`Suit::ALL`] order. Iteration runs by suit, then rank, which is the
+/// order a [`Hand`] keeps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub const EMPTY: CardSet = CardSet(0);
+    pub const DECK: CardSet = CardSet((1 << 52) - 1);
+
+    pub const fn new() -> Self {
+        Self::EMPTY
+    }
+
+    /// The bit `card` occupies.
+    pub const fn bit(card: Card) -> u64 {
+        1 << (card.suit.index() * 13 + card.rank.index())
+    }
+
+    /// Every card of `suit`.
+    pub const fn of_suit(suit: Suit) -> Self {
+        Self(SUIT_MASK << (suit.index() * 13))
+    }
+
+    /// Adds `card`, returning whether it was missing.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let missing = !self.contains(card);
+        self.0 |= Self::bit(card);
+        missing
+    }
+
+    /// Removes `card`, returning whether it was there.
+    pub fn remove(&mut self, card: Card) -> bool {
+        let present = self.contains(card);
+        self.0 &= !Self::bit(card);
+        present
+    }
+
+    pub const fn contains(self, card: Card) -> bool {
+        self.0 & Self::bit(card) != 0
+    }
+
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The cards of `suit` in the set.
+    pub const fn suit(self, suit: Suit) -> Self {
+        Self(self.0 & Self::of_suit(suit).0)
+    }
+
+    pub const fn count_in_suit(self, suit: Suit) -> usize {
+        self.suit(suit).len()
+    }
+
+    pub fn iter(self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+}
+
+/// Cards of a [`CardSet`], lowest bit first.
+#[derive(Debug, Clone)]
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let idx = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        let suit = Suit::from_index(idx / 13)?;
+        let rank = Rank::from_index(idx % 13)?;
+        Some(Card::new(rank, suit))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> Self {
+        let mut set = Self::EMPTY;
+        set.extend(cards);
+        set
+    }
+}
+
+impl<'a> FromIterator<&'a Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = &'a Card>>(cards: I) -> Self {
+        cards.into_iter().copied().collect()
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, cards: I) {
+        for card in cards {
+            self.0 |= Self::bit(card);
+        }
+    }
+}
+
+impl From<&Hand> for CardSet {
+    fn from(hand: &Hand) -> Self {
+        hand.iter().collect()
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+}
+
+/// The cards of `self` that are not in `other`.
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+}
+
+/// The rest of the deck.
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> CardSet {
+        CardSet(!self.0 & Self::DECK.0)
+    }
+}
+
+/// Writes the card codes in set order, separated by spaces.
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, card) in self.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{card}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CardSet;
+    use crate::model::card::Card;
+    use crate::model::deck::Deck;
+    use crate::model::hand::Hand;
+    use crate::model::suit::Suit;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    fn deck() -> Vec<Card> {
+        Deck::standard().cards().to_vec()
+    }
+
+    fn hand_order(cards: &[Card]) -> Vec<Card> {
+        let mut sorted = cards.to_vec();
+        sorted.sort_by(|a, b| a.suit.cmp(&b.suit).then(a.rank.cmp(&b.rank)));
+        sorted.dedup();
+        sorted
+    }
+
+    #[test]
+    fn random_card_lists_round_trip_through_a_set() {
+        let mut rng = StdRng::seed_from_u64(2263);
+        let mut deck = deck();
+        for _ in 0..500 {
+            deck.shuffle(&mut rng);
+            let len = rng.gen_range(0..=52);
+            // Draw with repeats so duplicates collapse as in a set.
+            let cards: Vec<Card> = (0..len).map(|_| deck[rng.gen_range(0..52)]).collect();
+            let set: CardSet = cards.iter().collect();
+            let expected = hand_order(&cards);
+            assert_eq!(set.iter().collect::<Vec<_>>(), expected);
+            assert_eq!(set.len(), expected.len());
+            assert_eq!(set.iter().len(), expected.len());
+            for &card in &deck {
+                assert_eq!(set.contains(card), cards.contains(&card), "{card}");
+            }
+            for suit in Suit::ALL {
+                let in_suit: Vec<Card> = expected
+                    .iter()
+                    .copied()
+                    .filter(|c| c.suit == suit)
+                    .collect();
+                assert_eq!(set.suit(suit).iter().collect::<Vec<_>>(), in_suit);
+                assert_eq!(set.count_in_suit(suit), in_suit.len());
+            }
+            let hand = Hand::with_cards(expected.clone());
+            assert_eq!(CardSet::from(&hand), set);
+            assert_eq!(set.to_string(), hand.to_string());
+        }
+    }
+
+    #[test]
+    fn random_inserts_and_removes_match_a_vec() {
+        let mut rng = StdRng::seed_from_u64(52);
+        let deck = deck();
+        let mut set = CardSet::new();
+        let mut cards: Vec<Card> = Vec::new();
+        for _ in 0..2000 {
+            let card = deck[rng.gen_range(0..52)];
+            if rng.gen_bool(0.5) {
+                assert_eq!(set.insert(card), !cards.contains(&card));
+                if !cards.contains(&card) {
+                    cards.push(card);
+                }
+            } else {
+                assert_eq!(set.remove(card), cards.contains(&card));
+                cards.retain(|&c| c != card);
+            }
+            assert_eq!(set.iter().collect::<Vec<_>>(), hand_order(&cards));
+        }
+    }
+
+    #[test]
+    fn set_operations_partition_the_deck() {
+        let hand = Hand::parse("2C AC QS 10H AH").unwrap();
+        let set = CardSet::from(&hand);
+        let rest = !set;
+        assert_eq!(rest.len(), 47);
+        assert_eq!(set | rest, CardSet::DECK);
+        assert!((set & rest).is_empty());
+        assert_eq!(CardSet::DECK - rest, set);
+        assert_eq!(set.suit(Suit::Hearts).to_string(), "10H AH");
+        assert_eq!(CardSet::of_suit(Suit::Spades).len(), 13);
+        assert_eq!(
+            CardSet::DECK.iter().collect::<Vec<_>>(),
+            hand_order(&deck())
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! A whole deal written out as card codes, for pinning exact hands instead
+//! of a seed.
+//!
+//! The text form is the four hands in N,E,S,W order separated by `/`, each
+//! hand a list of card codes as [`Hand::parse`] reads them:
+//! `"2C 5C ... / 3C KD ... / ... / ..."`. A deal must partition the deck:
+//! every card exactly once and 13 cards to each seat.
+
+use crate::model::card::{Card, CardParseError};
+use crate::model::deck::Deck;
+use crate::model::hand::Hand;
+use crate::model::player::PlayerPosition;
+use crate::model::round::RoundState;
+use alloc::vec::Vec;
+use core::array;
+use core::fmt;
+use core::str::FromStr;
+
+pub const CARDS_PER_HAND: usize = 13;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deal {
+    hands: [Hand; 4],
+}
+
+/// Why a deal is not a partition of the deck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DealError {
+    /// The text did not split into four hands.
+    HandCount(usize),
+    Card {
+        seat: PlayerPosition,
+        error: CardParseError,
+    },
+    /// `card` is given to `first` and again to `second`, which may be the
+    /// same seat.
+    Duplicate {
+        card: Card,
+        first: PlayerPosition,
+        second: PlayerPosition,
+    },
+    /// No seat holds these cards.
+    Missing(Vec<Card>),
+    CardCount {
+        seat: PlayerPosition,
+        count: usize,
+    },
+}
+
+impl fmt::Display for DealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DealError::HandCount(found) => {
+                write!(f, "expected 4 hands separated by '/', found {found}")
+            }
+            DealError::Card { seat, error } => write!(f, "{seat}: {error}"),
+            DealError::Duplicate {
+                card,
+                first,
+                second,
+            } if first == second => write!(f, "{card} is dealt to {first} twice"),
+            DealError::Duplicate {
+                card,
+                first,
+                second,
+            } => write!(f, "{card} is dealt to both {first} and {second}"),
+            DealError::Missing(cards) => {
+                f.write_str("no seat holds")?;
+                for card in cards {
+                    write!(f, " {card}")?;
+                }
+                Ok(())
+            }
+            DealError::CardCount { seat, count } => {
+                write!(f, "{seat} holds {count} cards, expected {CARDS_PER_HAND}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DealError {}
+
+fn deck_index(card: Card) -> usize {
+    card.suit as usize * 13 + card.rank.index()
+}
+
+impl Deal {
+    /// Checks that `hands` partition the deck.
+    pub fn from_hands(hands: [Hand; 4]) -> Result<Self, DealError> {
+        let mut owner: [Option<PlayerPosition>; 52] = [None; 52];
+        for seat in PlayerPosition::LOOP {
+            for &card in hands[seat.index()].iter() {
+                let slot = &mut owner[deck_index(card)];
+                if let Some(first) = *slot {
+                    return Err(DealError::Duplicate {
+                        card,
+                        first,
+                        second: seat,
+                    });
+                }
+                *slot = Some(seat);
+            }
+        }
+        let missing: Vec<Card> = Deck::standard()
+            .cards()
+            .iter()
+            .copied()
+            .filter(|&card| owner[deck_index(card)].is_none())
+            .collect();
+        if !missing.is_empty() {
+            return Err(DealError::Missing(missing));
+        }
+        for seat in PlayerPosition::LOOP {
+            let count = hands[seat.index()].len();
+            if count != CARDS_PER_HAND {
+                return Err(DealError::CardCount { seat, count });
+            }
+        }
+        Ok(Self { hands })
+    }
+
+    /// Reads the text form described in the module docs.
+    pub fn parse(text: &str) -> Result<Self, DealError> {
+        let parts: Vec<&str> = text.split('/').collect();
+        if parts.len() != 4 {
+            return Err(DealError::HandCount(parts.len()));
+        }
+        let mut hands: [Vec<Card>; 4] = array::from_fn(|_| Vec::new());
+        for (seat, part) in PlayerPosition::LOOP.into_iter().zip(parts) {
+            for code in part
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|code| !code.is_empty())
+            {
+                let card = code
+                    .parse()
+                    .map_err(|error| DealError::Card { seat, error })?;
+                hands[seat.index()].push(card);
+            }
+        }
+        // Hand::with_cards keeps repeats, so from_hands still reports them.
+        Self::from_hands(hands.map(Hand::with_cards))
+    }
+
+    /// The hands of `round` as they stand; fails once cards have been played.
+    pub fn of_round(round: &RoundState) -> Result<Self, DealError> {
+        Self::from_hands(PlayerPosition::LOOP.map(|seat| round.hand(seat).clone()))
+    }
+
+    pub fn hand(&self, seat: PlayerPosition) -> &Hand {
+        &self.hands[seat.index()]
+    }
+
+    pub fn hands(&self) -> &[Hand; 4] {
+        &self.hands
+    }
+}
+
+impl FromStr for Deal {
+    type Err = DealError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+/// Writes the text form, each hand in hand order.
+impl fmt::Display for Deal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, hand) in self.hands.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" / ")?;
+            }
+            write!(f, "{hand}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Deal, DealError};
+    use crate::model::card::CardParseError;
+    use crate::model::deck::Deck;
+    use crate::model::passing::PassingDirection;
+    use crate::model::player::PlayerPosition;
+    use crate::model::round::{RoundPhase, RoundState};
+    use crate::testkit::card;
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    fn seeded(seed: u64) -> RoundState {
+        RoundState::deal(
+            &Deck::shuffled_with_seed(seed),
+            PlayerPosition::North,
+            PassingDirection::Left,
+        )
+    }
+
+    #[test]
+    fn deals_round_trip_through_text_and_rounds() {
+        let round = seeded(2257);
+        let deal = Deal::of_round(&round).unwrap();
+        let text = deal.to_string();
+        assert_eq!(text.matches(" / ").count(), 3);
+        assert_eq!(text.parse::<Deal>().unwrap(), deal);
+        assert_eq!(text.to_ascii_lowercase().parse::<Deal>().unwrap(), deal);
+
+        let pinned = RoundState::from_deal_string(&text, PassingDirection::Left).unwrap();
+        for seat in PlayerPosition::LOOP {
+            assert_eq!(pinned.hand(seat).cards(), round.hand(seat).cards());
+        }
+        assert_eq!(pinned.starting_player(), round.starting_player());
+        assert!(matches!(pinned.phase(), RoundPhase::Passing(_)));
+        let held = RoundState::from_deal(&deal, PassingDirection::Hold, 3);
+        assert!(matches!(held.phase(), RoundPhase::Playing));
+    }
+
+    #[test]
+    fn errors_name_the_seat_and_card() {
+        let text = Deal::of_round(&seeded(7)).unwrap().to_string();
+        let hands: vec::Vec<&str> = text.split(" / ").collect();
+        let north_first = hands[0].split(' ').next().unwrap();
+        let east_first = hands[1].split(' ').next().unwrap();
+
+        // East is given North's first card in place of its own.
+        let swapped = [
+            hands[0].to_string(),
+            hands[1].replacen(east_first, north_first, 1),
+            hands[2].to_string(),
+            hands[3].to_string(),
+        ]
+        .join(" / ");
+        assert_eq!(
+            Deal::parse(&swapped),
+            Err(DealError::Duplicate {
+                card: card(north_first),
+                first: PlayerPosition::North,
+                second: PlayerPosition::East,
+            })
+        );
+
+        // East simply drops its first card.
+        let short = text.replacen(&[" / ", east_first, " "].concat(), " / ", 1);
+        assert_eq!(
+            Deal::parse(&short),
+            Err(DealError::Missing(vec![card(east_first)]))
+        );
+        assert_eq!(
+            Deal::parse(&short).unwrap_err().to_string(),
+            ["no seat holds ", east_first].concat()
+        );
+
+        // North's card moves to West: a partition, but not 13 each.
+        let moved = [
+            hands[0].replacen(&[north_first, " "].concat(), "", 1),
+            hands[1].to_string(),
+            hands[2].to_string(),
+            [hands[3], " ", north_first].concat(),
+        ]
+        .join(" / ");
+        assert_eq!(
+            Deal::parse(&moved),
+            Err(DealError::CardCount {
+                seat: PlayerPosition::North,
+                count: 12
+            })
+        );
+
+        assert_eq!(Deal::parse("2C / 3C"), Err(DealError::HandCount(2)));
+        let bad = text.replacen(north_first, "1X", 1);
+        assert_eq!(
+            Deal::parse(&bad),
+            Err(DealError::Card {
+                seat: PlayerPosition::North,
+                error: CardParseError::UnknownSuit(String::from("1X")),
+            })
+        );
+    }
+}
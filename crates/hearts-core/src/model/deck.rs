@@ -1,6 +1,7 @@
 use crate::model::card::Card;
 use crate::model::rank::Rank;
 use crate::model::suit::Suit;
+use alloc::vec::Vec;
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
@@ -1,7 +1,9 @@
-﻿use crate::model::card::Card;
-use std::vec::Vec;
+use crate::model::card::{Card, CardList, CardParseError};
+use crate::model::card_set::CardSet;
+use alloc::vec::Vec;
+use core::fmt;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Hand {
     cards: Vec<Card>,
 }
@@ -51,18 +53,67 @@ impl Hand {
         &self.cards
     }
 
+    /// The hand as a [`CardSet`], for membership and suit counts.
+    pub fn as_set(&self) -> CardSet {
+        CardSet::from(self)
+    }
+
+    /// Reads card codes separated by spaces or commas, e.g. `"AS KS QH"`.
+    pub fn parse(text: &str) -> Result<Self, HandParseError> {
+        let mut cards = Vec::new();
+        for code in text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|code| !code.is_empty())
+        {
+            let card: Card = code.parse().map_err(HandParseError::Card)?;
+            if cards.contains(&card) {
+                return Err(HandParseError::Duplicate(card));
+            }
+            cards.push(card);
+        }
+        Ok(Self::with_cards(cards))
+    }
+
     fn sort(&mut self) {
         self.cards
             .sort_by(|a, b| a.suit.cmp(&b.suit).then(a.rank.cmp(&b.rank)));
     }
 }
 
+/// Writes the card codes in hand order, separated by spaces; the inverse of
+/// [`Hand::parse`].
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        CardList(&self.cards).fmt(f)
+    }
+}
+
+/// Why a hand did not parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandParseError {
+    Card(CardParseError),
+    Duplicate(Card),
+}
+
+impl fmt::Display for HandParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandParseError::Card(err) => err.fmt(f),
+            HandParseError::Duplicate(card) => write!(f, "{card} is listed twice"),
+        }
+    }
+}
+
+impl core::error::Error for HandParseError {}
+
 #[cfg(test)]
 mod tests {
-    use super::Hand;
+    use super::{Hand, HandParseError};
     use crate::model::card::Card;
+    use crate::model::card::CardParseError;
     use crate::model::rank::Rank;
     use crate::model::suit::Suit;
+    use alloc::string::ToString;
 
     #[test]
     fn add_and_remove_cards() {
@@ -85,4 +136,27 @@ mod tests {
         assert_eq!(ordered[1], Card::new(Rank::Ace, Suit::Clubs));
         assert_eq!(ordered[2], Card::new(Rank::King, Suit::Spades));
     }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let hand = Hand::parse("qh AS, ks 2c th").unwrap();
+        assert_eq!(hand.len(), 5);
+        assert_eq!(hand.to_string(), "2C KS AS 10H QH");
+        let again = Hand::parse(&hand.to_string()).unwrap();
+        assert_eq!(again.cards(), hand.cards());
+        assert!(Hand::parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_duplicates_and_bad_codes() {
+        let ace = Card::new(Rank::Ace, Suit::Spades);
+        assert_eq!(
+            Hand::parse("AS as").unwrap_err(),
+            HandParseError::Duplicate(ace)
+        );
+        assert_eq!(
+            Hand::parse("AS ZZ").unwrap_err(),
+            HandParseError::Card(CardParseError::UnknownSuit("ZZ".to_string()))
+        );
+    }
 }
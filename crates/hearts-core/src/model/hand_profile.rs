@@ -0,0 +1,181 @@
+﻿use crate::model::card::Card;
+use crate::model::hand::Hand;
+use crate::model::per_suit::PerSuit;
+use crate::model::rank::Rank;
+use crate::model::suit::Suit;
+
+/// Shape and strength summary of a hand, computed once and shared by the pass
+/// scorer, the moon heuristics and the UI briefing so they all count the same
+/// way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HandProfile {
+    lengths: PerSuit<u8>,
+    honors: PerSuit<u8>,
+    queen_guards: u8,
+    high_hearts: u8,
+    high_spades: u8,
+    queen_of_spades: bool,
+    ace_of_spades: bool,
+}
+
+impl HandProfile {
+    pub fn from_hand(hand: &Hand) -> Self {
+        Self::from_cards(hand.cards())
+    }
+
+    pub fn from_cards(cards: &[Card]) -> Self {
+        let mut profile = Self::default();
+        for card in cards {
+            profile.lengths[card.suit] += 1;
+            if card.rank >= Rank::Jack {
+                profile.honors[card.suit] += 1;
+            }
+            match card.suit {
+                Suit::Spades => {
+                    if card.rank < Rank::Queen {
+                        profile.queen_guards += 1;
+                    } else {
+                        profile.high_spades += 1;
+                    }
+                    profile.queen_of_spades |= card.rank == Rank::Queen;
+                    profile.ace_of_spades |= card.rank == Rank::Ace;
+                }
+                Suit::Hearts if card.rank >= Rank::Ten => profile.high_hearts += 1,
+                _ => {}
+            }
+        }
+        profile
+    }
+
+    pub fn suit_len(&self, suit: Suit) -> usize {
+        usize::from(self.lengths[suit])
+    }
+
+    pub fn is_void(&self, suit: Suit) -> bool {
+        self.lengths[suit] == 0
+    }
+
+    /// Jacks and higher held in `suit`.
+    pub fn honors(&self, suit: Suit) -> usize {
+        usize::from(self.honors[suit])
+    }
+
+    /// Spades below the queen, which cover Q♠ when spades are led.
+    pub fn queen_guards(&self) -> usize {
+        self.queen_guards as usize
+    }
+
+    /// Hearts ten or higher: the cards that keep control of a hearts run.
+    pub fn high_hearts(&self) -> usize {
+        self.high_hearts as usize
+    }
+
+    /// Q♠, K♠ and A♠ held.
+    pub fn high_spades(&self) -> usize {
+        self.high_spades as usize
+    }
+
+    pub fn holds_queen_of_spades(&self) -> bool {
+        self.queen_of_spades
+    }
+
+    pub fn holds_ace_of_spades(&self) -> bool {
+        self.ace_of_spades
+    }
+
+    /// Holds Q♠, K♠ or A♠ with fewer than four spades, so a spade lead can
+    /// force the queen out or drop it on this hand.
+    pub fn queen_exposed(&self) -> bool {
+        self.high_spades > 0 && self.suit_len(Suit::Spades) < 4
+    }
+
+    /// Enough hearts, heart control and spade stoppers to try shooting the moon.
+    pub fn moon_shape(&self) -> bool {
+        self.suit_len(Suit::Hearts) >= 7
+            && self.high_hearts >= 4
+            && self.high_spades >= 2
+            && self.ace_of_spades
+    }
+
+    /// Shortest suit other than hearts and its length; ties go to the first of
+    /// clubs, diamonds, spades.
+    pub fn shortest_side_suit(&self) -> (Suit, usize) {
+        [Suit::Clubs, Suit::Diamonds, Suit::Spades]
+            .into_iter()
+            .map(|suit| (suit, self.suit_len(suit)))
+            .min_by_key(|&(_, len)| len)
+            .unwrap_or((Suit::Clubs, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HandProfile;
+    use crate::model::card::Card;
+    use crate::model::deck::Deck;
+    use crate::model::hand::Hand;
+    use crate::model::rank::Rank;
+    use crate::model::suit::Suit;
+
+    #[test]
+    fn counts_lengths_honors_and_spade_cover() {
+        let hand = Hand::with_cards(vec![
+            Card::new(Rank::Queen, Suit::Spades),
+            Card::new(Rank::Four, Suit::Spades),
+            Card::new(Rank::Two, Suit::Spades),
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ten, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::Jack, Suit::Clubs),
+        ]);
+        let profile = HandProfile::from_hand(&hand);
+        assert_eq!(profile.suit_len(Suit::Spades), 3);
+        assert_eq!(profile.suit_len(Suit::Hearts), 3);
+        assert!(profile.is_void(Suit::Diamonds));
+        assert_eq!(profile.honors(Suit::Spades), 1);
+        assert_eq!(profile.honors(Suit::Hearts), 1);
+        assert_eq!(profile.honors(Suit::Clubs), 1);
+        assert_eq!(profile.queen_guards(), 2);
+        assert_eq!(profile.high_hearts(), 2);
+        assert_eq!(profile.high_spades(), 1);
+        assert!(profile.holds_queen_of_spades());
+        assert!(!profile.holds_ace_of_spades());
+        assert!(profile.queen_exposed());
+        assert!(!profile.moon_shape());
+        assert_eq!(profile.shortest_side_suit(), (Suit::Diamonds, 0));
+    }
+
+    #[test]
+    fn moon_shape_needs_hearts_control_and_spade_stoppers() {
+        let mut cards = vec![
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::King, Suit::Spades),
+        ];
+        for rank in [
+            Rank::Ace,
+            Rank::King,
+            Rank::Queen,
+            Rank::Jack,
+            Rank::Five,
+            Rank::Four,
+            Rank::Three,
+        ] {
+            cards.push(Card::new(rank, Suit::Hearts));
+        }
+        assert!(HandProfile::from_cards(&cards).moon_shape());
+        cards.remove(0);
+        assert!(!HandProfile::from_cards(&cards).moon_shape());
+    }
+
+    #[test]
+    fn lengths_cover_every_card_of_a_deal() {
+        let deck = Deck::shuffled_with_seed(11);
+        let profile = HandProfile::from_cards(deck.cards());
+        for suit in Suit::ALL {
+            assert_eq!(profile.suit_len(suit), 13);
+            assert_eq!(profile.honors(suit), 4);
+        }
+        assert_eq!(profile.queen_guards(), 10);
+        assert_eq!(profile.high_spades(), 3);
+    }
+}
@@ -1,10 +1,16 @@
 ﻿pub mod card;
+pub mod card_set;
+pub mod deal;
 pub mod deck;
 pub mod hand;
+pub mod hand_profile;
 pub mod passing;
+pub mod per_suit;
 pub mod player;
 pub mod rank;
 pub mod round;
+pub mod rules;
+pub mod rules_text;
 pub mod score;
 pub mod suit;
 pub mod trick;
@@ -1,7 +1,16 @@
 use crate::model::card::Card;
 use crate::model::hand::Hand;
 use crate::model::player::PlayerPosition;
-use std::array;
+use crate::model::rank::Rank;
+use crate::model::suit::Suit;
+use core::array;
+use core::ops::Deref;
+
+/// Cards passed per seat in the standard game.
+pub const DEFAULT_PASS_COUNT: usize = 3;
+pub const MIN_PASS_COUNT: usize = 3;
+/// Largest pass any rule set asks for (the four-card house variant).
+pub const MAX_PASS_COUNT: usize = 4;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PassingDirection {
@@ -51,7 +60,7 @@ impl PassingDirection {
     }
 }
 
-impl std::str::FromStr for PassingDirection {
+impl core::str::FromStr for PassingDirection {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -66,10 +75,82 @@ impl std::str::FromStr for PassingDirection {
     }
 }
 
+/// The cards one seat passes, up to [`MAX_PASS_COUNT`], stored inline so a
+/// pass stays `Copy`. Derefs to the card slice.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PassCards {
+    cards: [Card; MAX_PASS_COUNT],
+    len: u8,
+}
+
+impl PassCards {
+    /// `None` when `cards` holds more than [`MAX_PASS_COUNT`] cards.
+    pub fn from_slice(cards: &[Card]) -> Option<Self> {
+        if cards.len() > MAX_PASS_COUNT {
+            return None;
+        }
+        let mut inline = [Card::new(Rank::Two, Suit::Clubs); MAX_PASS_COUNT];
+        inline[..cards.len()].copy_from_slice(cards);
+        Some(Self {
+            cards: inline,
+            len: cards.len() as u8,
+        })
+    }
+
+    pub fn as_slice(&self) -> &[Card] {
+        &self.cards[..usize::from(self.len)]
+    }
+}
+
+impl Deref for PassCards {
+    type Target = [Card];
+
+    fn deref(&self) -> &[Card] {
+        self.as_slice()
+    }
+}
+
+impl core::fmt::Debug for PassCards {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl From<[Card; 3]> for PassCards {
+    fn from(cards: [Card; 3]) -> Self {
+        Self::from_slice(&cards).expect("three cards fit")
+    }
+}
+
+impl From<[Card; 4]> for PassCards {
+    fn from(cards: [Card; 4]) -> Self {
+        Self::from_slice(&cards).expect("four cards fit")
+    }
+}
+
+impl IntoIterator for PassCards {
+    type Item = Card;
+    type IntoIter = core::iter::Take<array::IntoIter<Card, MAX_PASS_COUNT>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter().take(usize::from(self.len))
+    }
+}
+
+impl<'a> IntoIterator for &'a PassCards {
+    type Item = &'a Card;
+    type IntoIter = core::slice::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PassingState {
     direction: PassingDirection,
-    submissions: [Option<[Card; 3]>; 4],
+    count: usize,
+    submissions: [Option<PassCards>; 4],
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -79,41 +160,56 @@ pub enum PassingError {
     AlreadySubmitted(PlayerPosition),
     CardNotInHand(Card),
     Incomplete,
+    WrongCount { expected: usize, actual: usize },
 }
 
-impl PassingState {
-    pub fn new(direction: PassingDirection) -> Self {
-        Self {
-            direction,
-            submissions: array::from_fn(|_| None),
-        }
-    }
-
-    /// Construct a passing state with pre-filled submissions (used for snapshot restore).
-    pub fn with_submissions(
-        direction: PassingDirection,
-        submissions: [Option<[Card; 3]>; 4],
-    ) -> Self {
-        Self {
-            direction,
-            submissions,
-        }
-    }
-
-    pub fn direction(&self) -> PassingDirection {
-        self.direction
-    }
-
-    pub fn submissions(&self) -> &[Option<[Card; 3]>; 4] {
-        &self.submissions
-    }
+impl PassingState {
+    pub fn new(direction: PassingDirection) -> Self {
+        Self::with_count(direction, DEFAULT_PASS_COUNT)
+    }
+
+    /// A passing state where every seat passes `count` cards.
+    pub fn with_count(direction: PassingDirection, count: usize) -> Self {
+        Self {
+            direction,
+            count,
+            submissions: array::from_fn(|_| None),
+        }
+    }
+
+    /// Construct a passing state with pre-filled submissions (used for snapshot restore).
+    pub fn with_submissions(
+        direction: PassingDirection,
+        count: usize,
+        submissions: [Option<PassCards>; 4],
+    ) -> Self {
+        Self {
+            direction,
+            count,
+            submissions,
+        }
+    }
+
+    pub fn direction(&self) -> PassingDirection {
+        self.direction
+    }
+
+    /// Cards each seat passes.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn submissions(&self) -> &[Option<PassCards>; 4] {
+        &self.submissions
+    }
 
     pub fn submit(
         &mut self,
         seat: PlayerPosition,
-        cards: [Card; 3],
+        cards: impl Into<PassCards>,
         hand: &mut Hand,
     ) -> Result<(), PassingError> {
+        let cards = cards.into();
         if !self.direction.requires_selection() {
             return Err(PassingError::DirectionDoesNotPass);
         }
@@ -122,6 +218,13 @@ impl PassingState {
             return Err(PassingError::AlreadySubmitted(seat));
         }
 
+        if cards.len() != self.count {
+            return Err(PassingError::WrongCount {
+                expected: self.count,
+                actual: cards.len(),
+            });
+        }
+
         for card in cards.iter() {
             if !hand.contains(*card) {
                 return Err(PassingError::CardNotInHand(*card));
@@ -0,0 +1,91 @@
+use crate::model::suit::Suit;
+use core::ops::{Index, IndexMut};
+
+/// One value per suit, indexed by [`Suit`] rather than by `suit as usize`.
+/// Iteration always runs in [`Suit::ALL`] order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PerSuit<T>(pub [T; 4]);
+
+impl<T> PerSuit<T> {
+    pub fn from_fn(mut f: impl FnMut(Suit) -> T) -> Self {
+        Self(Suit::ALL.map(&mut f))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Suit, &T)> {
+        Suit::iter().zip(self.0.iter())
+    }
+
+    pub fn values(&self) -> core::slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    pub fn values_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.0.iter_mut()
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PerSuit<U> {
+        PerSuit(self.0.map(f))
+    }
+}
+
+impl<T: Copy> PerSuit<T> {
+    pub const fn splat(value: T) -> Self {
+        Self([value; 4])
+    }
+}
+
+impl<T> Index<Suit> for PerSuit<T> {
+    type Output = T;
+
+    fn index(&self, suit: Suit) -> &T {
+        &self.0[suit.index()]
+    }
+}
+
+impl<T> IndexMut<Suit> for PerSuit<T> {
+    fn index_mut(&mut self, suit: Suit) -> &mut T {
+        &mut self.0[suit.index()]
+    }
+}
+
+impl<T> From<[T; 4]> for PerSuit<T> {
+    fn from(values: [T; 4]) -> Self {
+        Self(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PerSuit;
+    use crate::model::suit::Suit;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn indexing_by_suit_reads_and_writes_its_own_slot() {
+        let mut counts = PerSuit::splat(0u8);
+        counts[Suit::Spades] += 2;
+        counts[Suit::Hearts] += 1;
+        assert_eq!(counts[Suit::Spades], 2);
+        assert_eq!(counts[Suit::Hearts], 1);
+        assert_eq!(counts[Suit::Clubs], 0);
+        assert_eq!(counts.0, [0, 0, 2, 1]);
+        let from_fn = PerSuit::from_fn(|suit| suit.is_black());
+        assert_eq!(from_fn, PerSuit::from([true, false, true, false]));
+    }
+
+    #[test]
+    fn iteration_follows_suit_order() {
+        let labels = PerSuit::from_fn(|suit| suit.index() * 10);
+        let seen: Vec<(Suit, usize)> = labels.iter().map(|(suit, v)| (suit, *v)).collect();
+        assert_eq!(
+            seen,
+            [
+                (Suit::Clubs, 0),
+                (Suit::Diamonds, 10),
+                (Suit::Spades, 20),
+                (Suit::Hearts, 30),
+            ]
+        );
+        assert_eq!(labels.map(|v| v + 1).values().sum::<usize>(), 64);
+    }
+}
@@ -57,6 +57,29 @@ impl Rank {
     pub const fn value(self) -> u8 {
         self as u8
     }
+
+    /// Position of the rank in [`Rank::ORDERED`]: two is 0, ace is 12.
+    pub const fn index(self) -> usize {
+        self as usize - 2
+    }
+
+    pub const fn from_index(index: usize) -> Option<Self> {
+        if index < Self::ORDERED.len() {
+            Some(Self::ORDERED[index])
+        } else {
+            None
+        }
+    }
+
+    /// Two up to ace.
+    pub fn iter() -> core::array::IntoIter<Rank, 13> {
+        Self::ORDERED.into_iter()
+    }
+
+    /// Ace down to two.
+    pub fn descending() -> core::iter::Rev<core::array::IntoIter<Rank, 13>> {
+        Self::ORDERED.into_iter().rev()
+    }
 }
 
 impl fmt::Display for Rank {
@@ -96,6 +119,20 @@ mod tests {
         assert_eq!(Rank::Ten.to_string(), "10");
     }
 
+    #[test]
+    fn iteration_orders_and_indices_agree() {
+        assert_eq!(Rank::iter().next(), Some(Rank::Two));
+        assert_eq!(Rank::descending().next(), Some(Rank::Ace));
+        assert!(Rank::iter().zip(Rank::iter().skip(1)).all(|(a, b)| a < b));
+        for (idx, rank) in Rank::iter().enumerate() {
+            assert_eq!(rank.index(), idx);
+            assert_eq!(Rank::from_index(idx), Some(rank));
+            assert_eq!(Rank::from_value(rank.value()), Some(rank));
+        }
+        assert_eq!(Rank::from_index(13), None);
+        assert_eq!(Rank::from_value(15), None);
+    }
+
     #[test]
     fn value_returns_u8_repr() {
         assert_eq!(Rank::Two.value(), 2);
@@ -1,12 +1,21 @@
 use crate::model::card::Card;
+use crate::model::deal::{Deal, DealError};
 use crate::model::deck::Deck;
 use crate::model::hand::Hand;
-use crate::model::passing::{PassingDirection, PassingError, PassingState};
+use crate::model::passing::{
+    DEFAULT_PASS_COUNT, PassCards, PassingDirection, PassingError, PassingState,
+};
 use crate::model::player::PlayerPosition;
 use crate::model::rank::Rank;
+use crate::model::rules::{JACK_OF_DIAMONDS, RuleSet};
 use crate::model::suit::Suit;
 use crate::model::trick::Trick;
-use std::{array, vec::Vec};
+use alloc::vec::Vec;
+use core::array;
+use core::fmt;
+
+/// Tricks in a round: one per card in each hand.
+pub const TRICKS_PER_ROUND: usize = 13;
 
 #[derive(Debug, Clone)]
 pub struct RoundState {
@@ -16,13 +25,101 @@ pub struct RoundState {
     starting_player: PlayerPosition,
     passing_direction: PassingDirection,
     phase: RoundPhase,
+    phase_history: PhaseHistory,
     hearts_broken: bool,
+    pass_count: usize,
 }
 
+/// Where a round stands. A round moves forward only, by
+/// [`advance`](Self::advance): passing rounds go Passing → Playing →
+/// Complete and hold rounds start at Playing.
 #[derive(Debug, Clone)]
 pub enum RoundPhase {
     Passing(PassingState),
     Playing,
+    /// All 13 tricks are taken and the round is ready to score.
+    Complete,
+}
+
+/// A [`RoundPhase`] without its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseKind {
+    Passing,
+    Playing,
+    Complete,
+}
+
+/// What moves a round to its next phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseEvent {
+    /// The passes have been exchanged.
+    PassesResolved,
+    /// The thirteenth trick has been taken.
+    LastTrickTaken,
+}
+
+/// `event` does not apply in `phase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseError {
+    pub phase: PhaseKind,
+    pub event: PhaseEvent,
+}
+
+impl fmt::Display for PhaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} cannot happen while {:?}", self.event, self.phase)
+    }
+}
+
+impl core::error::Error for PhaseError {}
+
+impl RoundPhase {
+    pub fn kind(&self) -> PhaseKind {
+        match self {
+            RoundPhase::Passing(_) => PhaseKind::Passing,
+            RoundPhase::Playing => PhaseKind::Playing,
+            RoundPhase::Complete => PhaseKind::Complete,
+        }
+    }
+
+    /// The phase `event` leads to from this one.
+    pub fn advance(&self, event: PhaseEvent) -> Result<RoundPhase, PhaseError> {
+        match (self, event) {
+            (RoundPhase::Passing(_), PhaseEvent::PassesResolved) => Ok(RoundPhase::Playing),
+            (RoundPhase::Playing, PhaseEvent::LastTrickTaken) => Ok(RoundPhase::Complete),
+            (phase, event) => Err(PhaseError {
+                phase: phase.kind(),
+                event,
+            }),
+        }
+    }
+}
+
+/// The phases a round has been in, oldest first. A round passes through
+/// at most three, so they are kept inline to keep `RoundState` cheap to
+/// clone.
+#[derive(Debug, Clone, Copy)]
+struct PhaseHistory {
+    kinds: [PhaseKind; 3],
+    len: usize,
+}
+
+impl PhaseHistory {
+    fn starting_at(kind: PhaseKind) -> Self {
+        Self {
+            kinds: [kind; 3],
+            len: 1,
+        }
+    }
+
+    fn push(&mut self, kind: PhaseKind) {
+        self.kinds[self.len] = kind;
+        self.len += 1;
+    }
+
+    fn as_slice(&self) -> &[PhaseKind] {
+        &self.kinds[..self.len]
+    }
 }
 
 impl RoundState {
@@ -52,14 +149,26 @@ impl RoundState {
         trick_history: Vec<Trick>,
         hearts_broken: bool,
     ) -> Self {
+        let pass_count = match &phase {
+            RoundPhase::Passing(state) => state.count(),
+            RoundPhase::Playing | RoundPhase::Complete => DEFAULT_PASS_COUNT,
+        };
+        // Positions saved before the Complete phase existed show a finished
+        // round as still playing.
+        let phase = match phase {
+            RoundPhase::Playing if trick_history.len() == TRICKS_PER_ROUND => RoundPhase::Complete,
+            phase => phase,
+        };
         Self {
             hands,
             current_trick,
             trick_history,
             starting_player,
             passing_direction,
+            phase_history: PhaseHistory::starting_at(phase.kind()),
             phase,
             hearts_broken,
+            pass_count,
         }
     }
 
@@ -67,6 +176,16 @@ impl RoundState {
         deck: &Deck,
         starting_player: PlayerPosition,
         passing_direction: PassingDirection,
+    ) -> Self {
+        Self::deal_with_pass_count(deck, starting_player, passing_direction, DEFAULT_PASS_COUNT)
+    }
+
+    /// Deals a round in which every seat passes `pass_count` cards.
+    pub fn deal_with_pass_count(
+        deck: &Deck,
+        starting_player: PlayerPosition,
+        passing_direction: PassingDirection,
+        pass_count: usize,
     ) -> Self {
         let mut hands = array::from_fn(|_| Hand::new());
 
@@ -75,6 +194,41 @@ impl RoundState {
             hands[seat.index()].add(*card);
         }
 
+        Self::dealt(hands, starting_player, passing_direction, pass_count)
+    }
+
+    /// Starts a round on the pinned hands of `deal`; see [`Deal`].
+    pub fn from_deal(deal: &Deal, passing_direction: PassingDirection, pass_count: usize) -> Self {
+        Self::dealt(
+            deal.hands().clone(),
+            PlayerPosition::North,
+            passing_direction,
+            pass_count,
+        )
+    }
+
+    /// [`from_deal`](Self::from_deal) on a deal's text form, with the
+    /// default pass count.
+    pub fn from_deal_string(
+        text: &str,
+        passing_direction: PassingDirection,
+    ) -> Result<Self, DealError> {
+        let deal = Deal::parse(text)?;
+        Ok(Self::from_deal(
+            &deal,
+            passing_direction,
+            DEFAULT_PASS_COUNT,
+        ))
+    }
+
+    /// A fresh round on `hands`, led by the holder of 2♣ (or
+    /// `starting_player` if nobody holds it).
+    fn dealt(
+        hands: [Hand; 4],
+        starting_player: PlayerPosition,
+        passing_direction: PassingDirection,
+        pass_count: usize,
+    ) -> Self {
         let two_of_clubs = Card::new(Rank::Two, Suit::Clubs);
         let starting_player = hands
             .iter()
@@ -84,7 +238,7 @@ impl RoundState {
             .unwrap_or(starting_player);
 
         let phase = if passing_direction.requires_selection() {
-            RoundPhase::Passing(PassingState::new(passing_direction))
+            RoundPhase::Passing(PassingState::with_count(passing_direction, pass_count))
         } else {
             RoundPhase::Playing
         };
@@ -95,11 +249,18 @@ impl RoundState {
             trick_history: Vec::new(),
             starting_player,
             passing_direction,
+            phase_history: PhaseHistory::starting_at(phase.kind()),
             phase,
             hearts_broken: false,
+            pass_count,
         }
     }
 
+    /// Cards each seat passes this round, also once passing is over.
+    pub fn pass_count(&self) -> usize {
+        self.pass_count
+    }
+
     pub fn passing_direction(&self) -> PassingDirection {
         self.passing_direction
     }
@@ -108,6 +269,18 @@ impl RoundState {
         &self.phase
     }
 
+    /// Every phase this round has been in, oldest first, ending with the
+    /// current one.
+    pub fn phase_history(&self) -> &[PhaseKind] {
+        self.phase_history.as_slice()
+    }
+
+    fn advance_phase(&mut self, event: PhaseEvent) -> Result<(), PhaseError> {
+        self.phase = self.phase.advance(event)?;
+        self.phase_history.push(self.phase.kind());
+        Ok(())
+    }
+
     pub fn hand(&self, seat: PlayerPosition) -> &Hand {
         &self.hands[seat.index()]
     }
@@ -129,7 +302,7 @@ impl RoundState {
     }
 
     pub fn complete_trick(&mut self, next_leader: PlayerPosition) {
-        let finished = std::mem::replace(&mut self.current_trick, Trick::new(next_leader));
+        let finished = core::mem::replace(&mut self.current_trick, Trick::new(next_leader));
         self.trick_history.push(finished);
     }
 
@@ -137,24 +310,60 @@ impl RoundState {
         self.trick_history.len()
     }
 
+    /// Seat whose hand currently holds `card`, if it has not been played.
+    pub fn holder_of(&self, card: Card) -> Option<PlayerPosition> {
+        PlayerPosition::LOOP
+            .iter()
+            .copied()
+            .find(|seat| self.hands[seat.index()].contains(card))
+    }
+
+    /// Winner of the completed trick that contained `card`.
+    pub fn captured_by(&self, card: Card) -> Option<PlayerPosition> {
+        self.trick_history
+            .iter()
+            .find(|trick| trick.plays().iter().any(|play| play.card == card))
+            .and_then(Trick::winner)
+    }
+
+    /// The seat that won every trick, once the last one is taken.
+    pub fn sun_shooter(&self) -> Option<PlayerPosition> {
+        if self.hands.iter().any(|hand| !hand.is_empty()) {
+            return None;
+        }
+        let mut winners = self.trick_history.iter().map(Trick::winner);
+        let first = winners.next()??;
+        winners.all(|winner| winner == Some(first)).then_some(first)
+    }
+
+    /// Whether Q♠ has been taken in a completed trick.
+    pub fn queen_played(&self) -> bool {
+        self.captured_by(Card::new(Rank::Queen, Suit::Spades))
+            .is_some()
+    }
+
+    /// Submits `seat`'s pass; takes a `[Card; 3]` or, for other pass counts,
+    /// [`PassCards`].
     pub fn submit_pass(
         &mut self,
         seat: PlayerPosition,
-        cards: [Card; 3],
+        cards: impl Into<PassCards>,
     ) -> Result<(), PassingError> {
         match &mut self.phase {
             RoundPhase::Passing(state) => {
                 let hand = &mut self.hands[seat.index()];
                 state.submit(seat, cards, hand)
             }
-            RoundPhase::Playing => Err(PassingError::NotInPassingPhase),
+            RoundPhase::Playing | RoundPhase::Complete => Err(PassingError::NotInPassingPhase),
         }
     }
 
     pub fn resolve_passes(&mut self) -> Result<(), PassingError> {
         let state = match &self.phase {
             RoundPhase::Passing(state) => state.clone(),
-            RoundPhase::Playing => return Err(PassingError::NotInPassingPhase),
+            RoundPhase::Playing | RoundPhase::Complete => {
+                return Err(PassingError::NotInPassingPhase);
+            }
         };
 
         if !state.direction().requires_selection() {
@@ -177,7 +386,8 @@ impl RoundState {
             self.starting_player = new_leader;
             self.current_trick = Trick::new(new_leader);
         }
-        self.phase = RoundPhase::Playing;
+        self.advance_phase(PhaseEvent::PassesResolved)
+            .expect("passes resolve from the passing phase");
         Ok(())
     }
 
@@ -206,6 +416,32 @@ impl RoundState {
         self.hearts_broken
     }
 
+    /// Whether every card that scores under `rules` has been captured: the
+    /// hearts and Q♠, and J♦ under the Omnibus rule. The remaining tricks
+    /// then cannot change this round's score. Under the sun rule they still
+    /// can while one seat has won every trick so far.
+    pub fn is_decided(&self, rules: &RuleSet) -> bool {
+        let scores =
+            |card: Card| card.is_penalty() || (rules.jack_of_diamonds && card == JACK_OF_DIAMONDS);
+        let cards_left = self.hands.iter().any(|hand| !hand.is_empty());
+        let sun_open = rules.shoot_the_sun && cards_left && {
+            let mut winners = self.trick_history.iter().map(Trick::winner);
+            let first = winners.next().flatten();
+            winners.all(|winner| winner == first)
+        };
+        !sun_open
+            && matches!(self.phase, RoundPhase::Playing | RoundPhase::Complete)
+            && self
+                .hands
+                .iter()
+                .all(|hand| hand.iter().all(|&card| !scores(card)))
+            && self
+                .current_trick
+                .plays()
+                .iter()
+                .all(|play| !scores(play.card))
+    }
+
     pub fn is_first_trick(&self) -> bool {
         self.trick_history.is_empty()
     }
@@ -218,91 +454,110 @@ impl RoundState {
         !hand.iter().any(|c| !c.suit.is_heart())
     }
 
-    fn validate_play(&self, seat: PlayerPosition, card: Card) -> Result<(), PlayError> {
-        if !matches!(self.phase, RoundPhase::Playing) {
-            return Err(PlayError::NotInPlayPhase);
-        }
-
-        if !self.hands[seat.index()].contains(card) {
-            return Err(PlayError::CardNotInHand(card));
-        }
-
-        let expected = self
-            .current_trick
-            .plays()
-            .last()
-            .map(|p| p.position.next())
-            .unwrap_or(self.current_trick.leader());
-        if expected != seat {
-            return Err(PlayError::OutOfTurn {
-                expected,
-                actual: seat,
-            });
-        }
-
-        let lead_suit = self.current_trick.lead_suit();
-        let is_lead = lead_suit.is_none();
-
-        if self.is_first_trick() {
-            if is_lead {
-                let two_of_clubs = Card::new(Rank::Two, Suit::Clubs);
-                if card != two_of_clubs {
-                    return Err(PlayError::MustLeadTwoOfClubs);
-                }
-            } else if lead_suit == Some(Suit::Clubs) {
-                let hand = &self.hands[seat.index()];
-                let can_follow = hand.iter().any(|c| c.suit == Suit::Clubs);
-                if !can_follow && card.is_penalty() {
-                    let has_non_penalty_option = hand.iter().any(|c| !c.is_penalty());
-                    if has_non_penalty_option {
-                        return Err(PlayError::NoPointsOnFirstTrick);
-                    }
-                }
-            }
-        }
-
-        if let Some(suit) = lead_suit {
-            if card.suit != suit && self.hands[seat.index()].iter().any(|c| c.suit == suit) {
-                return Err(PlayError::MustFollowSuit(suit));
-            }
-        } else if card.suit == Suit::Hearts && !self.legal_to_lead_hearts(seat) {
-            return Err(PlayError::HeartsNotBroken);
-        }
-
-        Ok(())
-    }
-
-    pub fn can_play_card(&self, seat: PlayerPosition, card: Card) -> bool {
-        self.validate_play(seat, card).is_ok()
-    }
-
-    pub fn legal_cards(&self, seat: PlayerPosition) -> Vec<Card> {
-        self.hands[seat.index()]
-            .iter()
-            .copied()
-            .filter(|&card| self.can_play_card(seat, card))
-            .collect()
-    }
-
-    pub fn play_card(
-        &mut self,
-        seat: PlayerPosition,
-        card: Card,
-    ) -> Result<PlayOutcome, PlayError> {
-        self.validate_play(seat, card)?;
-
-        let _ = self.hands[seat.index()].remove(card);
-        if card.is_penalty() {
-            self.hearts_broken = true;
-        }
-        self.current_trick
-            .play(seat, card)
-            .map_err(PlayError::Trick)?;
+    /// Why `seat` may not play `card` now, if it may not; see
+    /// [`crate::model::rules_text::RulesText::refusal`] for the wording.
+    pub fn validate_play(&self, seat: PlayerPosition, card: Card) -> Result<(), PlayError> {
+        if !matches!(self.phase, RoundPhase::Playing) {
+            return Err(PlayError::NotInPlayPhase);
+        }
+
+        if !self.hands[seat.index()].contains(card) {
+            return Err(PlayError::CardNotInHand(card));
+        }
+
+        let expected = self
+            .current_trick
+            .plays()
+            .last()
+            .map(|p| p.position.next())
+            .unwrap_or(self.current_trick.leader());
+        if expected != seat {
+            return Err(PlayError::OutOfTurn {
+                expected,
+                actual: seat,
+            });
+        }
+
+        let lead_suit = self.current_trick.lead_suit();
+        let is_lead = lead_suit.is_none();
+
+        if self.is_first_trick() {
+            if is_lead {
+                let two_of_clubs = Card::new(Rank::Two, Suit::Clubs);
+                if card != two_of_clubs {
+                    return Err(PlayError::MustLeadTwoOfClubs);
+                }
+            } else if lead_suit == Some(Suit::Clubs) {
+                let hand = &self.hands[seat.index()];
+                let can_follow = hand.iter().any(|c| c.suit == Suit::Clubs);
+                if !can_follow && card.is_penalty() {
+                    let has_non_penalty_option = hand.iter().any(|c| !c.is_penalty());
+                    if has_non_penalty_option {
+                        return Err(PlayError::NoPointsOnFirstTrick);
+                    }
+                }
+            }
+        }
+
+        if let Some(suit) = lead_suit {
+            if card.suit != suit && self.hands[seat.index()].iter().any(|c| c.suit == suit) {
+                return Err(PlayError::MustFollowSuit(suit));
+            }
+        } else if card.suit == Suit::Hearts && !self.legal_to_lead_hearts(seat) {
+            return Err(PlayError::HeartsNotBroken);
+        }
+
+        Ok(())
+    }
+
+    pub fn can_play_card(&self, seat: PlayerPosition, card: Card) -> bool {
+        self.validate_play(seat, card).is_ok()
+    }
+
+    /// Cards `seat` may play now, in hand order; empty when it is not the
+    /// seat's turn or the round is still passing.
+    ///
+    /// This is the one place the play rules live: follow suit, 2♣ leads the
+    /// first trick, no hearts or Q♠ on the first trick unless the hand holds
+    /// nothing else, and no heart lead before hearts break unless the hand
+    /// is all hearts. [`play_card`](Self::play_card) accepts exactly these.
+    pub fn legal_plays(&self, seat: PlayerPosition) -> Vec<Card> {
+        self.hands[seat.index()]
+            .iter()
+            .copied()
+            .filter(|&card| self.can_play_card(seat, card))
+            .collect()
+    }
+
+    /// The old name of [`legal_plays`](Self::legal_plays).
+    #[deprecated(note = "use `legal_plays`")]
+    pub fn legal_cards(&self, seat: PlayerPosition) -> Vec<Card> {
+        self.legal_plays(seat)
+    }
+
+    pub fn play_card(
+        &mut self,
+        seat: PlayerPosition,
+        card: Card,
+    ) -> Result<PlayOutcome, PlayError> {
+        self.validate_play(seat, card)?;
+
+        let _ = self.hands[seat.index()].remove(card);
+        if card.is_penalty() {
+            self.hearts_broken = true;
+        }
+        self.current_trick
+            .play(seat, card)
+            .map_err(PlayError::Trick)?;
 
         if self.current_trick.is_complete() {
             let winner = self.current_trick.winner().expect("winner when complete");
             let penalties = self.current_trick.penalty_total();
             self.complete_trick(winner);
+            if self.trick_history.len() == TRICKS_PER_ROUND {
+                self.advance_phase(PhaseEvent::LastTrickTaken)
+                    .expect("tricks are played in the playing phase");
+            }
             Ok(PlayOutcome::TrickCompleted { winner, penalties })
         } else {
             Ok(PlayOutcome::Played)
@@ -336,13 +591,20 @@ pub enum PlayError {
 
 #[cfg(test)]
 mod tests {
-    use super::{PassingDirection, PlayError, PlayOutcome, RoundPhase, RoundState};
+    use super::{
+        PassCards, PassingDirection, PassingError, PassingState, PhaseError, PhaseEvent, PhaseKind,
+        PlayError, PlayOutcome, RoundPhase, RoundState,
+    };
     use crate::model::card::Card;
-    use crate::model::deck::Deck;
-    use crate::model::hand::Hand;
+    use crate::model::deck::Deck;
     use crate::model::player::PlayerPosition;
     use crate::model::rank::Rank;
+    use crate::model::rules::{RuleSet, SunScore};
     use crate::model::suit::Suit;
+    use crate::testkit::{RoundBuilder, card, cards, fast_forward};
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
 
     #[test]
     fn dealing_distributes_thirteen_cards_per_player() {
@@ -384,6 +646,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn four_card_pass_requires_four_cards() {
+        let deck = Deck::standard();
+        let mut round = RoundState::deal_with_pass_count(
+            &deck,
+            PlayerPosition::North,
+            PassingDirection::Across,
+            4,
+        );
+        assert_eq!(round.pass_count(), 4);
+        let hand = round.hand(PlayerPosition::North).cards().to_vec();
+        assert_eq!(
+            round.submit_pass(PlayerPosition::North, [hand[0], hand[1], hand[2]]),
+            Err(PassingError::WrongCount {
+                expected: 4,
+                actual: 3
+            })
+        );
+
+        for seat in PlayerPosition::LOOP.iter().copied() {
+            let cards = PassCards::from_slice(&round.hand(seat).cards()[..4]).unwrap();
+            round.submit_pass(seat, cards).unwrap();
+        }
+        round.resolve_passes().unwrap();
+        assert!(matches!(round.phase(), RoundPhase::Playing));
+        for seat in PlayerPosition::LOOP.iter().copied() {
+            assert_eq!(round.hand(seat).len(), 13);
+        }
+        assert_eq!(round.pass_count(), 4);
+    }
+
     #[test]
     fn hold_direction_starts_in_play_phase() {
         let deck = Deck::standard();
@@ -431,6 +724,65 @@ mod tests {
         assert_eq!(totals[PlayerPosition::West.index()], 0);
     }
 
+    #[test]
+    fn a_seat_that_takes_every_trick_shoots_the_sun() {
+        use PlayerPosition::{East, North, South, West};
+        const RANKS: [&str; 13] = [
+            "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A",
+        ];
+        let suit = |suit: &str| {
+            RANKS
+                .iter()
+                .map(|rank| format!("{rank}{suit}"))
+                .collect::<Vec<_>>()
+        };
+        let (clubs, spades, diamonds, hearts) = (suit("C"), suit("S"), suit("D"), suit("H"));
+        let mut round = RoundBuilder::new()
+            .seat_hand(North, &clubs.join(" "))
+            .seat_hand(East, &spades.join(" "))
+            .seat_hand(South, &diamonds.join(" "))
+            .seat_hand(West, &hearts.join(" "))
+            .build();
+        // North leads every club; the others can only discard.
+        let plays: Vec<String> = (0..13)
+            .map(|i| {
+                [&clubs[i], &spades[i], &diamonds[i], &hearts[i]]
+                    .map(String::as_str)
+                    .join(" ")
+            })
+            .collect();
+        fast_forward(&mut round, &plays[..12].join(" "));
+        assert_eq!(round.sun_shooter(), None, "one trick is still to play");
+        fast_forward(&mut round, &plays[12]);
+        assert_eq!(round.sun_shooter(), Some(North));
+        assert_eq!(round.penalty_totals(), [26, 0, 0, 0]);
+    }
+
+    #[test]
+    fn holder_and_captor_of_queen_follow_the_card() {
+        let deck = Deck::standard();
+        let mut round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
+        let queen = Card::new(Rank::Queen, Suit::Spades);
+        let holder = round.holder_of(queen).expect("queen is dealt");
+        assert!(round.hand(holder).contains(queen));
+        assert_eq!(round.captured_by(queen), None);
+
+        let plays = [
+            (PlayerPosition::North, Card::new(Rank::Ace, Suit::Spades)),
+            (PlayerPosition::East, queen),
+            (PlayerPosition::South, Card::new(Rank::Three, Suit::Spades)),
+            (PlayerPosition::West, Card::new(Rank::Four, Suit::Spades)),
+        ];
+        for (seat, card) in plays {
+            round.current_trick_mut().play(seat, card).unwrap();
+        }
+        assert_eq!(round.captured_by(queen), None);
+        assert!(!round.queen_played());
+        round.complete_trick(PlayerPosition::North);
+        assert_eq!(round.captured_by(queen), Some(PlayerPosition::North));
+        assert!(round.queen_played());
+    }
+
     #[test]
     fn leader_follows_two_of_clubs_holder() {
         let deck = Deck::shuffled_with_seed(99);
@@ -505,10 +857,10 @@ mod tests {
         ));
     }
 
-    #[test]
-    fn follow_suit_is_required() {
-        let deck = Deck::standard();
-        let mut round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
+    #[test]
+    fn follow_suit_is_required() {
+        let deck = Deck::standard();
+        let mut round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
         round
             .play_card(PlayerPosition::North, Card::new(Rank::Two, Suit::Clubs))
             .unwrap();
@@ -517,93 +869,135 @@ mod tests {
             Err(PlayError::MustFollowSuit(Suit::Clubs)) => {}
             other => panic!("expected MustFollowSuit, got {other:?}"),
         }
-        round
-            .play_card(PlayerPosition::East, Card::new(Rank::Three, Suit::Clubs))
-            .unwrap();
-    }
-
-    #[test]
-    fn legal_cards_matches_play_requirements() {
-        let deck = Deck::standard();
-        let round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
-        let legal = round.legal_cards(PlayerPosition::North);
-        assert_eq!(legal.len(), 1);
-        assert_eq!(legal[0], Card::new(Rank::Two, Suit::Clubs));
-    }
-
-    #[test]
-    fn first_trick_allows_penalty_when_only_penalties_available() {
-        let hands = [
-            Hand::with_cards(vec![
-                Card::new(Rank::Two, Suit::Clubs),
-                Card::new(Rank::Three, Suit::Diamonds),
-            ]),
-            Hand::with_cards(vec![
-                Card::new(Rank::Queen, Suit::Spades),
-                Card::new(Rank::Four, Suit::Hearts),
-                Card::new(Rank::Five, Suit::Hearts),
-            ]),
-            Hand::with_cards(vec![Card::new(Rank::Six, Suit::Clubs)]),
-            Hand::with_cards(vec![Card::new(Rank::Seven, Suit::Clubs)]),
-        ];
-        let mut round = RoundState::from_hands(
-            hands,
-            PlayerPosition::North,
-            PassingDirection::Hold,
-            RoundPhase::Playing,
-        );
-        round
-            .play_card(PlayerPosition::North, Card::new(Rank::Two, Suit::Clubs))
-            .unwrap();
-        assert!(matches!(
-            round.play_card(PlayerPosition::East, Card::new(Rank::Queen, Suit::Spades)),
-            Ok(PlayOutcome::Played)
-        ));
-        assert!(matches!(
-            round.play_card(PlayerPosition::South, Card::new(Rank::Six, Suit::Clubs)),
-            Ok(PlayOutcome::Played)
-        ));
-    }
-
-    #[test]
-    fn first_trick_blocks_penalty_when_safe_card_available() {
-        let hands = [
-            Hand::with_cards(vec![
-                Card::new(Rank::Two, Suit::Clubs),
-                Card::new(Rank::Three, Suit::Diamonds),
-            ]),
-            Hand::with_cards(vec![
-                Card::new(Rank::Four, Suit::Hearts),
-                Card::new(Rank::Six, Suit::Diamonds),
-            ]),
-            Hand::with_cards(vec![Card::new(Rank::Six, Suit::Clubs)]),
-            Hand::with_cards(vec![Card::new(Rank::Seven, Suit::Clubs)]),
-        ];
-        let mut round = RoundState::from_hands(
-            hands,
-            PlayerPosition::North,
-            PassingDirection::Hold,
-            RoundPhase::Playing,
-        );
-        round
-            .play_card(PlayerPosition::North, Card::new(Rank::Two, Suit::Clubs))
-            .unwrap();
-        assert!(matches!(
-            round.play_card(PlayerPosition::East, Card::new(Rank::Four, Suit::Hearts)),
-            Err(PlayError::NoPointsOnFirstTrick)
-        ));
-        assert!(matches!(
-            round.play_card(PlayerPosition::East, Card::new(Rank::Six, Suit::Diamonds)),
-            Ok(PlayOutcome::Played)
-        ));
-    }
+        round
+            .play_card(PlayerPosition::East, Card::new(Rank::Three, Suit::Clubs))
+            .unwrap();
+    }
+
+    #[test]
+    fn legal_plays_matches_play_requirements() {
+        let deck = Deck::standard();
+        let round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
+        let legal = round.legal_plays(PlayerPosition::North);
+        assert_eq!(legal.len(), 1);
+        assert_eq!(legal[0], Card::new(Rank::Two, Suit::Clubs));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legal_cards_is_legal_plays_under_its_old_name() {
+        let round = RoundState::deal(
+            &Deck::standard(),
+            PlayerPosition::North,
+            PassingDirection::Hold,
+        );
+        for seat in PlayerPosition::LOOP {
+            assert_eq!(round.legal_cards(seat), round.legal_plays(seat));
+        }
+    }
+
+    #[test]
+    fn first_trick_allows_penalty_when_only_penalties_available() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "2C 3D")
+            .seat_hand(PlayerPosition::East, "QS 4H 5H")
+            .seat_hand(PlayerPosition::South, "6C")
+            .seat_hand(PlayerPosition::West, "7C")
+            .build();
+        fast_forward(&mut round, "2C");
+        assert!(matches!(
+            round.play_card(PlayerPosition::East, card("QS")),
+            Ok(PlayOutcome::Played)
+        ));
+        assert!(matches!(
+            round.play_card(PlayerPosition::South, card("6C")),
+            Ok(PlayOutcome::Played)
+        ));
+    }
+
+    #[test]
+    fn legal_plays_allow_any_penalty_when_first_trick_follower_holds_only_penalties() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "2C 3D")
+            .seat_hand(PlayerPosition::East, "QS 4H 5H")
+            .seat_hand(PlayerPosition::South, "6C")
+            .seat_hand(PlayerPosition::West, "7C")
+            .build();
+        assert!(round.legal_plays(PlayerPosition::East).is_empty());
+        fast_forward(&mut round, "2C");
+        assert_eq!(round.legal_plays(PlayerPosition::East), cards("QS 4H 5H"));
+    }
 
     #[test]
-    fn cannot_lead_hearts_before_broken_on_second_trick() {
-        let deck = Deck::standard();
-        let mut round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
-        round
-            .play_card(PlayerPosition::North, Card::new(Rank::Two, Suit::Clubs))
+    fn legal_plays_keep_penalties_off_the_first_trick() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "2C 3D")
+            .seat_hand(PlayerPosition::East, "QS 4H 6D")
+            .seat_hand(PlayerPosition::South, "6C 8C AH")
+            .seat_hand(PlayerPosition::West, "7C")
+            .build();
+        fast_forward(&mut round, "2C");
+        assert_eq!(round.legal_plays(PlayerPosition::East), cards("6D"));
+        fast_forward(&mut round, "6D");
+        assert_eq!(round.legal_plays(PlayerPosition::South), cards("6C 8C"));
+    }
+
+    #[test]
+    fn legal_plays_lead_hearts_only_once_broken_or_forced() {
+        let round = RoundBuilder::new()
+            .trick(PlayerPosition::North, "2C 3C 4C 5C")
+            .seat_hand(PlayerPosition::North, "6D")
+            .seat_hand(PlayerPosition::East, "7D")
+            .seat_hand(PlayerPosition::South, "8D")
+            .seat_hand(PlayerPosition::West, "2H 9S")
+            .build();
+        assert_eq!(round.legal_plays(PlayerPosition::West), cards("9S"));
+
+        let all_hearts = RoundBuilder::new()
+            .trick(PlayerPosition::North, "2C 3C 4C 5C")
+            .seat_hand(PlayerPosition::North, "6D")
+            .seat_hand(PlayerPosition::East, "7D")
+            .seat_hand(PlayerPosition::South, "8D")
+            .seat_hand(PlayerPosition::West, "2H")
+            .build();
+        assert_eq!(all_hearts.legal_plays(PlayerPosition::West), cards("2H"));
+
+        let broken = RoundBuilder::new()
+            .trick(PlayerPosition::North, "2C 3C 4C 5C")
+            .seat_hand(PlayerPosition::North, "6D")
+            .seat_hand(PlayerPosition::East, "7D")
+            .seat_hand(PlayerPosition::South, "8D")
+            .seat_hand(PlayerPosition::West, "2H 9S")
+            .hearts_broken(true)
+            .build();
+        assert_eq!(broken.legal_plays(PlayerPosition::West), cards("9S 2H"));
+    }
+
+    #[test]
+    fn first_trick_blocks_penalty_when_safe_card_available() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "2C 3D")
+            .seat_hand(PlayerPosition::East, "4H 6D")
+            .seat_hand(PlayerPosition::South, "6C")
+            .seat_hand(PlayerPosition::West, "7C")
+            .build();
+        fast_forward(&mut round, "2C");
+        assert!(matches!(
+            round.play_card(PlayerPosition::East, card("4H")),
+            Err(PlayError::NoPointsOnFirstTrick)
+        ));
+        assert!(matches!(
+            round.play_card(PlayerPosition::East, card("6D")),
+            Ok(PlayOutcome::Played)
+        ));
+    }
+
+    #[test]
+    fn cannot_lead_hearts_before_broken_on_second_trick() {
+        let deck = Deck::standard();
+        let mut round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
+        round
+            .play_card(PlayerPosition::North, Card::new(Rank::Two, Suit::Clubs))
             .unwrap();
         round
             .play_card(PlayerPosition::East, Card::new(Rank::Three, Suit::Clubs))
@@ -620,47 +1014,193 @@ mod tests {
         }
         assert!(!round.hearts_broken());
 
-        let west_hand = round.hand(PlayerPosition::West).clone();
-        if let Some(h) = west_hand.iter().find(|c| c.suit == Suit::Hearts).copied()
-            && west_hand.iter().any(|c| !c.suit.is_heart())
-        {
-            assert!(matches!(
-                round.play_card(PlayerPosition::West, h),
-                Err(PlayError::HeartsNotBroken)
-            ));
-        }
-    }
-
-    #[test]
-    fn queen_of_spades_breaks_hearts() {
-        // Construct a minimal round where QS is sloughed on the first trick
-        // (East has no clubs, only the queen of spades).
-        let hands = [
-            Hand::with_cards(vec![Card::new(Rank::Two, Suit::Clubs)]), // North leads 2C
-            Hand::with_cards(vec![Card::new(Rank::Queen, Suit::Spades)]), // East can only play QS
-            Hand::with_cards(vec![Card::new(Rank::Three, Suit::Clubs)]), // South follows
-            Hand::with_cards(vec![Card::new(Rank::Four, Suit::Clubs)]), // West follows
-        ];
-        let mut round = RoundState::from_hands(
-            hands,
-            PlayerPosition::North,
-            PassingDirection::Hold,
-            RoundPhase::Playing,
-        );
-
-        round
-            .play_card(PlayerPosition::North, Card::new(Rank::Two, Suit::Clubs))
-            .unwrap();
-        round
-            .play_card(PlayerPosition::East, Card::new(Rank::Queen, Suit::Spades))
-            .unwrap();
-        assert!(round.hearts_broken(), "QS should break hearts");
-        round
-            .play_card(PlayerPosition::South, Card::new(Rank::Three, Suit::Clubs))
-            .unwrap();
-        round
-            .play_card(PlayerPosition::West, Card::new(Rank::Four, Suit::Clubs))
-            .unwrap();
-        assert!(round.hearts_broken());
-    }
-}
+        let west_hand = round.hand(PlayerPosition::West).clone();
+        if let Some(h) = west_hand.iter().find(|c| c.suit == Suit::Hearts).copied()
+            && west_hand.iter().any(|c| !c.suit.is_heart())
+        {
+            assert!(matches!(
+                round.play_card(PlayerPosition::West, h),
+                Err(PlayError::HeartsNotBroken)
+            ));
+        }
+    }
+
+    #[test]
+    fn round_is_decided_once_the_last_penalty_is_captured() {
+        let deck = Deck::standard();
+        let passing = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Left);
+        assert!(!passing.is_decided(&RuleSet::STANDARD));
+
+        let mut round = RoundBuilder::new()
+            .trick(PlayerPosition::North, "2C 3C 4C 5C")
+            .seat_hand(PlayerPosition::North, "4D 8C")
+            .seat_hand(PlayerPosition::East, "5D 9C")
+            .seat_hand(PlayerPosition::South, "6D 10C")
+            .seat_hand(PlayerPosition::West, "7H JC")
+            .hearts_broken(true)
+            .build();
+        assert!(!round.is_decided(&RuleSet::STANDARD));
+        // The last heart on the table still has to be won by someone.
+        fast_forward(&mut round, "7H");
+        assert!(!round.is_decided(&RuleSet::STANDARD));
+        fast_forward(&mut round, "4D 5D 6D");
+        assert!(round.is_decided(&RuleSet::STANDARD));
+        assert_eq!(round.penalty_totals()[PlayerPosition::West.index()], 1);
+    }
+
+    #[test]
+    fn under_the_sun_rule_a_seat_winning_every_trick_keeps_the_hand_open() {
+        let sun = RuleSet::STANDARD.with_shoot_the_sun(Some(SunScore::FiftyTwoToOthers));
+        let build = |south: &str| {
+            RoundBuilder::new()
+                .trick(PlayerPosition::North, "2C 3C 4C 5C")
+                .seat_hand(PlayerPosition::North, "4D 8C")
+                .seat_hand(PlayerPosition::East, "5D 9C")
+                .seat_hand(PlayerPosition::South, south)
+                .seat_hand(PlayerPosition::West, "7D JC")
+                .build()
+        };
+        let mut round = build("8D 10C");
+        // West took the only trick and leads: the sun is still open.
+        assert!(round.is_decided(&RuleSet::STANDARD));
+        assert!(!round.is_decided(&sun));
+        fast_forward(&mut round, "7D 4D 5D");
+        assert!(!round.is_decided(&sun));
+        // South's 8D takes a trick off West, which closes the sun.
+        fast_forward(&mut round, "8D");
+        assert!(round.is_decided(&sun));
+
+        let mut round = build("6D 10C");
+        fast_forward(&mut round, "JC 8C 9C 10C 7D 4D 5D");
+        assert!(!round.is_decided(&sun));
+        fast_forward(&mut round, "6D");
+        assert_eq!(round.sun_shooter(), Some(PlayerPosition::West));
+        assert!(round.is_decided(&sun));
+    }
+
+    #[test]
+    fn under_the_omnibus_rule_the_jack_has_to_fall_too() {
+        let omnibus = RuleSet::STANDARD.with_jack_of_diamonds(true);
+        let mut round = RoundBuilder::new()
+            .trick(PlayerPosition::North, "2C 3C 4C 5C")
+            .seat_hand(PlayerPosition::North, "4D 8C")
+            .seat_hand(PlayerPosition::East, "JD 9C")
+            .seat_hand(PlayerPosition::South, "6D 10C")
+            .seat_hand(PlayerPosition::West, "7D JC")
+            .build();
+        assert!(round.is_decided(&RuleSet::STANDARD));
+        assert!(!round.is_decided(&omnibus));
+        // The jack on the table still has to be won by someone.
+        fast_forward(&mut round, "7D 4D JD");
+        assert!(!round.is_decided(&omnibus));
+        fast_forward(&mut round, "6D");
+        assert!(round.is_decided(&omnibus));
+    }
+
+    #[test]
+    fn queen_of_spades_breaks_hearts() {
+        // QS is sloughed on the first trick: East has no clubs, only the queen.
+        let mut round = RoundBuilder::new()
+            .seat_hand(PlayerPosition::North, "2C")
+            .seat_hand(PlayerPosition::East, "QS")
+            .seat_hand(PlayerPosition::South, "3C")
+            .seat_hand(PlayerPosition::West, "4C")
+            .build();
+
+        fast_forward(&mut round, "2C QS");
+        assert!(round.hearts_broken(), "QS should break hearts");
+        fast_forward(&mut round, "3C 4C");
+        assert!(round.hearts_broken());
+    }
+
+    #[test]
+    fn phases_advance_only_along_legal_edges() {
+        let phases = [
+            RoundPhase::Passing(PassingState::new(PassingDirection::Left)),
+            RoundPhase::Playing,
+            RoundPhase::Complete,
+        ];
+        let events = [PhaseEvent::PassesResolved, PhaseEvent::LastTrickTaken];
+        for phase in &phases {
+            for event in events {
+                let expected = match (phase.kind(), event) {
+                    (PhaseKind::Passing, PhaseEvent::PassesResolved) => Ok(PhaseKind::Playing),
+                    (PhaseKind::Playing, PhaseEvent::LastTrickTaken) => Ok(PhaseKind::Complete),
+                    (phase, event) => Err(PhaseError { phase, event }),
+                };
+                assert_eq!(
+                    phase.advance(event).map(|next| next.kind()),
+                    expected,
+                    "{event:?} from {:?}",
+                    phase.kind()
+                );
+            }
+        }
+    }
+
+    /// Plays each seat's first legal card until the round is complete.
+    fn play_out(round: &mut RoundState) {
+        while let RoundPhase::Playing = round.phase() {
+            let seat = crate::testkit::to_play(round);
+            let card = round.legal_plays(seat)[0];
+            round.play_card(seat, card).unwrap();
+        }
+    }
+
+    #[test]
+    fn a_round_records_its_phases_and_refuses_moves_once_complete() {
+        let deck = Deck::shuffled_with_seed(2259);
+        let mut round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Left);
+        assert_eq!(round.phase_history(), [PhaseKind::Passing]);
+        for seat in PlayerPosition::LOOP {
+            let pass = PassCards::from_slice(&round.hand(seat).cards()[..3]).unwrap();
+            round.submit_pass(seat, pass).unwrap();
+        }
+        round.resolve_passes().unwrap();
+        assert_eq!(round.resolve_passes(), Err(PassingError::NotInPassingPhase));
+        play_out(&mut round);
+        assert_eq!(round.tricks_completed(), 13);
+        assert_eq!(
+            round.phase_history(),
+            [PhaseKind::Passing, PhaseKind::Playing, PhaseKind::Complete]
+        );
+        assert!(round.is_decided(&RuleSet::STANDARD));
+
+        let any = card("2C");
+        assert!(matches!(
+            round.play_card(PlayerPosition::North, any),
+            Err(PlayError::NotInPlayPhase)
+        ));
+        let pass = PassCards::from_slice(&cards("2C 3C 4C")).unwrap();
+        assert_eq!(
+            round.submit_pass(PlayerPosition::North, pass),
+            Err(PassingError::NotInPassingPhase)
+        );
+        assert_eq!(round.resolve_passes(), Err(PassingError::NotInPassingPhase));
+
+        let mut held = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
+        play_out(&mut held);
+        assert_eq!(
+            held.phase_history(),
+            [PhaseKind::Playing, PhaseKind::Complete]
+        );
+    }
+
+    #[test]
+    fn a_finished_round_saved_as_playing_restores_complete() {
+        let deck = Deck::shuffled_with_seed(7);
+        let mut round = RoundState::deal(&deck, PlayerPosition::North, PassingDirection::Hold);
+        play_out(&mut round);
+        let restored = RoundState::from_hands_with_state(
+            PlayerPosition::LOOP.map(|seat| round.hand(seat).clone()),
+            round.starting_player(),
+            PassingDirection::Hold,
+            RoundPhase::Playing,
+            round.current_trick().clone(),
+            round.trick_history().to_vec(),
+            round.hearts_broken(),
+        );
+        assert!(matches!(restored.phase(), RoundPhase::Complete));
+        assert_eq!(restored.phase_history(), [PhaseKind::Complete]);
+    }
+}
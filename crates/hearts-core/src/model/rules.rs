@@ -0,0 +1,219 @@
+use crate::model::card::Card;
+use crate::model::passing::{DEFAULT_PASS_COUNT, MAX_PASS_COUNT, MIN_PASS_COUNT};
+use crate::model::rank::Rank;
+use crate::model::suit::Suit;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// House rules a match is played under. The default is the standard game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleSet {
+    /// Cards each seat passes on passing hands.
+    #[serde(default = "default_pass_count")]
+    pub pass_count: usize,
+    /// The match ends once a seat's total reaches this score.
+    #[serde(default = "default_target_score")]
+    pub target_score: u32,
+    /// Omnibus Hearts: the seat that takes J♦ scores
+    /// [`JACK_OF_DIAMONDS_BONUS`] fewer points for the hand.
+    #[serde(default)]
+    pub jack_of_diamonds: bool,
+    /// Whether a moon shooter who also took J♦ keeps its bonus.
+    #[serde(default)]
+    pub moon_jack: MoonJack,
+    /// Shooting the sun: a seat that wins all 13 tricks scores by
+    /// [`Self::sun_score`] instead of as a moon shot.
+    #[serde(default)]
+    pub shoot_the_sun: bool,
+    #[serde(default)]
+    pub sun_score: SunScore,
+}
+
+/// How J♦ combines with a moon shot under the Omnibus rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoonJack {
+    /// The shooter scores the moon and the bonus: 10 below their total.
+    #[default]
+    Kept,
+    /// A moon shot scores 0 for the shooter, jack or not.
+    Forfeited,
+}
+
+/// How a sun is scored when [`RuleSet::shoot_the_sun`] is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SunScore {
+    /// Every other seat scores [`SUN_POINTS`].
+    #[default]
+    FiftyTwoToOthers,
+    /// A double moon turned on the shooter: [`SUN_POINTS`] come off their
+    /// own total and the others score nothing.
+    DoubleMoon,
+}
+
+/// What a sun is worth: twice the moon.
+pub const SUN_POINTS: u8 = 52;
+
+pub const JACK_OF_DIAMONDS: Card = Card::new(Rank::Jack, Suit::Diamonds);
+/// Points the J♦ taker subtracts under the Omnibus rule.
+pub const JACK_OF_DIAMONDS_BONUS: u8 = 10;
+
+/// The standard match target.
+pub const DEFAULT_TARGET_SCORE: u32 = 100;
+/// Targets the game offers as presets: a half match, a three-quarter match
+/// and the standard game.
+pub const TARGET_SCORE_PRESETS: [u32; 3] = [50, 75, DEFAULT_TARGET_SCORE];
+/// Lowest target: a single round (26 points) can end the match.
+pub const MIN_TARGET_SCORE: u32 = 26;
+pub const MAX_TARGET_SCORE: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleError {
+    PassCount(usize),
+    TargetScore(u32),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::PassCount(count) => write!(
+                f,
+                "pass count {count} is not supported (use {MIN_PASS_COUNT}-{MAX_PASS_COUNT})"
+            ),
+            RuleError::TargetScore(target) => write!(
+                f,
+                "target score {target} is not supported (use {MIN_TARGET_SCORE}-{MAX_TARGET_SCORE})"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for RuleError {}
+
+const fn default_pass_count() -> usize {
+    DEFAULT_PASS_COUNT
+}
+
+const fn default_target_score() -> u32 {
+    DEFAULT_TARGET_SCORE
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl RuleSet {
+    pub const STANDARD: RuleSet = RuleSet {
+        pass_count: DEFAULT_PASS_COUNT,
+        target_score: DEFAULT_TARGET_SCORE,
+        jack_of_diamonds: false,
+        moon_jack: MoonJack::Kept,
+        shoot_the_sun: false,
+        sun_score: SunScore::FiftyTwoToOthers,
+    };
+
+    pub fn with_pass_count(self, count: usize) -> Result<Self, RuleError> {
+        if !(MIN_PASS_COUNT..=MAX_PASS_COUNT).contains(&count) {
+            return Err(RuleError::PassCount(count));
+        }
+        let mut rules = self;
+        rules.pass_count = count;
+        Ok(rules)
+    }
+
+    pub fn with_target_score(self, target: u32) -> Result<Self, RuleError> {
+        if !(MIN_TARGET_SCORE..=MAX_TARGET_SCORE).contains(&target) {
+            return Err(RuleError::TargetScore(target));
+        }
+        let mut rules = self;
+        rules.target_score = target;
+        Ok(rules)
+    }
+
+    /// Turns the Omnibus J♦ bonus on or off.
+    pub const fn with_jack_of_diamonds(mut self, enabled: bool) -> Self {
+        self.jack_of_diamonds = enabled;
+        self
+    }
+
+    pub const fn with_moon_jack(mut self, moon_jack: MoonJack) -> Self {
+        self.moon_jack = moon_jack;
+        self
+    }
+
+    /// Turns shooting the sun on, scored by `score`, or off with `None`.
+    pub const fn with_shoot_the_sun(mut self, score: Option<SunScore>) -> Self {
+        match score {
+            Some(score) => {
+                self.shoot_the_sun = true;
+                self.sun_score = score;
+            }
+            None => self.shoot_the_sun = false,
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MoonJack, RuleError, RuleSet, SunScore, TARGET_SCORE_PRESETS};
+
+    #[test]
+    fn pass_count_is_validated() {
+        assert_eq!(RuleSet::default().pass_count, 3);
+        assert_eq!(
+            RuleSet::default().with_pass_count(4).map(|r| r.pass_count),
+            Ok(4)
+        );
+        assert_eq!(
+            RuleSet::default().with_pass_count(5),
+            Err(RuleError::PassCount(5))
+        );
+        let parsed: RuleSet = serde_json::from_str("{}").unwrap();
+        assert_eq!(parsed, RuleSet::STANDARD);
+    }
+
+    #[test]
+    fn target_score_is_validated() {
+        assert_eq!(RuleSet::default().target_score, 100);
+        for target in TARGET_SCORE_PRESETS {
+            let rules = RuleSet::default().with_target_score(target).unwrap();
+            assert_eq!(rules.target_score, target);
+        }
+        assert_eq!(
+            RuleSet::default().with_target_score(10),
+            Err(RuleError::TargetScore(10))
+        );
+        let parsed: RuleSet = serde_json::from_str(r#"{"pass_count":4}"#).unwrap();
+        assert_eq!((parsed.pass_count, parsed.target_score), (4, 100));
+    }
+
+    #[test]
+    fn the_jack_of_diamonds_is_off_unless_asked_for() {
+        assert!(!RuleSet::default().jack_of_diamonds);
+        let parsed: RuleSet =
+            serde_json::from_str(r#"{"jack_of_diamonds":true,"moon_jack":"forfeited"}"#).unwrap();
+        assert_eq!(
+            parsed,
+            RuleSet::STANDARD
+                .with_jack_of_diamonds(true)
+                .with_moon_jack(MoonJack::Forfeited)
+        );
+    }
+
+    #[test]
+    fn the_sun_is_off_unless_asked_for() {
+        assert!(!RuleSet::default().shoot_the_sun);
+        let parsed: RuleSet =
+            serde_json::from_str(r#"{"shoot_the_sun":true,"sun_score":"double_moon"}"#).unwrap();
+        assert_eq!(
+            parsed,
+            RuleSet::STANDARD.with_shoot_the_sun(Some(SunScore::DoubleMoon))
+        );
+        let off = parsed.with_shoot_the_sun(None);
+        assert!(!off.shoot_the_sun);
+    }
+}
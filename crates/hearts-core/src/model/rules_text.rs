@@ -0,0 +1,434 @@
+//! The wording of the rules, for the help screen and for the message shown
+//! when a play is refused, under whichever [`RuleSet`] the match uses.
+//!
+//! Text lives in static tables of templates. A template names the settings
+//! it needs in braces, e.g. `{pass_count}` or `{variant}`, and
+//! [`RulesText`] fills them in from the rule set and the refused play. The
+//! tables are English; a translation replaces the tables, not the code.
+
+use crate::model::round::PlayError;
+use crate::model::rules::{JACK_OF_DIAMONDS_BONUS, MoonJack, RuleSet, SUN_POINTS, SunScore};
+use crate::model::suit::Suit;
+use crate::model::trick::TrickError;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// One rule as the help screen lists it, in play order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    Passing,
+    OpeningLead,
+    FollowSuit,
+    FirstTrickPoints,
+    LeadingHearts,
+    PenaltyCards,
+    Moon,
+    JackOfDiamonds,
+    ShootTheSun,
+    MatchEnd,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 10] = [
+        Rule::Passing,
+        Rule::OpeningLead,
+        Rule::FollowSuit,
+        Rule::FirstTrickPoints,
+        Rule::LeadingHearts,
+        Rule::PenaltyCards,
+        Rule::Moon,
+        Rule::JackOfDiamonds,
+        Rule::ShootTheSun,
+        Rule::MatchEnd,
+    ];
+
+    /// The rule `err` enforces, for refusals that come from a rule of the
+    /// game rather than from playing out of turn or out of phase.
+    pub const fn broken_by(err: &PlayError) -> Option<Rule> {
+        match err {
+            PlayError::MustLeadTwoOfClubs => Some(Rule::OpeningLead),
+            PlayError::MustFollowSuit(_) => Some(Rule::FollowSuit),
+            PlayError::HeartsNotBroken => Some(Rule::LeadingHearts),
+            PlayError::NoPointsOnFirstTrick => Some(Rule::FirstTrickPoints),
+            PlayError::NotInPlayPhase
+            | PlayError::CardNotInHand(_)
+            | PlayError::OutOfTurn { .. }
+            | PlayError::Trick(_) => None,
+        }
+    }
+}
+
+/// Which rule sets an entry of [`RULES`] is written for.
+#[derive(Debug, Clone, Copy)]
+enum Applies {
+    Always,
+    Omnibus(MoonJack),
+    Sun(SunScore),
+}
+
+impl Applies {
+    fn to(self, rules: &RuleSet) -> bool {
+        match self {
+            Applies::Always => true,
+            Applies::Omnibus(moon_jack) => rules.jack_of_diamonds && rules.moon_jack == moon_jack,
+            Applies::Sun(score) => rules.shoot_the_sun && rules.sun_score == score,
+        }
+    }
+}
+
+/// The long form of each rule. A rule with no entry for the rule set is not
+/// played under it.
+const RULES: [(Rule, Applies, &str); 12] = [
+    (
+        Rule::Passing,
+        Applies::Always,
+        "Before each hand every seat passes {pass_count} cards: to the left, then the right, then across, then a hand with no pass.",
+    ),
+    (
+        Rule::OpeningLead,
+        Applies::Always,
+        "Whoever holds the two of clubs leads it to the first trick.",
+    ),
+    (
+        Rule::FollowSuit,
+        Applies::Always,
+        "Follow the suit led when you can; otherwise play any card. The highest card of the suit led wins the trick and leads the next.",
+    ),
+    (
+        Rule::FirstTrickPoints,
+        Applies::Always,
+        "No heart or queen of spades may be played to the first trick unless your hand holds nothing else.",
+    ),
+    (
+        Rule::LeadingHearts,
+        Applies::Always,
+        "Hearts cannot be led until a heart or the queen of spades has been played, unless you hold only hearts.",
+    ),
+    (
+        Rule::PenaltyCards,
+        Applies::Always,
+        "Each heart you take scores 1 point and the queen of spades scores 13.",
+    ),
+    (
+        Rule::Moon,
+        Applies::Always,
+        "Taking every heart and the queen of spades shoots the moon: you score 0 and every other seat scores 26.",
+    ),
+    (
+        Rule::JackOfDiamonds,
+        Applies::Omnibus(MoonJack::Kept),
+        "Omnibus: whoever takes the jack of diamonds scores {jack_bonus} fewer points that hand, moon shot or not. A total never drops below 0.",
+    ),
+    (
+        Rule::JackOfDiamonds,
+        Applies::Omnibus(MoonJack::Forfeited),
+        "Omnibus: whoever takes the jack of diamonds scores {jack_bonus} fewer points that hand, except a moon shooter, who scores 0 either way. A total never drops below 0.",
+    ),
+    (
+        Rule::ShootTheSun,
+        Applies::Sun(SunScore::FiftyTwoToOthers),
+        "Winning all 13 tricks shoots the sun: you score 0 and every other seat scores {sun_points}.",
+    ),
+    (
+        Rule::ShootTheSun,
+        Applies::Sun(SunScore::DoubleMoon),
+        "Winning all 13 tricks shoots the sun: {sun_points} points come off your own total and the other seats score nothing.",
+    ),
+    (
+        Rule::MatchEnd,
+        Applies::Always,
+        "The match ends once a total reaches {target_score}; the lowest total wins.",
+    ),
+];
+
+/// The short form of a refusal, as the template for `err`.
+const fn refusal_template(err: &PlayError) -> &'static str {
+    match err {
+        PlayError::NotInPlayPhase => "No card can be played right now.",
+        PlayError::CardNotInHand(_) => "{card} is not in your hand.",
+        PlayError::OutOfTurn { .. } => "It is {seat}'s turn to play.",
+        PlayError::MustLeadTwoOfClubs => {
+            "The first trick must be led with the two of clubs (variant: {variant})."
+        }
+        PlayError::MustFollowSuit(_) => "You must follow suit with a {suit} (variant: {variant}).",
+        PlayError::HeartsNotBroken => "Hearts cannot be led until broken (variant: {variant}).",
+        PlayError::NoPointsOnFirstTrick => {
+            "Points cannot be played to the first trick (variant: {variant})."
+        }
+        PlayError::Trick(TrickError::TrickComplete) => "That trick is already complete.",
+        PlayError::Trick(TrickError::OutOfTurn { .. }) => "It is {seat}'s turn to play.",
+        PlayError::Trick(TrickError::AlreadyPlayed(_)) => {
+            "{seat} has already played to this trick."
+        }
+    }
+}
+
+const fn suit_name(suit: Suit) -> &'static str {
+    match suit {
+        Suit::Clubs => "club",
+        Suit::Diamonds => "diamond",
+        Suit::Spades => "spade",
+        Suit::Hearts => "heart",
+    }
+}
+
+/// The rules and refusals worded for one rule set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RulesText {
+    rules: RuleSet,
+}
+
+impl RulesText {
+    pub const fn new(rules: RuleSet) -> Self {
+        Self { rules }
+    }
+
+    /// The house rules in play, e.g. `standard` or `Omnibus, shoot the sun`.
+    pub fn variant(&self) -> String {
+        let mut parts = Vec::new();
+        if self.rules.jack_of_diamonds {
+            parts.push("Omnibus");
+        }
+        if self.rules.shoot_the_sun {
+            parts.push("shoot the sun");
+        }
+        if parts.is_empty() {
+            "standard".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// The long form of `rule`, or `None` when the rule set does not play it.
+    pub fn rule(&self, rule: Rule) -> Option<String> {
+        RULES
+            .iter()
+            .find(|(listed, applies, _)| *listed == rule && applies.to(&self.rules))
+            .map(|(_, _, template)| self.fill(template, &[]))
+    }
+
+    /// Every rule the rule set plays, in [`Rule::ALL`] order.
+    pub fn help(&self) -> Vec<String> {
+        Rule::ALL
+            .into_iter()
+            .filter_map(|rule| self.rule(rule))
+            .collect()
+    }
+
+    /// Why the play was refused, in one sentence.
+    pub fn refusal(&self, err: &PlayError) -> String {
+        let card;
+        let seat;
+        let mut values: Vec<(&str, &str)> = Vec::new();
+        match err {
+            PlayError::CardNotInHand(played) => {
+                card = played.to_string();
+                values.push(("card", &card));
+            }
+            PlayError::OutOfTurn { expected, .. }
+            | PlayError::Trick(TrickError::OutOfTurn { expected, .. })
+            | PlayError::Trick(TrickError::AlreadyPlayed(expected)) => {
+                seat = expected.to_string();
+                values.push(("seat", &seat));
+            }
+            PlayError::MustFollowSuit(suit) => values.push(("suit", suit_name(*suit))),
+            _ => {}
+        }
+        self.fill(refusal_template(err), &values)
+    }
+
+    /// Replaces each `{name}` in `template` with the rule set's setting of
+    /// that name or, first, with its entry in `values`.
+    fn fill(&self, template: &str, values: &[(&str, &str)]) -> String {
+        let mut text = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            text.push_str(&rest[..open]);
+            let Some(close) = rest[open..].find('}') else {
+                break;
+            };
+            let name = &rest[open + 1..open + close];
+            match values.iter().find(|(key, _)| *key == name) {
+                Some((_, value)) => text.push_str(value),
+                None => text.push_str(&self.setting(name)),
+            }
+            rest = &rest[open + close + 1..];
+        }
+        text.push_str(rest);
+        text
+    }
+
+    fn setting(&self, name: &str) -> String {
+        match name {
+            "pass_count" => self.rules.pass_count.to_string(),
+            "target_score" => self.rules.target_score.to_string(),
+            "jack_bonus" => JACK_OF_DIAMONDS_BONUS.to_string(),
+            "sun_points" => SUN_POINTS.to_string(),
+            "variant" => self.variant(),
+            _ => format!("{{{name}}}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RULES, Rule, RulesText};
+    use crate::model::card::Card;
+    use crate::model::passing::{MAX_PASS_COUNT, MIN_PASS_COUNT};
+    use crate::model::player::PlayerPosition;
+    use crate::model::rank::Rank;
+    use crate::model::round::PlayError;
+    use crate::model::rules::{MoonJack, RuleSet, SunScore, TARGET_SCORE_PRESETS};
+    use crate::model::suit::Suit;
+    use crate::model::trick::TrickError;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn every_rule_set() -> Vec<RuleSet> {
+        let mut sets = Vec::new();
+        for pass_count in MIN_PASS_COUNT..=MAX_PASS_COUNT {
+            for target in TARGET_SCORE_PRESETS {
+                for jack in [false, true] {
+                    for moon_jack in [MoonJack::Kept, MoonJack::Forfeited] {
+                        for sun in [
+                            None,
+                            Some(SunScore::FiftyTwoToOthers),
+                            Some(SunScore::DoubleMoon),
+                        ] {
+                            sets.push(
+                                RuleSet::STANDARD
+                                    .with_pass_count(pass_count)
+                                    .and_then(|rules| rules.with_target_score(target))
+                                    .unwrap()
+                                    .with_jack_of_diamonds(jack)
+                                    .with_moon_jack(moon_jack)
+                                    .with_shoot_the_sun(sun),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        sets
+    }
+
+    fn every_refusal() -> Vec<PlayError> {
+        let errors = vec![
+            PlayError::NotInPlayPhase,
+            PlayError::CardNotInHand(Card::new(Rank::Queen, Suit::Spades)),
+            PlayError::OutOfTurn {
+                expected: PlayerPosition::West,
+                actual: PlayerPosition::South,
+            },
+            PlayError::MustLeadTwoOfClubs,
+            PlayError::MustFollowSuit(Suit::Diamonds),
+            PlayError::HeartsNotBroken,
+            PlayError::NoPointsOnFirstTrick,
+            PlayError::Trick(TrickError::TrickComplete),
+            PlayError::Trick(TrickError::OutOfTurn {
+                expected: PlayerPosition::West,
+                actual: PlayerPosition::South,
+            }),
+            PlayError::Trick(TrickError::AlreadyPlayed(PlayerPosition::East)),
+        ];
+        // A new variant fails to compile here until it is listed above.
+        for err in &errors {
+            match err {
+                PlayError::NotInPlayPhase
+                | PlayError::CardNotInHand(_)
+                | PlayError::OutOfTurn { .. }
+                | PlayError::MustLeadTwoOfClubs
+                | PlayError::MustFollowSuit(_)
+                | PlayError::HeartsNotBroken
+                | PlayError::NoPointsOnFirstTrick
+                | PlayError::Trick(
+                    TrickError::TrickComplete
+                    | TrickError::OutOfTurn { .. }
+                    | TrickError::AlreadyPlayed(_),
+                ) => {}
+            }
+        }
+        errors
+    }
+
+    #[test]
+    fn every_rule_set_words_its_rules_without_placeholders() {
+        for rules in every_rule_set() {
+            let text = RulesText::new(rules);
+            for rule in Rule::ALL {
+                let plays = match rule {
+                    Rule::JackOfDiamonds => rules.jack_of_diamonds,
+                    Rule::ShootTheSun => rules.shoot_the_sun,
+                    _ => true,
+                };
+                let worded = text.rule(rule);
+                assert_eq!(worded.is_some(), plays, "{rule:?} under {rules:?}");
+                if let Some(worded) = worded {
+                    assert!(!worded.contains('{'), "{worded}");
+                }
+            }
+            let help = text.help();
+            assert!(
+                help.iter()
+                    .any(|line| line.contains(&rules.pass_count.to_string()))
+            );
+            assert!(
+                help.iter()
+                    .any(|line| line.contains(&rules.target_score.to_string()))
+            );
+        }
+        // Every entry in the table is reachable from some rule set.
+        assert!(
+            RULES
+                .iter()
+                .all(|(_, applies, _)| { every_rule_set().iter().any(|rules| applies.to(rules)) })
+        );
+    }
+
+    #[test]
+    fn every_refusal_has_a_short_form_under_every_rule_set() {
+        for rules in every_rule_set() {
+            let text = RulesText::new(rules);
+            for err in every_refusal() {
+                let refusal = text.refusal(&err);
+                assert!(!refusal.is_empty() && !refusal.contains('{'), "{refusal}");
+                if let Some(rule) = Rule::broken_by(&err) {
+                    assert!(text.rule(rule).is_some(), "{err:?}");
+                    assert!(refusal.contains(&text.variant()), "{refusal}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn refusals_name_the_variant_and_the_play() {
+        let standard = RulesText::new(RuleSet::STANDARD);
+        assert_eq!(
+            standard.refusal(&PlayError::HeartsNotBroken),
+            "Hearts cannot be led until broken (variant: standard)."
+        );
+        assert_eq!(
+            standard.refusal(&PlayError::MustFollowSuit(Suit::Spades)),
+            "You must follow suit with a spade (variant: standard)."
+        );
+        assert_eq!(
+            standard.refusal(&PlayError::CardNotInHand(Card::new(
+                Rank::Ten,
+                Suit::Hearts
+            ))),
+            "10H is not in your hand."
+        );
+        let house = RulesText::new(
+            RuleSet::STANDARD
+                .with_jack_of_diamonds(true)
+                .with_shoot_the_sun(Some(SunScore::DoubleMoon)),
+        );
+        assert_eq!(house.variant(), "Omnibus, shoot the sun");
+        assert_eq!(
+            house.refusal(&PlayError::NoPointsOnFirstTrick),
+            "Points cannot be played to the first trick (variant: Omnibus, shoot the sun)."
+        );
+        assert!(house.rule(Rule::ShootTheSun).unwrap().contains("52 points"));
+    }
+}
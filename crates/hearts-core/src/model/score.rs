@@ -1,13 +1,59 @@
 ﻿use crate::model::player::PlayerPosition;
+use crate::model::rules::{JACK_OF_DIAMONDS_BONUS, MoonJack, RuleSet, SUN_POINTS, SunScore};
+
+/// Each seat's points for a hand with raw `penalties`: a moon shot gives 26
+/// to everyone else instead, a sun (`sun_shooter` won every trick) is scored
+/// by [`RuleSet::sun_score`] when the rules play it, and under the Omnibus
+/// rule the seat that took J♦ (`jack_taker`) scores
+/// [`JACK_OF_DIAMONDS_BONUS`] fewer.
+pub fn hand_points(
+    penalties: [u8; 4],
+    jack_taker: Option<PlayerPosition>,
+    sun_shooter: Option<PlayerPosition>,
+    rules: &RuleSet,
+) -> [i32; 4] {
+    let mut points = penalties.map(i32::from);
+    let shooter = PlayerPosition::LOOP
+        .iter()
+        .copied()
+        .find(|seat| penalties[seat.index()] == 26);
+    if let Some(shooter) = shooter {
+        points = [26; 4];
+        points[shooter.index()] = 0;
+    }
+    if rules.shoot_the_sun
+        && let Some(sun) = sun_shooter
+    {
+        let sun_points = i32::from(SUN_POINTS);
+        let (others, own) = match rules.sun_score {
+            SunScore::FiftyTwoToOthers => (sun_points, 0),
+            SunScore::DoubleMoon => (0, -sun_points),
+        };
+        points = [others; 4];
+        points[sun.index()] = own;
+    }
+    if rules.jack_of_diamonds
+        && let Some(taker) = jack_taker
+        && !(shooter == Some(taker) && rules.moon_jack == MoonJack::Forfeited)
+    {
+        points[taker.index()] -= i32::from(JACK_OF_DIAMONDS_BONUS);
+    }
+    points
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ScoreBoard {
     totals: [u32; 4],
+    /// What each seat scored in the last hand applied, if one has been.
+    last_round: Option<[i32; 4]>,
 }
 
 impl ScoreBoard {
     pub const fn new() -> Self {
-        Self { totals: [0; 4] }
+        Self {
+            totals: [0; 4],
+            last_round: None,
+        }
     }
 
     pub fn add_penalty(&mut self, seat: PlayerPosition, points: u32) {
@@ -19,8 +65,17 @@ impl ScoreBoard {
         self.totals[seat.index()] = points;
     }
 
+    /// Replaces the totals and forgets the last round, which no longer
+    /// explains them.
     pub fn set_totals(&mut self, totals: [u32; 4]) {
         self.totals = totals;
+        self.last_round = None;
+    }
+
+    /// Puts back the last round a saved board had; call it after
+    /// [`ScoreBoard::set_totals`].
+    pub fn set_last_round(&mut self, last_round: Option<[i32; 4]>) {
+        self.last_round = last_round;
     }
 
     pub fn score(&self, seat: PlayerPosition) -> u32 {
@@ -32,13 +87,24 @@ impl ScoreBoard {
         let idx = seat.index();
         let base = totals[idx] as i32;
         totals[idx] = base.saturating_add(delta).max(0) as u32;
-        Self { totals }
+        Self {
+            totals,
+            last_round: self.last_round,
+        }
     }
 
     pub fn standings(&self) -> &[u32; 4] {
         &self.totals
     }
 
+    /// How much each seat's total moved in the last hand applied, after any
+    /// moon shot and J♦ bonus. A bonus that would have taken a total below
+    /// zero counts only as far as zero. `None` before the first hand and
+    /// after [`ScoreBoard::set_totals`].
+    pub fn last_round(&self) -> Option<[i32; 4]> {
+        self.last_round
+    }
+
     pub fn leading_player(&self) -> PlayerPosition {
         PlayerPosition::LOOP
             .iter()
@@ -47,25 +113,23 @@ impl ScoreBoard {
             .unwrap_or(PlayerPosition::North)
     }
 
+    /// Scores a hand of the standard game.
     pub fn apply_hand(&mut self, penalties: [u8; 4]) {
-        let total: u32 = penalties.iter().map(|&p| p as u32).sum();
-        if total == 26
-            && let Some(shooter) = PlayerPosition::LOOP
-                .iter()
-                .copied()
-                .find(|seat| penalties[seat.index()] == 26)
-        {
-            for seat in PlayerPosition::LOOP.iter().copied() {
-                if seat != shooter {
-                    self.add_penalty(seat, 26);
-                }
-            }
-            return;
-        }
+        self.apply_points(hand_points(penalties, None, None, &RuleSet::STANDARD));
+    }
 
+    /// Adds a hand's [`hand_points`]. A bonus can lower a total but never
+    /// below zero.
+    pub fn apply_points(&mut self, points: [i32; 4]) {
+        let mut applied = [0i32; 4];
         for seat in PlayerPosition::LOOP.iter().copied() {
-            self.add_penalty(seat, penalties[seat.index()] as u32);
+            let idx = seat.index();
+            let before = self.totals[idx];
+            let total = i64::from(before) + i64::from(points[idx]);
+            self.totals[idx] = total.clamp(0, i64::from(u32::MAX)) as u32;
+            applied[idx] = (i64::from(self.totals[idx]) - i64::from(before)) as i32;
         }
+        self.last_round = Some(applied);
     }
 }
 
@@ -121,6 +185,85 @@ mod tests {
         assert_eq!(board.score(PlayerPosition::West), 26);
     }
 
+    #[test]
+    fn apply_hand_records_the_round_as_scored() {
+        let mut board = ScoreBoard::new();
+        assert_eq!(board.last_round(), None);
+        board.apply_hand([1, 5, 0, 20]);
+        assert_eq!(board.last_round(), Some([1, 5, 0, 20]));
+        board.apply_hand([0, 26, 0, 0]);
+        assert_eq!(board.last_round(), Some([26, 0, 26, 26]));
+        assert_eq!(board.score(PlayerPosition::East), 5);
+        board.set_totals([10, 20, 30, 40]);
+        assert_eq!(board.last_round(), None);
+    }
+
+    #[test]
+    fn the_jack_of_diamonds_takes_ten_off_under_omnibus() {
+        use super::hand_points;
+        use crate::model::rules::{MoonJack, RuleSet};
+        let omnibus = RuleSet::STANDARD.with_jack_of_diamonds(true);
+        let east = Some(PlayerPosition::East);
+        let penalties = [1, 5, 0, 20];
+        assert_eq!(
+            hand_points(penalties, east, None, &RuleSet::STANDARD),
+            [1, 5, 0, 20]
+        );
+        assert_eq!(hand_points(penalties, east, None, &omnibus), [1, -5, 0, 20]);
+        // The shooter keeps the bonus unless the rules say otherwise.
+        let moon = [0, 26, 0, 0];
+        assert_eq!(hand_points(moon, east, None, &omnibus), [26, -10, 26, 26]);
+        let forfeited = omnibus.with_moon_jack(MoonJack::Forfeited);
+        assert_eq!(hand_points(moon, east, None, &forfeited), [26, 0, 26, 26]);
+        // Someone else holding the jack still gets it.
+        let north = Some(PlayerPosition::North);
+        assert_eq!(hand_points(moon, north, None, &forfeited), [16, 0, 26, 26]);
+
+        let mut board = ScoreBoard::new();
+        board.set_totals([4, 0, 0, 0]);
+        board.apply_points(hand_points(penalties, north, None, &omnibus));
+        assert_eq!(board.standings(), &[0, 5, 0, 20]);
+        assert_eq!(board.last_round(), Some([-4, 5, 0, 20]));
+    }
+
+    #[test]
+    fn a_sun_scores_by_the_rules_that_play_it() {
+        use super::hand_points;
+        use crate::model::rules::{MoonJack, RuleSet, SunScore};
+        let sun = [0, 0, 26, 0];
+        let south = Some(PlayerPosition::South);
+        // Without the rule a sun is just a moon.
+        assert_eq!(
+            hand_points(sun, None, south, &RuleSet::STANDARD),
+            [26, 26, 0, 26]
+        );
+        let others = RuleSet::STANDARD.with_shoot_the_sun(Some(SunScore::FiftyTwoToOthers));
+        assert_eq!(hand_points(sun, None, south, &others), [52, 52, 0, 52]);
+        let double = RuleSet::STANDARD.with_shoot_the_sun(Some(SunScore::DoubleMoon));
+        assert_eq!(hand_points(sun, None, south, &double), [0, 0, -52, 0]);
+        // A moon without every trick is still a moon.
+        assert_eq!(hand_points(sun, None, None, &others), [26, 26, 0, 26]);
+
+        // The sun shooter took J♦ too, and keeps or forfeits it like a moon.
+        let omnibus = others.with_jack_of_diamonds(true);
+        assert_eq!(hand_points(sun, south, south, &omnibus), [52, 52, -10, 52]);
+        let forfeited = omnibus.with_moon_jack(MoonJack::Forfeited);
+        assert_eq!(hand_points(sun, south, south, &forfeited), [52, 52, 0, 52]);
+    }
+
+    #[test]
+    fn last_round_stops_at_zero_like_the_totals() {
+        let mut board = ScoreBoard::new();
+        board.set_totals([3, 12, 0, 30]);
+        board.apply_points([-10, -10, -10, 6]);
+        assert_eq!(board.standings(), &[0, 2, 0, 36]);
+        assert_eq!(board.last_round(), Some([-3, -10, 0, 6]));
+        let shown: [i64; 4] = core::array::from_fn(|i| {
+            i64::from([3, 12, 0, 30][i]) + i64::from(board.last_round().unwrap()[i])
+        });
+        assert_eq!(shown, [0, 2, 0, 36]);
+    }
+
     #[test]
     fn set_totals_overwrites_scores() {
         let mut board = ScoreBoard::new();
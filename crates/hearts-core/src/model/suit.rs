@@ -22,6 +22,16 @@ impl Suit {
         }
     }
 
+    /// Suits in index order: clubs, diamonds, spades, hearts.
+    pub fn iter() -> core::array::IntoIter<Suit, 4> {
+        Self::ALL.into_iter()
+    }
+
+    /// Position of the suit in [`Suit::ALL`], for suit-indexed arrays.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
     pub const fn is_heart(self) -> bool {
         matches!(self, Suit::Hearts)
     }
@@ -59,6 +69,15 @@ mod tests {
         assert_eq!(Suit::from_index(4), None);
     }
 
+    #[test]
+    fn iter_follows_index_order() {
+        for (idx, suit) in Suit::iter().enumerate() {
+            assert_eq!(suit.index(), idx);
+            assert_eq!(Suit::from_index(idx), Some(suit));
+        }
+        assert_eq!(Suit::iter().count(), 4);
+    }
+
     #[test]
     fn properties_are_correct() {
         assert!(Suit::Hearts.is_heart());
@@ -1,6 +1,7 @@
 use crate::model::card::Card;
 use crate::model::player::PlayerPosition;
-use std::fmt;
+use alloc::vec::Vec;
+use core::fmt;
 
 #[derive(Debug, Clone)]
 pub struct Trick {
@@ -38,7 +39,7 @@ impl fmt::Display for TrickError {
     }
 }
 
-impl std::error::Error for TrickError {}
+impl core::error::Error for TrickError {}
 
 impl Trick {
     pub fn new(leader: PlayerPosition) -> Self {
@@ -0,0 +1,344 @@
+//! Builders for scenario tests, behind the `testkit` feature.
+//!
+//! This is the supported way for downstream crates to set up a position:
+//! enable the feature in `[dev-dependencies]` and describe the hands and the
+//! tricks so far as card strings instead of nested `Card::new` calls.
+//!
+//! ```toml
+//! [dev-dependencies]
+//! hearts-core = { path = "../hearts-core", features = ["testkit"] }
+//! ```
+//!
+//! Cards use the snapshot notation (`AH`, `10C`, `qs`), separated by spaces;
+//! `|` and `,` are ignored, so a play string can mark trick boundaries.
+//!
+//! ```
+//! use hearts_core::model::player::PlayerPosition::{East, North, South, West};
+//! use hearts_core::testkit::{RoundBuilder, ScoreBuilder, card, fast_forward};
+//!
+//! // North led the K♣ and East covered with the A♣; South is to play.
+//! let round = RoundBuilder::new()
+//!     .seat_hand(North, "10D")
+//!     .seat_hand(East, "JD")
+//!     .seat_hand(South, "AS 3S 9D 4D")
+//!     .seat_hand(West, "2C QS")
+//!     .trick(North, "3C 4C 5C 6C")
+//!     .current(North, "KC AC")
+//!     .build();
+//! assert_eq!(round.current_trick().plays().len(), 2);
+//! assert!(round.legal_plays(South).contains(&card("AS")));
+//!
+//! let scores = ScoreBuilder::new().seat(West, 95).build();
+//! assert_eq!(scores.score(West), 95);
+//!
+//! // Fast-forward a fresh round through its opening trick.
+//! let mut round = RoundBuilder::new()
+//!     .seat_hand(North, "2C AH")
+//!     .seat_hand(East, "3C KH")
+//!     .seat_hand(South, "4C QH")
+//!     .seat_hand(West, "5C JH")
+//!     .build();
+//! fast_forward(&mut round, "2C 3C 4C 5C");
+//! assert_eq!(round.current_trick().leader(), West);
+//! ```
+
+use crate::model::card::Card;
+use crate::model::hand::Hand;
+use crate::model::passing::PassingDirection;
+use crate::model::player::PlayerPosition;
+use crate::model::round::{PlayOutcome, RoundPhase, RoundState};
+use crate::model::score::ScoreBoard;
+use crate::model::trick::Trick;
+use alloc::vec::Vec;
+
+/// Parses one card; panics on anything that is not a card code.
+pub fn card(code: &str) -> Card {
+    code.trim()
+        .parse()
+        .unwrap_or_else(|err| panic!("testkit: '{code}' is not a card: {err}"))
+}
+
+/// Parses a list of cards separated by spaces, `,` or `|`.
+pub fn cards(codes: &str) -> Vec<Card> {
+    codes
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '|')
+        .filter(|code| !code.is_empty())
+        .map(card)
+        .collect()
+}
+
+pub fn hand(codes: &str) -> Hand {
+    Hand::with_cards(cards(codes))
+}
+
+/// Builds a [`RoundState`] from card strings. Defaults: empty hands, no pass,
+/// the playing phase, and hearts broken only when the completed tricks hold a
+/// penalty card.
+#[derive(Debug, Clone)]
+pub struct RoundBuilder {
+    hands: [Vec<Card>; 4],
+    passing: PassingDirection,
+    phase: RoundPhase,
+    leader: Option<PlayerPosition>,
+    history: Vec<Trick>,
+    current: Vec<Card>,
+    hearts_broken: Option<bool>,
+}
+
+impl Default for RoundBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoundBuilder {
+    pub fn new() -> Self {
+        Self {
+            hands: [Vec::new(), Vec::new(), Vec::new(), Vec::new()],
+            passing: PassingDirection::Hold,
+            phase: RoundPhase::Playing,
+            leader: None,
+            history: Vec::new(),
+            current: Vec::new(),
+            hearts_broken: None,
+        }
+    }
+
+    /// The cards `seat` still holds.
+    pub fn seat_hand(mut self, seat: PlayerPosition, codes: &str) -> Self {
+        self.hands[seat.index()] = cards(codes);
+        self
+    }
+
+    pub fn passing(mut self, direction: PassingDirection) -> Self {
+        self.passing = direction;
+        self
+    }
+
+    pub fn phase(mut self, phase: RoundPhase) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    /// Who leads the current trick; defaults to the winner of the last
+    /// completed trick, or North.
+    pub fn leader(mut self, seat: PlayerPosition) -> Self {
+        self.leader = Some(seat);
+        self
+    }
+
+    /// Overrides the hearts-broken flag inferred from the completed tricks.
+    pub fn hearts_broken(mut self, broken: bool) -> Self {
+        self.hearts_broken = Some(broken);
+        self
+    }
+
+    /// Appends a completed trick led by `leader`, cards in play order.
+    pub fn trick(mut self, leader: PlayerPosition, codes: &str) -> Self {
+        let played = cards(codes);
+        assert_eq!(
+            played.len(),
+            4,
+            "testkit: a trick needs four cards: '{codes}'"
+        );
+        self.history.push(trick_from(leader, &played));
+        self
+    }
+
+    /// Cards already played to the current trick by `leader` and the seats
+    /// after it.
+    pub fn current(mut self, leader: PlayerPosition, codes: &str) -> Self {
+        let played = cards(codes);
+        assert!(
+            played.len() < 4,
+            "testkit: the current trick is incomplete: '{codes}'"
+        );
+        self.leader = Some(leader);
+        self.current = played;
+        self
+    }
+
+    pub fn build(self) -> RoundState {
+        let leader = self.leader.unwrap_or_else(|| {
+            self.history
+                .last()
+                .and_then(Trick::winner)
+                .unwrap_or(PlayerPosition::North)
+        });
+        let starting = self.history.first().map_or(leader, Trick::leader);
+        let current = trick_from(leader, &self.current);
+        let hearts_broken = self.hearts_broken.unwrap_or_else(|| {
+            self.history.iter().any(|trick| trick.penalty_total() > 0)
+                || self.current.iter().any(|card| card.is_penalty())
+        });
+
+        let mut seen: Vec<Card> = Vec::with_capacity(52);
+        let played = self
+            .history
+            .iter()
+            .flat_map(|trick| trick.plays().iter().map(|play| play.card));
+        for card in self
+            .hands
+            .iter()
+            .flatten()
+            .copied()
+            .chain(played)
+            .chain(self.current)
+        {
+            assert!(!seen.contains(&card), "testkit: {card} appears twice");
+            seen.push(card);
+        }
+
+        let [north, east, south, west] = self.hands;
+        RoundState::from_hands_with_state(
+            [
+                Hand::with_cards(north),
+                Hand::with_cards(east),
+                Hand::with_cards(south),
+                Hand::with_cards(west),
+            ],
+            starting,
+            self.passing,
+            self.phase,
+            current,
+            self.history,
+            hearts_broken,
+        )
+    }
+}
+
+fn trick_from(leader: PlayerPosition, played: &[Card]) -> Trick {
+    let mut trick = Trick::new(leader);
+    let mut seat = leader;
+    for &card in played {
+        trick
+            .play(seat, card)
+            .unwrap_or_else(|err| panic!("testkit: {card} by {seat}: {err}"));
+        seat = seat.next();
+    }
+    trick
+}
+
+/// Builds a [`ScoreBoard`]; seats not set stay at zero.
+#[derive(Debug, Clone, Default)]
+pub struct ScoreBuilder {
+    totals: [u32; 4],
+}
+
+impl ScoreBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn seat(mut self, seat: PlayerPosition, points: u32) -> Self {
+        self.totals[seat.index()] = points;
+        self
+    }
+
+    /// All four totals in N, E, S, W order.
+    pub fn totals(mut self, totals: [u32; 4]) -> Self {
+        self.totals = totals;
+        self
+    }
+
+    pub fn build(self) -> ScoreBoard {
+        let mut scores = ScoreBoard::new();
+        scores.set_totals(self.totals);
+        scores
+    }
+}
+
+/// Plays `codes` in order, each card by the seat whose turn it is, through
+/// the normal rules. Panics on an illegal play; returns the outcomes.
+pub fn fast_forward(round: &mut RoundState, codes: &str) -> Vec<PlayOutcome> {
+    cards(codes)
+        .into_iter()
+        .map(|card| {
+            let seat = to_play(round);
+            round
+                .play_card(seat, card)
+                .unwrap_or_else(|err| panic!("testkit: {seat} cannot play {card}: {err:?}"))
+        })
+        .collect()
+}
+
+/// The seat whose turn it is in the current trick.
+pub fn to_play(round: &RoundState) -> PlayerPosition {
+    let trick = round.current_trick();
+    trick
+        .plays()
+        .last()
+        .map_or(trick.leader(), |play| play.position.next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RoundBuilder, ScoreBuilder, card, cards, fast_forward, to_play};
+    use crate::model::passing::{PassingDirection, PassingState};
+    use crate::model::player::PlayerPosition::{East, North, South, West};
+    use crate::model::round::{PlayOutcome, RoundPhase};
+
+    #[test]
+    fn card_strings_parse_in_snapshot_notation() {
+        assert_eq!(
+            cards("AH 10c | qs,2D"),
+            [card("AH"), card("10C"), card("QS"), card("2D")]
+        );
+    }
+
+    #[test]
+    fn builder_infers_leader_and_broken_hearts_from_history() {
+        let round = RoundBuilder::new()
+            .seat_hand(North, "2D")
+            .seat_hand(East, "3D")
+            .seat_hand(South, "4D")
+            .seat_hand(West, "5D")
+            .trick(North, "2C 3C AC QH")
+            .build();
+        assert_eq!(round.starting_player(), North);
+        assert_eq!(round.current_trick().leader(), South);
+        assert!(round.hearts_broken());
+        assert_eq!(round.penalty_totals(), [0, 0, 1, 0]);
+
+        let passing = RoundBuilder::new()
+            .passing(PassingDirection::Left)
+            .phase(RoundPhase::Passing(PassingState::new(
+                PassingDirection::Left,
+            )))
+            .build();
+        assert!(matches!(passing.phase(), RoundPhase::Passing(_)));
+        assert_eq!(
+            ScoreBuilder::new().seat(East, 7).build().standings(),
+            &[0, 7, 0, 0]
+        );
+    }
+
+    #[test]
+    fn fast_forward_plays_through_tricks() {
+        let mut round = RoundBuilder::new()
+            .seat_hand(North, "2C 9D")
+            .seat_hand(East, "3C KD")
+            .seat_hand(South, "4C 3D")
+            .seat_hand(West, "5C 4D")
+            .build();
+        let outcomes = fast_forward(&mut round, "2C 3C 4C 5C | 4D 9D KD 3D");
+        assert_eq!(
+            outcomes[3],
+            PlayOutcome::TrickCompleted {
+                winner: West,
+                penalties: 0
+            }
+        );
+        assert_eq!(round.tricks_completed(), 2);
+        assert_eq!(to_play(&round), East);
+    }
+
+    #[test]
+    #[should_panic(expected = "appears twice")]
+    fn duplicate_cards_are_rejected() {
+        RoundBuilder::new()
+            .seat_hand(North, "AS")
+            .seat_hand(East, "AS")
+            .build();
+    }
+}